@@ -0,0 +1,15 @@
+//! Generates the gRPC service from `proto/` when the `grpc` feature is enabled.
+
+fn main() {
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto");
+        // A bundled protoc, so building does not depend on one being installed
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("bundled protoc");
+        // SAFETY: the build script is single-threaded
+        unsafe { std::env::set_var("PROTOC", protoc) };
+        tonic_prost_build::configure()
+            .compile_protos(&["proto/items.proto"], &["proto"])
+            .expect("compile proto/items.proto");
+    }
+}
@@ -0,0 +1,14 @@
+-- Managed API keys with per-key scopes.
+-- Only the SHA-256 hash of the secret is stored; the plaintext is shown once at creation.
+CREATE TABLE IF NOT EXISTS api_keys (
+    id VARCHAR(255) PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    key_hash CHAR(64) NOT NULL UNIQUE,
+    scopes TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_created_at ON api_keys (created_at DESC);
+
+COMMENT ON COLUMN api_keys.scopes IS 'Scopes: items:read, items:write, admin';
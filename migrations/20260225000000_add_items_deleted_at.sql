@@ -0,0 +1,8 @@
+-- Soft delete for items: DELETE /items/{id} sets deleted_at instead of removing the row.
+-- The purge worker hard-deletes rows whose deleted_at is older than the retention window
+-- (outbox entries follow via ON DELETE CASCADE).
+ALTER TABLE items ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+-- Purge scans only soft-deleted rows
+CREATE INDEX IF NOT EXISTS idx_items_deleted_at ON items (deleted_at)
+    WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,11 @@
+-- Indexes for GET /items filters (blockchain_status, tag, author).
+-- Sorting by updated_at/name falls back to a sort over the filtered rows.
+
+-- Status filter with the default created_at DESC ordering
+CREATE INDEX IF NOT EXISTS idx_items_status_created_at ON items (blockchain_status, created_at DESC, id DESC);
+
+-- Tag containment: metadata -> 'tags' ? $tag
+CREATE INDEX IF NOT EXISTS idx_items_metadata_tags ON items USING GIN ((metadata -> 'tags'));
+
+-- Exact author match: metadata ->> 'author' = $author
+CREATE INDEX IF NOT EXISTS idx_items_metadata_author ON items ((metadata ->> 'author'));
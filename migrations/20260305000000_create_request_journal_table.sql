@@ -0,0 +1,18 @@
+-- Journal of POST requests sent with an Idempotency-Key header.
+-- A retry with the same key replays the stored response; while the original is still
+-- processing the retry gets 202 Accepted and a Location to poll (GET /requests/{key}).
+CREATE TABLE IF NOT EXISTS request_journal (
+    -- "<api key id>:<Idempotency-Key>" so keys never collide across callers
+    key VARCHAR(512) PRIMARY KEY,
+    request_hash CHAR(64) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'processing',
+    response_status SMALLINT,
+    response_body TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Lets operators prune old entries by age
+CREATE INDEX IF NOT EXISTS idx_request_journal_created_at ON request_journal (created_at);
+
+COMMENT ON COLUMN request_journal.status IS 'Status: processing, completed';
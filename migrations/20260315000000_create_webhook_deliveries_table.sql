@@ -0,0 +1,17 @@
+-- Audit trail of webhook deliveries: one row per attempt to POST an item status event
+-- (item.submitted, item.confirmed, item.failed) to a subscriber URL.
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id BIGSERIAL PRIMARY KEY,
+    event_id VARCHAR(64) NOT NULL,
+    event VARCHAR(50) NOT NULL,
+    item_id VARCHAR(255) NOT NULL,
+    url TEXT NOT NULL,
+    attempt INTEGER NOT NULL,
+    response_status SMALLINT,
+    error TEXT,
+    succeeded BOOLEAN NOT NULL,
+    attempted_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_event_id ON webhook_deliveries (event_id);
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_item_id ON webhook_deliveries (item_id, attempted_at DESC);
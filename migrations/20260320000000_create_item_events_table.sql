@@ -0,0 +1,22 @@
+-- Append-only log of item status events (item.submitted, item.confirmed, item.failed),
+-- written in the same transaction as the status change. `position` orders the whole log;
+-- `sequence` numbers each item's events from 1.
+CREATE TABLE IF NOT EXISTS item_events (
+    position BIGSERIAL PRIMARY KEY,
+    id VARCHAR(64) NOT NULL UNIQUE,
+    item_id VARCHAR(255) NOT NULL,
+    sequence BIGINT NOT NULL,
+    event VARCHAR(50) NOT NULL,
+    status VARCHAR(50) NOT NULL,
+    signature VARCHAR(255),
+    error TEXT,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (item_id, sequence)
+);
+
+-- Delivery cursor per subscriber: position of the last event it accepted
+CREATE TABLE IF NOT EXISTS webhook_subscriptions (
+    name TEXT PRIMARY KEY,
+    last_position BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,18 @@
+-- Dead-letter queue: blockchain submissions that exhausted their retries. Rows are kept
+-- after an operator requeues them (requeued_at is set) as a record of the failure.
+CREATE TABLE IF NOT EXISTS failed_submissions (
+    id VARCHAR(64) PRIMARY KEY,
+    item_id VARCHAR(255) NOT NULL REFERENCES items(id) ON DELETE CASCADE,
+    outbox_id UUID NOT NULL,
+    hash VARCHAR(255) NOT NULL,
+    payload JSONB NOT NULL,
+    retry_count INTEGER NOT NULL,
+    last_error TEXT NOT NULL,
+    attempt_blockhash VARCHAR(255),
+    failed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    requeued_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_failed_submissions_pending
+    ON failed_submissions (failed_at DESC)
+    WHERE requeued_at IS NULL;
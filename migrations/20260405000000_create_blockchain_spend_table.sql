@@ -0,0 +1,8 @@
+-- Submission budget: fees spent per signer and UTC day (see SUBMISSION_DAILY_BUDGET)
+CREATE TABLE IF NOT EXISTS blockchain_spend (
+    signer VARCHAR(255) NOT NULL,
+    day DATE NOT NULL,
+    spent BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (signer, day)
+);
@@ -0,0 +1,13 @@
+-- Named positions of export consumers (GET /items/export?bookmark=), advanced on
+-- acknowledgment. The position is the (updated_at, id) of the last acknowledged item.
+CREATE TABLE IF NOT EXISTS export_bookmarks (
+    name VARCHAR(64) PRIMARY KEY,
+    last_updated_at TIMESTAMPTZ,
+    last_item_id VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    acknowledged_at TIMESTAMPTZ
+);
+
+-- The change feed reads live items in (updated_at, id) order
+CREATE INDEX IF NOT EXISTS idx_items_live_updated_at_id
+    ON items (updated_at, id) WHERE deleted_at IS NULL;
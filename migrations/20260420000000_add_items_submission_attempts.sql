@@ -0,0 +1,3 @@
+-- Attempt history of each item's blockchain submission: when it ran, how long it took,
+-- which RPC endpoint handled it and how it ended (GET /items/{id}/attempts).
+ALTER TABLE items ADD COLUMN IF NOT EXISTS submission_attempts JSONB NOT NULL DEFAULT '[]'::jsonb;
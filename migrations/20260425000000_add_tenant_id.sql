@@ -0,0 +1,13 @@
+-- Tenant that owns each item and each API key. Rows from before tenancy belong to
+-- 'default', which is also where anonymous requests read and write.
+ALTER TABLE items ADD COLUMN IF NOT EXISTS tenant_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS tenant_id TEXT NOT NULL DEFAULT 'default';
+
+CREATE INDEX IF NOT EXISTS idx_items_tenant_pagination ON items (tenant_id, created_at DESC, id DESC);
+
+-- Content hashes are now unique per tenant; the index is recreated on (tenant_id, hash)
+-- at startup when ITEM_HASH_UNIQUE is on
+DROP INDEX IF EXISTS idx_items_hash_unique;
+
+-- Export bookmarks are stored as '<tenant>/<name>'
+ALTER TABLE export_bookmarks ALTER COLUMN name TYPE VARCHAR(129);
@@ -0,0 +1,3 @@
+-- Row version for optimistic concurrency: bumped by every content update, which must
+-- name the version it read (`If-Match`)
+ALTER TABLE items ADD COLUMN IF NOT EXISTS version BIGINT NOT NULL DEFAULT 1;
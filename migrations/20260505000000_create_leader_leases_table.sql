@@ -0,0 +1,6 @@
+-- Leases naming the one instance that runs each singleton scheduled job (LEADER_ELECTION)
+CREATE TABLE IF NOT EXISTS leader_leases (
+    name VARCHAR(255) PRIMARY KEY,
+    holder VARCHAR(255) NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,3 @@
+-- Object store key of content kept outside the row (large content); `content` is then
+-- empty and `hash` still covers the full content
+ALTER TABLE items ADD COLUMN IF NOT EXISTS content_key TEXT;
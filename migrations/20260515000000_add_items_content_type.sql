@@ -0,0 +1,6 @@
+-- Media type `GET /items/{id}/content` serves the content with, and its size in bytes so
+-- byte ranges can be answered without reading offloaded content first. Offloaded rows
+-- keep a length of 0 (unknown) since their content is not in the row.
+ALTER TABLE items ADD COLUMN IF NOT EXISTS content_type TEXT NOT NULL DEFAULT 'text/plain; charset=utf-8';
+ALTER TABLE items ADD COLUMN IF NOT EXISTS content_length BIGINT NOT NULL DEFAULT 0;
+UPDATE items SET content_length = octet_length(content) WHERE content_key IS NULL;
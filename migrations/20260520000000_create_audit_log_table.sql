@@ -0,0 +1,14 @@
+-- Audit log: who created, changed or deleted what. One row per item write (create,
+-- update, delete, retry) and per successful admin change, with the changed fields.
+CREATE TABLE IF NOT EXISTS audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    principal VARCHAR(255) NOT NULL,
+    action VARCHAR(64) NOT NULL,
+    resource_id VARCHAR(255),
+    request_id VARCHAR(255),
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    diff JSONB
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_principal ON audit_log (principal, id DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_resource_id ON audit_log (resource_id, id DESC);
@@ -0,0 +1,13 @@
+-- Request quotas per API key (NULL: unlimited) and the requests counted against them,
+-- per key and UTC day; monthly usage is the sum over the month's days.
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS daily_quota BIGINT;
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS monthly_quota BIGINT;
+
+CREATE TABLE IF NOT EXISTS api_key_usage (
+    key_id VARCHAR(255) NOT NULL,
+    day DATE NOT NULL,
+    requests BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (key_id, day)
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_key_usage_day ON api_key_usage (day);
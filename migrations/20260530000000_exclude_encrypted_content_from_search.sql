@@ -0,0 +1,16 @@
+-- Item content encrypted at rest (`enc:v1:...`) carries no searchable words: leave it out
+-- of the search vector, which then covers the name and description of such items.
+
+DROP INDEX IF EXISTS idx_items_search_vector;
+
+ALTER TABLE items DROP COLUMN IF EXISTS search_vector;
+
+ALTER TABLE items
+    ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', coalesce(name, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(description, '')), 'B') ||
+        setweight(to_tsvector('english',
+            CASE WHEN content LIKE 'enc:v1:%' THEN '' ELSE coalesce(content, '') END), 'C')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_items_search_vector ON items USING GIN (search_vector);
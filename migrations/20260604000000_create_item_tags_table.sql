@@ -0,0 +1,22 @@
+-- Metadata tags normalized into one row per (item, tag), maintained by the repository
+-- alongside every write of items.metadata. GET /items?tag= and GET /tags read this table
+-- instead of scanning the JSONB tags, so the GIN index on them is dropped.
+CREATE TABLE IF NOT EXISTS item_tags (
+    item_id VARCHAR(255) NOT NULL REFERENCES items(id) ON DELETE CASCADE,
+    tag TEXT NOT NULL,
+    PRIMARY KEY (item_id, tag)
+);
+
+-- Tag filter and usage counts: tag -> items
+CREATE INDEX IF NOT EXISTS idx_item_tags_tag ON item_tags (tag, item_id);
+
+INSERT INTO item_tags (item_id, tag)
+SELECT DISTINCT items.id, tags.tag
+FROM items
+CROSS JOIN LATERAL jsonb_array_elements_text(
+    CASE WHEN jsonb_typeof(items.metadata -> 'tags') = 'array'
+         THEN items.metadata -> 'tags' ELSE '[]'::jsonb END
+) AS tags(tag)
+ON CONFLICT DO NOTHING;
+
+DROP INDEX IF EXISTS idx_items_metadata_tags;
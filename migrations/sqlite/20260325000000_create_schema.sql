@@ -0,0 +1,94 @@
+-- SQLite schema for local development (mirrors the Postgres migrations in ../).
+-- Timestamps are RFC 3339 UTC text written by the application, so they sort as text;
+-- JSON columns hold serialized text.
+
+CREATE TABLE IF NOT EXISTS items (
+    id TEXT PRIMARY KEY,
+    hash TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    content TEXT NOT NULL,
+    metadata TEXT,
+    blockchain_status TEXT NOT NULL DEFAULT 'pending',
+    blockchain_signature TEXT,
+    blockchain_retry_count INTEGER NOT NULL DEFAULT 0,
+    blockchain_last_error TEXT,
+    blockchain_next_retry_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    deleted_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_items_pagination ON items (created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_items_status_created_at ON items (blockchain_status, created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_items_name ON items (name);
+CREATE INDEX IF NOT EXISTS idx_items_deleted_at ON items (deleted_at) WHERE deleted_at IS NOT NULL;
+
+CREATE TABLE IF NOT EXISTS solana_outbox (
+    id TEXT PRIMARY KEY,
+    aggregate_id TEXT NOT NULL REFERENCES items (id) ON DELETE CASCADE,
+    payload TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    retry_count INTEGER NOT NULL DEFAULT 0,
+    next_retry_at TEXT,
+    attempt_blockhash TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_solana_outbox_polling ON solana_outbox (status, next_retry_at, created_at);
+CREATE INDEX IF NOT EXISTS idx_solana_outbox_aggregate_id ON solana_outbox (aggregate_id);
+
+CREATE TABLE IF NOT EXISTS api_keys (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    key_hash TEXT NOT NULL UNIQUE,
+    -- JSON array of scope names
+    scopes TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL,
+    revoked_at TEXT
+);
+
+CREATE TABLE IF NOT EXISTS request_journal (
+    key TEXT PRIMARY KEY,
+    request_hash TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'processing',
+    response_status INTEGER,
+    response_body TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_id TEXT NOT NULL,
+    event TEXT NOT NULL,
+    item_id TEXT NOT NULL,
+    url TEXT NOT NULL,
+    attempt INTEGER NOT NULL,
+    response_status INTEGER,
+    error TEXT,
+    succeeded INTEGER NOT NULL,
+    attempted_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_event_id ON webhook_deliveries (event_id);
+
+CREATE TABLE IF NOT EXISTS item_events (
+    position INTEGER PRIMARY KEY AUTOINCREMENT,
+    id TEXT NOT NULL UNIQUE,
+    item_id TEXT NOT NULL,
+    sequence INTEGER NOT NULL,
+    event TEXT NOT NULL,
+    status TEXT NOT NULL,
+    signature TEXT,
+    error TEXT,
+    occurred_at TEXT NOT NULL,
+    UNIQUE (item_id, sequence)
+);
+
+CREATE TABLE IF NOT EXISTS webhook_subscriptions (
+    name TEXT PRIMARY KEY,
+    last_position INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL
+);
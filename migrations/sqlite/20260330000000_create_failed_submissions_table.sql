@@ -0,0 +1,15 @@
+-- Dead-letter queue for blockchain submissions that exhausted their retries
+CREATE TABLE IF NOT EXISTS failed_submissions (
+    id TEXT PRIMARY KEY,
+    item_id TEXT NOT NULL REFERENCES items (id) ON DELETE CASCADE,
+    outbox_id TEXT NOT NULL,
+    hash TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    retry_count INTEGER NOT NULL,
+    last_error TEXT NOT NULL,
+    attempt_blockhash TEXT,
+    failed_at TEXT NOT NULL,
+    requeued_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_failed_submissions_failed_at ON failed_submissions (failed_at);
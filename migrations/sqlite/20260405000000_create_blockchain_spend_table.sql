@@ -0,0 +1,8 @@
+-- Fees spent per signer and UTC day (submission budget)
+CREATE TABLE IF NOT EXISTS blockchain_spend (
+    signer TEXT NOT NULL,
+    day TEXT NOT NULL,
+    spent INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (signer, day)
+);
@@ -0,0 +1,11 @@
+-- Named positions of export consumers (GET /items/export?bookmark=), advanced on acknowledgment
+CREATE TABLE IF NOT EXISTS export_bookmarks (
+    name TEXT PRIMARY KEY,
+    last_updated_at TEXT,
+    last_item_id TEXT,
+    created_at TEXT NOT NULL,
+    acknowledged_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_items_live_updated_at_id
+    ON items (updated_at, id) WHERE deleted_at IS NULL;
@@ -0,0 +1,2 @@
+-- Attempt history of each item's blockchain submission (JSON array)
+ALTER TABLE items ADD COLUMN submission_attempts TEXT NOT NULL DEFAULT '[]';
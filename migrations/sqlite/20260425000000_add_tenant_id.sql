@@ -0,0 +1,7 @@
+-- Tenant that owns each item and each API key ('default' for earlier rows)
+ALTER TABLE items ADD COLUMN tenant_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE api_keys ADD COLUMN tenant_id TEXT NOT NULL DEFAULT 'default';
+
+CREATE INDEX IF NOT EXISTS idx_items_tenant_pagination ON items (tenant_id, created_at DESC, id DESC);
+
+DROP INDEX IF EXISTS idx_items_hash_unique;
@@ -0,0 +1,2 @@
+-- Row version for optimistic concurrency (bumped by every content update)
+ALTER TABLE items ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,6 @@
+-- Leases naming the one instance that runs each singleton scheduled job
+CREATE TABLE IF NOT EXISTS leader_leases (
+    name TEXT PRIMARY KEY,
+    holder TEXT NOT NULL,
+    expires_at TEXT NOT NULL
+);
@@ -0,0 +1,2 @@
+-- Object store key of content kept outside the row (large content)
+ALTER TABLE items ADD COLUMN content_key TEXT;
@@ -0,0 +1,4 @@
+-- Media type the content is served with and its size in bytes (0: unknown)
+ALTER TABLE items ADD COLUMN content_type TEXT NOT NULL DEFAULT 'text/plain; charset=utf-8';
+ALTER TABLE items ADD COLUMN content_length INTEGER NOT NULL DEFAULT 0;
+UPDATE items SET content_length = length(CAST(content AS BLOB)) WHERE content_key IS NULL;
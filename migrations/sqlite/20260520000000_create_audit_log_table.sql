@@ -0,0 +1,12 @@
+-- Who created, changed or deleted what, with the changed fields as JSON
+CREATE TABLE IF NOT EXISTS audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    principal TEXT NOT NULL,
+    action TEXT NOT NULL,
+    resource_id TEXT,
+    request_id TEXT,
+    occurred_at TEXT NOT NULL,
+    diff TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_resource_id ON audit_log (resource_id);
@@ -0,0 +1,12 @@
+-- Request quotas per API key (NULL: unlimited) and requests counted per key and UTC day
+ALTER TABLE api_keys ADD COLUMN daily_quota INTEGER;
+ALTER TABLE api_keys ADD COLUMN monthly_quota INTEGER;
+
+CREATE TABLE IF NOT EXISTS api_key_usage (
+    key_id TEXT NOT NULL,
+    day TEXT NOT NULL,
+    requests INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (key_id, day)
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_key_usage_day ON api_key_usage (day);
@@ -0,0 +1,14 @@
+-- Metadata tags normalized into one row per (item, tag), maintained by the repository
+-- alongside every write of items.metadata
+CREATE TABLE IF NOT EXISTS item_tags (
+    item_id TEXT NOT NULL REFERENCES items (id) ON DELETE CASCADE,
+    tag TEXT NOT NULL,
+    PRIMARY KEY (item_id, tag)
+);
+
+CREATE INDEX IF NOT EXISTS idx_item_tags_tag ON item_tags (tag, item_id);
+
+INSERT OR IGNORE INTO item_tags (item_id, tag)
+SELECT items.id, tags.value
+FROM items, json_each(items.metadata, '$.tags') AS tags
+WHERE json_type(items.metadata, '$.tags') = 'array';
@@ -0,0 +1,63 @@
+//! Conditional `GET` for item reads: `If-None-Match` revalidation with `304 Not Modified`.
+//!
+//! `GET /items/{id}` is tagged with the item version; list and search pages, which have
+//! no single version, with a hash of their body. Polling clients that send the tag back
+//! get an empty `304` while nothing changed.
+
+use axum::{
+    body::{Body, to_bytes},
+    http::{HeaderMap, HeaderValue, Method, Request, Response, StatusCode, header},
+    middleware::Next,
+};
+use sha2::{Digest, Sha256};
+
+/// Tag `200` responses to `GET` and answer a matching `If-None-Match` with `304`. An `ETag`
+/// set by the handler is kept; otherwise the tag is a hash of the body.
+pub async fn conditional_get_middleware(request: Request<Body>, next: Next) -> Response<Body> {
+    if !matches!(*request.method(), Method::GET | Method::HEAD) {
+        return next.run(request).await;
+    }
+    let request_headers = request.headers().clone();
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let (etag, body) = match parts.headers.get(header::ETAG) {
+        Some(etag) => (etag.clone(), body),
+        None => {
+            // JSON responses are serialized in one piece, so this only moves the buffer
+            let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+                return Response::from_parts(parts, Body::empty());
+            };
+            let etag = format!("\"{:x}\"", Sha256::digest(&bytes));
+            let etag = HeaderValue::from_str(&etag).expect("hex digest is a valid header value");
+            parts.headers.insert(header::ETAG, etag.clone());
+            (etag, Body::from(bytes))
+        }
+    };
+
+    if etag_matches(&request_headers, &etag) {
+        metrics::counter!("http_not_modified_total").increment(1);
+        let mut not_modified = Response::new(Body::empty());
+        *not_modified.status_mut() = StatusCode::NOT_MODIFIED;
+        not_modified.headers_mut().insert(header::ETAG, etag);
+        return not_modified;
+    }
+    Response::from_parts(parts, body)
+}
+
+/// `If-None-Match` lists `etag` (weak comparison) or is `*`
+pub(crate) fn etag_matches(headers: &HeaderMap, etag: &HeaderValue) -> bool {
+    let Some(value) = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return false;
+    };
+    let etag = etag.to_str().unwrap_or_default();
+    value.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
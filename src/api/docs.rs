@@ -0,0 +1,361 @@
+//! Swagger UI and OpenAPI document with cache headers and gzip bodies.
+//!
+//! Both are static for the lifetime of the process, so each response is compressed once and
+//! then served from memory. The OpenAPI document is compressed when the router is built;
+//! Swagger UI assets on their first request. Every response carries a content-hash `ETag`
+//! so clients revalidate with a `304` instead of downloading the bundle again.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::{Arc, RwLock};
+
+use axum::{
+    Router,
+    body::{Body, Bytes, to_bytes},
+    extract::State,
+    http::{HeaderMap, HeaderValue, Method, Request, Response, StatusCode, header},
+    middleware::{self, Next},
+};
+use flate2::{Compression, write::GzEncoder};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+use utoipa_swagger_ui::SwaggerUi;
+
+use super::conditional::etag_matches;
+
+/// Path the OpenAPI document is served from
+pub const OPENAPI_PATH: &str = "/api-docs/openapi.json";
+
+/// Cache policy for the OpenAPI document and Swagger UI pages that embed its URL
+/// (may change on every deploy, so clients revalidate hourly)
+const DOCUMENT_CACHE_CONTROL: &str = "public, max-age=3600";
+
+/// Cache policy for Swagger UI scripts, styles and images (only change with the crate version)
+const ASSET_CACHE_CONTROL: &str = "public, max-age=604800";
+
+/// Bodies smaller than this are not worth compressing
+const MIN_COMPRESS_BYTES: usize = 1024;
+
+/// Largest body the cache will hold (the Swagger UI bundle is about 1.5 MiB)
+const MAX_CACHED_BYTES: usize = 8 * 1024 * 1024;
+
+/// A response body kept in memory with its precompressed form
+#[derive(Debug, Clone)]
+struct CachedAsset {
+    content_type: HeaderValue,
+    etag: HeaderValue,
+    identity: Bytes,
+    gzip: Option<Bytes>,
+}
+
+impl CachedAsset {
+    fn new(content_type: HeaderValue, body: Bytes) -> Self {
+        let etag = format!("\"{:x}\"", Sha256::digest(&body));
+        let gzip = (body.len() >= MIN_COMPRESS_BYTES && is_compressible(&content_type))
+            .then(|| gzip(&body))
+            .flatten()
+            .filter(|compressed| compressed.len() < body.len());
+        Self {
+            content_type,
+            etag: HeaderValue::from_str(&etag).expect("hex digest is a valid header value"),
+            identity: body,
+            gzip,
+        }
+    }
+}
+
+/// In-memory cache of documentation responses keyed by request path
+#[derive(Debug, Default)]
+pub struct DocsCache {
+    assets: RwLock<HashMap<String, Arc<CachedAsset>>>,
+}
+
+impl DocsCache {
+    /// Cache with the OpenAPI document already serialized and compressed
+    #[must_use]
+    pub fn with_openapi_document(openapi: &utoipa::openapi::OpenApi) -> Self {
+        let cache = Self::default();
+        match openapi.to_json() {
+            Ok(json) => {
+                let asset =
+                    CachedAsset::new(HeaderValue::from_static("application/json"), json.into());
+                cache.insert(OPENAPI_PATH, asset);
+            }
+            Err(e) => warn!(error = %e, "Failed to serialize OpenAPI document"),
+        }
+        cache
+    }
+
+    fn get(&self, path: &str) -> Option<Arc<CachedAsset>> {
+        self.assets.read().unwrap().get(path).cloned()
+    }
+
+    fn insert(&self, path: &str, asset: CachedAsset) -> Arc<CachedAsset> {
+        let asset = Arc::new(asset);
+        self.assets
+            .write()
+            .unwrap()
+            .insert(path.to_string(), Arc::clone(&asset));
+        asset
+    }
+}
+
+/// Swagger UI at `/swagger-ui` and `openapi` at [`OPENAPI_PATH`]
+pub fn docs_routes(openapi: utoipa::openapi::OpenApi) -> Router {
+    let cache = Arc::new(DocsCache::with_openapi_document(&openapi));
+    Router::new()
+        .merge(SwaggerUi::new("/swagger-ui").url(OPENAPI_PATH, openapi))
+        .layer(middleware::from_fn_with_state(cache, docs_cache_middleware))
+}
+
+/// Serve documentation responses from the cache, filling it on the first successful GET
+pub async fn docs_cache_middleware(
+    State(cache): State<Arc<DocsCache>>,
+    request: Request<Body>,
+    next: Next,
+) -> Response<Body> {
+    if request.method() != Method::GET && request.method() != Method::HEAD {
+        return next.run(request).await;
+    }
+    let path = request.uri().path().to_string();
+    let head = request.method() == Method::HEAD;
+    let request_headers = request.headers().clone();
+
+    let asset = match cache.get(&path) {
+        Some(asset) => asset,
+        // HEAD responses have no body to cache
+        None if head => return next.run(request).await,
+        None => {
+            let response = next.run(request).await;
+            if response.status() != StatusCode::OK {
+                return response;
+            }
+            let (parts, body) = response.into_parts();
+            let Some(content_type) = parts.headers.get(header::CONTENT_TYPE).cloned() else {
+                return Response::from_parts(parts, body);
+            };
+            let Ok(body) = to_bytes(body, MAX_CACHED_BYTES).await else {
+                warn!(path = %path, "Documentation response too large to cache");
+                return Response::from_parts(parts, Body::empty());
+            };
+            cache.insert(&path, CachedAsset::new(content_type, body))
+        }
+    };
+    respond(&asset, &request_headers, cache_control(&path), head)
+}
+
+fn respond(
+    asset: &CachedAsset,
+    request_headers: &HeaderMap,
+    cache_control: &'static str,
+    head: bool,
+) -> Response<Body> {
+    let mut builder = Response::builder()
+        .header(header::CACHE_CONTROL, cache_control)
+        .header(header::ETAG, asset.etag.clone())
+        .header(header::VARY, "accept-encoding");
+
+    if etag_matches(request_headers, &asset.etag) {
+        return builder
+            .status(StatusCode::NOT_MODIFIED)
+            .body(Body::empty())
+            .unwrap();
+    }
+
+    let body = match &asset.gzip {
+        Some(gzip) if accepts_gzip(request_headers) => {
+            builder = builder.header(header::CONTENT_ENCODING, "gzip");
+            gzip.clone()
+        }
+        _ => asset.identity.clone(),
+    };
+    builder
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, asset.content_type.clone())
+        .header(header::CONTENT_LENGTH, body.len())
+        .body(if head {
+            Body::empty()
+        } else {
+            Body::from(body)
+        })
+        .unwrap()
+}
+
+fn cache_control(path: &str) -> &'static str {
+    let page = path == OPENAPI_PATH
+        || path.ends_with('/')
+        || path.ends_with(".html")
+        || path.ends_with("swagger-initializer.js");
+    if page {
+        DOCUMENT_CACHE_CONTROL
+    } else {
+        ASSET_CACHE_CONTROL
+    }
+}
+
+fn is_compressible(content_type: &HeaderValue) -> bool {
+    let content_type = content_type.to_str().unwrap_or_default();
+    content_type.starts_with("text/")
+        || content_type.starts_with("application/json")
+        || content_type.starts_with("application/javascript")
+        || content_type.starts_with("image/svg+xml")
+}
+
+fn gzip(body: &[u8]) -> Option<Bytes> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(body).ok()?;
+    encoder.finish().ok().map(Bytes::from)
+}
+
+/// `Accept-Encoding` lists gzip (or `*`) without `q=0`
+fn accepts_gzip(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|coding| {
+            let mut parts = coding.split(';').map(str::trim);
+            let name = parts.next().unwrap_or_default();
+            let rejected = parts.any(|param| {
+                param
+                    .strip_prefix("q=")
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .is_some_and(|q| q == 0.0)
+            });
+            (name.eq_ignore_ascii_case("gzip") || name == "*") && !rejected
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::ApiDoc;
+    use flate2::read::GzDecoder;
+    use http_body_util::BodyExt;
+    use std::io::Read;
+    use tower::ServiceExt;
+    use utoipa::OpenApi;
+
+    async fn get(router: &Router, uri: &str, headers: &[(&str, &str)]) -> Response<Body> {
+        let mut builder = Request::builder().uri(uri);
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        router
+            .clone()
+            .oneshot(builder.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn body_bytes(response: Response<Body>) -> Bytes {
+        response.into_body().collect().await.unwrap().to_bytes()
+    }
+
+    #[tokio::test]
+    async fn test_openapi_document_is_precompressed_and_cacheable() {
+        let router = docs_routes(ApiDoc::openapi());
+
+        let plain = get(&router, OPENAPI_PATH, &[]).await;
+        assert_eq!(plain.status(), StatusCode::OK);
+        assert!(plain.headers().get(header::CONTENT_ENCODING).is_none());
+        assert_eq!(
+            plain.headers()[header::CACHE_CONTROL],
+            DOCUMENT_CACHE_CONTROL
+        );
+        let etag = plain.headers()[header::ETAG].clone();
+        let plain = body_bytes(plain).await;
+
+        let compressed = get(&router, OPENAPI_PATH, &[("accept-encoding", "br, gzip")]).await;
+        assert_eq!(compressed.headers()[header::CONTENT_ENCODING], "gzip");
+        assert_eq!(compressed.headers()[header::VARY], "accept-encoding");
+        let compressed = body_bytes(compressed).await;
+        assert!(compressed.len() < plain.len());
+        let mut decoded = Vec::new();
+        GzDecoder::new(&compressed[..])
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, plain);
+
+        let revalidated = get(
+            &router,
+            OPENAPI_PATH,
+            &[("if-none-match", etag.to_str().unwrap())],
+        )
+        .await;
+        assert_eq!(revalidated.status(), StatusCode::NOT_MODIFIED);
+        assert!(body_bytes(revalidated).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_swagger_assets_get_long_lived_cache_headers() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // Stand-in for an embedded Swagger UI script
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let assets = Router::new()
+            .route(
+                "/swagger-ui/swagger-ui-bundle.js",
+                axum::routing::get(move || async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    (
+                        [(header::CONTENT_TYPE, "application/javascript")],
+                        "console.log('swagger');\n".repeat(200),
+                    )
+                }),
+            )
+            .layer(middleware::from_fn_with_state(
+                Arc::new(DocsCache::default()),
+                docs_cache_middleware,
+            ));
+
+        let first = get(
+            &assets,
+            "/swagger-ui/swagger-ui-bundle.js",
+            &[("accept-encoding", "gzip")],
+        )
+        .await;
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(first.headers()[header::CACHE_CONTROL], ASSET_CACHE_CONTROL);
+        assert_eq!(first.headers()[header::CONTENT_ENCODING], "gzip");
+        let etag = first.headers()[header::ETAG].clone();
+
+        // Served from the cache with the same validator
+        let second = get(&assets, "/swagger-ui/swagger-ui-bundle.js", &[]).await;
+        assert_eq!(second.headers()[header::ETAG], etag);
+        assert!(second.headers().get(header::CONTENT_ENCODING).is_none());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let router = docs_routes(ApiDoc::openapi());
+        let index = get(&router, "/swagger-ui/", &[]).await;
+        assert_eq!(index.status(), StatusCode::OK);
+        assert_eq!(
+            index.headers()[header::CACHE_CONTROL],
+            DOCUMENT_CACHE_CONTROL
+        );
+
+        let missing = get(&router, "/swagger-ui/missing.js", &[]).await;
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+        assert!(missing.headers().get(header::CACHE_CONTROL).is_none());
+    }
+
+    #[test]
+    fn test_accepts_gzip_respects_q_zero() {
+        let mut headers = HeaderMap::new();
+        assert!(!accepts_gzip(&headers));
+        headers.insert(
+            header::ACCEPT_ENCODING,
+            HeaderValue::from_static("gzip;q=0, br"),
+        );
+        assert!(!accepts_gzip(&headers));
+        headers.insert(
+            header::ACCEPT_ENCODING,
+            HeaderValue::from_static("deflate, GZIP;q=0.5"),
+        );
+        assert!(accepts_gzip(&headers));
+        headers.insert(header::ACCEPT_ENCODING, HeaderValue::from_static("*"));
+        assert!(accepts_gzip(&headers));
+    }
+}
@@ -0,0 +1,522 @@
+//! Request and response examples for the OpenAPI document.
+//!
+//! Payloads are built with the domain constructors and serialized, and error bodies come
+//! from each error type's own [`ApiError`] mapping, so the Swagger UI shows what the API
+//! actually sends instead of one placeholder per field. [`ApiExamples`] attaches them to
+//! every operation whose request or response uses the matching schema.
+
+use std::collections::HashMap;
+
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use utoipa::Modify;
+use utoipa::openapi::{OpenApi, RefOr, content::Content, example::ExampleBuilder, path::Operation};
+use validator::Validate;
+
+use super::handlers::{ApiError, PRECONDITION_REQUIRED_MESSAGE};
+use super::middleware::{
+    IP_BLOCKED_MESSAGE, MAINTENANCE_MESSAGE, MIGRATIONS_PENDING_MESSAGE, PAYLOAD_TOO_LARGE_MESSAGE,
+};
+use super::router::rate_limit_response;
+use crate::app::{DEFAULT_MAX_METADATA_BYTES, VerifyItemError};
+use crate::domain::{
+    ApiKeyError, BlockchainError, BlockchainStatus, ContentHasher, CreateItemRequest, ErrorDetail,
+    ErrorResponse, FieldError, Item, ItemError, ItemMetadata, ItemMetadataRequest, ItemSummary,
+    JobError, NotificationError, PaginatedResponse, RequestJournalError, ValidationError,
+    WorkerError,
+};
+
+const ITEM_ID: &str = "item_01945b3c-7e2a-7c41-9d3f-5a8b2e6c4f10";
+const REQUEST_ID: &str = "4b7e2c1a-9f0d-4e8a-b5c3-2d1f6a7e8b90";
+
+/// [`utoipa::Modify`] adding the examples to [`ApiDoc`](super::ApiDoc)
+pub struct ApiExamples;
+
+impl Modify for ApiExamples {
+    fn modify(&self, openapi: &mut OpenApi) {
+        let errors = error_examples();
+        for (path, item) in &mut openapi.paths.paths {
+            let operations = [
+                (false, &mut item.get),
+                (true, &mut item.put),
+                (true, &mut item.post),
+                (true, &mut item.delete),
+                (true, &mut item.patch),
+            ];
+            for (write, operation) in operations {
+                if let Some(operation) = operation {
+                    attach(path, write, operation, &errors);
+                }
+            }
+        }
+    }
+}
+
+/// An error body and the paths that can return it
+struct ErrorExample {
+    name: &'static str,
+    /// Path prefixes the error is returned under
+    paths: &'static [&'static str],
+    status: StatusCode,
+    /// Only returned to writes (e.g. by the schema guard)
+    writes_only: bool,
+    body: ErrorResponse,
+}
+
+impl ErrorExample {
+    fn new(
+        name: &'static str,
+        paths: &'static [&'static str],
+        error: &impl ApiError,
+        fields: Vec<FieldError>,
+    ) -> Self {
+        let (status, error_type, message) = error.parts();
+        let mut example = Self::raw(name, paths, status, error_type, message);
+        example.body.error.fields = fields;
+        example
+    }
+
+    /// An error body built outside an [`ApiError`] (middleware, extractors, handlers)
+    fn raw(
+        name: &'static str,
+        paths: &'static [&'static str],
+        status: StatusCode,
+        error_type: &str,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            name,
+            paths,
+            status,
+            writes_only: false,
+            body: ErrorResponse {
+                error: ErrorDetail {
+                    r#type: error_type.to_string(),
+                    message: message.into(),
+                    fields: Vec::new(),
+                    request_id: Some(REQUEST_ID.to_string()),
+                },
+            },
+        }
+    }
+
+    #[must_use]
+    fn writes_only(mut self) -> Self {
+        self.writes_only = true;
+        self
+    }
+}
+
+/// A created item that has been anchored on-chain
+fn item_example() -> Item {
+    let request = create_item_request_example();
+    let created_at = DateTime::<Utc>::from_timestamp(1_768_469_400, 0).unwrap_or_default();
+    let mut item = Item::new(
+        ITEM_ID.to_string(),
+        ContentHasher::hash_request(&request),
+        request.name,
+        request.content,
+    );
+    item.description = request.description;
+    item.metadata = Some(ItemMetadata {
+        author: Some("Ada Lovelace".to_string()),
+        version: Some("1.2.0".to_string()),
+        tags: vec!["contract".to_string(), "signed".to_string()],
+        custom_fields: HashMap::from([("department".to_string(), "legal".to_string())]),
+    });
+    item.blockchain_status = BlockchainStatus::Confirmed;
+    item.blockchain_signature = Some(
+        "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d8J8s5fYbkkmhV6YUKTB4YVQS3Bxb3PaTjxr2YbMRXsA"
+            .to_string(),
+    );
+    item.created_at = created_at;
+    item.updated_at = created_at + chrono::Duration::seconds(4);
+    item
+}
+
+fn create_item_request_example() -> CreateItemRequest {
+    let mut request = CreateItemRequest::new(
+        "Supplier agreement 2026-014".to_string(),
+        "This agreement is entered into by Acme Corp and Globex Ltd...".to_string(),
+    );
+    request.description = Some("Signed supply contract, countersigned copy".to_string());
+    request.metadata = Some(ItemMetadataRequest {
+        author: Some("Ada Lovelace".to_string()),
+        version: Some("1.2.0".to_string()),
+        tags: vec!["contract".to_string(), "signed".to_string()],
+        custom_fields: HashMap::from([("department".to_string(), "legal".to_string())]),
+    });
+    request
+}
+
+fn page_example() -> PaginatedResponse<ItemSummary> {
+    PaginatedResponse::new(
+        vec![item_example().into()],
+        Some("eyJjIjoiMjAyNi0wMS0xNVQwOTozMDowMFoiLCJpIjoiaXRlbV8wMTk0In0.Qm9va21hcms".to_string()),
+        true,
+    )
+}
+
+/// Every error the handlers report, one example per variant a client can see
+fn error_examples() -> Vec<ErrorExample> {
+    let invalid_request = CreateItemRequest::new(String::new(), String::new());
+    let field_errors = invalid_request
+        .validate()
+        .err()
+        .map(ValidationError::from)
+        .unwrap_or(ValidationError::MissingField("name".to_string()));
+    let too_large = ValidationError::TooLarge {
+        field: "metadata".to_string(),
+        size: DEFAULT_MAX_METADATA_BYTES + 512,
+        limit: DEFAULT_MAX_METADATA_BYTES,
+    };
+    let items = &["/items", "/admin/queue", "/admin/dlq"][..];
+    vec![
+        ErrorExample::new(
+            "validation_error",
+            &["/items", "/admin/blocklist", "/admin/api-keys", "/verify"],
+            &field_errors,
+            field_errors.field_errors(),
+        ),
+        ErrorExample::new(
+            "field_too_large",
+            &["/items"],
+            &too_large,
+            too_large.field_errors(),
+        ),
+        ErrorExample::new(
+            "not_found",
+            items,
+            &ItemError::NotFound(ITEM_ID.to_string()),
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "invalid_state",
+            items,
+            &ItemError::InvalidState("Search query must not be empty".to_string()),
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "conflict",
+            &["/items/{id}"],
+            &ItemError::Conflict {
+                id: ITEM_ID.to_string(),
+                expected: 2,
+                current: 3,
+            },
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "duplicate_content",
+            &["/items"],
+            &ItemError::DuplicateContent,
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "range_not_satisfiable",
+            &["/items/{id}/content"],
+            &ItemError::RangeNotSatisfiable { length: 1024 },
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "invalid_cursor",
+            &["/items"],
+            &ItemError::InvalidCursor("Cursor is malformed or was tampered with".to_string()),
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "invalid_cursor",
+            &["/admin/events", "/admin/webhook-deliveries", "/admin/audit"],
+            &NotificationError::InvalidCursor(
+                "Cursor is malformed or was tampered with".to_string(),
+            ),
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "blockchain_unavailable",
+            &["/items/{id}/verify"],
+            &VerifyItemError::Blockchain(BlockchainError::CircuitOpen),
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "logs_unavailable",
+            &["/admin/events", "/admin/webhook-deliveries", "/admin/audit"],
+            &NotificationError::LogUnavailable,
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "worker_not_running",
+            &["/admin/worker"],
+            &WorkerError::NotRunning,
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "not_found",
+            &["/admin/api-keys"],
+            &ApiKeyError::NotFound("key_7f3a9c".to_string()),
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "invalid_state",
+            &["/admin/api-keys"],
+            &ApiKeyError::Revoked("key_7f3a9c".to_string()),
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "api_keys_unavailable",
+            &["/admin/api-keys"],
+            &ApiKeyError::StoreUnavailable,
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "usage_unavailable",
+            &["/admin/usage"],
+            &ApiKeyError::UsageUnavailable,
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "not_found",
+            &["/jobs"],
+            &JobError::NotFound("job_2c5e8a".to_string()),
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "jobs_unavailable",
+            &["/jobs", "/admin/dlq", "/admin/encryption"],
+            &JobError::StoreUnavailable,
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "request_journal_unavailable",
+            &["/requests"],
+            &RequestJournalError::Unavailable,
+            Vec::new(),
+        ),
+        ErrorExample::new(
+            "repository_error",
+            &["/"],
+            &ItemError::RepositoryFailure,
+            Vec::new(),
+        ),
+        ErrorExample::raw(
+            "not_found",
+            &["/requests"],
+            StatusCode::NOT_FOUND,
+            "not_found",
+            "No request with idempotency key: 7d0c9a52-import-2026-01-15",
+        ),
+        ErrorExample::raw(
+            "not_found",
+            &["/admin/bans"],
+            StatusCode::NOT_FOUND,
+            "not_found",
+            "No active ban on 203.0.113.7",
+        ),
+        ErrorExample::raw(
+            "precondition_required",
+            &["/items/{id}"],
+            StatusCode::PRECONDITION_REQUIRED,
+            "precondition_required",
+            PRECONDITION_REQUIRED_MESSAGE,
+        ),
+        ErrorExample::raw(
+            "forbidden",
+            &["/ws"],
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Tenant 'globex' is not accessible with this key",
+        ),
+        // Rejected before the handler runs
+        ErrorExample::raw(
+            "ip_blocked",
+            &["/admin/blocklist"],
+            StatusCode::FORBIDDEN,
+            "ip_blocked",
+            IP_BLOCKED_MESSAGE,
+        ),
+        ErrorExample::raw(
+            "payload_too_large",
+            &["/items"],
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "payload_too_large",
+            PAYLOAD_TOO_LARGE_MESSAGE,
+        ),
+        ErrorExample::raw(
+            "unsupported_media_type",
+            &["/items"],
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "unsupported_media_type",
+            "Expected request with `Content-Type: application/json`",
+        ),
+        ErrorExample::raw(
+            "invalid_body",
+            &["/items"],
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "invalid_body",
+            "Failed to deserialize the JSON body into the target type: name: invalid type: \
+             integer `42`, expected a string at line 1 column 11",
+        ),
+        ErrorExample::raw(
+            "invalid_query",
+            &["/admin/usage"],
+            StatusCode::BAD_REQUEST,
+            "invalid_query",
+            "Failed to deserialize query string: day: input contains invalid characters",
+        ),
+        ErrorExample::raw(
+            "maintenance",
+            &["/items"],
+            StatusCode::SERVICE_UNAVAILABLE,
+            "maintenance",
+            MAINTENANCE_MESSAGE,
+        )
+        .writes_only(),
+        ErrorExample::raw(
+            "migrations_pending",
+            &["/items"],
+            StatusCode::SERVICE_UNAVAILABLE,
+            "migrations_pending",
+            MIGRATIONS_PENDING_MESSAGE,
+        )
+        .writes_only(),
+    ]
+}
+
+fn attach(path: &str, write: bool, operation: &mut Operation, errors: &[ErrorExample]) {
+    if let Some(body) = &mut operation.request_body {
+        for content in body.content.values_mut() {
+            if schema_name(content) == Some("CreateItemRequest") {
+                content.example = Some(to_value(&create_item_request_example()));
+            }
+        }
+    }
+    for (status, response) in &mut operation.responses.responses {
+        let RefOr::T(response) = response else {
+            continue;
+        };
+        for content in response.content.values_mut() {
+            match schema_name(content) {
+                Some("Item") => content.example = Some(to_value(&item_example())),
+                Some("PaginatedResponse_ItemSummary") => {
+                    content.example = Some(to_value(&page_example()));
+                }
+                Some("RateLimitResponse") => {
+                    let mut body = rate_limit_response(12);
+                    body.error.request_id = Some(REQUEST_ID.to_string());
+                    content.example = Some(to_value(&body));
+                }
+                Some("ErrorResponse") => {
+                    for example in errors.iter().filter(|e| {
+                        e.status.as_str() == status
+                            && (write || !e.writes_only)
+                            && e.paths.iter().any(|prefix| path.starts_with(prefix))
+                    }) {
+                        let value = ExampleBuilder::new()
+                            .summary(example.body.error.message.clone())
+                            .value(Some(to_value(&example.body)))
+                            .build();
+                        content
+                            .examples
+                            .insert(example.name.to_string(), RefOr::T(value));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Name of the component schema `content` refers to
+fn schema_name(content: &Content) -> Option<&str> {
+    match content.schema.as_ref()? {
+        RefOr::Ref(reference) => reference.ref_location.rsplit('/').next(),
+        RefOr::T(_) => None,
+    }
+}
+
+fn to_value(value: &impl Serialize) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::ApiDoc;
+    use utoipa::OpenApi;
+
+    fn response_content(doc: &Value, path: &str, method: &str, status: &str) -> Value {
+        doc["paths"][path][method]["responses"][status]["content"]["application/json"].clone()
+    }
+
+    #[test]
+    fn test_payload_examples_are_built_from_domain_types() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+        let item = response_content(&doc, "/items/{id}", "get", "200")["example"].clone();
+        let item: Item = serde_json::from_value(item).unwrap();
+        assert_eq!(item.id, ITEM_ID);
+        assert_eq!(
+            item.hash,
+            ContentHasher::hash_request(&create_item_request_example())
+        );
+
+        let page = response_content(&doc, "/items", "get", "200")["example"].clone();
+        let page: PaginatedResponse<ItemSummary> = serde_json::from_value(page).unwrap();
+        assert_eq!(page.items, vec![ItemSummary::from(item)]);
+        assert!(page.has_more);
+
+        let request = &doc["paths"]["/items"]["post"]["requestBody"]["content"]["application/json"]
+            ["example"];
+        let request: CreateItemRequest = serde_json::from_value(request.clone()).unwrap();
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_error_examples_match_status_and_path() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+        let not_found = response_content(&doc, "/items/{id}", "get", "404");
+        let example = &not_found["examples"]["not_found"]["value"]["error"];
+        assert_eq!(example["type"], "not_found");
+        assert_eq!(example["message"], format!("Item not found: {}", ITEM_ID));
+
+        // Field-level details come from running the real validator
+        let create = response_content(&doc, "/items", "post", "400");
+        let fields = &create["examples"]["validation_error"]["value"]["error"]["fields"];
+        assert_eq!(fields[0]["field"], "content");
+        assert_eq!(fields[1]["field"], "name");
+
+        // Only errors the endpoint can return are listed
+        let key_not_found = response_content(&doc, "/admin/api-keys/{id}", "delete", "404");
+        let message = &key_not_found["examples"]["not_found"]["value"]["error"]["message"];
+        assert!(message.as_str().unwrap().starts_with("API key not found"));
+
+        let limited = response_content(&doc, "/items", "get", "429");
+        assert_eq!(limited["example"]["error"]["type"], "rate_limited");
+    }
+
+    #[test]
+    fn test_every_error_response_has_an_example() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let mut missing = Vec::new();
+        for (path, item) in doc["paths"].as_object().unwrap() {
+            for (method, operation) in item.as_object().unwrap() {
+                let Some(responses) = operation["responses"].as_object() else {
+                    continue;
+                };
+                for (status, response) in responses {
+                    let content = &response["content"]["application/json"];
+                    if content["schema"]["$ref"] == "#/components/schemas/ErrorResponse"
+                        && content["example"].is_null()
+                        && content["examples"].as_object().is_none_or(|e| e.is_empty())
+                    {
+                        missing.push(format!("{} {} {}", method, path, status));
+                    }
+                }
+            }
+        }
+        assert!(missing.is_empty(), "no error example for {:#?}", missing);
+    }
+}
@@ -0,0 +1,128 @@
+//! Encoding of `GET /items/export` bodies.
+//!
+//! Items are encoded one at a time as the repository stream yields them, so the response
+//! is written while the export is still being read.
+
+use crate::domain::{ExportFormat, Item};
+
+/// Columns of a CSV export, in order
+const CSV_COLUMNS: [&str; 12] = [
+    "id",
+    "hash",
+    "name",
+    "description",
+    "content",
+    "metadata",
+    "blockchain_status",
+    "blockchain_signature",
+    "blockchain_retry_count",
+    "blockchain_last_error",
+    "created_at",
+    "updated_at",
+];
+
+impl ExportFormat {
+    /// `Content-Type` of the response body
+    #[must_use]
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    /// Suggested download file name
+    #[must_use]
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Self::Csv => "items.csv",
+            Self::Ndjson => "items.ndjson",
+        }
+    }
+
+    /// Bytes written before the first item (the CSV header row)
+    #[must_use]
+    pub fn preamble(&self) -> String {
+        match self {
+            Self::Csv => csv_line(CSV_COLUMNS.iter().map(|c| (*c).to_string())),
+            Self::Ndjson => String::new(),
+        }
+    }
+
+    /// One item as a line terminated by `\n`
+    #[must_use]
+    pub fn encode(&self, item: &Item) -> String {
+        match self {
+            Self::Csv => csv_line([
+                item.id.clone(),
+                item.hash.clone(),
+                item.name.clone(),
+                item.description.clone().unwrap_or_default(),
+                item.content.clone(),
+                item.metadata
+                    .as_ref()
+                    .map(|m| serde_json::to_string(m).unwrap_or_default())
+                    .unwrap_or_default(),
+                item.blockchain_status.as_str().to_string(),
+                item.blockchain_signature.clone().unwrap_or_default(),
+                item.blockchain_retry_count.to_string(),
+                item.blockchain_last_error.clone().unwrap_or_default(),
+                item.created_at.to_rfc3339(),
+                item.updated_at.to_rfc3339(),
+            ]),
+            Self::Ndjson => {
+                let mut line = serde_json::to_string(item).unwrap_or_default();
+                line.push('\n');
+                line
+            }
+        }
+    }
+}
+
+/// RFC 4180 row: fields containing a separator, quote or line break are quoted, with
+/// quotes doubled
+fn csv_line(fields: impl IntoIterator<Item = String>) -> String {
+    let fields: Vec<String> = fields
+        .into_iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field
+            }
+        })
+        .collect();
+    let mut line = fields.join(",");
+    line.push_str("\r\n");
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_quotes_fields_that_need_it() {
+        let item = Item {
+            name: "Plain".to_string(),
+            content: "a, \"b\"\nc".to_string(),
+            ..Item::default()
+        };
+        let line = ExportFormat::Csv.encode(&item);
+        assert!(line.starts_with("default_id,default_hash,Plain,,\"a, \"\"b\"\"\nc\","));
+        assert!(line.ends_with("\r\n"));
+        assert_eq!(
+            ExportFormat::Csv.preamble().split(',').count(),
+            CSV_COLUMNS.len()
+        );
+    }
+
+    #[test]
+    fn test_ndjson_is_one_item_per_line() {
+        let line = ExportFormat::Ndjson.encode(&Item::default());
+        assert_eq!(line.matches('\n').count(), 1);
+        let parsed: Item = serde_json::from_str(line.trim_end()).unwrap();
+        assert_eq!(parsed.id, "default_id");
+        assert!(ExportFormat::Ndjson.preamble().is_empty());
+    }
+}
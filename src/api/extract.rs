@@ -0,0 +1,222 @@
+//! Extractors that report rejections in the standard `ErrorResponse` envelope.
+//!
+//! axum's built-in `Json`, `Query` and `Path` reject with plain-text bodies. These
+//! wrappers delegate to them and map every rejection to a typed error so clients
+//! can handle malformed requests the same way as domain errors.
+
+use axum::{
+    Json,
+    extract::{
+        FromRequest, FromRequestParts, Path, Query, Request,
+        rejection::{JsonRejection, PathRejection, QueryRejection},
+    },
+    http::{StatusCode, request::Parts},
+    response::{IntoResponse, Response},
+};
+use serde::de::DeserializeOwned;
+
+use super::handlers::error_response;
+
+/// Extractor rejection rendered as an `ErrorResponse`
+#[derive(Debug)]
+pub struct ApiRejection {
+    status: StatusCode,
+    error_type: &'static str,
+    message: String,
+}
+
+impl ApiRejection {
+    /// HTTP status returned to the client
+    #[must_use]
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Machine-readable error type (`ErrorDetail::type`)
+    #[must_use]
+    pub fn error_type(&self) -> &'static str {
+        self.error_type
+    }
+}
+
+impl IntoResponse for ApiRejection {
+    fn into_response(self) -> Response {
+        error_response(self.status, self.error_type, self.message)
+    }
+}
+
+impl From<JsonRejection> for ApiRejection {
+    fn from(rejection: JsonRejection) -> Self {
+        let error_type = match &rejection {
+            // Body over the route's limit (see `BodyLimits`)
+            _ if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => "payload_too_large",
+            JsonRejection::JsonSyntaxError(_) => "invalid_json",
+            JsonRejection::JsonDataError(_) => "invalid_body",
+            JsonRejection::MissingJsonContentType(_) => "unsupported_media_type",
+            _ => "invalid_body",
+        };
+        Self {
+            status: rejection.status(),
+            error_type,
+            message: rejection.body_text(),
+        }
+    }
+}
+
+impl From<QueryRejection> for ApiRejection {
+    fn from(rejection: QueryRejection) -> Self {
+        Self {
+            status: rejection.status(),
+            error_type: "invalid_query",
+            message: rejection.body_text(),
+        }
+    }
+}
+
+impl From<PathRejection> for ApiRejection {
+    fn from(rejection: PathRejection) -> Self {
+        Self {
+            status: rejection.status(),
+            error_type: "invalid_path",
+            message: rejection.body_text(),
+        }
+    }
+}
+
+/// JSON body extractor (`axum::Json` with enveloped rejections)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApiJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state).await?;
+        Ok(Self(value))
+    }
+}
+
+/// Query string extractor (`axum::extract::Query` with enveloped rejections)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApiQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ApiQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state).await?;
+        Ok(Self(value))
+    }
+}
+
+/// Path parameter extractor (`axum::extract::Path` with enveloped rejections)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApiPath<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ApiPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = ApiRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(value) = Path::<T>::from_request_parts(parts, state).await?;
+        Ok(Self(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{CreateItemRequest, ErrorResponse, PaginationParams};
+    use axum::{Router, body::Body, routing::get};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    async fn json_endpoint(ApiJson(_): ApiJson<CreateItemRequest>) -> StatusCode {
+        StatusCode::OK
+    }
+
+    async fn query_endpoint(ApiQuery(_): ApiQuery<PaginationParams>) -> StatusCode {
+        StatusCode::OK
+    }
+
+    async fn path_endpoint(ApiPath(_): ApiPath<u32>) -> StatusCode {
+        StatusCode::OK
+    }
+
+    fn router() -> Router {
+        Router::new()
+            .route("/json", axum::routing::post(json_endpoint))
+            .route("/query", get(query_endpoint))
+            .route("/path/{id}", get(path_endpoint))
+    }
+
+    async fn send(request: axum::http::Request<Body>) -> (StatusCode, ErrorResponse) {
+        let response = router().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn post_json(content_type: Option<&str>, body: &'static str) -> axum::http::Request<Body> {
+        let mut builder = axum::http::Request::builder().method("POST").uri("/json");
+        if let Some(content_type) = content_type {
+            builder = builder.header("Content-Type", content_type);
+        }
+        builder.body(Body::from(body)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_json_syntax_error_is_enveloped() {
+        let (status, body) = send(post_json(Some("application/json"), "{not json")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.error.r#type, "invalid_json");
+    }
+
+    #[tokio::test]
+    async fn test_json_data_error_is_enveloped() {
+        let (status, body) = send(post_json(Some("application/json"), r#"{"name":"x"}"#)).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body.error.r#type, "invalid_body");
+        assert!(body.error.message.contains("content"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_content_type_is_enveloped() {
+        let (status, body) = send(post_json(None, r#"{"name":"x","content":"y"}"#)).await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(body.error.r#type, "unsupported_media_type");
+    }
+
+    #[tokio::test]
+    async fn test_query_rejection_is_enveloped() {
+        let request = axum::http::Request::builder()
+            .uri("/query?limit=abc")
+            .body(Body::empty())
+            .unwrap();
+        let (status, body) = send(request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.error.r#type, "invalid_query");
+    }
+
+    #[tokio::test]
+    async fn test_path_rejection_is_enveloped() {
+        let request = axum::http::Request::builder()
+            .uri("/path/not-a-number")
+            .body(Body::empty())
+            .unwrap();
+        let (status, body) = send(request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.error.r#type, "invalid_path");
+    }
+}
@@ -0,0 +1,418 @@
+//! GraphQL endpoint (feature `graphql`).
+//!
+//! Exposes the same use cases as the REST API by calling [`AppService`](crate::app::AppService):
+//! queries are public like `GET /items`, mutations require an API key with the
+//! `items:write` scope like `POST /items`.
+
+use std::sync::Arc;
+
+use async_graphql::{
+    Context, EmptySubscription, Enum, Error, ErrorExtensions, InputObject, Object, Schema,
+    SimpleObject, http::GraphiQLSource,
+};
+use axum::{
+    Extension, Json, Router,
+    extract::State,
+    http::HeaderMap,
+    response::{Html, IntoResponse},
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+
+use super::extract::ApiJson;
+use super::middleware::{MAINTENANCE_MESSAGE, MIGRATIONS_PENDING_MESSAGE, authenticate};
+use super::request_id::current_request_id;
+use crate::app::{AppState, CreateItemError};
+use crate::domain::{
+    ApiKeyScope, CreateItemRequest, HealthResponse, Item, ItemError, ItemListFilter, ItemMetadata,
+    ItemMetadataRequest, Principal,
+};
+
+/// Schema served at `/graphql`
+pub type ApiSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+/// Blockchain submission status
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[graphql(remote = "crate::domain::BlockchainStatus")]
+pub enum BlockchainStatus {
+    Pending,
+    PendingSubmission,
+    Submitted,
+    Confirmed,
+    Failed,
+}
+
+/// Component or overall health
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[graphql(remote = "crate::domain::HealthStatus")]
+pub enum HealthStatus {
+    Healthy,
+    Degraded,
+    Unhealthy,
+    Disabled,
+}
+
+/// Item as exposed over GraphQL (mirrors the REST `Item` schema)
+pub struct ItemObject(Item);
+
+#[Object(name = "Item")]
+impl ItemObject {
+    async fn id(&self) -> &str {
+        &self.0.id
+    }
+
+    async fn hash(&self) -> &str {
+        &self.0.hash
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
+    async fn content(&self) -> &str {
+        &self.0.content
+    }
+
+    /// Metadata as a JSON object (author, version, tags, custom_fields)
+    async fn metadata(&self) -> Option<async_graphql::Json<ItemMetadata>> {
+        self.0.metadata.clone().map(async_graphql::Json)
+    }
+
+    async fn blockchain_status(&self) -> BlockchainStatus {
+        self.0.blockchain_status.into()
+    }
+
+    async fn blockchain_signature(&self) -> Option<&str> {
+        self.0.blockchain_signature.as_deref()
+    }
+
+    async fn blockchain_retry_count(&self) -> i32 {
+        self.0.blockchain_retry_count
+    }
+
+    async fn blockchain_last_error(&self) -> Option<&str> {
+        self.0.blockchain_last_error.as_deref()
+    }
+
+    async fn blockchain_next_retry_at(&self) -> Option<DateTime<Utc>> {
+        self.0.blockchain_next_retry_at
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+}
+
+/// Page of items (same cursor semantics as `GET /items`)
+#[derive(SimpleObject)]
+pub struct ItemPage {
+    items: Vec<ItemObject>,
+    next_cursor: Option<String>,
+    has_more: bool,
+    /// The `after` item no longer exists; the page continues from its position
+    cursor_degraded: bool,
+}
+
+/// Health snapshot (same data as `GET /health`)
+#[derive(SimpleObject)]
+#[graphql(name = "Health")]
+pub struct HealthObject {
+    status: HealthStatus,
+    database: HealthStatus,
+    blockchain: HealthStatus,
+    timestamp: DateTime<Utc>,
+    version: String,
+}
+
+impl From<HealthResponse> for HealthObject {
+    fn from(health: HealthResponse) -> Self {
+        Self {
+            status: health.status.into(),
+            database: health.database.into(),
+            blockchain: health.blockchain.into(),
+            timestamp: health.timestamp,
+            version: health.version,
+        }
+    }
+}
+
+/// Input for `createItem` (validated by the service like `POST /items`)
+#[derive(InputObject)]
+pub struct CreateItemInput {
+    name: String,
+    description: Option<String>,
+    content: String,
+    /// Optional metadata as a JSON object (author, version, tags, custom_fields)
+    metadata: Option<async_graphql::Json<ItemMetadataRequest>>,
+    /// Media type to serve the content with (default `text/plain; charset=utf-8`)
+    content_type: Option<String>,
+}
+
+impl From<CreateItemInput> for CreateItemRequest {
+    fn from(input: CreateItemInput) -> Self {
+        Self {
+            name: input.name,
+            description: input.description,
+            content: input.content,
+            metadata: input.metadata.map(|m| m.0),
+            content_key: None,
+            content_type: input.content_type,
+        }
+    }
+}
+
+/// GraphQL error with the same `type` codes (and `request_id`) as the REST `ErrorResponse`
+fn gql_error(error_type: &'static str, message: impl Into<String>) -> Error {
+    Error::new(message).extend_with(|_, e| {
+        e.set("type", error_type);
+        if let Some(request_id) = current_request_id() {
+            e.set("request_id", request_id);
+        }
+    })
+}
+
+fn item_error(e: ItemError) -> Error {
+    match e {
+        ItemError::NotFound(_) => gql_error("not_found", e.to_string()),
+        ItemError::InvalidState(_) => gql_error("invalid_state", e.to_string()),
+        ItemError::InvalidCursor(_) => gql_error("invalid_cursor", e.to_string()),
+        ItemError::Conflict { .. } => gql_error("conflict", e.to_string()),
+        ItemError::DuplicateContent => gql_error("duplicate_content", e.to_string()),
+        ItemError::RangeNotSatisfiable { .. } => gql_error("range_not_satisfiable", e.to_string()),
+        ItemError::RepositoryFailure => gql_error("repository_error", "Internal server error"),
+    }
+}
+
+fn create_item_error(e: CreateItemError) -> Error {
+    match e {
+        CreateItemError::Validation(e) => gql_error("validation_error", e.to_string()),
+        CreateItemError::Item(e) => item_error(e),
+    }
+}
+
+/// Mutations require a principal with `items:write` (attached by [`graphql_handler`])
+fn require_write(ctx: &Context<'_>) -> Result<(), Error> {
+    match ctx.data_opt::<Principal>() {
+        Some(principal) if principal.has_scope(ApiKeyScope::ItemsWrite) => {
+            // Same rules as the REST schema guard: no writes in maintenance mode or
+            // against a mismatched schema
+            if state(ctx).maintenance_enabled() {
+                Err(gql_error("maintenance", MAINTENANCE_MESSAGE))
+            } else if state(ctx).schema_status.is_current() {
+                Ok(())
+            } else {
+                Err(gql_error("migrations_pending", MIGRATIONS_PENDING_MESSAGE))
+            }
+        }
+        Some(_) => Err(gql_error(
+            "forbidden",
+            "API key lacks the items:write scope",
+        )),
+        None => Err(gql_error("unauthorized", "Missing or invalid API key")),
+    }
+}
+
+fn state<'a>(ctx: &Context<'a>) -> &'a Arc<AppState> {
+    ctx.data_unchecked::<Arc<AppState>>()
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Get a single item by ID
+    async fn item(&self, ctx: &Context<'_>, id: String) -> Result<Option<ItemObject>, Error> {
+        let item = state(ctx).service.get_item(&id).await.map_err(item_error)?;
+        Ok(item.map(ItemObject))
+    }
+
+    /// List items with cursor-based pagination (`first`: 1-100, default 20)
+    async fn items(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> Result<ItemPage, Error> {
+        let limit = i64::from(first.unwrap_or(20)).clamp(1, 100);
+        let page = state(ctx)
+            .service
+            .list_items(limit, after.as_deref(), &ItemListFilter::default())
+            .await
+            .map_err(item_error)?;
+        Ok(ItemPage {
+            items: page.items.into_iter().map(ItemObject).collect(),
+            next_cursor: page.next_cursor,
+            has_more: page.has_more,
+            cursor_degraded: page.cursor_degraded,
+        })
+    }
+
+    /// Dependency health (served from the cached snapshot)
+    async fn health(&self, ctx: &Context<'_>) -> HealthObject {
+        let state = state(ctx);
+        state
+            .service
+            .health_check()
+            .await
+            .with_schema_status(&state.schema_status)
+            .into()
+    }
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    /// Create a new item and enqueue blockchain submission
+    async fn create_item(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateItemInput,
+    ) -> Result<ItemObject, Error> {
+        require_write(ctx)?;
+        let item = state(ctx)
+            .service
+            .create_and_submit_item(&input.into())
+            .await
+            .map_err(create_item_error)?;
+        Ok(ItemObject(item))
+    }
+
+    /// Retry blockchain submission for a failed item
+    async fn retry_blockchain(&self, ctx: &Context<'_>, id: String) -> Result<ItemObject, Error> {
+        require_write(ctx)?;
+        let item = state(ctx)
+            .service
+            .retry_blockchain_submission(&id)
+            .await
+            .map_err(item_error)?;
+        Ok(ItemObject(item))
+    }
+}
+
+/// Build the schema with the shared application state
+#[must_use]
+pub fn build_schema(app_state: Arc<AppState>) -> ApiSchema {
+    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .data(app_state)
+        .finish()
+}
+
+/// Execute a GraphQL request. The API key, if present, is resolved to a principal
+/// so mutations can check scopes; queries work without one.
+pub async fn graphql_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(schema): Extension<ApiSchema>,
+    headers: HeaderMap,
+    ApiJson(request): ApiJson<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    let mut request = request;
+    if headers.contains_key("x-api-key")
+        && let Some(principal) = authenticate(&state, &headers).await
+    {
+        request = request.data(principal);
+    }
+    Json(schema.execute(request).await)
+}
+
+/// GraphiQL playground
+pub async fn graphiql_handler() -> impl IntoResponse {
+    Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
+
+/// `/graphql` routes (GET: GraphiQL, POST: execute)
+pub fn graphql_routes(app_state: Arc<AppState>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(graphiql_handler).post(graphql_handler))
+        .layer(Extension(build_schema(app_state)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::SchemaStatus;
+    use crate::test_utils::{MockBlockchainClient, MockProvider, mock_repos, test_api_key};
+
+    fn app_state() -> AppState {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        AppState::new(item_repo, outbox_repo, bc, test_api_key())
+    }
+
+    fn schema() -> ApiSchema {
+        build_schema(Arc::new(app_state()))
+    }
+
+    const CREATE: &str = r#"mutation { createItem(input: {name: "GQL", content: "body"}) { id name blockchainStatus } }"#;
+
+    #[tokio::test]
+    async fn test_create_item_requires_write_scope() {
+        let response = schema().execute(CREATE).await;
+        assert_eq!(response.errors.len(), 1);
+        let json = serde_json::to_value(&response.errors[0]).unwrap();
+        assert_eq!(json["extensions"]["type"], "unauthorized");
+    }
+
+    #[tokio::test]
+    async fn test_create_then_query_item() {
+        let schema = schema();
+        let request = async_graphql::Request::new(CREATE).data(Principal::bootstrap());
+        let response = schema.execute(request).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["createItem"]["blockchainStatus"], "PENDING_SUBMISSION");
+        let id = data["createItem"]["id"].as_str().unwrap().to_string();
+
+        let query = format!(
+            r#"{{ item(id: "{}") {{ name }} items(first: 10) {{ hasMore items {{ id }} }} }}"#,
+            id
+        );
+        let response = schema.execute(query).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["item"]["name"], "GQL");
+        assert_eq!(data["items"]["items"][0]["id"], id.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_validation_error_is_typed() {
+        let request = async_graphql::Request::new(
+            r#"mutation { createItem(input: {name: "", content: "body"}) { id } }"#,
+        )
+        .data(Principal::bootstrap());
+        let response = schema().execute(request).await;
+        let json = serde_json::to_value(&response.errors[0]).unwrap();
+        assert_eq!(json["extensions"]["type"], "validation_error");
+    }
+
+    #[tokio::test]
+    async fn test_mutation_rejected_while_migrations_pending() {
+        let schema = build_schema(Arc::new(app_state().with_schema_status(SchemaStatus {
+            pending: vec![20990101000000],
+            unknown: Vec::new(),
+        })));
+        let request = async_graphql::Request::new(CREATE).data(Principal::bootstrap());
+        let response = schema.execute(request).await;
+        let json = serde_json::to_value(&response.errors[0]).unwrap();
+        assert_eq!(json["extensions"]["type"], "migrations_pending");
+
+        let response = schema.execute("{ items(first: 10) { hasMore } }").await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+    }
+
+    #[tokio::test]
+    async fn test_health_query() {
+        let response = schema().execute("{ health { status database } }").await;
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["health"]["status"], "HEALTHY");
+    }
+}
@@ -0,0 +1,489 @@
+//! gRPC item service (feature `grpc`).
+//!
+//! Serves `items.v1.ItemService` from `proto/items.proto` on its own port. Like the
+//! GraphQL endpoint it calls [`AppService`](crate::app::AppService), so validation,
+//! pagination and errors match the REST API: reads are public, `CreateItem` requires
+//! an `x-api-key` metadata entry with the `items:write` scope.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use tokio::sync::watch;
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status};
+
+use super::middleware::{MAINTENANCE_MESSAGE, MIGRATIONS_PENDING_MESSAGE, authenticate};
+use crate::app::{AppState, CreateItemError};
+use crate::domain::{
+    ApiKeyScope, AuditActor, BlockchainStatus, CreateItemRequest, Item, ItemError, ItemListFilter,
+    ItemMetadata, ItemMetadataRequest, TenantScope,
+};
+
+/// Types and service traits generated from `proto/items.proto`
+pub mod proto {
+    tonic::include_proto!("items.v1");
+}
+
+use proto::item_service_server::{ItemService, ItemServiceServer};
+
+/// How often `WatchItem` re-reads the watched item
+pub const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+impl From<BlockchainStatus> for proto::BlockchainStatus {
+    fn from(status: BlockchainStatus) -> Self {
+        match status {
+            BlockchainStatus::Pending => Self::Pending,
+            BlockchainStatus::PendingSubmission => Self::PendingSubmission,
+            BlockchainStatus::Submitted => Self::Submitted,
+            BlockchainStatus::Confirmed => Self::Confirmed,
+            BlockchainStatus::Failed => Self::Failed,
+        }
+    }
+}
+
+impl From<ItemMetadata> for proto::ItemMetadata {
+    fn from(metadata: ItemMetadata) -> Self {
+        Self {
+            author: metadata.author,
+            version: metadata.version,
+            tags: metadata.tags,
+            custom_fields: metadata.custom_fields,
+        }
+    }
+}
+
+impl From<proto::ItemMetadata> for ItemMetadataRequest {
+    fn from(metadata: proto::ItemMetadata) -> Self {
+        Self {
+            author: metadata.author,
+            version: metadata.version,
+            tags: metadata.tags,
+            custom_fields: metadata.custom_fields,
+        }
+    }
+}
+
+impl From<Item> for proto::Item {
+    fn from(item: Item) -> Self {
+        Self {
+            id: item.id,
+            hash: item.hash,
+            name: item.name,
+            description: item.description,
+            content: item.content,
+            metadata: item.metadata.map(Into::into),
+            blockchain_status: proto::BlockchainStatus::from(item.blockchain_status).into(),
+            blockchain_signature: item.blockchain_signature,
+            blockchain_retry_count: item.blockchain_retry_count,
+            blockchain_last_error: item.blockchain_last_error,
+            blockchain_next_retry_at: item.blockchain_next_retry_at.map(timestamp),
+            created_at: timestamp(item.created_at),
+            updated_at: timestamp(item.updated_at),
+        }
+    }
+}
+
+impl From<proto::CreateItemRequest> for CreateItemRequest {
+    fn from(request: proto::CreateItemRequest) -> Self {
+        Self {
+            name: request.name,
+            description: request.description,
+            content: request.content,
+            metadata: request.metadata.map(Into::into),
+            content_key: None,
+            content_type: None,
+        }
+    }
+}
+
+fn item_status(e: ItemError) -> Status {
+    match e {
+        ItemError::NotFound(_) => Status::not_found(e.to_string()),
+        ItemError::InvalidState(_) => Status::failed_precondition(e.to_string()),
+        ItemError::InvalidCursor(_) => Status::invalid_argument(e.to_string()),
+        ItemError::Conflict { .. } => Status::aborted(e.to_string()),
+        ItemError::DuplicateContent => Status::already_exists(e.to_string()),
+        ItemError::RangeNotSatisfiable { .. } => Status::out_of_range(e.to_string()),
+        ItemError::RepositoryFailure => Status::internal("Internal server error"),
+    }
+}
+
+fn create_item_status(e: CreateItemError) -> Status {
+    match e {
+        CreateItemError::Validation(e) => Status::invalid_argument(e.to_string()),
+        CreateItemError::Item(e) => item_status(e),
+    }
+}
+
+/// `items.v1.ItemService` backed by the application service
+pub struct ItemGrpcService {
+    state: Arc<AppState>,
+    watch_interval: Duration,
+    /// Ends open `WatchItem` streams when the server shuts down
+    stop: watch::Receiver<bool>,
+}
+
+impl ItemGrpcService {
+    #[must_use]
+    pub fn new(state: Arc<AppState>, stop: watch::Receiver<bool>) -> Self {
+        Self {
+            state,
+            watch_interval: DEFAULT_WATCH_INTERVAL,
+            stop,
+        }
+    }
+
+    #[must_use]
+    pub fn with_watch_interval(mut self, interval: Duration) -> Self {
+        self.watch_interval = interval;
+        self
+    }
+
+    /// Same rules as the REST schema guard and GraphQL mutations; returns the caller's
+    /// tenant and the actor its writes are audited as
+    async fn require_write<T>(
+        &self,
+        request: &Request<T>,
+    ) -> Result<(Option<String>, AuditActor), Status> {
+        let headers = request.metadata().clone().into_headers();
+        match authenticate(&self.state, &headers).await {
+            Some(principal) if principal.has_scope(ApiKeyScope::ItemsWrite) => {
+                if self.state.maintenance_enabled() {
+                    Err(Status::unavailable(MAINTENANCE_MESSAGE))
+                } else if self.state.schema_status.is_current() {
+                    Ok((
+                        TenantScope::for_principal(Some(&principal)),
+                        AuditActor::for_principal(Some(&principal), None),
+                    ))
+                } else {
+                    Err(Status::unavailable(MIGRATIONS_PENDING_MESSAGE))
+                }
+            }
+            Some(_) => Err(Status::permission_denied(
+                "API key lacks the items:write scope",
+            )),
+            None => Err(Status::unauthenticated("Missing or invalid API key")),
+        }
+    }
+
+    /// Tenant a read is confined to, decided like the REST tenant middleware
+    async fn read_tenant<T>(&self, request: &Request<T>) -> Option<String> {
+        let principal = if request.metadata().contains_key("x-api-key") {
+            let headers = request.metadata().clone().into_headers();
+            authenticate(&self.state, &headers).await
+        } else {
+            None
+        };
+        TenantScope::for_principal(principal.as_ref())
+    }
+
+    async fn find_item(&self, id: &str) -> Result<Item, Status> {
+        self.state
+            .service
+            .get_item(id)
+            .await
+            .map_err(item_status)?
+            .ok_or_else(|| Status::not_found(format!("Item not found: {id}")))
+    }
+}
+
+type ItemStream = Pin<Box<dyn Stream<Item = Result<proto::Item, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl ItemService for ItemGrpcService {
+    async fn create_item(
+        &self,
+        request: Request<proto::CreateItemRequest>,
+    ) -> Result<Response<proto::Item>, Status> {
+        let (tenant, actor) = self.require_write(&request).await?;
+        let item = TenantScope::scope(
+            tenant,
+            AuditActor::scope(
+                actor,
+                self.state
+                    .service
+                    .create_and_submit_item(&request.into_inner().into()),
+            ),
+        )
+        .await
+        .map_err(create_item_status)?;
+        Ok(Response::new(item.into()))
+    }
+
+    async fn get_item(
+        &self,
+        request: Request<proto::GetItemRequest>,
+    ) -> Result<Response<proto::Item>, Status> {
+        let tenant = self.read_tenant(&request).await;
+        let item = TenantScope::scope(tenant, self.find_item(&request.into_inner().id)).await?;
+        Ok(Response::new(item.into()))
+    }
+
+    async fn list_items(
+        &self,
+        request: Request<proto::ListItemsRequest>,
+    ) -> Result<Response<proto::ListItemsResponse>, Status> {
+        let tenant = self.read_tenant(&request).await;
+        let request = request.into_inner();
+        let limit = i64::from(request.limit.unwrap_or(20)).clamp(1, 100);
+        let page = TenantScope::scope(
+            tenant,
+            self.state.service.list_items(
+                limit,
+                request.cursor.as_deref(),
+                &ItemListFilter::default(),
+            ),
+        )
+        .await
+        .map_err(item_status)?;
+        Ok(Response::new(proto::ListItemsResponse {
+            items: page.items.into_iter().map(Into::into).collect(),
+            next_cursor: page.next_cursor,
+            has_more: page.has_more,
+            cursor_degraded: page.cursor_degraded,
+        }))
+    }
+
+    type WatchItemStream = ItemStream;
+
+    /// The item now, then again whenever `updated_at` moves. The stream ends once the
+    /// item is confirmed or deleted, or when the server shuts down.
+    async fn watch_item(
+        &self,
+        request: Request<proto::GetItemRequest>,
+    ) -> Result<Response<Self::WatchItemStream>, Status> {
+        let tenant = self.read_tenant(&request).await;
+        let id = request.into_inner().id;
+        let first = TenantScope::scope(tenant.clone(), self.find_item(&id)).await?;
+        let state = Arc::clone(&self.state);
+        let period = self.watch_interval;
+        let mut stop = self.stop.clone();
+
+        let stream = async_stream::try_stream! {
+            let mut last_update = first.updated_at;
+            let mut done = first.blockchain_status == BlockchainStatus::Confirmed;
+            yield proto::Item::from(first);
+
+            let mut ticker = tokio::time::interval(period);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            ticker.tick().await;
+            while !done {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = stop.wait_for(|stop| *stop) => break,
+                }
+                let poll = state.service.get_item(&id);
+                let Some(item) = TenantScope::scope(tenant.clone(), poll)
+                    .await
+                    .map_err(item_status)?
+                else {
+                    break;
+                };
+                if item.updated_at != last_update {
+                    last_update = item.updated_at;
+                    done = item.blockchain_status == BlockchainStatus::Confirmed;
+                    yield proto::Item::from(item);
+                }
+            }
+        };
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// Build the tonic service; `stop` ends open `WatchItem` streams on shutdown
+#[must_use]
+pub fn grpc_service(
+    state: Arc<AppState>,
+    stop: watch::Receiver<bool>,
+    watch_interval: Duration,
+) -> ItemServiceServer<ItemGrpcService> {
+    ItemServiceServer::new(ItemGrpcService::new(state, stop).with_watch_interval(watch_interval))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::SchemaStatus;
+    use crate::test_utils::{MockBlockchainClient, MockProvider, mock_repos, test_api_key};
+    use chrono::SubsecRound;
+    use secrecy::ExposeSecret;
+    use tokio_stream::StreamExt;
+
+    fn app_state() -> AppState {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        AppState::new(item_repo, outbox_repo, bc, test_api_key())
+    }
+
+    fn service(state: AppState) -> (ItemGrpcService, watch::Sender<bool>) {
+        let (stop, stop_rx) = watch::channel(false);
+        let service = ItemGrpcService::new(Arc::new(state), stop_rx)
+            .with_watch_interval(Duration::from_millis(10));
+        (service, stop)
+    }
+
+    fn create_request(name: &str, api_key: Option<&str>) -> Request<proto::CreateItemRequest> {
+        let mut request = Request::new(proto::CreateItemRequest {
+            name: name.to_string(),
+            description: None,
+            content: "body".to_string(),
+            metadata: Some(proto::ItemMetadata {
+                author: Some("Ada".to_string()),
+                tags: vec!["grpc".to_string()],
+                ..Default::default()
+            }),
+        });
+        if let Some(key) = api_key {
+            request
+                .metadata_mut()
+                .insert("x-api-key", key.parse().unwrap());
+        }
+        request
+    }
+
+    fn api_key() -> String {
+        test_api_key().expose_secret().to_string()
+    }
+
+    #[test]
+    fn test_item_conversion() {
+        let mut item = Item::new(
+            "item_1".to_string(),
+            "hash".to_string(),
+            "Name".to_string(),
+            "content".to_string(),
+        );
+        item.blockchain_status = BlockchainStatus::Submitted;
+        let proto_item = proto::Item::from(item.clone());
+        assert_eq!(proto_item.id, "item_1");
+        assert_eq!(
+            proto_item.blockchain_status(),
+            proto::BlockchainStatus::Submitted
+        );
+        assert_eq!(
+            DateTime::parse_from_rfc3339(&proto_item.created_at).unwrap(),
+            item.created_at.trunc_subsecs(3)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_item_requires_api_key() {
+        let (service, _stop) = service(app_state());
+        let status = service
+            .create_item(create_request("gRPC", None))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[tokio::test]
+    async fn test_create_get_and_list() {
+        let (service, _stop) = service(app_state());
+        let created = service
+            .create_item(create_request("gRPC", Some(&api_key())))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(
+            created.blockchain_status(),
+            proto::BlockchainStatus::PendingSubmission
+        );
+        assert_eq!(created.metadata.unwrap().tags, vec!["grpc"]);
+
+        let fetched = service
+            .get_item(Request::new(proto::GetItemRequest {
+                id: created.id.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(fetched.name, "gRPC");
+
+        let page = service
+            .list_items(Request::new(proto::ListItemsRequest {
+                limit: Some(10),
+                cursor: None,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(page.items[0].id, created.id);
+        assert!(!page.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_errors_map_to_status_codes() {
+        let (service, _stop) = service(app_state());
+        let status = service
+            .get_item(Request::new(proto::GetItemRequest {
+                id: "item_missing".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+
+        let status = service
+            .create_item(create_request("", Some(&api_key())))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let (service, _stop) = self::service(app_state().with_schema_status(SchemaStatus {
+            pending: vec![20990101000000],
+            unknown: Vec::new(),
+        }));
+        let status = service
+            .create_item(create_request("gRPC", Some(&api_key())))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+    }
+
+    #[tokio::test]
+    async fn test_watch_item_streams_changes_until_shutdown() {
+        let state = app_state();
+        let outbox_repo = Arc::clone(&state.outbox_repo);
+        let (service, stop) = service(state);
+        let created = service
+            .create_item(create_request("gRPC", Some(&api_key())))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let mut stream = service
+            .watch_item(Request::new(proto::GetItemRequest {
+                id: created.id.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(first.id, created.id);
+
+        outbox_repo
+            .update_blockchain_status(
+                &created.id,
+                BlockchainStatus::Submitted,
+                Some("sig"),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let update = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            update.blockchain_status(),
+            proto::BlockchainStatus::Submitted
+        );
+
+        stop.send(true).unwrap();
+        assert!(stream.next().await.is_none());
+    }
+}
@@ -4,17 +4,33 @@ use std::sync::Arc;
 
 use axum::{
     Json,
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{Multipart, State},
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode, header},
     response::IntoResponse,
 };
-use tracing::error;
+use chrono::Utc;
+use futures::{StreamExt, TryStreamExt, stream};
+use tracing::{error, info};
 use utoipa::OpenApi;
 
-use crate::app::{AppState, CreateItemError};
+use super::examples::ApiExamples;
+use super::extract::{ApiJson, ApiPath, ApiQuery};
+use super::request_id::current_request_id;
+use super::security::DEFAULT_API_SECURITY;
+use crate::app::IpBlocklist;
+use crate::app::api_keys::{IssueApiKeyError, issue_api_key, rotate_api_key};
+use crate::app::{AppState, ContentBody, CreateItemError, StartJobError, VerifyItemError};
 use crate::domain::{
-    BlockchainError, CreateItemRequest, ErrorDetail, ErrorResponse, HealthResponse, HealthStatus,
-    Item, ItemError, PaginatedResponse, PaginationParams, RateLimitResponse, ValidationError,
+    ApiKey, ApiKeyError, ApiKeyStore, AuditEntry, AuditParams, BlockchainError, BlocklistResponse,
+    CreateApiKeyRequest, CreateApiKeyResponse, CreateItemParams, CreateItemRequest,
+    DeadLetterParams, DedupeMode, DependencyHealth, ErrorDetail, ErrorResponse, ExportBookmark,
+    ExportFormat, ExportParams, FailedSubmission, FieldError, HealthResponse, HealthStatus,
+    ImportReport, ImportUpload, Item, ItemError, ItemPosition, ItemSortField, ItemStatusEvent,
+    ItemSummary, ItemTimeline, ItemVerification, ItemView, Job, JobError, LogPageParams,
+    MaintenanceMode, NotificationError, PaginatedResponse, PaginationParams, QueueDepth, RangeSpec,
+    RateLimitResponse, ReceiptVerification, RequestJournalError, SearchParams, SearchResponse,
+    SortOrder, SubmissionAttempt, TagCount, TemporaryBan, UpdateBlocklistRequest, UsageParams,
+    UsageReport, ValidationError, VerifyReceiptRequest, WebhookDelivery, WorkerError, WorkerStatus,
 };
 
 /// OpenAPI documentation structure
@@ -35,11 +51,48 @@ use crate::domain::{
     paths(
         create_item_handler,
         list_items_handler,
+        search_items_handler,
+        list_tags_handler,
+        export_items_handler,
+        acknowledge_export_bookmark_handler,
+        import_items_handler,
         get_item_handler,
+        get_item_content_handler,
+        update_item_handler,
+        delete_item_handler,
         retry_blockchain_handler,
+        verify_item_handler,
+        list_submission_attempts_handler,
+        item_timeline_handler,
         health_check_handler,
+        deep_health_handler,
         liveness_handler,
         readiness_handler,
+        get_blocklist_handler,
+        update_blocklist_handler,
+        list_bans_handler,
+        lift_ban_handler,
+        create_api_key_handler,
+        list_api_keys_handler,
+        revoke_api_key_handler,
+        rotate_api_key_handler,
+        get_usage_handler,
+        get_queue_depth_handler,
+        get_maintenance_handler,
+        set_maintenance_handler,
+        get_worker_status_handler,
+        run_worker_now_handler,
+        list_dead_letters_handler,
+        requeue_dead_letter_handler,
+        requeue_all_dead_letters_handler,
+        reencrypt_content_handler,
+        list_item_events_handler,
+        list_webhook_deliveries_handler,
+        list_audit_entries_handler,
+        get_job_handler,
+        verify_receipt_handler,
+        super::idempotency::get_request_status_handler,
+        super::ws::live_feed_handler,
     ),
     components(
         schemas(
@@ -47,19 +100,76 @@ use crate::domain::{
             CreateItemRequest,
             crate::domain::ItemMetadata,
             crate::domain::ItemMetadataRequest,
+            ImportUpload,
+            ImportReport,
+            crate::domain::ImportLineResult,
             crate::domain::BlockchainStatus,
             PaginationParams,
-            PaginatedResponse<Item>,
+            ItemSortField,
+            SortOrder,
+            DedupeMode,
+            PaginatedResponse<ItemSummary>,
+            ItemSummary,
+            ItemView,
+            SearchParams,
+            SearchResponse,
+            crate::domain::ItemSearchHit,
+            TagCount,
+            crate::domain::ExportFormat,
+            ExportBookmark,
+            ItemPosition,
             HealthResponse,
+            DependencyHealth,
             HealthStatus,
             ErrorResponse,
             ErrorDetail,
+            FieldError,
             RateLimitResponse,
+            BlocklistResponse,
+            UpdateBlocklistRequest,
+            TemporaryBan,
+            WorkerStatus,
+            QueueDepth,
+            MaintenanceMode,
+            FailedSubmission,
+            DeadLetterParams,
+            ApiKey,
+            crate::domain::ApiKeyScope,
+            CreateApiKeyRequest,
+            CreateApiKeyResponse,
+            crate::domain::ApiKeyQuota,
+            crate::domain::KeyUsage,
+            UsageReport,
+            UsageParams,
+            crate::domain::JournalStatus,
+            crate::domain::RequestStatusResponse,
+            Job,
+            crate::domain::JobStatus,
+            LogPageParams,
+            ItemStatusEvent,
+            PaginatedResponse<ItemStatusEvent>,
+            WebhookDelivery,
+            PaginatedResponse<WebhookDelivery>,
+            AuditParams,
+            AuditEntry,
+            PaginatedResponse<AuditEntry>,
+            VerifyReceiptRequest,
+            ReceiptVerification,
+            crate::domain::IssuerKeyStatus,
+            ItemVerification,
+            SubmissionAttempt,
+            ItemTimeline,
+            crate::domain::TimelineEntry,
+            crate::domain::TimelineEntryKind,
         )
     ),
+    modifiers(&ApiExamples, &DEFAULT_API_SECURITY),
     tags(
         (name = "items", description = "Item management endpoints"),
-        (name = "health", description = "Health check endpoints")
+        (name = "health", description = "Health check endpoints"),
+        (name = "admin", description = "Operational endpoints (API key required for every method)"),
+        (name = "jobs", description = "Status of long-running operations started with `202 Accepted`"),
+        (name = "verify", description = "Checks external systems can run without an API key")
     )
 )]
 pub struct ApiDoc;
@@ -70,152 +180,1248 @@ pub struct ApiDoc;
     path = "/items",
     tag = "items",
     request_body = CreateItemRequest,
+    params(
+        ("dedupe" = Option<DedupeMode>, Query, description = "Content matching a live item: `reject` (default, `409` with `ITEM_HASH_UNIQUE`) or `return_existing`")
+    ),
+    responses(
+        (status = 200, description = "Item created successfully, or the existing item with `dedupe=return_existing`", body = Item),
+        (status = 400, description = "Validation error or malformed JSON", body = ErrorResponse),
+        (status = 409, description = "Same content as a live item (`duplicate_content`, with `ITEM_HASH_UNIQUE`)", body = ErrorResponse),
+        (status = 413, description = "Body larger than `BODY_LIMIT_ITEMS_BYTES`", body = ErrorResponse),
+        (status = 415, description = "Missing `application/json` content type", body = ErrorResponse),
+        (status = 422, description = "JSON does not match the request schema", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+        (status = 503, description = "Service unavailable", body = ErrorResponse)
+    )
+)]
+pub async fn create_item_handler(
+    State(state): State<Arc<AppState>>,
+    ApiQuery(params): ApiQuery<CreateItemParams>,
+    ApiJson(payload): ApiJson<CreateItemRequest>,
+) -> Result<Json<Item>, CreateItemError> {
+    let item = state
+        .service
+        .create_item_with_dedupe(&payload, params.dedupe)
+        .await?;
+    Ok(Json(item))
+}
+
+/// List items with pagination
+///
+/// Pages by cursor (`limit`, `cursor`) unless `page` or `per_page` is given: then by page
+/// number, with `total` counting every matching item (estimated on very large results,
+/// flagged by `total_estimated`) and no `next_cursor`.
+#[utoipa::path(
+    get,
+    path = "/items",
+    tag = "items",
+    params(
+        ("limit" = Option<i64>, Query, description = "Maximum number of items to return (1-100, default: 20)"),
+        ("cursor" = Option<String>, Query, description = "Opaque `next_cursor` of the previous page"),
+        ("include_deleted" = Option<bool>, Query, description = "Include soft-deleted items (requires the `admin` scope)"),
+        ("blockchain_status" = Option<crate::domain::BlockchainStatus>, Query, description = "Only items with this blockchain status"),
+        ("tag" = Option<String>, Query, description = "Only items whose metadata tags contain this tag"),
+        ("author" = Option<String>, Query, description = "Only items whose metadata author matches exactly"),
+        ("created_after" = Option<String>, Query, format = DateTime, description = "Only items created at or after this time (RFC 3339)"),
+        ("created_before" = Option<String>, Query, format = DateTime, description = "Only items created before this time (RFC 3339)"),
+        ("sort" = Option<ItemSortField>, Query, description = "Sort field (default: created_at)"),
+        ("order" = Option<SortOrder>, Query, description = "Sort direction (default: desc)"),
+        ("view" = Option<ItemView>, Query, description = "`summary` (default): items without `content`; `full`: whole items"),
+        ("fields" = Option<String>, Query, description = "Comma-separated item fields to return instead of a view, e.g. `id,name,blockchain_status`"),
+        ("page" = Option<i64>, Query, description = "Page number from 1; switches to page-number mode (cannot be combined with `cursor`)"),
+        ("per_page" = Option<i64>, Query, description = "Items per page in page-number mode (1-100, default: `limit`)")
+    ),
+    responses(
+        (status = 200, description = "List of items: summaries by default, whole items with `view=full`, or objects with only the `fields` asked for", body = PaginatedResponse<ItemSummary>,
+            headers(("ETag" = String, description = "Hash of the page, for `If-None-Match`"))),
+        (status = 304, description = "Page unchanged since the `If-None-Match` tag"),
+        (status = 400, description = "Invalid pagination parameters, unknown field (`invalid_query`), tampered cursor (`invalid_cursor`) or `cursor` with `page` (`invalid_state`)", body = ErrorResponse),
+        (status = 401, description = "`include_deleted` set without an API key"),
+        (status = 403, description = "`include_deleted` set and the API key lacks the admin scope"),
+        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn list_items_handler(
+    State(state): State<Arc<AppState>>,
+    ApiQuery(params): ApiQuery<PaginationParams>,
+) -> Result<Json<PaginatedResponse<serde_json::Value>>, ItemError> {
+    let items = match params.page_request() {
+        Some(_) if params.cursor.is_some() => {
+            return Err(ItemError::InvalidState(
+                "`cursor` cannot be combined with `page` or `per_page`".to_string(),
+            ));
+        }
+        Some((page, per_page)) => {
+            state
+                .service
+                .list_items_page(page, per_page, &params.filter())
+                .await?
+        }
+        None => {
+            // Validate limit
+            let limit = params.limit.clamp(1, 100);
+            state
+                .service
+                .list_items(limit, params.cursor.as_deref(), &params.filter())
+                .await?
+        }
+    };
+    Ok(Json(items.map(|item| params.project(item))))
+}
+
+/// Full-text search over item name, description and content
+#[utoipa::path(
+    get,
+    path = "/items/search",
+    tag = "items",
+    params(
+        ("q" = String, Query, description = "Search terms (web search syntax: quoted phrases, `or`, `-excluded`; max 200 characters)"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of results (1-100, default: 20)")
+    ),
+    responses(
+        (status = 200, description = "Matches ordered by relevance", body = SearchResponse,
+            headers(("ETag" = String, description = "Hash of the results, for `If-None-Match`"))),
+        (status = 304, description = "Results unchanged since the `If-None-Match` tag"),
+        (status = 400, description = "Missing, empty or overlong query", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn search_items_handler(
+    State(state): State<Arc<AppState>>,
+    ApiQuery(params): ApiQuery<SearchParams>,
+) -> Result<Json<SearchResponse>, ItemError> {
+    let results = state.service.search_items(&params.q, params.limit).await?;
+    Ok(Json(results))
+}
+
+/// List metadata tags with usage counts
+///
+/// Every tag on a live item visible to the caller, with the number of items carrying it,
+/// most used first. Use a tag with `GET /items?tag=` to list its items.
+#[utoipa::path(
+    get,
+    path = "/tags",
+    tag = "items",
+    responses(
+        (status = 200, description = "Tags, most used first (ties by tag)", body = Vec<TagCount>),
+        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn list_tags_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<TagCount>>, ItemError> {
+    let tags = state.service.list_tags().await?;
+    Ok(Json(tags))
+}
+
+/// Stream every live item as NDJSON or CSV
+#[utoipa::path(
+    get,
+    path = "/items/export",
+    tag = "items",
+    params(
+        ("format" = Option<ExportFormat>, Query, description = "`ndjson` (default, one JSON item per line) or `csv` (header row first)"),
+        ("bookmark" = Option<String>, Query, description = "Named bookmark: only items changed since its acknowledged position, in `(updated_at, id)` order (registered on first use)")
+    ),
+    responses(
+        (status = 200, description = "Every live item, oldest first (or changed since the bookmark), streamed as it is read",
+            content(
+                (String = "application/x-ndjson"),
+                (String = "text/csv")
+            )
+        ),
+        (status = 400, description = "Unknown format or invalid bookmark name", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn export_items_handler(
+    State(state): State<Arc<AppState>>,
+    ApiQuery(params): ApiQuery<ExportParams>,
+) -> Result<axum::response::Response, ItemError> {
+    let format = params.format;
+    let mut items = match &params.bookmark {
+        Some(name) => state.service.export_item_changes(name).await?,
+        None => state.service.export_items(),
+    };
+    // Failing before the first item still gets a proper error response; later failures
+    // can only abort the body, which clients see as a truncated download
+    let first = items.next().await.transpose()?;
+    let lines = stream::iter(first.map(Ok))
+        .chain(items)
+        .inspect_err(|e| error!(error = %e, "Item export failed mid-stream"))
+        .map_ok(move |item| format.encode(&item));
+    let body = stream::once(async move { Ok(format.preamble()) }).chain(lines);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", format.file_name()),
+            ),
+        ],
+        axum::body::Body::from_stream(body),
+    )
+        .into_response())
+}
+
+/// Acknowledge exported items: the bookmark's next export starts after `position`
+#[utoipa::path(
+    post,
+    path = "/items/export/bookmarks/{name}/ack",
+    tag = "items",
+    params(
+        ("name" = String, Path, description = "Bookmark name")
+    ),
+    request_body = ItemPosition,
+    responses(
+        (status = 200, description = "Bookmark after the acknowledgment (never moved backwards)", body = ExportBookmark),
+        (status = 400, description = "Invalid bookmark name or position", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the items:read scope"),
+        (status = 404, description = "Bookmark was never used for an export", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse)
+    )
+)]
+pub async fn acknowledge_export_bookmark_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(name): ApiPath<String>,
+    ApiJson(position): ApiJson<ItemPosition>,
+) -> Result<Json<ExportBookmark>, ItemError> {
+    let bookmark = state
+        .service
+        .acknowledge_export_bookmark(&name, &position)
+        .await?;
+    Ok(Json(bookmark))
+}
+
+/// Import items from an NDJSON or CSV upload
+#[utoipa::path(
+    post,
+    path = "/items/import",
+    tag = "items",
+    request_body(content = ImportUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Per-line report; valid rows were imported even if others failed", body = ImportReport),
+        (status = 400, description = "Missing `file` part, undecodable file or too many rows", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the items:write scope"),
+        (status = 413, description = "Upload larger than `BODY_LIMIT_IMPORT_BYTES`", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse)
+    )
+)]
+pub async fn import_items_handler(
+    State(state): State<Arc<AppState>>,
+    mut multipart: Multipart,
+) -> Result<Json<ImportReport>, CreateItemError> {
+    let invalid = |message: String| ValidationError::InvalidField {
+        field: "file".to_string(),
+        message,
+    };
+    let (format, bytes) = loop {
+        let field = multipart
+            .next_field()
+            .await
+            .map_err(|e| invalid(e.body_text()))?
+            .ok_or_else(|| invalid("Missing multipart `file` part".to_string()))?;
+        if field.name() == Some("file") {
+            let format = ExportFormat::detect(field.content_type(), field.file_name());
+            let bytes = field.bytes().await.map_err(|e| invalid(e.body_text()))?;
+            break (format, bytes);
+        }
+    };
+    let text =
+        std::str::from_utf8(&bytes).map_err(|_| invalid("File must be UTF-8".to_string()))?;
+    let rows = format.decode(text)?;
+
+    let report = state.service.import_items(rows).await?;
+    Ok(Json(report))
+}
+
+/// Get a single item by ID
+#[utoipa::path(
+    get,
+    path = "/items/{id}",
+    tag = "items",
+    params(
+        ("id" = String, Path, description = "Item ID")
+    ),
+    responses(
+        (status = 200, description = "Item found", body = Item,
+            headers(("ETag" = String, description = "Item version, to send back in `If-Match` when updating"))),
+        (status = 304, description = "Item unchanged since the version in `If-None-Match`"),
+        (status = 404, description = "Item not found", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_item_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(id): ApiPath<String>,
+) -> Result<([(HeaderName, String); 1], Json<Item>), ItemError> {
+    let item = state
+        .service
+        .get_item(&id)
+        .await?
+        .ok_or(ItemError::NotFound(id))?;
+    Ok(([(header::ETAG, item.etag())], Json(item)))
+}
+
+/// Download an item's raw content
+///
+/// Served with the `content_type` the item was created with and as an attachment named
+/// after the item. Large content is kept in the object store and only its key in the item
+/// (`content` is then empty and `content_key` set); this endpoint returns the content
+/// either way, streaming it from the object store as it is read.
+///
+/// A single byte range (`Range: bytes=0-1023`, `bytes=1024-` or `bytes=-1024`) is answered
+/// with `206` and `Content-Range`; a range past the end with `416`. Other `Range` headers
+/// are ignored, as are ranges on content offloaded before sizes were recorded.
+#[utoipa::path(
+    get,
+    path = "/items/{id}/content",
+    tag = "items",
+    params(
+        ("id" = String, Path, description = "Item ID"),
+        ("Range" = Option<String>, Header, description = "Byte range to return, e.g. `bytes=0-1023`")
+    ),
+    responses(
+        (status = 200, description = "Item content", content_type = "application/octet-stream", body = String),
+        (status = 206, description = "Requested range of the content", content_type = "application/octet-stream", body = String),
+        (status = 404, description = "Item not found", body = ErrorResponse),
+        (status = 416, description = "Range outside the content", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
+        (status = 500, description = "Internal server error or object store unavailable", body = ErrorResponse)
+    )
+)]
+pub async fn get_item_content_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(id): ApiPath<String>,
+    headers: HeaderMap,
+) -> Result<axum::response::Response, ItemError> {
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(RangeSpec::parse);
+    let content = state.service.get_item_content(&id, range).await?;
+
+    let mut response_headers = HeaderMap::new();
+    let disposition = format!(
+        "attachment; filename=\"{id}{}\"",
+        content_file_extension(&content.content_type)
+    );
+    for (name, value) in [
+        (header::CONTENT_TYPE, content.content_type),
+        (header::CONTENT_DISPOSITION, disposition),
+    ] {
+        if let Ok(value) = HeaderValue::try_from(value) {
+            response_headers.insert(name, value);
+        }
+    }
+    let status = match (content.length, &content.range) {
+        (Some(length), Some(range)) => {
+            response_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+            response_headers.insert(
+                header::CONTENT_LENGTH,
+                HeaderValue::from(range.end - range.start),
+            );
+            let content_range = format!("bytes {}-{}/{length}", range.start, range.end - 1);
+            if let Ok(value) = HeaderValue::try_from(content_range) {
+                response_headers.insert(header::CONTENT_RANGE, value);
+            }
+            StatusCode::PARTIAL_CONTENT
+        }
+        (Some(length), None) => {
+            response_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+            response_headers.insert(header::CONTENT_LENGTH, HeaderValue::from(length));
+            StatusCode::OK
+        }
+        (None, _) => StatusCode::OK,
+    };
+
+    let body = match content.body {
+        ContentBody::Inline(content) => axum::body::Body::from(content),
+        // A failure after the first chunk can only abort the body
+        ContentBody::Stream(chunks) => axum::body::Body::from_stream(
+            chunks.inspect_err(|e| error!(error = %e, "Item content stream failed")),
+        ),
+    };
+    Ok((status, response_headers, body).into_response())
+}
+
+/// File name extension of downloaded content, from its media type
+fn content_file_extension(content_type: &str) -> &'static str {
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+    match media_type.to_ascii_lowercase().as_str() {
+        "text/plain" => ".txt",
+        "text/markdown" => ".md",
+        "text/csv" => ".csv",
+        "text/html" => ".html",
+        "application/json" => ".json",
+        "application/xml" | "text/xml" => ".xml",
+        _ => "",
+    }
+}
+
+/// Message of updates rejected for a missing or malformed `If-Match`
+pub(crate) const PRECONDITION_REQUIRED_MESSAGE: &str =
+    "Updates require an If-Match header with the item's version (its ETag)";
+
+/// Update an item's name, description, content and metadata
+///
+/// Optimistic concurrency: `If-Match` must carry the version the change is based on (the
+/// `ETag` of `GET /items/{id}`). If the item was updated since, nothing is written and the
+/// response is `409 conflict`; read it again and reapply the change.
+#[utoipa::path(
+    put,
+    path = "/items/{id}",
+    tag = "items",
+    request_body = CreateItemRequest,
+    params(
+        ("id" = String, Path, description = "Item ID"),
+        ("If-Match" = String, Header, description = "Version the update is based on, e.g. `\"3\"`")
+    ),
+    responses(
+        (status = 200, description = "Item updated", body = Item,
+            headers(("ETag" = String, description = "New item version"))),
+        (status = 400, description = "Validation error or malformed JSON", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the items:write scope"),
+        (status = 404, description = "Item not found or deleted", body = ErrorResponse),
+        (status = 409, description = "Item was updated since the version in `If-Match`", body = ErrorResponse),
+        (status = 428, description = "Missing or malformed `If-Match`", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn update_item_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(id): ApiPath<String>,
+    headers: HeaderMap,
+    ApiJson(payload): ApiJson<CreateItemRequest>,
+) -> Result<([(HeaderName, String); 1], Json<Item>), axum::response::Response> {
+    let expected_version = if_match_version(&headers).ok_or_else(|| {
+        error_response(
+            StatusCode::PRECONDITION_REQUIRED,
+            "precondition_required",
+            PRECONDITION_REQUIRED_MESSAGE.to_string(),
+        )
+    })?;
+    let item = state
+        .service
+        .update_item(&id, &payload, expected_version)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    Ok(([(header::ETAG, item.etag())], Json(item)))
+}
+
+/// Version named by an `If-Match` header: a single strong entity tag such as `"3"`
+fn if_match_version(headers: &HeaderMap) -> Option<i64> {
+    let value = headers.get(header::IF_MATCH)?.to_str().ok()?.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+        .parse()
+        .ok()
+}
+
+/// Soft-delete an item (hidden from reads, purged after the retention window)
+#[utoipa::path(
+    delete,
+    path = "/items/{id}",
+    tag = "items",
+    params(
+        ("id" = String, Path, description = "Item ID")
+    ),
+    responses(
+        (status = 200, description = "Item soft-deleted", body = Item),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the items:write scope"),
+        (status = 404, description = "Item not found or already deleted", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn delete_item_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(id): ApiPath<String>,
+) -> Result<Json<Item>, ItemError> {
+    let item = state.service.delete_item(&id).await?;
+    Ok(Json(item))
+}
+
+/// Retry blockchain submission for an item
+#[utoipa::path(
+    post,
+    path = "/items/{id}/retry",
+    tag = "items",
+    params(
+        ("id" = String, Path, description = "Item ID")
+    ),
+    responses(
+        (status = 200, description = "Retry successful", body = Item),
+        (status = 400, description = "Item not eligible for retry", body = ErrorResponse),
+        (status = 404, description = "Item not found", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn retry_blockchain_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(id): ApiPath<String>,
+) -> Result<Json<Item>, ItemError> {
+    let item = state.service.retry_blockchain_submission(&id).await?;
+    Ok(Json(item))
+}
+
+/// Check an item against the chain
+///
+/// Recomputes the item's content hash and the hash it anchors, reads its transaction back
+/// and compares the anchored memo. `verified` is true when the content is unchanged and
+/// its hash is on-chain; `confirmation_depth` counts the slots (or blocks) since it landed.
+#[utoipa::path(
+    get,
+    path = "/items/{id}/verify",
+    tag = "items",
+    params(
+        ("id" = String, Path, description = "Item ID")
+    ),
+    responses(
+        (status = 200, description = "Verification report", body = ItemVerification),
+        (status = 400, description = "Blockchain submission is disabled", body = ErrorResponse),
+        (status = 404, description = "Item not found", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+        (status = 503, description = "The transaction could not be read from the chain", body = ErrorResponse)
+    )
+)]
+pub async fn verify_item_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(id): ApiPath<String>,
+) -> Result<Json<ItemVerification>, VerifyItemError> {
+    let verification = state.service.verify_item(&id).await?;
+    Ok(Json(verification))
+}
+
+/// Submission attempt history of an item
+///
+/// Every blockchain submission of the item, oldest first: when it ran, how long it took,
+/// the RPC endpoint that handled it (and any it failed over from) and how it ended.
+/// Submissions held back by an open circuit breaker never reach an endpoint and are not
+/// listed.
+#[utoipa::path(
+    get,
+    path = "/items/{id}/attempts",
+    tag = "items",
+    params(
+        ("id" = String, Path, description = "Item ID")
+    ),
+    responses(
+        (status = 200, description = "Submission attempts, oldest first", body = Vec<SubmissionAttempt>),
+        (status = 404, description = "Item not found", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn list_submission_attempts_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(id): ApiPath<String>,
+) -> Result<Json<Vec<SubmissionAttempt>>, ItemError> {
+    let attempts = state.service.list_submission_attempts(&id).await?;
+    Ok(Json(attempts))
+}
+
+/// Lifecycle timeline of an item
+///
+/// Creation, every submission attempt, every notified status change and every webhook
+/// delivery of the item in one list, oldest first. Each entry has a `kind` and the detail
+/// object for that kind (`attempt`, `event` or `delivery`). Status changes and deliveries
+/// come from the event and delivery logs and are left out on instances without them.
+#[utoipa::path(
+    get,
+    path = "/items/{id}/timeline",
+    tag = "items",
+    params(
+        ("id" = String, Path, description = "Item ID")
+    ),
+    responses(
+        (status = 200, description = "Item lifecycle, oldest first", body = ItemTimeline),
+        (status = 404, description = "Item not found", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn item_timeline_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(id): ApiPath<String>,
+) -> Result<Json<ItemTimeline>, ItemError> {
+    let timeline = state.service.item_timeline(&id).await?;
+    Ok(Json(timeline))
+}
+
+/// Detailed health check (served from the cached dependency snapshot)
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses(
+        (status = 200, description = "Health status", body = HealthResponse)
+    )
+)]
+pub async fn health_check_handler(State(state): State<Arc<AppState>>) -> Json<HealthResponse> {
+    let health = state.service.health_check().await;
+    Json(health.with_schema_status(&state.schema_status))
+}
+
+/// Kubernetes liveness probe (never touches dependencies)
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "health",
+    responses(
+        (status = 200, description = "Application is alive")
+    )
+)]
+pub async fn liveness_handler() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Kubernetes readiness probe (served from the cached dependency snapshot)
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "Application is ready to serve traffic"),
+        (status = 503, description = "Application is not ready")
+    )
+)]
+pub async fn readiness_handler(State(state): State<Arc<AppState>>) -> StatusCode {
+    let health = state.service.health_check().await;
+    match health.status {
+        HealthStatus::Healthy | HealthStatus::Degraded | HealthStatus::Disabled => StatusCode::OK,
+        HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
+/// Deep health check that forces fresh dependency checks
+#[utoipa::path(
+    get,
+    path = "/health/deep",
+    tag = "health",
+    responses(
+        (status = 200, description = "Fresh health status", body = HealthResponse),
+        (status = 401, description = "Missing or invalid API key")
+    )
+)]
+pub async fn deep_health_handler(State(state): State<Arc<AppState>>) -> Json<HealthResponse> {
+    let health = state.service.deep_health_check().await;
+    Json(health.with_schema_status(&state.schema_status))
+}
+
+/// Verify a receipt's signature against the current and retired issuer keys
+#[utoipa::path(
+    post,
+    path = "/verify/receipt",
+    tag = "verify",
+    request_body = VerifyReceiptRequest,
+    responses(
+        (status = 200, description = "Verification result (`valid: false` when no issuer key matches)", body = ReceiptVerification),
+        (status = 400, description = "Receipt is not an EdDSA compact JWS", body = ErrorResponse)
+    )
+)]
+pub async fn verify_receipt_handler(
+    State(state): State<Arc<AppState>>,
+    ApiJson(payload): ApiJson<VerifyReceiptRequest>,
+) -> Result<Json<ReceiptVerification>, ValidationError> {
+    let verification = state.issuer_keys.verify(&payload.receipt)?;
+    metrics::counter!(
+        "receipt_verifications_total",
+        "valid" => verification.valid.to_string()
+    )
+    .increment(1);
+    Ok(Json(verification))
+}
+
+/// Get the current IP blocklist
+#[utoipa::path(
+    get,
+    path = "/admin/blocklist",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Blocked CIDR ranges", body = BlocklistResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "Source address is blocked", body = ErrorResponse)
+    )
+)]
+pub async fn get_blocklist_handler(State(state): State<Arc<AppState>>) -> Json<BlocklistResponse> {
+    let cidrs = state
+        .blocklist
+        .ranges()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    Json(BlocklistResponse { cidrs })
+}
+
+/// Replace the IP blocklist (takes effect immediately)
+#[utoipa::path(
+    put,
+    path = "/admin/blocklist",
+    tag = "admin",
+    request_body = UpdateBlocklistRequest,
+    responses(
+        (status = 200, description = "Blocklist replaced", body = BlocklistResponse),
+        (status = 400, description = "Invalid CIDR range", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "Source address is blocked", body = ErrorResponse)
+    )
+)]
+pub async fn update_blocklist_handler(
+    State(state): State<Arc<AppState>>,
+    ApiJson(payload): ApiJson<UpdateBlocklistRequest>,
+) -> Result<Json<BlocklistResponse>, ValidationError> {
+    let ranges = IpBlocklist::parse_ranges(&payload.cidrs)?;
+    let cidrs = ranges.iter().map(ToString::to_string).collect();
+    state.blocklist.replace(ranges);
+    info!(cidrs = ?cidrs, "IP blocklist replaced");
+    Ok(Json(BlocklistResponse { cidrs }))
+}
+
+/// List the active temporary bans (sources with repeated `401`/`429` responses)
+#[utoipa::path(
+    get,
+    path = "/admin/bans",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Active bans on this instance, soonest to expire first", body = [TemporaryBan]),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope")
+    )
+)]
+pub async fn list_bans_handler(State(state): State<Arc<AppState>>) -> Json<Vec<TemporaryBan>> {
+    Json(state.abuse.active_bans())
+}
+
+/// Lift a temporary ban before it expires
+#[utoipa::path(
+    delete,
+    path = "/admin/bans/{subject}",
+    tag = "admin",
+    params(
+        ("subject" = String, Path, description = "Banned subject, e.g. `ip:203.0.113.7`")
+    ),
+    responses(
+        (status = 200, description = "Ban lifted", body = TemporaryBan),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope"),
+        (status = 404, description = "No active ban on the subject", body = ErrorResponse)
+    )
+)]
+pub async fn lift_ban_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(subject): ApiPath<String>,
+) -> Result<Json<TemporaryBan>, axum::response::Response> {
+    let ban = state.abuse.lift(&subject).ok_or_else(|| {
+        error_response(
+            StatusCode::NOT_FOUND,
+            "not_found",
+            format!("No active ban on {}", subject),
+        )
+    })?;
+    info!(subject = %ban.subject, "Temporary ban lifted");
+    Ok(Json(ban))
+}
+
+/// Get the background retry worker's status
+#[utoipa::path(
+    get,
+    path = "/admin/worker",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Worker status on this instance", body = WorkerStatus),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope")
+    )
+)]
+pub async fn get_worker_status_handler(State(state): State<Arc<AppState>>) -> Json<WorkerStatus> {
+    let status = state
+        .worker_monitor
+        .as_ref()
+        .map(|monitor| monitor.status())
+        .unwrap_or_default();
+    Json(status)
+}
+
+/// Run a worker batch now instead of waiting for the next poll
+#[utoipa::path(
+    post,
+    path = "/admin/worker/run-now",
+    tag = "admin",
+    responses(
+        (status = 202, description = "Batch scheduled; poll `GET /admin/worker` for the result", body = WorkerStatus),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope"),
+        (status = 409, description = "Worker is not running on this instance", body = ErrorResponse)
+    )
+)]
+pub async fn run_worker_now_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<(StatusCode, Json<WorkerStatus>), WorkerError> {
+    let monitor = state
+        .worker_monitor
+        .as_ref()
+        .ok_or(WorkerError::NotRunning)?;
+    let status = monitor.trigger()?;
+    info!(
+        manual_runs = status.manual_runs,
+        "Manual worker run triggered"
+    );
+    Ok((StatusCode::ACCEPTED, Json(status)))
+}
+
+/// Get the blockchain submission queue depth
+#[utoipa::path(
+    get,
+    path = "/admin/queue",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Entries per queue state", body = QueueDepth),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope"),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_queue_depth_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<QueueDepth>, ItemError> {
+    Ok(Json(state.service.queue_depth().await?))
+}
+
+/// Get the maintenance mode setting
+#[utoipa::path(
+    get,
+    path = "/admin/maintenance",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Current setting", body = MaintenanceMode),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope")
+    )
+)]
+pub async fn get_maintenance_handler(State(state): State<Arc<AppState>>) -> Json<MaintenanceMode> {
+    Json(MaintenanceMode {
+        enabled: state.maintenance_enabled(),
+    })
+}
+
+/// Turn maintenance mode on or off (writes outside `/admin` get 503 while it is on)
+#[utoipa::path(
+    put,
+    path = "/admin/maintenance",
+    tag = "admin",
+    request_body = MaintenanceMode,
+    responses(
+        (status = 200, description = "Setting applied", body = MaintenanceMode),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope")
+    )
+)]
+pub async fn set_maintenance_handler(
+    State(state): State<Arc<AppState>>,
+    ApiJson(payload): ApiJson<MaintenanceMode>,
+) -> Json<MaintenanceMode> {
+    state.set_maintenance(payload.enabled);
+    Json(payload)
+}
+
+/// List blockchain submissions in the dead-letter queue
+#[utoipa::path(
+    get,
+    path = "/admin/dlq",
+    tag = "admin",
+    params(
+        ("limit" = Option<i64>, Query, description = "Maximum number of entries (1-100, default: 50)"),
+        ("include_requeued" = Option<bool>, Query, description = "Also return entries that were already requeued")
+    ),
+    responses(
+        (status = 200, description = "Dead-lettered submissions, most recent first", body = Vec<FailedSubmission>),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope"),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn list_dead_letters_handler(
+    State(state): State<Arc<AppState>>,
+    ApiQuery(params): ApiQuery<DeadLetterParams>,
+) -> Result<Json<Vec<FailedSubmission>>, ItemError> {
+    let submissions = state
+        .service
+        .list_failed_submissions(params.limit, params.include_requeued)
+        .await?;
+    Ok(Json(submissions))
+}
+
+/// Requeue a dead-lettered submission (its item goes back to `pending_submission`)
+#[utoipa::path(
+    post,
+    path = "/admin/dlq/{id}/requeue",
+    tag = "admin",
+    params(
+        ("id" = String, Path, description = "Dead-letter entry ID")
+    ),
+    responses(
+        (status = 200, description = "Submission requeued", body = Item),
+        (status = 400, description = "Already requeued, item no longer failed, or blockchain disabled", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope"),
+        (status = 404, description = "Dead-letter entry not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn requeue_dead_letter_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(id): ApiPath<String>,
+) -> Result<Json<Item>, ItemError> {
+    let item = state.service.requeue_failed_submission(&id).await?;
+    Ok(Json(item))
+}
+
+/// Requeue every dead-lettered submission in a background job
+#[utoipa::path(
+    post,
+    path = "/admin/dlq/requeue",
+    tag = "admin",
+    responses(
+        (status = 202, description = "Job started; poll `Location` for progress", body = Job,
+            headers(("Location" = String, description = "Job status URL (`/jobs/{id}`)"))),
+        (status = 400, description = "Blockchain disabled", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope"),
+        (status = 503, description = "Job store is not configured", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn requeue_all_dead_letters_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, StartJobError> {
+    let jobs = state.job_store.clone().ok_or(JobError::StoreUnavailable)?;
+    let job = state
+        .service
+        .start_requeue_all_failed_submissions(jobs)
+        .await?;
+    Ok(accepted_job(job))
+}
+
+/// Encrypt plaintext item content and move content under retired master keys to the
+/// current one in a background job
+#[utoipa::path(
+    post,
+    path = "/admin/encryption/reencrypt",
+    tag = "admin",
+    responses(
+        (status = 202, description = "Job started; poll `Location` for progress. The job \
+            fails when content encryption is not configured", body = Job,
+            headers(("Location" = String, description = "Job status URL (`/jobs/{id}`)"))),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope"),
+        (status = 503, description = "Job store is not configured", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn reencrypt_content_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, StartJobError> {
+    let jobs = state.job_store.clone().ok_or(JobError::StoreUnavailable)?;
+    let job = state.service.start_reencrypt_content(jobs).await?;
+    Ok(accepted_job(job))
+}
+
+/// `202 Accepted` pointing at the job's status URL
+fn accepted_job(job: Job) -> impl IntoResponse {
+    (
+        StatusCode::ACCEPTED,
+        [(header::LOCATION, format!("/jobs/{}", job.id))],
+        Json(job),
+    )
+}
+
+/// List item status events (the audit log of blockchain status changes)
+#[utoipa::path(
+    get,
+    path = "/admin/events",
+    tag = "admin",
+    params(
+        ("limit" = Option<i64>, Query, description = "Maximum number of entries (1-100, default: 50)"),
+        ("cursor" = Option<String>, Query, description = "Opaque `next_cursor` of the previous page"),
+        ("since" = Option<String>, Query, format = DateTime, description = "Only entries at or after this time (RFC 3339)"),
+        ("until" = Option<String>, Query, format = DateTime, description = "Only entries before this time (RFC 3339)")
+    ),
     responses(
-        (status = 200, description = "Item created successfully", body = Item),
-        (status = 400, description = "Validation error", body = ErrorResponse),
-        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
+        (status = 200, description = "Events, most recent first", body = PaginatedResponse<ItemStatusEvent>),
+        (status = 400, description = "Invalid parameters or tampered cursor (`invalid_cursor`)", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope"),
         (status = 500, description = "Internal server error", body = ErrorResponse),
-        (status = 503, description = "Service unavailable", body = ErrorResponse)
+        (status = 503, description = "Logs not configured on this instance", body = ErrorResponse)
     )
 )]
-pub async fn create_item_handler(
+pub async fn list_item_events_handler(
     State(state): State<Arc<AppState>>,
-    Json(payload): Json<CreateItemRequest>,
-) -> Result<Json<Item>, CreateItemError> {
-    let item = state.service.create_and_submit_item(&payload).await?;
-    Ok(Json(item))
+    ApiQuery(params): ApiQuery<LogPageParams>,
+) -> Result<Json<PaginatedResponse<ItemStatusEvent>>, NotificationError> {
+    let page = state
+        .service
+        .list_item_events(params.limit, params.cursor.as_deref(), &params.range())
+        .await?;
+    Ok(Json(page))
 }
 
-/// List items with pagination
+/// List webhook delivery attempts
 #[utoipa::path(
     get,
-    path = "/items",
-    tag = "items",
+    path = "/admin/webhook-deliveries",
+    tag = "admin",
     params(
-        ("limit" = Option<i64>, Query, description = "Maximum number of items to return (1-100, default: 20)"),
-        ("cursor" = Option<String>, Query, description = "Cursor for pagination (item ID to start after)")
+        ("limit" = Option<i64>, Query, description = "Maximum number of entries (1-100, default: 50)"),
+        ("cursor" = Option<String>, Query, description = "Opaque `next_cursor` of the previous page"),
+        ("since" = Option<String>, Query, format = DateTime, description = "Only entries at or after this time (RFC 3339)"),
+        ("until" = Option<String>, Query, format = DateTime, description = "Only entries before this time (RFC 3339)")
     ),
     responses(
-        (status = 200, description = "List of items", body = PaginatedResponse<Item>),
-        (status = 400, description = "Invalid pagination parameters", body = ErrorResponse),
-        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
-        (status = 500, description = "Internal server error", body = ErrorResponse)
+        (status = 200, description = "Delivery attempts, most recent first", body = PaginatedResponse<WebhookDelivery>),
+        (status = 400, description = "Invalid parameters or tampered cursor (`invalid_cursor`)", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope"),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+        (status = 503, description = "Logs not configured on this instance", body = ErrorResponse)
     )
 )]
-pub async fn list_items_handler(
+pub async fn list_webhook_deliveries_handler(
     State(state): State<Arc<AppState>>,
-    Query(params): Query<PaginationParams>,
-) -> Result<Json<PaginatedResponse<Item>>, ItemError> {
-    // Validate limit
-    let limit = params.limit.clamp(1, 100);
-    let items = state
+    ApiQuery(params): ApiQuery<LogPageParams>,
+) -> Result<Json<PaginatedResponse<WebhookDelivery>>, NotificationError> {
+    let page = state
         .service
-        .list_items(limit, params.cursor.as_deref())
+        .list_webhook_deliveries(params.limit, params.cursor.as_deref(), &params.range())
         .await?;
-    Ok(Json(items))
+    Ok(Json(page))
 }
 
-/// Get a single item by ID
+/// List the audit log: who created, changed or deleted what
 #[utoipa::path(
     get,
-    path = "/items/{id}",
-    tag = "items",
+    path = "/admin/audit",
+    tag = "admin",
     params(
-        ("id" = String, Path, description = "Item ID")
+        ("limit" = Option<i64>, Query, description = "Maximum number of entries (1-100, default: 50)"),
+        ("cursor" = Option<String>, Query, description = "Opaque `next_cursor` of the previous page"),
+        ("since" = Option<String>, Query, format = DateTime, description = "Only entries at or after this time (RFC 3339)"),
+        ("until" = Option<String>, Query, format = DateTime, description = "Only entries before this time (RFC 3339)"),
+        ("principal" = Option<String>, Query, description = "Only entries by this key ID"),
+        ("action" = Option<String>, Query, description = "Only entries of this action, e.g. `item.delete`"),
+        ("resource_id" = Option<String>, Query, description = "Only entries about this resource")
     ),
     responses(
-        (status = 200, description = "Item found", body = Item),
-        (status = 404, description = "Item not found", body = ErrorResponse),
-        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
-        (status = 500, description = "Internal server error", body = ErrorResponse)
+        (status = 200, description = "Audit entries, most recent first", body = PaginatedResponse<AuditEntry>),
+        (status = 400, description = "Invalid parameters or tampered cursor (`invalid_cursor`)", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope"),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+        (status = 503, description = "Audit log not configured on this instance", body = ErrorResponse)
     )
 )]
-pub async fn get_item_handler(
+pub async fn list_audit_entries_handler(
     State(state): State<Arc<AppState>>,
-    Path(id): Path<String>,
-) -> Result<Json<Item>, ItemError> {
-    let item = state
+    ApiQuery(params): ApiQuery<AuditParams>,
+) -> Result<Json<PaginatedResponse<AuditEntry>>, NotificationError> {
+    let page = state
         .service
-        .get_item(&id)
-        .await?
-        .ok_or(ItemError::NotFound(id))?;
-    Ok(Json(item))
+        .list_audit_entries(params.limit, params.cursor.as_deref(), &params.filter())
+        .await?;
+    Ok(Json(page))
 }
 
-/// Retry blockchain submission for an item
+/// Status, progress and outcome of a background job
 #[utoipa::path(
-    post,
-    path = "/items/{id}/retry",
-    tag = "items",
+    get,
+    path = "/jobs/{id}",
+    tag = "jobs",
     params(
-        ("id" = String, Path, description = "Item ID")
+        ("id" = String, Path, description = "Job ID")
     ),
     responses(
-        (status = 200, description = "Retry successful", body = Item),
-        (status = 400, description = "Item not eligible for retry", body = ErrorResponse),
-        (status = 404, description = "Item not found", body = ErrorResponse),
-        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse),
-        (status = 500, description = "Internal server error", body = ErrorResponse),
-        (status = 503, description = "Blockchain unavailable", body = ErrorResponse)
+        (status = 200, description = "Job found", body = Job),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 404, description = "Job not found", body = ErrorResponse),
+        (status = 503, description = "Job store is not configured", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
-pub async fn retry_blockchain_handler(
+pub async fn get_job_handler(
     State(state): State<Arc<AppState>>,
-    Path(id): Path<String>,
-) -> Result<Json<Item>, ItemError> {
-    let item = state.service.retry_blockchain_submission(&id).await?;
-    Ok(Json(item))
+    ApiPath(id): ApiPath<String>,
+) -> Result<Json<Job>, JobError> {
+    let store = state
+        .job_store
+        .as_deref()
+        .ok_or(JobError::StoreUnavailable)?;
+    store
+        .get_job(&id)
+        .await?
+        .map(Json)
+        .ok_or(JobError::NotFound(id))
+}
+
+fn api_key_store(state: &AppState) -> Result<&dyn ApiKeyStore, ApiKeyError> {
+    state
+        .api_key_store
+        .as_deref()
+        .ok_or(ApiKeyError::StoreUnavailable)
 }
 
-/// Detailed health check
+/// Create an API key (the secret is returned only once)
 #[utoipa::path(
-    get,
-    path = "/health",
-    tag = "health",
+    post,
+    path = "/admin/api-keys",
+    tag = "admin",
+    request_body = CreateApiKeyRequest,
     responses(
-        (status = 200, description = "Health status", body = HealthResponse)
+        (status = 201, description = "API key created", body = CreateApiKeyResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope"),
+        (status = 503, description = "API key store not configured", body = ErrorResponse)
     )
 )]
-pub async fn health_check_handler(State(state): State<Arc<AppState>>) -> Json<HealthResponse> {
-    let health = state.service.health_check().await;
-    Json(health)
+pub async fn create_api_key_handler(
+    State(state): State<Arc<AppState>>,
+    ApiJson(payload): ApiJson<CreateApiKeyRequest>,
+) -> Result<(StatusCode, Json<CreateApiKeyResponse>), IssueApiKeyError> {
+    let created = issue_api_key(api_key_store(&state)?, &payload).await?;
+    Ok((StatusCode::CREATED, Json(created)))
 }
 
-/// Kubernetes liveness probe
+/// List API keys (secrets are never returned)
 #[utoipa::path(
     get,
-    path = "/health/live",
-    tag = "health",
+    path = "/admin/api-keys",
+    tag = "admin",
     responses(
-        (status = 200, description = "Application is alive")
+        (status = 200, description = "API keys, newest first", body = Vec<ApiKey>),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope"),
+        (status = 503, description = "API key store not configured", body = ErrorResponse)
     )
 )]
-pub async fn liveness_handler() -> StatusCode {
-    StatusCode::OK
+pub async fn list_api_keys_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<ApiKey>>, ApiKeyError> {
+    let keys = api_key_store(&state)?.list_api_keys().await?;
+    Ok(Json(keys))
+}
+
+/// Revoke an API key (takes effect on the next request)
+#[utoipa::path(
+    delete,
+    path = "/admin/api-keys/{id}",
+    tag = "admin",
+    params(
+        ("id" = String, Path, description = "API key ID")
+    ),
+    responses(
+        (status = 200, description = "API key revoked", body = ApiKey),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope"),
+        (status = 404, description = "API key not found", body = ErrorResponse),
+        (status = 503, description = "API key store not configured", body = ErrorResponse)
+    )
+)]
+pub async fn revoke_api_key_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(id): ApiPath<String>,
+) -> Result<Json<ApiKey>, ApiKeyError> {
+    let key = api_key_store(&state)?.revoke_api_key(&id).await?;
+    info!(key_id = %key.id, "API key revoked");
+    Ok(Json(key))
+}
+
+/// Rotate an API key: a new key with the same name and scopes replaces it (the new secret
+/// is returned only once) and the old key is revoked
+#[utoipa::path(
+    post,
+    path = "/admin/api-keys/{id}/rotate",
+    tag = "admin",
+    params(
+        ("id" = String, Path, description = "API key ID")
+    ),
+    responses(
+        (status = 201, description = "Replacement key created, old key revoked", body = CreateApiKeyResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope"),
+        (status = 404, description = "API key not found", body = ErrorResponse),
+        (status = 409, description = "API key is already revoked", body = ErrorResponse),
+        (status = 503, description = "API key store not configured", body = ErrorResponse)
+    )
+)]
+pub async fn rotate_api_key_handler(
+    State(state): State<Arc<AppState>>,
+    ApiPath(id): ApiPath<String>,
+) -> Result<(StatusCode, Json<CreateApiKeyResponse>), ApiKeyError> {
+    let rotated = rotate_api_key(api_key_store(&state)?, &id).await?;
+    info!(old_key_id = %id, key_id = %rotated.key.id, "API key rotated");
+    Ok((StatusCode::CREATED, Json(rotated)))
 }
 
-/// Kubernetes readiness probe
+/// Requests counted per API key on a day (UTC) and in its month, with the keys' quotas
 #[utoipa::path(
     get,
-    path = "/health/ready",
-    tag = "health",
+    path = "/admin/usage",
+    tag = "admin",
+    params(
+        ("day" = Option<String>, Query, format = Date, description = "UTC day to report (`YYYY-MM-DD`, default: today)")
+    ),
     responses(
-        (status = 200, description = "Application is ready to serve traffic"),
-        (status = 503, description = "Application is not ready")
+        (status = 200, description = "Usage per API key, by key ID", body = UsageReport),
+        (status = 400, description = "Invalid day", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the admin scope"),
+        (status = 503, description = "Usage ledger not configured", body = ErrorResponse)
     )
 )]
-pub async fn readiness_handler(State(state): State<Arc<AppState>>) -> StatusCode {
-    let health = state.service.health_check().await;
-    match health.status {
-        HealthStatus::Healthy | HealthStatus::Degraded => StatusCode::OK,
-        HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
-    }
+pub async fn get_usage_handler(
+    State(state): State<Arc<AppState>>,
+    ApiQuery(params): ApiQuery<UsageParams>,
+) -> Result<Json<UsageReport>, ApiKeyError> {
+    let ledger = state
+        .usage_ledger
+        .as_deref()
+        .ok_or(ApiKeyError::UsageUnavailable)?;
+    let day = params.day.unwrap_or_else(|| Utc::now().date_naive());
+    let keys = ledger.usage_on(day).await?;
+    Ok(Json(UsageReport { day, keys }))
+}
+
+pub(crate) fn error_response(
+    status: StatusCode,
+    error_type: &str,
+    message: String,
+) -> axum::response::Response {
+    error_response_with_fields(status, error_type, message, Vec::new())
 }
 
-fn error_response(
+/// [`error_response`] listing the offending fields (validation errors)
+fn error_response_with_fields(
     status: StatusCode,
     error_type: &str,
     message: String,
+    fields: Vec<FieldError>,
 ) -> axum::response::Response {
     if status.is_server_error() {
         error!(error_type = %error_type, message = %message, "Server error");
@@ -224,31 +1430,78 @@ fn error_response(
         error: ErrorDetail {
             r#type: error_type.to_string(),
             message,
+            fields,
+            request_id: current_request_id(),
         },
     });
     (status, body).into_response()
 }
 
-impl IntoResponse for ItemError {
-    fn into_response(self) -> axum::response::Response {
-        let (status, error_type, message) = match &self {
+/// Status, `error.type` and client-facing message an error is reported with
+pub(crate) trait ApiError {
+    fn parts(&self) -> (StatusCode, &'static str, String);
+}
+
+impl ApiError for ItemError {
+    fn parts(&self) -> (StatusCode, &'static str, String) {
+        match self {
             ItemError::NotFound(_) => (StatusCode::NOT_FOUND, "not_found", self.to_string()),
             ItemError::InvalidState(_) => {
                 (StatusCode::BAD_REQUEST, "invalid_state", self.to_string())
             }
+            ItemError::InvalidCursor(_) => {
+                (StatusCode::BAD_REQUEST, "invalid_cursor", self.to_string())
+            }
+            ItemError::Conflict { .. } => (StatusCode::CONFLICT, "conflict", self.to_string()),
+            ItemError::DuplicateContent => {
+                (StatusCode::CONFLICT, "duplicate_content", self.to_string())
+            }
+            ItemError::RangeNotSatisfiable { .. } => (
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                "range_not_satisfiable",
+                self.to_string(),
+            ),
             ItemError::RepositoryFailure => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "repository_error",
                 "Internal server error".to_string(),
             ),
+        }
+    }
+}
+
+impl IntoResponse for ItemError {
+    fn into_response(self) -> axum::response::Response {
+        let (status, error_type, message) = self.parts();
+        let mut response = error_response(status, error_type, message);
+        if let ItemError::RangeNotSatisfiable { length } = self
+            && let Ok(value) = HeaderValue::try_from(format!("bytes */{length}"))
+        {
+            response.headers_mut().insert(header::CONTENT_RANGE, value);
+        }
+        response
+    }
+}
+
+impl ApiError for WorkerError {
+    fn parts(&self) -> (StatusCode, &'static str, String) {
+        let (status, error_type) = match self {
+            WorkerError::NotRunning => (StatusCode::CONFLICT, "worker_not_running"),
         };
-        error_response(status, error_type, message)
+        (status, error_type, self.to_string())
     }
 }
 
-impl IntoResponse for BlockchainError {
+impl IntoResponse for WorkerError {
     fn into_response(self) -> axum::response::Response {
-        let (status, error_type, message) = match &self {
+        let (status, error_type, message) = self.parts();
+        error_response(status, error_type, message)
+    }
+}
+
+impl ApiError for BlockchainError {
+    fn parts(&self) -> (StatusCode, &'static str, String) {
+        match self {
             BlockchainError::SubmissionFailed(_) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "blockchain_error",
@@ -277,18 +1530,181 @@ impl IntoResponse for BlockchainError {
             BlockchainError::Timeout { .. } => {
                 (StatusCode::GATEWAY_TIMEOUT, "timeout", self.to_string())
             }
-        };
+            BlockchainError::CircuitOpen => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "blockchain_unavailable",
+                "Blockchain service unavailable".to_string(),
+            ),
+        }
+    }
+}
+
+impl IntoResponse for BlockchainError {
+    fn into_response(self) -> axum::response::Response {
+        let (status, error_type, message) = self.parts();
         error_response(status, error_type, message)
     }
 }
 
+impl ApiError for ValidationError {
+    fn parts(&self) -> (StatusCode, &'static str, String) {
+        let error_type = match self {
+            ValidationError::TooLarge { .. } => "field_too_large",
+            _ => "validation_error",
+        };
+        (StatusCode::BAD_REQUEST, error_type, self.to_string())
+    }
+}
+
 impl IntoResponse for ValidationError {
     fn into_response(self) -> axum::response::Response {
-        error_response(
-            StatusCode::BAD_REQUEST,
-            "validation_error",
-            self.to_string(),
-        )
+        let (status, error_type, message) = self.parts();
+        error_response_with_fields(status, error_type, message, self.field_errors())
+    }
+}
+
+impl ApiError for ApiKeyError {
+    fn parts(&self) -> (StatusCode, &'static str, String) {
+        match self {
+            ApiKeyError::NotFound(_) => (StatusCode::NOT_FOUND, "not_found", self.to_string()),
+            ApiKeyError::Revoked(_) => (StatusCode::CONFLICT, "invalid_state", self.to_string()),
+            ApiKeyError::StoreUnavailable => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "api_keys_unavailable",
+                self.to_string(),
+            ),
+            ApiKeyError::UsageUnavailable => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "usage_unavailable",
+                self.to_string(),
+            ),
+            ApiKeyError::RepositoryFailure => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "repository_error",
+                "Internal server error".to_string(),
+            ),
+        }
+    }
+}
+
+impl IntoResponse for ApiKeyError {
+    fn into_response(self) -> axum::response::Response {
+        let (status, error_type, message) = self.parts();
+        error_response(status, error_type, message)
+    }
+}
+
+impl ApiError for RequestJournalError {
+    fn parts(&self) -> (StatusCode, &'static str, String) {
+        match self {
+            RequestJournalError::Unavailable => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "request_journal_unavailable",
+                self.to_string(),
+            ),
+            RequestJournalError::RepositoryFailure => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "repository_error",
+                "Internal server error".to_string(),
+            ),
+        }
+    }
+}
+
+impl IntoResponse for RequestJournalError {
+    fn into_response(self) -> axum::response::Response {
+        let (status, error_type, message) = self.parts();
+        error_response(status, error_type, message)
+    }
+}
+
+impl ApiError for JobError {
+    fn parts(&self) -> (StatusCode, &'static str, String) {
+        match self {
+            JobError::NotFound(_) => (StatusCode::NOT_FOUND, "not_found", self.to_string()),
+            JobError::StoreUnavailable => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "jobs_unavailable",
+                self.to_string(),
+            ),
+            JobError::RepositoryFailure => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "repository_error",
+                "Internal server error".to_string(),
+            ),
+        }
+    }
+}
+
+impl IntoResponse for JobError {
+    fn into_response(self) -> axum::response::Response {
+        let (status, error_type, message) = self.parts();
+        error_response(status, error_type, message)
+    }
+}
+
+impl ApiError for NotificationError {
+    fn parts(&self) -> (StatusCode, &'static str, String) {
+        match self {
+            NotificationError::InvalidCursor(msg) => {
+                (StatusCode::BAD_REQUEST, "invalid_cursor", msg.clone())
+            }
+            NotificationError::LogUnavailable => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "logs_unavailable",
+                self.to_string(),
+            ),
+            NotificationError::DeliveryFailed { .. } | NotificationError::RepositoryFailure => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "repository_error",
+                "Internal server error".to_string(),
+            ),
+        }
+    }
+}
+
+impl IntoResponse for NotificationError {
+    fn into_response(self) -> axum::response::Response {
+        let (status, error_type, message) = self.parts();
+        error_response(status, error_type, message)
+    }
+}
+
+impl IntoResponse for StartJobError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            StartJobError::Job(e) => e.into_response(),
+            StartJobError::Item(e) => e.into_response(),
+        }
+    }
+}
+
+impl IntoResponse for IssueApiKeyError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            IssueApiKeyError::Validation(e) => e.into_response(),
+            IssueApiKeyError::ApiKey(e) => e.into_response(),
+        }
+    }
+}
+
+impl ApiError for VerifyItemError {
+    fn parts(&self) -> (StatusCode, &'static str, String) {
+        match self {
+            VerifyItemError::Item(e) => e.parts(),
+            VerifyItemError::Blockchain(_) => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "blockchain_unavailable",
+                "Blockchain service unavailable".to_string(),
+            ),
+        }
+    }
+}
+
+impl IntoResponse for VerifyItemError {
+    fn into_response(self) -> axum::response::Response {
+        let (status, error_type, message) = self.parts();
+        error_response(status, error_type, message)
     }
 }
 
@@ -304,7 +1720,7 @@ impl IntoResponse for CreateItemError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::ItemRepository;
+    use crate::domain::{ItemRepository, SubmissionRepository};
     use crate::test_utils::{MockBlockchainClient, MockProvider, mock_repos, test_api_key};
 
     #[tokio::test]
@@ -319,9 +1735,16 @@ mod tests {
             description: Some("Desc".to_string()),
             content: "Content".to_string(),
             metadata: None,
+            content_key: None,
+            content_type: None,
         };
 
-        let result = create_item_handler(State(state), Json(payload)).await;
+        let result = create_item_handler(
+            State(state),
+            ApiQuery(CreateItemParams::default()),
+            ApiJson(payload),
+        )
+        .await;
         assert!(result.is_ok());
         let Json(item) = result.unwrap();
         assert_eq!(item.name, "Test Item");
@@ -342,10 +1765,11 @@ mod tests {
         let req = CreateItemRequest::new("Seed".to_string(), "Content".to_string());
         let created = mock.create_item(&req).await.unwrap();
 
-        let result = get_item_handler(State(state), Path(created.id.clone())).await;
+        let result = get_item_handler(State(state), ApiPath(created.id.clone())).await;
         assert!(result.is_ok());
-        let Json(fetched) = result.unwrap();
+        let ([(_, etag)], Json(fetched)) = result.unwrap();
         assert_eq!(fetched.id, created.id);
+        assert_eq!(etag, "\"1\"");
     }
 
     #[tokio::test]
@@ -369,8 +1793,9 @@ mod tests {
         let params_high = PaginationParams {
             limit: i64::MAX,
             cursor: None,
+            ..PaginationParams::default()
         };
-        let result = list_items_handler(State(state.clone()), Query(params_high)).await;
+        let result = list_items_handler(State(state.clone()), ApiQuery(params_high)).await;
         assert!(result.is_ok());
         // Note: We can't verify the internal call argument without a spy,
         // but we ensure the handler doesn't panic and returns success.
@@ -379,8 +1804,9 @@ mod tests {
         let params_low = PaginationParams {
             limit: i64::MIN,
             cursor: None,
+            ..PaginationParams::default()
         };
-        let result_low = list_items_handler(State(state), Query(params_low)).await;
+        let result_low = list_items_handler(State(state), ApiQuery(params_low)).await;
         assert!(result_low.is_ok());
     }
 
@@ -391,7 +1817,7 @@ mod tests {
         let bc = Arc::new(MockBlockchainClient::new());
         let state = Arc::new(AppState::new(item_repo, outbox_repo, bc, test_api_key()));
 
-        let result = get_item_handler(State(state), Path("non-existent-id".to_string())).await;
+        let result = get_item_handler(State(state), ApiPath("non-existent-id".to_string())).await;
 
         match result {
             Err(ItemError::NotFound(id)) => {
@@ -423,7 +1849,7 @@ mod tests {
         .await
         .unwrap();
 
-        let result = retry_blockchain_handler(State(state), Path(created.id)).await;
+        let result = retry_blockchain_handler(State(state), ApiPath(created.id)).await;
         assert!(result.is_ok());
         let Json(item) = result.unwrap();
         assert_eq!(item.name, "Retry Item");
@@ -462,6 +1888,12 @@ mod tests {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[test]
+    fn test_error_mapping_item_duplicate_content() {
+        let response = ItemError::DuplicateContent.into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
     #[test]
     fn test_error_mapping_item_repository_failure() {
         let err = ItemError::RepositoryFailure;
@@ -503,6 +1935,12 @@ mod tests {
         assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
     }
 
+    #[test]
+    fn test_error_mapping_blockchain_circuit_open() {
+        let response = BlockchainError::CircuitOpen.into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
     #[test]
     fn test_error_mapping_validation_error() {
         let err = ValidationError::InvalidFormat("Invalid email format".into());
@@ -546,7 +1984,8 @@ mod tests {
         let bc = Arc::new(MockBlockchainClient::new());
         let state = Arc::new(AppState::new(item_repo, outbox_repo, bc, test_api_key()));
 
-        let result = retry_blockchain_handler(State(state), Path("nonexistent".to_string())).await;
+        let result =
+            retry_blockchain_handler(State(state), ApiPath("nonexistent".to_string())).await;
         assert!(matches!(result, Err(ItemError::NotFound(_))));
     }
 }
@@ -0,0 +1,421 @@
+//! `Idempotency-Key` support for POST endpoints.
+//!
+//! The first request with a key is journaled as `processing`, executed, and its response
+//! stored. Retries with the same key replay that response; retries that arrive while the
+//! original is still running get `202 Accepted` with a `Location` to poll. Server errors
+//! are not stored, so the client can retry them. A `processing` entry whose request was
+//! interrupted by a crash is taken over by the next retry once [`PROCESSING_LEASE`] expires.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    Extension, Json,
+    body::{Body, Bytes, to_bytes},
+    extract::{OriginalUri, State},
+    http::{HeaderValue, Request, Response, StatusCode, header},
+    middleware::Next,
+    response::IntoResponse,
+};
+use sha2::{Digest, Sha256};
+use tracing::{error, info};
+
+use super::extract::ApiPath;
+use super::handlers::error_response;
+use crate::app::AppState;
+use crate::domain::{
+    ErrorResponse, JournalStatus, Principal, RequestJournal, RequestJournalEntry,
+    RequestJournalError, RequestStatusResponse,
+};
+
+/// Header carrying the client-chosen idempotency key
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Set on responses replayed from the journal
+pub const IDEMPOTENT_REPLAY_HEADER: &str = "idempotent-replayed";
+
+/// Maximum accepted key length
+const MAX_KEY_LEN: usize = 255;
+
+/// How long a `processing` entry is trusted before a retry may take it over
+pub const PROCESSING_LEASE: Duration = Duration::from_secs(60);
+
+/// Largest request or response body that is journaled (axum's default body limit)
+const MAX_JOURNALED_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+fn journal(state: &AppState) -> Result<&dyn RequestJournal, RequestJournalError> {
+    state
+        .request_journal
+        .as_deref()
+        .ok_or(RequestJournalError::Unavailable)
+}
+
+/// Journal key: idempotency keys are scoped to the API key that sent them
+fn journal_key(principal: Option<&Principal>, key: &str) -> String {
+    let owner = principal.map_or("anonymous", |p| p.key_id.as_str());
+    format!("{}:{}", owner, key)
+}
+
+fn request_hash(method: &str, path: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(b" ");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    format!("{:x}", hasher.finalize())
+}
+
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty() && key.len() <= MAX_KEY_LEN && key.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// Rebuild the stored response of a completed entry
+fn replay(entry: &RequestJournalEntry) -> Response<Body> {
+    let status = entry
+        .response_status
+        .and_then(|s| StatusCode::from_u16(s).ok())
+        .unwrap_or(StatusCode::OK);
+    let mut response = (
+        status,
+        [(header::CONTENT_TYPE, "application/json")],
+        entry.response_body.clone().unwrap_or_default(),
+    )
+        .into_response();
+    response
+        .headers_mut()
+        .insert(IDEMPOTENT_REPLAY_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+/// `202 Accepted` pointing at the polling endpoint
+fn accepted(key: &str) -> Response<Body> {
+    let location = format!("/requests/{}", key);
+    let mut response = (
+        StatusCode::ACCEPTED,
+        Json(RequestStatusResponse {
+            idempotency_key: key.to_string(),
+            status: JournalStatus::Processing,
+            location: location.clone(),
+        }),
+    )
+        .into_response();
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&location) {
+        headers.insert(header::LOCATION, value);
+    }
+    headers.insert(header::RETRY_AFTER, HeaderValue::from_static("1"));
+    response
+}
+
+/// Idempotency middleware for POST routes. Requests without an `Idempotency-Key`
+/// header, or when no journal is configured, pass through unchanged.
+/// Must run after auth so the key can be scoped to the caller.
+pub async fn idempotency_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request<Body>,
+    next: Next,
+) -> Response<Body> {
+    if request.method() != axum::http::Method::POST {
+        return next.run(request).await;
+    }
+    let Some(journal) = state.request_journal.clone() else {
+        return next.run(request).await;
+    };
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return next.run(request).await;
+    };
+    let Some(key) = key.to_str().ok().filter(|k| is_valid_key(k)) else {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "invalid_idempotency_key",
+            format!(
+                "Idempotency-Key must be 1-{} visible ASCII characters",
+                MAX_KEY_LEN
+            ),
+        );
+    };
+    let key = key.to_string();
+
+    let (parts, body) = request.into_parts();
+    let Ok(body) = to_bytes(body, MAX_JOURNALED_BODY_BYTES).await else {
+        return error_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "payload_too_large",
+            "Request body too large".to_string(),
+        );
+    };
+    let stored_key = journal_key(parts.extensions.get::<Principal>(), &key);
+    // Nested routers strip their prefix, so hash the path the client actually called
+    let path = parts.extensions.get::<OriginalUri>().map_or_else(
+        || parts.uri.path().to_string(),
+        |uri| uri.path().to_string(),
+    );
+    let hash = request_hash(parts.method.as_str(), &path, &body);
+    let reclaim_before = chrono::Utc::now()
+        - chrono::Duration::from_std(PROCESSING_LEASE).unwrap_or(chrono::Duration::zero());
+
+    match journal
+        .begin_request(&stored_key, &hash, reclaim_before)
+        .await
+    {
+        Err(e) => e.into_response(),
+        Ok(Some(entry)) if entry.request_hash != hash => error_response(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "idempotency_key_reused",
+            "Idempotency-Key was already used for a different request".to_string(),
+        ),
+        Ok(Some(entry)) => match entry.status {
+            JournalStatus::Completed => {
+                info!(idempotency_key = %key, "Replaying journaled response");
+                replay(&entry)
+            }
+            JournalStatus::Processing => accepted(&key),
+        },
+        Ok(None) => {
+            let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+            record(journal.as_ref(), &stored_key, response).await
+        }
+    }
+}
+
+/// Store a non-5xx response in the journal and return it; abandon the entry otherwise
+async fn record(
+    journal: &dyn RequestJournal,
+    key: &str,
+    response: Response<Body>,
+) -> Response<Body> {
+    let status = response.status();
+    if status.is_server_error() {
+        if let Err(e) = journal.abandon_request(key).await {
+            error!(error = %e, "Failed to abandon journaled request");
+        }
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body: Bytes = match to_bytes(body, MAX_JOURNALED_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(e) => {
+            error!(error = %e, "Failed to buffer response for the request journal");
+            if let Err(e) = journal.abandon_request(key).await {
+                error!(error = %e, "Failed to abandon journaled request");
+            }
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal_error",
+                "Internal server error".to_string(),
+            );
+        }
+    };
+    if let Err(e) = journal
+        .complete_request(key, status.as_u16(), &String::from_utf8_lossy(&body))
+        .await
+    {
+        // The request already succeeded; a retry after the lease re-executes it
+        error!(error = %e, "Failed to store journaled response");
+    }
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Poll the outcome of a request sent with an `Idempotency-Key`
+#[utoipa::path(
+    get,
+    path = "/requests/{key}",
+    tag = "items",
+    params(
+        ("key" = String, Path, description = "Idempotency-Key of the original request")
+    ),
+    responses(
+        (status = 200, description = "Stored response of the completed request (any non-5xx status is replayed as-is)"),
+        (status = 202, description = "Request is still processing", body = RequestStatusResponse),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the items:write scope"),
+        (status = 404, description = "No request with this key", body = ErrorResponse),
+        (status = 503, description = "Request journal not configured", body = ErrorResponse)
+    )
+)]
+pub async fn get_request_status_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(principal): Extension<Principal>,
+    ApiPath(key): ApiPath<String>,
+) -> Result<Response<Body>, RequestJournalError> {
+    let entry = journal(&state)?
+        .get_request(&journal_key(Some(&principal), &key))
+        .await?;
+    Ok(match entry {
+        Some(entry) if entry.status == JournalStatus::Completed => replay(&entry),
+        Some(_) => accepted(&key),
+        None => error_response(
+            StatusCode::NOT_FOUND,
+            "not_found",
+            format!("No request with idempotency key: {}", key),
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::create_router;
+    use crate::test_utils::MockProvider;
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    const ITEM_BODY: &str = r#"{"name":"Test","content":"x"}"#;
+
+    fn router_with_journal() -> (axum::Router, Arc<AppState>, Arc<MockProvider>) {
+        let journal = Arc::new(MockProvider::new());
+        let state = Arc::try_unwrap(AppState::new_for_test()).ok().unwrap();
+        let state = Arc::new(state.with_request_journal(journal.clone()));
+        (create_router(Arc::clone(&state)), state, journal)
+    }
+
+    fn post_item(key: &str, body: &'static str) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/items")
+            .header("Content-Type", "application/json")
+            .header("x-api-key", "test-api-key")
+            .header(IDEMPOTENCY_KEY_HEADER, key)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    fn poll(key: &str) -> Request<Body> {
+        Request::builder()
+            .uri(format!("/requests/{}", key))
+            .header("x-api-key", "test-api-key")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    async fn body_string(response: Response<Body>) -> String {
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    async fn item_count(state: &AppState) -> usize {
+        state
+            .service
+            .list_items(100, None, &Default::default())
+            .await
+            .unwrap()
+            .items
+            .len()
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_same_key_replays_response() {
+        let (router, state, _) = router_with_journal();
+
+        let first = router
+            .clone()
+            .oneshot(post_item("order-1", ITEM_BODY))
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        assert!(first.headers().get(IDEMPOTENT_REPLAY_HEADER).is_none());
+        let first_body = body_string(first).await;
+
+        let retry = router
+            .clone()
+            .oneshot(post_item("order-1", ITEM_BODY))
+            .await
+            .unwrap();
+        assert_eq!(retry.status(), StatusCode::OK);
+        assert_eq!(retry.headers()[IDEMPOTENT_REPLAY_HEADER], "true");
+        assert_eq!(body_string(retry).await, first_body);
+        assert_eq!(item_count(&state).await, 1);
+
+        let polled = router.oneshot(poll("order-1")).await.unwrap();
+        assert_eq!(polled.status(), StatusCode::OK);
+        assert_eq!(body_string(polled).await, first_body);
+    }
+
+    #[tokio::test]
+    async fn test_reused_key_with_different_body_is_rejected() {
+        let (router, _, _) = router_with_journal();
+        router
+            .clone()
+            .oneshot(post_item("order-2", ITEM_BODY))
+            .await
+            .unwrap();
+
+        let response = router
+            .oneshot(post_item("order-2", r#"{"name":"Other","content":"y"}"#))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_request_returns_accepted_with_location() {
+        let (router, state, journal) = router_with_journal();
+        let stored_key = journal_key(Some(&Principal::bootstrap()), "order-3");
+        let hash = request_hash("POST", "/items", ITEM_BODY.as_bytes());
+        journal
+            .begin_request(&stored_key, &hash, chrono::Utc::now())
+            .await
+            .unwrap();
+
+        let response = router
+            .clone()
+            .oneshot(post_item("order-3", ITEM_BODY))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        assert_eq!(response.headers()[header::LOCATION], "/requests/order-3");
+        assert_eq!(item_count(&state).await, 0);
+
+        let polled = router.clone().oneshot(poll("order-3")).await.unwrap();
+        assert_eq!(polled.status(), StatusCode::ACCEPTED);
+
+        journal
+            .complete_request(&stored_key, 200, r#"{"id":"item_done"}"#)
+            .await
+            .unwrap();
+        let polled = router.oneshot(poll("order-3")).await.unwrap();
+        assert_eq!(polled.status(), StatusCode::OK);
+        assert_eq!(body_string(polled).await, r#"{"id":"item_done"}"#);
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_request_is_reclaimed_after_lease() {
+        let journal = MockProvider::new();
+        let now = chrono::Utc::now();
+        assert!(
+            journal
+                .begin_request("k", "hash", now)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        // Still within the lease: the entry is reported as processing
+        let existing = journal.begin_request("k", "hash", now).await.unwrap();
+        assert_eq!(existing.unwrap().status, JournalStatus::Processing);
+        // Lease expired: the retry takes over
+        let reclaim_before = chrono::Utc::now() + chrono::Duration::seconds(1);
+        assert!(
+            journal
+                .begin_request("k", "hash", reclaim_before)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_key_and_invalid_key() {
+        let (router, _, _) = router_with_journal();
+
+        let response = router.clone().oneshot(poll("missing")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = router
+            .oneshot(post_item("bad key", ITEM_BODY))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
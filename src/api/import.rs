@@ -0,0 +1,219 @@
+//! Decoding of `POST /items/import` uploads.
+//!
+//! Uploads use the export formats, so an export can be imported again: NDJSON lines are
+//! item creation requests (extra fields such as `id` are ignored), and CSV files need a
+//! header row naming at least `name` and `content`, with `description` and `metadata`
+//! (a JSON object) optional. Every row keeps the line it starts on for the report; a row
+//! that does not decode fails on its own without stopping the others.
+
+use crate::domain::{
+    CreateItemRequest, ExportFormat, ImportRow, ItemMetadataRequest, ValidationError,
+};
+
+impl ExportFormat {
+    /// Format of an uploaded file: CSV when its content type or file name says so
+    #[must_use]
+    pub fn detect(content_type: Option<&str>, file_name: Option<&str>) -> Self {
+        let csv_type = content_type.is_some_and(|t| t.starts_with("text/csv"));
+        let csv_name = file_name.is_some_and(|n| n.to_ascii_lowercase().ends_with(".csv"));
+        if csv_type || csv_name {
+            Self::Csv
+        } else {
+            Self::Ndjson
+        }
+    }
+
+    /// Rows of an uploaded file (blank lines skipped)
+    pub fn decode(&self, text: &str) -> Result<Vec<ImportRow>, ValidationError> {
+        let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+        match self {
+            Self::Ndjson => Ok(decode_ndjson(text)),
+            Self::Csv => decode_csv(text),
+        }
+    }
+}
+
+fn decode_ndjson(text: &str) -> Vec<ImportRow> {
+    text.lines()
+        .zip(1..)
+        .filter(|(line, _)| !line.trim().is_empty())
+        .map(|(line, number)| ImportRow {
+            line: number,
+            request: serde_json::from_str::<CreateItemRequest>(line)
+                .map_err(|e| ValidationError::InvalidFormat(format!("Invalid JSON: {}", e))),
+        })
+        .collect()
+}
+
+fn decode_csv(text: &str) -> Result<Vec<ImportRow>, ValidationError> {
+    let mut records = csv_records(text).into_iter();
+    let Some((_, header)) = records.next() else {
+        return Ok(Vec::new());
+    };
+    let header: Vec<String> = header
+        .map_err(ValidationError::InvalidFormat)?
+        .iter()
+        .map(|column| column.trim().to_ascii_lowercase())
+        .collect();
+    let position = |name: &str| header.iter().position(|column| column == name);
+    let (Some(name), Some(content)) = (position("name"), position("content")) else {
+        return Err(ValidationError::InvalidFormat(
+            "CSV header must name the `name` and `content` columns".to_string(),
+        ));
+    };
+    let description = position("description");
+    let metadata = position("metadata");
+
+    Ok(records
+        .map(|(line, record)| ImportRow {
+            line,
+            request: record
+                .map_err(ValidationError::InvalidFormat)
+                .and_then(|fields| {
+                    if fields.len() != header.len() {
+                        return Err(ValidationError::InvalidFormat(format!(
+                            "Expected {} fields, found {}",
+                            header.len(),
+                            fields.len()
+                        )));
+                    }
+                    let optional = |column: Option<usize>| {
+                        column
+                            .map(|i| fields[i].clone())
+                            .filter(|value| !value.is_empty())
+                    };
+                    let metadata = optional(metadata)
+                        .map(|json| {
+                            serde_json::from_str::<ItemMetadataRequest>(&json).map_err(|e| {
+                                ValidationError::InvalidField {
+                                    field: "metadata".to_string(),
+                                    message: format!("Invalid metadata JSON: {}", e),
+                                }
+                            })
+                        })
+                        .transpose()?;
+                    Ok(CreateItemRequest {
+                        name: fields[name].clone(),
+                        description: optional(description),
+                        content: fields[content].clone(),
+                        metadata,
+                        content_key: None,
+                        content_type: None,
+                    })
+                }),
+        })
+        .collect())
+}
+
+/// RFC 4180 records with the 1-based line each starts on. Quoted fields may contain
+/// separators, doubled quotes and line breaks; an unterminated quote fails the last record.
+fn csv_records(text: &str) -> Vec<(u64, Result<Vec<String>, String>)> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut start = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            '\n' if quoted => {
+                line += 1;
+                field.push(c);
+            }
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                fields.push(std::mem::take(&mut field));
+                let record = std::mem::take(&mut fields);
+                // Blank lines hold no record
+                if record.len() > 1 || !record[0].is_empty() {
+                    records.push((start, Ok(record)));
+                }
+                line += 1;
+                start = line;
+            }
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        records.push((start, Err("Unterminated quoted field".to_string())));
+    } else if !fields.is_empty() || !field.is_empty() {
+        fields.push(field);
+        records.push((start, Ok(fields)));
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Item;
+
+    #[test]
+    fn test_csv_export_decodes_back_into_requests() {
+        let item = Item {
+            name: "Plain".to_string(),
+            description: Some("Two\nlines".to_string()),
+            content: "a, \"b\"".to_string(),
+            ..Item::default()
+        };
+        let format = ExportFormat::Csv;
+        let file = format!("{}{}", format.preamble(), format.encode(&item));
+
+        let rows = format.decode(&file).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].line, 2);
+        let request = rows[0].request.as_ref().unwrap();
+        assert_eq!(request.name, "Plain");
+        assert_eq!(request.description.as_deref(), Some("Two\nlines"));
+        assert_eq!(request.content, "a, \"b\"");
+        assert!(request.metadata.is_none());
+    }
+
+    #[test]
+    fn test_bad_rows_fail_on_their_own_line() {
+        let rows = ExportFormat::Ndjson
+            .decode("{\"name\":\"a\",\"content\":\"x\"}\n\nnot json\n")
+            .unwrap();
+        assert_eq!(rows.iter().map(|r| r.line).collect::<Vec<_>>(), [1, 3]);
+        assert!(rows[0].request.is_ok());
+        assert!(rows[1].request.is_err());
+
+        let rows = ExportFormat::Csv
+            .decode("name,content\r\nok,x\r\ntoo,many,fields\r\n\"open,x\r\n")
+            .unwrap();
+        assert_eq!(rows.iter().map(|r| r.line).collect::<Vec<_>>(), [2, 3, 4]);
+        assert!(rows[0].request.is_ok());
+        assert!(rows[1].request.is_err());
+        assert!(rows[2].request.is_err());
+
+        assert!(ExportFormat::Csv.decode("title,body\nx,y\n").is_err());
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(
+            ExportFormat::detect(Some("text/csv"), None),
+            ExportFormat::Csv
+        );
+        assert_eq!(
+            ExportFormat::detect(None, Some("Items.CSV")),
+            ExportFormat::Csv
+        );
+        assert_eq!(
+            ExportFormat::detect(Some("application/octet-stream"), Some("items.ndjson")),
+            ExportFormat::Ndjson
+        );
+    }
+}
@@ -1,19 +1,26 @@
 //! HTTP middleware for API layer.
 
 use axum::{
+    Json,
     body::Body,
-    extract::State,
-    http::{Request, Response, StatusCode},
+    extract::{ConnectInfo, MatchedPath, OriginalUri, State},
+    http::{HeaderMap, Method, Request, Response, StatusCode, header},
     middleware::Next,
     response::IntoResponse,
 };
+use chrono::{SecondsFormat, Utc};
 use secrecy::ExposeSecret;
 use sha2::{Digest, Sha256};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::Arc;
 use std::time::Instant;
-use tracing::warn;
+use tracing::{error, info, warn};
 
-use crate::app::AppState;
+use super::request_id::current_request_id;
+use crate::app::api_keys::resolve_api_key;
+use crate::app::{AbuseSubject, Access, AppState, Violation};
+use crate::domain::{ApiKeyScope, AuditActor, ErrorDetail, ErrorResponse, Principal, TenantScope};
+use crate::infra::AUDIT_LOG_TARGET;
 
 /// Constant-time comparison of two byte slices to prevent timing attacks.
 fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
@@ -27,43 +34,504 @@ fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
     result == 0
 }
 
-/// API key authentication middleware.
-/// Protects POST endpoints by requiring a valid `x-api-key` header.
-/// GET requests pass through without authentication.
-/// Uses constant-time comparison (via SHA-256 digest) to prevent timing attacks.
-pub async fn auth_middleware(
+/// CV-02 remediation: Extract client IP for rate limiting and blocklisting.
+/// Prioritizes ConnectInfo (from axum into_make_service_with_connect_info) as the
+/// source of truth so that spoofed X-Forwarded-For / X-Real-IP cannot bypass limits.
+/// Headers are only used when trust_proxy_headers is true (e.g. behind a trusted proxy).
+pub(crate) fn client_ip_from_request<B>(request: &Request<B>, trust_proxy_headers: bool) -> IpAddr {
+    // Source of truth: connection peer from the TCP layer (not spoofable).
+    if let Some(connect_info) = request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        return connect_info.0.ip();
+    }
+    if let Some(addr) = request.extensions().get::<SocketAddr>() {
+        return addr.ip();
+    }
+    // Only use headers when explicitly configured to trust upstream proxies.
+    if trust_proxy_headers {
+        if let Some(forwarded) = request.headers().get("x-forwarded-for")
+            && let Ok(s) = forwarded.to_str()
+            && let Some(first) = s.split(',').next()
+        {
+            let trimmed = first.trim();
+            if let Ok(ip) = trimmed.parse::<IpAddr>() {
+                return ip;
+            }
+        }
+        if let Some(real_ip) = request.headers().get("x-real-ip")
+            && let Ok(s) = real_ip.to_str()
+            && let Ok(ip) = s.trim().parse::<IpAddr>()
+        {
+            return ip;
+        }
+    }
+    // Fallback: unknown clients share one bucket (prevents total global DoS).
+    IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+}
+
+/// Resolve the `x-api-key` header to a principal.
+/// The bootstrap key is compared via SHA-256 digests in constant time to prevent timing
+/// attacks; any other key is looked up by hash in the managed key store (if configured).
+pub(crate) async fn authenticate(state: &AppState, headers: &HeaderMap) -> Option<Principal> {
+    let Some(provided) = headers.get("x-api-key").and_then(|v| v.to_str().ok()) else {
+        warn!("API auth failed: missing x-api-key header");
+        return None;
+    };
+
+    let expected_hash = Sha256::digest(state.api_auth_key.expose_secret().as_bytes());
+    let provided_hash = Sha256::digest(provided.as_bytes());
+    if constant_time_eq(expected_hash.as_slice(), provided_hash.as_slice()) {
+        // A separate admin token takes the admin scope away from the bootstrap key
+        return Some(match state.admin_auth_key {
+            Some(_) => Principal::bootstrap().without_scope(ApiKeyScope::Admin),
+            None => Principal::bootstrap(),
+        });
+    }
+    if let Some(admin_key) = &state.admin_auth_key {
+        let admin_hash = Sha256::digest(admin_key.expose_secret().as_bytes());
+        if constant_time_eq(admin_hash.as_slice(), provided_hash.as_slice()) {
+            return Some(Principal::admin_token());
+        }
+    }
+
+    if let Some(store) = &state.api_key_store {
+        match resolve_api_key(store.as_ref(), provided).await {
+            Ok(Some(principal)) => return Some(principal),
+            Ok(None) => {}
+            Err(e) => {
+                error!(error = %e, "API auth failed: key store lookup failed");
+                return None;
+            }
+        }
+    }
+
+    warn!("API auth failed: invalid x-api-key");
+    None
+}
+
+/// Authorization middleware: looks up the access the request needs in the configured
+/// [`AuthPolicy`](crate::app::AuthPolicy) and, unless the route is public, authenticates
+/// it and checks the scope, attaching the [`Principal`] to request extensions on success.
+/// Applied inside nested routers, so the path is taken from [`OriginalUri`].
+pub async fn policy_middleware(
+    State(state): State<Arc<AppState>>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response<Body> {
+    let uri = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map_or_else(|| request.uri().clone(), |original| original.0.clone());
+    let access = state
+        .auth_policy
+        .access(request.method().as_str(), uri.path(), uri.query());
+    if access == Access::Public {
+        return next.run(request).await;
+    }
+
+    let Some(principal) = authenticate(&state, request.headers()).await else {
+        return (StatusCode::UNAUTHORIZED, "Unauthorized").into_response();
+    };
+    if let Access::Scope(scope) = access
+        && !principal.has_scope(scope)
+    {
+        warn!(key_id = %principal.key_id, scope = %scope, "API auth failed: missing scope");
+        return (StatusCode::FORBIDDEN, "Forbidden").into_response();
+    }
+
+    request.extensions_mut().insert(principal);
+    next.run(request).await
+}
+
+/// Tenant isolation: runs the request confined to the caller's tenant (see
+/// [`TenantScope::for_principal`]), so repositories only see that tenant's items.
+/// Applied inside the policy middleware and reuses its [`Principal`]; on public routes a
+/// key that was sent anyway is still resolved, so its tenant applies. The same caller is
+/// the [`AuditActor`] the audit log attributes the request's writes to.
+pub async fn tenant_middleware(
     State(state): State<Arc<AppState>>,
     request: Request<Body>,
     next: Next,
 ) -> Response<Body> {
-    // Only protect POST requests
-    if request.method() != axum::http::Method::POST {
+    let principal = match request.extensions().get::<Principal>() {
+        Some(principal) => Some(principal.clone()),
+        None if request.headers().contains_key("x-api-key") => {
+            authenticate(&state, request.headers()).await
+        }
+        None => None,
+    };
+    let actor = AuditActor::for_principal(principal.as_ref(), current_request_id());
+    TenantScope::scope(
+        TenantScope::for_principal(principal.as_ref()),
+        AuditActor::scope(actor, next.run(request)),
+    )
+    .await
+}
+
+/// Header with the requests left in the caller's tighter quota period
+pub const QUOTA_REMAINING_HEADER: &str = "x-quota-remaining";
+
+/// Quota middleware: counts the request against the caller's key in the usage ledger and
+/// rejects it with 429 `quota_exceeded` once the key's daily or monthly quota is used up,
+/// with a `Retry-After` until the period ends. Responses of keys with a quota carry
+/// `X-Quota-Remaining`. Applied inside the policy middleware and reuses its
+/// [`Principal`]; on public routes a key that was sent anyway is still counted, and the
+/// principal it resolves to is stored for the tenant middleware.
+/// Anonymous requests are not counted, and when the ledger fails the request goes
+/// through uncounted.
+pub async fn quota_middleware(
+    State(state): State<Arc<AppState>>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response<Body> {
+    let principal = match request.extensions().get::<Principal>() {
+        Some(principal) => Some(principal.clone()),
+        None if request.headers().contains_key("x-api-key") => {
+            let principal = authenticate(&state, request.headers()).await;
+            // Keep it for the tenant scope so the key is looked up once per request
+            if let Some(principal) = &principal {
+                request.extensions_mut().insert(principal.clone());
+            }
+            principal
+        }
+        None => None,
+    };
+    let (Some(ledger), Some(principal)) = (&state.usage_ledger, principal) else {
         return next.run(request).await;
+    };
+    let now = Utc::now();
+    let usage = match ledger
+        .record_request(&principal.key_id, now.date_naive())
+        .await
+    {
+        Ok(usage) => usage,
+        Err(e) => {
+            metrics::counter!("quota_check_failures_total").increment(1);
+            error!(key_id = %principal.key_id, error = %e, "Failed to count request against quota");
+            return next.run(request).await;
+        }
+    };
+
+    let quota = principal.quota;
+    if let Some(period) = quota.exceeded(&usage) {
+        metrics::counter!("http_quota_exceeded_total", "period" => period.as_str()).increment(1);
+        warn!(key_id = %principal.key_id, period = period.as_str(), "Request quota exceeded");
+        let resets_at = period.resets_at(now);
+        let body = ErrorResponse {
+            error: ErrorDetail {
+                r#type: "quota_exceeded".to_string(),
+                message: format!(
+                    "The {} request quota of this API key is used up until {}",
+                    period.as_str(),
+                    resets_at.to_rfc3339_opts(SecondsFormat::Secs, true)
+                ),
+                fields: Vec::new(),
+                request_id: current_request_id(),
+            },
+        };
+        let retry_after = (resets_at - now).num_seconds().max(1);
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [
+                (header::RETRY_AFTER, retry_after.to_string()),
+                (
+                    header::HeaderName::from_static(QUOTA_REMAINING_HEADER),
+                    "0".to_string(),
+                ),
+            ],
+            Json(body),
+        )
+            .into_response();
     }
 
-    let api_key_header = request
+    let mut response = next.run(request).await;
+    if let Some(remaining) = quota.remaining(&usage) {
+        response
+            .headers_mut()
+            .insert(QUOTA_REMAINING_HEADER, header::HeaderValue::from(remaining));
+    }
+    response
+}
+
+/// IP deny-list middleware: rejects blocked sources with 403 before auth and rate limiting.
+pub async fn blocklist_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request<Body>,
+    next: Next,
+) -> Response<Body> {
+    let client_ip = client_ip_from_request(&request, state.blocklist.trust_proxy_headers());
+    if state.blocklist.is_blocked(client_ip) {
+        warn!(client_ip = %client_ip, "Request rejected by IP blocklist");
+        metrics::counter!("http_blocked_requests_total").increment(1);
+        let body = ErrorResponse {
+            error: ErrorDetail {
+                r#type: "ip_blocked".to_string(),
+                message: IP_BLOCKED_MESSAGE.to_string(),
+                fields: Vec::new(),
+                request_id: current_request_id(),
+            },
+        };
+        return (StatusCode::FORBIDDEN, Json(body)).into_response();
+    }
+
+    next.run(request).await
+}
+
+/// Automatic temporary bans: requests from a banned address or with a banned API key get
+/// 403 `temporarily_banned` with a `Retry-After`; otherwise `401` and `429` responses are
+/// counted against the address and the key, and crossing a threshold bans both for
+/// [`AbuseConfig::ban_duration`](crate::app::AbuseConfig). Runs right inside the blocklist,
+/// so it sees every rejection from auth and rate limiting.
+pub async fn abuse_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request<Body>,
+    next: Next,
+) -> Response<Body> {
+    if !state.abuse.is_enabled() {
+        return next.run(request).await;
+    }
+    let client_ip = client_ip_from_request(&request, state.blocklist.trust_proxy_headers());
+    // An unknown address stands for every client without one; banning it would ban them all
+    let mut subjects = Vec::with_capacity(2);
+    if !client_ip.is_unspecified() {
+        subjects.push(AbuseSubject::Ip(client_ip));
+    }
+    if let Some(key) = request
         .headers()
         .get("x-api-key")
-        .and_then(|v| v.to_str().ok());
+        .and_then(|v| v.to_str().ok())
+    {
+        subjects.push(AbuseSubject::api_key(key));
+    }
 
-    let Some(provided) = api_key_header else {
-        warn!("API auth failed: missing x-api-key header");
-        return (StatusCode::UNAUTHORIZED, "Unauthorized").into_response();
+    if let Some(ban) = state.abuse.ban_for(&subjects) {
+        metrics::counter!("http_banned_requests_total").increment(1);
+        let retry_after = (ban.expires_at - chrono::Utc::now()).num_seconds().max(1);
+        let body = ErrorResponse {
+            error: ErrorDetail {
+                r#type: "temporarily_banned".to_string(),
+                message: format!(
+                    "Too many failed or rate-limited requests; banned until {}",
+                    ban.expires_at.to_rfc3339()
+                ),
+                fields: Vec::new(),
+                request_id: current_request_id(),
+            },
+        };
+        return (
+            StatusCode::FORBIDDEN,
+            [(header::RETRY_AFTER, retry_after.to_string())],
+            Json(body),
+        )
+            .into_response();
+    }
+
+    let response = next.run(request).await;
+    let violation = match response.status() {
+        StatusCode::UNAUTHORIZED => Violation::Unauthorized,
+        StatusCode::TOO_MANY_REQUESTS => Violation::RateLimited,
+        _ => return response,
     };
+    for subject in subjects {
+        if let Some(ban) = state.abuse.record(subject, violation) {
+            warn!(
+                target: AUDIT_LOG_TARGET,
+                subject = %ban.subject,
+                reason = %ban.reason,
+                expires_at = %ban.expires_at.to_rfc3339(),
+                "Temporary ban applied"
+            );
+            metrics::counter!("abuse_bans_total", "reason" => violation.as_str()).increment(1);
+        }
+    }
+    response
+}
 
-    let expected = state.api_auth_key.expose_secret().as_bytes();
-    let provided_bytes = provided.as_bytes();
+/// Schema guard: while the database schema does not match this build's migrations, only
+/// reads (GET/HEAD/OPTIONS) pass; writes get 503 `migrations_pending`. In maintenance mode
+/// writes get 503 `maintenance` with a `Retry-After`, except under `/admin` so the mode can
+/// be switched off.
+pub async fn schema_guard_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request<Body>,
+    next: Next,
+) -> Response<Body> {
+    let read_only = matches!(
+        *request.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS
+    );
+    let admin = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map_or_else(|| request.uri().path(), |original| original.0.path())
+        .starts_with("/admin");
+    if !read_only && !admin && state.maintenance_enabled() {
+        metrics::counter!("http_writes_rejected_maintenance_total").increment(1);
+        let body = ErrorResponse {
+            error: ErrorDetail {
+                r#type: "maintenance".to_string(),
+                message: MAINTENANCE_MESSAGE.to_string(),
+                fields: Vec::new(),
+                request_id: current_request_id(),
+            },
+        };
+        let retry_after = state.maintenance_retry_after.as_secs().max(1);
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::RETRY_AFTER, retry_after.to_string())],
+            Json(body),
+        )
+            .into_response();
+    }
+    if !read_only && !state.schema_status.is_current() {
+        metrics::counter!("http_writes_rejected_migrations_pending_total").increment(1);
+        let body = ErrorResponse {
+            error: ErrorDetail {
+                r#type: "migrations_pending".to_string(),
+                message: MIGRATIONS_PENDING_MESSAGE.to_string(),
+                fields: Vec::new(),
+                request_id: current_request_id(),
+            },
+        };
+        return (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response();
+    }
 
-    // Compare via SHA-256 digests for constant-time comparison (prevents timing attacks)
-    let expected_hash = Sha256::digest(expected);
-    let provided_hash = Sha256::digest(provided_bytes);
+    next.run(request).await
+}
 
-    if !constant_time_eq(expected_hash.as_slice(), provided_hash.as_slice()) {
-        warn!("API auth failed: invalid x-api-key");
-        return (StatusCode::UNAUTHORIZED, "Unauthorized").into_response();
+/// Structured 413 for bodies over a route's limit: `RequestBodyLimitLayer` and the body
+/// extractors answer with plain text, which is replaced by an [`ErrorResponse`] of type
+/// `payload_too_large`. JSON 413s (e.g. from the idempotency journal) pass unchanged.
+pub async fn payload_too_large_middleware(request: Request<Body>, next: Next) -> Response<Body> {
+    let response = next.run(request).await;
+    let json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|v| v.as_bytes().starts_with(b"application/json"));
+    if response.status() != StatusCode::PAYLOAD_TOO_LARGE || json {
+        return response;
     }
+    metrics::counter!("http_payload_too_large_total").increment(1);
+    let body = ErrorResponse {
+        error: ErrorDetail {
+            r#type: "payload_too_large".to_string(),
+            message: PAYLOAD_TOO_LARGE_MESSAGE.to_string(),
+            fields: Vec::new(),
+            request_id: current_request_id(),
+        },
+    };
+    (StatusCode::PAYLOAD_TOO_LARGE, Json(body)).into_response()
+}
 
-    next.run(request).await
+/// Message of requests rejected by [`blocklist_middleware`]
+pub(crate) const IP_BLOCKED_MESSAGE: &str = "Requests from this address are blocked";
+
+/// Message of bodies rejected by a route's size limit
+pub(crate) const PAYLOAD_TOO_LARGE_MESSAGE: &str =
+    "Request body exceeds the size limit of this route";
+
+/// Message for writes rejected by [`schema_guard_middleware`]
+pub(crate) const MIGRATIONS_PENDING_MESSAGE: &str =
+    "Database schema does not match this version's migrations; writes are disabled";
+
+/// Message for writes rejected while maintenance mode is on
+pub(crate) const MAINTENANCE_MESSAGE: &str =
+    "The service is in maintenance mode; writes are temporarily disabled";
+
+/// Audit trail of admin actions: every request that passed authorization is written to the
+/// `audit` log target with the caller's key ID, method, path and response status, and
+/// counted in `admin_actions_total`. Successful changes (anything but `GET`) are also
+/// recorded in the audit log under the action from [`admin_audit_action`]. Applied inside
+/// the policy middleware, which attaches the [`Principal`].
+pub async fn admin_audit_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request<Body>,
+    next: Next,
+) -> Response<Body> {
+    let principal = request.extensions().get::<Principal>().cloned();
+    let key_id = principal
+        .as_ref()
+        .map_or_else(|| "-".to_string(), |principal| principal.key_id.clone());
+    let method = request.method().clone();
+    let path = request.extensions().get::<OriginalUri>().map_or_else(
+        || request.uri().path().to_string(),
+        |original| original.0.path().to_string(),
+    );
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched| matched.as_str().to_string());
+
+    let response = next.run(request).await;
+    let status = response.status().as_u16();
+    let outcome = if response.status().is_success() {
+        "success"
+    } else {
+        "failure"
+    };
+    info!(
+        target: AUDIT_LOG_TARGET,
+        key_id = %key_id,
+        method = %method,
+        path = %path,
+        status,
+        outcome,
+        request_id = current_request_id().as_deref().unwrap_or("-"),
+        "Admin action"
+    );
+    metrics::counter!(
+        "admin_actions_total",
+        "method" => method.to_string(),
+        "outcome" => outcome
+    )
+    .increment(1);
+
+    if response.status().is_success()
+        && method != Method::GET
+        && let Some(route) = route
+    {
+        let (action, resource_id) = admin_audit_action(&method, &route, &path);
+        let actor = AuditActor::for_principal(principal.as_ref(), current_request_id());
+        AuditActor::scope(
+            actor,
+            state
+                .service
+                .record_audit(&action, resource_id.as_deref(), None),
+        )
+        .await;
+    }
+    response
+}
+
+/// Audit log action and resource ID of an admin request to `path`, matched by `route`:
+/// e.g. `api_key.revoke` and the key ID for `DELETE /admin/api-keys/{id}`. Routes without
+/// a name of their own fall back to `admin.<method>` with the route.
+fn admin_audit_action(method: &Method, route: &str, path: &str) -> (String, Option<String>) {
+    // The segment of `path` where `route` has its (single) parameter
+    let resource_id = route
+        .split('/')
+        .zip(path.split('/'))
+        .find(|(pattern, _)| pattern.starts_with('{'))
+        .map(|(_, value)| value.to_string());
+    let admin_route = route.rsplit_once("/admin").map_or(route, |(_, rest)| rest);
+    let action = match (method.as_str(), admin_route) {
+        ("PUT", "/blocklist") => "blocklist.update",
+        ("DELETE", "/bans/{subject}") => "ban.lift",
+        ("POST", "/api-keys") => "api_key.create",
+        ("DELETE", "/api-keys/{id}") => "api_key.revoke",
+        ("POST", "/api-keys/{id}/rotate") => "api_key.rotate",
+        ("PUT", "/maintenance") => "maintenance.update",
+        ("POST", "/worker/run-now") => "worker.run",
+        ("POST", "/dlq/requeue") => "dlq.requeue_all",
+        ("POST", "/dlq/{id}/requeue") => "dlq.requeue",
+        ("POST", "/encryption/reencrypt") => "encryption.reencrypt",
+        _ => {
+            return (
+                format!("admin.{} {admin_route}", method.as_str().to_lowercase()),
+                resource_id,
+            );
+        }
+    };
+    (action.to_string(), resource_id)
 }
 
 /// HTTP metrics middleware: records request count and duration for Grafana.
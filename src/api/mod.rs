@@ -1,8 +1,28 @@
 //! The API layer, containing web handlers and routing.
 
+pub mod conditional;
+pub mod docs;
+pub mod examples;
+pub mod export;
+pub mod extract;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod handlers;
+pub mod idempotency;
+pub mod import;
 pub mod middleware;
+pub mod openapi;
+pub mod rate_limit_store;
+pub mod request_id;
 pub mod router;
+pub mod security;
+pub mod typescript;
+pub mod ws;
 
 pub use handlers::ApiDoc;
-pub use router::{RateLimitConfig, create_router, create_router_with_rate_limit};
+pub use openapi::{OpenApiConfig, OpenApiServer};
+pub use router::{RateLimitConfig, create_router, create_router_with_rate_limit, serve};
+pub use security::ApiSecurity;
+pub use typescript::typescript_types;
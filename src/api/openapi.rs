@@ -0,0 +1,251 @@
+//! Deployment-specific OpenAPI metadata.
+//!
+//! [`ApiDoc`] carries the template's defaults; [`OpenApiConfig`] overrides title, description,
+//! contact and `servers` per environment so forks don't have to edit the derive attributes.
+//! One set of variables can describe every deployment: `OPENAPI_SERVERS_<ENVIRONMENT>` is
+//! preferred over `OPENAPI_SERVERS` for the environment in `OPENAPI_ENVIRONMENT`.
+
+use utoipa::OpenApi;
+use utoipa::openapi::{
+    self, Contact,
+    server::{Server, ServerVariableBuilder},
+};
+
+use super::handlers::ApiDoc;
+
+/// A server entry for the OpenAPI `servers` section
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenApiServer {
+    /// Base URL; `{name}` placeholders become server variables (e.g. `https://{tenant}.example.com`)
+    pub url: String,
+    pub description: Option<String>,
+}
+
+/// Overrides for the generated OpenAPI document (unset fields keep the [`ApiDoc`] defaults)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpenApiConfig {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// Deployment environment shown after the title (e.g. `staging`)
+    pub environment: Option<String>,
+    pub contact_name: Option<String>,
+    pub contact_email: Option<String>,
+    pub contact_url: Option<String>,
+    /// Servers in the order clients should try them (empty: no `servers` section)
+    pub servers: Vec<OpenApiServer>,
+    /// Default values for `{name}` placeholders in server URLs
+    pub server_variables: Vec<(String, String)>,
+}
+
+impl OpenApiConfig {
+    /// Create config from environment variables
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let environment = var("OPENAPI_ENVIRONMENT");
+        let servers = environment
+            .as_deref()
+            .and_then(|environment| var(&Self::servers_var(environment)))
+            .or_else(|| var("OPENAPI_SERVERS"));
+        Self {
+            title: var("OPENAPI_TITLE"),
+            description: var("OPENAPI_DESCRIPTION"),
+            environment,
+            contact_name: var("OPENAPI_CONTACT_NAME"),
+            contact_email: var("OPENAPI_CONTACT_EMAIL"),
+            contact_url: var("OPENAPI_CONTACT_URL"),
+            servers: servers.map(|v| Self::parse_servers(&v)).unwrap_or_default(),
+            server_variables: var("OPENAPI_SERVER_VARIABLES")
+                .map(|v| Self::parse_server_variables(&v))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Variable with the servers of `environment`: `OPENAPI_SERVERS_` and its name
+    /// upper-cased, other characters than letters and digits replaced by `_`
+    #[must_use]
+    pub fn servers_var(environment: &str) -> String {
+        let suffix: String = environment
+            .trim()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("OPENAPI_SERVERS_{}", suffix)
+    }
+
+    /// Parse `url|description,url` (description optional)
+    #[must_use]
+    pub fn parse_servers(value: &str) -> Vec<OpenApiServer> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| match entry.split_once('|') {
+                Some((url, description)) => OpenApiServer {
+                    url: url.trim().to_string(),
+                    description: Some(description.trim().to_string()),
+                },
+                None => OpenApiServer {
+                    url: entry.to_string(),
+                    description: None,
+                },
+            })
+            .collect()
+    }
+
+    /// Parse `name=default,name=default`; entries without `=` are ignored
+    #[must_use]
+    pub fn parse_server_variables(value: &str) -> Vec<(String, String)> {
+        value
+            .split(',')
+            .filter_map(|entry| entry.split_once('='))
+            .map(|(name, default)| (name.trim().to_string(), default.trim().to_string()))
+            .filter(|(name, _)| !name.is_empty())
+            .collect()
+    }
+
+    /// The [`ApiDoc`] document with these overrides applied
+    #[must_use]
+    pub fn document(&self) -> openapi::OpenApi {
+        let mut doc = ApiDoc::openapi();
+        self.apply(&mut doc);
+        doc
+    }
+
+    /// Apply the overrides to an existing document
+    pub fn apply(&self, doc: &mut openapi::OpenApi) {
+        if let Some(title) = &self.title {
+            doc.info.title = title.clone();
+        }
+        if let Some(environment) = &self.environment {
+            doc.info.title = format!("{} ({})", doc.info.title, environment);
+        }
+        if let Some(description) = &self.description {
+            doc.info.description = Some(description.clone());
+        }
+        if self.contact_name.is_some() || self.contact_email.is_some() || self.contact_url.is_some()
+        {
+            let contact = doc.info.contact.get_or_insert_with(Contact::new);
+            if let Some(name) = &self.contact_name {
+                contact.name = Some(name.clone());
+            }
+            if let Some(email) = &self.contact_email {
+                contact.email = Some(email.clone());
+            }
+            if let Some(url) = &self.contact_url {
+                contact.url = Some(url.clone());
+            }
+        }
+        if !self.servers.is_empty() {
+            doc.servers = Some(self.servers.iter().map(|s| self.server(s)).collect());
+        }
+    }
+
+    fn server(&self, server: &OpenApiServer) -> Server {
+        let mut built = Server::new(&server.url);
+        built.description = server.description.clone();
+        for name in placeholders(&server.url) {
+            let default = self
+                .server_variables
+                .iter()
+                .find(|(var, _)| *var == name)
+                .map_or_else(|| name.clone(), |(_, default)| default.clone());
+            built.variables.get_or_insert_with(Default::default).insert(
+                name,
+                ServerVariableBuilder::new().default_value(default).build(),
+            );
+        }
+        built
+    }
+}
+
+/// `{name}` placeholders in a server URL, in order of appearance
+fn placeholders(url: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = url;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + len];
+        if !name.is_empty() && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+        rest = &rest[start + len + 1..];
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_keeps_api_doc_metadata() {
+        let doc = OpenApiConfig::default().document();
+        assert!(doc.info == ApiDoc::openapi().info);
+        assert!(doc.servers.is_none());
+    }
+
+    #[test]
+    fn test_overrides_metadata_and_contact() {
+        let config = OpenApiConfig {
+            title: Some("Acme Ledger API".to_string()),
+            environment: Some("staging".to_string()),
+            contact_email: Some("platform@acme.test".to_string()),
+            ..OpenApiConfig::default()
+        };
+        let doc = config.document();
+        assert_eq!(doc.info.title, "Acme Ledger API (staging)");
+        let contact = doc.info.contact.unwrap();
+        assert_eq!(contact.email.as_deref(), Some("platform@acme.test"));
+        // Unset fields keep the ApiDoc defaults
+        assert_eq!(contact.name.as_deref(), Some("API Support"));
+        assert_eq!(doc.info.description, ApiDoc::openapi().info.description);
+    }
+
+    #[test]
+    fn test_servers_with_tenant_variable() {
+        let config = OpenApiConfig {
+            servers: OpenApiConfig::parse_servers(
+                "https://{tenant}.api.acme.test|Tenant API, http://localhost:3000",
+            ),
+            server_variables: OpenApiConfig::parse_server_variables("tenant=demo, bogus"),
+            ..OpenApiConfig::default()
+        };
+        let servers = config.document().servers.unwrap();
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers[0].url, "https://{tenant}.api.acme.test");
+        assert_eq!(servers[0].description.as_deref(), Some("Tenant API"));
+        let json = serde_json::to_value(&servers[0]).unwrap();
+        assert_eq!(json["variables"]["tenant"]["default"], "demo");
+        assert_eq!(servers[1].url, "http://localhost:3000");
+        assert!(servers[1].variables.is_none());
+    }
+
+    #[test]
+    fn test_servers_var_per_environment() {
+        assert_eq!(
+            OpenApiConfig::servers_var("staging"),
+            "OPENAPI_SERVERS_STAGING"
+        );
+        assert_eq!(
+            OpenApiConfig::servers_var(" eu-west.prod "),
+            "OPENAPI_SERVERS_EU_WEST_PROD"
+        );
+    }
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!(
+            placeholders("https://{tenant}.{region}.example.com/{tenant}"),
+            vec!["tenant", "region"]
+        );
+        assert!(placeholders("https://example.com/{").is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+//! Bounded key store for the per-IP rate limiters.
+//!
+//! governor's `DashMapStateStore` keeps one entry per key forever, so a scanner cycling
+//! through source addresses grows it without limit. [`BoundedStateStore`] caps the number of
+//! tracked keys and evicts the least recently used one when full. An evicted key simply
+//! starts over with a full burst, which is what an idle key would get anyway.
+
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+use governor::nanos::Nanos;
+use governor::state::StateStore;
+use lru::LruCache;
+
+/// Default cap on tracked keys per limiter (~100 bytes each)
+pub const DEFAULT_MAX_TRACKED_KEYS: usize = 100_000;
+
+/// Rate limiter state store holding at most `capacity` keys (LRU eviction)
+pub struct BoundedStateStore<K: Hash + Eq> {
+    /// Limiter name, used as the `limiter` label on metrics
+    name: &'static str,
+    states: Mutex<LruCache<K, u64>>,
+}
+
+impl<K: Hash + Eq> BoundedStateStore<K> {
+    #[must_use]
+    pub fn new(name: &'static str, capacity: NonZeroUsize) -> Self {
+        Self {
+            name,
+            states: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Number of keys currently tracked
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.states.lock().unwrap().len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K: Hash + Eq + Clone> StateStore for BoundedStateStore<K> {
+    type Key = K;
+
+    fn measure_and_replace<T, F, E>(&self, key: &K, f: F) -> Result<T, E>
+    where
+        F: Fn(Option<Nanos>) -> Result<(T, Nanos), E>,
+    {
+        let mut states = self.states.lock().unwrap();
+        let previous = states.get(key).copied().map(Nanos::from);
+        // A rejected request keeps the stored state; it still counts as use of the key
+        let (result, next) = f(previous)?;
+        if let Some((evicted, _)) = states.push(key.clone(), next.into())
+            && evicted != *key
+        {
+            metrics::counter!("rate_limiter_evictions_total", "limiter" => self.name).increment(1);
+        }
+        metrics::gauge!("rate_limiter_tracked_keys", "limiter" => self.name)
+            .set(states.len() as f64);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use governor::{Quota, RateLimiter, clock::DefaultClock};
+    use std::net::IpAddr;
+    use std::num::NonZeroU32;
+
+    fn limiter(capacity: usize) -> RateLimiter<IpAddr, BoundedStateStore<IpAddr>, DefaultClock> {
+        RateLimiter::new(
+            Quota::per_hour(NonZeroU32::new(1).unwrap()),
+            BoundedStateStore::new("test", NonZeroUsize::new(capacity).unwrap()),
+            DefaultClock::default(),
+        )
+    }
+
+    fn ip(last: u8) -> IpAddr {
+        IpAddr::from([10, 0, 0, last])
+    }
+
+    #[test]
+    fn test_scanning_addresses_stays_within_capacity() {
+        let limiter = limiter(3);
+        for last in 0..=200 {
+            assert!(limiter.check_key(&ip(last)).is_ok());
+        }
+        // Only the three most recent keys are still limited; older ones were forgotten
+        for last in 198..=200 {
+            assert!(limiter.check_key(&ip(last)).is_err());
+        }
+        assert!(limiter.check_key(&ip(0)).is_ok());
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used_key() {
+        let limiter = limiter(2);
+        assert!(limiter.check_key(&ip(1)).is_ok());
+        assert!(limiter.check_key(&ip(2)).is_ok());
+        // Touch 1 so that 2 is the least recently used
+        assert!(limiter.check_key(&ip(1)).is_err());
+        assert!(limiter.check_key(&ip(3)).is_ok());
+
+        assert!(limiter.check_key(&ip(1)).is_err(), "recently used key kept");
+        assert!(limiter.check_key(&ip(2)).is_ok(), "LRU key was evicted");
+    }
+
+    #[test]
+    fn test_len_tracks_keys() {
+        let store = BoundedStateStore::new("test", NonZeroUsize::new(2).unwrap());
+        assert!(store.is_empty());
+        for last in 1..=3u8 {
+            store
+                .measure_and_replace(&ip(last), |_| Ok::<_, ()>(((), Nanos::from(1u64))))
+                .unwrap();
+        }
+        assert_eq!(store.len(), 2);
+    }
+}
@@ -0,0 +1,123 @@
+//! `X-Request-Id` propagation.
+//!
+//! Every request gets an ID: the client's `X-Request-Id` when it is a plausible token,
+//! otherwise a generated UUID. The ID is recorded on an `http_request` tracing span (so
+//! every log line of the request carries it), attached as a [`RequestId`] extension,
+//! echoed in the response header and included in `ErrorResponse` bodies so users can
+//! quote it when reporting failures.
+
+use std::fmt;
+
+use axum::{
+    body::Body,
+    http::{HeaderValue, Request, Response},
+    middleware::Next,
+};
+use tracing::{Instrument, info_span};
+use uuid::Uuid;
+
+/// Header carrying the request ID (accepted from clients and echoed in responses)
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest client-supplied ID that is kept; longer ones are replaced
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: RequestId;
+}
+
+/// ID of one HTTP request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// A fresh random ID
+    #[must_use]
+    pub fn generate() -> Self {
+        Self(Uuid::new_v4().to_string())
+    }
+
+    /// Accept a client-supplied ID of visible ASCII up to [`MAX_REQUEST_ID_LEN`] characters
+    #[must_use]
+    pub fn from_header(value: &HeaderValue) -> Option<Self> {
+        let value = value.to_str().ok()?;
+        let valid = !value.is_empty()
+            && value.len() <= MAX_REQUEST_ID_LEN
+            && value.bytes().all(|b| b.is_ascii_graphic());
+        valid.then(|| Self(value.to_string()))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// ID of the request being handled by the current task (None outside
+    /// [`request_id_middleware`])
+    #[must_use]
+    pub fn current() -> Option<Self> {
+        CURRENT_REQUEST_ID.try_with(Clone::clone).ok()
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// ID for `ErrorDetail::request_id` of the request being handled
+pub(crate) fn current_request_id() -> Option<String> {
+    RequestId::current().map(|id| id.0)
+}
+
+/// Request ID middleware (outermost layer): accepts or generates the ID and runs the rest
+/// of the stack inside its span and task-local scope.
+pub async fn request_id_middleware(mut request: Request<Body>, next: Next) -> Response<Body> {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(RequestId::from_header)
+        .unwrap_or_else(RequestId::generate);
+    request.extensions_mut().insert(request_id.clone());
+
+    let span = info_span!("http_request", request_id = %request_id);
+    let mut response = CURRENT_REQUEST_ID
+        .scope(request_id.clone(), next.run(request))
+        .instrument(span)
+        .await;
+
+    if let Ok(value) = HeaderValue::from_str(request_id.as_str()) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_header_accepts_tokens() {
+        let id = RequestId::from_header(&HeaderValue::from_static("req-123_abc")).unwrap();
+        assert_eq!(id.as_str(), "req-123_abc");
+    }
+
+    #[test]
+    fn test_from_header_rejects_empty_spaced_and_overlong() {
+        assert!(RequestId::from_header(&HeaderValue::from_static("")).is_none());
+        assert!(RequestId::from_header(&HeaderValue::from_static("a b")).is_none());
+        let long = HeaderValue::from_str(&"a".repeat(MAX_REQUEST_ID_LEN + 1)).unwrap();
+        assert!(RequestId::from_header(&long).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_current_is_scoped() {
+        assert!(RequestId::current().is_none());
+        let id = RequestId::generate();
+        let seen = CURRENT_REQUEST_ID
+            .scope(id.clone(), async { current_request_id() })
+            .await;
+        assert_eq!(seen.as_deref(), Some(id.as_str()));
+    }
+}
@@ -1,37 +1,63 @@
 //! HTTP routing configuration with rate limiting and OpenAPI documentation.
 
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
-use std::num::NonZeroU32;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::num::{NonZeroU32, NonZeroUsize};
 use std::sync::Arc;
 use std::time::Duration;
 
 use axum::{
     Json, Router,
     body::Body,
-    extract::{ConnectInfo, State},
-    http::{Request, Response, StatusCode},
+    extract::{DefaultBodyLimit, State},
+    http::{HeaderName, Method, Request, Response, StatusCode, header},
     middleware::{self, Next},
     response::IntoResponse,
-    routing::{get, post},
+    routing::{delete, get, post},
 };
 use governor::{Quota, RateLimiter};
 use tower::ServiceBuilder;
+use tower::layer::util::{Identity, Stack};
+use tower::util::{Either, option_layer};
 use tower_http::{
+    compression::CompressionLayer,
+    cors::{AllowOrigin, CorsLayer},
+    limit::RequestBodyLimitLayer,
     timeout::TimeoutLayer,
     trace::{DefaultMakeSpan, DefaultOnResponse, TraceLayer},
 };
 use tracing::Level;
-use utoipa::OpenApi;
-use utoipa_swagger_ui::SwaggerUi;
+use utoipa::{Modify, OpenApi};
 
-use crate::app::AppState;
+use crate::app::{AppState, CorsOrigins, CorsPolicy};
 use crate::domain::{ErrorDetail, ErrorResponse, RateLimitResponse};
 
+use super::conditional::conditional_get_middleware;
+use super::docs::docs_routes;
 use super::handlers::{
-    ApiDoc, create_item_handler, get_item_handler, health_check_handler, list_items_handler,
-    liveness_handler, readiness_handler, retry_blockchain_handler,
+    ApiDoc, acknowledge_export_bookmark_handler, create_api_key_handler, create_item_handler,
+    deep_health_handler, delete_item_handler, export_items_handler, get_blocklist_handler,
+    get_item_content_handler, get_item_handler, get_job_handler, get_maintenance_handler,
+    get_queue_depth_handler, get_usage_handler, get_worker_status_handler, health_check_handler,
+    import_items_handler, item_timeline_handler, lift_ban_handler, list_api_keys_handler,
+    list_audit_entries_handler, list_bans_handler, list_dead_letters_handler,
+    list_item_events_handler, list_items_handler, list_submission_attempts_handler,
+    list_tags_handler, list_webhook_deliveries_handler, liveness_handler, readiness_handler,
+    reencrypt_content_handler, requeue_all_dead_letters_handler, requeue_dead_letter_handler,
+    retry_blockchain_handler, revoke_api_key_handler, rotate_api_key_handler,
+    run_worker_now_handler, search_items_handler, set_maintenance_handler,
+    update_blocklist_handler, update_item_handler, verify_item_handler, verify_receipt_handler,
 };
-use super::middleware::{auth_middleware, metrics_middleware};
+use super::idempotency::{get_request_status_handler, idempotency_middleware};
+use super::middleware::{
+    QUOTA_REMAINING_HEADER, abuse_middleware, admin_audit_middleware, blocklist_middleware,
+    client_ip_from_request, metrics_middleware, payload_too_large_middleware, policy_middleware,
+    quota_middleware, schema_guard_middleware, tenant_middleware,
+};
+use super::rate_limit_store::{BoundedStateStore, DEFAULT_MAX_TRACKED_KEYS};
+use super::request_id::{current_request_id, request_id_middleware};
+use super::security::ApiSecurity;
+use super::ws::live_feed_handler;
 
 /// Rate limiter configuration
 #[derive(Debug, Clone)]
@@ -47,6 +73,8 @@ pub struct RateLimitConfig {
     /// CV-02: If true, allow using X-Forwarded-For / X-Real-IP when ConnectInfo is missing.
     /// Default false (safe): only use ConnectInfo so rate limiting cannot be bypassed by spoofed headers.
     pub trust_proxy_headers: bool,
+    /// Client addresses tracked per limiter before the least recently seen is forgotten
+    pub max_tracked_keys: usize,
 }
 
 impl Default for RateLimitConfig {
@@ -57,6 +85,7 @@ impl Default for RateLimitConfig {
             health_rps: 100,
             health_burst: 100,
             trust_proxy_headers: false,
+            max_tracked_keys: DEFAULT_MAX_TRACKED_KEYS,
         }
     }
 }
@@ -72,6 +101,11 @@ impl RateLimitConfig {
             .ok()
             .and_then(|v| v.parse().ok())
             .unwrap_or(20);
+        let max_tracked_keys = std::env::var("RATE_LIMIT_MAX_KEYS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_MAX_TRACKED_KEYS);
 
         Self {
             general_rps,
@@ -79,22 +113,18 @@ impl RateLimitConfig {
             health_rps: 100,
             health_burst: 100,
             trust_proxy_headers: false,
+            max_tracked_keys,
         }
     }
 }
 
+/// Per-IP limiter whose memory is bounded by `RateLimitConfig::max_tracked_keys`
+type KeyedLimiter = RateLimiter<IpAddr, BoundedStateStore<IpAddr>, governor::clock::DefaultClock>;
+
 /// Shared rate limiter state (keyed by client IP to prevent global DoS)
 pub struct RateLimitState {
-    items_limiter: governor::RateLimiter<
-        IpAddr,
-        governor::state::keyed::DashMapStateStore<IpAddr>,
-        governor::clock::DefaultClock,
-    >,
-    health_limiter: governor::RateLimiter<
-        IpAddr,
-        governor::state::keyed::DashMapStateStore<IpAddr>,
-        governor::clock::DefaultClock,
-    >,
+    items_limiter: KeyedLimiter,
+    health_limiter: KeyedLimiter,
     config: RateLimitConfig,
 }
 
@@ -105,46 +135,35 @@ impl RateLimitState {
         let health_quota = Quota::per_second(NonZeroU32::new(config.health_rps).unwrap())
             .allow_burst(NonZeroU32::new(config.health_burst).unwrap());
 
+        let max_keys = NonZeroUsize::new(config.max_tracked_keys)
+            .unwrap_or(NonZeroUsize::new(DEFAULT_MAX_TRACKED_KEYS).unwrap());
+        let limiter = |name, quota| {
+            RateLimiter::new(
+                quota,
+                BoundedStateStore::new(name, max_keys),
+                governor::clock::DefaultClock::default(),
+            )
+        };
+
         Self {
-            items_limiter: RateLimiter::dashmap(items_quota),
-            health_limiter: RateLimiter::dashmap(health_quota),
+            items_limiter: limiter("items", items_quota),
+            health_limiter: limiter("health", health_quota),
             config,
         }
     }
 }
 
-/// CV-02 remediation: Extract client IP for rate limiting.
-/// Prioritizes ConnectInfo (from axum into_make_service_with_connect_info) as the
-/// source of truth so that spoofed X-Forwarded-For / X-Real-IP cannot bypass limits.
-/// Headers are only used when trust_proxy_headers is true (e.g. behind a trusted proxy).
-fn client_ip_from_request<B>(request: &Request<B>, trust_proxy_headers: bool) -> IpAddr {
-    // Source of truth: connection peer from the TCP layer (not spoofable).
-    if let Some(connect_info) = request.extensions().get::<ConnectInfo<SocketAddr>>() {
-        return connect_info.0.ip();
-    }
-    if let Some(addr) = request.extensions().get::<SocketAddr>() {
-        return addr.ip();
-    }
-    // Only use headers when explicitly configured to trust upstream proxies.
-    if trust_proxy_headers {
-        if let Some(forwarded) = request.headers().get("x-forwarded-for")
-            && let Ok(s) = forwarded.to_str()
-            && let Some(first) = s.split(',').next()
-        {
-            let trimmed = first.trim();
-            if let Ok(ip) = trimmed.parse::<IpAddr>() {
-                return ip;
-            }
-        }
-        if let Some(real_ip) = request.headers().get("x-real-ip")
-            && let Ok(s) = real_ip.to_str()
-            && let Ok(ip) = s.trim().parse::<IpAddr>()
-        {
-            return ip;
-        }
+/// Body of a `429` from the items limiter
+pub(crate) fn rate_limit_response(retry_after: u64) -> RateLimitResponse {
+    RateLimitResponse {
+        error: ErrorDetail {
+            r#type: "rate_limited".to_string(),
+            message: "Rate limit exceeded. Please slow down your requests.".to_string(),
+            fields: Vec::new(),
+            request_id: current_request_id(),
+        },
+        retry_after,
     }
-    // Fallback: unknown clients share one bucket (prevents total global DoS).
-    IpAddr::V4(Ipv4Addr::UNSPECIFIED)
 }
 
 /// Rate limit middleware for items endpoints (per-IP to prevent global DoS)
@@ -171,14 +190,7 @@ async fn rate_limit_items_middleware(
             ));
             let retry_after = wait_time.as_secs();
 
-            let body = RateLimitResponse {
-                error: ErrorDetail {
-                    r#type: "rate_limited".to_string(),
-                    message: "Rate limit exceeded. Please slow down your requests.".to_string(),
-                },
-                retry_after,
-            };
-
+            let body = rate_limit_response(retry_after);
             let mut response = (StatusCode::TOO_MANY_REQUESTS, Json(body)).into_response();
             let headers = response.headers_mut();
             headers.insert(
@@ -211,6 +223,8 @@ async fn rate_limit_health_middleware(
                 error: ErrorDetail {
                     r#type: "rate_limited".to_string(),
                     message: "Rate limit exceeded".to_string(),
+                    fields: Vec::new(),
+                    request_id: current_request_id(),
                 },
             };
 
@@ -223,6 +237,79 @@ async fn rate_limit_health_middleware(
     }
 }
 
+/// Request headers cross-origin clients may send
+const CORS_ALLOW_HEADERS: [HeaderName; 6] = [
+    header::CONTENT_TYPE,
+    header::IF_MATCH,
+    header::IF_NONE_MATCH,
+    HeaderName::from_static("x-api-key"),
+    HeaderName::from_static("idempotency-key"),
+    HeaderName::from_static("x-request-id"),
+];
+
+/// Response headers cross-origin clients may read
+const CORS_EXPOSE_HEADERS: [HeaderName; 8] = [
+    header::ETAG,
+    header::LOCATION,
+    header::RETRY_AFTER,
+    header::CONTENT_DISPOSITION,
+    HeaderName::from_static("x-request-id"),
+    HeaderName::from_static("x-ratelimit-limit"),
+    HeaderName::from_static("x-ratelimit-remaining"),
+    HeaderName::from_static(QUOTA_REMAINING_HEADER),
+];
+
+const ITEMS_CORS_METHODS: [Method; 4] = [Method::GET, Method::POST, Method::PUT, Method::DELETE];
+const ADMIN_CORS_METHODS: [Method; 4] = [Method::GET, Method::POST, Method::PUT, Method::DELETE];
+const HEALTH_CORS_METHODS: [Method; 1] = [Method::GET];
+
+/// CORS layer of a route group; without a policy the group sends no CORS headers.
+/// Applied outside the group's auth and rate limit so preflights never need a key
+/// and rejections stay readable to the calling page.
+fn cors_layer(policy: Option<&CorsPolicy>, methods: &[Method]) -> Either<CorsLayer, Identity> {
+    option_layer(policy.map(|policy| {
+        let origin = match &policy.origins {
+            CorsOrigins::Any => AllowOrigin::any(),
+            CorsOrigins::List(origins) => AllowOrigin::list(
+                origins
+                    .iter()
+                    .filter_map(|origin| origin.parse().ok())
+                    .collect::<Vec<_>>(),
+            ),
+        };
+        // Configured methods narrow the group's; configured headers add to the built-in ones
+        let methods = if policy.methods.is_empty() {
+            methods.to_vec()
+        } else {
+            policy
+                .methods
+                .iter()
+                .filter_map(|method| method.parse().ok())
+                .collect()
+        };
+        let headers: Vec<HeaderName> = CORS_ALLOW_HEADERS
+            .into_iter()
+            .chain(policy.headers.iter().filter_map(|name| name.parse().ok()))
+            .collect();
+        CorsLayer::new()
+            .allow_origin(origin)
+            .allow_methods(methods)
+            .allow_headers(headers)
+            .expose_headers(CORS_EXPOSE_HEADERS)
+            .max_age(policy.max_age)
+    }))
+}
+
+/// Body limit of a route group: `RequestBodyLimitLayer` rejects a larger `Content-Length`
+/// up front, and the extractors stop reading at the same size instead of axum's default
+fn body_limit(
+    limit: usize,
+) -> ServiceBuilder<Stack<DefaultBodyLimit, Stack<RequestBodyLimitLayer, Identity>>> {
+    ServiceBuilder::new()
+        .layer(RequestBodyLimitLayer::new(limit))
+        .layer(DefaultBodyLimit::max(limit))
+}
+
 /// Prometheus scrape endpoint: returns metrics in exposition format.
 async fn metrics_handler(
     State(app_state): State<Arc<AppState>>,
@@ -242,6 +329,22 @@ async fn metrics_handler(
     ))
 }
 
+/// Serve `router` on `listener` until `shutdown` completes. Each request carries the
+/// connection's peer address as [`ConnectInfo`](axum::extract::ConnectInfo), which the IP blocklist, temporary bans
+/// and rate limits key on; without it every caller would look like `0.0.0.0`.
+pub async fn serve(
+    listener: tokio::net::TcpListener,
+    router: Router,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> std::io::Result<()> {
+    axum::serve(
+        listener,
+        router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown)
+    .await
+}
+
 /// Create router without rate limiting
 pub fn create_router(app_state: Arc<AppState>) -> Router {
     let middleware = ServiceBuilder::new()
@@ -257,31 +360,280 @@ pub fn create_router(app_state: Arc<AppState>) -> Router {
         .layer(TimeoutLayer::with_status_code(
             StatusCode::REQUEST_TIMEOUT,
             Duration::from_secs(30),
-        ));
+        ))
+        // gzip or brotli as the client accepts (not for small bodies, images or gRPC)
+        .layer(CompressionLayer::new());
+
+    // One CORS policy for the item API: `/items` and the routes that serve its clients
+    let items_cors = cors_layer(app_state.cors.items.as_ref(), &ITEMS_CORS_METHODS);
 
-    // Items routes (auth middleware protects POST endpoints)
+    // Items routes (the default auth policy protects POST/PUT/DELETE and include_deleted listings)
     let items_routes = Router::new()
-        .route("/", post(create_item_handler).get(list_items_handler))
-        .route("/{id}", get(get_item_handler))
+        .route(
+            "/",
+            post(create_item_handler)
+                .get(list_items_handler)
+                .layer(middleware::from_fn(conditional_get_middleware)),
+        )
+        .route(
+            "/search",
+            get(search_items_handler).layer(middleware::from_fn(conditional_get_middleware)),
+        )
+        .route("/export", get(export_items_handler))
+        .route(
+            "/export/bookmarks/{name}/ack",
+            post(acknowledge_export_bookmark_handler),
+        )
+        .route(
+            "/{id}",
+            get(get_item_handler)
+                .put(update_item_handler)
+                .delete(delete_item_handler)
+                .layer(middleware::from_fn(conditional_get_middleware)),
+        )
         .route("/{id}/retry", post(retry_blockchain_handler))
+        .route("/{id}/content", get(get_item_content_handler))
+        .route("/{id}/verify", get(verify_item_handler))
+        .route("/{id}/attempts", get(list_submission_attempts_handler))
+        .route("/{id}/timeline", get(item_timeline_handler))
+        // Applies to the routes above only; the import upload has its own limit
+        .layer(body_limit(app_state.body_limits.items))
+        .route(
+            "/import",
+            post(import_items_handler).layer(body_limit(app_state.body_limits.import)),
+        )
+        // Route layers run bottom-up: auth policy, quota, tenant scope, schema guard, then the
+        // idempotency journal
         .route_layer(middleware::from_fn_with_state(
             Arc::clone(&app_state),
-            auth_middleware,
-        ));
+            idempotency_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            schema_guard_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            tenant_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            quota_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            policy_middleware,
+        ))
+        .layer(items_cors.clone());
+
+    // Outcome of requests sent with an Idempotency-Key (same scope as the POST)
+    let requests_routes = Router::new()
+        .route("/{key}", get(get_request_status_handler))
+        .layer(body_limit(app_state.body_limits.default))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            tenant_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            quota_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            policy_middleware,
+        ))
+        .layer(items_cors.clone());
+
+    // Status of background jobs started with 202 Accepted
+    let jobs_routes = Router::new()
+        .route("/{id}", get(get_job_handler))
+        .layer(body_limit(app_state.body_limits.default))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            quota_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            policy_middleware,
+        ))
+        .layer(items_cors.clone());
 
     // Health routes
     let health_routes = Router::new()
         .route("/", get(health_check_handler))
         .route("/live", get(liveness_handler))
-        .route("/ready", get(readiness_handler));
+        .route("/ready", get(readiness_handler))
+        .route("/deep", get(deep_health_handler))
+        .layer(body_limit(app_state.body_limits.default))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            policy_middleware,
+        ))
+        .layer(cors_layer(
+            app_state.cors.health.as_ref(),
+            &HEALTH_CORS_METHODS,
+        ));
+
+    // Admin routes (every method requires the API key)
+    let admin_routes = Router::new()
+        .route(
+            "/blocklist",
+            get(get_blocklist_handler).put(update_blocklist_handler),
+        )
+        .route("/bans", get(list_bans_handler))
+        .route("/bans/{subject}", delete(lift_ban_handler))
+        .route(
+            "/api-keys",
+            get(list_api_keys_handler).post(create_api_key_handler),
+        )
+        .route("/api-keys/{id}", delete(revoke_api_key_handler))
+        .route("/api-keys/{id}/rotate", post(rotate_api_key_handler))
+        .route("/usage", get(get_usage_handler))
+        .route("/queue", get(get_queue_depth_handler))
+        .route(
+            "/maintenance",
+            get(get_maintenance_handler).put(set_maintenance_handler),
+        )
+        .route("/worker", get(get_worker_status_handler))
+        .route("/worker/run-now", post(run_worker_now_handler))
+        .route("/dlq", get(list_dead_letters_handler))
+        .route("/dlq/requeue", post(requeue_all_dead_letters_handler))
+        .route("/dlq/{id}/requeue", post(requeue_dead_letter_handler))
+        .route("/encryption/reencrypt", post(reencrypt_content_handler))
+        .route("/events", get(list_item_events_handler))
+        .route("/webhook-deliveries", get(list_webhook_deliveries_handler))
+        .route("/audit", get(list_audit_entries_handler))
+        .layer(body_limit(app_state.body_limits.admin))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            schema_guard_middleware,
+        ))
+        // Inside the policy check, so only authorized requests are audited (with their key)
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            admin_audit_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            policy_middleware,
+        ))
+        .layer(cors_layer(
+            app_state.cors.admin.as_ref(),
+            &ADMIN_CORS_METHODS,
+        ));
 
-    Router::new()
-        .route("/metrics", get(metrics_handler))
+    let routes = Router::new()
+        .route(
+            "/metrics",
+            get(metrics_handler)
+                .layer(body_limit(app_state.body_limits.default))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    policy_middleware,
+                )),
+        )
         .nest("/items", items_routes)
+        .nest("/requests", requests_routes)
+        .nest("/jobs", jobs_routes)
+        // Tag usage across the caller's items, read like `GET /items`
+        .route(
+            "/tags",
+            get(list_tags_handler)
+                .layer(body_limit(app_state.body_limits.default))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    tenant_middleware,
+                ))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    quota_middleware,
+                ))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    policy_middleware,
+                ))
+                .layer(items_cors.clone()),
+        )
+        .route(
+            "/verify/receipt",
+            post(verify_receipt_handler)
+                .layer(body_limit(app_state.body_limits.items))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    tenant_middleware,
+                ))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    quota_middleware,
+                ))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    policy_middleware,
+                ))
+                .layer(items_cors.clone()),
+        )
+        // Live item feed; the upgrade is a plain GET, so the item read rules apply
+        .route(
+            "/ws",
+            get(live_feed_handler)
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    tenant_middleware,
+                ))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    quota_middleware,
+                ))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    policy_middleware,
+                )),
+        )
         .nest("/health", health_routes)
+        .nest("/admin", admin_routes);
+
+    // GraphQL shares the items use cases; mutations check scopes inside the resolvers
+    #[cfg(feature = "graphql")]
+    let routes = routes.nest(
+        "/graphql",
+        super::graphql::graphql_routes(Arc::clone(&app_state))
+            .layer(body_limit(app_state.body_limits.items))
+            .layer(middleware::from_fn_with_state(
+                Arc::clone(&app_state),
+                tenant_middleware,
+            ))
+            .layer(items_cors),
+    );
+
+    // IP blocklist runs before auth and rate limiting, then temporary bans, which count the
+    // 401s and 429s from inside; only the request ID wraps them, so every response
+    // (including a blocklist or ban 403) carries `X-Request-Id`
+    routes
         .layer(middleware)
-        .with_state(app_state)
-        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
+        .layer(middleware::from_fn(payload_too_large_middleware))
+        .with_state(Arc::clone(&app_state))
+        .merge(docs_routes(openapi_document(&app_state)))
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            abuse_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            app_state,
+            blocklist_middleware,
+        ))
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+/// The configured OpenAPI document, or the built-in [`ApiDoc`] defaults, with the
+/// security requirements of the state's auth policy
+fn openapi_document(app_state: &AppState) -> utoipa::openapi::OpenApi {
+    let mut doc = app_state
+        .openapi
+        .as_deref()
+        .cloned()
+        .unwrap_or_else(ApiDoc::openapi);
+    ApiSecurity::new((*app_state.auth_policy).clone()).modify(&mut doc);
+    doc
 }
 
 /// Create router with rate limiting enabled
@@ -301,39 +653,304 @@ pub fn create_router_with_rate_limit(app_state: Arc<AppState>, config: RateLimit
         .layer(TimeoutLayer::with_status_code(
             StatusCode::REQUEST_TIMEOUT,
             Duration::from_secs(30),
-        ));
+        ))
+        // gzip or brotli as the client accepts (not for small bodies, images or gRPC)
+        .layer(CompressionLayer::new());
 
-    // Items routes with auth (POST protected) and rate limiting
+    // One CORS policy for the item API: `/items` and the routes that serve its clients
+    let items_cors = cors_layer(app_state.cors.items.as_ref(), &ITEMS_CORS_METHODS);
+
+    // Items routes with auth (POST/DELETE protected) and rate limiting
     let items_routes = Router::new()
-        .route("/", post(create_item_handler).get(list_items_handler))
-        .route("/{id}", get(get_item_handler))
+        .route(
+            "/",
+            post(create_item_handler)
+                .get(list_items_handler)
+                .layer(middleware::from_fn(conditional_get_middleware)),
+        )
+        .route(
+            "/search",
+            get(search_items_handler).layer(middleware::from_fn(conditional_get_middleware)),
+        )
+        .route("/export", get(export_items_handler))
+        .route(
+            "/export/bookmarks/{name}/ack",
+            post(acknowledge_export_bookmark_handler),
+        )
+        .route(
+            "/{id}",
+            get(get_item_handler)
+                .put(update_item_handler)
+                .delete(delete_item_handler)
+                .layer(middleware::from_fn(conditional_get_middleware)),
+        )
         .route("/{id}/retry", post(retry_blockchain_handler))
+        .route("/{id}/content", get(get_item_content_handler))
+        .route("/{id}/verify", get(verify_item_handler))
+        .route("/{id}/attempts", get(list_submission_attempts_handler))
+        .route("/{id}/timeline", get(item_timeline_handler))
+        // Applies to the routes above only; the import upload has its own limit
+        .layer(body_limit(app_state.body_limits.items))
+        .route(
+            "/import",
+            post(import_items_handler).layer(body_limit(app_state.body_limits.import)),
+        )
+        // Route layers run bottom-up: auth policy, quota, tenant scope, schema guard, then the
+        // idempotency journal
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            idempotency_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            schema_guard_middleware,
+        ))
         .route_layer(middleware::from_fn_with_state(
             Arc::clone(&app_state),
-            auth_middleware,
+            tenant_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            quota_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            policy_middleware,
         ))
         .layer(middleware::from_fn_with_state(
             Arc::clone(&rate_limit_state),
             rate_limit_items_middleware,
-        ));
+        ))
+        .layer(items_cors.clone());
+
+    // Outcome of requests sent with an Idempotency-Key (same scope as the POST)
+    let requests_routes = Router::new()
+        .route("/{key}", get(get_request_status_handler))
+        .layer(body_limit(app_state.body_limits.default))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            tenant_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            quota_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            policy_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&rate_limit_state),
+            rate_limit_items_middleware,
+        ))
+        .layer(items_cors.clone());
+
+    // Status of background jobs started with 202 Accepted
+    let jobs_routes = Router::new()
+        .route("/{id}", get(get_job_handler))
+        .layer(body_limit(app_state.body_limits.default))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            quota_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            policy_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&rate_limit_state),
+            rate_limit_items_middleware,
+        ))
+        .layer(items_cors.clone());
 
     // Health routes with separate rate limiting
     let health_routes = Router::new()
         .route("/", get(health_check_handler))
         .route("/live", get(liveness_handler))
         .route("/ready", get(readiness_handler))
+        .route("/deep", get(deep_health_handler))
+        .layer(body_limit(app_state.body_limits.default))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            policy_middleware,
+        ))
         .layer(middleware::from_fn_with_state(
             Arc::clone(&rate_limit_state),
             rate_limit_health_middleware,
+        ))
+        .layer(cors_layer(
+            app_state.cors.health.as_ref(),
+            &HEALTH_CORS_METHODS,
+        ));
+
+    // Admin routes (every method requires the API key) share the general rate limit
+    let admin_routes = Router::new()
+        .route(
+            "/blocklist",
+            get(get_blocklist_handler).put(update_blocklist_handler),
+        )
+        .route("/bans", get(list_bans_handler))
+        .route("/bans/{subject}", delete(lift_ban_handler))
+        .route(
+            "/api-keys",
+            get(list_api_keys_handler).post(create_api_key_handler),
+        )
+        .route("/api-keys/{id}", delete(revoke_api_key_handler))
+        .route("/api-keys/{id}/rotate", post(rotate_api_key_handler))
+        .route("/usage", get(get_usage_handler))
+        .route("/queue", get(get_queue_depth_handler))
+        .route(
+            "/maintenance",
+            get(get_maintenance_handler).put(set_maintenance_handler),
+        )
+        .route("/worker", get(get_worker_status_handler))
+        .route("/worker/run-now", post(run_worker_now_handler))
+        .route("/dlq", get(list_dead_letters_handler))
+        .route("/dlq/requeue", post(requeue_all_dead_letters_handler))
+        .route("/dlq/{id}/requeue", post(requeue_dead_letter_handler))
+        .route("/encryption/reencrypt", post(reencrypt_content_handler))
+        .route("/events", get(list_item_events_handler))
+        .route("/webhook-deliveries", get(list_webhook_deliveries_handler))
+        .route("/audit", get(list_audit_entries_handler))
+        .layer(body_limit(app_state.body_limits.admin))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            schema_guard_middleware,
+        ))
+        // Inside the policy check, so only authorized requests are audited (with their key)
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            admin_audit_middleware,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            policy_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&rate_limit_state),
+            rate_limit_items_middleware,
+        ))
+        .layer(cors_layer(
+            app_state.cors.admin.as_ref(),
+            &ADMIN_CORS_METHODS,
         ));
 
-    Router::new()
-        .route("/metrics", get(metrics_handler))
+    let routes = Router::new()
+        .route(
+            "/metrics",
+            get(metrics_handler)
+                .layer(body_limit(app_state.body_limits.default))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    policy_middleware,
+                )),
+        )
         .nest("/items", items_routes)
+        .nest("/requests", requests_routes)
+        .nest("/jobs", jobs_routes)
+        .route(
+            "/tags",
+            get(list_tags_handler)
+                .layer(body_limit(app_state.body_limits.default))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    tenant_middleware,
+                ))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    quota_middleware,
+                ))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    policy_middleware,
+                ))
+                .layer(ServiceBuilder::new().layer(items_cors.clone()).layer(
+                    middleware::from_fn_with_state(
+                        Arc::clone(&rate_limit_state),
+                        rate_limit_items_middleware,
+                    ),
+                )),
+        )
+        .route(
+            "/verify/receipt",
+            post(verify_receipt_handler)
+                .layer(body_limit(app_state.body_limits.items))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    tenant_middleware,
+                ))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    quota_middleware,
+                ))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    policy_middleware,
+                ))
+                .layer(ServiceBuilder::new().layer(items_cors.clone()).layer(
+                    middleware::from_fn_with_state(
+                        Arc::clone(&rate_limit_state),
+                        rate_limit_items_middleware,
+                    ),
+                )),
+        )
+        .route(
+            "/ws",
+            get(live_feed_handler)
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    tenant_middleware,
+                ))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    quota_middleware,
+                ))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    policy_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    Arc::clone(&rate_limit_state),
+                    rate_limit_items_middleware,
+                )),
+        )
         .nest("/health", health_routes)
+        .nest("/admin", admin_routes);
+
+    // GraphQL shares the items use cases (and rate limit); mutations check scopes in resolvers
+    #[cfg(feature = "graphql")]
+    let routes = routes.nest(
+        "/graphql",
+        super::graphql::graphql_routes(Arc::clone(&app_state))
+            .layer(body_limit(app_state.body_limits.items))
+            .layer(middleware::from_fn_with_state(
+                Arc::clone(&app_state),
+                tenant_middleware,
+            ))
+            .layer(middleware::from_fn_with_state(
+                Arc::clone(&rate_limit_state),
+                rate_limit_items_middleware,
+            ))
+            .layer(items_cors),
+    );
+
+    // IP blocklist runs before auth and rate limiting, then temporary bans, which count the
+    // 401s and 429s from inside; only the request ID wraps them, so every response
+    // (including a blocklist or ban 403) carries `X-Request-Id`
+    routes
         .layer(middleware)
-        .with_state(app_state)
-        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
+        .layer(middleware::from_fn(payload_too_large_middleware))
+        .with_state(Arc::clone(&app_state))
+        .merge(docs_routes(openapi_document(&app_state)))
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&app_state),
+            abuse_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            app_state,
+            blocklist_middleware,
+        ))
+        .layer(middleware::from_fn(request_id_middleware))
 }
 
 #[cfg(test)]
@@ -382,6 +999,7 @@ mod tests {
             let config = RateLimitConfig::default();
             assert_eq!(config.health_rps, 100);
             assert_eq!(config.health_burst, 100);
+            assert_eq!(config.max_tracked_keys, 100_000);
         }
 
         #[test]
@@ -392,6 +1010,7 @@ mod tests {
                 health_rps: 200,
                 health_burst: 200,
                 trust_proxy_headers: false,
+                max_tracked_keys: DEFAULT_MAX_TRACKED_KEYS,
             };
             assert_eq!(config.general_rps, 50);
             assert_eq!(config.general_burst, 100);
@@ -418,6 +1037,7 @@ mod tests {
                 health_rps: 100,
                 health_burst: 100,
                 trust_proxy_headers: false,
+                max_tracked_keys: DEFAULT_MAX_TRACKED_KEYS,
             };
             let config2 = config1.clone();
             assert_eq!(config1.general_rps, config2.general_rps);
@@ -427,7 +1047,9 @@ mod tests {
 
     mod middleware_tests {
         use super::*;
+        use axum::extract::ConnectInfo;
         use http_body_util::BodyExt;
+        use std::net::SocketAddr;
 
         async fn dummy_handler() -> impl IntoResponse {
             StatusCode::OK
@@ -575,6 +1197,7 @@ mod tests {
                 health_rps: 100,
                 health_burst: 100,
                 trust_proxy_headers: false,
+                max_tracked_keys: DEFAULT_MAX_TRACKED_KEYS,
             };
 
             let state = Arc::new(RateLimitState::new(config));
@@ -606,6 +1229,7 @@ mod tests {
                 health_rps: 1,
                 health_burst: 1,
                 trust_proxy_headers: false,
+                max_tracked_keys: DEFAULT_MAX_TRACKED_KEYS,
             };
 
             let state = Arc::new(RateLimitState::new(config));
@@ -683,6 +1307,7 @@ mod tests {
                 health_rps: 1,
                 health_burst: 1,
                 trust_proxy_headers: false,
+                max_tracked_keys: DEFAULT_MAX_TRACKED_KEYS,
             };
 
             let state = Arc::new(RateLimitState::new(config));
@@ -778,6 +1403,131 @@ mod tests {
             let response = router.oneshot(request).await.unwrap();
             assert_eq!(response.status(), StatusCode::OK);
         }
+
+        #[tokio::test]
+        async fn test_custom_auth_policy_protects_reads_and_metrics() {
+            use crate::app::AuthPolicy;
+
+            let app_state = (*AppState::new_for_test()).clone().with_auth_policy(
+                AuthPolicy::parse("GET /items/** items:read; GET /metrics admin")
+                    .unwrap()
+                    .then(AuthPolicy::default()),
+            );
+            let router = create_router(Arc::new(app_state));
+
+            for (uri, key, expected) in [
+                ("/items", None, StatusCode::UNAUTHORIZED),
+                ("/items", Some("test-api-key"), StatusCode::OK),
+                ("/metrics", None, StatusCode::UNAUTHORIZED),
+                ("/health", None, StatusCode::OK),
+            ] {
+                let mut request = Request::builder().uri(uri);
+                if let Some(key) = key {
+                    request = request.header("x-api-key", key);
+                }
+                let response = router
+                    .clone()
+                    .oneshot(request.body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), expected, "{uri} {key:?}");
+            }
+        }
+
+        #[tokio::test]
+        async fn test_list_items_filter_and_sort_params() {
+            let router = create_router(AppState::new_for_test());
+            for name in ["Charlie", "Alpha", "Bravo"] {
+                let request = Request::builder()
+                    .method("POST")
+                    .uri("/items")
+                    .header("Content-Type", "application/json")
+                    .header("x-api-key", "test-api-key")
+                    .body(Body::from(format!(
+                        r#"{{"name":"{}","content":"x","metadata":{{"tags":["{}"],"custom_fields":{{}}}}}}"#,
+                        name,
+                        if name == "Alpha" { "other" } else { "demo" }
+                    )))
+                    .unwrap();
+                let response = router.clone().oneshot(request).await.unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+            }
+
+            let request = Request::builder()
+                .uri("/items?tag=demo&sort=name&order=asc&blockchain_status=pending_submission")
+                .body(Body::empty())
+                .unwrap();
+            let response = router.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = http_body_util::BodyExt::collect(response.into_body())
+                .await
+                .unwrap()
+                .to_bytes();
+            let page: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let names: Vec<&str> = page["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|i| i["name"].as_str().unwrap())
+                .collect();
+            assert_eq!(names, vec!["Bravo", "Charlie"]);
+
+            let request = Request::builder()
+                .uri("/items?sort=size")
+                .body(Body::empty())
+                .unwrap();
+            let response = router.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_search_items_ranks_name_matches_first() {
+            let router = create_router(AppState::new_for_test());
+            for (name, content) in [
+                ("Ledger notes", "Outbox draining for Solana"),
+                ("Outbox design", "How the worker claims rows"),
+                ("Unrelated", "Nothing to see"),
+            ] {
+                let request = Request::builder()
+                    .method("POST")
+                    .uri("/items")
+                    .header("Content-Type", "application/json")
+                    .header("x-api-key", "test-api-key")
+                    .body(Body::from(format!(
+                        r#"{{"name":"{}","content":"{}"}}"#,
+                        name, content
+                    )))
+                    .unwrap();
+                let response = router.clone().oneshot(request).await.unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+            }
+
+            let request = Request::builder()
+                .uri("/items/search?q=outbox")
+                .body(Body::empty())
+                .unwrap();
+            let response = router.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = http_body_util::BodyExt::collect(response.into_body())
+                .await
+                .unwrap()
+                .to_bytes();
+            let results: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(results["query"], "outbox");
+            let names: Vec<&str> = results["results"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|hit| hit["item"]["name"].as_str().unwrap())
+                .collect();
+            assert_eq!(names, vec!["Outbox design", "Ledger notes"]);
+
+            for uri in ["/items/search", "/items/search?q=%20%20"] {
+                let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+                let response = router.clone().oneshot(request).await.unwrap();
+                assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{uri}");
+            }
+        }
     }
 
     mod router_tests {
@@ -905,6 +1655,7 @@ mod tests {
                 health_rps: 100,
                 health_burst: 100,
                 trust_proxy_headers: false,
+                max_tracked_keys: DEFAULT_MAX_TRACKED_KEYS,
             };
             let router = create_router_with_rate_limit(app_state, config);
 
@@ -955,6 +1706,833 @@ mod tests {
         }
     }
 
+    mod blocklist_tests {
+        use super::*;
+        use crate::app::{AppState, IpBlocklist};
+        use axum::extract::ConnectInfo;
+        use http_body_util::BodyExt;
+        use std::net::SocketAddr;
+
+        fn blocked_state(cidrs: &[&str]) -> Arc<AppState> {
+            let entries: Vec<String> = cidrs.iter().map(|s| s.to_string()).collect();
+            let ranges = IpBlocklist::parse_ranges(&entries).unwrap();
+            let state = Arc::try_unwrap(AppState::new_for_test()).ok().unwrap();
+            Arc::new(state.with_blocklist(Arc::new(IpBlocklist::new(ranges, false))))
+        }
+
+        fn request_from(ip: [u8; 4], method: &str, uri: &str) -> Request<Body> {
+            let mut request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap();
+            request
+                .extensions_mut()
+                .insert(ConnectInfo(SocketAddr::from((ip, 0))));
+            request
+        }
+
+        #[tokio::test]
+        async fn test_blocked_ip_returns_403_with_code() {
+            let router = create_router(blocked_state(&["203.0.113.0/24"]));
+
+            let response = router
+                .oneshot(request_from([203, 0, 113, 9], "GET", "/items"))
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let body: ErrorResponse = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body.error.r#type, "ip_blocked");
+        }
+
+        #[tokio::test]
+        async fn test_blocklist_matches_the_peer_address_of_a_real_connection() {
+            let router = create_router(blocked_state(&["127.0.0.0/8"]));
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            tokio::spawn(serve(listener, router, std::future::pending()));
+
+            let response = reqwest::get(format!("http://{addr}/items")).await.unwrap();
+
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+            let body: ErrorResponse = response.json().await.unwrap();
+            assert_eq!(body.error.r#type, "ip_blocked");
+        }
+
+        #[tokio::test]
+        async fn test_blocklist_runs_before_auth() {
+            let router = create_router(blocked_state(&["203.0.113.0/24"]));
+
+            // POST without API key would be 401; blocklist must answer first
+            let response = router
+                .oneshot(request_from([203, 0, 113, 9], "POST", "/items"))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        }
+
+        #[tokio::test]
+        async fn test_blocklist_runs_before_rate_limit() {
+            let config = RateLimitConfig {
+                general_rps: 1,
+                general_burst: 1,
+                ..Default::default()
+            };
+            let router = create_router_with_rate_limit(blocked_state(&["203.0.113.0/24"]), config);
+
+            for _ in 0..3 {
+                let response = router
+                    .clone()
+                    .oneshot(request_from([203, 0, 113, 9], "GET", "/items"))
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::FORBIDDEN);
+            }
+        }
+
+        #[tokio::test]
+        async fn test_unblocked_ip_passes() {
+            let router = create_router(blocked_state(&["203.0.113.0/24"]));
+
+            let response = router
+                .oneshot(request_from([10, 0, 0, 1], "GET", "/items"))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        #[tokio::test]
+        async fn test_admin_blocklist_requires_api_key_for_get() {
+            let router = create_router(AppState::new_for_test());
+
+            let response = router
+                .oneshot(request_from([10, 0, 0, 1], "GET", "/admin/blocklist"))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+
+        #[tokio::test]
+        async fn test_admin_worker_status_and_run_now() {
+            let monitor = Arc::new(crate::app::WorkerMonitor::new(true));
+            let state = Arc::try_unwrap(AppState::new_for_test()).ok().unwrap();
+            let router = create_router(Arc::new(state.with_worker_monitor(monitor)));
+
+            let response = router
+                .clone()
+                .oneshot(request_from([10, 0, 0, 1], "GET", "/admin/worker"))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+            let request = Request::builder()
+                .uri("/admin/worker")
+                .header("x-api-key", "test-api-key")
+                .body(Body::empty())
+                .unwrap();
+            let response = router.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = http_body_util::BodyExt::collect(response.into_body())
+                .await
+                .unwrap()
+                .to_bytes();
+            let status: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(status["enabled"], true);
+            assert_eq!(status["leader"], false);
+
+            // The worker loop was never started, so there is nothing to nudge
+            let request = Request::builder()
+                .method("POST")
+                .uri("/admin/worker/run-now")
+                .header("x-api-key", "test-api-key")
+                .body(Body::empty())
+                .unwrap();
+            let response = router.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CONFLICT);
+        }
+
+        #[tokio::test]
+        async fn test_admin_dlq_requires_api_key() {
+            let router = create_router(AppState::new_for_test());
+
+            let response = router
+                .clone()
+                .oneshot(request_from([10, 0, 0, 1], "GET", "/admin/dlq"))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+            let request = Request::builder()
+                .uri("/admin/dlq?include_requeued=true")
+                .header("x-api-key", "test-api-key")
+                .body(Body::empty())
+                .unwrap();
+            let response = router.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = http_body_util::BodyExt::collect(response.into_body())
+                .await
+                .unwrap()
+                .to_bytes();
+            let entries: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(entries, serde_json::json!([]));
+
+            let request = Request::builder()
+                .method("POST")
+                .uri("/admin/dlq/dlq_missing/requeue")
+                .header("x-api-key", "test-api-key")
+                .body(Body::empty())
+                .unwrap();
+            let response = router.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+
+        #[tokio::test]
+        async fn test_admin_blocklist_update_hot_reloads() {
+            let state = AppState::new_for_test();
+            let router = create_router(Arc::clone(&state));
+
+            let request = Request::builder()
+                .method("PUT")
+                .uri("/admin/blocklist")
+                .header("Content-Type", "application/json")
+                .header("x-api-key", "test-api-key")
+                .body(Body::from(r#"{"cidrs":["198.51.100.0/24"]}"#))
+                .unwrap();
+            let response = router.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let response = router
+                .oneshot(request_from([198, 51, 100, 20], "GET", "/items"))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+            assert_eq!(state.blocklist.ranges().len(), 1);
+        }
+
+        #[tokio::test]
+        async fn test_admin_blocklist_update_rejects_invalid_cidr() {
+            let router = create_router(AppState::new_for_test());
+
+            let request = Request::builder()
+                .method("PUT")
+                .uri("/admin/blocklist")
+                .header("Content-Type", "application/json")
+                .header("x-api-key", "test-api-key")
+                .body(Body::from(r#"{"cidrs":["not-a-cidr"]}"#))
+                .unwrap();
+            let response = router.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_repeated_401s_ban_the_source_until_lifted() {
+            let guard = crate::app::AbuseGuard::new(crate::app::AbuseConfig {
+                unauthorized_threshold: Some(2),
+                ..Default::default()
+            });
+            let state = Arc::try_unwrap(AppState::new_for_test()).ok().unwrap();
+            let router = create_router(Arc::new(state.with_abuse_guard(Arc::new(guard))));
+
+            let bad_key = |ip| {
+                let mut request = request_from(ip, "POST", "/items");
+                request
+                    .headers_mut()
+                    .insert("x-api-key", "wrong-key".parse().unwrap());
+                request
+            };
+            for _ in 0..2 {
+                let response = router
+                    .clone()
+                    .oneshot(bad_key([203, 0, 113, 9]))
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+            }
+
+            // Banned by address, even with no key at all
+            let response = router
+                .clone()
+                .oneshot(request_from([203, 0, 113, 9], "GET", "/items"))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+            assert!(response.headers().contains_key(header::RETRY_AFTER));
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let body: ErrorResponse = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body.error.r#type, "temporarily_banned");
+
+            // ...and by key, from any address
+            let response = router
+                .clone()
+                .oneshot(bad_key([10, 0, 0, 2]))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+            let mut request = request_from([10, 0, 0, 1], "GET", "/admin/bans");
+            request
+                .headers_mut()
+                .insert("x-api-key", "test-api-key".parse().unwrap());
+            let response = router.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let bans: Vec<crate::domain::TemporaryBan> = serde_json::from_slice(&body).unwrap();
+            assert_eq!(bans.len(), 2);
+            assert!(bans.iter().all(|ban| ban.reason == "unauthorized"));
+            assert!(bans.iter().any(|ban| ban.subject == "ip:203.0.113.9"));
+
+            let mut request = request_from([10, 0, 0, 1], "DELETE", "/admin/bans/ip:203.0.113.9");
+            request
+                .headers_mut()
+                .insert("x-api-key", "test-api-key".parse().unwrap());
+            let response = router.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let response = router
+                .oneshot(request_from([203, 0, 113, 9], "GET", "/items"))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        #[tokio::test]
+        async fn test_unknown_address_is_never_banned() {
+            let guard = crate::app::AbuseGuard::new(crate::app::AbuseConfig {
+                unauthorized_threshold: Some(2),
+                ..Default::default()
+            });
+            let state = Arc::try_unwrap(AppState::new_for_test()).ok().unwrap();
+            let router = create_router(Arc::new(state.with_abuse_guard(Arc::new(guard))));
+
+            // No ConnectInfo: every such request resolves to 0.0.0.0
+            let bad_key = || {
+                Request::builder()
+                    .method("POST")
+                    .uri("/items")
+                    .header("x-api-key", "wrong-key")
+                    .body(Body::empty())
+                    .unwrap()
+            };
+            for _ in 0..2 {
+                let response = router.clone().oneshot(bad_key()).await.unwrap();
+                assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+            }
+
+            let response = router.clone().oneshot(bad_key()).await.unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+            let anonymous = Request::builder()
+                .uri("/items")
+                .body(Body::empty())
+                .unwrap();
+            let response = router.oneshot(anonymous).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        #[tokio::test]
+        async fn test_bans_the_peer_address_of_a_real_connection() {
+            let guard = Arc::new(crate::app::AbuseGuard::new(crate::app::AbuseConfig {
+                unauthorized_threshold: Some(2),
+                ..Default::default()
+            }));
+            let state = Arc::try_unwrap(AppState::new_for_test()).ok().unwrap();
+            let router = create_router(Arc::new(state.with_abuse_guard(Arc::clone(&guard))));
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            tokio::spawn(serve(listener, router, std::future::pending()));
+
+            let client = reqwest::Client::new();
+            for _ in 0..2 {
+                let response = client
+                    .post(format!("http://{addr}/items"))
+                    .header("x-api-key", "wrong-key")
+                    .send()
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+            }
+
+            let loopback = crate::app::AbuseSubject::Ip(addr.ip());
+            let ban = guard.ban_for(&[loopback]).expect("peer address banned");
+            assert_eq!(ban.subject, "ip:127.0.0.1");
+            let unknown = crate::app::AbuseSubject::Ip(std::net::Ipv4Addr::UNSPECIFIED.into());
+            assert!(guard.ban_for(&[unknown]).is_none());
+        }
+    }
+
+    mod api_key_tests {
+        use super::*;
+        use crate::app::AppState;
+        use crate::domain::{ApiKey, CreateApiKeyResponse};
+        use crate::test_utils::MockProvider;
+        use http_body_util::BodyExt;
+
+        fn router_with_store() -> Router {
+            let state = Arc::try_unwrap(AppState::new_for_test()).ok().unwrap();
+            let store = Arc::new(MockProvider::new());
+            create_router(Arc::new(state.with_api_key_store(store)))
+        }
+
+        async fn create_key(router: &Router, scopes: &str) -> CreateApiKeyResponse {
+            let request = Request::builder()
+                .method("POST")
+                .uri("/admin/api-keys")
+                .header("Content-Type", "application/json")
+                .header("x-api-key", "test-api-key")
+                .body(Body::from(format!(
+                    r#"{{"name":"ci","scopes":{}}}"#,
+                    scopes
+                )))
+                .unwrap();
+            let response = router.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            serde_json::from_slice(&body).unwrap()
+        }
+
+        fn post_item(key: &str) -> Request<Body> {
+            Request::builder()
+                .method("POST")
+                .uri("/items")
+                .header("Content-Type", "application/json")
+                .header("x-api-key", key)
+                .body(Body::from(r#"{"name":"Test","content":"x"}"#))
+                .unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_managed_key_with_write_scope_can_create_items() {
+            let router = router_with_store();
+            let created = create_key(&router, r#"["items:write"]"#).await;
+
+            let response = router.oneshot(post_item(&created.secret)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        #[tokio::test]
+        async fn test_managed_key_without_write_scope_is_forbidden() {
+            let router = router_with_store();
+            let created = create_key(&router, r#"["items:read"]"#).await;
+
+            let response = router.oneshot(post_item(&created.secret)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        }
+
+        #[tokio::test]
+        async fn test_non_admin_key_cannot_manage_keys() {
+            let router = router_with_store();
+            let created = create_key(&router, r#"["items:write"]"#).await;
+
+            let request = Request::builder()
+                .method("GET")
+                .uri("/admin/api-keys")
+                .header("x-api-key", &created.secret)
+                .body(Body::empty())
+                .unwrap();
+            let response = router.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        }
+
+        #[tokio::test]
+        async fn test_revoked_key_is_rejected() {
+            let router = router_with_store();
+            let created = create_key(&router, r#"["items:write"]"#).await;
+
+            let request = Request::builder()
+                .method("DELETE")
+                .uri(format!("/admin/api-keys/{}", created.key.id))
+                .header("x-api-key", "test-api-key")
+                .body(Body::empty())
+                .unwrap();
+            let response = router.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let revoked: ApiKey = serde_json::from_slice(&body).unwrap();
+            assert!(revoked.revoked_at.is_some());
+
+            let response = router.oneshot(post_item(&created.secret)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+
+        fn items_request(method: &str, uri: &str, key: Option<&str>) -> Request<Body> {
+            let mut builder = Request::builder().method(method).uri(uri);
+            if let Some(key) = key {
+                builder = builder.header("x-api-key", key);
+            }
+            builder.body(Body::empty()).unwrap()
+        }
+
+        async fn listed_ids(response: axum::response::Response) -> Vec<String> {
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let page: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            page["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|i| i["id"].as_str().unwrap().to_string())
+                .collect()
+        }
+
+        #[tokio::test]
+        async fn test_soft_deleted_item_is_hidden_except_for_admins() {
+            let router = router_with_store();
+            let response = router
+                .clone()
+                .oneshot(post_item("test-api-key"))
+                .await
+                .unwrap();
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let item: crate::domain::Item = serde_json::from_slice(&body).unwrap();
+            let item_uri = format!("/items/{}", item.id);
+
+            let response = router
+                .clone()
+                .oneshot(items_request("DELETE", &item_uri, None))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+            let response = router
+                .clone()
+                .oneshot(items_request("DELETE", &item_uri, Some("test-api-key")))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let response = router
+                .clone()
+                .oneshot(items_request("DELETE", &item_uri, Some("test-api-key")))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+            let response = router
+                .clone()
+                .oneshot(items_request("GET", &item_uri, None))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+            let response = router
+                .clone()
+                .oneshot(items_request("GET", "/items", None))
+                .await
+                .unwrap();
+            assert!(listed_ids(response).await.is_empty());
+
+            let response = router
+                .clone()
+                .oneshot(items_request(
+                    "GET",
+                    "/items?include_deleted=true",
+                    Some("test-api-key"),
+                ))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(listed_ids(response).await, vec![item.id]);
+        }
+
+        async fn create_tenant_key(router: &Router, tenant: &str) -> CreateApiKeyResponse {
+            let request = Request::builder()
+                .method("POST")
+                .uri("/admin/api-keys")
+                .header("Content-Type", "application/json")
+                .header("x-api-key", "test-api-key")
+                .body(Body::from(format!(
+                    r#"{{"name":"{tenant}","scopes":["items:read","items:write"],"tenant_id":"{tenant}"}}"#
+                )))
+                .unwrap();
+            let response = router.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            serde_json::from_slice(&body).unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_tenants_cannot_see_or_change_each_others_items() {
+            let router = router_with_store();
+            let acme = create_tenant_key(&router, "acme").await;
+            let globex = create_tenant_key(&router, "globex").await;
+
+            let response = router
+                .clone()
+                .oneshot(post_item(&acme.secret))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let item: crate::domain::Item = serde_json::from_slice(&body).unwrap();
+            assert_eq!(item.tenant_id, "acme");
+            let item_uri = format!("/items/{}", item.id);
+
+            // Another tenant and anonymous callers (the default tenant) see nothing
+            for key in [Some(globex.secret.as_str()), None] {
+                let response = router
+                    .clone()
+                    .oneshot(items_request("GET", &item_uri, key))
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::NOT_FOUND);
+                let response = router
+                    .clone()
+                    .oneshot(items_request("GET", "/items", key))
+                    .await
+                    .unwrap();
+                assert!(listed_ids(response).await.is_empty());
+            }
+            let response = router
+                .clone()
+                .oneshot(items_request("DELETE", &item_uri, Some(&globex.secret)))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+            // The owner and the operator key still see it
+            for key in [acme.secret.as_str(), "test-api-key"] {
+                let response = router
+                    .clone()
+                    .oneshot(items_request("GET", "/items", Some(key)))
+                    .await
+                    .unwrap();
+                assert_eq!(listed_ids(response).await, vec![item.id.clone()]);
+            }
+        }
+
+        #[tokio::test]
+        async fn test_invalid_tenant_id_is_rejected() {
+            let router = router_with_store();
+            let request = Request::builder()
+                .method("POST")
+                .uri("/admin/api-keys")
+                .header("Content-Type", "application/json")
+                .header("x-api-key", "test-api-key")
+                .body(Body::from(
+                    r#"{"name":"ci","scopes":["items:read"],"tenant_id":"acme/../globex"}"#,
+                ))
+                .unwrap();
+            let response = router.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_include_deleted_requires_admin_scope() {
+            let router = router_with_store();
+            let created = create_key(&router, r#"["items:read", "items:write"]"#).await;
+
+            let response = router
+                .clone()
+                .oneshot(items_request("GET", "/items?include_deleted=true", None))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+            let response = router
+                .oneshot(items_request(
+                    "GET",
+                    "/items?include_deleted=true",
+                    Some(&created.secret),
+                ))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        }
+
+        #[tokio::test]
+        async fn test_list_api_keys_hides_secrets() {
+            let router = router_with_store();
+            create_key(&router, r#"["items:read"]"#).await;
+
+            let request = Request::builder()
+                .method("GET")
+                .uri("/admin/api-keys")
+                .header("x-api-key", "test-api-key")
+                .body(Body::empty())
+                .unwrap();
+            let response = router.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let keys: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+            assert_eq!(keys.len(), 1);
+            assert!(keys[0].get("secret").is_none());
+        }
+
+        #[tokio::test]
+        async fn test_revoke_unknown_key_returns_404() {
+            let router = router_with_store();
+
+            let request = Request::builder()
+                .method("DELETE")
+                .uri("/admin/api-keys/key_missing")
+                .header("x-api-key", "test-api-key")
+                .body(Body::empty())
+                .unwrap();
+            let response = router.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+
+        #[tokio::test]
+        async fn test_key_management_without_store_returns_503() {
+            let router = create_router(AppState::new_for_test());
+
+            let request = Request::builder()
+                .method("GET")
+                .uri("/admin/api-keys")
+                .header("x-api-key", "test-api-key")
+                .body(Body::empty())
+                .unwrap();
+            let response = router.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        }
+    }
+
+    mod cors_tests {
+        use super::*;
+        use crate::app::{CorsConfig, CorsPolicy};
+
+        const APP: &str = "https://app.example.com";
+        const CONSOLE: &str = "https://console.internal";
+
+        fn cors_state() -> Arc<AppState> {
+            let policy = |origins| {
+                CorsPolicy::parse(origins, Duration::from_secs(3600))
+                    .unwrap()
+                    .unwrap()
+            };
+            let state = Arc::try_unwrap(AppState::new_for_test()).ok().unwrap();
+            Arc::new(state.with_cors(CorsConfig {
+                items: Some(policy("*")),
+                admin: Some(policy(CONSOLE)),
+                health: None,
+            }))
+        }
+
+        fn preflight(uri: &str, origin: &str, method: &str) -> Request<Body> {
+            Request::builder()
+                .method(Method::OPTIONS)
+                .uri(uri)
+                .header(header::ORIGIN, origin)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, method)
+                .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "x-api-key")
+                .body(Body::empty())
+                .unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_items_preflight_allows_any_origin_without_a_key() {
+            for router in [
+                create_router(cors_state()),
+                create_router_with_rate_limit(cors_state(), RateLimitConfig::default()),
+            ] {
+                let response = router
+                    .oneshot(preflight("/items", APP, "POST"))
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let headers = response.headers();
+                assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+                assert_eq!(headers[header::ACCESS_CONTROL_MAX_AGE], "3600");
+                assert!(
+                    headers[header::ACCESS_CONTROL_ALLOW_HEADERS]
+                        .to_str()
+                        .unwrap()
+                        .contains("x-api-key")
+                );
+            }
+        }
+
+        #[tokio::test]
+        async fn test_admin_only_answers_its_origin() {
+            let router = create_router(cors_state());
+            let response = router
+                .clone()
+                .oneshot(preflight("/admin/maintenance", CONSOLE, "PUT"))
+                .await
+                .unwrap();
+            assert_eq!(
+                response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+                CONSOLE
+            );
+
+            let response = router
+                .oneshot(preflight("/admin/maintenance", APP, "PUT"))
+                .await
+                .unwrap();
+            assert!(
+                !response
+                    .headers()
+                    .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            );
+        }
+
+        #[tokio::test]
+        async fn test_rejected_requests_carry_cors_headers() {
+            let request = Request::builder()
+                .method(Method::POST)
+                .uri("/items")
+                .header(header::ORIGIN, APP)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from("{}"))
+                .unwrap();
+            let response = create_router(cors_state()).oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+            assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+        }
+
+        #[tokio::test]
+        async fn test_configured_methods_and_headers() {
+            let policy = CorsPolicy::permissive()
+                .with_methods("GET")
+                .unwrap()
+                .with_headers("x-tenant-id")
+                .unwrap();
+            let state = Arc::try_unwrap(AppState::new_for_test()).ok().unwrap();
+            let state = Arc::new(state.with_cors(CorsConfig {
+                items: Some(policy),
+                ..CorsConfig::default()
+            }));
+            let response = create_router(state)
+                .oneshot(preflight("/items", APP, "GET"))
+                .await
+                .unwrap();
+            let headers = response.headers();
+            assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_METHODS], "GET");
+            let allowed = headers[header::ACCESS_CONTROL_ALLOW_HEADERS]
+                .to_str()
+                .unwrap();
+            assert!(allowed.contains("x-api-key") && allowed.contains("x-tenant-id"));
+        }
+
+        #[tokio::test]
+        async fn test_permissive_preset_opens_every_group() {
+            let state = Arc::try_unwrap(AppState::new_for_test()).ok().unwrap();
+            let router = create_router(Arc::new(state.with_cors(CorsConfig::permissive())));
+            for uri in ["/items", "/admin/maintenance", "/health/live"] {
+                let response = router
+                    .clone()
+                    .oneshot(preflight(uri, APP, "GET"))
+                    .await
+                    .unwrap();
+                assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+            }
+        }
+
+        #[tokio::test]
+        async fn test_group_without_policy_sends_no_cors_headers() {
+            let request = Request::builder()
+                .uri("/health/live")
+                .header(header::ORIGIN, APP)
+                .body(Body::empty())
+                .unwrap();
+            let response = create_router(cors_state()).oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert!(
+                !response
+                    .headers()
+                    .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            );
+        }
+    }
+
     mod rate_limit_state_tests {
         use super::*;
 
@@ -973,6 +2551,7 @@ mod tests {
                 health_rps: 200,
                 health_burst: 400,
                 trust_proxy_headers: false,
+                max_tracked_keys: DEFAULT_MAX_TRACKED_KEYS,
             };
             let _state = RateLimitState::new(config);
             // Should not panic with various configurations
@@ -0,0 +1,197 @@
+//! Security requirements for the OpenAPI document.
+//!
+//! The document's requirements come from the same [`AuthPolicy`] the
+//! [`policy_middleware`](super::middleware::policy_middleware) enforces, so generated clients
+//! send the API key exactly where the server asks for it. Every operation gets the
+//! `api_key` scheme (the `x-api-key` header) with the scope its route needs, plus the
+//! plain-text 401 and 403 the middleware answers with; public operations list the key as
+//! optional, since a tenant-bound key still narrows what they return.
+
+use utoipa::Modify;
+use utoipa::openapi::{
+    Components, OpenApi, RefOr, Response,
+    content::Content,
+    path::Operation,
+    schema::{ObjectBuilder, Type},
+    security::{ApiKey, ApiKeyValue, SecurityRequirement, SecurityScheme},
+};
+
+use crate::app::{Access, AuthPolicy};
+
+/// Name of the API key scheme in `components.securitySchemes`
+pub const API_KEY_SCHEME: &str = "api_key";
+
+/// Requirements of the built-in policy, as [`ApiDoc`](super::ApiDoc) declares them
+pub const DEFAULT_API_SECURITY: ApiSecurity = ApiSecurity { policy: None };
+
+/// [`utoipa::Modify`] declaring the API key scheme and each operation's requirement
+#[derive(Debug, Clone, Default)]
+pub struct ApiSecurity {
+    /// `None`: the built-in [`AuthPolicy::default`]
+    policy: Option<AuthPolicy>,
+}
+
+impl ApiSecurity {
+    /// Requirements of `policy` instead of the built-in defaults
+    #[must_use]
+    pub fn new(policy: AuthPolicy) -> Self {
+        Self {
+            policy: Some(policy),
+        }
+    }
+}
+
+impl Modify for ApiSecurity {
+    fn modify(&self, openapi: &mut OpenApi) {
+        openapi
+            .components
+            .get_or_insert_with(Components::new)
+            .add_security_scheme(
+                API_KEY_SCHEME,
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                    "x-api-key",
+                    "API key; scopes: `items:read`, `items:write`, `admin`",
+                ))),
+            );
+        let default_policy;
+        let policy = match &self.policy {
+            Some(policy) => policy,
+            None => {
+                default_policy = AuthPolicy::default();
+                &default_policy
+            }
+        };
+        for (path, item) in &mut openapi.paths.paths {
+            let operations = [
+                ("GET", &mut item.get),
+                ("PUT", &mut item.put),
+                ("POST", &mut item.post),
+                ("DELETE", &mut item.delete),
+                ("PATCH", &mut item.patch),
+            ];
+            for (method, operation) in operations {
+                if let Some(operation) = operation {
+                    secure(operation, policy.access(method, path, None));
+                }
+            }
+        }
+    }
+}
+
+fn secure(operation: &mut Operation, access: Access) {
+    let scopes: Vec<String> = match access {
+        Access::Public => {
+            operation.security = Some(vec![
+                SecurityRequirement::default(),
+                SecurityRequirement::new(API_KEY_SCHEME, Vec::<String>::new()),
+            ]);
+            return;
+        }
+        Access::Authenticated => Vec::new(),
+        Access::Scope(scope) => vec![scope.as_str().to_string()],
+    };
+    let forbidden = scopes
+        .first()
+        .map(|scope| format!("API key lacks the {} scope", scope));
+    operation.security = Some(vec![SecurityRequirement::new(API_KEY_SCHEME, scopes)]);
+    plain_text_response(
+        operation,
+        "401",
+        "Missing or invalid API key",
+        "Unauthorized",
+    );
+    if let Some(description) = forbidden {
+        plain_text_response(operation, "403", &description, "Forbidden");
+    }
+}
+
+/// Add the middleware's plain-text body to the `status` response, declaring it if missing
+fn plain_text_response(operation: &mut Operation, status: &str, description: &str, body: &str) {
+    let RefOr::T(response) = operation
+        .responses
+        .responses
+        .entry(status.to_string())
+        .or_insert_with(|| RefOr::T(Response::new(description)))
+    else {
+        return;
+    };
+    response
+        .content
+        .entry("text/plain".to_string())
+        .or_insert_with(|| {
+            let mut content = Content::new(Some(ObjectBuilder::new().schema_type(Type::String)));
+            content.example = Some(body.into());
+            content
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::ApiDoc;
+    use utoipa::OpenApi;
+
+    fn security(doc: &serde_json::Value, path: &str, method: &str) -> serde_json::Value {
+        doc["paths"][path][method]["security"].clone()
+    }
+
+    #[test]
+    fn test_requirements_follow_the_default_policy() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+        let scheme = &doc["components"]["securitySchemes"][API_KEY_SCHEME];
+        assert_eq!(scheme["type"], "apiKey");
+        assert_eq!(scheme["in"], "header");
+        assert_eq!(scheme["name"], "x-api-key");
+
+        assert_eq!(
+            security(&doc, "/items", "post"),
+            serde_json::json!([{ "api_key": ["items:write"] }])
+        );
+        assert_eq!(
+            security(&doc, "/admin/worker", "get"),
+            serde_json::json!([{ "api_key": ["admin"] }])
+        );
+        assert_eq!(
+            security(&doc, "/jobs/{id}", "get"),
+            serde_json::json!([{ "api_key": [] }])
+        );
+        // Public reads take a key optionally (tenant-bound keys narrow the results)
+        assert_eq!(
+            security(&doc, "/items", "get"),
+            serde_json::json!([{}, { "api_key": [] }])
+        );
+
+        let responses = &doc["paths"]["/items"]["post"]["responses"];
+        assert_eq!(
+            responses["401"]["content"]["text/plain"]["example"],
+            "Unauthorized"
+        );
+        assert_eq!(
+            responses["403"]["content"]["text/plain"]["example"],
+            "Forbidden"
+        );
+        // Routes that only need a key cannot be forbidden by the policy
+        assert!(doc["paths"]["/jobs/{id}"]["get"]["responses"]["403"].is_null());
+    }
+
+    #[test]
+    fn test_custom_policy_overrides_requirements() {
+        let policy = AuthPolicy::parse("GET /items/** items:read")
+            .unwrap()
+            .then(AuthPolicy::default());
+        let mut doc = ApiDoc::openapi();
+        ApiSecurity::new(policy).modify(&mut doc);
+        let doc = serde_json::to_value(doc).unwrap();
+
+        assert_eq!(
+            security(&doc, "/items/{id}", "get"),
+            serde_json::json!([{ "api_key": ["items:read"] }])
+        );
+        let forbidden = &doc["paths"]["/items/{id}"]["get"]["responses"]["403"];
+        assert_eq!(
+            forbidden["description"],
+            "API key lacks the items:read scope"
+        );
+    }
+}
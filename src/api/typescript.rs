@@ -0,0 +1,219 @@
+//! TypeScript declarations generated from the OpenAPI spec.
+//!
+//! Walks `components.schemas` of [`ApiDoc`](super::ApiDoc) and emits one exported
+//! interface or type alias per schema, so frontends get typed models without running
+//! an external OpenAPI toolchain. Invoked via `<binary> openapi --typescript <path>`.
+
+use serde_json::{Map, Value};
+use utoipa::openapi::OpenApi;
+
+const HEADER: &str = "// Generated from the OpenAPI spec. Do not edit by hand.\n// Regenerate with: cargo run -- openapi --typescript <path>\n";
+
+/// Render all component schemas as a `.d.ts` file
+#[must_use]
+pub fn typescript_types(spec: &OpenApi) -> String {
+    let spec = serde_json::to_value(spec).unwrap_or(Value::Null);
+    let mut out = String::from(HEADER);
+    if let Some(schemas) = spec
+        .pointer("/components/schemas")
+        .and_then(Value::as_object)
+    {
+        for (name, schema) in schemas {
+            out.push('\n');
+            out.push_str(&declaration(&type_name(name), schema));
+        }
+    }
+    out
+}
+
+/// Schema names such as `PaginatedResponse_Item` are valid identifiers already;
+/// anything else is replaced with `_`.
+fn type_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn doc_comment(schema: &Value, indent: &str) -> String {
+    match schema.get("description").and_then(Value::as_str) {
+        Some(description) => format!(
+            "{}/** {} */\n",
+            indent,
+            description.replace("*/", "*\\/").replace('\n', " ")
+        ),
+        None => String::new(),
+    }
+}
+
+fn declaration(name: &str, schema: &Value) -> String {
+    let doc = doc_comment(schema, "");
+    match schema.get("properties").and_then(Value::as_object) {
+        Some(properties) if schema.get("allOf").is_none() => {
+            format!(
+                "{}export interface {} {}\n",
+                doc,
+                name,
+                object_body(properties, required(schema), "")
+            )
+        }
+        _ => format!("{}export type {} = {};\n", doc, name, type_expr(schema, "")),
+    }
+}
+
+fn required(schema: &Value) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|r| r.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn object_body(properties: &Map<String, Value>, required: Vec<&str>, indent: &str) -> String {
+    let inner = format!("{}  ", indent);
+    let mut out = String::from("{\n");
+    for (key, property) in properties {
+        let optional = if required.contains(&key.as_str()) {
+            ""
+        } else {
+            "?"
+        };
+        out.push_str(&doc_comment(property, &inner));
+        out.push_str(&format!(
+            "{}{}{}: {};\n",
+            inner,
+            property_key(key),
+            optional,
+            type_expr(property, &inner)
+        ));
+    }
+    out.push_str(indent);
+    out.push('}');
+    out
+}
+
+fn property_key(key: &str) -> String {
+    let is_identifier = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_identifier {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
+
+fn join(schemas: &[Value], separator: &str, indent: &str) -> String {
+    let parts: Vec<String> = schemas.iter().map(|s| type_expr(s, indent)).collect();
+    if parts.len() == 1 {
+        parts.into_iter().next().unwrap_or_default()
+    } else {
+        parts
+            .into_iter()
+            .map(|p| {
+                if !p.starts_with('{') && (p.contains(" | ") || p.contains(" & ")) {
+                    format!("({})", p)
+                } else {
+                    p
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+}
+
+fn type_expr(schema: &Value, indent: &str) -> String {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return type_name(reference.rsplit('/').next().unwrap_or(reference));
+    }
+    if let Some(variants) = schema
+        .get("oneOf")
+        .or_else(|| schema.get("anyOf"))
+        .and_then(Value::as_array)
+    {
+        return join(variants, " | ", indent);
+    }
+    if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+        return join(parts, " & ", indent);
+    }
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return values
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join(" | ");
+    }
+    match schema.get("type") {
+        Some(Value::String(t)) => primitive(t, schema, indent),
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .map(|t| primitive(t, schema, indent))
+            .collect::<Vec<_>>()
+            .join(" | "),
+        _ => "unknown".to_string(),
+    }
+}
+
+fn primitive(t: &str, schema: &Value, indent: &str) -> String {
+    match t {
+        "string" => "string".to_string(),
+        "integer" | "number" => "number".to_string(),
+        "boolean" => "boolean".to_string(),
+        "null" => "null".to_string(),
+        "array" => {
+            let items = schema
+                .get("items")
+                .map_or_else(|| "unknown".to_string(), |i| type_expr(i, indent));
+            if items.contains(' ') || items.contains('\n') {
+                format!("Array<{}>", items)
+            } else {
+                format!("{}[]", items)
+            }
+        }
+        "object" => match (
+            schema.get("properties").and_then(Value::as_object),
+            schema.get("additionalProperties"),
+        ) {
+            (Some(properties), _) => object_body(properties, required(schema), indent),
+            (None, Some(values)) if values.is_object() => {
+                format!("Record<string, {}>", type_expr(values, indent))
+            }
+            _ => "Record<string, unknown>".to_string(),
+        },
+        _ => "unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::ApiDoc;
+    use utoipa::OpenApi as _;
+
+    #[test]
+    fn test_generates_interfaces_enums_and_intersections() {
+        let ts = typescript_types(&ApiDoc::openapi());
+
+        assert!(ts.starts_with("// Generated from the OpenAPI spec."));
+        assert!(ts.contains("export interface Item {"));
+        assert!(ts.contains("  id: string;"));
+        assert!(ts.contains("  description?: string | null;"));
+        assert!(ts.contains("  metadata?: null | ItemMetadata;"));
+        assert!(ts.contains("  blockchain_retry_count: number;"));
+        assert!(ts.contains("  custom_fields: Record<string, string>;"));
+        assert!(ts.contains("  tags: string[];"));
+        assert!(
+            ts.contains("export type ApiKeyScope = \"items:read\" | \"items:write\" | \"admin\";")
+        );
+        assert!(ts.contains("export type CreateApiKeyResponse = ApiKey & {"));
+        assert!(ts.contains("/** Core item entity */\nexport interface Item"));
+    }
+
+    #[test]
+    fn test_quotes_non_identifier_keys() {
+        assert_eq!(property_key("type"), "type");
+        assert_eq!(property_key("x-api-key"), "\"x-api-key\"");
+    }
+}
@@ -0,0 +1,136 @@
+//! `GET /ws`: live feed of item events over a WebSocket.
+//!
+//! Each text message is one [`DomainEvent`](crate::domain::DomainEvent) as JSON, the shape
+//! the message bus receives. The server pings every [`PING_INTERVAL`] and drops a client
+//! that has sent nothing (not even a pong) for two intervals. A client that falls more
+//! than the feed's capacity behind gets `{"type":"lagged","missed":n}` in place of the
+//! events it lost; one that does not take a message within [`SEND_TIMEOUT`] is
+//! disconnected, so a stalled socket never holds the feed back.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::extract::ws::{CloseFrame, Message, Utf8Bytes, WebSocket, WebSocketUpgrade, close_code};
+use axum::http::StatusCode;
+use axum::response::Response;
+use serde_json::json;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::{Instant, MissedTickBehavior, interval_at, timeout};
+use tracing::{debug, warn};
+
+use super::extract::ApiQuery;
+use super::handlers::error_response;
+use crate::app::{AppState, FeedFilter, ItemFeed};
+use crate::domain::{ErrorResponse, LiveFeedParams, RateLimitResponse, TenantScope};
+
+/// How often the server pings each connection
+pub const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Longest a single message may wait for the client to accept it
+pub const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Stream item creation and status changes as they happen
+#[utoipa::path(
+    get,
+    path = "/ws",
+    tag = "items",
+    params(
+        ("tag" = Option<String>, Query, description = "Only events of items tagged with this tag"),
+        ("tenant" = Option<String>, Query, description = "Only events of this tenant's items (keys bound to a tenant always get theirs only)")
+    ),
+    responses(
+        (status = 101, description = "Switched to the WebSocket protocol; every text message is an item event (`item.created`, `item.submitted`, `item.confirmed`, `item.failed`) as JSON"),
+        (status = 400, description = "Not a WebSocket upgrade request (plain-text body)"),
+        (status = 403, description = "Tenant not accessible with this key", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded", body = RateLimitResponse)
+    )
+)]
+pub async fn live_feed_handler(
+    State(state): State<Arc<AppState>>,
+    ApiQuery(params): ApiQuery<LiveFeedParams>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    // The connection outlives the request's tenant scope, so the filter carries it
+    let tenant = match (TenantScope::current(), params.tenant) {
+        (Some(scope), Some(tenant)) if scope != tenant => {
+            return error_response(
+                StatusCode::FORBIDDEN,
+                "forbidden",
+                format!("Tenant '{}' is not accessible with this key", tenant),
+            );
+        }
+        (scope, tenant) => scope.or(tenant),
+    };
+    let filter = FeedFilter {
+        tenant,
+        tag: params.tag,
+    };
+    let feed = state.service.feed().clone();
+    upgrade.on_upgrade(move |socket| stream_feed(socket, feed, filter))
+}
+
+/// Forward matching events to `socket` until either side closes or the feed shuts down
+async fn stream_feed(mut socket: WebSocket, feed: ItemFeed, filter: FeedFilter) {
+    let mut events = feed.subscribe();
+    let closed = feed.closed();
+    tokio::pin!(closed);
+    let mut ping = interval_at(Instant::now() + PING_INTERVAL, PING_INTERVAL);
+    ping.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut last_heard = Instant::now();
+    metrics::gauge!("ws_connections").increment(1.0);
+
+    loop {
+        let outgoing = tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) if filter.matches(&event) => match serde_json::to_string(&event.event) {
+                    Ok(text) => Message::Text(text.into()),
+                    Err(e) => {
+                        warn!(error = %e, "Failed to serialize live feed event");
+                        continue;
+                    }
+                },
+                Ok(_) => continue,
+                Err(RecvError::Lagged(missed)) => {
+                    metrics::counter!("ws_lagged_events_total").increment(missed);
+                    Message::Text(json!({ "type": "lagged", "missed": missed }).to_string().into())
+                }
+                Err(RecvError::Closed) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                // Pings are answered by axum; anything else only shows the client is alive
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {
+                    last_heard = Instant::now();
+                    continue;
+                }
+            },
+            _ = ping.tick() => {
+                if last_heard.elapsed() > 2 * PING_INTERVAL {
+                    debug!("Live feed client stopped answering pings");
+                    break;
+                }
+                Message::Ping(Default::default())
+            }
+            () = &mut closed => {
+                let _ = socket
+                    .send(Message::Close(Some(CloseFrame {
+                        code: close_code::AWAY,
+                        reason: Utf8Bytes::from_static("server shutting down"),
+                    })))
+                    .await;
+                break;
+            }
+        };
+        match timeout(SEND_TIMEOUT, socket.send(outgoing)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(_)) => break,
+            Err(_) => {
+                metrics::counter!("ws_send_timeouts_total").increment(1);
+                debug!("Live feed client too slow; disconnecting");
+                break;
+            }
+        }
+    }
+    metrics::gauge!("ws_connections").decrement(1.0);
+}
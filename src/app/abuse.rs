@@ -0,0 +1,319 @@
+//! Temporary bans for sources that keep failing authentication or hitting the rate limit.
+//!
+//! `401` and `429` responses are counted per client address and per presented API key.
+//! A source that collects `ABUSE_*_THRESHOLD` of one kind within `ABUSE_WINDOW_SECS` is
+//! banned for `ABUSE_BAN_SECS`. Counters live in a bounded LRU like the rate limiters'
+//! state, and bans are kept in memory on each instance and expire on their own.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::net::IpAddr;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use lru::LruCache;
+use sha2::{Digest, Sha256};
+
+use crate::domain::TemporaryBan;
+
+/// Window violations are counted in unless configured otherwise
+pub const DEFAULT_ABUSE_WINDOW: Duration = Duration::from_secs(60);
+
+/// How long a ban lasts unless configured otherwise
+pub const DEFAULT_BAN_DURATION: Duration = Duration::from_secs(900);
+
+/// Default cap on sources with violations being counted
+pub const DEFAULT_MAX_TRACKED_SOURCES: usize = 100_000;
+
+/// Response that counts against a source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Violation {
+    /// `401`: missing or invalid API key
+    Unauthorized,
+    /// `429`: rate limit exceeded
+    RateLimited,
+}
+
+impl Violation {
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unauthorized => "unauthorized",
+            Self::RateLimited => "rate_limited",
+        }
+    }
+}
+
+/// Source violations are counted for and bans apply to
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AbuseSubject {
+    Ip(IpAddr),
+    /// SHA-256 prefix of a presented API key (the key itself is never kept)
+    ApiKey(String),
+}
+
+impl AbuseSubject {
+    /// Subject of the API key sent as `secret`, valid or not
+    #[must_use]
+    pub fn api_key(secret: &str) -> Self {
+        let digest = format!("{:x}", Sha256::digest(secret.as_bytes()));
+        Self::ApiKey(digest[..16].to_string())
+    }
+}
+
+impl fmt::Display for AbuseSubject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ip(ip) => write!(f, "ip:{ip}"),
+            Self::ApiKey(prefix) => write!(f, "key:{prefix}"),
+        }
+    }
+}
+
+/// Thresholds and durations of automatic bans
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbuseConfig {
+    /// `401`s within the window that ban a source (None: never)
+    pub unauthorized_threshold: Option<u32>,
+    /// `429`s within the window that ban a source (None: never)
+    pub rate_limited_threshold: Option<u32>,
+    pub window: Duration,
+    pub ban_duration: Duration,
+    pub max_tracked: NonZeroUsize,
+}
+
+impl Default for AbuseConfig {
+    fn default() -> Self {
+        Self {
+            unauthorized_threshold: None,
+            rate_limited_threshold: None,
+            window: DEFAULT_ABUSE_WINDOW,
+            ban_duration: DEFAULT_BAN_DURATION,
+            max_tracked: NonZeroUsize::new(DEFAULT_MAX_TRACKED_SOURCES).unwrap(),
+        }
+    }
+}
+
+impl AbuseConfig {
+    /// Create config from environment variables (no threshold set: bans are off)
+    #[must_use]
+    pub fn from_env() -> Self {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+        };
+        let threshold = |name: &str| {
+            var(name)
+                .filter(|n| *n > 0)
+                .map(|n| n.min(u64::from(u32::MAX)) as u32)
+        };
+        let defaults = Self::default();
+        Self {
+            unauthorized_threshold: threshold("ABUSE_UNAUTHORIZED_THRESHOLD"),
+            rate_limited_threshold: threshold("ABUSE_RATE_LIMITED_THRESHOLD"),
+            window: var("ABUSE_WINDOW_SECS")
+                .filter(|n| *n > 0)
+                .map_or(defaults.window, Duration::from_secs),
+            ban_duration: var("ABUSE_BAN_SECS")
+                .filter(|n| *n > 0)
+                .map_or(defaults.ban_duration, Duration::from_secs),
+            max_tracked: var("ABUSE_MAX_TRACKED")
+                .and_then(|n| NonZeroUsize::new(n as usize))
+                .unwrap_or(defaults.max_tracked),
+        }
+    }
+
+    fn threshold(&self, violation: Violation) -> Option<u32> {
+        match violation {
+            Violation::Unauthorized => self.unauthorized_threshold,
+            Violation::RateLimited => self.rate_limited_threshold,
+        }
+    }
+}
+
+/// Violation counter of one source: the start of its window and the count in it
+struct Window {
+    started_at: DateTime<Utc>,
+    count: u32,
+}
+
+/// Counts violations and holds the active bans
+pub struct AbuseGuard {
+    config: AbuseConfig,
+    windows: Mutex<LruCache<(AbuseSubject, Violation), Window>>,
+    bans: Mutex<HashMap<AbuseSubject, TemporaryBan>>,
+}
+
+impl AbuseGuard {
+    #[must_use]
+    pub fn new(config: AbuseConfig) -> Self {
+        Self {
+            windows: Mutex::new(LruCache::new(config.max_tracked)),
+            bans: Mutex::new(HashMap::new()),
+            config,
+        }
+    }
+
+    /// Guard that never bans
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self::new(AbuseConfig::default())
+    }
+
+    /// Whether any threshold is set
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.config.unauthorized_threshold.is_some() || self.config.rate_limited_threshold.is_some()
+    }
+
+    /// Active ban on any of `subjects`
+    #[must_use]
+    pub fn ban_for(&self, subjects: &[AbuseSubject]) -> Option<TemporaryBan> {
+        let mut bans = self.bans.lock().unwrap();
+        Self::prune(&mut bans);
+        subjects
+            .iter()
+            .find_map(|subject| bans.get(subject).cloned())
+    }
+
+    /// Count a violation by `subject`; returns the ban if this one crossed the threshold
+    pub fn record(&self, subject: AbuseSubject, violation: Violation) -> Option<TemporaryBan> {
+        let threshold = self.config.threshold(violation)?;
+        let now = Utc::now();
+        let window = chrono::Duration::from_std(self.config.window).unwrap_or_default();
+        let key = (subject, violation);
+        {
+            let mut windows = self.windows.lock().unwrap();
+            let entry = windows.get_or_insert_mut(key.clone(), || Window {
+                started_at: now,
+                count: 0,
+            });
+            if now - entry.started_at >= window {
+                entry.started_at = now;
+                entry.count = 0;
+            }
+            entry.count += 1;
+            if entry.count < threshold {
+                return None;
+            }
+            windows.pop(&key);
+        }
+
+        let (subject, violation) = key;
+        let ban = TemporaryBan {
+            subject: subject.to_string(),
+            reason: violation.as_str().to_string(),
+            banned_at: now,
+            expires_at: now
+                + chrono::Duration::from_std(self.config.ban_duration).unwrap_or_default(),
+        };
+        let mut bans = self.bans.lock().unwrap();
+        bans.insert(subject, ban.clone());
+        Self::prune(&mut bans);
+        Some(ban)
+    }
+
+    /// Active bans, soonest to expire first
+    #[must_use]
+    pub fn active_bans(&self) -> Vec<TemporaryBan> {
+        let mut bans = self.bans.lock().unwrap();
+        Self::prune(&mut bans);
+        let mut active: Vec<TemporaryBan> = bans.values().cloned().collect();
+        active.sort_by(|a, b| (a.expires_at, &a.subject).cmp(&(b.expires_at, &b.subject)));
+        active
+    }
+
+    /// Lift the ban on `subject` (as shown in [`TemporaryBan::subject`]) before it expires
+    pub fn lift(&self, subject: &str) -> Option<TemporaryBan> {
+        let mut bans = self.bans.lock().unwrap();
+        let key = bans
+            .keys()
+            .find(|key| key.to_string() == subject)
+            .cloned()?;
+        let ban = bans.remove(&key);
+        Self::prune(&mut bans);
+        ban
+    }
+
+    /// Drop expired bans and report how many remain
+    fn prune(bans: &mut HashMap<AbuseSubject, TemporaryBan>) {
+        let now = Utc::now();
+        bans.retain(|_, ban| ban.expires_at > now);
+        metrics::gauge!("abuse_active_bans").set(bans.len() as f64);
+    }
+}
+
+impl Default for AbuseGuard {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guard(threshold: u32, ban_duration: Duration) -> AbuseGuard {
+        AbuseGuard::new(AbuseConfig {
+            unauthorized_threshold: Some(threshold),
+            ban_duration,
+            ..AbuseConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_threshold_bans_subject_until_it_expires() {
+        let guard = guard(3, Duration::from_millis(50));
+        let ip = AbuseSubject::Ip("203.0.113.7".parse().unwrap());
+        let other = AbuseSubject::Ip("203.0.113.8".parse().unwrap());
+
+        assert!(guard.record(ip.clone(), Violation::Unauthorized).is_none());
+        assert!(guard.record(ip.clone(), Violation::Unauthorized).is_none());
+        // Other sources and other violations count separately
+        assert!(
+            guard
+                .record(other.clone(), Violation::Unauthorized)
+                .is_none()
+        );
+        assert!(guard.record(ip.clone(), Violation::RateLimited).is_none());
+        assert!(guard.ban_for(std::slice::from_ref(&ip)).is_none());
+
+        let ban = guard.record(ip.clone(), Violation::Unauthorized).unwrap();
+        assert_eq!(ban.subject, "ip:203.0.113.7");
+        assert_eq!(ban.reason, "unauthorized");
+        assert_eq!(guard.ban_for(&[other.clone(), ip.clone()]), Some(ban));
+        assert!(guard.ban_for(&[other]).is_none());
+        assert_eq!(guard.active_bans().len(), 1);
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(guard.ban_for(&[ip]).is_none());
+        assert!(guard.active_bans().is_empty());
+    }
+
+    #[test]
+    fn test_key_subjects_and_lifting() {
+        let guard = guard(1, DEFAULT_BAN_DURATION);
+        let key = AbuseSubject::api_key("leaked-secret");
+        assert_eq!(key, AbuseSubject::api_key("leaked-secret"));
+        assert!(!key.to_string().contains("leaked"));
+
+        let ban = guard.record(key.clone(), Violation::Unauthorized).unwrap();
+        assert!(ban.subject.starts_with("key:"));
+        assert!(guard.lift("key:unknown").is_none());
+        assert_eq!(guard.lift(&ban.subject), Some(ban));
+        assert!(guard.ban_for(&[key]).is_none());
+    }
+
+    #[test]
+    fn test_disabled_guard_never_bans() {
+        let guard = AbuseGuard::disabled();
+        assert!(!guard.is_enabled());
+        let ip = AbuseSubject::Ip("203.0.113.7".parse().unwrap());
+        for _ in 0..100 {
+            assert!(guard.record(ip.clone(), Violation::RateLimited).is_none());
+        }
+    }
+}
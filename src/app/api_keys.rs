@@ -0,0 +1,227 @@
+//! API key issuance and resolution.
+//!
+//! Secrets are random, shown to the caller once, and stored only as SHA-256 hashes
+//! through an [`ApiKeyStore`]. The static `API_AUTH_KEY` remains as a bootstrap key
+//! with every scope so the first managed keys can be created.
+
+use rand::RngCore;
+use rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+use validator::Validate;
+
+use crate::domain::{
+    ApiKeyError, ApiKeyStore, CreateApiKeyRequest, CreateApiKeyResponse, DEFAULT_TENANT, Principal,
+    ValidationError,
+};
+
+/// Error type for the key issuance flow (validation or store).
+#[derive(Debug)]
+pub enum IssueApiKeyError {
+    Validation(ValidationError),
+    ApiKey(ApiKeyError),
+}
+
+impl From<ValidationError> for IssueApiKeyError {
+    fn from(e: ValidationError) -> Self {
+        IssueApiKeyError::Validation(e)
+    }
+}
+
+impl From<ApiKeyError> for IssueApiKeyError {
+    fn from(e: ApiKeyError) -> Self {
+        IssueApiKeyError::ApiKey(e)
+    }
+}
+
+/// Prefix that makes issued keys easy to recognise in logs and secret scanners
+const API_KEY_PREFIX: &str = "sk_";
+
+/// Generate a new random key secret (256 bits, Base58-encoded)
+#[must_use]
+pub fn generate_api_key_secret() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    format!("{}{}", API_KEY_PREFIX, bs58::encode(bytes).into_string())
+}
+
+/// Hex-encoded SHA-256 of a key secret, as persisted by the store
+#[must_use]
+pub fn hash_api_key(secret: &str) -> String {
+    format!("{:x}", Sha256::digest(secret.as_bytes()))
+}
+
+/// Validate the request, issue a new key and return its one-time plaintext secret
+pub async fn issue_api_key(
+    store: &dyn ApiKeyStore,
+    request: &CreateApiKeyRequest,
+) -> Result<CreateApiKeyResponse, IssueApiKeyError> {
+    request.validate().map_err(|e| {
+        warn!(error = %e, "API key validation failed");
+        IssueApiKeyError::Validation(ValidationError::from(e))
+    })?;
+
+    let secret = generate_api_key_secret();
+    let tenant_id = request.tenant_id.as_deref().unwrap_or(DEFAULT_TENANT);
+    let key = store
+        .create_api_key(
+            &request.name,
+            &hash_api_key(&secret),
+            &request.scopes,
+            tenant_id,
+            request.quota,
+        )
+        .await?;
+    info!(key_id = %key.id, name = %key.name, tenant_id = %key.tenant_id, "API key created");
+    Ok(CreateApiKeyResponse { key, secret })
+}
+
+/// Replace an active key with a new one holding the same name, scopes, tenant and quota,
+/// revoking the old key; the new secret is returned once
+pub async fn rotate_api_key(
+    store: &dyn ApiKeyStore,
+    id: &str,
+) -> Result<CreateApiKeyResponse, ApiKeyError> {
+    let old = store
+        .list_api_keys()
+        .await?
+        .into_iter()
+        .find(|key| key.id == id)
+        .ok_or_else(|| ApiKeyError::NotFound(id.to_string()))?;
+    if !old.is_active() {
+        return Err(ApiKeyError::Revoked(id.to_string()));
+    }
+
+    let secret = generate_api_key_secret();
+    let key = store
+        .create_api_key(
+            &old.name,
+            &hash_api_key(&secret),
+            &old.scopes,
+            &old.tenant_id,
+            old.quota,
+        )
+        .await?;
+    store.revoke_api_key(&old.id).await?;
+    info!(old_key_id = %old.id, key_id = %key.id, name = %key.name, "API key rotated");
+    Ok(CreateApiKeyResponse { key, secret })
+}
+
+/// Resolve a presented secret to a principal; revoked and unknown keys yield `None`
+pub async fn resolve_api_key(
+    store: &dyn ApiKeyStore,
+    secret: &str,
+) -> Result<Option<Principal>, ApiKeyError> {
+    let key = store.find_api_key_by_hash(&hash_api_key(secret)).await?;
+    Ok(key
+        .filter(crate::domain::ApiKey::is_active)
+        .map(|key| Principal::from(&key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ApiKeyQuota, ApiKeyScope};
+    use crate::test_utils::MockProvider;
+
+    fn request(scopes: Vec<ApiKeyScope>) -> CreateApiKeyRequest {
+        CreateApiKeyRequest {
+            name: "ci".to_string(),
+            scopes,
+            tenant_id: None,
+            quota: ApiKeyQuota::default(),
+        }
+    }
+
+    #[test]
+    fn test_generated_secrets_are_unique_and_prefixed() {
+        let a = generate_api_key_secret();
+        let b = generate_api_key_secret();
+        assert!(a.starts_with(API_KEY_PREFIX));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_hash_is_hex_sha256() {
+        let hash = hash_api_key("secret");
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, hash_api_key("secret"));
+        assert_ne!(hash, hash_api_key("other"));
+    }
+
+    #[tokio::test]
+    async fn test_issued_key_resolves_to_principal() {
+        let store = MockProvider::new();
+        let created = issue_api_key(&store, &request(vec![ApiKeyScope::ItemsWrite]))
+            .await
+            .unwrap();
+
+        let principal = resolve_api_key(&store, &created.secret)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(principal.key_id, created.key.id);
+        assert!(principal.has_scope(ApiKeyScope::ItemsWrite));
+        assert!(!principal.has_scope(ApiKeyScope::Admin));
+    }
+
+    #[tokio::test]
+    async fn test_issue_rejects_empty_scopes() {
+        let store = MockProvider::new();
+        let result = issue_api_key(&store, &request(vec![])).await;
+        assert!(matches!(result, Err(IssueApiKeyError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_revoked_key_does_not_resolve() {
+        let store = MockProvider::new();
+        let created = issue_api_key(&store, &request(vec![ApiKeyScope::ItemsRead]))
+            .await
+            .unwrap();
+        store.revoke_api_key(&created.key.id).await.unwrap();
+
+        let principal = resolve_api_key(&store, &created.secret).await.unwrap();
+        assert!(principal.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_secret_does_not_resolve() {
+        let store = MockProvider::new();
+        let principal = resolve_api_key(&store, "sk_unknown").await.unwrap();
+        assert!(principal.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rotation_replaces_the_key_and_revokes_the_old_one() {
+        let store = MockProvider::new();
+        let old = issue_api_key(&store, &request(vec![ApiKeyScope::Admin]))
+            .await
+            .unwrap();
+
+        let rotated = rotate_api_key(&store, &old.key.id).await.unwrap();
+        assert_ne!(rotated.key.id, old.key.id);
+        assert_eq!(rotated.key.name, "ci");
+        assert_eq!(rotated.key.scopes, vec![ApiKeyScope::Admin]);
+        assert!(
+            resolve_api_key(&store, &old.secret)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            resolve_api_key(&store, &rotated.secret)
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        assert!(matches!(
+            rotate_api_key(&store, &old.key.id).await,
+            Err(ApiKeyError::Revoked(_))
+        ));
+        assert!(matches!(
+            rotate_api_key(&store, "key_missing").await,
+            Err(ApiKeyError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,316 @@
+//! Per-route authorization policy.
+//!
+//! An ordered list of rules maps `(method, path, query)` to the access a request needs.
+//! The first matching rule wins; requests no rule matches are public. Rules from
+//! `AUTH_POLICY` are checked before the built-in defaults, so a fork can tighten or open
+//! up individual routes without touching the middleware.
+//!
+//! One rule per line (or separated by `;`): `METHODS PATH ACCESS`, where
+//! - `METHODS` is `*` or a comma-separated list (`POST,DELETE`)
+//! - `PATH` is segments matched literally, `*` or `{name}` for any one segment, and a
+//!   trailing `**` for any rest (including none); it may end in `?key=value` to only match
+//!   requests with that query parameter
+//! - `ACCESS` is `public`, `authenticated` (any valid key) or a scope (`items:write`)
+//!
+//! ```text
+//! GET /items/** items:read
+//! POST /admin/worker/run-now items:write
+//! ```
+
+use std::fmt;
+
+use crate::domain::{ApiKeyScope, ValidationError};
+
+/// Built-in rules, matching the routes' documented auth requirements
+pub const DEFAULT_AUTH_POLICY: &str = "\
+POST /items/export/bookmarks/*/ack items:read
+POST,PUT,DELETE /items/** items:write
+* /items/**?include_deleted=true admin
+* /requests/** items:write
+* /jobs/** authenticated
+* /health/deep admin
+* /admin/** admin";
+
+/// What a request has to present
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// No API key needed
+    Public,
+    /// Any valid API key
+    Authenticated,
+    /// A key holding this scope
+    Scope(ApiKeyScope),
+}
+
+impl std::str::FromStr for Access {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "public" => Ok(Self::Public),
+            "authenticated" => Ok(Self::Authenticated),
+            scope => scope.parse().map(Self::Scope),
+        }
+    }
+}
+
+impl fmt::Display for Access {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Public => f.write_str("public"),
+            Self::Authenticated => f.write_str("authenticated"),
+            Self::Scope(scope) => write!(f, "{}", scope),
+        }
+    }
+}
+
+/// One segment of a rule's path pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    /// `*` or `{name}`
+    Any,
+    /// Trailing `**`
+    Rest,
+}
+
+/// Rule mapping matching requests to the access they need
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyRule {
+    /// Upper-case method names (empty: any method)
+    methods: Vec<String>,
+    path: Vec<Segment>,
+    /// Required `key=value` query parameter
+    query: Option<(String, String)>,
+    access: Access,
+}
+
+impl PolicyRule {
+    /// Parse `METHODS PATH ACCESS`
+    pub fn parse(rule: &str) -> Result<Self, ValidationError> {
+        let invalid = |message: String| ValidationError::InvalidField {
+            field: "auth_policy".to_string(),
+            message: format!("'{}': {}", rule, message),
+        };
+        let parts: Vec<&str> = rule.split_whitespace().collect();
+        let [methods, pattern, access] = parts[..] else {
+            return Err(invalid("expected `METHODS PATH ACCESS`".to_string()));
+        };
+
+        let methods = if methods == "*" {
+            Vec::new()
+        } else {
+            methods
+                .split(',')
+                .map(|m| m.trim().to_ascii_uppercase())
+                .filter(|m| !m.is_empty())
+                .collect()
+        };
+        let (path, query) = match pattern.split_once('?') {
+            Some((path, query)) => {
+                let (key, value) = query
+                    .split_once('=')
+                    .ok_or_else(|| invalid("query condition must be `key=value`".to_string()))?;
+                (path, Some((key.to_string(), value.to_string())))
+            }
+            None => (pattern, None),
+        };
+        if !path.starts_with('/') {
+            return Err(invalid("path must start with '/'".to_string()));
+        }
+        let segments: Vec<&str> = split_path(path).collect();
+        let path = segments
+            .iter()
+            .enumerate()
+            .map(|(i, segment)| match *segment {
+                "**" if i + 1 == segments.len() => Ok(Segment::Rest),
+                "**" => Err(invalid("'**' is only allowed at the end".to_string())),
+                "*" => Ok(Segment::Any),
+                s if s.starts_with('{') && s.ends_with('}') => Ok(Segment::Any),
+                s => Ok(Segment::Literal(s.to_string())),
+            })
+            .collect::<Result<_, _>>()?;
+        let access = access.parse().map_err(invalid)?;
+
+        Ok(Self {
+            methods,
+            path,
+            query,
+            access,
+        })
+    }
+
+    fn matches(&self, method: &str, path: &str, query: Option<&str>) -> bool {
+        if !self.methods.is_empty() && !self.methods.iter().any(|m| m == method) {
+            return false;
+        }
+        if let Some((key, value)) = &self.query {
+            let found = query
+                .unwrap_or_default()
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .any(|(k, v)| k == key && v == value);
+            if !found {
+                return false;
+            }
+        }
+
+        let mut segments = split_path(path);
+        for pattern in &self.path {
+            match pattern {
+                Segment::Rest => return true,
+                Segment::Any => {
+                    if segments.next().is_none() {
+                        return false;
+                    }
+                }
+                Segment::Literal(literal) => {
+                    if segments.next() != Some(literal.as_str()) {
+                        return false;
+                    }
+                }
+            }
+        }
+        segments.next().is_none()
+    }
+}
+
+/// Non-empty path segments (so `/items` and `/items/` are the same route)
+fn split_path(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|s| !s.is_empty())
+}
+
+/// Ordered authorization rules; the first match decides
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthPolicy {
+    rules: Vec<PolicyRule>,
+}
+
+impl AuthPolicy {
+    /// Parse rules separated by newlines or `;` (blank entries and `#` comments skipped)
+    pub fn parse(rules: &str) -> Result<Self, ValidationError> {
+        let rules = rules
+            .split(['\n', ';'])
+            .map(str::trim)
+            .filter(|rule| !rule.is_empty() && !rule.starts_with('#'))
+            .map(PolicyRule::parse)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Load `AUTH_POLICY` rules, checked before [`DEFAULT_AUTH_POLICY`]
+    pub fn from_env() -> Result<Self, ValidationError> {
+        let custom = Self::parse(&std::env::var("AUTH_POLICY").unwrap_or_default())?;
+        Ok(custom.then(Self::default()))
+    }
+
+    /// This policy followed by `fallback`'s rules
+    #[must_use]
+    pub fn then(mut self, fallback: Self) -> Self {
+        self.rules.extend(fallback.rules);
+        self
+    }
+
+    /// Access required for a request (`query` without the leading `?`)
+    #[must_use]
+    pub fn access(&self, method: &str, path: &str, query: Option<&str>) -> Access {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(method, path, query))
+            .map_or(Access::Public, |rule| rule.access)
+    }
+
+    /// Number of rules
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+impl Default for AuthPolicy {
+    fn default() -> Self {
+        Self::parse(DEFAULT_AUTH_POLICY).expect("default auth policy is valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_matches_routes() {
+        let policy = AuthPolicy::default();
+        let write = Access::Scope(ApiKeyScope::ItemsWrite);
+        let admin = Access::Scope(ApiKeyScope::Admin);
+        assert_eq!(policy.access("GET", "/items", None), Access::Public);
+        assert_eq!(policy.access("GET", "/items/item_1", None), Access::Public);
+        assert_eq!(policy.access("POST", "/items", None), write);
+        assert_eq!(policy.access("POST", "/items/", None), write);
+        assert_eq!(policy.access("POST", "/items/item_1/retry", None), write);
+        assert_eq!(policy.access("PUT", "/items/item_1", None), write);
+        assert_eq!(policy.access("DELETE", "/items/item_1", None), write);
+        assert_eq!(
+            policy.access("POST", "/items/export/bookmarks/analytics/ack", None),
+            Access::Scope(ApiKeyScope::ItemsRead)
+        );
+        assert_eq!(
+            policy.access("GET", "/items", Some("limit=5&include_deleted=true")),
+            admin
+        );
+        assert_eq!(
+            policy.access("GET", "/items", Some("include_deleted=false")),
+            Access::Public
+        );
+        assert_eq!(policy.access("GET", "/requests/key-1", None), write);
+        assert_eq!(
+            policy.access("GET", "/jobs/job_1", None),
+            Access::Authenticated
+        );
+        assert_eq!(policy.access("GET", "/health", None), Access::Public);
+        assert_eq!(policy.access("GET", "/health/deep", None), admin);
+        assert_eq!(policy.access("GET", "/admin/worker", None), admin);
+        assert_eq!(policy.access("GET", "/administrator", None), Access::Public);
+    }
+
+    #[test]
+    fn test_custom_rules_take_precedence() {
+        let policy = AuthPolicy::parse("get /items/{id} items:read; POST /items public")
+            .unwrap()
+            .then(AuthPolicy::default());
+        assert_eq!(
+            policy.access("GET", "/items/item_1", None),
+            Access::Scope(ApiKeyScope::ItemsRead)
+        );
+        assert_eq!(policy.access("GET", "/items", None), Access::Public);
+        assert_eq!(policy.access("POST", "/items", None), Access::Public);
+        assert_eq!(
+            policy.access("POST", "/items/item_1/retry", None),
+            Access::Scope(ApiKeyScope::ItemsWrite)
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_rules() {
+        for rule in [
+            "/items admin",
+            "GET items admin",
+            "GET /items superuser",
+            "GET /**/items admin",
+            "GET /items?deleted admin",
+        ] {
+            assert!(
+                matches!(
+                    AuthPolicy::parse(rule),
+                    Err(ValidationError::InvalidField { .. })
+                ),
+                "{rule}"
+            );
+        }
+        assert!(AuthPolicy::parse("# comment\n\n* /** authenticated").is_ok());
+    }
+}
@@ -0,0 +1,142 @@
+//! IP deny-list for quickly blocking abusive sources.
+//!
+//! Ranges are loaded from `IP_BLOCKLIST` at startup and can be replaced at runtime
+//! through the admin API without a restart (hot reload).
+
+use std::net::IpAddr;
+use std::sync::RwLock;
+
+use ipnet::IpNet;
+
+use crate::domain::ValidationError;
+
+/// Hot-reloadable set of blocked CIDR ranges
+#[derive(Debug, Default)]
+pub struct IpBlocklist {
+    ranges: RwLock<Vec<IpNet>>,
+    /// If true, X-Forwarded-For / X-Real-IP are used when ConnectInfo is missing
+    /// (only safe behind a trusted proxy; mirrors `RateLimitConfig::trust_proxy_headers`).
+    trust_proxy_headers: bool,
+}
+
+impl IpBlocklist {
+    /// Create a blocklist with the given ranges
+    #[must_use]
+    pub fn new(ranges: Vec<IpNet>, trust_proxy_headers: bool) -> Self {
+        Self {
+            ranges: RwLock::new(ranges),
+            trust_proxy_headers,
+        }
+    }
+
+    /// Create an empty blocklist (every request passes)
+    #[must_use]
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Create blocklist from environment variables.
+    /// `IP_BLOCKLIST` is a comma-separated list of CIDR ranges or single addresses.
+    pub fn from_env() -> Result<Self, ValidationError> {
+        let raw = std::env::var("IP_BLOCKLIST").unwrap_or_default();
+        let entries: Vec<String> = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect();
+        let ranges = Self::parse_ranges(&entries)?;
+        let trust_proxy_headers = std::env::var("IP_BLOCKLIST_TRUST_PROXY_HEADERS")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+        Ok(Self::new(ranges, trust_proxy_headers))
+    }
+
+    /// Parse CIDR ranges; a bare address is treated as a single-host range.
+    pub fn parse_ranges(entries: &[String]) -> Result<Vec<IpNet>, ValidationError> {
+        entries
+            .iter()
+            .map(|entry| {
+                let entry = entry.trim();
+                entry
+                    .parse::<IpNet>()
+                    .or_else(|_| entry.parse::<IpAddr>().map(IpNet::from))
+                    .map_err(|_| ValidationError::InvalidField {
+                        field: "cidrs".to_string(),
+                        message: format!("'{}' is not a valid CIDR range or IP address", entry),
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns true if the address falls in any blocked range
+    #[must_use]
+    pub fn is_blocked(&self, ip: IpAddr) -> bool {
+        let ranges = self.ranges.read().unwrap();
+        ranges.iter().any(|range| range.contains(&ip))
+    }
+
+    /// Atomically replace all blocked ranges
+    pub fn replace(&self, ranges: Vec<IpNet>) {
+        *self.ranges.write().unwrap() = ranges;
+    }
+
+    /// Snapshot of the currently blocked ranges
+    #[must_use]
+    pub fn ranges(&self) -> Vec<IpNet> {
+        self.ranges.read().unwrap().clone()
+    }
+
+    /// Whether proxy headers are trusted for resolving the client address
+    #[must_use]
+    pub fn trust_proxy_headers(&self) -> bool {
+        self.trust_proxy_headers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(entries: &[&str]) -> Vec<IpNet> {
+        let entries: Vec<String> = entries.iter().map(|s| s.to_string()).collect();
+        IpBlocklist::parse_ranges(&entries).unwrap()
+    }
+
+    #[test]
+    fn test_empty_blocklist_blocks_nothing() {
+        let blocklist = IpBlocklist::empty();
+        assert!(!blocklist.is_blocked("10.0.0.1".parse().unwrap()));
+        assert!(blocklist.ranges().is_empty());
+    }
+
+    #[test]
+    fn test_cidr_range_matching() {
+        let blocklist = IpBlocklist::new(ranges(&["10.0.0.0/8", "2001:db8::/32"]), false);
+        assert!(blocklist.is_blocked("10.1.2.3".parse().unwrap()));
+        assert!(blocklist.is_blocked("2001:db8::1".parse().unwrap()));
+        assert!(!blocklist.is_blocked("192.168.1.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_bare_address_is_single_host() {
+        let blocklist = IpBlocklist::new(ranges(&["192.168.1.1"]), false);
+        assert!(blocklist.is_blocked("192.168.1.1".parse().unwrap()));
+        assert!(!blocklist.is_blocked("192.168.1.2".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_parse_ranges_rejects_invalid_entry() {
+        let entries = vec!["10.0.0.0/8".to_string(), "not-an-ip".to_string()];
+        let result = IpBlocklist::parse_ranges(&entries);
+        assert!(matches!(result, Err(ValidationError::InvalidField { .. })));
+    }
+
+    #[test]
+    fn test_replace_hot_reloads_ranges() {
+        let blocklist = IpBlocklist::new(ranges(&["10.0.0.0/8"]), false);
+        blocklist.replace(ranges(&["172.16.0.0/12"]));
+        assert!(!blocklist.is_blocked("10.0.0.1".parse().unwrap()));
+        assert!(blocklist.is_blocked("172.16.5.4".parse().unwrap()));
+    }
+}
@@ -0,0 +1,59 @@
+//! Request body size limits per route group.
+//!
+//! Item content is capped at 1 MiB by validation, but only after the body was buffered.
+//! These limits reject a larger body while it is read (or up front from its
+//! `Content-Length`) with a structured `413 payload_too_large`. The import upload and
+//! the admin API get their own limits; routes that take no body share a small default.
+
+/// Largest body of item writes and the other item API routes
+pub const DEFAULT_ITEMS_BODY_LIMIT: usize = 2 * 1024 * 1024;
+/// Largest `POST /items/import` upload
+pub const DEFAULT_IMPORT_BODY_LIMIT: usize = 16 * 1024 * 1024;
+/// Largest body of `/admin` requests
+pub const DEFAULT_ADMIN_BODY_LIMIT: usize = 1024 * 1024;
+/// Largest body of the routes that take none (`/health`, `/jobs`, `/requests`, `/metrics`)
+pub const DEFAULT_BODY_LIMIT: usize = 64 * 1024;
+
+/// Body limits in bytes, per route group
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BodyLimits {
+    /// `/items` (except the import), `/verify/receipt` and `/graphql`
+    pub items: usize,
+    /// `POST /items/import`
+    pub import: usize,
+    pub admin: usize,
+    /// Every other route
+    pub default: usize,
+}
+
+impl Default for BodyLimits {
+    fn default() -> Self {
+        Self {
+            items: DEFAULT_ITEMS_BODY_LIMIT,
+            import: DEFAULT_IMPORT_BODY_LIMIT,
+            admin: DEFAULT_ADMIN_BODY_LIMIT,
+            default: DEFAULT_BODY_LIMIT,
+        }
+    }
+}
+
+impl BodyLimits {
+    /// Create config from environment variables
+    /// (`BODY_LIMIT_{ITEMS,IMPORT,ADMIN,DEFAULT}_BYTES`; unset or zero keeps the default).
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let limit = |name: &str, default: usize| {
+            std::env::var(format!("BODY_LIMIT_{name}_BYTES"))
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(default)
+        };
+        Self {
+            items: limit("ITEMS", defaults.items),
+            import: limit("IMPORT", defaults.import),
+            admin: limit("ADMIN", defaults.admin),
+            default: limit("DEFAULT", defaults.default),
+        }
+    }
+}
@@ -0,0 +1,429 @@
+//! Message consumer: the inbound side of the message bus.
+//!
+//! A [`MessageConsumer`] subscribes to one subject, decodes each message as a
+//! [`DomainEvent`] and hands it to the [`MessageHandler`]s registered for its name. A
+//! handler failing with [`HandlerError::Transient`] is retried with the consumer's
+//! [`RetryPolicy`]; a [`HandlerError::Permanent`] failure, an exhausted retry budget or a
+//! message that does not decode goes to the dead-letter queue with the reason. Handlers
+//! may see a message again (redelivery, or a dead-lettered message replayed by an
+//! operator), so they must be idempotent.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use thiserror::Error;
+use tokio::sync::watch;
+use tracing::{debug, error, info, warn};
+
+use super::retry::{BackoffStrategy, RetryPolicy};
+use crate::domain::{DomainEvent, InboundMessage, MessageSubscriber};
+
+/// Subject consumed when `MESSAGE_CONSUMER_SUBJECT` is unset (every item event)
+pub const DEFAULT_CONSUMER_SUBJECT: &str = "items.>";
+
+/// Failure of a [`MessageHandler`]
+#[derive(Debug, Clone, Error)]
+pub enum HandlerError {
+    /// Retrying may help (e.g. a dependency is briefly down)
+    #[error("{0}")]
+    Transient(String),
+    /// Retrying cannot help; the message is dead-lettered at once
+    #[error("{0}")]
+    Permanent(String),
+}
+
+/// Processes one kind of domain event
+#[async_trait]
+pub trait MessageHandler: Send + Sync {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), HandlerError>;
+}
+
+/// Configuration for the message consumer
+#[derive(Debug, Clone)]
+pub struct ConsumerConfig {
+    /// Subject subscribed to (broker wildcards allowed)
+    pub subject: String,
+    /// Attempts and backoff of a handler failing with [`HandlerError::Transient`]
+    pub retry: RetryPolicy,
+    pub enabled: bool,
+}
+
+impl Default for ConsumerConfig {
+    /// 5 attempts per handler, 200ms after the first failure, doubling up to 5 seconds
+    fn default() -> Self {
+        Self {
+            subject: DEFAULT_CONSUMER_SUBJECT.to_string(),
+            retry: RetryPolicy {
+                strategy: BackoffStrategy::Exponential,
+                max_attempts: 5,
+                base_delay: Duration::from_millis(100),
+                max_delay: Duration::from_secs(5),
+            },
+            enabled: false,
+        }
+    }
+}
+
+impl ConsumerConfig {
+    /// Create config from environment variables (`MESSAGE_CONSUMER_ENABLED`,
+    /// `MESSAGE_CONSUMER_SUBJECT`, `MESSAGE_HANDLER_RETRY_*`)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            subject: std::env::var("MESSAGE_CONSUMER_SUBJECT")
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+                .unwrap_or(defaults.subject),
+            retry: RetryPolicy::from_env("MESSAGE_HANDLER_RETRY", defaults.retry),
+            enabled: std::env::var("MESSAGE_CONSUMER_ENABLED")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(defaults.enabled),
+        }
+    }
+}
+
+/// What became of one message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    /// Every handler registered for the event succeeded
+    Handled,
+    /// No handler is registered for the event
+    Ignored,
+    DeadLettered,
+}
+
+impl Delivery {
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Handled => "handled",
+            Self::Ignored => "ignored",
+            Self::DeadLettered => "dead_lettered",
+        }
+    }
+}
+
+/// Background worker dispatching bus messages to handlers by event name
+pub struct MessageConsumer {
+    subscriber: Arc<dyn MessageSubscriber>,
+    /// Handlers by event name (e.g. `item.confirmed`), run in registration order
+    handlers: HashMap<&'static str, Vec<Arc<dyn MessageHandler>>>,
+    config: ConsumerConfig,
+}
+
+impl MessageConsumer {
+    pub fn new(subscriber: Arc<dyn MessageSubscriber>, config: ConsumerConfig) -> Self {
+        Self {
+            subscriber,
+            handlers: HashMap::new(),
+            config,
+        }
+    }
+
+    /// Run `handler` for every event named `event` (see [`DomainEvent::name`])
+    #[must_use]
+    pub fn with_handler(mut self, event: &'static str, handler: Arc<dyn MessageHandler>) -> Self {
+        self.handlers.entry(event).or_default().push(handler);
+        self
+    }
+
+    /// Consume until shutdown or until the subscription ends
+    pub async fn run(self, mut shutdown_rx: watch::Receiver<bool>) {
+        if !self.config.enabled {
+            info!("Message consumer is disabled");
+            return;
+        }
+        let mut messages = match self.subscriber.subscribe(&self.config.subject).await {
+            Ok(messages) => messages,
+            Err(e) => {
+                error!(error = %e, "Message consumer could not subscribe");
+                return;
+            }
+        };
+        info!(subject = %self.config.subject, "Starting message consumer");
+
+        loop {
+            tokio::select! {
+                message = messages.next() => {
+                    let Some(message) = message else {
+                        warn!(subject = %self.config.subject, "Subscription closed; message consumer stopping");
+                        break;
+                    };
+                    self.process(&message).await;
+                }
+                result = shutdown_rx.changed() => {
+                    if result.is_err() || *shutdown_rx.borrow() {
+                        info!("Message consumer shutting down");
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Decode `message` and run its handlers, dead-lettering it when they cannot succeed
+    pub async fn process(&self, message: &InboundMessage) -> Delivery {
+        let event: DomainEvent = match serde_json::from_slice(&message.payload) {
+            Ok(event) => event,
+            Err(e) => {
+                let reason = format!("Undecodable message: {e}");
+                return self.dead_letter(message, "unknown", &reason).await;
+            }
+        };
+        let name = event.name();
+        let Some(handlers) = self.handlers.get(name) else {
+            debug!(event = name, item_id = %event.item_id, "No handler for message");
+            return record(name, Delivery::Ignored);
+        };
+        for handler in handlers {
+            if let Err(e) = self.handle_with_retry(handler.as_ref(), &event).await {
+                return self.dead_letter(message, name, &e.to_string()).await;
+            }
+        }
+        record(name, Delivery::Handled)
+    }
+
+    /// Run `handler`, retrying transient failures until the retry policy is used up
+    async fn handle_with_retry(
+        &self,
+        handler: &dyn MessageHandler,
+        event: &DomainEvent,
+    ) -> Result<(), HandlerError> {
+        let mut failures = 0;
+        loop {
+            match handler.handle(event).await {
+                Ok(()) => return Ok(()),
+                Err(e @ HandlerError::Permanent(_)) => return Err(e),
+                Err(e) => {
+                    failures += 1;
+                    if self.config.retry.is_exhausted(failures) {
+                        return Err(e);
+                    }
+                    let delay = self.config.retry.delay(failures as u32);
+                    warn!(event = event.name(), item_id = %event.item_id, error = %e, attempt = failures, ?delay, "Message handler failed; retrying");
+                    metrics::counter!("messages_handler_retries_total", "event" => event.name())
+                        .increment(1);
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    }
+
+    async fn dead_letter(
+        &self,
+        message: &InboundMessage,
+        event: &'static str,
+        reason: &str,
+    ) -> Delivery {
+        error!(subject = %message.subject, event, reason, "Dead-lettering message");
+        if let Err(e) = self.subscriber.dead_letter(message, reason).await {
+            // Core subscriptions do not redeliver, so the message is lost
+            error!(subject = %message.subject, error = %e, "Failed to dead-letter message");
+            metrics::counter!("messages_dead_letter_failures_total").increment(1);
+        }
+        record(event, Delivery::DeadLettered)
+    }
+}
+
+fn record(event: &'static str, delivery: Delivery) -> Delivery {
+    metrics::counter!("messages_consumed_total", "event" => event, "outcome" => delivery.as_str())
+        .increment(1);
+    delivery
+}
+
+/// Logs every event it is given; a minimal handler for wiring and local development
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LogEventHandler;
+
+#[async_trait]
+impl MessageHandler for LogEventHandler {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), HandlerError> {
+        info!(event = event.name(), item_id = %event.item_id, id = %event.id, "Domain event received");
+        Ok(())
+    }
+}
+
+/// Spawn the message consumer as a tokio task
+pub fn spawn_message_consumer(
+    consumer: MessageConsumer,
+) -> (tokio::task::JoinHandle<()>, watch::Sender<bool>) {
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let handle = tokio::spawn(consumer.run(shutdown_rx));
+    (handle, shutdown_tx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::DomainEventKind;
+    use crate::test_utils::MockMessageSubscriber;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    /// Fails with the queued errors first, then succeeds; records every call
+    #[derive(Default)]
+    struct ScriptedHandler {
+        failures: Mutex<VecDeque<HandlerError>>,
+        calls: Mutex<Vec<DomainEvent>>,
+    }
+
+    impl ScriptedHandler {
+        fn failing(failures: impl IntoIterator<Item = HandlerError>) -> Arc<Self> {
+            Arc::new(Self {
+                failures: Mutex::new(failures.into_iter().collect()),
+                calls: Mutex::default(),
+            })
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.lock().unwrap().len()
+        }
+    }
+
+    #[async_trait]
+    impl MessageHandler for ScriptedHandler {
+        async fn handle(&self, event: &DomainEvent) -> Result<(), HandlerError> {
+            self.calls.lock().unwrap().push(event.clone());
+            match self.failures.lock().unwrap().pop_front() {
+                Some(e) => Err(e),
+                None => Ok(()),
+            }
+        }
+    }
+
+    fn confirmed() -> DomainEvent {
+        DomainEvent::new(
+            "item_1",
+            DomainEventKind::ItemConfirmed {
+                signature: "sig_1".to_string(),
+            },
+        )
+    }
+
+    fn message(event: &DomainEvent) -> InboundMessage {
+        InboundMessage {
+            subject: format!("items.{}", event.name()),
+            payload: serde_json::to_vec(event).unwrap(),
+        }
+    }
+
+    fn consumer(
+        subscriber: &Arc<MockMessageSubscriber>,
+        handler: &Arc<ScriptedHandler>,
+    ) -> MessageConsumer {
+        let config = ConsumerConfig {
+            enabled: true,
+            ..ConsumerConfig::default()
+        };
+        MessageConsumer::new(Arc::clone(subscriber) as Arc<dyn MessageSubscriber>, config)
+            .with_handler(
+                "item.confirmed",
+                Arc::clone(handler) as Arc<dyn MessageHandler>,
+            )
+    }
+
+    #[tokio::test]
+    async fn test_dispatches_by_event_name() {
+        let subscriber = Arc::new(MockMessageSubscriber::new());
+        let handler = ScriptedHandler::failing([]);
+        let consumer = consumer(&subscriber, &handler);
+
+        let event = confirmed();
+        assert_eq!(consumer.process(&message(&event)).await, Delivery::Handled);
+        assert_eq!(handler.calls.lock().unwrap()[..], [event]);
+
+        let created = DomainEvent::new(
+            "item_2",
+            DomainEventKind::ItemCreated {
+                tenant_id: "default".to_string(),
+                hash: "abc".to_string(),
+            },
+        );
+        assert_eq!(
+            consumer.process(&message(&created)).await,
+            Delivery::Ignored
+        );
+        assert_eq!(handler.calls(), 1);
+        assert!(subscriber.get_dead_letters().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_transient_failures_are_retried() {
+        let subscriber = Arc::new(MockMessageSubscriber::new());
+        let handler = ScriptedHandler::failing([
+            HandlerError::Transient("db down".to_string()),
+            HandlerError::Transient("db down".to_string()),
+        ]);
+        let consumer = consumer(&subscriber, &handler);
+
+        let started = tokio::time::Instant::now();
+        assert_eq!(
+            consumer.process(&message(&confirmed())).await,
+            Delivery::Handled
+        );
+        assert_eq!(handler.calls(), 3);
+        // 200ms after the first failure, 400ms after the second
+        assert_eq!(started.elapsed(), Duration::from_millis(600));
+        assert!(subscriber.get_dead_letters().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_exhausted_retries_dead_letter_the_message() {
+        let subscriber = Arc::new(MockMessageSubscriber::new());
+        let handler =
+            ScriptedHandler::failing(vec![HandlerError::Transient("db down".to_string()); 5]);
+        let consumer = consumer(&subscriber, &handler);
+
+        let message = message(&confirmed());
+        assert_eq!(consumer.process(&message).await, Delivery::DeadLettered);
+        assert_eq!(handler.calls(), 5);
+        assert_eq!(
+            subscriber.get_dead_letters(),
+            [(message, "db down".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_permanent_failure_and_bad_payload_skip_retries() {
+        let subscriber = Arc::new(MockMessageSubscriber::new());
+        let handler =
+            ScriptedHandler::failing([HandlerError::Permanent("unknown item".to_string())]);
+        let consumer = consumer(&subscriber, &handler);
+
+        assert_eq!(
+            consumer.process(&message(&confirmed())).await,
+            Delivery::DeadLettered
+        );
+        assert_eq!(handler.calls(), 1);
+
+        let garbage = InboundMessage {
+            subject: "items.item.confirmed".to_string(),
+            payload: b"not json".to_vec(),
+        };
+        assert_eq!(consumer.process(&garbage).await, Delivery::DeadLettered);
+        let dead_letters = subscriber.get_dead_letters();
+        assert_eq!(dead_letters.len(), 2);
+        assert_eq!(dead_letters[0].1, "unknown item");
+        assert!(dead_letters[1].1.starts_with("Undecodable message"));
+    }
+
+    #[tokio::test]
+    async fn test_run_consumes_the_subscription_until_shutdown() {
+        let subscriber = Arc::new(MockMessageSubscriber::new());
+        let handler = ScriptedHandler::failing([]);
+        let (handle, shutdown_tx) = spawn_message_consumer(consumer(&subscriber, &handler));
+
+        subscriber.deliver(message(&confirmed()));
+        subscriber.deliver(message(&confirmed()));
+        while handler.calls() < 2 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(subscriber.subjects(), [DEFAULT_CONSUMER_SUBJECT]);
+
+        shutdown_tx.send(true).unwrap();
+        handle.await.unwrap();
+    }
+}
@@ -0,0 +1,274 @@
+//! Cross-origin (CORS) policy per route group.
+//!
+//! `/items` (with `/requests`, `/jobs`, `/verify/receipt` and `/graphql`), `/admin` and
+//! `/health` each get their own allowed origins and preflight cache lifetime, so public
+//! reads can be open to any site while the admin API only answers an internal console.
+//! A group without a policy sends no CORS headers, which leaves browsers at same-origin.
+//! That strict default applies everywhere unless configured; `CORS_PRESET=permissive`
+//! opens every group to any origin for local development.
+
+use std::time::Duration;
+
+use crate::domain::ValidationError;
+
+/// How long browsers may cache a preflight response unless configured otherwise
+pub const DEFAULT_CORS_MAX_AGE: Duration = Duration::from_secs(600);
+
+/// Origins a group answers cross-origin requests from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CorsOrigins {
+    /// Any origin (`*`)
+    Any,
+    /// Exactly these origins (`scheme://host[:port]`)
+    List(Vec<String>),
+}
+
+/// Methods a policy may allow
+const KNOWN_METHODS: [&str; 7] = ["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"];
+
+/// CORS settings of one route group
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorsPolicy {
+    pub origins: CorsOrigins,
+    /// `Access-Control-Max-Age` of preflight responses
+    pub max_age: Duration,
+    /// Methods allowed cross-origin, uppercase (empty: every method the group serves)
+    pub methods: Vec<String>,
+    /// Request headers allowed on top of the built-in ones (`Content-Type`, `X-Api-Key`, ...),
+    /// lowercase
+    pub headers: Vec<String>,
+}
+
+impl CorsPolicy {
+    /// Parse `*` or a comma-separated list of origins (`none` or empty: no policy)
+    pub fn parse(value: &str, max_age: Duration) -> Result<Option<Self>, ValidationError> {
+        let entries: Vec<&str> = split_list(value).collect();
+        let origins = match entries.as_slice() {
+            [] | ["none"] => return Ok(None),
+            ["*"] => CorsOrigins::Any,
+            _ => CorsOrigins::List(
+                entries
+                    .into_iter()
+                    .map(parse_origin)
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+        Ok(Some(Self {
+            origins,
+            max_age,
+            methods: Vec::new(),
+            headers: Vec::new(),
+        }))
+    }
+
+    /// Any origin, the group's methods and the built-in headers
+    #[must_use]
+    pub fn permissive() -> Self {
+        Self {
+            origins: CorsOrigins::Any,
+            max_age: DEFAULT_CORS_MAX_AGE,
+            methods: Vec::new(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Allow only `methods` (a comma-separated list; empty keeps the group's methods)
+    pub fn with_methods(mut self, methods: &str) -> Result<Self, ValidationError> {
+        self.methods = split_list(methods)
+            .map(|method| {
+                let method = method.to_ascii_uppercase();
+                if KNOWN_METHODS.contains(&method.as_str()) {
+                    Ok(method)
+                } else {
+                    Err(ValidationError::InvalidField {
+                        field: "cors_methods".to_string(),
+                        message: format!("'{}' is not an HTTP method", method),
+                    })
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(self)
+    }
+
+    /// Also allow the request `headers` (a comma-separated list of header names)
+    pub fn with_headers(mut self, headers: &str) -> Result<Self, ValidationError> {
+        self.headers = split_list(headers)
+            .map(|name| {
+                let valid = name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c));
+                if valid {
+                    Ok(name.to_ascii_lowercase())
+                } else {
+                    Err(ValidationError::InvalidField {
+                        field: "cors_headers".to_string(),
+                        message: format!("'{}' is not a header name", name),
+                    })
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(self)
+    }
+}
+
+/// Non-empty trimmed entries of a comma-separated list
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value.split(',').map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// An origin is a scheme and host with an optional port, nothing else
+fn parse_origin(origin: &str) -> Result<String, ValidationError> {
+    let invalid = || ValidationError::InvalidField {
+        field: "cors_origins".to_string(),
+        message: format!(
+            "'{}' is not an origin (expected scheme://host[:port], or a lone '*')",
+            origin
+        ),
+    };
+    let origin = origin.strip_suffix('/').unwrap_or(origin);
+    let (scheme, host) = origin.split_once("://").ok_or_else(invalid)?;
+    let valid = matches!(scheme, "http" | "https")
+        && !host.is_empty()
+        && !host.contains(['/', '?', '#', '@', '*'])
+        && origin.chars().all(|c| c.is_ascii_graphic());
+    if valid {
+        // Browsers send the scheme and host in lowercase
+        Ok(origin.to_ascii_lowercase())
+    } else {
+        Err(invalid())
+    }
+}
+
+/// CORS policies of the route groups (all None by default: no CORS headers anywhere)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorsConfig {
+    /// `/items`, `/requests`, `/jobs`, `/verify/receipt` and `/graphql`
+    pub items: Option<CorsPolicy>,
+    pub admin: Option<CorsPolicy>,
+    pub health: Option<CorsPolicy>,
+}
+
+impl CorsConfig {
+    /// Development preset: every group answers any origin
+    #[must_use]
+    pub fn permissive() -> Self {
+        Self {
+            items: Some(CorsPolicy::permissive()),
+            admin: Some(CorsPolicy::permissive()),
+            health: Some(CorsPolicy::permissive()),
+        }
+    }
+
+    /// Whether any group answers every origin
+    #[must_use]
+    pub fn allows_any_origin(&self) -> bool {
+        [&self.items, &self.admin, &self.health]
+            .into_iter()
+            .flatten()
+            .any(|policy| policy.origins == CorsOrigins::Any)
+    }
+
+    /// Create config from environment variables.
+    ///
+    /// `CORS_ALLOWED_ORIGINS`, `CORS_ALLOWED_METHODS`, `CORS_ALLOWED_HEADERS` and
+    /// `CORS_MAX_AGE_SECS` apply to every group; `CORS_{ITEMS,ADMIN,HEALTH}_ALLOWED_ORIGINS`
+    /// (and `..._METHODS`, `..._HEADERS`, `..._MAX_AGE_SECS`) override them for one group
+    /// (`none` turns CORS off there). `CORS_PRESET=permissive` makes `*` the default origin
+    /// instead of none.
+    pub fn from_env() -> Result<Self, ValidationError> {
+        let var = |name: &str| std::env::var(name).ok();
+        let max_age = |name: &str| var(name).and_then(|v| v.trim().parse().ok());
+        let preset_origins = match var("CORS_PRESET").as_deref().map(str::trim) {
+            None | Some("" | "strict") => "",
+            Some("permissive" | "dev") => "*",
+            Some(other) => {
+                return Err(ValidationError::InvalidField {
+                    field: "cors_preset".to_string(),
+                    message: format!("'{}' is not a preset (strict or permissive)", other),
+                });
+            }
+        };
+        let default_origins =
+            var("CORS_ALLOWED_ORIGINS").unwrap_or_else(|| preset_origins.to_string());
+        let default_methods = var("CORS_ALLOWED_METHODS").unwrap_or_default();
+        let default_headers = var("CORS_ALLOWED_HEADERS").unwrap_or_default();
+        let default_max_age = max_age("CORS_MAX_AGE_SECS")
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_CORS_MAX_AGE);
+        let group = |name: &str| {
+            let origins = var(&format!("CORS_{name}_ALLOWED_ORIGINS"))
+                .unwrap_or_else(|| default_origins.clone());
+            let max_age = max_age(&format!("CORS_{name}_MAX_AGE_SECS"))
+                .map(Duration::from_secs)
+                .unwrap_or(default_max_age);
+            let methods = var(&format!("CORS_{name}_ALLOWED_METHODS"))
+                .unwrap_or_else(|| default_methods.clone());
+            let headers = var(&format!("CORS_{name}_ALLOWED_HEADERS"))
+                .unwrap_or_else(|| default_headers.clone());
+            CorsPolicy::parse(&origins, max_age)?
+                .map(|policy| policy.with_methods(&methods)?.with_headers(&headers))
+                .transpose()
+        };
+        Ok(Self {
+            items: group("ITEMS")?,
+            admin: group("ADMIN")?,
+            health: group("HEALTH")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_policy() {
+        let max_age = Duration::from_secs(60);
+        assert_eq!(CorsPolicy::parse("", max_age).unwrap(), None);
+        assert_eq!(CorsPolicy::parse("none", max_age).unwrap(), None);
+        assert_eq!(
+            CorsPolicy::parse(" * ", max_age).unwrap().unwrap().origins,
+            CorsOrigins::Any
+        );
+        let policy = CorsPolicy::parse("https://app.example.com, http://localhost:5173/", max_age)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            policy.origins,
+            CorsOrigins::List(vec![
+                "https://app.example.com".to_string(),
+                "http://localhost:5173".to_string(),
+            ])
+        );
+        assert_eq!(policy.max_age, max_age);
+    }
+
+    #[test]
+    fn test_methods_and_headers() {
+        let policy = CorsPolicy::permissive()
+            .with_methods("get, Post")
+            .unwrap()
+            .with_headers("X-Tenant-Id, traceparent")
+            .unwrap();
+        assert_eq!(policy.methods, ["GET", "POST"]);
+        assert_eq!(policy.headers, ["x-tenant-id", "traceparent"]);
+        assert!(CorsPolicy::permissive().with_methods("FETCH").is_err());
+        assert!(CorsPolicy::permissive().with_headers("x tenant").is_err());
+        assert!(CorsConfig::permissive().allows_any_origin());
+        assert!(!CorsConfig::default().allows_any_origin());
+    }
+
+    #[test]
+    fn test_parse_rejects_non_origins() {
+        let max_age = DEFAULT_CORS_MAX_AGE;
+        for value in [
+            "app.example.com",
+            "https://app.example.com/path",
+            "ftp://files.example.com",
+            "https://*.example.com",
+            "*, https://app.example.com",
+        ] {
+            assert!(CorsPolicy::parse(value, max_age).is_err(), "{value}");
+        }
+    }
+}
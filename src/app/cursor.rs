@@ -0,0 +1,206 @@
+//! Signed, opaque pagination cursors.
+//!
+//! Repositories page by item ID. Clients never see that ID directly: the service hands
+//! out `base64url(payload).base64url(hmac)` where the payload holds the last item's
+//! `(created_at, id)` and the HMAC-SHA256 is keyed with `CURSOR_SECRET`. A cursor that
+//! does not decode or whose signature does not match is rejected as `invalid_cursor`
+//! before any query runs, so IDs cannot be enumerated through forged cursors. A valid
+//! cursor whose item has since been purged still carries its position, so listing
+//! continues from there (see `AppService::list_items`).
+//!
+//! The admin log listings page by an integer position the same way; their payload also
+//! names the log, so a cursor from one listing is rejected by the other.
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use secrecy::{ExposeSecret, SecretSlice};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::domain::{Item, ItemError};
+
+/// Position after which the next page starts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CursorPayload {
+    created_at: DateTime<Utc>,
+    id: String,
+}
+
+/// Position in an append-only log listed newest first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct PositionPayload {
+    log: String,
+    position: i64,
+}
+
+/// Encodes and verifies pagination cursors with an HMAC key
+pub struct CursorCodec {
+    key: SecretSlice<u8>,
+}
+
+impl CursorCodec {
+    /// Codec keyed with `secret`; every instance serving the same clients needs the same one
+    #[must_use]
+    pub fn new(secret: &[u8]) -> Self {
+        Self {
+            key: SecretSlice::from(secret.to_vec()),
+        }
+    }
+
+    /// Codec with a random key: cursors stop verifying after a restart and are not
+    /// accepted by other instances
+    #[must_use]
+    pub fn ephemeral() -> Self {
+        let mut key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        Self::new(&key)
+    }
+
+    fn mac(&self, payload: &[u8]) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.key.expose_secret())
+            .expect("HMAC accepts keys of any length");
+        mac.update(payload);
+        mac
+    }
+
+    /// `base64url(payload).base64url(hmac)`
+    fn sign(&self, payload: &impl Serialize) -> String {
+        let payload = serde_json::to_vec(payload).expect("cursor payload serializes");
+        let signature = self.mac(&payload).finalize().into_bytes();
+        format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(&payload),
+            URL_SAFE_NO_PAD.encode(signature)
+        )
+    }
+
+    /// Payload of a cursor this codec signed (None: malformed or tampered)
+    fn verify<T: DeserializeOwned>(&self, cursor: &str) -> Option<T> {
+        let (payload, signature) = cursor.split_once('.')?;
+        let payload = URL_SAFE_NO_PAD.decode(payload).ok()?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+        // Constant-time comparison
+        self.mac(&payload).verify_slice(&signature).ok()?;
+        serde_json::from_slice(&payload).ok()
+    }
+
+    /// Cursor pointing after `item`
+    #[must_use]
+    pub fn encode(&self, item: &Item) -> String {
+        self.sign(&CursorPayload {
+            created_at: item.created_at,
+            id: item.id.clone(),
+        })
+    }
+
+    /// Verify `cursor` and return the item ID it points after
+    pub fn decode(&self, cursor: &str) -> Result<String, ItemError> {
+        self.decode_keyset(cursor).map(|(_, id)| id)
+    }
+
+    /// Verify `cursor` and return the `(created_at, id)` position it points after, which
+    /// stays usable once the item itself is gone
+    pub fn decode_keyset(&self, cursor: &str) -> Result<(DateTime<Utc>, String), ItemError> {
+        self.verify::<CursorPayload>(cursor)
+            .map(|payload| (payload.created_at, payload.id))
+            .ok_or_else(|| {
+                ItemError::InvalidCursor("Cursor is malformed or was tampered with".into())
+            })
+    }
+
+    /// Cursor pointing below `position` in the log named `log` (e.g. `item_events`)
+    #[must_use]
+    pub fn encode_position(&self, log: &str, position: i64) -> String {
+        self.sign(&PositionPayload {
+            log: log.to_string(),
+            position,
+        })
+    }
+
+    /// Verify a cursor of the log named `log` and return its position (None: malformed,
+    /// tampered or issued for another log)
+    #[must_use]
+    pub fn decode_position(&self, log: &str, cursor: &str) -> Option<i64> {
+        self.verify::<PositionPayload>(cursor)
+            .filter(|payload| payload.log == log)
+            .map(|payload| payload.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str) -> Item {
+        Item {
+            id: id.to_string(),
+            ..Item::default()
+        }
+    }
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let codec = CursorCodec::new(b"secret");
+        let cursor = codec.encode(&item("item_123"));
+        assert!(!cursor.contains("item_123"), "cursor is opaque");
+        assert_eq!(codec.decode(&cursor).unwrap(), "item_123");
+    }
+
+    #[test]
+    fn test_cursor_keeps_the_item_position() {
+        let codec = CursorCodec::new(b"secret");
+        let created_at = Utc::now();
+        let cursor = codec.encode(&Item {
+            created_at,
+            ..item("item_123")
+        });
+        assert_eq!(
+            codec.decode_keyset(&cursor).unwrap(),
+            (created_at, "item_123".to_string())
+        );
+    }
+
+    #[test]
+    fn test_tampered_and_foreign_cursors_are_rejected() {
+        let codec = CursorCodec::new(b"secret");
+        let cursor = codec.encode(&item("item_123"));
+
+        // Payload swapped for another ID, signature kept
+        let (_, signature) = cursor.split_once('.').unwrap();
+        let forged_payload = serde_json::to_vec(&CursorPayload {
+            created_at: Utc::now(),
+            id: "item_999".to_string(),
+        })
+        .unwrap();
+        let forged = format!("{}.{}", URL_SAFE_NO_PAD.encode(forged_payload), signature);
+
+        for bad in [
+            forged.as_str(),
+            "item_123",
+            "not-base64!.sig",
+            "",
+            &cursor[..cursor.len() - 2],
+        ] {
+            assert!(
+                matches!(codec.decode(bad), Err(ItemError::InvalidCursor(_))),
+                "{bad:?}"
+            );
+        }
+        assert!(CursorCodec::new(b"other").decode(&cursor).is_err());
+    }
+
+    #[test]
+    fn test_position_cursors_are_scoped_to_their_log() {
+        let codec = CursorCodec::new(b"secret");
+        let cursor = codec.encode_position("item_events", 42);
+        assert_eq!(codec.decode_position("item_events", &cursor), Some(42));
+        assert_eq!(codec.decode_position("webhook_deliveries", &cursor), None);
+        assert!(codec.decode(&cursor).is_err());
+        let item_cursor = codec.encode(&item("item_123"));
+        assert_eq!(codec.decode_position("item_events", &item_cursor), None);
+    }
+}
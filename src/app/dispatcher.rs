@@ -0,0 +1,393 @@
+//! Event dispatcher: delivers the item status event log to subscribers.
+//!
+//! Each [`Subscription`] has a cursor (the log position of the last event it accepted)
+//! stored in the [`EventLog`]. The dispatcher reads events after the cursor in log order,
+//! hands them to the subscriber as one batch and advances the cursor only once the batch
+//! was accepted. A failed batch is offered again, unchanged, on the next tick, so every
+//! subscriber sees every event at least once and each item's events in `sequence` order.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+use tracing::{error, info, warn};
+
+use crate::domain::{EventLog, NotificationClient, NotificationError};
+
+/// Configuration for the event dispatcher
+#[derive(Debug, Clone)]
+pub struct DispatcherConfig {
+    pub poll_interval: Duration,
+    /// Maximum events per delivered batch
+    pub batch_size: i64,
+    pub enabled: bool,
+}
+
+impl Default for DispatcherConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(1),
+            batch_size: 100,
+            enabled: true,
+        }
+    }
+}
+
+impl DispatcherConfig {
+    /// Create config from environment variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let poll_interval = std::env::var("EVENT_DISPATCH_INTERVAL_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_millis)
+            .unwrap_or(defaults.poll_interval);
+        let batch_size = std::env::var("EVENT_DISPATCH_BATCH_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(defaults.batch_size);
+        Self {
+            poll_interval,
+            batch_size,
+            ..defaults
+        }
+    }
+}
+
+/// A named consumer of the event log with its own delivery cursor
+#[derive(Clone)]
+pub struct Subscription {
+    /// Cursor key; keep it stable across restarts (e.g. the webhook URL)
+    pub name: String,
+    pub client: Arc<dyn NotificationClient>,
+}
+
+impl Subscription {
+    pub fn new(name: impl Into<String>, client: Arc<dyn NotificationClient>) -> Self {
+        Self {
+            name: name.into(),
+            client,
+        }
+    }
+}
+
+/// Background worker delivering the event log to every subscription
+pub struct EventDispatcher {
+    event_log: Arc<dyn EventLog>,
+    subscriptions: Vec<Subscription>,
+    config: DispatcherConfig,
+    shutdown_rx: watch::Receiver<bool>,
+}
+
+impl EventDispatcher {
+    pub fn new(
+        event_log: Arc<dyn EventLog>,
+        subscriptions: Vec<Subscription>,
+        config: DispatcherConfig,
+        shutdown_rx: watch::Receiver<bool>,
+    ) -> Self {
+        Self {
+            event_log,
+            subscriptions,
+            config,
+            shutdown_rx,
+        }
+    }
+
+    /// Run the dispatcher loop
+    pub async fn run(mut self) {
+        if !self.config.enabled || self.subscriptions.is_empty() {
+            info!("Event dispatcher is disabled");
+            return;
+        }
+
+        info!(
+            subscriptions = self.subscriptions.len(),
+            poll_interval = ?self.config.poll_interval,
+            "Starting event dispatcher"
+        );
+
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(self.config.poll_interval) => {
+                    self.dispatch_once().await;
+                }
+                result = self.shutdown_rx.changed() => {
+                    if result.is_ok() && *self.shutdown_rx.borrow() {
+                        // Deliver what the drained requests and workers recorded last
+                        let flushed = self.dispatch_once().await;
+                        info!(flushed, "Event dispatcher shutting down");
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Deliver pending events to every subscription (concurrently, so a slow endpoint
+    /// does not hold up the others). Returns the number of events delivered.
+    pub async fn dispatch_once(&self) -> usize {
+        let mut deliveries = JoinSet::new();
+        for subscription in &self.subscriptions {
+            let event_log = Arc::clone(&self.event_log);
+            let subscription = subscription.clone();
+            let batch_size = self.config.batch_size;
+            deliveries.spawn(async move {
+                let result =
+                    drain_subscription(event_log.as_ref(), &subscription, batch_size).await;
+                (subscription.name, result)
+            });
+        }
+
+        let mut delivered = 0;
+        while let Some(joined) = deliveries.join_next().await {
+            match joined {
+                Ok((_, Ok(count))) => delivered += count,
+                Ok((name, Err(e))) => {
+                    warn!(subscription = %name, error = %e, "Event delivery failed; will retry");
+                }
+                Err(e) => error!(error = %e, "Event delivery task panicked"),
+            }
+        }
+        delivered
+    }
+}
+
+/// Deliver batches to one subscription until it is caught up or a batch is rejected
+async fn drain_subscription(
+    event_log: &dyn EventLog,
+    subscription: &Subscription,
+    batch_size: i64,
+) -> Result<usize, NotificationError> {
+    let mut delivered = 0;
+    let mut cursor = event_log.subscription_cursor(&subscription.name).await?;
+    loop {
+        let events = event_log.events_after(cursor, batch_size).await?;
+        let Some(last) = events.last() else {
+            return Ok(delivered);
+        };
+        let last_position = last.position;
+        subscription.client.notify(&events).await?;
+        event_log
+            .save_subscription_cursor(&subscription.name, last_position)
+            .await?;
+        metrics::counter!("events_dispatched_total").increment(events.len() as u64);
+        delivered += events.len();
+        cursor = last_position;
+        if (events.len() as i64) < batch_size {
+            return Ok(delivered);
+        }
+    }
+}
+
+/// Spawn the event dispatcher as a tokio task
+pub fn spawn_event_dispatcher(
+    event_log: Arc<dyn EventLog>,
+    subscriptions: Vec<Subscription>,
+    config: DispatcherConfig,
+) -> (tokio::task::JoinHandle<()>, watch::Sender<bool>) {
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let dispatcher = EventDispatcher::new(event_log, subscriptions, config, shutdown_rx);
+    let handle = tokio::spawn(dispatcher.run());
+    (handle, shutdown_tx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppService;
+    use crate::domain::{
+        BlockchainStatus, CreateItemRequest, OutboxRepository, OutboxStatus, SubmissionRepository,
+    };
+    use crate::test_utils::{
+        MockBlockchainClient, MockNotificationClient, MockProvider, mock_repos,
+    };
+
+    struct Fixture {
+        mock: Arc<MockProvider>,
+        service: AppService,
+        webhook: Arc<MockNotificationClient>,
+        dispatcher: EventDispatcher,
+    }
+
+    fn fixture(batch_size: i64) -> Fixture {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let service = AppService::new(
+            item_repo,
+            outbox_repo,
+            Arc::new(MockBlockchainClient::new()),
+        );
+        let webhook = Arc::new(MockNotificationClient::new());
+        let dispatcher = EventDispatcher::new(
+            Arc::clone(&mock) as Arc<dyn EventLog>,
+            vec![Subscription::new(
+                "https://hooks.example.com",
+                Arc::clone(&webhook) as Arc<dyn NotificationClient>,
+            )],
+            DispatcherConfig {
+                batch_size,
+                ..DispatcherConfig::default()
+            },
+            watch::channel(false).1,
+        );
+        Fixture {
+            mock,
+            service,
+            webhook,
+            dispatcher,
+        }
+    }
+
+    async fn create_items(service: &AppService, count: usize) -> Vec<String> {
+        let mut ids = Vec::new();
+        for i in 0..count {
+            let request = CreateItemRequest::new(format!("Item {}", i), "Content".to_string());
+            ids.push(service.create_and_submit_item(&request).await.unwrap().id);
+        }
+        ids
+    }
+
+    #[tokio::test]
+    async fn test_delivers_transitions_in_batches() {
+        let f = fixture(2);
+        create_items(&f.service, 3).await;
+        f.service.process_pending_submissions(10).await.unwrap();
+
+        assert_eq!(f.dispatcher.dispatch_once().await, 3);
+
+        let batch_sizes: Vec<usize> = f.webhook.get_batches().iter().map(Vec::len).collect();
+        assert_eq!(batch_sizes, vec![2, 1]);
+        let positions: Vec<i64> = f.webhook.get_events().iter().map(|e| e.position).collect();
+        assert_eq!(positions, vec![1, 2, 3]);
+        assert!(
+            f.webhook
+                .get_events()
+                .iter()
+                .all(|e| e.status == BlockchainStatus::Submitted && e.sequence == 1)
+        );
+
+        // Caught up: nothing is delivered twice
+        assert_eq!(f.dispatcher.dispatch_once().await, 0);
+        assert_eq!(f.webhook.get_batches().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_flushes_pending_events_on_shutdown() {
+        let f = fixture(100);
+        create_items(&f.service, 2).await;
+        f.service.process_pending_submissions(10).await.unwrap();
+        let (handle, stop) = spawn_event_dispatcher(
+            Arc::clone(&f.mock) as Arc<dyn EventLog>,
+            vec![Subscription::new(
+                "https://hooks.example.com",
+                Arc::clone(&f.webhook) as Arc<dyn NotificationClient>,
+            )],
+            DispatcherConfig {
+                // Never polls on its own during the test
+                poll_interval: Duration::from_secs(3600),
+                ..DispatcherConfig::default()
+            },
+        );
+
+        stop.send(true).unwrap();
+        handle.await.unwrap();
+
+        assert_eq!(f.webhook.get_events().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_batch_is_redelivered() {
+        let f = fixture(100);
+        create_items(&f.service, 1).await;
+        f.service.process_pending_submissions(10).await.unwrap();
+
+        f.webhook.set_failing(true);
+        assert_eq!(f.dispatcher.dispatch_once().await, 0);
+        assert_eq!(
+            f.mock
+                .subscription_cursor("https://hooks.example.com")
+                .await
+                .unwrap(),
+            0
+        );
+
+        f.webhook.set_failing(false);
+        assert_eq!(f.dispatcher.dispatch_once().await, 1);
+        assert_eq!(
+            f.mock
+                .subscription_cursor("https://hooks.example.com")
+                .await
+                .unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_per_item_sequence_follows_transitions() {
+        let f = fixture(100);
+        let ids = create_items(&f.service, 1).await;
+        let item_id = &ids[0];
+        f.service.process_pending_submissions(10).await.unwrap();
+        f.mock
+            .update_blockchain_status(item_id, BlockchainStatus::Confirmed, None, None, None)
+            .await
+            .unwrap();
+        // Re-setting the same status is not a transition
+        f.mock
+            .update_blockchain_status(item_id, BlockchainStatus::Confirmed, None, None, None)
+            .await
+            .unwrap();
+
+        f.dispatcher.dispatch_once().await;
+
+        let events = f.webhook.get_events();
+        let seen: Vec<(i64, BlockchainStatus)> =
+            events.iter().map(|e| (e.sequence, e.status)).collect();
+        assert_eq!(
+            seen,
+            vec![
+                (1, BlockchainStatus::Submitted),
+                (2, BlockchainStatus::Confirmed)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_only_final_failure_is_an_event() {
+        let f = fixture(100);
+        let ids = create_items(&f.service, 1).await;
+        let entry = f.mock.get_all_outbox_entries().pop().unwrap();
+        for (retry_count, outbox_status, item_status) in [
+            (
+                1,
+                OutboxStatus::Pending,
+                BlockchainStatus::PendingSubmission,
+            ),
+            (10, OutboxStatus::Failed, BlockchainStatus::Failed),
+        ] {
+            f.mock
+                .fail_solana_outbox(
+                    &entry.id,
+                    &ids[0],
+                    retry_count,
+                    outbox_status,
+                    item_status,
+                    "rpc error",
+                    None,
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+
+        f.dispatcher.dispatch_once().await;
+
+        let events = f.webhook.get_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, "item.failed");
+        assert_eq!(events[0].error.as_deref(), Some("rpc error"));
+    }
+}
@@ -0,0 +1,284 @@
+//! Startup self-check (`doctor` subcommand).
+//!
+//! Each check of configuration and dependencies adds a line to a [`DoctorReport`], which
+//! is printed instead of starting the server. The binary exits non-zero when any check
+//! failed, so the command can gate a deployment.
+
+use std::fmt;
+
+use ed25519_dalek::{Signature, VerifyingKey};
+use k256::ecdsa::{RecoveryId, Signature as EcdsaSignature, VerifyingKey as EcdsaVerifyingKey};
+use sha2::{Digest, Sha256};
+
+use crate::domain::{SignatureScheme, TransactionSigner};
+
+/// Outcome of one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    /// Works, but needs attention (e.g. migrations that startup would apply)
+    Warn,
+    /// The server would fail to start or could not do its job
+    Fail,
+    /// Not applicable to this configuration
+    Skip,
+}
+
+impl CheckStatus {
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Pass => "✓",
+            Self::Warn => "⚠",
+            Self::Fail => "✗",
+            Self::Skip => "○",
+        }
+    }
+}
+
+/// One line of the report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+/// Results of the self-check, in the order they ran
+#[derive(Debug, Clone, Default)]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    pub fn record(
+        &mut self,
+        name: impl Into<String>,
+        status: CheckStatus,
+        detail: impl Into<String>,
+    ) {
+        self.checks.push(DoctorCheck {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        });
+    }
+
+    pub fn pass(&mut self, name: impl Into<String>, detail: impl Into<String>) {
+        self.record(name, CheckStatus::Pass, detail);
+    }
+
+    pub fn warn(&mut self, name: impl Into<String>, detail: impl Into<String>) {
+        self.record(name, CheckStatus::Warn, detail);
+    }
+
+    pub fn fail(&mut self, name: impl Into<String>, detail: impl Into<String>) {
+        self.record(name, CheckStatus::Fail, detail);
+    }
+
+    pub fn skip(&mut self, name: impl Into<String>, detail: impl Into<String>) {
+        self.record(name, CheckStatus::Skip, detail);
+    }
+
+    /// Record `result` as a pass with its detail, or a failure with the error
+    pub fn check<E: fmt::Display>(&mut self, name: impl Into<String>, result: Result<String, E>) {
+        match result {
+            Ok(detail) => self.pass(name, detail),
+            Err(e) => self.fail(name, e.to_string()),
+        }
+    }
+
+    /// Number of failed checks
+    #[must_use]
+    pub fn failures(&self) -> usize {
+        self.checks
+            .iter()
+            .filter(|check| check.status == CheckStatus::Fail)
+            .count()
+    }
+
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.failures() == 0
+    }
+}
+
+impl fmt::Display for DoctorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .checks
+            .iter()
+            .map(|check| check.name.len())
+            .max()
+            .unwrap_or(0);
+        for check in &self.checks {
+            writeln!(
+                f,
+                "  {} {:width$}  {}",
+                check.status.symbol(),
+                check.name,
+                check.detail
+            )?;
+        }
+        let warnings = self
+            .checks
+            .iter()
+            .filter(|check| check.status == CheckStatus::Warn)
+            .count();
+        match self.failures() {
+            0 => writeln!(f, "\nReady to start ({} warnings)", warnings),
+            failures => writeln!(
+                f,
+                "\n{} checks failed, {} warnings; the server would not run correctly",
+                failures, warnings
+            ),
+        }
+    }
+}
+
+/// Sign a probe message with `signer` and verify the signature against its public key.
+/// Returns the public key.
+pub async fn verify_signer(signer: &dyn TransactionSigner) -> Result<String, String> {
+    let public_key = signer.public_key();
+    let key_bytes = bs58::decode(&public_key)
+        .into_vec()
+        .map_err(|_| format!("public key {} is not base58", public_key))?;
+    // Secp256k1 signers sign a 32-byte prehash; Ed25519 signs the message itself
+    let probe: [u8; 32] = Sha256::digest(b"doctor: signer self-check").into();
+    let signature = signer
+        .sign_message(&probe)
+        .await
+        .map_err(|e| format!("signing failed: {}", e))?;
+    let signature = bs58::decode(&signature)
+        .into_vec()
+        .map_err(|_| "signature is not base58".to_string())?;
+    let verified = match signer.scheme() {
+        SignatureScheme::Ed25519 => {
+            let key = <[u8; 32]>::try_from(key_bytes.as_slice())
+                .ok()
+                .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+                .ok_or_else(|| format!("{} is not an Ed25519 public key", public_key))?;
+            Signature::from_slice(&signature)
+                .is_ok_and(|signature| key.verify_strict(&probe, &signature).is_ok())
+        }
+        SignatureScheme::Secp256k1 => {
+            let key = EcdsaVerifyingKey::from_sec1_bytes(&key_bytes)
+                .map_err(|_| format!("{} is not a secp256k1 public key", public_key))?;
+            signature.len() == 65
+                && EcdsaSignature::from_slice(&signature[..64])
+                    .ok()
+                    .zip(RecoveryId::from_byte(signature[64]))
+                    .and_then(|(signature, recovery_id)| {
+                        EcdsaVerifyingKey::recover_from_prehash(&probe, &signature, recovery_id)
+                            .ok()
+                    })
+                    .is_some_and(|recovered| recovered == key)
+        }
+    };
+    if verified {
+        Ok(public_key)
+    } else {
+        Err(format!(
+            "signature does not verify against public key {}",
+            public_key
+        ))
+    }
+}
+
+/// Status of a fee payer `balance` against the configured minimum
+#[must_use]
+pub fn balance_status(balance: u64, minimum: Option<u64>) -> (CheckStatus, String) {
+    match minimum {
+        Some(minimum) if balance < minimum => (
+            CheckStatus::Fail,
+            format!(
+                "{} is below MIN_WALLET_BALANCE {}; submissions would be deferred",
+                balance, minimum
+            ),
+        ),
+        Some(minimum) => (
+            CheckStatus::Pass,
+            format!("{} (minimum {})", balance, minimum),
+        ),
+        None if balance == 0 => (
+            CheckStatus::Warn,
+            "0; submissions will fail until the fee payer is funded".to_string(),
+        ),
+        None => (CheckStatus::Pass, balance.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::{LocalSecp256k1Signer, LocalSigner};
+    use async_trait::async_trait;
+    use secrecy::SecretString;
+
+    /// Signs with one key but reports another
+    struct MismatchedSigner {
+        signer: LocalSigner,
+        public_key: String,
+    }
+
+    #[async_trait]
+    impl TransactionSigner for MismatchedSigner {
+        async fn sign_message(
+            &self,
+            message: &[u8],
+        ) -> Result<String, crate::domain::BlockchainError> {
+            self.signer.sign_message(message).await
+        }
+
+        fn public_key(&self) -> String {
+            self.public_key.clone()
+        }
+    }
+
+    fn local_signer(byte: u8) -> LocalSigner {
+        LocalSigner::new(SecretString::from(bs58::encode([byte; 32]).into_string())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_verify_signer_checks_the_probe_signature() {
+        let signer = local_signer(7);
+        assert_eq!(verify_signer(&signer).await.unwrap(), signer.public_key());
+
+        let evm = LocalSecp256k1Signer::new(SecretString::from("46".repeat(32))).unwrap();
+        assert_eq!(verify_signer(&evm).await.unwrap(), evm.public_key());
+
+        let mismatched = MismatchedSigner {
+            signer: local_signer(7),
+            public_key: local_signer(8).public_key(),
+        };
+        assert!(
+            verify_signer(&mismatched)
+                .await
+                .unwrap_err()
+                .contains("does not verify")
+        );
+    }
+
+    #[test]
+    fn test_balance_status_against_minimum() {
+        assert_eq!(balance_status(10, Some(5)).0, CheckStatus::Pass);
+        assert_eq!(balance_status(4, Some(5)).0, CheckStatus::Fail);
+        assert_eq!(balance_status(0, None).0, CheckStatus::Warn);
+        assert_eq!(balance_status(1, None).0, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn test_report_counts_failures_and_renders_every_check() {
+        let mut report = DoctorReport::default();
+        report.pass("database", "connected (Postgres)");
+        report.warn("migrations", "2 pending");
+        report.check::<String>("rpc", Err("connection refused".to_string()));
+        report.skip("wallet balance", "blockchain disabled");
+        assert_eq!(report.failures(), 1);
+        assert!(!report.is_ok());
+
+        let rendered = report.to_string();
+        assert!(rendered.contains("✓ database        connected (Postgres)"));
+        assert!(rendered.contains("✗ rpc             connection refused"));
+        assert!(rendered.contains("1 checks failed, 1 warnings"));
+    }
+}
@@ -0,0 +1,250 @@
+//! Issuer key registry for verifying signed receipts.
+//!
+//! Receipts are compact JWS tokens (`base64url(header).base64url(payload).base64url(sig)`)
+//! signed with Ed25519 (`"alg": "EdDSA"`). The registry holds the issuer's current public
+//! keys and the ones retired by key rotation, so a receipt signed before a rotation still
+//! verifies and callers never need to know the key history. A header `kid` (the base58
+//! public key) selects the key; without one every known key is tried.
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::Deserialize;
+
+use crate::domain::{IssuerKeyStatus, ReceiptVerification, ValidationError};
+
+/// Protected header of a receipt
+#[derive(Debug, Deserialize)]
+struct ReceiptHeader {
+    alg: String,
+    #[serde(default)]
+    kid: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct IssuerKey {
+    id: String,
+    key: VerifyingKey,
+    status: IssuerKeyStatus,
+}
+
+/// Current and retired issuer public keys
+#[derive(Debug, Clone, Default)]
+pub struct IssuerKeyRegistry {
+    keys: Vec<IssuerKey>,
+}
+
+impl IssuerKeyRegistry {
+    /// Registry with no keys (every receipt fails to verify)
+    #[must_use]
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Keys from `ISSUER_PUBLIC_KEYS` (current) and `ISSUER_RETIRED_PUBLIC_KEYS` (retired),
+    /// comma-separated base58 Ed25519 public keys
+    pub fn from_env() -> Result<Self, ValidationError> {
+        let mut registry = Self::empty();
+        for (var, status) in [
+            ("ISSUER_PUBLIC_KEYS", IssuerKeyStatus::Current),
+            ("ISSUER_RETIRED_PUBLIC_KEYS", IssuerKeyStatus::Retired),
+        ] {
+            let raw = std::env::var(var).unwrap_or_default();
+            for key in raw.split(',').map(str::trim).filter(|k| !k.is_empty()) {
+                registry = registry.with_key(key, status)?;
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Add a base58 public key; a key added twice keeps its first status
+    pub fn with_key(
+        mut self,
+        public_key: &str,
+        status: IssuerKeyStatus,
+    ) -> Result<Self, ValidationError> {
+        let invalid = || ValidationError::InvalidField {
+            field: "issuer_key".to_string(),
+            message: format!("'{}' is not a base58 Ed25519 public key", public_key),
+        };
+        let bytes: [u8; 32] = bs58::decode(public_key)
+            .into_vec()
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(invalid)?;
+        let key = VerifyingKey::from_bytes(&bytes).map_err(|_| invalid())?;
+        if !self.keys.iter().any(|k| k.id == public_key) {
+            self.keys.push(IssuerKey {
+                id: public_key.to_string(),
+                key,
+                status,
+            });
+        }
+        Ok(self)
+    }
+
+    /// Number of known keys
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Check `receipt` against the known keys. Tokens that are not an EdDSA compact JWS
+    /// are rejected; a well-formed token whose signature matches no key is reported as
+    /// not valid.
+    pub fn verify(&self, receipt: &str) -> Result<ReceiptVerification, ValidationError> {
+        let malformed = |message: &str| ValidationError::InvalidField {
+            field: "receipt".to_string(),
+            message: message.to_string(),
+        };
+        let mut parts = receipt.trim().split('.');
+        let (Some(header_b64), Some(payload_b64), Some(signature_b64), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(malformed("Receipt must be a compact JWS with three parts"));
+        };
+        let decode = |part: &str| URL_SAFE_NO_PAD.decode(part).ok();
+        let header: ReceiptHeader = decode(header_b64)
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .ok_or_else(|| malformed("Receipt header is not base64url JSON"))?;
+        if header.alg != "EdDSA" {
+            return Err(malformed("Receipt must be signed with EdDSA"));
+        }
+        let payload: serde_json::Value = decode(payload_b64)
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .ok_or_else(|| malformed("Receipt payload is not base64url JSON"))?;
+        let signature = decode(signature_b64)
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or_else(|| malformed("Receipt signature is not a base64url Ed25519 signature"))?;
+
+        let signing_input = format!("{}.{}", header_b64, payload_b64);
+        let signer = self
+            .keys
+            .iter()
+            .filter(|key| header.kid.as_ref().is_none_or(|kid| *kid == key.id))
+            .find(|key| {
+                key.key
+                    .verify_strict(signing_input.as_bytes(), &signature)
+                    .is_ok()
+            });
+        Ok(match signer {
+            Some(key) => ReceiptVerification {
+                valid: true,
+                key_id: Some(key.id.clone()),
+                key_status: Some(key.status),
+                payload: Some(payload),
+            },
+            None => ReceiptVerification {
+                valid: false,
+                key_id: None,
+                key_status: None,
+                payload: None,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn public_key(key: &SigningKey) -> String {
+        bs58::encode(key.verifying_key().to_bytes()).into_string()
+    }
+
+    fn receipt(key: &SigningKey, header: &str, payload: &str) -> String {
+        let input = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header),
+            URL_SAFE_NO_PAD.encode(payload)
+        );
+        let signature = key.sign(input.as_bytes());
+        format!("{}.{}", input, URL_SAFE_NO_PAD.encode(signature.to_bytes()))
+    }
+
+    fn registry(current: &SigningKey, retired: &SigningKey) -> IssuerKeyRegistry {
+        IssuerKeyRegistry::empty()
+            .with_key(&public_key(current), IssuerKeyStatus::Current)
+            .unwrap()
+            .with_key(&public_key(retired), IssuerKeyStatus::Retired)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_current_and_retired_keys_verify() {
+        let (current, retired) = (key(1), key(2));
+        let registry = registry(&current, &retired);
+        let payload = r#"{"item_id":"item_1"}"#;
+
+        let result = registry
+            .verify(&receipt(&current, r#"{"alg":"EdDSA"}"#, payload))
+            .unwrap();
+        assert!(result.valid);
+        assert_eq!(result.key_id, Some(public_key(&current)));
+        assert_eq!(result.key_status, Some(IssuerKeyStatus::Current));
+        assert_eq!(result.payload.unwrap()["item_id"], "item_1");
+
+        let header = format!(r#"{{"alg":"EdDSA","kid":"{}"}}"#, public_key(&retired));
+        let result = registry
+            .verify(&receipt(&retired, &header, payload))
+            .unwrap();
+        assert!(result.valid);
+        assert_eq!(result.key_status, Some(IssuerKeyStatus::Retired));
+    }
+
+    #[test]
+    fn test_unknown_key_tampered_payload_and_wrong_kid_are_not_valid() {
+        let (current, retired) = (key(1), key(2));
+        let registry = registry(&current, &retired);
+
+        let foreign = receipt(&key(3), r#"{"alg":"EdDSA"}"#, "{}");
+        assert!(!registry.verify(&foreign).unwrap().valid);
+
+        let signed = receipt(&current, r#"{"alg":"EdDSA"}"#, r#"{"n":1}"#);
+        let parts: Vec<&str> = signed.split('.').collect();
+        let tampered = format!(
+            "{}.{}.{}",
+            parts[0],
+            URL_SAFE_NO_PAD.encode(r#"{"n":2}"#),
+            parts[2]
+        );
+        assert!(!registry.verify(&tampered).unwrap().valid);
+
+        // `kid` names the retired key but the current one signed
+        let header = format!(r#"{{"alg":"EdDSA","kid":"{}"}}"#, public_key(&retired));
+        assert!(
+            !registry
+                .verify(&receipt(&current, &header, "{}"))
+                .unwrap()
+                .valid
+        );
+    }
+
+    #[test]
+    fn test_malformed_receipts_and_keys_are_rejected() {
+        let registry = registry(&key(1), &key(2));
+        for bad in [
+            "not-a-jws".to_string(),
+            "a.b.c.d".to_string(),
+            receipt(&key(1), r#"{"alg":"HS256"}"#, "{}"),
+            receipt(&key(1), r#"{"alg":"EdDSA"}"#, "not json"),
+        ] {
+            assert!(registry.verify(&bad).is_err(), "{bad}");
+        }
+        assert!(
+            IssuerKeyRegistry::empty()
+                .with_key("not-a-key", IssuerKeyStatus::Current)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Background jobs for long-running operations.
+//!
+//! An endpoint whose work does not fit in one request records a job, starts the work
+//! with [`spawn_job`] and answers `202 Accepted` with the job; clients poll
+//! `GET /jobs/{id}` for progress and the outcome. The work runs as a task on the instance
+//! that accepted the request, so a restart leaves its job `running` for good.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use tracing::{Instrument, error, info, info_span, warn};
+
+use crate::domain::{AuditActor, ItemError, Job, JobError, JobStatus, JobStore, TenantScope};
+
+/// Error starting a job: the job could not be recorded, or the operation cannot run
+#[derive(Debug)]
+pub enum StartJobError {
+    Job(JobError),
+    Item(ItemError),
+}
+
+impl From<JobError> for StartJobError {
+    fn from(e: JobError) -> Self {
+        StartJobError::Job(e)
+    }
+}
+
+impl From<ItemError> for StartJobError {
+    fn from(e: ItemError) -> Self {
+        StartJobError::Item(e)
+    }
+}
+
+/// Progress reporting for one running job
+#[derive(Clone)]
+pub struct JobHandle {
+    id: String,
+    store: Arc<dyn JobStore>,
+}
+
+impl JobHandle {
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Record progress; a failed write is logged and does not stop the job
+    pub async fn progress(&self, processed: i64, failed: i64) {
+        if let Err(e) = self
+            .store
+            .update_job_progress(&self.id, processed, failed)
+            .await
+        {
+            warn!(job_id = %self.id, error = %e, "Failed to record job progress");
+        }
+    }
+}
+
+/// Record a `queued` job of `kind` and run `work` for it in the background.
+///
+/// `work` returns the outcome stored in `result`, or the message stored in `error`.
+pub async fn spawn_job<F, Fut>(
+    store: Arc<dyn JobStore>,
+    kind: &str,
+    work: F,
+) -> Result<Job, JobError>
+where
+    F: FnOnce(JobHandle) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<serde_json::Value, String>> + Send + 'static,
+{
+    let job = store.create_job(kind).await?;
+    let handle = JobHandle {
+        id: job.id.clone(),
+        store: Arc::clone(&store),
+    };
+    let kind = job.kind.clone();
+    let span = info_span!("job", job_id = %job.id, kind = %kind);
+    // The job works on the starting request's tenant and is audited as its caller, like
+    // the request itself
+    let tenant = TenantScope::current();
+    let actor = AuditActor::current();
+    tokio::spawn(TenantScope::scope(
+        tenant,
+        AuditActor::scope(actor, async move {
+            info!("Job started");
+            handle.progress(0, 0).await;
+            let (status, result, message) = match work(handle.clone()).await {
+                Ok(result) => (JobStatus::Succeeded, Some(result), None),
+                Err(message) => (JobStatus::Failed, None, Some(message)),
+            };
+            metrics::counter!(
+                "jobs_finished_total",
+                "kind" => kind,
+                "status" => status.as_str(),
+            )
+            .increment(1);
+            match store
+                .finish_job(handle.id(), status, result.as_ref(), message.as_deref())
+                .await
+            {
+                Ok(job) => info!(status = job.status.as_str(), "Job finished"),
+                Err(e) => error!(error = %e, "Failed to record job outcome"),
+            }
+        })
+        .instrument(span),
+    ));
+    Ok(job)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockProvider;
+
+    async fn wait_until_finished(store: &MockProvider, id: &str) -> Job {
+        for _ in 0..100 {
+            let job = store.get_job(id).await.unwrap().unwrap();
+            if job.status.is_finished() {
+                return job;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        panic!("job {id} did not finish");
+    }
+
+    #[tokio::test]
+    async fn test_spawn_job_records_progress_and_result() {
+        let store = Arc::new(MockProvider::new());
+        let job = spawn_job(Arc::clone(&store) as _, "count", |handle| async move {
+            handle.progress(3, 1).await;
+            Ok(serde_json::json!({ "counted": 3 }))
+        })
+        .await
+        .unwrap();
+        assert_eq!(job.status, JobStatus::Queued);
+
+        let finished = wait_until_finished(&store, &job.id).await;
+        assert_eq!(finished.status, JobStatus::Succeeded);
+        assert_eq!((finished.processed, finished.failed), (3, 1));
+        assert_eq!(finished.result.unwrap()["counted"], 3);
+        assert!(finished.finished_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_spawn_job_records_failure() {
+        let store = Arc::new(MockProvider::new());
+        let job = spawn_job(Arc::clone(&store) as _, "broken", |_| async {
+            Err("source unavailable".to_string())
+        })
+        .await
+        .unwrap();
+
+        let finished = wait_until_finished(&store, &job.id).await;
+        assert_eq!(finished.status, JobStatus::Failed);
+        assert_eq!(finished.error.as_deref(), Some("source unavailable"));
+        assert!(finished.result.is_none());
+    }
+}
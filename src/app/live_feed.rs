@@ -0,0 +1,180 @@
+//! In-process broadcast of item events to live subscribers (`GET /ws`).
+//!
+//! [`AppService`](super::AppService) sends every [`DomainEvent`] it publishes to its
+//! [`ItemFeed`] as well, with the item's tenant and tags so subscribers can filter on
+//! them. The channel is bounded: a subscriber that falls more than its capacity behind
+//! loses the oldest events and learns how many it missed on its next receive.
+
+use std::future::Future;
+
+use tokio::sync::{broadcast, watch};
+
+use crate::domain::{DomainEvent, Item};
+
+/// Events buffered for each subscriber when `with_capacity` is not called
+pub const DEFAULT_FEED_CAPACITY: usize = 1024;
+
+/// An event with the item attributes subscribers filter on
+#[derive(Debug, Clone)]
+pub struct FeedEvent {
+    pub event: DomainEvent,
+    pub tenant_id: String,
+    pub tags: Vec<String>,
+}
+
+impl FeedEvent {
+    #[must_use]
+    pub fn new(event: DomainEvent, item: &Item) -> Self {
+        Self {
+            event,
+            tenant_id: item.tenant_id.clone(),
+            tags: item
+                .metadata
+                .as_ref()
+                .map(|m| m.tags.clone())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Which events a subscriber receives; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeedFilter {
+    pub tenant: Option<String>,
+    pub tag: Option<String>,
+}
+
+impl FeedFilter {
+    #[must_use]
+    pub fn matches(&self, event: &FeedEvent) -> bool {
+        self.tenant.as_ref().is_none_or(|t| *t == event.tenant_id)
+            && self.tag.as_ref().is_none_or(|t| event.tags.contains(t))
+    }
+}
+
+/// Broadcast channel of item events, cheap to clone
+#[derive(Clone)]
+pub struct ItemFeed {
+    events: broadcast::Sender<FeedEvent>,
+    closed: watch::Sender<bool>,
+}
+
+impl Default for ItemFeed {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_FEED_CAPACITY)
+    }
+}
+
+impl ItemFeed {
+    /// Feed buffering up to `capacity` events per subscriber (at least 1)
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            events: broadcast::channel(capacity.max(1)).0,
+            closed: watch::channel(false).0,
+        }
+    }
+
+    /// Events sent from now on
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<FeedEvent> {
+        self.events.subscribe()
+    }
+
+    /// Whether anyone is subscribed, so callers can skip building events nobody reads
+    #[must_use]
+    pub fn has_subscribers(&self) -> bool {
+        self.events.receiver_count() > 0
+    }
+
+    pub fn send(&self, event: FeedEvent) {
+        // Fails only when nobody is subscribed
+        let _ = self.events.send(event);
+    }
+
+    /// Tell subscribers the server is shutting down
+    pub fn close(&self) {
+        self.closed.send_replace(true);
+    }
+
+    /// Resolves once [`Self::close`] has been called (at once if it already has)
+    pub fn closed(&self) -> impl Future<Output = ()> + Send + 'static {
+        let mut closed = self.closed.subscribe();
+        async move {
+            let _ = closed.wait_for(|closed| *closed).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{DomainEventKind, ItemMetadata};
+
+    fn event(tenant: &str, tags: &[&str]) -> FeedEvent {
+        FeedEvent {
+            event: DomainEvent::new(
+                "item_1",
+                DomainEventKind::ItemSubmitted {
+                    signature: "sig".to_string(),
+                },
+            ),
+            tenant_id: tenant.to_string(),
+            tags: tags.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn test_filter_matches_tenant_and_tag() {
+        let rust = event("acme", &["rust", "test"]);
+        let other = event("globex", &["go"]);
+        let filter = FeedFilter {
+            tenant: Some("acme".to_string()),
+            tag: Some("rust".to_string()),
+        };
+
+        assert!(FeedFilter::default().matches(&other));
+        assert!(filter.matches(&rust));
+        assert!(!filter.matches(&other));
+        assert!(
+            !FeedFilter {
+                tag: Some("go".to_string()),
+                ..filter
+            }
+            .matches(&rust)
+        );
+    }
+
+    #[test]
+    fn test_feed_event_takes_tags_from_metadata() {
+        let item = Item {
+            tenant_id: "acme".to_string(),
+            metadata: Some(ItemMetadata {
+                tags: vec!["rust".to_string()],
+                ..ItemMetadata::default()
+            }),
+            ..Item::default()
+        };
+        let event = FeedEvent::new(DomainEvent::item_created(&item), &item);
+        assert_eq!(event.tags, vec!["rust"]);
+        assert_eq!(event.tenant_id, "acme");
+    }
+
+    #[tokio::test]
+    async fn test_slow_subscriber_is_told_how_many_events_it_missed() {
+        let feed = ItemFeed::with_capacity(2);
+        let mut receiver = feed.subscribe();
+        assert!(feed.has_subscribers());
+        for _ in 0..5 {
+            feed.send(event("acme", &[]));
+        }
+        assert!(matches!(
+            receiver.recv().await,
+            Err(broadcast::error::RecvError::Lagged(3))
+        ));
+        assert!(receiver.recv().await.is_ok());
+
+        feed.close();
+        feed.closed().await;
+    }
+}
@@ -1,9 +1,65 @@
 //! Application layer containing business logic and shared state.
 
+pub mod abuse;
+pub mod api_keys;
+pub mod auth_policy;
+pub mod blocklist;
+pub mod body_limits;
+pub mod clock;
+pub mod consumer;
+pub mod cors;
+pub mod cursor;
+pub mod dispatcher;
+pub mod doctor;
+pub mod issuer_keys;
+pub mod jobs;
+pub mod live_feed;
+pub mod retry;
+pub mod scheduler;
 pub mod service;
+pub mod shutdown;
 pub mod state;
 pub mod worker;
 
-pub use service::{AppService, CreateItemError};
-pub use state::AppState;
-pub use worker::{BlockchainRetryWorker, WorkerConfig, spawn_worker};
+pub use abuse::{
+    AbuseConfig, AbuseGuard, AbuseSubject, DEFAULT_ABUSE_WINDOW, DEFAULT_BAN_DURATION, Violation,
+};
+pub use auth_policy::{Access, AuthPolicy, DEFAULT_AUTH_POLICY};
+pub use blocklist::IpBlocklist;
+pub use body_limits::{
+    BodyLimits, DEFAULT_ADMIN_BODY_LIMIT, DEFAULT_BODY_LIMIT, DEFAULT_IMPORT_BODY_LIMIT,
+    DEFAULT_ITEMS_BODY_LIMIT,
+};
+pub use clock::SystemClock;
+pub use consumer::{
+    ConsumerConfig, DEFAULT_CONSUMER_SUBJECT, Delivery, HandlerError, LogEventHandler,
+    MessageConsumer, MessageHandler, spawn_message_consumer,
+};
+pub use cors::{CorsConfig, CorsOrigins, CorsPolicy, DEFAULT_CORS_MAX_AGE};
+pub use cursor::CursorCodec;
+pub use dispatcher::{DispatcherConfig, EventDispatcher, Subscription, spawn_event_dispatcher};
+pub use doctor::{CheckStatus, DoctorCheck, DoctorReport, balance_status, verify_signer};
+pub use issuer_keys::IssuerKeyRegistry;
+pub use jobs::{JobHandle, StartJobError, spawn_job};
+pub use live_feed::{DEFAULT_FEED_CAPACITY, FeedEvent, FeedFilter, ItemFeed};
+pub use retry::{BackoffStrategy, RetryPolicy};
+pub use scheduler::{
+    DEFAULT_JOB_JITTER, JobRunError, JobRunStats, JobScheduler, JobStats, LEADER_LEASE_INTERVALS,
+    PeriodicJob,
+};
+pub use service::{
+    AppService, BatchOutcome, BulkRequeueSummary, CONTENT_REENCRYPT_JOB, ContentBody,
+    CreateItemError, DEFAULT_CLAIM_TTL, DEFAULT_CONTENT_OFFLOAD_THRESHOLD,
+    DEFAULT_COUNT_ESTIMATE_ABOVE, DEFAULT_HEALTH_CACHE_TTL, DEFAULT_MAX_METADATA_BYTES,
+    DEFAULT_SUBMISSION_COST, DLQ_REQUEUE_JOB, ItemContent, ReencryptionSummary, SubmissionBudget,
+    VerifyItemError,
+};
+pub use shutdown::{
+    DEFAULT_SHUTDOWN_TIMEOUT, Shutdown, ShutdownConfig, ShutdownPhase, ShutdownReport,
+};
+pub use state::{AppState, DEFAULT_MAINTENANCE_RETRY_AFTER};
+pub use worker::{
+    BlockchainRetryWorker, ConfirmationConfig, ConfirmationPoller, HealthRefreshWorker,
+    ItemPurgeWorker, PurgeConfig, WorkerConfig, WorkerMonitor, spawn_health_refresh_worker,
+    spawn_purge_worker, spawn_worker,
+};
@@ -0,0 +1,193 @@
+//! Retry policies: how long to wait before the next attempt, and when to give up.
+//!
+//! The service uses one for failed blockchain submissions (dead-lettered after
+//! `max_attempts`), the retry worker another for the extra delay after failed batches.
+//! Both can be configured from the environment under their own prefix.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use rand::Rng;
+
+/// How the delay grows with each attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackoffStrategy {
+    /// `base_delay * 2^attempt`
+    #[default]
+    Exponential,
+    /// Exponential, randomized to between half and all of it so retries spread out
+    ExponentialJitter,
+    /// `base_delay` every time
+    Fixed,
+}
+
+impl BackoffStrategy {
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Exponential => "exponential",
+            Self::ExponentialJitter => "exponential_jitter",
+            Self::Fixed => "fixed",
+        }
+    }
+}
+
+impl fmt::Display for BackoffStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BackoffStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "exponential" => Ok(Self::Exponential),
+            "exponential_jitter" | "jitter" => Ok(Self::ExponentialJitter),
+            "fixed" => Ok(Self::Fixed),
+            other => Err(format!("Unknown backoff strategy: {}", other)),
+        }
+    }
+}
+
+/// Delay schedule and attempt limit for retried work
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub strategy: BackoffStrategy,
+    /// Attempts after which the work is given up
+    pub max_attempts: i32,
+    /// Delay of the first retry (and of every retry with [`BackoffStrategy::Fixed`])
+    pub base_delay: Duration,
+    /// Longest delay between attempts
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// Blockchain submissions: 10 attempts, 2s after the first failure, doubling up to 5
+    /// minutes
+    fn default() -> Self {
+        Self {
+            strategy: BackoffStrategy::Exponential,
+            max_attempts: 10,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(300),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// `defaults` overridden by `{prefix}_STRATEGY`, `{prefix}_MAX_ATTEMPTS`,
+    /// `{prefix}_BASE_DELAY_SECS` and `{prefix}_MAX_DELAY_SECS`; malformed values are
+    /// ignored
+    #[must_use]
+    pub fn from_env(prefix: &str, defaults: Self) -> Self {
+        let var = |name: &str| std::env::var(format!("{}_{}", prefix, name)).ok();
+        let secs = |name: &str| {
+            var(name)
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_secs)
+        };
+        Self {
+            strategy: var("STRATEGY")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.strategy),
+            max_attempts: var("MAX_ATTEMPTS")
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(defaults.max_attempts),
+            base_delay: secs("BASE_DELAY_SECS").unwrap_or(defaults.base_delay),
+            max_delay: secs("MAX_DELAY_SECS").unwrap_or(defaults.max_delay),
+        }
+    }
+
+    /// Delay before retrying after `attempt` failures
+    #[must_use]
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let exponential = || {
+            self.base_delay
+                .saturating_mul(2u32.saturating_pow(attempt))
+                .min(self.max_delay)
+        };
+        match self.strategy {
+            BackoffStrategy::Fixed => self.base_delay.min(self.max_delay),
+            BackoffStrategy::Exponential => exponential(),
+            BackoffStrategy::ExponentialJitter => {
+                let full = exponential();
+                rand::thread_rng().gen_range(full / 2..=full)
+            }
+        }
+    }
+
+    /// Whether `attempts` failed attempts use up the policy
+    #[must_use]
+    pub fn is_exhausted(&self, attempts: i32) -> bool {
+        attempts >= self.max_attempts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(strategy: BackoffStrategy) -> RetryPolicy {
+        RetryPolicy {
+            strategy,
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_exponential_doubles_up_to_the_cap() {
+        let policy = policy(BackoffStrategy::Exponential);
+        let delays: Vec<u64> = (0..11).map(|n| policy.delay(n).as_secs()).collect();
+        assert_eq!(delays, [1, 2, 4, 8, 16, 32, 64, 128, 256, 300, 300]);
+        assert_eq!(policy.delay(u32::MAX), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn test_exponential_jitter_stays_within_half_and_full_delay() {
+        let jittered = policy(BackoffStrategy::ExponentialJitter);
+        let exponential = policy(BackoffStrategy::Exponential);
+        for attempt in 0..12 {
+            let full = exponential.delay(attempt);
+            for _ in 0..20 {
+                let delay = jittered.delay(attempt);
+                assert!(delay >= full / 2 && delay <= full, "{attempt}: {delay:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fixed_waits_the_base_delay() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_secs(7),
+            ..policy(BackoffStrategy::Fixed)
+        };
+        assert!(
+            (0..10).all(|n| policy.delay(n) == Duration::from_secs(7)),
+            "fixed delay"
+        );
+    }
+
+    #[test]
+    fn test_exhaustion_and_strategy_names() {
+        let policy = RetryPolicy::default();
+        assert!(!policy.is_exhausted(9));
+        assert!(policy.is_exhausted(10));
+
+        for strategy in [
+            BackoffStrategy::Exponential,
+            BackoffStrategy::ExponentialJitter,
+            BackoffStrategy::Fixed,
+        ] {
+            assert_eq!(strategy.as_str().parse::<BackoffStrategy>(), Ok(strategy));
+        }
+        assert_eq!(
+            "Exponential-Jitter".parse(),
+            Ok(BackoffStrategy::ExponentialJitter)
+        );
+        assert!("linear".parse::<BackoffStrategy>().is_err());
+    }
+}
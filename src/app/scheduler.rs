@@ -0,0 +1,547 @@
+//! Periodic background jobs.
+//!
+//! A [`PeriodicJob`] is one piece of recurring work: the blockchain retry worker, the
+//! confirmation poller. The [`JobScheduler`] runs every registered job on its own task:
+//! it waits the job's interval plus a random jitter, so instances started together do
+//! not poll the database in lockstep, then runs it and records the outcome. A run that
+//! fails or panics is logged and counted; the job keeps its schedule either way. On
+//! shutdown each job finishes the run in progress and stops.
+//!
+//! With [`JobScheduler::with_leader_election`], a job that declares itself a
+//! [`PeriodicJob::singleton`] only runs on the instance holding its lease. The lease is
+//! renewed before every run and lasts [`LEADER_LEASE_INTERVALS`] intervals, so when the
+//! leader dies another instance takes the job over within that time.
+
+use std::collections::HashMap;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::FutureExt;
+use rand::Rng;
+use tokio::sync::{Notify, watch};
+use tokio::task::{JoinHandle, JoinSet};
+use tracing::{error, info, warn};
+
+use super::clock::SystemClock;
+use crate::domain::{Clock, LeaderElection};
+
+/// Default share of a job's interval added as random delay before each run
+pub const DEFAULT_JOB_JITTER: f64 = 0.1;
+
+/// Length of a singleton job's leader lease, in intervals of the job
+pub const LEADER_LEASE_INTERVALS: u32 = 3;
+
+/// Why a run of a job failed
+pub type JobRunError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Recurring work run by the [`JobScheduler`]
+#[async_trait]
+pub trait PeriodicJob: Send + Sync {
+    /// Stable name used in logs and as the `job` metric label
+    fn name(&self) -> &'static str;
+
+    /// Delay before the next run, read again after every run (so a job can back off)
+    fn interval(&self) -> Duration;
+
+    /// Run once
+    async fn run(&self) -> Result<(), JobRunError>;
+
+    /// Notified to run right away instead of waiting for the interval
+    fn wake_signal(&self) -> Option<&Notify> {
+        None
+    }
+
+    /// Must run on one instance at a time (honoured with leader election configured)
+    fn singleton(&self) -> bool {
+        false
+    }
+
+    /// Called when the scheduler starts running the job
+    fn started(&self) {}
+
+    /// Called when the job stopped after shutdown
+    fn stopped(&self) {}
+}
+
+/// Run counters of one job since the scheduler started
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JobRunStats {
+    pub runs: u64,
+    pub failures: u64,
+    pub panics: u64,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_duration_ms: Option<u64>,
+    /// Error or panic message of the last run (None when it succeeded)
+    pub last_error: Option<String>,
+}
+
+/// Shared run counters of every scheduled job, by job name
+#[derive(Default)]
+pub struct JobStats {
+    jobs: Mutex<HashMap<&'static str, JobRunStats>>,
+}
+
+impl JobStats {
+    /// Counters of job `name` (None before its first run)
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<JobRunStats> {
+        self.jobs.lock().unwrap().get(name).cloned()
+    }
+
+    fn record(
+        &self,
+        name: &'static str,
+        finished_at: DateTime<Utc>,
+        elapsed: Duration,
+        outcome: &RunOutcome,
+    ) {
+        let mut jobs = self.jobs.lock().unwrap();
+        let stats = jobs.entry(name).or_default();
+        stats.runs += 1;
+        stats.last_run_at = Some(finished_at);
+        stats.last_duration_ms = Some(elapsed.as_millis() as u64);
+        stats.last_error = match outcome {
+            RunOutcome::Succeeded => None,
+            RunOutcome::Failed(message) => {
+                stats.failures += 1;
+                Some(message.clone())
+            }
+            RunOutcome::Panicked(message) => {
+                stats.panics += 1;
+                Some(message.clone())
+            }
+        };
+    }
+}
+
+enum RunOutcome {
+    Succeeded,
+    Failed(String),
+    Panicked(String),
+}
+
+impl RunOutcome {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Succeeded => "succeeded",
+            Self::Failed(_) => "failed",
+            Self::Panicked(_) => "panicked",
+        }
+    }
+}
+
+/// Lease store and the name this instance holds leases under
+struct Leadership {
+    election: Arc<dyn LeaderElection>,
+    holder: String,
+}
+
+impl Leadership {
+    /// Take or renew the lease of `job`; an unreachable store counts as not leading, so
+    /// two instances never both run the job
+    async fn lead(&self, job: &dyn PeriodicJob) -> bool {
+        let ttl = job.interval() * LEADER_LEASE_INTERVALS;
+        match self
+            .election
+            .try_acquire_lease(job.name(), &self.holder, ttl)
+            .await
+        {
+            Ok(leading) => leading,
+            Err(e) => {
+                warn!(job = job.name(), error = %e, "Leader lease check failed");
+                false
+            }
+        }
+    }
+
+    async fn resign(&self, job: &dyn PeriodicJob) {
+        if let Err(e) = self.election.release_lease(job.name(), &self.holder).await {
+            warn!(job = job.name(), error = %e, "Failed to release leader lease");
+        }
+    }
+}
+
+/// Runs registered [`PeriodicJob`]s until shut down
+pub struct JobScheduler {
+    jobs: Vec<Arc<dyn PeriodicJob>>,
+    jitter: f64,
+    stats: Arc<JobStats>,
+    leadership: Option<Arc<Leadership>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for JobScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JobScheduler {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            jobs: Vec::new(),
+            jitter: DEFAULT_JOB_JITTER,
+            stats: Arc::new(JobStats::default()),
+            leadership: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Wait out intervals and timestamp runs on `clock` instead of the system clock
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Add up to `ratio` of the interval (0 to 1) as random delay before each run
+    #[must_use]
+    pub fn with_jitter(mut self, ratio: f64) -> Self {
+        self.jitter = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Run singleton jobs only while this instance, named `holder`, holds their lease in
+    /// `election`. `holder` must be unique per instance.
+    #[must_use]
+    pub fn with_leader_election(
+        mut self,
+        election: Arc<dyn LeaderElection>,
+        holder: impl Into<String>,
+    ) -> Self {
+        self.leadership = Some(Arc::new(Leadership {
+            election,
+            holder: holder.into(),
+        }));
+        self
+    }
+
+    #[must_use]
+    pub fn register(mut self, job: Arc<dyn PeriodicJob>) -> Self {
+        self.jobs.push(job);
+        self
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Names of the registered jobs, in registration order
+    #[must_use]
+    pub fn job_names(&self) -> Vec<&'static str> {
+        self.jobs.iter().map(|job| job.name()).collect()
+    }
+
+    /// Run counters of the registered jobs
+    #[must_use]
+    pub fn stats(&self) -> Arc<JobStats> {
+        Arc::clone(&self.stats)
+    }
+
+    /// Spawn every job; the task ends once all of them stopped after `true` is sent
+    pub fn spawn(self) -> (JoinHandle<()>, watch::Sender<bool>) {
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let mut tasks = JoinSet::new();
+        for job in self.jobs {
+            info!(job = job.name(), interval = ?job.interval(), "Starting scheduled job");
+            let leadership = self.leadership.clone().filter(|_| job.singleton());
+            tasks.spawn(run_job(
+                job,
+                self.jitter,
+                Arc::clone(&self.stats),
+                leadership,
+                Arc::clone(&self.clock),
+                shutdown_rx.clone(),
+            ));
+        }
+        let handle = tokio::spawn(async move { while tasks.join_next().await.is_some() {} });
+        (handle, shutdown_tx)
+    }
+}
+
+async fn run_job(
+    job: Arc<dyn PeriodicJob>,
+    jitter: f64,
+    stats: Arc<JobStats>,
+    leadership: Option<Arc<Leadership>>,
+    clock: Arc<dyn Clock>,
+    mut shutdown_rx: watch::Receiver<bool>,
+) {
+    job.started();
+    let mut leading = false;
+    loop {
+        let delay = with_jitter(job.interval(), jitter);
+        tokio::select! {
+            _ = clock.sleep(delay) => {}
+            _ = woken(job.as_ref()) => {
+                info!(job = job.name(), "Scheduled job woken up early");
+            }
+            result = shutdown_rx.changed() => {
+                if result.is_err() || *shutdown_rx.borrow() {
+                    info!(job = job.name(), "Scheduled job shutting down");
+                    break;
+                }
+                continue;
+            }
+        }
+        if let Some(leadership) = &leadership {
+            let now_leading = leadership.lead(job.as_ref()).await;
+            if now_leading != leading {
+                info!(
+                    job = job.name(),
+                    leader = now_leading,
+                    "Job leadership changed"
+                );
+                metrics::gauge!("scheduled_job_leader", "job" => job.name())
+                    .set(f64::from(u8::from(now_leading)));
+                leading = now_leading;
+            }
+            if !leading {
+                continue;
+            }
+        }
+        execute(job.as_ref(), &stats, clock.as_ref()).await;
+    }
+    if let Some(leadership) = leadership.filter(|_| leading) {
+        leadership.resign(job.as_ref()).await;
+    }
+    job.stopped();
+}
+
+async fn woken(job: &dyn PeriodicJob) {
+    match job.wake_signal() {
+        Some(signal) => signal.notified().await,
+        None => std::future::pending().await,
+    }
+}
+
+fn with_jitter(interval: Duration, ratio: f64) -> Duration {
+    if ratio <= 0.0 {
+        return interval;
+    }
+    interval + interval.mul_f64(rand::thread_rng().gen_range(0.0..=ratio))
+}
+
+/// Run `job` once, isolating a panic to this run, and record the outcome
+async fn execute(job: &dyn PeriodicJob, stats: &JobStats, clock: &dyn Clock) {
+    let name = job.name();
+    let started = Instant::now();
+    let outcome = match AssertUnwindSafe(job.run()).catch_unwind().await {
+        Ok(Ok(())) => RunOutcome::Succeeded,
+        Ok(Err(e)) => {
+            warn!(job = name, error = %e, "Scheduled job failed");
+            RunOutcome::Failed(e.to_string())
+        }
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| (*s).to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "panic".to_string());
+            error!(job = name, panic = %message, "Scheduled job panicked");
+            RunOutcome::Panicked(message)
+        }
+    };
+    let elapsed = started.elapsed();
+    metrics::counter!(
+        "scheduled_job_runs_total",
+        "job" => name,
+        "outcome" => outcome.as_str(),
+    )
+    .increment(1);
+    metrics::histogram!("scheduled_job_duration_seconds", "job" => name)
+        .record(elapsed.as_secs_f64());
+    stats.record(name, clock.now(), elapsed, &outcome);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockProvider;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails every second run and panics every third
+    struct Flaky {
+        runs: AtomicU32,
+        wake: Notify,
+    }
+
+    #[async_trait]
+    impl PeriodicJob for Flaky {
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        fn interval(&self) -> Duration {
+            Duration::from_secs(60)
+        }
+
+        async fn run(&self) -> Result<(), JobRunError> {
+            let run = self.runs.fetch_add(1, Ordering::SeqCst) + 1;
+            if run.is_multiple_of(3) {
+                panic!("run {run} exploded");
+            }
+            if run.is_multiple_of(2) {
+                return Err("database unavailable".into());
+            }
+            Ok(())
+        }
+
+        fn wake_signal(&self) -> Option<&Notify> {
+            Some(&self.wake)
+        }
+    }
+
+    fn flaky() -> Arc<Flaky> {
+        Arc::new(Flaky {
+            runs: AtomicU32::new(0),
+            wake: Notify::new(),
+        })
+    }
+
+    #[test]
+    fn test_jitter_stays_within_ratio() {
+        let interval = Duration::from_secs(100);
+        assert_eq!(with_jitter(interval, 0.0), interval);
+        for _ in 0..100 {
+            let delay = with_jitter(interval, 0.2);
+            assert!((interval..=Duration::from_secs(120)).contains(&delay));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_records_failures_and_isolates_panics() {
+        let job = flaky();
+        let stats = JobStats::default();
+
+        execute(job.as_ref(), &stats, &SystemClock).await;
+        assert_eq!(stats.get("flaky").unwrap().last_error, None);
+        execute(job.as_ref(), &stats, &SystemClock).await;
+        assert_eq!(
+            stats.get("flaky").unwrap().last_error.as_deref(),
+            Some("database unavailable")
+        );
+        execute(job.as_ref(), &stats, &SystemClock).await;
+        execute(job.as_ref(), &stats, &SystemClock).await;
+
+        let recorded = stats.get("flaky").unwrap();
+        assert_eq!(
+            (recorded.runs, recorded.failures, recorded.panics),
+            (4, 2, 1)
+        );
+        assert!(recorded.last_run_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_runs_on_interval_and_wake_then_shuts_down() {
+        tokio::time::pause();
+        let job = flaky();
+        let scheduler = JobScheduler::new()
+            .with_jitter(0.0)
+            .register(Arc::clone(&job) as Arc<dyn PeriodicJob>);
+        let stats = scheduler.stats();
+        let (handle, shutdown_tx) = scheduler.spawn();
+
+        tokio::time::sleep(Duration::from_secs(61)).await;
+        assert_eq!(stats.get("flaky").unwrap().runs, 1);
+
+        job.wake.notify_one();
+        while stats.get("flaky").unwrap().runs < 2 {
+            tokio::task::yield_now().await;
+        }
+
+        // The panicking third run does not stop the job
+        tokio::time::sleep(Duration::from_secs(121)).await;
+        assert_eq!(stats.get("flaky").unwrap().runs, 4);
+
+        shutdown_tx.send(true).unwrap();
+        handle.await.unwrap();
+    }
+
+    /// Counts its runs; only one instance may run it at a time
+    #[derive(Default)]
+    struct Singleton {
+        runs: AtomicU32,
+    }
+
+    #[async_trait]
+    impl PeriodicJob for Singleton {
+        fn name(&self) -> &'static str {
+            "singleton"
+        }
+
+        fn interval(&self) -> Duration {
+            Duration::from_secs(10)
+        }
+
+        async fn run(&self) -> Result<(), JobRunError> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn singleton(&self) -> bool {
+            true
+        }
+    }
+
+    async fn total_runs_reach(jobs: &[&Singleton], runs: u32) {
+        while jobs
+            .iter()
+            .map(|job| job.runs.load(Ordering::SeqCst))
+            .sum::<u32>()
+            < runs
+        {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_singleton_job_runs_on_the_leader_only() {
+        tokio::time::pause();
+        let election = Arc::new(MockProvider::new());
+        let instance = |holder: &str, job: &Arc<Singleton>| {
+            JobScheduler::new()
+                .with_jitter(0.0)
+                .with_leader_election(Arc::clone(&election) as Arc<dyn LeaderElection>, holder)
+                .register(Arc::clone(job) as Arc<dyn PeriodicJob>)
+                .spawn()
+        };
+        let (job_a, job_b) = (
+            Arc::new(Singleton::default()),
+            Arc::new(Singleton::default()),
+        );
+        let (handle_a, shutdown_a) = instance("a", &job_a);
+        let (handle_b, shutdown_b) = instance("b", &job_b);
+
+        tokio::time::sleep(Duration::from_secs(21)).await;
+        total_runs_reach(&[&job_a, &job_b], 2).await;
+        let (leader, follower, leader_shutdown, leader_handle) =
+            if job_a.runs.load(Ordering::SeqCst) > 0 {
+                (&job_a, &job_b, shutdown_a, handle_a)
+            } else {
+                (&job_b, &job_a, shutdown_b, handle_b)
+            };
+        assert_eq!(leader.runs.load(Ordering::SeqCst), 2);
+        assert_eq!(follower.runs.load(Ordering::SeqCst), 0);
+
+        // The leader releases its lease on shutdown, so the follower takes over at once
+        leader_shutdown.send(true).unwrap();
+        leader_handle.await.unwrap();
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        total_runs_reach(&[follower], 1).await;
+        assert_eq!(leader.runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_empty_scheduler_finishes_immediately() {
+        let scheduler = JobScheduler::new();
+        assert!(scheduler.is_empty());
+        let (handle, _shutdown_tx) = scheduler.spawn();
+        handle.await.unwrap();
+    }
+}
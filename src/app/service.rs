@@ -1,17 +1,38 @@
 //! Application service layer with graceful degradation.
 
-use chrono::{Duration, Utc};
-use std::sync::Arc;
+use bytes::Bytes;
+use chrono::{DateTime, Duration, Utc};
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use tracing::{error, info, instrument, warn};
 use validator::Validate;
 
+use super::clock::SystemClock;
+use super::cursor::CursorCodec;
+use super::jobs::{JobHandle, StartJobError, spawn_job};
+use super::live_feed::{FeedEvent, ItemFeed};
+use super::retry::RetryPolicy;
 use crate::domain::{
-    BlockchainClient, BlockchainError, BlockchainStatus, CreateItemRequest, HealthResponse,
-    HealthStatus, Item, ItemError, ItemRepository, OutboxRepository, OutboxStatus,
-    PaginatedResponse, SolanaOutboxEntry, ValidationError, build_solana_outbox_payload_from_item,
+    AuditEntry, AuditFilter, AuditLogger, BlockchainClient, BlockchainError, BlockchainStatus,
+    Clock, ContentHasher, CreateItemRequest, DedupeMode, DependencyHealth, DomainEvent,
+    DomainEventKind, ErrorDetail, EventLog, ExportBookmark, FailedSubmission, HealthResponse,
+    HealthStatus, ImportLineResult, ImportReport, ImportRow, Item, ItemError, ItemListFilter,
+    ItemPosition, ItemRepository, ItemSortField, ItemStatusEvent, ItemTimeline, ItemVerification,
+    Job, JobStore, MessagePublisher, NotificationError, ObjectStore, ObjectStoreError,
+    OutboxRepository, OutboxStatus, PaginatedResponse, QueueDepth, RangeSpec, SearchResponse,
+    SigningContext, SolanaOutboxEntry, SortOrder, SpendLedger, SubmissionAttempt, SubmissionTrace,
+    TagCount, TelemetrySink, TenantScope, TimeRange, TimelineEntry, UnitOfWork, ValidationError,
+    WebhookDelivery, WebhookDeliveryLog, build_solana_outbox_payload_from_item,
+    build_solana_outbox_payload_from_request,
 };
 
-/// Error type for create-item flow (validation or repository).
+/// Error type for the create- and update-item flows (validation or repository).
 #[derive(Debug)]
 pub enum CreateItemError {
     Validation(ValidationError),
@@ -30,6 +51,45 @@ impl From<ItemError> for CreateItemError {
     }
 }
 
+/// Content of an item as `GET /items/{id}/content` serves it
+pub struct ItemContent {
+    /// Media type the item was created with
+    pub content_type: String,
+    /// Size of the whole content in bytes; None for content offloaded before sizes were
+    /// recorded, which is always served whole
+    pub length: Option<u64>,
+    /// Bytes of the content `body` holds (end exclusive); None when it is all of it
+    pub range: Option<Range<u64>>,
+    pub body: ContentBody,
+}
+
+/// Bytes of an [`ItemContent`]
+pub enum ContentBody {
+    /// Kept in the item row
+    Inline(Bytes),
+    /// Read from the object store as the stream is polled
+    Stream(BoxStream<'static, Result<Bytes, ObjectStoreError>>),
+}
+
+/// Error type for on-chain verification (item lookup or reading the chain).
+#[derive(Debug)]
+pub enum VerifyItemError {
+    Item(ItemError),
+    Blockchain(BlockchainError),
+}
+
+impl From<ItemError> for VerifyItemError {
+    fn from(e: ItemError) -> Self {
+        VerifyItemError::Item(e)
+    }
+}
+
+impl From<BlockchainError> for VerifyItemError {
+    fn from(e: BlockchainError) -> Self {
+        VerifyItemError::Blockchain(e)
+    }
+}
+
 /// Error type for outbox processing (repository or blockchain).
 #[derive(Debug)]
 pub enum ProcessError {
@@ -49,17 +109,190 @@ impl From<crate::domain::BlockchainError> for ProcessError {
     }
 }
 
-/// Maximum number of retry attempts for blockchain submission
-const MAX_RETRY_ATTEMPTS: i32 = 10;
+/// Result of one outbox batch run by the background worker
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchOutcome {
+    /// Outbox entries claimed for this batch
+    pub claimed: usize,
+    /// Entries whose transaction was submitted
+    pub submitted: usize,
+    /// Entries that failed and were rescheduled (or marked failed)
+    pub failed: usize,
+    /// Entries put back without an attempt (wallet balance too low or daily budget spent)
+    pub deferred: usize,
+}
+
+/// `kind` of the job started by `POST /admin/dlq/requeue`
+pub const DLQ_REQUEUE_JOB: &str = "dlq_requeue";
+
+/// Result of a bulk dead-letter requeue job
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct BulkRequeueSummary {
+    /// Entries moved back into the outbox
+    pub requeued: i64,
+    /// Entries that could not be requeued and were left parked
+    pub failed: i64,
+}
+
+/// `kind` of the job started by `POST /admin/encryption/reencrypt`
+pub const CONTENT_REENCRYPT_JOB: &str = "content_reencrypt";
+
+/// Items read per batch by the content re-encryption job
+const REENCRYPT_BATCH_SIZE: i64 = 500;
+
+/// Result of a content re-encryption job
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ReencryptionSummary {
+    /// Items read
+    pub scanned: u64,
+    /// Items whose content was plaintext or under a retired master key and was rewritten
+    pub reencrypted: u64,
+}
+
+/// Log names signed into the cursors of the admin log listings
+const ITEM_EVENTS_LOG: &str = "item_events";
+const WEBHOOK_DELIVERIES_LOG: &str = "webhook_deliveries";
+const AUDIT_LOG: &str = "audit_log";
+
+/// Fee charged per submission when none is configured (one Solana signature, in lamports)
+pub const DEFAULT_SUBMISSION_COST: u64 = 5_000;
+
+/// Daily fee budget of one signer. Spend is recorded in a [`SpendLedger`] shared by every
+/// instance; submissions beyond the budget wait for the next UTC day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmissionBudget {
+    /// Signer the spend is tracked for (fee payer public key or address)
+    pub signer: String,
+    /// Most that may be spent per UTC day, in the chain's smallest unit
+    pub daily_limit: u64,
+    /// Fee charged for one submitted transaction, in the same unit
+    pub cost_per_submission: u64,
+}
+
+impl SubmissionBudget {
+    /// Submissions still affordable after `spent`
+    #[must_use]
+    pub fn remaining_submissions(&self, spent: u64) -> u64 {
+        self.daily_limit.saturating_sub(spent) / self.cost_per_submission.max(1)
+    }
+}
+
+/// Delay before entries deferred for a low wallet balance are claimed again
+const LOW_BALANCE_RECHECK_SECS: i64 = 60;
+
+/// Default for how long a dependency health snapshot is reused by `/health` and
+/// `/health/ready`
+pub const DEFAULT_HEALTH_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Default for how long claimed outbox entries and items stay reserved for the worker that
+/// claimed them; an entry still `processing` after this is claimed again
+pub const DEFAULT_CLAIM_TTL: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Maximum length of a full-text search query in characters
+const MAX_SEARCH_QUERY_LEN: usize = 200;
+
+/// Longest accepted export bookmark name
+const MAX_BOOKMARK_NAME_LEN: usize = 64;
+
+/// Bookmark names are 1-64 ASCII letters, digits, `-`, `_` or `.`
+fn validate_bookmark_name(name: &str) -> Result<(), ItemError> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_BOOKMARK_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(ItemError::InvalidState(format!(
+            "Bookmark name must be 1-{} letters, digits, '-', '_' or '.'",
+            MAX_BOOKMARK_NAME_LEN
+        )))
+    }
+}
+
+/// Default for how many matching rows a page-number listing counts exactly; above it
+/// `total` is the database's estimate
+pub const DEFAULT_COUNT_ESTIMATE_ABOVE: u64 = 100_000;
+
+/// Default limit for an item's serialized metadata (16 KiB)
+pub const DEFAULT_MAX_METADATA_BYTES: usize = 16 * 1024;
+
+/// `item` with the object at its `content_key` read back into `content`
+async fn read_offloaded_content(
+    store: &Arc<dyn ObjectStore>,
+    mut item: Item,
+) -> Result<Item, ItemError> {
+    let Some(key) = &item.content_key else {
+        return Ok(item);
+    };
+    let failed = |e: ObjectStoreError| {
+        error!(item_id = %item.id, key = %key, error = %e, "Failed to read item content");
+        metrics::counter!("object_store_failures_total", "operation" => "get").increment(1);
+        ItemError::RepositoryFailure
+    };
+    let chunks: Vec<Bytes> = match store.get(key, None).await {
+        Ok(stream) => stream.try_collect().await.map_err(failed)?,
+        Err(e) => return Err(failed(e)),
+    };
+    item.content = String::from_utf8(chunks.concat()).map_err(|_| {
+        error!(item_id = %item.id, key = %key, "Item content object is not UTF-8");
+        ItemError::RepositoryFailure
+    })?;
+    Ok(item)
+}
+
+/// Default size above which content goes to the object store, when one is configured
+/// (64 KiB)
+pub const DEFAULT_CONTENT_OFFLOAD_THRESHOLD: usize = 64 * 1024;
+
+/// Most rows accepted by one `POST /items/import`
+pub const MAX_IMPORT_ROWS: usize = 10_000;
 
-/// Maximum backoff duration in seconds (5 minutes)
-const MAX_BACKOFF_SECS: i64 = 300;
+/// Imported rows stored per transaction
+const IMPORT_BATCH_SIZE: usize = 100;
 
 /// Application service containing business logic
 pub struct AppService {
     item_repo: Arc<dyn ItemRepository>,
     outbox_repo: Arc<dyn OutboxRepository>,
-    blockchain_client: Arc<dyn BlockchainClient>,
+    /// None when blockchain submission is disabled (`CHAIN_DISABLED=true`)
+    blockchain_client: Option<Arc<dyn BlockchainClient>>,
+    /// Last dependency check result, so frequent probes don't hammer Postgres/RPC
+    health_cache: Mutex<Option<(Instant, HealthResponse)>>,
+    /// How long the cached health snapshot is served
+    health_cache_ttl: std::time::Duration,
+    /// Largest accepted metadata, measured as serialized JSON
+    max_metadata_bytes: usize,
+    /// Backoff between failed submissions and when to dead-letter them
+    retry_policy: RetryPolicy,
+    /// Below this fee payer balance submissions are deferred instead of attempted
+    min_wallet_balance: Option<u64>,
+    /// Daily fee budget and the ledger its spend is recorded in
+    budget: Option<(SubmissionBudget, Arc<dyn SpendLedger>)>,
+    /// Signs the pagination cursors handed to clients
+    cursors: CursorCodec,
+    /// Page-number listings matching more rows report an estimated `total` (None: always exact)
+    count_estimate_above: Option<u64>,
+    /// Item status event log listed by `GET /admin/events`
+    event_log: Option<Arc<dyn EventLog>>,
+    /// Webhook delivery attempts listed by `GET /admin/webhook-deliveries`
+    delivery_log: Option<Arc<dyn WebhookDeliveryLog>>,
+    /// Who created, changed or deleted what, listed by `GET /admin/audit`
+    audit_log: Option<Arc<dyn AuditLogger>>,
+    /// Business KPIs (items created, confirmation latency, failure reasons)
+    telemetry: Option<Arc<dyn TelemetrySink>>,
+    /// Message bus receiving item lifecycle events
+    publisher: Option<Arc<dyn MessagePublisher>>,
+    /// Store for content larger than `offload_threshold` bytes (None: kept in the row)
+    object_store: Option<Arc<dyn ObjectStore>>,
+    offload_threshold: usize,
+    /// How long a claimed outbox entry is reserved for this instance
+    claim_ttl: std::time::Duration,
+    /// Time source of backoff schedules, budgets and the workers' waits
+    clock: Arc<dyn Clock>,
+    /// Live subscribers of item events (`GET /ws`)
+    feed: ItemFeed,
 }
 
 impl AppService {
@@ -72,518 +305,2983 @@ impl AppService {
         Self {
             item_repo,
             outbox_repo,
-            blockchain_client,
+            blockchain_client: Some(blockchain_client),
+            health_cache: Mutex::new(None),
+            health_cache_ttl: DEFAULT_HEALTH_CACHE_TTL,
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            retry_policy: RetryPolicy::default(),
+            min_wallet_balance: None,
+            budget: None,
+            cursors: CursorCodec::ephemeral(),
+            count_estimate_above: Some(DEFAULT_COUNT_ESTIMATE_ABOVE),
+            event_log: None,
+            delivery_log: None,
+            audit_log: None,
+            telemetry: None,
+            publisher: None,
+            object_store: None,
+            offload_threshold: DEFAULT_CONTENT_OFFLOAD_THRESHOLD,
+            claim_ttl: DEFAULT_CLAIM_TTL,
+            clock: Arc::new(SystemClock),
+            feed: ItemFeed::default(),
+        }
+    }
+
+    /// Service without a blockchain client: items are stored in `pending` and
+    /// never enqueued for submission (read-only / preview deployments).
+    #[must_use]
+    pub fn without_blockchain(
+        item_repo: Arc<dyn ItemRepository>,
+        outbox_repo: Arc<dyn OutboxRepository>,
+    ) -> Self {
+        Self {
+            item_repo,
+            outbox_repo,
+            blockchain_client: None,
+            health_cache: Mutex::new(None),
+            health_cache_ttl: DEFAULT_HEALTH_CACHE_TTL,
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            retry_policy: RetryPolicy::default(),
+            min_wallet_balance: None,
+            budget: None,
+            cursors: CursorCodec::ephemeral(),
+            count_estimate_above: Some(DEFAULT_COUNT_ESTIMATE_ABOVE),
+            event_log: None,
+            delivery_log: None,
+            audit_log: None,
+            telemetry: None,
+            publisher: None,
+            object_store: None,
+            offload_threshold: DEFAULT_CONTENT_OFFLOAD_THRESHOLD,
+            claim_ttl: DEFAULT_CLAIM_TTL,
+            clock: Arc::new(SystemClock),
+            feed: ItemFeed::default(),
         }
     }
 
+    /// Reject items whose metadata serializes to more than `limit` bytes
+    #[must_use]
+    pub fn with_max_metadata_bytes(mut self, limit: usize) -> Self {
+        self.max_metadata_bytes = limit;
+        self
+    }
+
+    /// Serve the cached dependency health for `ttl` before checking again
+    #[must_use]
+    pub fn with_health_cache_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.health_cache_ttl = ttl;
+        self
+    }
+
+    /// Retry failed submissions on `policy` instead of the default exponential backoff
+    #[must_use]
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Defer submissions while the fee payer holds less than `balance`, so a drained
+    /// wallet does not burn retry attempts on `InsufficientFunds`
+    #[must_use]
+    pub fn with_min_wallet_balance(mut self, balance: u64) -> Self {
+        self.min_wallet_balance = Some(balance);
+        self
+    }
+
+    /// Sign pagination cursors with `codec` (shared by every instance behind one endpoint)
+    #[must_use]
+    pub fn with_cursor_codec(mut self, codec: CursorCodec) -> Self {
+        self.cursors = codec;
+        self
+    }
+
+    /// Estimate the `total` of page-number listings matching more than `rows` items
+    /// instead of counting them (None: always count)
+    #[must_use]
+    pub fn with_count_estimate_above(mut self, rows: Option<u64>) -> Self {
+        self.count_estimate_above = rows;
+        self
+    }
+
+    /// List the item status event log and webhook delivery attempts from these stores
+    #[must_use]
+    pub fn with_operational_logs(
+        mut self,
+        event_log: Arc<dyn EventLog>,
+        delivery_log: Arc<dyn WebhookDeliveryLog>,
+    ) -> Self {
+        self.event_log = Some(event_log);
+        self.delivery_log = Some(delivery_log);
+        self
+    }
+
+    /// Record item writes in `log` and list it with [`Self::list_audit_entries`]
+    #[must_use]
+    pub fn with_audit_logger(mut self, log: Arc<dyn AuditLogger>) -> Self {
+        self.audit_log = Some(log);
+        self
+    }
+
+    /// Report business KPIs to `sink`
+    #[must_use]
+    pub fn with_telemetry(mut self, sink: Arc<dyn TelemetrySink>) -> Self {
+        self.telemetry = Some(sink);
+        self
+    }
+
+    /// Publish item lifecycle events (created, submitted, confirmed, failed) to `publisher`
+    #[must_use]
+    pub fn with_message_publisher(mut self, publisher: Arc<dyn MessagePublisher>) -> Self {
+        self.publisher = Some(publisher);
+        self
+    }
+
+    /// Take the current time and delays from `clock` instead of the system clock
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Time source shared with the background workers
+    #[must_use]
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Keep up to `capacity` undelivered events per live subscriber
+    #[must_use]
+    pub fn with_feed_capacity(mut self, capacity: usize) -> Self {
+        self.feed = ItemFeed::with_capacity(capacity);
+        self
+    }
+
+    /// Item events as they are published, for live subscribers
+    #[must_use]
+    pub fn feed(&self) -> &ItemFeed {
+        &self.feed
+    }
+
+    /// Keep content larger than `threshold` bytes in `store`, with only its key in the
+    /// item row
+    #[must_use]
+    pub fn with_object_store(mut self, store: Arc<dyn ObjectStore>, threshold: usize) -> Self {
+        self.object_store = Some(store);
+        self.offload_threshold = threshold;
+        self
+    }
+
+    /// Reserve claimed outbox entries for `ttl`; set it above the longest batch, or
+    /// another instance takes over entries still being submitted
+    #[must_use]
+    pub fn with_claim_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.claim_ttl = ttl;
+        self
+    }
+
+    /// Stop submitting once `budget.signer` has spent `budget.daily_limit` today; later
+    /// submissions stay pending with a `budget_exceeded` error until the next UTC day
+    #[must_use]
+    pub fn with_submission_budget(
+        mut self,
+        budget: SubmissionBudget,
+        ledger: Arc<dyn SpendLedger>,
+    ) -> Self {
+        self.budget = Some((budget, ledger));
+        self
+    }
+
+    /// Whether items are submitted to the blockchain
+    #[must_use]
+    pub fn blockchain_enabled(&self) -> bool {
+        self.blockchain_client.is_some()
+    }
+
     /// Create a new item and enqueue blockchain submission in the outbox.
-    #[instrument(skip(self, request), fields(item_name = %request.name))]
+    #[instrument(
+        skip(self, request),
+        fields(item_name = %request.name, item_id = tracing::field::Empty)
+    )]
     pub async fn create_and_submit_item(
         &self,
         request: &CreateItemRequest,
     ) -> Result<Item, CreateItemError> {
-        request.validate().map_err(|e| {
-            warn!(error = %e, "Validation failed");
-            CreateItemError::Validation(ValidationError::from(e))
-        })?;
+        self.validate_create_request(request)?;
+        let request = self.offload_content(request).await?;
 
         info!("Creating new item: {}", request.name);
-        let item = self.item_repo.create_item(request).await?;
-        info!(item_id = %item.id, "Item created and outbox queued");
+        // Item and outbox entry commit together; an early return rolls both back
+        let mut tx = self.item_repo.begin().await?;
+        let item = self.stage_item(tx.as_mut(), &request).await?;
+        tx.commit().await?;
+        tracing::Span::current().record("item_id", item.id.as_str());
+        if let Some(telemetry) = &self.telemetry {
+            telemetry.item_created(&item.tenant_id);
+        }
+        self.publish(DomainEvent::item_created(&item), Some(&item))
+            .await;
+        self.record_audit(
+            "item.create",
+            Some(&item.id),
+            AuditEntry::item_diff(None, &item),
+        )
+        .await;
 
+        if self.blockchain_enabled() {
+            info!(item_id = %item.id, "Item created and outbox queued");
+        } else {
+            info!(item_id = %item.id, "Item created (blockchain disabled)");
+        }
         Ok(item)
     }
 
-    /// Get an item by ID
-    #[instrument(skip(self))]
-    pub async fn get_item(&self, id: &str) -> Result<Option<Item>, ItemError> {
-        self.item_repo.get_item(id).await
+    /// Create an item like [`Self::create_and_submit_item`], handling content that
+    /// matches a live item of the tenant as `mode` asks. With
+    /// [`DedupeMode::ReturnExisting`] the oldest such item is returned instead, also when
+    /// a concurrent request stored the same content first.
+    pub async fn create_item_with_dedupe(
+        &self,
+        request: &CreateItemRequest,
+        mode: DedupeMode,
+    ) -> Result<Item, CreateItemError> {
+        if mode == DedupeMode::Reject {
+            return self.create_and_submit_item(request).await;
+        }
+
+        self.validate_create_request(request)?;
+        let hash = ContentHasher::hash_request(request);
+        if let Some(existing) = self.existing_item(&hash).await? {
+            return Ok(existing);
+        }
+        match self.create_and_submit_item(request).await {
+            Err(CreateItemError::Item(ItemError::DuplicateContent)) => self
+                .existing_item(&hash)
+                .await?
+                .ok_or(CreateItemError::Item(ItemError::DuplicateContent)),
+            result => result,
+        }
     }
 
-    /// List items with pagination
-    #[instrument(skip(self))]
-    pub async fn list_items(
+    /// Live item of the current tenant with content hash `hash`
+    async fn existing_item(&self, hash: &str) -> Result<Option<Item>, ItemError> {
+        let existing = self.item_repo.find_item_by_hash(hash).await?;
+        if let Some(item) = &existing {
+            info!(item_id = %item.id, "Returning existing item with the same content");
+            metrics::counter!("items_deduplicated_total").increment(1);
+        }
+        Ok(existing)
+    }
+
+    fn validate_create_request(&self, request: &CreateItemRequest) -> Result<(), CreateItemError> {
+        request.validate().map_err(|e| {
+            warn!(error = %e, "Validation failed");
+            CreateItemError::Validation(ValidationError::from(e))
+        })?;
+        Ok(self.check_metadata_size(request)?)
+    }
+
+    /// Insert `request` and, with a blockchain client, its outbox entry into `tx`
+    async fn stage_item(
         &self,
-        limit: i64,
-        cursor: Option<&str>,
-    ) -> Result<PaginatedResponse<Item>, ItemError> {
-        self.item_repo.list_items(limit, cursor).await
+        tx: &mut dyn UnitOfWork,
+        request: &CreateItemRequest,
+    ) -> Result<Item, ItemError> {
+        let item = tx.insert_item(request).await?;
+        if !self.blockchain_enabled() {
+            return Ok(item);
+        }
+        // From the request: the row may hold only the key of offloaded content
+        let payload = build_solana_outbox_payload_from_request(&item.id, request);
+        tx.enqueue_solana_outbox(&item.id, &payload).await
     }
 
-    /// Retry blockchain submission for a specific item
+    /// Upload content above the offload threshold to the object store and return the
+    /// request with its key set; smaller content (or no store) is left in the request
+    async fn offload_content<'a>(
+        &self,
+        request: &'a CreateItemRequest,
+    ) -> Result<Cow<'a, CreateItemRequest>, ItemError> {
+        let Some(store) = &self.object_store else {
+            return Ok(Cow::Borrowed(request));
+        };
+        if request.content.len() <= self.offload_threshold {
+            return Ok(Cow::Borrowed(request));
+        }
+        let key = ContentHasher::object_key(&request.content);
+        store
+            .put(&key, Bytes::from(request.content.clone()))
+            .await
+            .map_err(|e| {
+                error!(key = %key, error = %e, "Failed to upload item content");
+                metrics::counter!("object_store_failures_total", "operation" => "put").increment(1);
+                ItemError::RepositoryFailure
+            })?;
+        metrics::counter!("items_content_offloaded_total").increment(1);
+        Ok(Cow::Owned(CreateItemRequest {
+            content_key: Some(key),
+            ..request.clone()
+        }))
+    }
+
+    /// Content of item `id`, or the part of it `range` asks for, streamed from the object
+    /// store when the row only holds its key
     #[instrument(skip(self))]
-    pub async fn retry_blockchain_submission(&self, id: &str) -> Result<Item, ItemError> {
+    pub async fn get_item_content(
+        &self,
+        id: &str,
+        range: Option<RangeSpec>,
+    ) -> Result<ItemContent, ItemError> {
         let item = self
-            .item_repo
             .get_item(id)
             .await?
             .ok_or_else(|| ItemError::NotFound(id.to_string()))?;
+        let length = match &item.content_key {
+            None => Some(item.content.len() as u64),
+            Some(_) => u64::try_from(item.content_length).ok().filter(|&n| n > 0),
+        };
+        let range = match (range, length) {
+            (Some(spec), Some(length)) => Some(
+                spec.resolve(length)
+                    .ok_or(ItemError::RangeNotSatisfiable { length })?,
+            ),
+            _ => None,
+        };
+        let body = match &item.content_key {
+            None => {
+                let content = Bytes::from(item.content);
+                ContentBody::Inline(match &range {
+                    Some(range) => content.slice(range.start as usize..range.end as usize),
+                    None => content,
+                })
+            }
+            Some(key) => {
+                let store = self.object_store()?;
+                let stream = store.get(key, range.clone()).await.map_err(|e| {
+                    error!(item_id = %item.id, key = %key, error = %e, "Failed to read item content");
+                    metrics::counter!("object_store_failures_total", "operation" => "get")
+                        .increment(1);
+                    ItemError::RepositoryFailure
+                })?;
+                ContentBody::Stream(stream)
+            }
+        };
+        Ok(ItemContent {
+            content_type: item.content_type,
+            length,
+            range,
+            body,
+        })
+    }
 
-        if item.blockchain_status != BlockchainStatus::PendingSubmission
-            && item.blockchain_status != BlockchainStatus::Failed
-        {
-            return Err(ItemError::InvalidState(
-                "Item is not pending submission or failed".to_string(),
-            ));
-        }
-
-        if item.blockchain_status == BlockchainStatus::PendingSubmission {
-            info!(item_id = %item.id, "Item already queued for submission");
+    /// `item` with offloaded content read back into `content`, for the code that hashes it
+    async fn load_content(&self, item: Item) -> Result<Item, ItemError> {
+        if item.content_key.is_none() {
             return Ok(item);
         }
+        read_offloaded_content(self.object_store()?, item).await
+    }
 
-        let payload = build_solana_outbox_payload_from_item(&item);
-        let updated = self
-            .item_repo
-            .enqueue_solana_outbox_for_item(&item.id, &payload)
-            .await?;
+    /// The object store, which rows with a `content_key` need
+    fn object_store(&self) -> Result<&Arc<dyn ObjectStore>, ItemError> {
+        self.object_store.as_ref().ok_or_else(|| {
+            error!("Item content is in the object store, but none is configured");
+            ItemError::RepositoryFailure
+        })
+    }
 
-        Ok(updated)
+    /// `items` with offloaded content read back, so exports carry the full content
+    fn with_loaded_content(
+        &self,
+        items: BoxStream<'static, Result<Item, ItemError>>,
+    ) -> BoxStream<'static, Result<Item, ItemError>> {
+        match &self.object_store {
+            Some(store) => {
+                let store = Arc::clone(store);
+                items
+                    .and_then(move |item| {
+                        let store = Arc::clone(&store);
+                        async move { read_offloaded_content(&store, item).await }
+                    })
+                    .boxed()
+            }
+            None => items,
+        }
     }
 
-    /// Process pending blockchain submissions (called by background worker)
-    #[instrument(skip(self))]
-    pub async fn process_pending_submissions(&self, batch_size: i64) -> Result<usize, ItemError> {
-        let pending_entries = self
-            .outbox_repo
-            .claim_pending_solana_outbox(batch_size)
-            .await?;
-        let count = pending_entries.len();
+    /// Create items from decoded import rows.
+    ///
+    /// Every row is validated like `POST /items`; valid rows are stored in transactions of
+    /// [`IMPORT_BATCH_SIZE`]. When a batch fails its rows are retried one at a time, so a
+    /// failure is reported on the row that caused it and the others are still imported.
+    #[instrument(skip(self, rows), fields(rows = rows.len()))]
+    pub async fn import_items(
+        &self,
+        rows: Vec<ImportRow>,
+    ) -> Result<ImportReport, ValidationError> {
+        if rows.is_empty() || rows.len() > MAX_IMPORT_ROWS {
+            return Err(ValidationError::InvalidField {
+                field: "file".to_string(),
+                message: format!("An import must contain 1-{} rows", MAX_IMPORT_ROWS),
+            });
+        }
 
-        metrics::gauge!("outbox_pending_items_count").set(count as f64);
+        let mut lines = Vec::with_capacity(rows.len());
+        let mut valid = Vec::new();
+        for row in rows {
+            let checked = row.request.and_then(|request| {
+                request.validate().map_err(ValidationError::from)?;
+                self.check_metadata_size(&request)?;
+                Ok(request)
+            });
+            let error = match checked {
+                Ok(request) => {
+                    valid.push((lines.len(), request));
+                    None
+                }
+                Err(e) => Some(ErrorDetail {
+                    r#type: "validation_error".to_string(),
+                    message: e.to_string(),
+                    fields: e.field_errors(),
+                    request_id: None,
+                }),
+            };
+            lines.push(ImportLineResult {
+                line: row.line,
+                item_id: None,
+                error,
+            });
+        }
 
-        if count == 0 {
-            return Ok(0);
+        for batch in valid.chunks(IMPORT_BATCH_SIZE) {
+            match self.insert_import_batch(batch).await {
+                Ok(items) => {
+                    for ((index, _), item) in batch.iter().zip(items) {
+                        lines[*index].item_id = Some(item.id);
+                    }
+                }
+                Err(e) => {
+                    warn!(error = %e, rows = batch.len(), "Import batch failed; retrying rows one at a time");
+                    for row in batch {
+                        match self.insert_import_batch(std::slice::from_ref(row)).await {
+                            Ok(items) => {
+                                lines[row.0].item_id = items.into_iter().next().map(|i| i.id)
+                            }
+                            Err(e) => {
+                                lines[row.0].error = Some(ErrorDetail {
+                                    r#type: "repository_error".to_string(),
+                                    message: e.to_string(),
+                                    fields: Vec::new(),
+                                    request_id: None,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
         }
 
-        info!(count = count, "Processing pending blockchain submissions");
+        let report = ImportReport::new(lines);
+        metrics::counter!("items_imported_total").increment(report.imported);
+        info!(
+            imported = report.imported,
+            failed = report.failed,
+            "Import finished"
+        );
+        Ok(report)
+    }
 
-        for entry in pending_entries {
-            if let Err(e) = self.process_outbox_entry(&entry).await {
-                error!(
-                    outbox_id = %entry.id,
-                    item_id = %entry.aggregate_id,
-                    error = ?e,
-                    "Failed to process pending submission"
-                );
+    /// Store one batch of import rows in a single transaction
+    async fn insert_import_batch(
+        &self,
+        batch: &[(usize, CreateItemRequest)],
+    ) -> Result<Vec<Item>, ItemError> {
+        // Uploads happen before the transaction opens, so it is not held across them
+        let mut requests = Vec::with_capacity(batch.len());
+        for (_, request) in batch {
+            requests.push(self.offload_content(request).await?);
+        }
+        let mut tx = self.item_repo.begin().await?;
+        let mut items = Vec::with_capacity(batch.len());
+        for request in &requests {
+            items.push(self.stage_item(tx.as_mut(), request).await?);
+        }
+        tx.commit().await?;
+        for item in &items {
+            if let Some(telemetry) = &self.telemetry {
+                telemetry.item_created(&item.tenant_id);
             }
+            self.publish(DomainEvent::item_created(item), Some(item))
+                .await;
         }
+        Ok(items)
+    }
 
-        Ok(count)
+    /// Metadata is stored as JSONB and returned in every list page, so bound it before insert
+    fn check_metadata_size(&self, request: &CreateItemRequest) -> Result<(), ValidationError> {
+        let Some(metadata) = &request.metadata else {
+            return Ok(());
+        };
+        let size = serde_json::to_vec(metadata).map_or(usize::MAX, |json| json.len());
+        if size > self.max_metadata_bytes {
+            warn!(size, limit = self.max_metadata_bytes, "Metadata too large");
+            return Err(ValidationError::TooLarge {
+                field: "metadata".to_string(),
+                size,
+                limit: self.max_metadata_bytes,
+            });
+        }
+        Ok(())
     }
 
-    /// Process a single pending submission (sticky blockhash for idempotent retries).
-    async fn process_outbox_entry(&self, entry: &SolanaOutboxEntry) -> Result<(), ProcessError> {
-        let hash = &entry.payload.hash;
-        let existing_blockhash = entry.attempt_blockhash.as_deref();
+    /// Get an item by ID (soft-deleted items are treated as missing)
+    #[instrument(skip(self))]
+    pub async fn get_item(&self, id: &str) -> Result<Option<Item>, ItemError> {
+        let item = self.item_repo.get_item(id).await?;
+        // Repositories already filter by tenant; checked again so a foreign item can never
+        // reach a handler
+        Ok(item.filter(|item| !item.is_deleted() && TenantScope::permits(&item.tenant_id)))
+    }
 
-        match self
-            .blockchain_client
-            .submit_transaction(hash, existing_blockhash)
-            .await
-        {
-            Ok((signature, _blockhash_used)) => {
-                info!(
-                    outbox_id = %entry.id,
-                    item_id = %entry.aggregate_id,
-                    signature = %signature,
-                    "Background submission successful"
+    /// List items with pagination. `cursor` is a signed cursor from a previous page; the
+    /// repository only ever sees the item ID it verifies to. When that item has been
+    /// purged since, a listing in `created_at` order continues from the position the
+    /// cursor recorded and the page is marked `cursor_degraded`.
+    #[instrument(skip(self))]
+    pub async fn list_items(
+        &self,
+        limit: i64,
+        cursor: Option<&str>,
+        filter: &ItemListFilter,
+    ) -> Result<PaginatedResponse<Item>, ItemError> {
+        let after = cursor
+            .map(|cursor| self.cursors.decode_keyset(cursor))
+            .transpose()
+            .inspect_err(|_| warn!("Rejected tampered pagination cursor"))?;
+        let result = self
+            .item_repo
+            .list_items(limit, after.as_ref().map(|(_, id)| id.as_str()), filter)
+            .await;
+        let mut page = match (result, after) {
+            (Err(ItemError::InvalidCursor(_)), Some((created_at, id)))
+                if filter.sort == ItemSortField::CreatedAt =>
+            {
+                warn!(cursor_item = %id, "Cursor item no longer exists, paging from its position");
+                metrics::counter!("pagination_cursor_degraded_total").increment(1);
+                return self
+                    .list_items_after_position(limit, created_at, &id, filter)
+                    .await;
+            }
+            (result, _) => result?,
+        };
+        if page.next_cursor.is_some() {
+            page.next_cursor = page.items.last().map(|item| self.cursors.encode(item));
+        }
+        Ok(page)
+    }
+
+    /// Page `page` (from 1) of `per_page` items matching `filter`, with the number of items
+    /// they are taken from as `total` and no cursor
+    #[instrument(skip(self))]
+    pub async fn list_items_page(
+        &self,
+        page: i64,
+        per_page: i64,
+        filter: &ItemListFilter,
+    ) -> Result<PaginatedResponse<Item>, ItemError> {
+        let offset = (page - 1)
+            .checked_mul(per_page)
+            .ok_or_else(|| ItemError::InvalidState("Page number is too large".to_string()))?;
+        // One extra row shows whether another page follows, even when `total` is estimated
+        let (mut items, count) = futures::try_join!(
+            self.item_repo
+                .list_items_at_offset(offset, per_page + 1, filter),
+            self.item_repo
+                .count_items(filter, self.count_estimate_above),
+        )?;
+        let per_page = usize::try_from(per_page).unwrap_or(0);
+        let has_more = items.len() > per_page;
+        items.truncate(per_page);
+        Ok(PaginatedResponse::new(items, None, has_more).with_total(count))
+    }
+
+    /// Metadata tags of the current tenant's live items with their usage counts, most used first
+    #[instrument(skip(self))]
+    pub async fn list_tags(&self) -> Result<Vec<TagCount>, ItemError> {
+        self.item_repo.list_tags().await
+    }
+
+    /// Page of items after `(created_at, id)` without the item at that position: the
+    /// position becomes a `created_at` bound, and rows the bound lets through at or before
+    /// the position are dropped
+    async fn list_items_after_position(
+        &self,
+        limit: i64,
+        created_at: DateTime<Utc>,
+        id: &str,
+        filter: &ItemListFilter,
+    ) -> Result<PaginatedResponse<Item>, ItemError> {
+        let mut bounded = filter.clone();
+        match filter.order {
+            SortOrder::Asc => {
+                bounded.created_after = Some(
+                    filter
+                        .created_after
+                        .map_or(created_at, |after| after.max(created_at)),
                 );
-                self.outbox_repo
-                    .complete_solana_outbox(&entry.id, &entry.aggregate_id, &signature)
-                    .await?;
             }
-            Err(e) => {
-                metrics::counter!("blockchain_submission_retry_total").increment(1);
-                warn!(
-                    outbox_id = %entry.id,
-                    item_id = %entry.aggregate_id,
-                    error = ?e,
-                    "Background submission failed"
+            SortOrder::Desc => {
+                // `created_before` is exclusive
+                let before = created_at + Duration::microseconds(1);
+                bounded.created_before = Some(
+                    filter
+                        .created_before
+                        .map_or(before, |current| current.min(before)),
                 );
-                let retry_count = entry.retry_count + 1;
-                let (outbox_status, item_status, next_retry) = if retry_count >= MAX_RETRY_ATTEMPTS
-                {
-                    (OutboxStatus::Failed, BlockchainStatus::Failed, None)
-                } else {
-                    let backoff = calculate_backoff(retry_count);
-                    (
-                        OutboxStatus::Pending,
-                        BlockchainStatus::PendingSubmission,
-                        Some(Utc::now() + Duration::seconds(backoff)),
+            }
+        }
+        let mut page = self.item_repo.list_items(limit, None, &bounded).await?;
+        // The repository's last row continues the listing even if it is dropped below
+        if page.next_cursor.is_some() {
+            page.next_cursor = page.items.last().map(|item| self.cursors.encode(item));
+        }
+        let position = (created_at, id);
+        page.items.retain(|item| {
+            let key = (item.created_at, item.id.as_str());
+            match filter.order {
+                SortOrder::Asc => key > position,
+                SortOrder::Desc => key < position,
+            }
+        });
+        page.cursor_degraded = true;
+        Ok(page)
+    }
+
+    /// Page of item status events, newest first, within `range`
+    #[instrument(skip(self))]
+    pub async fn list_item_events(
+        &self,
+        limit: i64,
+        cursor: Option<&str>,
+        range: &TimeRange,
+    ) -> Result<PaginatedResponse<ItemStatusEvent>, NotificationError> {
+        let log = self
+            .event_log
+            .as_ref()
+            .ok_or(NotificationError::LogUnavailable)?;
+        let before = self.decode_log_cursor(ITEM_EVENTS_LOG, cursor)?;
+        let mut page = log.list_events(limit, before, range).await?;
+        if page.next_cursor.is_some() {
+            page.next_cursor = page
+                .items
+                .last()
+                .map(|e| self.cursors.encode_position(ITEM_EVENTS_LOG, e.position));
+        }
+        Ok(page)
+    }
+
+    /// Page of webhook delivery attempts, newest first, within `range`
+    #[instrument(skip(self))]
+    pub async fn list_webhook_deliveries(
+        &self,
+        limit: i64,
+        cursor: Option<&str>,
+        range: &TimeRange,
+    ) -> Result<PaginatedResponse<WebhookDelivery>, NotificationError> {
+        let log = self
+            .delivery_log
+            .as_ref()
+            .ok_or(NotificationError::LogUnavailable)?;
+        let before = self.decode_log_cursor(WEBHOOK_DELIVERIES_LOG, cursor)?;
+        let mut page = log.list_webhook_deliveries(limit, before, range).await?;
+        if page.next_cursor.is_some() {
+            page.next_cursor = page
+                .items
+                .last()
+                .map(|d| self.cursors.encode_position(WEBHOOK_DELIVERIES_LOG, d.id));
+        }
+        Ok(page)
+    }
+
+    /// Page of audit log entries matching `filter`, newest first
+    #[instrument(skip(self))]
+    pub async fn list_audit_entries(
+        &self,
+        limit: i64,
+        cursor: Option<&str>,
+        filter: &AuditFilter,
+    ) -> Result<PaginatedResponse<AuditEntry>, NotificationError> {
+        let log = self
+            .audit_log
+            .as_ref()
+            .ok_or(NotificationError::LogUnavailable)?;
+        let before = self.decode_log_cursor(AUDIT_LOG, cursor)?;
+        let mut page = log.list_audit_entries(limit, before, filter).await?;
+        if page.next_cursor.is_some() {
+            page.next_cursor = page
+                .items
+                .last()
+                .map(|e| self.cursors.encode_position(AUDIT_LOG, e.id));
+        }
+        Ok(page)
+    }
+
+    /// Record `action` on `resource_id` in the audit log, attributed to the current
+    /// [`AuditActor`](crate::domain::AuditActor). Best effort: the change already
+    /// happened, so a failed write is logged and counted rather than returned.
+    pub async fn record_audit(
+        &self,
+        action: &str,
+        resource_id: Option<&str>,
+        diff: Option<serde_json::Value>,
+    ) {
+        let Some(log) = &self.audit_log else {
+            return;
+        };
+        let entry = AuditEntry::new(action, resource_id, diff);
+        if let Err(e) = log.record_audit_entry(&entry).await {
+            metrics::counter!("audit_log_failures_total", "action" => action.to_string())
+                .increment(1);
+            warn!(action, resource_id, error = %e, "Failed to record audit entry");
+        }
+    }
+
+    fn decode_log_cursor(
+        &self,
+        log: &str,
+        cursor: Option<&str>,
+    ) -> Result<Option<i64>, NotificationError> {
+        cursor
+            .map(|cursor| {
+                self.cursors.decode_position(log, cursor).ok_or_else(|| {
+                    warn!("Rejected tampered pagination cursor");
+                    NotificationError::InvalidCursor(
+                        "Cursor is malformed or was tampered with".into(),
                     )
-                };
+                })
+            })
+            .transpose()
+    }
 
-                // CV-01 remediation: Sticky blockhash to prevent double-spend.
-                // We MUST NOT clear attempt_blockhash on Timeout, NetworkError, or
-                // SubmissionFailed, because the transaction may have landed on-chain
-                // despite the error. Clearing would cause the next retry to use a new
-                // blockhash and produce a new signature, risking double-spend.
-                // Only clear when we know the blockhash is invalid (BlockhashExpired).
-                // For errors that don't carry blockhash_used, pass None so we do not
-                // update the column and thus keep the existing value (safe default).
-                let attempt_blockhash = match &e {
-                    BlockchainError::BlockhashExpired => Some(None),
-                    BlockchainError::SubmissionFailedWithBlockhash { blockhash_used, .. } => {
-                        Some(Some(blockhash_used.as_str()))
-                    }
-                    BlockchainError::Timeout { blockhash, .. }
-                    | BlockchainError::NetworkError { blockhash, .. } => {
-                        Some(Some(blockhash.as_str()))
+    /// Every live item, oldest first, streamed for `GET /items/export`
+    pub fn export_items(&self) -> BoxStream<'static, Result<Item, ItemError>> {
+        self.with_loaded_content(self.item_repo.stream_items())
+    }
+
+    /// Items changed since the acknowledged position of bookmark `name`, which is
+    /// registered on first use, streamed for `GET /items/export?bookmark=`. Bookmarks
+    /// are per tenant, so two tenants may use the same name.
+    #[instrument(skip(self))]
+    pub async fn export_item_changes(
+        &self,
+        name: &str,
+    ) -> Result<BoxStream<'static, Result<Item, ItemError>>, ItemError> {
+        validate_bookmark_name(name)?;
+        let bookmark = self
+            .item_repo
+            .export_bookmark(&TenantScope::qualify(name))
+            .await?;
+        Ok(self.with_loaded_content(self.item_repo.stream_item_changes(bookmark.position)))
+    }
+
+    /// Advance bookmark `name` to `position` once its consumer has processed the items
+    /// up to it (acknowledging an older position is a no-op)
+    #[instrument(skip(self))]
+    pub async fn acknowledge_export_bookmark(
+        &self,
+        name: &str,
+        position: &ItemPosition,
+    ) -> Result<ExportBookmark, ItemError> {
+        validate_bookmark_name(name)?;
+        let mut bookmark = self
+            .item_repo
+            .acknowledge_export_bookmark(&TenantScope::qualify(name), position)
+            .await?;
+        bookmark.name = name.to_string();
+        Ok(bookmark)
+    }
+
+    /// Full-text search over item name, description and content
+    #[instrument(skip(self))]
+    pub async fn search_items(&self, query: &str, limit: i64) -> Result<SearchResponse, ItemError> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(ItemError::InvalidState(
+                "Search query must not be empty".to_string(),
+            ));
+        }
+        if query.chars().count() > MAX_SEARCH_QUERY_LEN {
+            return Err(ItemError::InvalidState(format!(
+                "Search query must be at most {} characters",
+                MAX_SEARCH_QUERY_LEN
+            )));
+        }
+        let results = self
+            .item_repo
+            .search_items(query, limit.clamp(1, 100))
+            .await?;
+        Ok(SearchResponse {
+            query: query.to_string(),
+            results,
+        })
+    }
+
+    /// Replace an item's name, description, content and metadata, validated like
+    /// `POST /items`. `expected_version` is the version the caller based its change on;
+    /// if the item has moved on since, nothing is written and `Conflict` is returned.
+    /// The blockchain status is left alone, so an anchored item keeps the signature of
+    /// its earlier content (and fails `verify_item` until it is anchored again).
+    #[instrument(skip(self, request), fields(item_name = %request.name))]
+    pub async fn update_item(
+        &self,
+        id: &str,
+        request: &CreateItemRequest,
+        expected_version: i64,
+    ) -> Result<Item, CreateItemError> {
+        request.validate().map_err(|e| {
+            warn!(error = %e, "Validation failed");
+            CreateItemError::Validation(ValidationError::from(e))
+        })?;
+        self.check_metadata_size(request)?;
+        let request = self.offload_content(request).await?;
+
+        // Read only for the audit diff
+        let before = match &self.audit_log {
+            Some(_) => self.item_repo.get_item(id).await?,
+            None => None,
+        };
+        let item = self
+            .item_repo
+            .update_item(id, &request, expected_version)
+            .await
+            .inspect_err(|e| {
+                if matches!(e, ItemError::Conflict { .. }) {
+                    metrics::counter!("item_update_conflicts_total").increment(1);
+                }
+            })?;
+        info!(item_id = %item.id, version = item.version, "Item updated");
+        self.record_audit(
+            "item.update",
+            Some(&item.id),
+            AuditEntry::item_diff(before.as_ref(), &item),
+        )
+        .await;
+        Ok(item)
+    }
+
+    /// Soft-delete an item. The row is kept until the purge job removes it.
+    #[instrument(skip(self))]
+    pub async fn delete_item(&self, id: &str) -> Result<Item, ItemError> {
+        let item = self
+            .item_repo
+            .soft_delete_item(id)
+            .await?
+            .ok_or_else(|| ItemError::NotFound(id.to_string()))?;
+        info!(item_id = %item.id, "Item soft-deleted");
+        let diff = serde_json::json!({ "deleted_at": { "from": null, "to": item.deleted_at } });
+        self.record_audit("item.delete", Some(&item.id), Some(diff))
+            .await;
+        Ok(item)
+    }
+
+    /// Hard-delete items that were soft-deleted more than `retention` ago
+    /// (called by the purge worker).
+    #[instrument(skip(self))]
+    pub async fn purge_deleted_items(&self, retention: Duration) -> Result<u64, ItemError> {
+        let purged = self
+            .item_repo
+            .purge_deleted_items(self.clock.now() - retention)
+            .await?;
+        if purged > 0 {
+            info!(count = purged, "Purged soft-deleted items");
+            metrics::counter!("items_purged_total").increment(purged);
+        }
+        Ok(purged)
+    }
+
+    /// Move up to `limit` submitted items, oldest first, to `confirmed` once the chain
+    /// reports their transaction confirmed (called by the confirmation poller). Items the
+    /// chain cannot be asked about stay `submitted` for the next poll.
+    #[instrument(skip(self))]
+    pub async fn confirm_submitted_items(&self, limit: i64) -> Result<usize, ItemError> {
+        let Some(client) = &self.blockchain_client else {
+            return Ok(0);
+        };
+        let filter = ItemListFilter {
+            blockchain_status: Some(BlockchainStatus::Submitted),
+            sort: ItemSortField::CreatedAt,
+            order: SortOrder::Asc,
+            ..ItemListFilter::default()
+        };
+        let submitted = self.item_repo.list_items(limit, None, &filter).await?;
+        let mut confirmed = 0;
+        for item in submitted.items {
+            let Some(signature) = item.blockchain_signature.as_deref() else {
+                continue;
+            };
+            match client.get_transaction_status(signature).await {
+                Ok(true) => {
+                    self.outbox_repo
+                        .update_blockchain_status(
+                            &item.id,
+                            BlockchainStatus::Confirmed,
+                            Some(signature),
+                            None,
+                            None,
+                        )
+                        .await?;
+                    confirmed += 1;
+                    if let Some(telemetry) = &self.telemetry {
+                        let latency = (self.clock.now() - item.created_at)
+                            .to_std()
+                            .unwrap_or_default();
+                        telemetry.item_confirmed(latency);
                     }
-                    BlockchainError::SubmissionFailed(_) | BlockchainError::InsufficientFunds => {
-                        None
+                    let kind = DomainEventKind::ItemConfirmed {
+                        signature: signature.to_string(),
+                    };
+                    self.publish(DomainEvent::new(&item.id, kind), Some(&item))
+                        .await;
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    warn!(item_id = %item.id, error = %e, "Failed to check transaction confirmation");
+                }
+            }
+        }
+        if confirmed > 0 {
+            info!(count = confirmed, "Confirmed submitted items");
+            metrics::counter!("items_confirmed_total").increment(confirmed as u64);
+        }
+        Ok(confirmed)
+    }
+
+    /// Retry blockchain submission for a specific item
+    #[instrument(skip(self))]
+    pub async fn retry_blockchain_submission(&self, id: &str) -> Result<Item, ItemError> {
+        if !self.blockchain_enabled() {
+            return Err(ItemError::InvalidState(
+                "Blockchain submission is disabled".to_string(),
+            ));
+        }
+        let item = self
+            .get_item(id)
+            .await?
+            .ok_or_else(|| ItemError::NotFound(id.to_string()))?;
+
+        if item.blockchain_status != BlockchainStatus::PendingSubmission
+            && item.blockchain_status != BlockchainStatus::Failed
+        {
+            return Err(ItemError::InvalidState(
+                "Item is not pending submission or failed".to_string(),
+            ));
+        }
+
+        if item.blockchain_status == BlockchainStatus::PendingSubmission {
+            info!(item_id = %item.id, "Item already queued for submission");
+            return Ok(item);
+        }
+
+        let payload =
+            build_solana_outbox_payload_from_item(&self.load_content(item.clone()).await?);
+        let updated = self
+            .outbox_repo
+            .enqueue_solana_outbox_for_item(&item.id, &payload)
+            .await?;
+        self.record_audit(
+            "item.retry",
+            Some(&updated.id),
+            AuditEntry::item_diff(Some(&item), &updated),
+        )
+        .await;
+
+        Ok(updated)
+    }
+
+    /// Check an item against the chain: recompute its content hash and the hash it
+    /// anchors, then read its transaction back and compare the anchored memo
+    #[instrument(skip(self))]
+    pub async fn verify_item(&self, id: &str) -> Result<ItemVerification, VerifyItemError> {
+        let Some(client) = &self.blockchain_client else {
+            return Err(
+                ItemError::InvalidState("Blockchain submission is disabled".to_string()).into(),
+            );
+        };
+        let item = self
+            .get_item(id)
+            .await?
+            .ok_or_else(|| ItemError::NotFound(id.to_string()))?;
+        let item = self.load_content(item).await?;
+
+        let content_hash = ContentHasher::hash_item(&item);
+        let expected_hash = build_solana_outbox_payload_from_item(&item).hash;
+        let transaction = match &item.blockchain_signature {
+            Some(signature) => client.get_transaction(signature).await.inspect_err(|e| {
+                warn!(item_id = %item.id, error = %e, "Failed to read transaction for verification");
+            })?,
+            None => None,
+        };
+        let on_chain_hash = transaction.as_ref().and_then(|tx| tx.memo.clone());
+        let content_hash_matches = content_hash == item.hash;
+        let hash_matches = on_chain_hash.as_deref() == Some(expected_hash.as_str());
+        Ok(ItemVerification {
+            item_id: item.id,
+            content_hash,
+            content_hash_matches,
+            expected_hash,
+            signature: item.blockchain_signature,
+            found_on_chain: transaction.is_some(),
+            on_chain_hash,
+            hash_matches,
+            slot: transaction.as_ref().map(|tx| tx.slot),
+            confirmation_depth: transaction.as_ref().map(|tx| tx.confirmation_depth),
+            verified: content_hash_matches && hash_matches,
+        })
+    }
+
+    /// Submissions parked in the dead-letter queue, most recent first
+    #[instrument(skip(self))]
+    pub async fn list_failed_submissions(
+        &self,
+        limit: i64,
+        include_requeued: bool,
+    ) -> Result<Vec<FailedSubmission>, ItemError> {
+        self.outbox_repo
+            .list_failed_submissions(limit.clamp(1, 100), include_requeued)
+            .await
+    }
+
+    /// Move a dead-lettered submission back into the outbox with its retries reset
+    #[instrument(skip(self))]
+    pub async fn requeue_failed_submission(&self, id: &str) -> Result<Item, ItemError> {
+        if !self.blockchain_enabled() {
+            return Err(ItemError::InvalidState(
+                "Blockchain submission is disabled".to_string(),
+            ));
+        }
+        let item = self.outbox_repo.requeue_failed_submission(id).await?;
+        info!(dlq_id = %id, item_id = %item.id, "Dead-lettered submission requeued");
+        metrics::counter!("blockchain_dead_letter_requeued_total").increment(1);
+        Ok(item)
+    }
+
+    /// Start a job requeueing every parked submission (`POST /admin/dlq/requeue`)
+    pub async fn start_requeue_all_failed_submissions(
+        self: &Arc<Self>,
+        jobs: Arc<dyn JobStore>,
+    ) -> Result<Job, StartJobError> {
+        if !self.blockchain_enabled() {
+            return Err(
+                ItemError::InvalidState("Blockchain submission is disabled".to_string()).into(),
+            );
+        }
+        let service = Arc::clone(self);
+        let job = spawn_job(jobs, DLQ_REQUEUE_JOB, move |handle| async move {
+            service
+                .requeue_all_failed_submissions(&handle)
+                .await
+                .map(|summary| serde_json::json!(summary))
+                .map_err(|e| e.to_string())
+        })
+        .await?;
+        Ok(job)
+    }
+
+    /// Requeue parked submissions page by page until none are left. Entries that cannot
+    /// be requeued (e.g. their item was deleted) are counted as failed and skipped.
+    #[instrument(skip(self, job), fields(job_id = %job.id()))]
+    async fn requeue_all_failed_submissions(
+        &self,
+        job: &JobHandle,
+    ) -> Result<BulkRequeueSummary, ItemError> {
+        let mut summary = BulkRequeueSummary::default();
+        let mut seen = HashSet::new();
+        loop {
+            let page = self.outbox_repo.list_failed_submissions(100, false).await?;
+            let fresh: Vec<_> = page
+                .into_iter()
+                .filter(|submission| seen.insert(submission.id.clone()))
+                .collect();
+            if fresh.is_empty() {
+                break;
+            }
+            for submission in fresh {
+                match self.requeue_failed_submission(&submission.id).await {
+                    Ok(_) => summary.requeued += 1,
+                    Err(e) => {
+                        warn!(dlq_id = %submission.id, error = %e, "Bulk requeue skipped entry");
+                        summary.failed += 1;
                     }
-                };
+                }
+            }
+            job.progress(summary.requeued, summary.failed).await;
+        }
+        Ok(summary)
+    }
 
-                self.outbox_repo
-                    .fail_solana_outbox(
-                        &entry.id,
-                        &entry.aggregate_id,
-                        retry_count,
-                        outbox_status,
-                        item_status,
-                        &e.to_string(),
-                        next_retry,
-                        attempt_blockhash,
-                    )
+    /// Start a job encrypting plaintext content and re-wrapping content under retired
+    /// master keys with the current one (`POST /admin/encryption/reencrypt`)
+    pub async fn start_reencrypt_content(
+        self: &Arc<Self>,
+        jobs: Arc<dyn JobStore>,
+    ) -> Result<Job, StartJobError> {
+        let service = Arc::clone(self);
+        let job = spawn_job(jobs, CONTENT_REENCRYPT_JOB, move |handle| async move {
+            service
+                .reencrypt_content(&handle)
+                .await
+                .map(|summary| serde_json::json!(summary))
+                .map_err(|e| e.to_string())
+        })
+        .await?;
+        Ok(job)
+    }
+
+    /// Walk every item (deleted ones included) in ID order, re-encrypting batch by batch
+    #[instrument(skip(self, job), fields(job_id = %job.id()))]
+    async fn reencrypt_content(&self, job: &JobHandle) -> Result<ReencryptionSummary, ItemError> {
+        let mut summary = ReencryptionSummary::default();
+        let mut after: Option<String> = None;
+        loop {
+            let batch = self
+                .item_repo
+                .reencrypt_content(after.as_deref(), REENCRYPT_BATCH_SIZE)
+                .await?;
+            summary.scanned += batch.scanned;
+            summary.reencrypted += batch.reencrypted;
+            metrics::counter!("content_reencrypted_total").increment(batch.reencrypted);
+            job.progress(summary.scanned as i64, 0).await;
+            match batch.last_id {
+                Some(last_id) if batch.scanned >= REENCRYPT_BATCH_SIZE as u64 => {
+                    after = Some(last_id);
+                }
+                _ => break,
+            }
+        }
+        info!(
+            scanned = summary.scanned,
+            reencrypted = summary.reencrypted,
+            "Item content re-encrypted"
+        );
+        Ok(summary)
+    }
+
+    /// Process pending blockchain submissions and return how many entries were claimed
+    #[instrument(skip(self))]
+    pub async fn process_pending_submissions(&self, batch_size: i64) -> Result<usize, ItemError> {
+        let outcome = self.process_pending_batch(batch_size).await?;
+        Ok(outcome.claimed)
+    }
+
+    /// Process pending blockchain submissions (called by background worker)
+    #[instrument(skip(self))]
+    pub async fn process_pending_batch(&self, batch_size: i64) -> Result<BatchOutcome, ItemError> {
+        if !self.blockchain_enabled() {
+            return Ok(BatchOutcome::default());
+        }
+        let pending_entries = self
+            .outbox_repo
+            .claim_pending_solana_outbox(batch_size, self.claim_ttl)
+            .await?;
+        let count = pending_entries.len();
+
+        metrics::gauge!("outbox_pending_items_count").set(count as f64);
+
+        let mut outcome = BatchOutcome {
+            claimed: count,
+            ..BatchOutcome::default()
+        };
+        if count == 0 {
+            return Ok(outcome);
+        }
+
+        if let Some((balance, minimum)) = self.low_wallet_balance().await {
+            let message = format!(
+                "Wallet balance {} is below the minimum {}; submission deferred",
+                balance, minimum
+            );
+            let next_retry_at = self.clock.now() + Duration::seconds(LOW_BALANCE_RECHECK_SECS);
+            self.defer_entries(&pending_entries, &message, next_retry_at)
+                .await?;
+            warn!(
+                count = count,
+                balance, minimum, "Wallet balance too low; deferring submissions"
+            );
+            outcome.deferred = count;
+            return Ok(outcome);
+        }
+
+        let mut pending_entries = pending_entries;
+        if let Some((budget, spent)) = self.budget_spent().await {
+            let allowed =
+                usize::try_from(budget.remaining_submissions(spent)).unwrap_or(usize::MAX);
+            if allowed < count {
+                let over_budget = pending_entries.split_off(allowed);
+                let message = format!(
+                    "budget_exceeded: daily submission budget {} of signer {} is spent ({} used); \
+                     submission deferred to the next UTC day",
+                    budget.daily_limit, budget.signer, spent
+                );
+                self.defer_entries(&over_budget, &message, next_utc_day(self.clock.now()))
                     .await?;
+                metrics::counter!("blockchain_budget_exceeded_total")
+                    .increment(over_budget.len() as u64);
+                error!(
+                    signer = %budget.signer,
+                    spent,
+                    daily_limit = budget.daily_limit,
+                    deferred = over_budget.len(),
+                    "Daily submission budget exhausted; deferring submissions"
+                );
+                outcome.deferred = over_budget.len();
+            }
+        }
+        if pending_entries.is_empty() {
+            return Ok(outcome);
+        }
+
+        info!(
+            count = pending_entries.len(),
+            "Processing pending blockchain submissions"
+        );
+
+        for entry in pending_entries {
+            match self.process_outbox_entry(&entry).await {
+                Ok(true) => outcome.submitted += 1,
+                Ok(false) => outcome.failed += 1,
+                Err(e) => {
+                    outcome.failed += 1;
+                    error!(
+                        outbox_id = %entry.id,
+                        item_id = %entry.aggregate_id,
+                        error = ?e,
+                        "Failed to process pending submission"
+                    );
+                }
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Put `entries` back to pending until `next_retry_at` without using an attempt
+    async fn defer_entries(
+        &self,
+        entries: &[SolanaOutboxEntry],
+        message: &str,
+        next_retry_at: DateTime<Utc>,
+    ) -> Result<(), ItemError> {
+        for entry in entries {
+            self.outbox_repo
+                .fail_solana_outbox(
+                    &entry.id,
+                    &entry.aggregate_id,
+                    entry.retry_count,
+                    OutboxStatus::Pending,
+                    BlockchainStatus::PendingSubmission,
+                    message,
+                    Some(next_retry_at),
+                    None,
+                )
+                .await?;
+        }
+        metrics::counter!("blockchain_submissions_deferred_total").increment(entries.len() as u64);
+        Ok(())
+    }
+
+    /// The configured budget and what its signer has spent today. A failed ledger read
+    /// does not block submissions.
+    async fn budget_spent(&self) -> Option<(&SubmissionBudget, u64)> {
+        let (budget, ledger) = self.budget.as_ref()?;
+        match ledger
+            .spent_on(&budget.signer, self.clock.now().date_naive())
+            .await
+        {
+            Ok(spent) => {
+                metrics::gauge!("blockchain_budget_spent", "signer" => budget.signer.clone())
+                    .set(spent as f64);
+                Some((budget, spent))
+            }
+            Err(e) => {
+                warn!(error = %e, "Could not read submission budget spend; submitting anyway");
+                None
+            }
+        }
+    }
+
+    /// Charge one submission to today's budget
+    async fn record_submission_cost(&self) {
+        let Some((budget, ledger)) = &self.budget else {
+            return;
+        };
+        match ledger
+            .record_spend(
+                &budget.signer,
+                self.clock.now().date_naive(),
+                budget.cost_per_submission,
+            )
+            .await
+        {
+            Ok(spent) => {
+                metrics::gauge!("blockchain_budget_spent", "signer" => budget.signer.clone())
+                    .set(spent as f64);
             }
+            Err(e) => error!(error = %e, "Failed to record submission cost"),
         }
+    }
+
+    /// `(balance, minimum)` when a minimum is configured and the wallet is below it.
+    /// A failed balance lookup does not block submissions.
+    async fn low_wallet_balance(&self) -> Option<(u64, u64)> {
+        let minimum = self.min_wallet_balance?;
+        let client = self.blockchain_client.as_ref()?;
+        match client.get_balance().await {
+            Ok(balance) if balance < minimum => Some((balance, minimum)),
+            Ok(_) => None,
+            Err(e) => {
+                warn!(error = %e, "Could not check wallet balance; submitting anyway");
+                None
+            }
+        }
+    }
+
+    /// Process a single pending submission (sticky blockhash for idempotent retries).
+    /// Returns whether the transaction was submitted.
+    async fn process_outbox_entry(&self, entry: &SolanaOutboxEntry) -> Result<bool, ProcessError> {
+        let Some(blockchain_client) = &self.blockchain_client else {
+            return Ok(false);
+        };
+        let hash = &entry.payload.hash;
+        let existing_blockhash = entry.attempt_blockhash.as_deref();
+
+        let trace = SubmissionTrace::default();
+        let attempted_at = self.clock.now();
+        let started = Instant::now();
+        let submission = blockchain_client.submit_transaction(hash, existing_blockhash);
+        let result = trace
+            .scope(SigningContext::for_item(&entry.aggregate_id, hash).scope(submission))
+            .await;
+        // An open circuit never reached the RPC, so there is no attempt to attribute.
+        if !matches!(result, Err(BlockchainError::CircuitOpen)) {
+            let (endpoint, failed_over) = trace.endpoints();
+            let attempt = SubmissionAttempt {
+                attempted_at,
+                duration_ms: started.elapsed().as_millis() as u64,
+                endpoint,
+                failed_over,
+                succeeded: result.is_ok(),
+                signature: result.as_ref().ok().map(|(signature, _)| signature.clone()),
+                error: result.as_ref().err().map(ToString::to_string),
+            };
+            if let Err(e) = self
+                .outbox_repo
+                .record_submission_attempt(&entry.aggregate_id, &attempt)
+                .await
+            {
+                warn!(item_id = %entry.aggregate_id, error = %e, "Failed to record submission attempt");
+            }
+        }
+
+        match result {
+            Ok((signature, _blockhash_used)) => {
+                info!(
+                    outbox_id = %entry.id,
+                    item_id = %entry.aggregate_id,
+                    signature = %signature,
+                    "Background submission successful"
+                );
+                self.record_submission_cost().await;
+                self.outbox_repo
+                    .complete_solana_outbox(&entry.id, &entry.aggregate_id, &signature)
+                    .await?;
+                let kind = DomainEventKind::ItemSubmitted { signature };
+                self.publish(DomainEvent::new(&entry.aggregate_id, kind), None)
+                    .await;
+                Ok(true)
+            }
+            Err(e) => {
+                metrics::counter!("blockchain_submission_retry_total").increment(1);
+                if let Some(telemetry) = &self.telemetry {
+                    telemetry.submission_failed(e.reason());
+                }
+                warn!(
+                    outbox_id = %entry.id,
+                    item_id = %entry.aggregate_id,
+                    error = ?e,
+                    "Background submission failed"
+                );
+                // An open circuit never reached the RPC, so it does not consume an attempt.
+                let retry_count = if matches!(e, BlockchainError::CircuitOpen) {
+                    entry.retry_count
+                } else {
+                    entry.retry_count + 1
+                };
+
+                // CV-01 remediation: Sticky blockhash to prevent double-spend.
+                // We MUST NOT clear attempt_blockhash on Timeout, NetworkError, or
+                // SubmissionFailed, because the transaction may have landed on-chain
+                // despite the error. Clearing would cause the next retry to use a new
+                // blockhash and produce a new signature, risking double-spend.
+                // Only clear when we know the blockhash is invalid (BlockhashExpired).
+                // For errors that don't carry blockhash_used, pass None so we do not
+                // update the column and thus keep the existing value (safe default).
+                let attempt_blockhash = match &e {
+                    BlockchainError::BlockhashExpired => Some(None),
+                    BlockchainError::SubmissionFailedWithBlockhash { blockhash_used, .. } => {
+                        Some(Some(blockhash_used.as_str()))
+                    }
+                    BlockchainError::Timeout { blockhash, .. }
+                    | BlockchainError::NetworkError { blockhash, .. } => {
+                        Some(Some(blockhash.as_str()))
+                    }
+                    BlockchainError::SubmissionFailed(_)
+                    | BlockchainError::InsufficientFunds
+                    | BlockchainError::CircuitOpen => None,
+                };
+
+                if self.retry_policy.is_exhausted(retry_count) {
+                    let submission = self
+                        .outbox_repo
+                        .dead_letter_solana_outbox(
+                            &entry.id,
+                            &entry.aggregate_id,
+                            retry_count,
+                            &e.to_string(),
+                            attempt_blockhash,
+                        )
+                        .await?;
+                    metrics::counter!("blockchain_dead_lettered_total").increment(1);
+                    error!(
+                        outbox_id = %entry.id,
+                        item_id = %entry.aggregate_id,
+                        dlq_id = %submission.id,
+                        retry_count = retry_count,
+                        "Submission exhausted its retries; moved to dead-letter queue"
+                    );
+                    let kind = DomainEventKind::ItemFailed {
+                        error: submission.last_error,
+                    };
+                    self.publish(DomainEvent::new(&entry.aggregate_id, kind), None)
+                        .await;
+                } else {
+                    let backoff = self.retry_policy.delay(retry_count.max(1) as u32);
+                    self.outbox_repo
+                        .fail_solana_outbox(
+                            &entry.id,
+                            &entry.aggregate_id,
+                            retry_count,
+                            OutboxStatus::Pending,
+                            BlockchainStatus::PendingSubmission,
+                            &e.to_string(),
+                            Some(self.clock.now() + Duration::seconds(backoff.as_secs() as i64)),
+                            attempt_blockhash,
+                        )
+                        .await?;
+                }
+                Ok(false)
+            }
+        }
+    }
+
+    /// Hand `event` about `item` (looked up when None) to the live feed and the message
+    /// bus, if any; a failure is logged and counted only
+    async fn publish(&self, event: DomainEvent, item: Option<&Item>) {
+        self.broadcast(&event, item).await;
+        let Some(publisher) = &self.publisher else {
+            return;
+        };
+        if let Err(e) = publisher.publish(&event).await {
+            metrics::counter!("domain_events_publish_failures_total", "event" => event.name())
+                .increment(1);
+            warn!(event = event.name(), item_id = %event.item_id, error = %e, "Failed to publish domain event");
+        }
+    }
+
+    /// Send `event` to the live feed with the tenant and tags of its item. The item is
+    /// only read when nobody passed it and someone is subscribed.
+    async fn broadcast(&self, event: &DomainEvent, item: Option<&Item>) {
+        if !self.feed.has_subscribers() {
+            return;
+        }
+        let looked_up;
+        let item = match item {
+            Some(item) => item,
+            None => match self.item_repo.get_item(&event.item_id).await {
+                Ok(Some(item)) => {
+                    looked_up = item;
+                    &looked_up
+                }
+                Ok(None) => return,
+                Err(e) => {
+                    warn!(event = event.name(), item_id = %event.item_id, error = %e, "Failed to read item for the live feed");
+                    return;
+                }
+            },
+        };
+        self.feed.send(FeedEvent::new(event.clone(), item));
+    }
+
+    /// Submission attempts of an item, oldest first, with the RPC endpoint of each
+    #[instrument(skip(self))]
+    pub async fn list_submission_attempts(
+        &self,
+        id: &str,
+    ) -> Result<Vec<SubmissionAttempt>, ItemError> {
+        self.get_item(id)
+            .await?
+            .ok_or_else(|| ItemError::NotFound(id.to_string()))?;
+        self.outbox_repo.list_submission_attempts(id).await
+    }
+
+    /// Lifecycle of an item, oldest first: creation, submission attempts, status changes
+    /// and webhook deliveries (the last two only when the operational logs are configured)
+    #[instrument(skip(self))]
+    pub async fn item_timeline(&self, id: &str) -> Result<ItemTimeline, ItemError> {
+        let item = self
+            .get_item(id)
+            .await?
+            .ok_or_else(|| ItemError::NotFound(id.to_string()))?;
+        let mut entries = vec![TimelineEntry::created(item.created_at)];
+        entries.extend(
+            self.outbox_repo
+                .list_submission_attempts(id)
+                .await?
+                .into_iter()
+                .map(TimelineEntry::attempt),
+        );
+        if let Some(log) = &self.event_log {
+            let events = log
+                .item_events(id)
+                .await
+                .map_err(|_| ItemError::RepositoryFailure)?;
+            entries.extend(events.into_iter().map(TimelineEntry::status_changed));
+        }
+        if let Some(log) = &self.delivery_log {
+            let deliveries = log
+                .item_deliveries(id)
+                .await
+                .map_err(|_| ItemError::RepositoryFailure)?;
+            entries.extend(deliveries.into_iter().map(TimelineEntry::delivery));
+        }
+        // Stable, so entries recorded at the same instant keep the order above
+        entries.sort_by_key(|e| e.at);
+        Ok(ItemTimeline {
+            item_id: item.id,
+            entries,
+        })
+    }
+
+    /// Size of the submission queue (pending, due, in flight and dead-lettered)
+    #[instrument(skip(self))]
+    pub async fn queue_depth(&self) -> Result<QueueDepth, ItemError> {
+        self.outbox_repo.queue_depth().await
+    }
+
+    /// Health of all dependencies, served from cache while the last check is fresh
+    #[instrument(skip(self))]
+    pub async fn health_check(&self) -> HealthResponse {
+        if let Some((checked_at, health)) = self.health_cache.lock().unwrap().as_ref()
+            && checked_at.elapsed() < self.health_cache_ttl
+        {
+            return health.clone();
+        }
+        self.deep_health_check().await
+    }
+
+    /// Force a fresh check of all dependencies and refresh the cache
+    #[instrument(skip(self))]
+    pub async fn deep_health_check(&self) -> HealthResponse {
+        let started = Instant::now();
+        let db_health = match self.item_repo.health_check().await {
+            Ok(()) => HealthStatus::Healthy,
+            Err(_) => HealthStatus::Unhealthy,
+        };
+        let db_latency = started.elapsed();
+
+        let mut wallet_balance = None;
+        let mut blockchain_latency = None;
+        let blockchain_health = match &self.blockchain_client {
+            Some(client) => {
+                let started = Instant::now();
+                let reachable = client.health_check().await;
+                blockchain_latency = Some(started.elapsed());
+                match reachable {
+                    Ok(()) => {
+                        wallet_balance = client.get_balance().await.ok();
+                        let budget_exhausted = matches!(
+                            self.budget_spent().await,
+                            Some((budget, spent)) if budget.remaining_submissions(spent) == 0
+                        );
+                        // Reachable but unable (or not allowed) to pay fees: submissions are
+                        // being deferred
+                        match (wallet_balance, self.min_wallet_balance) {
+                            (Some(balance), Some(minimum)) if balance < minimum => {
+                                HealthStatus::Degraded
+                            }
+                            _ if budget_exhausted => HealthStatus::Degraded,
+                            _ => HealthStatus::Healthy,
+                        }
+                    }
+                    Err(_) => HealthStatus::Unhealthy,
+                }
+            }
+            None => HealthStatus::Disabled,
+        };
+
+        let mut health = HealthResponse::new(db_health, blockchain_health)
+            .with_wallet_balance(wallet_balance)
+            .with_dependency("database", DependencyHealth::new(db_health, db_latency));
+        if let Some(latency) = blockchain_latency {
+            health = health.with_dependency(
+                "blockchain",
+                DependencyHealth::new(blockchain_health, latency),
+            );
+        }
+        *self.health_cache.lock().unwrap() = Some((Instant::now(), health.clone()));
+        health
+    }
+}
+
+/// Midnight UTC at the start of tomorrow, when a spent daily budget resets
+fn next_utc_day(now: DateTime<Utc>) -> DateTime<Utc> {
+    let tomorrow = now.date_naive() + Duration::days(1);
+    tomorrow.and_time(chrono::NaiveTime::MIN).and_utc()
+}
+
+#[cfg(test)]
+mod service_tests {
+    use super::*;
+    use crate::domain::{AuditActor, BlockchainStatus, ItemMetadataRequest, SubmissionRepository};
+    use crate::test_utils::{
+        MockBlockchainClient, MockClock, MockConfig, MockMessagePublisher, MockObjectStore,
+        MockProvider, MockStep, MockTelemetrySink, TelemetryRecord, TraceCapture, mock_repos,
+    };
+    use chrono::Utc;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    // --- Tests ---
+
+    #[tokio::test]
+    async fn test_create_item_validation_error() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = AppService::new(item_repo, outbox_repo, bc);
+
+        // Name too short/empty assumes validation logic in CreateItemRequest
+        // We simulate a request that fails validator::Validate
+        let request = CreateItemRequest {
+            name: "".to_string(), // Invalid
+            description: None,
+            content: "content".to_string(),
+            metadata: None,
+            content_key: None,
+            content_type: None,
+        };
+
+        let result = service.create_and_submit_item(&request).await;
+        assert!(matches!(result, Err(CreateItemError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_item_span_records_item_id() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = AppService::new(item_repo, outbox_repo, bc);
+        let capture = TraceCapture::default();
+
+        let request = CreateItemRequest::new("Traced".to_string(), "Content".to_string());
+        let item = capture
+            .run(service.create_and_submit_item(&request))
+            .await
+            .unwrap();
+
+        let span = capture.span("create_and_submit_item").unwrap();
+        assert_eq!(span.field("item_name"), Some("Traced"));
+        assert_eq!(span.field("item_id"), Some(item.id.as_str()));
+        let insert = capture.span("insert_item").unwrap();
+        assert_eq!(insert.parent.as_deref(), Some("create_and_submit_item"));
+        assert_eq!(insert.field("item_name"), Some("Traced"));
+        assert!(capture.span("enqueue_solana_outbox").is_some());
+        assert!(capture.span("commit").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_create_item_metadata_size_limit() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = AppService::new(item_repo, outbox_repo, bc).with_max_metadata_bytes(256);
+
+        let mut request = CreateItemRequest::new("Item".to_string(), "Content".to_string());
+        request.metadata = Some(ItemMetadataRequest {
+            author: None,
+            version: None,
+            tags: vec![],
+            custom_fields: [("note".to_string(), "x".repeat(100))].into(),
+        });
+        assert!(service.create_and_submit_item(&request).await.is_ok());
+
+        request.metadata.as_mut().unwrap().custom_fields =
+            [("note".to_string(), "x".repeat(300))].into();
+        let result = service.create_and_submit_item(&request).await;
+        match result {
+            Err(CreateItemError::Validation(ValidationError::TooLarge { field, size, limit })) => {
+                assert_eq!(field, "metadata");
+                assert!(size > 300);
+                assert_eq!(limit, 256);
+            }
+            other => panic!("expected TooLarge, got {:?}", other),
+        }
+        assert_eq!(mock.get_all_items().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_item_injected_failures_leave_other_calls_working() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = AppService::new(item_repo, outbox_repo, bc);
+        let request = CreateItemRequest::new("Item".to_string(), "Content".to_string());
+
+        // Only the second insert fails; the unit of work is rolled back
+        mock.fail_nth_call("insert_item", 2);
+        assert!(service.create_and_submit_item(&request).await.is_ok());
+        let result = service.create_and_submit_item(&request).await;
+        assert!(matches!(
+            result,
+            Err(CreateItemError::Item(ItemError::RepositoryFailure))
+        ));
+        assert!(service.create_and_submit_item(&request).await.is_ok());
+        assert_eq!(mock.call_count("insert_item"), 3);
+        assert_eq!(mock.get_all_items().len(), 2);
+
+        mock.fail_on("commit");
+        assert!(service.create_and_submit_item(&request).await.is_err());
+        let item = &mock.get_all_items()[0];
+        assert!(service.get_item(&item.id).await.unwrap().is_some());
+        assert_eq!(mock.get_all_outbox_entries().len(), 2);
+
+        mock.clear_failures();
+        assert!(service.create_and_submit_item(&request).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_item_with_dedupe_returns_existing_item() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = AppService::new(item_repo, outbox_repo, bc);
+        let request = CreateItemRequest::new("Item".to_string(), "Content".to_string());
+
+        let first = service
+            .create_item_with_dedupe(&request, DedupeMode::ReturnExisting)
+            .await
+            .unwrap();
+        let again = service
+            .create_item_with_dedupe(&request, DedupeMode::ReturnExisting)
+            .await
+            .unwrap();
+        assert_eq!(again.id, first.id);
+        assert_eq!(mock.get_all_items().len(), 1);
+
+        // Another tenant's item with the same content is not returned
+        let other = TenantScope::scope(
+            Some("acme".to_string()),
+            service.create_item_with_dedupe(&request, DedupeMode::ReturnExisting),
+        )
+        .await
+        .unwrap();
+        assert_ne!(other.id, first.id);
+
+        // Without a unique index, rejecting still stores a copy
+        let copy = service
+            .create_item_with_dedupe(&request, DedupeMode::Reject)
+            .await
+            .unwrap();
+        assert_ne!(copy.id, first.id);
+        assert_eq!(mock.get_all_items().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_create_item_does_not_submit_blockchain() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::failing("Chain down"));
+        let service = AppService::new(item_repo, outbox_repo, bc);
+
+        let request = CreateItemRequest {
+            name: "Test Item".to_string(),
+            description: None,
+            content: "Content".to_string(),
+            metadata: None,
+            content_key: None,
+            content_type: None,
+        };
+
+        let result = service.create_and_submit_item(&request).await;
+
+        assert!(result.is_ok());
+        let item = result.unwrap();
+
+        // Item should be queued for submission, no immediate blockchain attempt
+        assert_eq!(item.blockchain_status, BlockchainStatus::PendingSubmission);
+        assert!(item.blockchain_signature.is_none());
+        assert!(item.blockchain_last_error.is_none());
+        assert!(item.blockchain_next_retry_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_create_item_writes_outbox_entry_with_item() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = AppService::new(item_repo, outbox_repo, bc);
+
+        let request = CreateItemRequest::new("Outbox".to_string(), "Content".to_string());
+        let item = service.create_and_submit_item(&request).await.unwrap();
+
+        let entries = mock.get_all_outbox_entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].aggregate_id, item.id);
+        assert_eq!(entries[0].status, OutboxStatus::Pending);
+        assert_eq!(
+            entries[0].payload,
+            crate::domain::build_solana_outbox_payload_from_item(&item)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_item_rolls_back_when_outbox_write_fails() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let service = AppService::new(
+            item_repo,
+            outbox_repo,
+            Arc::new(MockBlockchainClient::new()),
+        );
+        mock.set_outbox_writes_failing(true);
+
+        let request = CreateItemRequest::new("Atomic".to_string(), "Content".to_string());
+        let result = service.create_and_submit_item(&request).await;
+
+        assert!(matches!(
+            result,
+            Err(CreateItemError::Item(ItemError::RepositoryFailure))
+        ));
+        assert!(mock.get_all_items().is_empty(), "item rolled back");
+        assert!(mock.get_all_outbox_entries().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_items_reports_each_row() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let service = AppService::new(
+            item_repo,
+            outbox_repo,
+            Arc::new(MockBlockchainClient::new()),
+        );
+        let row = |line, name: &str| ImportRow {
+            line,
+            request: Ok(CreateItemRequest::new(
+                name.to_string(),
+                "Content".to_string(),
+            )),
+        };
+        let rows = vec![
+            row(1, "First"),
+            row(2, ""),
+            ImportRow {
+                line: 3,
+                request: Err(ValidationError::InvalidFormat("Invalid JSON".to_string())),
+            },
+            row(4, "Second"),
+        ];
+
+        let report = service.import_items(rows).await.unwrap();
+        assert_eq!((report.total, report.imported, report.failed), (4, 2, 2));
+        assert!(report.lines[0].item_id.is_some());
+        let error = report.lines[1].error.as_ref().unwrap();
+        assert_eq!(error.r#type, "validation_error");
+        assert_eq!(error.fields[0].field, "name");
+        assert!(report.lines[2].error.is_some());
+        assert!(report.lines[3].item_id.is_some());
+        assert_eq!(mock.get_all_items().len(), 2);
+        assert_eq!(mock.get_all_outbox_entries().len(), 2);
+
+        assert!(service.import_items(Vec::new()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_items_reports_storage_failures_per_row() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let service = AppService::new(
+            item_repo,
+            outbox_repo,
+            Arc::new(MockBlockchainClient::new()),
+        );
+        mock.set_outbox_writes_failing(true);
+
+        let rows = (1..=3)
+            .map(|line| ImportRow {
+                line,
+                request: Ok(CreateItemRequest::new(
+                    format!("Item {}", line),
+                    "Content".to_string(),
+                )),
+            })
+            .collect();
+        let report = service.import_items(rows).await.unwrap();
+        assert_eq!((report.imported, report.failed), (0, 3));
+        assert!(
+            report
+                .lines
+                .iter()
+                .all(|l| l.error.as_ref().unwrap().r#type == "repository_error")
+        );
+        assert!(mock.get_all_items().is_empty(), "batches rolled back");
+    }
+
+    #[tokio::test]
+    async fn test_retry_submission_invalid_state() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = AppService::new(item_repo, outbox_repo, bc);
+
+        let request = CreateItemRequest::new("Test".to_string(), "Content".to_string());
+        let created = mock.create_item(&request).await.unwrap();
+        mock.update_blockchain_status(
+            &created.id,
+            BlockchainStatus::Submitted,
+            Some("sig"),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let result = service.retry_blockchain_submission(&created.id).await;
+
+        match result {
+            Err(ItemError::InvalidState(msg)) => {
+                assert!(msg.contains("not pending submission"));
+            }
+            _ => panic!("Expected invalid state error for invalid item status"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_submission_failed_requeues() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = AppService::new(item_repo, outbox_repo, bc);
+
+        let request = CreateItemRequest::new("Retry".to_string(), "Content".to_string());
+        let created = mock.create_item(&request).await.unwrap();
+        mock.update_blockchain_status(
+            &created.id,
+            BlockchainStatus::Failed,
+            None,
+            Some("previous failure"),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let updated = service
+            .retry_blockchain_submission(&created.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            updated.blockchain_status,
+            BlockchainStatus::PendingSubmission
+        );
+        assert!(updated.blockchain_last_error.is_none());
+        assert!(updated.blockchain_next_retry_at.is_none());
+        assert_eq!(updated.blockchain_retry_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_process_pending_submissions_batch() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = AppService::new(item_repo, outbox_repo, bc);
+
+        let request1 = CreateItemRequest::new("Item1".to_string(), "Content".to_string());
+        let request2 = CreateItemRequest::new("Item2".to_string(), "Content".to_string());
+        let item1 = service.create_and_submit_item(&request1).await.unwrap();
+        let item2 = service.create_and_submit_item(&request2).await.unwrap();
+
+        let count = service.process_pending_submissions(10).await.unwrap();
+        assert_eq!(count, 2);
+
+        let updated1 = mock.get_item(&item1.id).await.unwrap().unwrap();
+        let updated2 = mock.get_item(&item2.id).await.unwrap().unwrap();
+
+        assert_eq!(updated1.blockchain_status, BlockchainStatus::Submitted);
+        assert_eq!(updated2.blockchain_status, BlockchainStatus::Submitted);
+        assert!(updated1.blockchain_signature.is_some());
+        assert!(updated2.blockchain_signature.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_health_check_mixed() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, _outbox_repo) = mock_repos(&mock);
+        let other = Arc::new(MockProvider::new());
+        let (_, outbox_repo2) = mock_repos(&other);
+        let bc = Arc::new(MockBlockchainClient::failing("unhealthy"));
+        let service = AppService::new(item_repo, outbox_repo2, bc);
+        let health = service.health_check().await;
 
-        Ok(())
+        assert_eq!(health.status, HealthStatus::Unhealthy);
+        assert_eq!(health.database, HealthStatus::Healthy);
+        assert_eq!(health.blockchain, HealthStatus::Unhealthy);
     }
 
-    /// Perform health check on all dependencies
-    #[instrument(skip(self))]
-    pub async fn health_check(&self) -> HealthResponse {
-        let db_health = match self.item_repo.health_check().await {
-            Ok(()) => HealthStatus::Healthy,
-            Err(_) => HealthStatus::Unhealthy,
-        };
-        let blockchain_health = match self.blockchain_client.health_check().await {
-            Ok(()) => HealthStatus::Healthy,
-            Err(_) => HealthStatus::Unhealthy,
-        };
-        HealthResponse::new(db_health, blockchain_health)
+    #[tokio::test]
+    async fn test_retry_blockchain_submission_item_not_found() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = AppService::new(item_repo, outbox_repo, bc);
+
+        let result = service.retry_blockchain_submission("nonexistent").await;
+
+        assert!(matches!(result, Err(ItemError::NotFound(_))));
     }
-}
 
-/// Calculate exponential backoff with maximum cap
-fn calculate_backoff(retry_count: i32) -> i64 {
-    let backoff = 2_i64.pow(retry_count.min(8) as u32);
-    backoff.min(MAX_BACKOFF_SECS)
-}
+    #[tokio::test]
+    async fn test_retry_blockchain_submission_failed_status() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = AppService::new(item_repo, outbox_repo, bc);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let request = CreateItemRequest::new("Failed".to_string(), "Content".to_string());
+        let created = mock.create_item(&request).await.unwrap();
+        mock.update_blockchain_status(
+            &created.id,
+            BlockchainStatus::Failed,
+            None,
+            Some("failed"),
+            None,
+        )
+        .await
+        .unwrap();
 
-    #[test]
-    fn test_calculate_backoff() {
-        assert_eq!(calculate_backoff(0), 1);
-        assert_eq!(calculate_backoff(1), 2);
-        assert_eq!(calculate_backoff(2), 4);
-        assert_eq!(calculate_backoff(3), 8);
-        assert_eq!(calculate_backoff(4), 16);
-        assert_eq!(calculate_backoff(5), 32);
-        assert_eq!(calculate_backoff(6), 64);
-        assert_eq!(calculate_backoff(7), 128);
-        assert_eq!(calculate_backoff(8), 256);
-        assert_eq!(calculate_backoff(9), 256); // Capped at 2^8
-        assert_eq!(calculate_backoff(10), 256);
+        let result = service.retry_blockchain_submission(&created.id).await;
+        assert!(result.is_ok());
+        let updated = result.unwrap();
+        assert_eq!(
+            updated.blockchain_status,
+            BlockchainStatus::PendingSubmission
+        );
     }
-}
-#[cfg(test)]
-mod service_tests {
-    use super::*;
-    use crate::domain::BlockchainStatus;
-    use crate::test_utils::{MockBlockchainClient, MockProvider, mock_repos};
-    use chrono::Utc;
-    use std::sync::Arc;
 
-    // --- Tests ---
+    #[tokio::test]
+    async fn test_process_pending_submissions_empty() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = AppService::new(item_repo, outbox_repo, bc);
+
+        let count = service.process_pending_submissions(10).await.unwrap();
+        assert_eq!(count, 0);
+    }
 
     #[tokio::test]
-    async fn test_create_item_validation_error() {
+    async fn test_get_item_success() {
         let mock = Arc::new(MockProvider::new());
         let (item_repo, outbox_repo) = mock_repos(&mock);
         let bc = Arc::new(MockBlockchainClient::new());
         let service = AppService::new(item_repo, outbox_repo, bc);
 
-        // Name too short/empty assumes validation logic in CreateItemRequest
-        // We simulate a request that fails validator::Validate
-        let request = CreateItemRequest {
-            name: "".to_string(), // Invalid
-            description: None,
-            content: "content".to_string(),
-            metadata: None,
-        };
+        let request = CreateItemRequest::new("Test Item".to_string(), "Content".to_string());
+        let created = service.create_and_submit_item(&request).await.unwrap();
 
-        let result = service.create_and_submit_item(&request).await;
-        assert!(matches!(result, Err(CreateItemError::Validation(_))));
+        let result = service.get_item(&created.id).await.unwrap();
+        assert!(result.is_some());
+        assert_eq!(result.unwrap().id, created.id);
     }
 
     #[tokio::test]
-    async fn test_create_item_does_not_submit_blockchain() {
+    async fn test_list_items_success() {
         let mock = Arc::new(MockProvider::new());
         let (item_repo, outbox_repo) = mock_repos(&mock);
-        let bc = Arc::new(MockBlockchainClient::failing("Chain down"));
+        let bc = Arc::new(MockBlockchainClient::new());
         let service = AppService::new(item_repo, outbox_repo, bc);
 
+        let result = service
+            .list_items(10, None, &ItemListFilter::default())
+            .await
+            .unwrap();
+        assert!(result.items.is_empty());
+        assert!(!result.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_create_item_blockchain_success() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = AppService::new(item_repo, outbox_repo, bc);
         let request = CreateItemRequest {
-            name: "Test Item".to_string(),
-            description: None,
+            name: "Success Item".to_string(),
+            description: Some("Description".to_string()),
             content: "Content".to_string(),
             metadata: None,
+            content_key: None,
+            content_type: None,
         };
 
         let result = service.create_and_submit_item(&request).await;
-
         assert!(result.is_ok());
-        let item = result.unwrap();
 
-        // Item should be queued for submission, no immediate blockchain attempt
+        let item = result.unwrap();
         assert_eq!(item.blockchain_status, BlockchainStatus::PendingSubmission);
         assert!(item.blockchain_signature.is_none());
-        assert!(item.blockchain_last_error.is_none());
-        assert!(item.blockchain_next_retry_at.is_none());
     }
 
     #[tokio::test]
-    async fn test_retry_submission_invalid_state() {
+    async fn test_health_check_both_healthy() {
         let mock = Arc::new(MockProvider::new());
         let (item_repo, outbox_repo) = mock_repos(&mock);
         let bc = Arc::new(MockBlockchainClient::new());
         let service = AppService::new(item_repo, outbox_repo, bc);
+        let health = service.health_check().await;
 
-        let request = CreateItemRequest::new("Test".to_string(), "Content".to_string());
-        let created = mock.create_item(&request).await.unwrap();
-        mock.update_blockchain_status(
-            &created.id,
-            BlockchainStatus::Submitted,
-            Some("sig"),
-            None,
-            None,
+        assert_eq!(health.status, HealthStatus::Healthy);
+        assert_eq!(health.database, HealthStatus::Healthy);
+        assert_eq!(health.blockchain, HealthStatus::Healthy);
+    }
+
+    #[tokio::test]
+    async fn test_low_wallet_balance_degrades_health() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        bc.set_balance(4_000);
+        let service =
+            AppService::new(item_repo, outbox_repo, bc.clone()).with_min_wallet_balance(5_000);
+
+        let health = service.deep_health_check().await;
+        assert_eq!(health.blockchain, HealthStatus::Degraded);
+        assert_eq!(health.status, HealthStatus::Degraded);
+        assert_eq!(health.wallet_balance, Some(4_000));
+
+        bc.set_balance(5_000);
+        assert_eq!(
+            service.deep_health_check().await.status,
+            HealthStatus::Healthy
+        );
+    }
+
+    #[tokio::test]
+    async fn test_low_wallet_balance_defers_submissions() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        bc.set_balance(4_000);
+        let service =
+            AppService::new(item_repo, outbox_repo, bc.clone()).with_min_wallet_balance(5_000);
+        let request = CreateItemRequest::new("Unfunded".to_string(), "Content".to_string());
+        let created = service.create_and_submit_item(&request).await.unwrap();
+
+        let outcome = service.process_pending_batch(10).await.unwrap();
+        assert_eq!((outcome.claimed, outcome.deferred), (1, 1));
+        assert!(bc.get_transactions().is_empty());
+        let item = service.get_item(&created.id).await.unwrap().unwrap();
+        assert_eq!(item.blockchain_status, BlockchainStatus::PendingSubmission);
+        assert_eq!(item.blockchain_retry_count, 0, "no attempt was used");
+        assert!(
+            item.blockchain_last_error
+                .unwrap()
+                .contains("below the minimum 5000")
+        );
+        let entry = mock.get_all_outbox_entries().pop().unwrap();
+        assert_eq!(entry.status, OutboxStatus::Pending);
+        assert_eq!(entry.retry_count, 0);
+
+        // Funded again: submitted once the deferral has passed
+        bc.set_balance(5_000);
+        mock.advance_clock(Duration::seconds(LOW_BALANCE_RECHECK_SECS));
+        let outcome = service.process_pending_batch(10).await.unwrap();
+        assert_eq!((outcome.submitted, outcome.deferred), (1, 0));
+    }
+
+    fn budget(daily_limit: u64) -> SubmissionBudget {
+        SubmissionBudget {
+            signer: "fee-payer".to_string(),
+            daily_limit,
+            cost_per_submission: 5_000,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submission_budget_defers_submissions_over_budget() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        // Room for two submissions today
+        let service = AppService::new(item_repo, outbox_repo, bc.clone())
+            .with_submission_budget(budget(12_000), mock.clone());
+        for i in 0..3 {
+            let request = CreateItemRequest::new(format!("Item {}", i), format!("Content {}", i));
+            service.create_and_submit_item(&request).await.unwrap();
+        }
+
+        let outcome = service.process_pending_batch(10).await.unwrap();
+        assert_eq!((outcome.submitted, outcome.deferred), (2, 1));
+        assert_eq!(bc.get_transactions().len(), 2);
+        let today = Utc::now().date_naive();
+        assert_eq!(mock.spent_on("fee-payer", today).await.unwrap(), 10_000);
+
+        let deferred = mock
+            .get_all_items()
+            .into_iter()
+            .find(|item| item.blockchain_status == BlockchainStatus::PendingSubmission)
+            .unwrap();
+        assert_eq!(deferred.blockchain_retry_count, 0, "no attempt was used");
+        assert!(
+            deferred
+                .blockchain_last_error
+                .unwrap()
+                .starts_with("budget_exceeded")
+        );
+        assert_eq!(
+            deferred.blockchain_next_retry_at,
+            Some(next_utc_day(Utc::now()))
+        );
+
+        // Make the entry due again while the service's day is still spent: it is deferred
+        // without an attempt and health degrades
+        mock.advance_clock(Duration::days(1));
+        let outcome = service.process_pending_batch(10).await.unwrap();
+        assert_eq!((outcome.submitted, outcome.deferred), (0, 1));
+        let health = service.deep_health_check().await;
+        assert_eq!(health.blockchain, HealthStatus::Degraded);
+    }
+
+    #[test]
+    fn test_submission_budget_remaining_submissions() {
+        assert_eq!(budget(12_000).remaining_submissions(0), 2);
+        assert_eq!(budget(12_000).remaining_submissions(10_000), 0);
+        assert_eq!(budget(12_000).remaining_submissions(20_000), 0);
+    }
+
+    #[tokio::test]
+    async fn test_health_check_is_cached_until_deep_check() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = AppService::new(item_repo, outbox_repo, bc.clone());
+        assert_eq!(service.health_check().await.status, HealthStatus::Healthy);
+
+        bc.set_healthy(false);
+        // Cached snapshot is still served
+        assert_eq!(service.health_check().await.status, HealthStatus::Healthy);
+
+        // Deep check bypasses and refreshes the cache
+        let deep = service.deep_health_check().await;
+        assert_eq!(deep.blockchain, HealthStatus::Unhealthy);
+        assert_eq!(service.health_check().await.status, HealthStatus::Unhealthy);
+    }
+
+    #[tokio::test]
+    async fn test_health_cache_ttl_and_dependency_timings() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = AppService::new(item_repo, outbox_repo, bc.clone())
+            .with_health_cache_ttl(std::time::Duration::ZERO);
+
+        let health = service.health_check().await;
+        let database = &health.dependencies["database"];
+        assert_eq!(database.status, HealthStatus::Healthy);
+        assert!(database.checked_at <= Utc::now());
+        assert_eq!(
+            health.dependencies["blockchain"].status,
+            HealthStatus::Healthy
+        );
+
+        // Without a TTL every call checks again
+        bc.set_healthy(false);
+        let health = service.health_check().await;
+        assert_eq!(health.status, HealthStatus::Unhealthy);
+        assert_eq!(
+            health.dependencies["blockchain"].status,
+            HealthStatus::Unhealthy
+        );
+        assert!(health.dependencies["blockchain"].checked_at >= database.checked_at);
+
+        let json = serde_json::to_value(&health).unwrap();
+        assert!(json["dependencies"]["database"]["latency_ms"].is_u64());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_slow_dependencies_trip_caller_deadline() {
+        let mock = Arc::new(MockProvider::with_config(
+            MockConfig::success().with_latency_ms(2_000),
+        ));
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::with_config(
+            MockConfig::success().with_latency_ms(3_000),
+        ));
+        let service = AppService::new(item_repo, outbox_repo, bc);
+
+        let started = tokio::time::Instant::now();
+        let result = tokio::time::timeout(
+            std::time::Duration::from_secs(1),
+            service.deep_health_check(),
         )
-        .await
-        .unwrap();
+        .await;
+        assert!(result.is_err());
 
-        let result = service.retry_blockchain_submission(&created.id).await;
+        // Database, RPC and balance checks run back to back on the paused clock
+        let health = service.deep_health_check().await;
+        assert_eq!(health.status, HealthStatus::Healthy);
+        assert_eq!(started.elapsed(), std::time::Duration::from_secs(9));
+    }
 
-        match result {
-            Err(ItemError::InvalidState(msg)) => {
-                assert!(msg.contains("not pending submission"));
-            }
-            _ => panic!("Expected invalid state error for invalid item status"),
+    #[tokio::test]
+    async fn test_process_pending_submissions_failure_updates_retry() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::failing("rpc error"));
+        let service = AppService::new(item_repo, outbox_repo, bc);
+
+        let request = CreateItemRequest::new("Retry Item".to_string(), "Content".to_string());
+        let created = service.create_and_submit_item(&request).await.unwrap();
+
+        let count = service.process_pending_submissions(10).await.unwrap();
+        assert_eq!(count, 1);
+
+        let updated = mock.get_item(&created.id).await.unwrap().unwrap();
+        assert_eq!(
+            updated.blockchain_status,
+            BlockchainStatus::PendingSubmission
+        );
+        assert!(updated.blockchain_last_error.is_some());
+        assert!(updated.blockchain_next_retry_at.is_some());
+        assert_eq!(updated.blockchain_retry_count, 1);
+        assert!(updated.blockchain_next_retry_at.unwrap() > Utc::now());
+    }
+
+    #[tokio::test]
+    async fn test_retry_is_scheduled_and_becomes_due_on_the_service_clock() {
+        let clock = Arc::new(MockClock::default());
+        let mock = Arc::new(MockProvider::new().with_clock(clock.clone()));
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::with_script(vec![
+            MockStep::Fail("rpc error".to_string()),
+            MockStep::Succeed,
+        ]));
+        let service = AppService::new(item_repo, outbox_repo, bc).with_clock(clock.clone());
+
+        let request = CreateItemRequest::new("Clocked Item".to_string(), "Content".to_string());
+        let created = service.create_and_submit_item(&request).await.unwrap();
+        service.process_pending_submissions(10).await.unwrap();
+
+        let backoff = RetryPolicy::default().delay(1);
+        let item = mock.get_item(&created.id).await.unwrap().unwrap();
+        assert_eq!(
+            item.blockchain_next_retry_at,
+            Some(clock.now() + Duration::from_std(backoff).unwrap())
+        );
+
+        clock.advance(backoff - std::time::Duration::from_secs(1));
+        assert_eq!(service.process_pending_submissions(10).await.unwrap(), 0);
+        clock.advance(std::time::Duration::from_secs(1));
+        assert_eq!(service.process_pending_submissions(10).await.unwrap(), 1);
+
+        let item = mock.get_item(&created.id).await.unwrap().unwrap();
+        assert_eq!(item.blockchain_status, BlockchainStatus::Submitted);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_open_does_not_consume_retry_attempt() {
+        use crate::infra::{CircuitBreakerBlockchainClient, CircuitBreakerConfig};
+
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(CircuitBreakerBlockchainClient::new(
+            Arc::new(MockBlockchainClient::timeout_with_blockhash("hash")),
+            CircuitBreakerConfig {
+                failure_threshold: 1,
+                open_duration: std::time::Duration::from_secs(60),
+            },
+        ));
+        let service = AppService::new(item_repo, outbox_repo, bc);
+
+        for name in ["First", "Second"] {
+            let request = CreateItemRequest::new(name.to_string(), "Content".to_string());
+            service.create_and_submit_item(&request).await.unwrap();
         }
+        service.process_pending_submissions(10).await.unwrap();
+
+        // First submission times out and opens the circuit; the second is rejected
+        // without reaching the RPC and keeps its attempt budget.
+        let mut retry_counts: Vec<i32> = mock
+            .get_all_items()
+            .iter()
+            .map(|i| i.blockchain_retry_count)
+            .collect();
+        retry_counts.sort_unstable();
+        assert_eq!(retry_counts, vec![0, 1]);
+        assert!(mock.get_all_items().iter().all(|i| {
+            i.blockchain_status == BlockchainStatus::PendingSubmission
+                && i.blockchain_next_retry_at.is_some()
+        }));
     }
 
     #[tokio::test]
-    async fn test_retry_submission_failed_requeues() {
+    async fn test_submission_attempts_record_endpoint_and_outcome() {
         let mock = Arc::new(MockProvider::new());
         let (item_repo, outbox_repo) = mock_repos(&mock);
-        let bc = Arc::new(MockBlockchainClient::new());
+        let bc = Arc::new(MockBlockchainClient::with_script(vec![
+            MockStep::Fail("rpc error".into()),
+            MockStep::Succeed,
+        ]));
         let service = AppService::new(item_repo, outbox_repo, bc);
 
-        let request = CreateItemRequest::new("Retry".to_string(), "Content".to_string());
-        let created = mock.create_item(&request).await.unwrap();
-        mock.update_blockchain_status(
-            &created.id,
-            BlockchainStatus::Failed,
-            None,
-            Some("previous failure"),
-            None,
-        )
-        .await
-        .unwrap();
+        let request = CreateItemRequest::new("Attempts".to_string(), "Content".to_string());
+        let created = service.create_and_submit_item(&request).await.unwrap();
+        service.process_pending_submissions(10).await.unwrap();
+        mock.advance_clock(chrono::Duration::hours(1));
+        service.process_pending_submissions(10).await.unwrap();
 
-        let updated = service
-            .retry_blockchain_submission(&created.id)
-            .await
-            .unwrap();
+        let attempts = service.list_submission_attempts(&created.id).await.unwrap();
+        assert_eq!(attempts.len(), 2);
+        assert!(attempts[0].attempted_at <= attempts[1].attempted_at);
+        assert!(
+            attempts
+                .iter()
+                .all(|a| a.endpoint.as_deref() == Some("mock-rpc.local"))
+        );
+        assert!(!attempts[0].succeeded);
+        assert!(attempts[0].error.as_deref().unwrap().contains("rpc error"));
+        assert!(attempts[1].succeeded);
         assert_eq!(
-            updated.blockchain_status,
-            BlockchainStatus::PendingSubmission
+            attempts[1].signature,
+            mock.get_item(&created.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .blockchain_signature
         );
-        assert!(updated.blockchain_last_error.is_none());
-        assert!(updated.blockchain_next_retry_at.is_none());
-        assert_eq!(updated.blockchain_retry_count, 0);
+
+        assert!(matches!(
+            service.list_submission_attempts("missing").await,
+            Err(ItemError::NotFound(_))
+        ));
     }
 
     #[tokio::test]
-    async fn test_process_pending_submissions_batch() {
+    async fn test_chain_disabled_stores_pending_without_outbox() {
         let mock = Arc::new(MockProvider::new());
         let (item_repo, outbox_repo) = mock_repos(&mock);
-        let bc = Arc::new(MockBlockchainClient::new());
-        let service = AppService::new(item_repo, outbox_repo, bc);
+        let service = AppService::without_blockchain(item_repo, outbox_repo);
 
-        let request1 = CreateItemRequest::new("Item1".to_string(), "Content".to_string());
-        let request2 = CreateItemRequest::new("Item2".to_string(), "Content".to_string());
-        let item1 = service.create_and_submit_item(&request1).await.unwrap();
-        let item2 = service.create_and_submit_item(&request2).await.unwrap();
+        let request = CreateItemRequest::new("Preview".to_string(), "Content".to_string());
+        let item = service.create_and_submit_item(&request).await.unwrap();
+        assert_eq!(item.blockchain_status, BlockchainStatus::Pending);
+        assert!(mock.get_all_outbox_entries().is_empty());
+        assert_eq!(service.process_pending_submissions(10).await.unwrap(), 0);
 
-        let count = service.process_pending_submissions(10).await.unwrap();
-        assert_eq!(count, 2);
-
-        let updated1 = mock.get_item(&item1.id).await.unwrap().unwrap();
-        let updated2 = mock.get_item(&item2.id).await.unwrap().unwrap();
+        let result = service.retry_blockchain_submission(&item.id).await;
+        assert!(matches!(result, Err(ItemError::InvalidState(_))));
 
-        assert_eq!(updated1.blockchain_status, BlockchainStatus::Submitted);
-        assert_eq!(updated2.blockchain_status, BlockchainStatus::Submitted);
-        assert!(updated1.blockchain_signature.is_some());
-        assert!(updated2.blockchain_signature.is_some());
+        let health = service.health_check().await;
+        assert_eq!(health.blockchain, HealthStatus::Disabled);
+        assert_eq!(health.status, HealthStatus::Healthy);
     }
 
     #[tokio::test]
-    async fn test_health_check_mixed() {
+    async fn test_update_item_requires_the_current_version() {
         let mock = Arc::new(MockProvider::new());
-        let (item_repo, _outbox_repo) = mock_repos(&mock);
-        let other = Arc::new(MockProvider::new());
-        let (_, outbox_repo2) = mock_repos(&other);
-        let bc = Arc::new(MockBlockchainClient::failing("unhealthy"));
-        let service = AppService::new(item_repo, outbox_repo2, bc);
-        let health = service.health_check().await;
-
-        assert_eq!(health.status, HealthStatus::Unhealthy);
-        assert_eq!(health.database, HealthStatus::Healthy);
-        assert_eq!(health.blockchain, HealthStatus::Unhealthy);
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let service = AppService::without_blockchain(item_repo, outbox_repo);
+
+        let request = CreateItemRequest::new("Draft".to_string(), "First".to_string());
+        let item = service.create_and_submit_item(&request).await.unwrap();
+        assert_eq!(item.version, 1);
+
+        let edit = CreateItemRequest::new("Final".to_string(), "Second".to_string());
+        let updated = service.update_item(&item.id, &edit, 1).await.unwrap();
+        assert_eq!(updated.version, 2);
+        assert_eq!(updated.content, "Second");
+        assert_ne!(updated.hash, item.hash);
+
+        // A writer still holding version 1 loses
+        let stale = CreateItemRequest::new("Stale".to_string(), "Third".to_string());
+        match service.update_item(&item.id, &stale, 1).await {
+            Err(CreateItemError::Item(ItemError::Conflict {
+                expected, current, ..
+            })) => assert_eq!((expected, current), (1, 2)),
+            other => panic!("Expected a conflict, got {other:?}"),
+        }
+        let stored = service.get_item(&item.id).await.unwrap().unwrap();
+        assert_eq!(stored.name, "Final");
+
+        let invalid = CreateItemRequest::new(String::new(), "Content".to_string());
+        assert!(matches!(
+            service.update_item(&item.id, &invalid, 2).await,
+            Err(CreateItemError::Validation(_))
+        ));
+        assert!(matches!(
+            service.update_item("item_missing", &edit, 1).await,
+            Err(CreateItemError::Item(ItemError::NotFound(_)))
+        ));
     }
 
     #[tokio::test]
-    async fn test_retry_blockchain_submission_item_not_found() {
+    async fn test_delete_item_soft_deletes_and_purge_respects_retention() {
         let mock = Arc::new(MockProvider::new());
         let (item_repo, outbox_repo) = mock_repos(&mock);
-        let bc = Arc::new(MockBlockchainClient::new());
-        let service = AppService::new(item_repo, outbox_repo, bc);
+        let service = AppService::new(
+            item_repo,
+            outbox_repo,
+            Arc::new(MockBlockchainClient::new()),
+        );
 
-        let result = service.retry_blockchain_submission("nonexistent").await;
+        let request = CreateItemRequest::new("Doomed".to_string(), "Content".to_string());
+        let item = service.create_and_submit_item(&request).await.unwrap();
+
+        let deleted = service.delete_item(&item.id).await.unwrap();
+        assert!(deleted.deleted_at.is_some());
+        assert!(matches!(
+            service.delete_item(&item.id).await,
+            Err(ItemError::NotFound(_))
+        ));
+        assert!(service.get_item(&item.id).await.unwrap().is_none());
+        assert!(matches!(
+            service.retry_blockchain_submission(&item.id).await,
+            Err(ItemError::NotFound(_))
+        ));
+        assert!(
+            service
+                .list_items(10, None, &ItemListFilter::default())
+                .await
+                .unwrap()
+                .items
+                .is_empty()
+        );
+        assert_eq!(
+            service
+                .list_items(
+                    10,
+                    None,
+                    &ItemListFilter {
+                        include_deleted: true,
+                        ..ItemListFilter::default()
+                    }
+                )
+                .await
+                .unwrap()
+                .items
+                .len(),
+            1
+        );
 
-        assert!(matches!(result, Err(ItemError::NotFound(_))));
+        assert_eq!(
+            service
+                .purge_deleted_items(Duration::days(1))
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            service.purge_deleted_items(Duration::zero()).await.unwrap(),
+            1
+        );
+        assert!(mock.get_all_items().is_empty());
     }
 
     #[tokio::test]
-    async fn test_retry_blockchain_submission_failed_status() {
+    async fn test_list_items_continues_after_a_purged_cursor_item() {
         let mock = Arc::new(MockProvider::new());
         let (item_repo, outbox_repo) = mock_repos(&mock);
-        let bc = Arc::new(MockBlockchainClient::new());
-        let service = AppService::new(item_repo, outbox_repo, bc);
+        let service = AppService::without_blockchain(item_repo, outbox_repo);
+        let start = Utc::now() - Duration::hours(1);
+        let mut ids = Vec::new();
+        for n in 0..4 {
+            let request = CreateItemRequest::new(format!("Item {n}"), "Content".to_string());
+            let item = service.create_and_submit_item(&request).await.unwrap();
+            mock.edit_item(&item.id, |item| {
+                item.created_at = start + Duration::minutes(n);
+            });
+            ids.push(item.id);
+        }
+        let page_ids = |page: &PaginatedResponse<Item>| -> Vec<String> {
+            page.items.iter().map(|item| item.id.clone()).collect()
+        };
+        let filter = ItemListFilter {
+            order: SortOrder::Asc,
+            ..ItemListFilter::default()
+        };
+        let by_name = ItemListFilter {
+            sort: ItemSortField::Name,
+            ..filter.clone()
+        };
+        let newest_first = ItemListFilter::default();
+        let first = service.list_items(2, None, &filter).await.unwrap();
+        let name_first = service.list_items(2, None, &by_name).await.unwrap();
+        let desc_first = service.list_items(3, None, &newest_first).await.unwrap();
+        assert_eq!(page_ids(&first), ids[..2]);
+        assert!(!first.cursor_degraded);
+
+        // The item behind every cursor is purged
+        service.delete_item(&ids[1]).await.unwrap();
+        service.purge_deleted_items(Duration::zero()).await.unwrap();
+
+        let next = service
+            .list_items(2, first.next_cursor.as_deref(), &filter)
+            .await
+            .unwrap();
+        assert!(next.cursor_degraded);
+        assert_eq!(page_ids(&next), ids[2..]);
+        assert!(!next.has_more);
 
-        let request = CreateItemRequest::new("Failed".to_string(), "Content".to_string());
-        let created = mock.create_item(&request).await.unwrap();
-        mock.update_blockchain_status(
-            &created.id,
-            BlockchainStatus::Failed,
-            None,
-            Some("failed"),
-            None,
-        )
-        .await
-        .unwrap();
+        let next = service
+            .list_items(2, desc_first.next_cursor.as_deref(), &newest_first)
+            .await
+            .unwrap();
+        assert!(next.cursor_degraded);
+        assert_eq!(page_ids(&next), ids[..1]);
+
+        // Other orders have no recorded position to fall back to
+        assert!(matches!(
+            service
+                .list_items(1, name_first.next_cursor.as_deref(), &by_name)
+                .await,
+            Err(ItemError::InvalidCursor(_))
+        ));
+    }
 
-        let result = service.retry_blockchain_submission(&created.id).await;
-        assert!(result.is_ok());
-        let updated = result.unwrap();
+    #[tokio::test]
+    async fn test_domain_events_follow_the_item_lifecycle() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::with_script(vec![
+            MockStep::Succeed,
+            MockStep::Fail("rejected".into()),
+        ]));
+        let publisher = Arc::new(MockMessagePublisher::new());
+        let service = AppService::new(item_repo, outbox_repo, bc)
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 1,
+                ..RetryPolicy::default()
+            })
+            .with_message_publisher(Arc::clone(&publisher) as Arc<dyn MessagePublisher>);
+
+        let request = CreateItemRequest::new("Published".to_string(), "Content".to_string());
+        let item = service.create_and_submit_item(&request).await.unwrap();
+        service.process_pending_submissions(10).await.unwrap();
+        assert_eq!(service.confirm_submitted_items(10).await.unwrap(), 1);
         assert_eq!(
-            updated.blockchain_status,
-            BlockchainStatus::PendingSubmission
+            publisher.event_names(),
+            ["item.created", "item.submitted", "item.confirmed"]
+        );
+        let events = publisher.get_events();
+        assert!(events.iter().all(|e| e.item_id == item.id));
+        assert_eq!(
+            events[0].kind,
+            DomainEventKind::ItemCreated {
+                tenant_id: item.tenant_id.clone(),
+                hash: item.hash.clone(),
+            }
+        );
+
+        // The only attempt fails, so the submission is dead-lettered
+        let request = CreateItemRequest::new("Rejected".to_string(), "Other".to_string());
+        let failed = service.create_and_submit_item(&request).await.unwrap();
+        service.process_pending_submissions(10).await.unwrap();
+        let last = publisher.get_events().pop().unwrap();
+        assert_eq!(last.item_id, failed.id);
+        assert!(
+            matches!(last.kind, DomainEventKind::ItemFailed { ref error } if error.contains("rejected"))
         );
     }
 
     #[tokio::test]
-    async fn test_process_pending_submissions_empty() {
+    async fn test_publish_failure_does_not_fail_the_operation() {
         let mock = Arc::new(MockProvider::new());
         let (item_repo, outbox_repo) = mock_repos(&mock);
-        let bc = Arc::new(MockBlockchainClient::new());
-        let service = AppService::new(item_repo, outbox_repo, bc);
+        let publisher = Arc::new(MockMessagePublisher::new());
+        publisher.set_failing(true);
+        let service = AppService::new(
+            item_repo,
+            outbox_repo,
+            Arc::new(MockBlockchainClient::new()),
+        )
+        .with_message_publisher(Arc::clone(&publisher) as Arc<dyn MessagePublisher>);
 
-        let count = service.process_pending_submissions(10).await.unwrap();
-        assert_eq!(count, 0);
+        let request = CreateItemRequest::new("Item".to_string(), "Content".to_string());
+        assert!(service.create_and_submit_item(&request).await.is_ok());
+        assert!(publisher.get_events().is_empty());
+        assert_eq!(mock.get_all_items().len(), 1);
+    }
+
+    async fn read_range(service: &AppService, id: &str, range: Option<RangeSpec>) -> String {
+        let content = match service.get_item_content(id, range).await.unwrap().body {
+            ContentBody::Inline(content) => content.to_vec(),
+            ContentBody::Stream(chunks) => {
+                let chunks: Vec<Bytes> = chunks.try_collect().await.unwrap();
+                chunks.concat()
+            }
+        };
+        String::from_utf8(content).unwrap()
+    }
+
+    async fn read_content(service: &AppService, id: &str) -> String {
+        read_range(service, id, None).await
     }
 
     #[tokio::test]
-    async fn test_get_item_success() {
+    async fn test_large_content_is_kept_in_the_object_store() {
         let mock = Arc::new(MockProvider::new());
         let (item_repo, outbox_repo) = mock_repos(&mock);
-        let bc = Arc::new(MockBlockchainClient::new());
-        let service = AppService::new(item_repo, outbox_repo, bc);
-
-        let request = CreateItemRequest::new("Test Item".to_string(), "Content".to_string());
-        let created = service.create_and_submit_item(&request).await.unwrap();
+        let store = Arc::new(MockObjectStore::new());
+        let service = AppService::new(
+            item_repo,
+            outbox_repo,
+            Arc::new(MockBlockchainClient::new()),
+        )
+        .with_object_store(Arc::clone(&store) as Arc<dyn ObjectStore>, 16);
+
+        let small = CreateItemRequest::new("Small".to_string(), "Short content".to_string());
+        let small = service.create_and_submit_item(&small).await.unwrap();
+        assert_eq!(small.content, "Short content");
+        assert!(small.content_key.is_none());
+        assert_eq!(read_content(&service, &small.id).await, "Short content");
+
+        let content = "x".repeat(100);
+        let request = CreateItemRequest::new("Large".to_string(), content.clone());
+        let item = service.create_and_submit_item(&request).await.unwrap();
+        let key = ContentHasher::object_key(&content);
+        assert_eq!(item.content, "");
+        assert_eq!(item.content_key.as_deref(), Some(key.as_str()));
+        assert_eq!(store.get_object(&key).unwrap(), content.as_bytes());
+        // The hashes still cover the full content
+        assert_eq!(item.hash, ContentHasher::hash_request(&request));
+        let entry = mock
+            .get_all_outbox_entries()
+            .into_iter()
+            .find(|e| e.aggregate_id == item.id)
+            .unwrap();
+        assert_eq!(
+            entry.payload,
+            build_solana_outbox_payload_from_request(&item.id, &request)
+        );
+        assert_eq!(read_content(&service, &item.id).await, content);
+
+        // Exports carry the full content
+        let exported: Vec<Item> = service.export_items().try_collect().await.unwrap();
+        assert_eq!(exported.len(), 2);
+        assert!(exported.iter().any(|i| i.content == content));
+
+        // Equal content shares the object
+        let update = CreateItemRequest::new("Large, renamed".to_string(), content.clone());
+        let updated = service.update_item(&item.id, &update, 1).await.unwrap();
+        assert_eq!(updated.content_key.as_deref(), Some(key.as_str()));
+        assert_eq!(store.keys(), [key]);
+    }
 
-        let result = service.get_item(&created.id).await.unwrap();
-        assert!(result.is_some());
-        assert_eq!(result.unwrap().id, created.id);
+    #[tokio::test]
+    async fn test_item_content_ranges() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let store = Arc::new(MockObjectStore::new());
+        let service = AppService::without_blockchain(item_repo, outbox_repo)
+            .with_object_store(Arc::clone(&store) as Arc<dyn ObjectStore>, 16);
+
+        let mut request = CreateItemRequest::new("Small".to_string(), "0123456789".to_string());
+        request.content_type = Some("text/csv".to_string());
+        let small = service.create_and_submit_item(&request).await.unwrap();
+        let large = CreateItemRequest::new("Large".to_string(), "abcdefghij".repeat(10));
+        let large = service.create_and_submit_item(&large).await.unwrap();
+        assert!(large.content_key.is_some());
+
+        let content = service.get_item_content(&small.id, None).await.unwrap();
+        assert_eq!(content.content_type, "text/csv");
+        assert_eq!(content.length, Some(10));
+        assert_eq!(content.range, None);
+
+        for id in [&small.id, &large.id] {
+            let range = Some(RangeSpec::FromTo(2, 4));
+            assert_eq!(read_range(&service, id, range).await.len(), 3);
+        }
+        assert_eq!(
+            read_range(&service, &small.id, Some(RangeSpec::Suffix(3))).await,
+            "789"
+        );
+        assert_eq!(
+            read_range(&service, &large.id, Some(RangeSpec::From(95))).await,
+            "fghij"
+        );
+        let content = service
+            .get_item_content(&large.id, Some(RangeSpec::From(95)))
+            .await
+            .unwrap();
+        assert_eq!(content.length, Some(100));
+        assert_eq!(content.range, Some(95..100));
+
+        let result = service
+            .get_item_content(&small.id, Some(RangeSpec::From(10)))
+            .await;
+        assert!(matches!(
+            result,
+            Err(ItemError::RangeNotSatisfiable { length: 10 })
+        ));
     }
 
     #[tokio::test]
-    async fn test_list_items_success() {
+    async fn test_item_writes_are_audited() {
         let mock = Arc::new(MockProvider::new());
         let (item_repo, outbox_repo) = mock_repos(&mock);
         let bc = Arc::new(MockBlockchainClient::new());
-        let service = AppService::new(item_repo, outbox_repo, bc);
+        let service = AppService::new(item_repo, outbox_repo, bc)
+            .with_audit_logger(Arc::clone(&mock) as Arc<dyn AuditLogger>);
+        let actor = AuditActor {
+            principal: "key_ops".to_string(),
+            request_id: Some("req-1".to_string()),
+        };
 
-        let result = service.list_items(10, None).await.unwrap();
-        assert!(result.items.is_empty());
-        assert!(!result.has_more);
+        let request = CreateItemRequest::new("Audited".to_string(), "content".to_string());
+        let item = AuditActor::scope(actor.clone(), service.create_and_submit_item(&request))
+            .await
+            .unwrap();
+        let update = CreateItemRequest::new("Renamed".to_string(), "content".to_string());
+        AuditActor::scope(actor, service.update_item(&item.id, &update, 1))
+            .await
+            .unwrap();
+        // Outside a request the actor is the system
+        service.delete_item(&item.id).await.unwrap();
+
+        let entries = mock.get_audit_entries();
+        let actions: Vec<_> = entries.iter().map(|e| e.action.as_str()).collect();
+        assert_eq!(actions, ["item.create", "item.update", "item.delete"]);
+        assert!(
+            entries
+                .iter()
+                .all(|e| e.resource_id.as_deref() == Some(item.id.as_str()))
+        );
+        assert_eq!(entries[0].principal, "key_ops");
+        assert_eq!(entries[0].request_id.as_deref(), Some("req-1"));
+        assert_eq!(entries[0].diff.as_ref().unwrap()["name"]["to"], "Audited");
+        let diff = entries[1].diff.as_ref().unwrap();
+        assert_eq!(diff["name"], json!({ "from": "Audited", "to": "Renamed" }));
+        assert_eq!(diff["version"], json!({ "from": 1, "to": 2 }));
+        assert!(diff.get("content_type").is_none());
+        assert_eq!(entries[2].principal, AuditActor::SYSTEM);
+        assert!(entries[2].diff.as_ref().unwrap()["deleted_at"]["to"].is_string());
+
+        let filter = AuditFilter {
+            action: Some("item.update".to_string()),
+            ..AuditFilter::default()
+        };
+        let page = service.list_audit_entries(10, None, &filter).await.unwrap();
+        assert_eq!(page.items.len(), 1);
+        let page = service
+            .list_audit_entries(2, None, &AuditFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(page.items[0].action, "item.delete");
+        let next = service
+            .list_audit_entries(2, page.next_cursor.as_deref(), &AuditFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(next.items.len(), 1);
+        assert_eq!(next.items[0].action, "item.create");
+        assert!(next.next_cursor.is_none());
+
+        // A failed audit write does not fail the change
+        mock.fail_on("record_audit_entry");
+        let request = CreateItemRequest::new("Unaudited".to_string(), "other".to_string());
+        assert!(service.create_and_submit_item(&request).await.is_ok());
+        assert_eq!(mock.get_audit_entries().len(), 3);
     }
 
     #[tokio::test]
-    async fn test_create_item_blockchain_success() {
+    async fn test_object_store_failure_fails_the_create() {
         let mock = Arc::new(MockProvider::new());
         let (item_repo, outbox_repo) = mock_repos(&mock);
-        let bc = Arc::new(MockBlockchainClient::new());
-        let service = AppService::new(item_repo, outbox_repo, bc);
-        let request = CreateItemRequest {
-            name: "Success Item".to_string(),
-            description: Some("Description".to_string()),
-            content: "Content".to_string(),
-            metadata: None,
-        };
+        let store = Arc::new(MockObjectStore::new());
+        store.set_failing(true);
+        let service = AppService::without_blockchain(item_repo, outbox_repo)
+            .with_object_store(Arc::clone(&store) as Arc<dyn ObjectStore>, 16);
 
+        let request = CreateItemRequest::new("Large".to_string(), "x".repeat(100));
         let result = service.create_and_submit_item(&request).await;
-        assert!(result.is_ok());
-
-        let item = result.unwrap();
-        assert_eq!(item.blockchain_status, BlockchainStatus::PendingSubmission);
-        assert!(item.blockchain_signature.is_none());
+        assert!(matches!(
+            result,
+            Err(CreateItemError::Item(ItemError::RepositoryFailure))
+        ));
+        assert!(mock.get_all_items().is_empty());
+
+        // Small content never touches the store
+        let request = CreateItemRequest::new("Small".to_string(), "x".to_string());
+        let item = service.create_and_submit_item(&request).await.unwrap();
+        assert_eq!(read_content(&service, &item.id).await, "x");
     }
 
     #[tokio::test]
-    async fn test_health_check_both_healthy() {
+    async fn test_telemetry_reports_creation_failure_and_confirmation() {
         let mock = Arc::new(MockProvider::new());
         let (item_repo, outbox_repo) = mock_repos(&mock);
-        let bc = Arc::new(MockBlockchainClient::new());
-        let service = AppService::new(item_repo, outbox_repo, bc);
-        let health = service.health_check().await;
+        let bc = Arc::new(MockBlockchainClient::with_script(vec![
+            MockStep::Fail("rejected".into()),
+            MockStep::Succeed,
+        ]));
+        let telemetry = Arc::new(MockTelemetrySink::new());
+        let service = AppService::new(item_repo, outbox_repo, bc)
+            .with_retry_policy(RetryPolicy {
+                base_delay: std::time::Duration::ZERO,
+                ..RetryPolicy::default()
+            })
+            .with_telemetry(Arc::clone(&telemetry) as Arc<dyn TelemetrySink>);
+
+        let request = CreateItemRequest::new("Tracked".to_string(), "Content".to_string());
+        service.create_and_submit_item(&request).await.unwrap();
+        service.process_pending_submissions(10).await.unwrap();
+        service.process_pending_submissions(10).await.unwrap();
+        assert_eq!(service.confirm_submitted_items(10).await.unwrap(), 1);
 
-        assert_eq!(health.status, HealthStatus::Healthy);
-        assert_eq!(health.database, HealthStatus::Healthy);
-        assert_eq!(health.blockchain, HealthStatus::Healthy);
+        let records = telemetry.get_records();
+        assert_eq!(
+            records[..2],
+            [
+                TelemetryRecord::ItemCreated(crate::domain::DEFAULT_TENANT.to_string()),
+                TelemetryRecord::SubmissionFailed("submission_failed".to_string()),
+            ]
+        );
+        assert!(matches!(records[2..], [TelemetryRecord::ItemConfirmed(_)]));
     }
 
     #[tokio::test]
-    async fn test_process_pending_submissions_failure_updates_retry() {
+    async fn test_noop_blockchain_confirms_items() {
         let mock = Arc::new(MockProvider::new());
         let (item_repo, outbox_repo) = mock_repos(&mock);
-        let bc = Arc::new(MockBlockchainClient::failing("rpc error"));
-        let service = AppService::new(item_repo, outbox_repo, bc);
-
-        let request = CreateItemRequest::new("Retry Item".to_string(), "Content".to_string());
-        let created = service.create_and_submit_item(&request).await.unwrap();
+        let service = AppService::new(
+            item_repo,
+            outbox_repo,
+            Arc::new(crate::infra::NoopBlockchainClient::new()),
+        );
 
-        let count = service.process_pending_submissions(10).await.unwrap();
-        assert_eq!(count, 1);
+        let request = CreateItemRequest::new("Demo".to_string(), "Content".to_string());
+        let item = service.create_and_submit_item(&request).await.unwrap();
+        service.process_pending_submissions(10).await.unwrap();
 
-        let updated = mock.get_item(&created.id).await.unwrap().unwrap();
-        assert_eq!(
-            updated.blockchain_status,
-            BlockchainStatus::PendingSubmission
-        );
-        assert!(updated.blockchain_last_error.is_some());
-        assert!(updated.blockchain_next_retry_at.is_some());
-        assert_eq!(updated.blockchain_retry_count, 1);
-        assert!(updated.blockchain_next_retry_at.unwrap() > Utc::now());
+        let updated = service.get_item(&item.id).await.unwrap().unwrap();
+        assert_eq!(updated.blockchain_status, BlockchainStatus::Submitted);
+        assert!(updated.blockchain_signature.unwrap().starts_with("noop_"));
     }
 
     #[tokio::test]
@@ -621,4 +3319,130 @@ mod service_tests {
             "Blockhash must be persisted after timeout to prevent double spend"
         );
     }
+
+    #[tokio::test]
+    async fn test_retry_policy_sets_backoff_and_attempt_limit() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::failing("rpc down"));
+        let service = AppService::new(item_repo, outbox_repo, bc).with_retry_policy(RetryPolicy {
+            strategy: crate::app::BackoffStrategy::Fixed,
+            max_attempts: 2,
+            base_delay: std::time::Duration::from_secs(60),
+            max_delay: std::time::Duration::from_secs(60),
+        });
+        let request = CreateItemRequest::new("Flaky".to_string(), "Content".to_string());
+        let created = service.create_and_submit_item(&request).await.unwrap();
+
+        service.process_pending_submissions(10).await.unwrap();
+        let entry = mock.get_all_outbox_entries().pop().unwrap();
+        assert_eq!(entry.retry_count, 1);
+        let item = mock.get_item(&created.id).await.unwrap().unwrap();
+        let wait = item.blockchain_next_retry_at.unwrap() - Utc::now();
+        assert!(wait > Duration::seconds(55) && wait <= Duration::seconds(60));
+        assert!(
+            service
+                .list_failed_submissions(50, false)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // Second failure uses up the policy
+        mock.fail_solana_outbox(
+            &entry.id,
+            &entry.aggregate_id,
+            1,
+            OutboxStatus::Pending,
+            BlockchainStatus::PendingSubmission,
+            "rpc down",
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        service.process_pending_submissions(10).await.unwrap();
+        let failed = service.list_failed_submissions(50, false).await.unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].retry_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_submission_is_dead_lettered_and_requeued() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::timeout_with_blockhash("sticky_hash"));
+        let service = AppService::new(item_repo, outbox_repo, bc);
+
+        let request = CreateItemRequest::new("Doomed Item".to_string(), "Content".to_string());
+        let created = service.create_and_submit_item(&request).await.unwrap();
+        let entry = mock.get_all_outbox_entries().pop().unwrap();
+        // One attempt left
+        mock.fail_solana_outbox(
+            &entry.id,
+            &created.id,
+            RetryPolicy::default().max_attempts - 1,
+            OutboxStatus::Pending,
+            BlockchainStatus::PendingSubmission,
+            "rpc error",
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        service.process_pending_submissions(10).await.unwrap();
+
+        let failed = service.list_failed_submissions(50, false).await.unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].item_id, created.id);
+        assert_eq!(failed[0].outbox_id, entry.id);
+        assert_eq!(failed[0].hash, entry.payload.hash);
+        assert_eq!(failed[0].retry_count, RetryPolicy::default().max_attempts);
+        assert!(failed[0].last_error.starts_with("Timeout"));
+        let item = mock.get_item(&created.id).await.unwrap().unwrap();
+        assert_eq!(item.blockchain_status, BlockchainStatus::Failed);
+
+        let requeued = service
+            .requeue_failed_submission(&failed[0].id)
+            .await
+            .unwrap();
+        assert_eq!(
+            requeued.blockchain_status,
+            BlockchainStatus::PendingSubmission
+        );
+        assert_eq!(requeued.blockchain_retry_count, 0);
+        assert!(requeued.blockchain_last_error.is_none());
+
+        // The new outbox entry keeps the sticky blockhash of the last attempt
+        let pending: Vec<_> = mock
+            .get_all_outbox_entries()
+            .into_iter()
+            .filter(|e| e.status == OutboxStatus::Pending)
+            .collect();
+        assert_eq!(pending.len(), 1);
+        assert_ne!(pending[0].id, entry.id);
+        assert_eq!(pending[0].attempt_blockhash.as_deref(), Some("sticky_hash"));
+
+        assert!(
+            service
+                .list_failed_submissions(50, false)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            service.list_failed_submissions(50, true).await.unwrap()[0]
+                .requeued_at
+                .is_some()
+        );
+        assert!(matches!(
+            service.requeue_failed_submission(&failed[0].id).await,
+            Err(ItemError::InvalidState(_))
+        ));
+        assert!(matches!(
+            service.requeue_failed_submission("dlq_missing").await,
+            Err(ItemError::NotFound(_))
+        ));
+    }
 }
@@ -0,0 +1,541 @@
+//! Coordinated application shutdown.
+//!
+//! [`Shutdown`] stops the application in [`ShutdownPhase`] order: the HTTP server stops
+//! accepting and drains in-flight requests, background workers finish their current unit
+//! of work (e.g. an outbox batch), event dispatch flushes what it holds, and finally
+//! resources such as the database pool close. Each phase tells only its own tasks to stop,
+//! waits for them and its hooks until the phase deadline, and aborts whatever is still
+//! running before the next phase starts.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// Default time allowed for each phase after the signal
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+type CloseHook = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Stage of shutdown, in the order they run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShutdownPhase {
+    /// Stop accepting HTTP connections and finish in-flight requests
+    Http,
+    /// Background workers finish their current batch
+    Workers,
+    /// Event and webhook dispatch deliver what they hold and save their cursors
+    Flush,
+    /// Connection pools and other resources close
+    Close,
+}
+
+impl ShutdownPhase {
+    /// Every phase, in shutdown order
+    pub const ALL: [Self; 4] = [Self::Http, Self::Workers, Self::Flush, Self::Close];
+
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Http => "http",
+            Self::Workers => "workers",
+            Self::Flush => "flush",
+            Self::Close => "close",
+        }
+    }
+}
+
+impl fmt::Display for ShutdownPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ShutdownPhase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|phase| phase.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("Unknown shutdown phase: {}", s))
+    }
+}
+
+/// Time allowed for each shutdown phase
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShutdownConfig {
+    /// Deadline of phases without an override (`SHUTDOWN_TIMEOUT_SECS`)
+    pub timeout: Duration,
+    /// Per-phase deadlines (`SHUTDOWN_PHASE_TIMEOUTS`, e.g. `http=10,workers=20`)
+    pub phase_timeouts: HashMap<ShutdownPhase, Duration>,
+}
+
+impl ShutdownConfig {
+    /// Read `SHUTDOWN_TIMEOUT_SECS` and `SHUTDOWN_PHASE_TIMEOUTS`; malformed entries are
+    /// ignored
+    #[must_use]
+    pub fn from_env() -> Self {
+        let timeout = std::env::var("SHUTDOWN_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT);
+        let phase_timeouts = std::env::var("SHUTDOWN_PHASE_TIMEOUTS")
+            .map(|v| parse_phase_timeouts(&v))
+            .unwrap_or_default();
+        Self {
+            timeout,
+            phase_timeouts,
+        }
+    }
+
+    /// Deadline of `phase`, measured from the moment it starts
+    #[must_use]
+    pub fn phase_timeout(&self, phase: ShutdownPhase) -> Duration {
+        self.phase_timeouts
+            .get(&phase)
+            .copied()
+            .unwrap_or(self.timeout)
+    }
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            phase_timeouts: HashMap::new(),
+        }
+    }
+}
+
+/// `phase=secs` pairs separated by commas
+fn parse_phase_timeouts(value: &str) -> HashMap<ShutdownPhase, Duration> {
+    value
+        .split(',')
+        .filter_map(|pair| {
+            let (phase, secs) = pair.split_once('=')?;
+            Some((
+                phase.parse().ok()?,
+                Duration::from_secs(secs.trim().parse().ok()?),
+            ))
+        })
+        .collect()
+}
+
+/// A background task and the sender that tells it to stop
+struct ShutdownTask {
+    phase: ShutdownPhase,
+    name: String,
+    handle: JoinHandle<()>,
+    stop: watch::Sender<bool>,
+}
+
+/// What finished in time and what had to be abandoned
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Tasks and close hooks that completed before their phase deadline
+    pub completed: Vec<String>,
+    /// Tasks aborted (or close hooks dropped) at their phase deadline
+    pub aborted: Vec<String>,
+}
+
+/// Shutdown coordinator shared by `main`, the HTTP server and the workers
+pub struct Shutdown {
+    signal: watch::Sender<bool>,
+    tasks: Mutex<Vec<ShutdownTask>>,
+    close_hooks: Mutex<Vec<(ShutdownPhase, String, CloseHook)>>,
+    config: ShutdownConfig,
+}
+
+impl Shutdown {
+    /// Coordinator giving every phase `timeout`
+    #[must_use]
+    pub fn new(timeout: Duration) -> Self {
+        Self::with_config(ShutdownConfig {
+            timeout,
+            phase_timeouts: HashMap::new(),
+        })
+    }
+
+    /// Coordinator with per-phase deadlines
+    #[must_use]
+    pub fn with_config(config: ShutdownConfig) -> Self {
+        Self {
+            signal: watch::channel(false).0,
+            tasks: Mutex::new(Vec::new()),
+            close_hooks: Mutex::new(Vec::new()),
+            config,
+        }
+    }
+
+    /// Track a background worker (stopped in [`ShutdownPhase::Workers`])
+    pub fn register(&self, name: impl Into<String>, task: (JoinHandle<()>, watch::Sender<bool>)) {
+        self.register_in(ShutdownPhase::Workers, name, task);
+    }
+
+    /// Track a task spawned with a stop sender (the shape `spawn_worker` returns); it is
+    /// told to stop when `phase` starts
+    pub fn register_in(
+        &self,
+        phase: ShutdownPhase,
+        name: impl Into<String>,
+        (handle, stop): (JoinHandle<()>, watch::Sender<bool>),
+    ) {
+        let task = ShutdownTask {
+            phase,
+            name: name.into(),
+            handle,
+            stop,
+        };
+        if self.is_triggered() {
+            let _ = task.stop.send(true);
+        }
+        self.tasks.lock().unwrap().push(task);
+    }
+
+    /// Run `hook` in [`ShutdownPhase::Close`], once every task has stopped (e.g. closing a
+    /// connection pool)
+    pub fn on_close<F>(&self, name: impl Into<String>, hook: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.on_phase(ShutdownPhase::Close, name, hook);
+    }
+
+    /// Run `hook` in `phase`, after the phase's tasks have stopped
+    pub fn on_phase<F>(&self, phase: ShutdownPhase, name: impl Into<String>, hook: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.close_hooks
+            .lock()
+            .unwrap()
+            .push((phase, name.into(), Box::pin(hook)));
+    }
+
+    /// Whether shutdown has been requested
+    #[must_use]
+    pub fn is_triggered(&self) -> bool {
+        *self.signal.borrow()
+    }
+
+    /// Request shutdown. Tasks keep running until [`Shutdown::shutdown`] reaches their
+    /// phase; only those registered from now on are stopped right away.
+    pub fn trigger(&self) {
+        if !self.signal.send_replace(true) {
+            info!("Shutdown requested");
+        }
+    }
+
+    /// Resolves once shutdown has been requested
+    pub fn wait(&self) -> impl Future<Output = ()> + Send + 'static {
+        let mut signal = self.signal.subscribe();
+        async move {
+            let _ = signal.wait_for(|triggered| *triggered).await;
+        }
+    }
+
+    /// Trigger shutdown and run every phase in order: stop its tasks, wait for them until
+    /// the phase deadline, abort stragglers, then run its hooks with whatever time is left
+    pub async fn shutdown(&self) -> ShutdownReport {
+        self.trigger();
+        let mut report = ShutdownReport::default();
+        for phase in ShutdownPhase::ALL {
+            self.run_phase(phase, &mut report).await;
+        }
+        report
+    }
+
+    async fn run_phase(&self, phase: ShutdownPhase, report: &mut ShutdownReport) {
+        let (tasks, rest): (Vec<_>, Vec<_>) = std::mem::take(&mut *self.tasks.lock().unwrap())
+            .into_iter()
+            .partition(|task| task.phase == phase);
+        self.tasks.lock().unwrap().extend(rest);
+        let (hooks, rest): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut *self.close_hooks.lock().unwrap())
+                .into_iter()
+                .partition(|(hook_phase, _, _)| *hook_phase == phase);
+        self.close_hooks.lock().unwrap().extend(rest);
+        if tasks.is_empty() && hooks.is_empty() {
+            return;
+        }
+
+        let timeout = self.config.phase_timeout(phase);
+        let started = tokio::time::Instant::now();
+        let deadline = started + timeout;
+        info!(
+            phase = %phase,
+            tasks = tasks.len(),
+            hooks = hooks.len(),
+            timeout_secs = timeout.as_secs_f64(),
+            "Shutdown phase started"
+        );
+        for task in &tasks {
+            let _ = task.stop.send(true);
+        }
+        let aborted_before = report.aborted.len();
+
+        for mut task in tasks {
+            match tokio::time::timeout_at(deadline, &mut task.handle).await {
+                Ok(_) => {
+                    info!(phase = %phase, task = %task.name, "Task stopped");
+                    report.completed.push(task.name);
+                }
+                Err(_) => {
+                    warn!(phase = %phase, task = %task.name, "Task did not stop before the deadline; aborting");
+                    task.handle.abort();
+                    report.aborted.push(task.name);
+                }
+            }
+        }
+
+        for (_, name, hook) in hooks {
+            if tokio::time::timeout_at(deadline, hook).await.is_ok() {
+                info!(phase = %phase, hook = %name, "Closed");
+                report.completed.push(name);
+            } else {
+                warn!(phase = %phase, hook = %name, "Close hook did not finish before the deadline");
+                report.aborted.push(name);
+            }
+        }
+
+        info!(
+            phase = %phase,
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            aborted = report.aborted.len() - aborted_before,
+            "Shutdown phase finished"
+        );
+    }
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::with_config(ShutdownConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{
+        AppService, PurgeConfig, WorkerConfig, WorkerMonitor, spawn_purge_worker, spawn_worker,
+    };
+    use crate::test_utils::{MockBlockchainClient, MockProvider, mock_repos};
+    use std::sync::Arc;
+
+    fn service() -> Arc<AppService> {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        Arc::new(AppService::new(
+            item_repo,
+            outbox_repo,
+            Arc::new(MockBlockchainClient::new()),
+        ))
+    }
+
+    /// Task that takes `linger` to finish after being told to stop
+    fn lingering_task(linger: Duration) -> (JoinHandle<()>, watch::Sender<bool>) {
+        let (stop, mut stop_rx) = watch::channel(false);
+        let handle = tokio::spawn(async move {
+            let _ = stop_rx.wait_for(|stop| *stop).await;
+            tokio::time::sleep(linger).await;
+        });
+        (handle, stop)
+    }
+
+    #[tokio::test]
+    async fn test_stops_workers_then_runs_close_hooks() {
+        let shutdown = Shutdown::new(Duration::from_secs(5));
+        let service = service();
+        let monitor = Arc::new(WorkerMonitor::new(true));
+        shutdown.register(
+            "retry_worker",
+            spawn_worker(
+                Arc::clone(&service),
+                WorkerConfig::default(),
+                Arc::clone(&monitor),
+            ),
+        );
+        shutdown.register(
+            "purge_worker",
+            spawn_purge_worker(service, PurgeConfig::default()),
+        );
+        let closed = Arc::new(Mutex::new(false));
+        let flag = Arc::clone(&closed);
+        shutdown.on_close("database", async move {
+            *flag.lock().unwrap() = true;
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(monitor.status().leader);
+
+        let report = shutdown.shutdown().await;
+
+        assert_eq!(
+            report.completed,
+            vec!["retry_worker", "purge_worker", "database"]
+        );
+        assert!(report.aborted.is_empty());
+        assert!(*closed.lock().unwrap());
+        assert!(!monitor.status().leader);
+    }
+
+    #[tokio::test]
+    async fn test_waits_for_in_flight_work_within_deadline() {
+        let shutdown = Shutdown::new(Duration::from_secs(2));
+        shutdown.register("batch", lingering_task(Duration::from_millis(100)));
+
+        let report = shutdown.shutdown().await;
+
+        assert_eq!(report.completed, vec!["batch"]);
+        assert!(report.aborted.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_aborts_tasks_past_deadline() {
+        let shutdown = Shutdown::new(Duration::from_millis(50));
+        shutdown.register("stuck", lingering_task(Duration::from_secs(60)));
+        shutdown.on_close("slow_pool", tokio::time::sleep(Duration::from_secs(60)));
+
+        let started = std::time::Instant::now();
+        let report = shutdown.shutdown().await;
+
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(report.completed.is_empty());
+        assert_eq!(report.aborted, vec!["stuck", "slow_pool"]);
+    }
+
+    /// Task that logs when it is told to stop and when it finishes `linger` later
+    fn logged_task(
+        name: &'static str,
+        linger: Duration,
+        log: &Arc<Mutex<Vec<String>>>,
+    ) -> (JoinHandle<()>, watch::Sender<bool>) {
+        let (stop, mut stop_rx) = watch::channel(false);
+        let log = Arc::clone(log);
+        let handle = tokio::spawn(async move {
+            let _ = stop_rx.wait_for(|stop| *stop).await;
+            log.lock().unwrap().push(format!("{name} stopping"));
+            tokio::time::sleep(linger).await;
+            log.lock().unwrap().push(format!("{name} stopped"));
+        });
+        (handle, stop)
+    }
+
+    #[tokio::test]
+    async fn test_phases_run_in_order() {
+        let shutdown = Shutdown::new(Duration::from_secs(5));
+        let log = Arc::new(Mutex::new(Vec::new()));
+        // Registered out of order on purpose
+        shutdown.on_close("pool", {
+            let log = Arc::clone(&log);
+            async move { log.lock().unwrap().push("pool closed".to_string()) }
+        });
+        shutdown.register_in(
+            ShutdownPhase::Flush,
+            "dispatcher",
+            logged_task("dispatcher", Duration::ZERO, &log),
+        );
+        shutdown.register(
+            "worker",
+            logged_task("worker", Duration::from_millis(20), &log),
+        );
+        shutdown.register_in(
+            ShutdownPhase::Http,
+            "http",
+            logged_task("http", Duration::from_millis(20), &log),
+        );
+
+        shutdown.trigger();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(
+            log.lock().unwrap().is_empty(),
+            "trigger alone stops nothing"
+        );
+
+        let report = shutdown.shutdown().await;
+        assert_eq!(
+            report.completed,
+            vec!["http", "worker", "dispatcher", "pool"]
+        );
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "http stopping",
+                "http stopped",
+                "worker stopping",
+                "worker stopped",
+                "dispatcher stopping",
+                "dispatcher stopped",
+                "pool closed",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_each_phase_has_its_own_deadline() {
+        let shutdown = Shutdown::with_config(ShutdownConfig {
+            timeout: Duration::from_secs(5),
+            phase_timeouts: HashMap::from([(ShutdownPhase::Http, Duration::from_millis(30))]),
+        });
+        shutdown.register_in(
+            ShutdownPhase::Http,
+            "stuck_http",
+            lingering_task(Duration::from_secs(60)),
+        );
+        // Longer than the HTTP deadline, well within its own
+        shutdown.register("worker", lingering_task(Duration::from_millis(100)));
+
+        let report = shutdown.shutdown().await;
+
+        assert_eq!(report.aborted, vec!["stuck_http"]);
+        assert_eq!(report.completed, vec!["worker"]);
+    }
+
+    #[test]
+    fn test_parse_phase_timeouts() {
+        let timeouts = parse_phase_timeouts("http=10, Workers=20,flush=x,bogus=5");
+        assert_eq!(
+            timeouts,
+            HashMap::from([
+                (ShutdownPhase::Http, Duration::from_secs(10)),
+                (ShutdownPhase::Workers, Duration::from_secs(20)),
+            ])
+        );
+        let config = ShutdownConfig {
+            phase_timeouts: timeouts,
+            ..ShutdownConfig::default()
+        };
+        assert_eq!(
+            config.phase_timeout(ShutdownPhase::Close),
+            DEFAULT_SHUTDOWN_TIMEOUT
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_resolves_on_trigger() {
+        let shutdown = Arc::new(Shutdown::default());
+        let waiter = tokio::spawn(shutdown.wait());
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!waiter.is_finished());
+
+        shutdown.trigger();
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("wait() should resolve after trigger")
+            .unwrap();
+        assert!(shutdown.is_triggered());
+
+        // Tasks registered after the signal are stopped immediately
+        let (handle, stop) = lingering_task(Duration::ZERO);
+        shutdown.register("late", (handle, stop));
+        assert_eq!(shutdown.shutdown().await.completed, vec!["late"]);
+    }
+}
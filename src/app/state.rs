@@ -1,13 +1,29 @@
 //! Application state management.
 
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 use secrecy::SecretString;
+use tracing::warn;
 
-use crate::domain::{BlockchainClient, ItemRepository, OutboxRepository};
+use crate::domain::{
+    ApiKeyStore, AuditLogger, BlockchainClient, Clock, EventLog, ItemRepository, JobStore,
+    MessagePublisher, ObjectStore, OutboxRepository, RequestJournal, SchemaStatus, SpendLedger,
+    TelemetrySink, UsageLedger, WebhookDeliveryLog,
+};
 use crate::infra::PrometheusHandle;
 
-use super::service::AppService;
+use super::abuse::AbuseGuard;
+use super::auth_policy::AuthPolicy;
+use super::blocklist::IpBlocklist;
+use super::body_limits::BodyLimits;
+use super::cors::CorsConfig;
+use super::cursor::CursorCodec;
+use super::issuer_keys::IssuerKeyRegistry;
+use super::retry::RetryPolicy;
+use super::service::{AppService, SubmissionBudget};
+use super::worker::WorkerMonitor;
 
 /// Shared application state
 #[derive(Clone)]
@@ -15,14 +31,52 @@ pub struct AppState {
     pub service: Arc<AppService>,
     pub item_repo: Arc<dyn ItemRepository>,
     pub outbox_repo: Arc<dyn OutboxRepository>,
-    pub blockchain_client: Arc<dyn BlockchainClient>,
-    /// API key for authenticating write requests (POST /items, POST /items/{id}/retry).
+    /// None when blockchain submission is disabled (`CHAIN_DISABLED=true`).
+    pub blockchain_client: Option<Arc<dyn BlockchainClient>>,
+    /// Bootstrap API key (all scopes) for write and admin requests.
     /// Used by auth middleware for constant-time comparison.
     pub api_auth_key: SecretString,
+    /// Separate admin token (`ADMIN_AUTH_KEY`). When set it is the only static key with the
+    /// admin scope: the bootstrap key keeps its other scopes but loses access to `/admin`.
+    pub admin_auth_key: Option<SecretString>,
+    /// Managed API keys with per-key scopes (None: only the bootstrap key is accepted).
+    pub api_key_store: Option<Arc<dyn ApiKeyStore>>,
+    /// Requests counted per key for quotas and `GET /admin/usage` (None: nothing is counted).
+    pub usage_ledger: Option<Arc<dyn UsageLedger>>,
+    /// Status records of background jobs (None: endpoints that start jobs return 503).
+    pub job_store: Option<Arc<dyn JobStore>>,
+    /// Journal for `Idempotency-Key` replays (None: the header is ignored).
+    pub request_journal: Option<Arc<dyn RequestJournal>>,
     /// Prometheus handle for GET /metrics (None when metrics are disabled, e.g. in tests).
     pub metrics_handle: Option<Arc<PrometheusHandle>>,
+    /// IP deny-list checked before auth and rate limiting (empty by default).
+    pub blocklist: Arc<IpBlocklist>,
+    /// Temporary bans of sources with repeated `401`/`429` responses (off by default).
+    pub abuse: Arc<AbuseGuard>,
+    /// Retry worker status and manual trigger for `/admin/worker` (None: no worker here).
+    pub worker_monitor: Option<Arc<WorkerMonitor>>,
+    /// OpenAPI document served at `/api-docs/openapi.json` (None: the built-in `ApiDoc`).
+    pub openapi: Option<Arc<utoipa::openapi::OpenApi>>,
+    /// Migration state found at startup; writes are rejected unless it is current
+    pub schema_status: Arc<SchemaStatus>,
+    /// Access each route requires (the built-in rules by default)
+    pub auth_policy: Arc<AuthPolicy>,
+    /// CORS policy of each route group (none by default: same-origin only)
+    pub cors: Arc<CorsConfig>,
+    /// Request body limit of each route group
+    pub body_limits: BodyLimits,
+    /// Issuer keys `POST /verify/receipt` checks signatures against (empty by default).
+    pub issuer_keys: Arc<IssuerKeyRegistry>,
+    /// Maintenance mode, toggled at runtime through `/admin/maintenance` or SIGHUP: writes
+    /// outside `/admin` are rejected with 503 while it is on.
+    pub maintenance: Arc<AtomicBool>,
+    /// `Retry-After` sent with writes rejected for maintenance.
+    pub maintenance_retry_after: Duration,
 }
 
+/// Default `Retry-After` of writes rejected while maintenance mode is on
+pub const DEFAULT_MAINTENANCE_RETRY_AFTER: Duration = Duration::from_secs(60);
+
 impl AppState {
     /// Create a new application state (metrics_handle None; use `new_with_metrics` for production).
     #[must_use]
@@ -55,13 +109,300 @@ impl AppState {
             Arc::clone(&outbox_repo),
             Arc::clone(&blockchain_client),
         ));
+        Self::from_service(
+            service,
+            item_repo,
+            outbox_repo,
+            Some(blockchain_client),
+            api_auth_key,
+            metrics_handle,
+        )
+    }
+
+    /// Create application state without a blockchain client (`CHAIN_DISABLED=true`):
+    /// items are stored in `pending` and never submitted.
+    #[must_use]
+    pub fn without_blockchain(
+        item_repo: Arc<dyn ItemRepository>,
+        outbox_repo: Arc<dyn OutboxRepository>,
+        api_auth_key: SecretString,
+        metrics_handle: Option<Arc<PrometheusHandle>>,
+    ) -> Self {
+        let service = Arc::new(AppService::without_blockchain(
+            Arc::clone(&item_repo),
+            Arc::clone(&outbox_repo),
+        ));
+        Self::from_service(
+            service,
+            item_repo,
+            outbox_repo,
+            None,
+            api_auth_key,
+            metrics_handle,
+        )
+    }
+
+    fn from_service(
+        service: Arc<AppService>,
+        item_repo: Arc<dyn ItemRepository>,
+        outbox_repo: Arc<dyn OutboxRepository>,
+        blockchain_client: Option<Arc<dyn BlockchainClient>>,
+        api_auth_key: SecretString,
+        metrics_handle: Option<Arc<PrometheusHandle>>,
+    ) -> Self {
         Self {
             service,
             item_repo,
             outbox_repo,
             blockchain_client,
             api_auth_key,
+            admin_auth_key: None,
+            api_key_store: None,
+            usage_ledger: None,
+            job_store: None,
+            request_journal: None,
             metrics_handle,
+            blocklist: Arc::new(IpBlocklist::empty()),
+            abuse: Arc::new(AbuseGuard::disabled()),
+            worker_monitor: None,
+            openapi: None,
+            schema_status: Arc::new(SchemaStatus::default()),
+            auth_policy: Arc::new(AuthPolicy::default()),
+            cors: Arc::new(CorsConfig::default()),
+            body_limits: BodyLimits::default(),
+            issuer_keys: Arc::new(IssuerKeyRegistry::empty()),
+            maintenance: Arc::new(AtomicBool::new(false)),
+            maintenance_retry_after: DEFAULT_MAINTENANCE_RETRY_AFTER,
+        }
+    }
+
+    /// Require `key` (instead of the bootstrap key) for admin access.
+    #[must_use]
+    pub fn with_admin_auth_key(mut self, key: SecretString) -> Self {
+        self.admin_auth_key = Some(key);
+        self
+    }
+
+    /// Whether writes are currently rejected for maintenance.
+    #[must_use]
+    pub fn maintenance_enabled(&self) -> bool {
+        self.maintenance.load(Ordering::Relaxed)
+    }
+
+    /// Turn maintenance mode on or off; returns the previous setting.
+    pub fn set_maintenance(&self, enabled: bool) -> bool {
+        let previous = self.maintenance.swap(enabled, Ordering::Relaxed);
+        if previous != enabled {
+            warn!(enabled, "Maintenance mode changed");
         }
+        metrics::gauge!("maintenance_mode").set(if enabled { 1.0 } else { 0.0 });
+        previous
+    }
+
+    /// Flip maintenance mode (e.g. on SIGHUP); returns the new setting.
+    pub fn toggle_maintenance(&self) -> bool {
+        let enabled = !self.maintenance.fetch_xor(true, Ordering::Relaxed);
+        warn!(enabled, "Maintenance mode changed");
+        metrics::gauge!("maintenance_mode").set(if enabled { 1.0 } else { 0.0 });
+        enabled
+    }
+
+    /// How long clients are told to wait (`Retry-After`) while maintenance mode is on.
+    #[must_use]
+    pub fn with_maintenance_retry_after(mut self, retry_after: Duration) -> Self {
+        self.maintenance_retry_after = retry_after;
+        self
+    }
+
+    /// Replace the per-route authorization rules (e.g. ones loaded from `AUTH_POLICY`).
+    #[must_use]
+    pub fn with_auth_policy(mut self, auth_policy: AuthPolicy) -> Self {
+        self.auth_policy = Arc::new(auth_policy);
+        self
+    }
+
+    /// Set the CORS policy of each route group (e.g. one loaded from `CORS_*`).
+    #[must_use]
+    pub fn with_cors(mut self, cors: CorsConfig) -> Self {
+        self.cors = Arc::new(cors);
+        self
+    }
+
+    /// Set the request body limit of each route group (e.g. one loaded from `BODY_LIMIT_*`).
+    #[must_use]
+    pub fn with_body_limits(mut self, body_limits: BodyLimits) -> Self {
+        self.body_limits = body_limits;
+        self
+    }
+
+    /// Replace the IP deny-list (e.g. one loaded from `IP_BLOCKLIST`).
+    #[must_use]
+    pub fn with_blocklist(mut self, blocklist: Arc<IpBlocklist>) -> Self {
+        self.blocklist = blocklist;
+        self
+    }
+
+    /// Ban sources with repeated `401`/`429` responses (e.g. thresholds from `ABUSE_*`).
+    #[must_use]
+    pub fn with_abuse_guard(mut self, abuse: Arc<AbuseGuard>) -> Self {
+        self.abuse = abuse;
+        self
+    }
+
+    /// Verify receipts against these current and retired issuer keys.
+    #[must_use]
+    pub fn with_issuer_keys(mut self, issuer_keys: IssuerKeyRegistry) -> Self {
+        self.issuer_keys = Arc::new(issuer_keys);
+        self
+    }
+
+    /// Enable managed API keys backed by the given store.
+    #[must_use]
+    pub fn with_api_key_store(mut self, store: Arc<dyn ApiKeyStore>) -> Self {
+        self.api_key_store = Some(store);
+        self
+    }
+
+    /// Count requests per API key in `ledger` and enforce the keys' quotas.
+    #[must_use]
+    pub fn with_usage_ledger(mut self, ledger: Arc<dyn UsageLedger>) -> Self {
+        self.usage_ledger = Some(ledger);
+        self
+    }
+
+    /// Enable idempotent POST replays backed by the given journal.
+    #[must_use]
+    pub fn with_request_journal(mut self, journal: Arc<dyn RequestJournal>) -> Self {
+        self.request_journal = Some(journal);
+        self
+    }
+
+    /// Track background jobs (`GET /jobs/{id}`) in the given store.
+    #[must_use]
+    pub fn with_job_store(mut self, store: Arc<dyn JobStore>) -> Self {
+        self.job_store = Some(store);
+        self
+    }
+
+    /// Expose the background worker's status and manual trigger on `/admin/worker`.
+    #[must_use]
+    pub fn with_worker_monitor(mut self, monitor: Arc<WorkerMonitor>) -> Self {
+        self.worker_monitor = Some(monitor);
+        self
+    }
+
+    /// Reject item metadata larger than `limit` bytes of serialized JSON.
+    #[must_use]
+    pub fn with_max_metadata_bytes(self, limit: usize) -> Self {
+        self.map_service(|service| service.with_max_metadata_bytes(limit))
+    }
+
+    /// Estimate the `total` of page-number listings matching more than `rows` items
+    /// (None: always count them).
+    #[must_use]
+    pub fn with_count_estimate_above(self, rows: Option<u64>) -> Self {
+        self.map_service(|service| service.with_count_estimate_above(rows))
+    }
+
+    /// Serve cached dependency health for `ttl` before checking again.
+    #[must_use]
+    pub fn with_health_cache_ttl(self, ttl: std::time::Duration) -> Self {
+        self.map_service(|service| service.with_health_cache_ttl(ttl))
+    }
+
+    /// Back off and dead-letter failed blockchain submissions according to `policy`.
+    #[must_use]
+    pub fn with_retry_policy(self, policy: RetryPolicy) -> Self {
+        self.map_service(|service| service.with_retry_policy(policy))
+    }
+
+    /// Defer blockchain submissions while the fee payer balance is below `balance`.
+    #[must_use]
+    pub fn with_min_wallet_balance(self, balance: u64) -> Self {
+        self.map_service(|service| service.with_min_wallet_balance(balance))
+    }
+
+    /// Sign pagination cursors with `codec` instead of a per-process random key.
+    #[must_use]
+    pub fn with_cursor_codec(self, codec: CursorCodec) -> Self {
+        self.map_service(|service| service.with_cursor_codec(codec))
+    }
+
+    /// Serve `GET /admin/events` and `GET /admin/webhook-deliveries` from these stores.
+    #[must_use]
+    pub fn with_operational_logs(
+        self,
+        event_log: Arc<dyn EventLog>,
+        delivery_log: Arc<dyn WebhookDeliveryLog>,
+    ) -> Self {
+        self.map_service(|service| service.with_operational_logs(event_log, delivery_log))
+    }
+
+    /// Record item and admin changes in `log` and serve `GET /admin/audit` from it.
+    #[must_use]
+    pub fn with_audit_logger(self, log: Arc<dyn AuditLogger>) -> Self {
+        self.map_service(|service| service.with_audit_logger(log))
+    }
+
+    /// Report business KPIs from the service layer to `sink`.
+    #[must_use]
+    pub fn with_telemetry(self, sink: Arc<dyn TelemetrySink>) -> Self {
+        self.map_service(|service| service.with_telemetry(sink))
+    }
+
+    /// Publish item lifecycle events from the service layer to `publisher`.
+    #[must_use]
+    pub fn with_message_publisher(self, publisher: Arc<dyn MessagePublisher>) -> Self {
+        self.map_service(|service| service.with_message_publisher(publisher))
+    }
+
+    /// Keep item content larger than `threshold` bytes in `store`.
+    #[must_use]
+    pub fn with_object_store(self, store: Arc<dyn ObjectStore>, threshold: usize) -> Self {
+        self.map_service(|service| service.with_object_store(store, threshold))
+    }
+
+    /// Take the current time and delays from `clock` (e.g. a test clock).
+    #[must_use]
+    pub fn with_clock(self, clock: Arc<dyn Clock>) -> Self {
+        self.map_service(|service| service.with_clock(clock))
+    }
+
+    /// Reserve outbox entries claimed by the retry worker for `ttl`.
+    #[must_use]
+    pub fn with_claim_ttl(self, ttl: Duration) -> Self {
+        self.map_service(|service| service.with_claim_ttl(ttl))
+    }
+
+    /// Cap the fees `budget.signer` may spend per UTC day; spend is recorded in `ledger`.
+    #[must_use]
+    pub fn with_submission_budget(
+        self,
+        budget: SubmissionBudget,
+        ledger: Arc<dyn SpendLedger>,
+    ) -> Self {
+        self.map_service(|service| service.with_submission_budget(budget, ledger))
+    }
+
+    /// Reconfigure the service; only valid while this state is its sole owner.
+    fn map_service(mut self, f: impl FnOnce(AppService) -> AppService) -> Self {
+        let service = Arc::try_unwrap(self.service)
+            .unwrap_or_else(|_| panic!("service options must be set before the state is shared"));
+        self.service = Arc::new(f(service));
+        self
+    }
+
+    /// Serve this OpenAPI document (e.g. with deployment-specific servers and metadata).
+    #[must_use]
+    pub fn with_openapi(mut self, openapi: utoipa::openapi::OpenApi) -> Self {
+        self.openapi = Some(Arc::new(openapi));
+        self
+    }
+
+    /// Record the startup migration check (`AUTO_MIGRATE=false`); a mismatch makes the API read-only.
+    #[must_use]
+    pub fn with_schema_status(mut self, status: SchemaStatus) -> Self {
+        self.schema_status = Arc::new(status);
+        self
     }
 }
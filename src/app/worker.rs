@@ -1,11 +1,18 @@
-//! Background worker for processing pending blockchain submissions.
-
-use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::watch;
-use tracing::{error, info};
-
-use super::service::AppService;
+//! Background workers: pending blockchain submissions and their confirmation (run by the
+//! [`JobScheduler`]), purging soft-deleted items and keeping the dependency health
+//! snapshot fresh.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{Notify, watch};
+use tracing::{error, info, warn};
+
+use super::retry::{BackoffStrategy, RetryPolicy};
+use super::scheduler::{JobRunError, JobScheduler, PeriodicJob};
+use super::service::{AppService, BatchOutcome};
+use crate::domain::{HealthStatus, ItemError, WorkerError, WorkerStatus};
 
 /// Configuration for the background worker
 #[derive(Debug, Clone)]
@@ -16,6 +23,9 @@ pub struct WorkerConfig {
     pub batch_size: i64,
     /// Whether the worker is enabled
     pub enabled: bool,
+    /// Extra delay after consecutive failed batches (`max_attempts` is not used: the
+    /// worker keeps trying)
+    pub backoff: RetryPolicy,
 }
 
 impl Default for WorkerConfig {
@@ -24,58 +34,395 @@ impl Default for WorkerConfig {
             poll_interval: Duration::from_secs(10),
             batch_size: 10,
             enabled: true,
+            backoff: RetryPolicy {
+                strategy: BackoffStrategy::Exponential,
+                max_attempts: i32::MAX,
+                base_delay: Duration::from_secs(10),
+                max_delay: Duration::from_secs(300),
+            },
+        }
+    }
+}
+
+/// Shared view of the retry worker for `GET /admin/worker`, plus the
+/// `POST /admin/worker/run-now` trigger.
+pub struct WorkerMonitor {
+    status: Mutex<WorkerStatus>,
+    run_now: Notify,
+}
+
+impl WorkerMonitor {
+    #[must_use]
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            status: Mutex::new(WorkerStatus {
+                enabled,
+                ..WorkerStatus::default()
+            }),
+            run_now: Notify::new(),
+        }
+    }
+
+    /// Snapshot of the current status
+    #[must_use]
+    pub fn status(&self) -> WorkerStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    /// Ask the worker loop to run a batch now instead of waiting for the next tick.
+    /// A request made while a batch is running triggers one more batch right after it.
+    pub fn trigger(&self) -> Result<WorkerStatus, WorkerError> {
+        let mut status = self.status.lock().unwrap();
+        if !status.leader {
+            return Err(WorkerError::NotRunning);
+        }
+        status.manual_runs += 1;
+        self.run_now.notify_one();
+        Ok(status.clone())
+    }
+
+    fn set_running(&self, running: bool) {
+        self.status.lock().unwrap().leader = running;
+    }
+
+    /// Extra delay before the next batch
+    fn backoff(&self) -> Duration {
+        Duration::from_secs(self.status.lock().unwrap().current_backoff_secs)
+    }
+
+    fn record(
+        &self,
+        result: &Result<BatchOutcome, ItemError>,
+        finished_at: DateTime<Utc>,
+        elapsed: Duration,
+        backoff: &RetryPolicy,
+    ) {
+        let mut status = self.status.lock().unwrap();
+        status.last_batch_at = Some(finished_at);
+        status.last_batch_duration_ms = Some(elapsed.as_millis() as u64);
+        match result {
+            Ok(outcome) => {
+                status.last_batch_claimed = outcome.claimed;
+                status.last_batch_submitted = outcome.submitted;
+                status.last_batch_failed = outcome.failed;
+                status.total_submitted += outcome.submitted as u64;
+                status.total_failed += outcome.failed as u64;
+                status.last_error = None;
+                status.consecutive_errors = 0;
+                status.current_backoff_secs = 0;
+            }
+            Err(e) => {
+                status.last_batch_claimed = 0;
+                status.last_batch_submitted = 0;
+                status.last_batch_failed = 0;
+                status.last_error = Some(e.to_string());
+                status.consecutive_errors = status.consecutive_errors.saturating_add(1);
+                status.current_backoff_secs =
+                    backoff.delay(status.consecutive_errors - 1).as_secs();
+            }
         }
     }
 }
 
-/// Background worker for processing pending blockchain submissions
+/// Scheduled job processing pending blockchain submissions
 pub struct BlockchainRetryWorker {
     service: Arc<AppService>,
     config: WorkerConfig,
-    shutdown_rx: watch::Receiver<bool>,
+    monitor: Arc<WorkerMonitor>,
 }
 
 impl BlockchainRetryWorker {
     /// Create a new worker instance
-    pub fn new(
-        service: Arc<AppService>,
-        config: WorkerConfig,
-        shutdown_rx: watch::Receiver<bool>,
-    ) -> Self {
+    pub fn new(service: Arc<AppService>, config: WorkerConfig) -> Self {
+        let monitor = Arc::new(WorkerMonitor::new(config.enabled));
         Self {
             service,
             config,
-            shutdown_rx,
+            monitor,
         }
     }
 
+    /// Report status to (and accept manual runs from) a shared monitor
+    #[must_use]
+    pub fn with_monitor(mut self, monitor: Arc<WorkerMonitor>) -> Self {
+        self.monitor = monitor;
+        self
+    }
+
+    /// Monitor this worker reports to
+    #[must_use]
+    pub fn monitor(&self) -> &Arc<WorkerMonitor> {
+        &self.monitor
+    }
+
     /// Get the configured batch size
     #[must_use]
     pub fn batch_size(&self) -> i64 {
         self.config.batch_size
     }
 
-    /// Run the worker loop
+    /// Execute a single tick of the worker loop (for testing)
+    /// This processes one batch without the scheduler
+    pub async fn run_once(&self) {
+        if !self.config.enabled {
+            return;
+        }
+        self.process_batch().await;
+    }
+
+    /// Process a batch of pending submissions
+    pub async fn process_batch(&self) {
+        let _ = self.run_batch().await;
+    }
+
+    async fn run_batch(&self) -> Result<BatchOutcome, ItemError> {
+        let started = Instant::now();
+        let result = self
+            .service
+            .process_pending_batch(self.config.batch_size)
+            .await;
+        self.monitor.record(
+            &result,
+            self.service.clock().now(),
+            started.elapsed(),
+            &self.config.backoff,
+        );
+        match &result {
+            Ok(outcome) if outcome.claimed == 0 => {
+                // No pending items, nothing to log
+            }
+            Ok(outcome) => {
+                info!(
+                    count = outcome.claimed,
+                    submitted = outcome.submitted,
+                    failed = outcome.failed,
+                    deferred = outcome.deferred,
+                    "Processed pending blockchain submissions"
+                );
+            }
+            Err(e) => {
+                error!(error = ?e, "Error processing pending submissions");
+                warn!(
+                    backoff_secs = self.monitor.backoff().as_secs(),
+                    "Backing off blockchain retry worker"
+                );
+            }
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl PeriodicJob for BlockchainRetryWorker {
+    fn name(&self) -> &'static str {
+        "blockchain_retry"
+    }
+
+    /// Poll interval plus the backoff after consecutive failed batches
+    fn interval(&self) -> Duration {
+        self.config.poll_interval + self.monitor.backoff()
+    }
+
+    async fn run(&self) -> Result<(), JobRunError> {
+        self.run_batch().await?;
+        Ok(())
+    }
+
+    fn wake_signal(&self) -> Option<&Notify> {
+        Some(&self.monitor.run_now)
+    }
+
+    fn started(&self) {
+        self.monitor.set_running(true);
+    }
+
+    fn stopped(&self) {
+        self.monitor.set_running(false);
+    }
+}
+
+/// Spawn the retry worker on its own scheduler (nothing runs when it is disabled)
+pub fn spawn_worker(
+    service: Arc<AppService>,
+    config: WorkerConfig,
+    monitor: Arc<WorkerMonitor>,
+) -> (tokio::task::JoinHandle<()>, watch::Sender<bool>) {
+    let mut scheduler = JobScheduler::new().with_clock(Arc::clone(service.clock()));
+    if config.enabled {
+        let worker = BlockchainRetryWorker::new(service, config).with_monitor(monitor);
+        scheduler = scheduler.register(Arc::new(worker));
+    } else {
+        info!("Blockchain retry worker is disabled");
+    }
+    scheduler.spawn()
+}
+
+/// Configuration for the confirmation poller
+#[derive(Debug, Clone)]
+pub struct ConfirmationConfig {
+    /// Interval between polls
+    pub interval: Duration,
+    /// Submitted items checked per poll, oldest first
+    pub batch_size: i64,
+    /// Whether the poller is enabled
+    pub enabled: bool,
+}
+
+impl Default for ConfirmationConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+            batch_size: 50,
+            enabled: true,
+        }
+    }
+}
+
+impl ConfirmationConfig {
+    /// Read `CONFIRMATION_POLL_INTERVAL_SECS` and `CONFIRMATION_BATCH_SIZE`, falling back
+    /// to defaults
+    #[must_use]
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let interval = std::env::var("CONFIRMATION_POLL_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v > 0)
+            .map_or(defaults.interval, Duration::from_secs);
+        let batch_size = std::env::var("CONFIRMATION_BATCH_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(defaults.batch_size);
+        Self {
+            interval,
+            batch_size,
+            ..defaults
+        }
+    }
+}
+
+/// Scheduled job moving submitted items to `confirmed` once their transaction landed
+pub struct ConfirmationPoller {
+    service: Arc<AppService>,
+    config: ConfirmationConfig,
+}
+
+impl ConfirmationPoller {
+    pub fn new(service: Arc<AppService>, config: ConfirmationConfig) -> Self {
+        Self { service, config }
+    }
+}
+
+#[async_trait]
+impl PeriodicJob for ConfirmationPoller {
+    fn name(&self) -> &'static str {
+        "confirmation_poller"
+    }
+
+    fn interval(&self) -> Duration {
+        self.config.interval
+    }
+
+    /// Every instance polling would repeat the same RPC status lookups
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    async fn run(&self) -> Result<(), JobRunError> {
+        self.service
+            .confirm_submitted_items(self.config.batch_size)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Configuration for the soft-deleted item purge job
+#[derive(Debug, Clone)]
+pub struct PurgeConfig {
+    /// Interval between purge runs
+    pub interval: Duration,
+    /// How long soft-deleted items are kept before being hard-deleted
+    pub retention: Duration,
+    /// Whether the purge job is enabled
+    pub enabled: bool,
+}
+
+impl Default for PurgeConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(3600),
+            retention: Duration::from_secs(30 * 24 * 3600),
+            enabled: true,
+        }
+    }
+}
+
+impl PurgeConfig {
+    /// Read `ITEM_PURGE_INTERVAL_SECS` and `ITEM_PURGE_RETENTION_DAYS`, falling back to defaults
+    #[must_use]
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let interval = std::env::var("ITEM_PURGE_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .map_or(defaults.interval, Duration::from_secs);
+        let retention = std::env::var("ITEM_PURGE_RETENTION_DAYS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .map_or(defaults.retention, |days| {
+                Duration::from_secs(days * 24 * 3600)
+            });
+        Self {
+            interval,
+            retention,
+            ..defaults
+        }
+    }
+}
+
+/// Background job that hard-deletes items soft-deleted longer ago than the retention window
+pub struct ItemPurgeWorker {
+    service: Arc<AppService>,
+    config: PurgeConfig,
+    shutdown_rx: watch::Receiver<bool>,
+}
+
+impl ItemPurgeWorker {
+    /// Create a new purge worker instance
+    pub fn new(
+        service: Arc<AppService>,
+        config: PurgeConfig,
+        shutdown_rx: watch::Receiver<bool>,
+    ) -> Self {
+        Self {
+            service,
+            config,
+            shutdown_rx,
+        }
+    }
+
+    /// Run the purge loop
     pub async fn run(mut self) {
         if !self.config.enabled {
-            info!("Blockchain retry worker is disabled");
+            info!("Item purge worker is disabled");
             return;
         }
 
         info!(
-            poll_interval = ?self.config.poll_interval,
-            batch_size = self.config.batch_size,
-            "Starting blockchain retry worker"
+            interval = ?self.config.interval,
+            retention = ?self.config.retention,
+            "Starting item purge worker"
         );
 
         loop {
             tokio::select! {
-                _ = tokio::time::sleep(self.config.poll_interval) => {
-                    self.process_batch().await;
+                _ = self.service.clock().sleep(self.config.interval) => {
+                    self.run_once().await;
                 }
                 result = self.shutdown_rx.changed() => {
                     if result.is_ok() && *self.shutdown_rx.borrow() {
-                        info!("Blockchain retry worker shutting down");
+                        info!("Item purge worker shutting down");
                         break;
                     }
                 }
@@ -83,42 +430,87 @@ impl BlockchainRetryWorker {
         }
     }
 
-    /// Execute a single tick of the worker loop (for testing)
-    /// This processes one batch without the full loop infrastructure
+    /// Execute a single purge pass
     pub async fn run_once(&self) {
         if !self.config.enabled {
             return;
         }
-        self.process_batch().await;
+        let retention =
+            chrono::Duration::from_std(self.config.retention).unwrap_or(chrono::Duration::MAX);
+        if let Err(e) = self.service.purge_deleted_items(retention).await {
+            error!(error = ?e, "Error purging soft-deleted items");
+        }
     }
+}
 
-    /// Process a batch of pending submissions
-    pub async fn process_batch(&self) {
-        match self
-            .service
-            .process_pending_submissions(self.config.batch_size)
-            .await
-        {
-            Ok(0) => {
-                // No pending items, nothing to log
-            }
-            Ok(count) => {
-                info!(count = count, "Processed pending blockchain submissions");
+/// Spawn the purge worker as a tokio task
+pub fn spawn_purge_worker(
+    service: Arc<AppService>,
+    config: PurgeConfig,
+) -> (tokio::task::JoinHandle<()>, watch::Sender<bool>) {
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let worker = ItemPurgeWorker::new(service, config, shutdown_rx);
+    let handle = tokio::spawn(worker.run());
+    (handle, shutdown_tx)
+}
+
+/// Background job that re-checks dependencies before the cached health snapshot expires,
+/// so `/health` and `/health/ready` probes never wait on Postgres or the RPC node
+pub struct HealthRefreshWorker {
+    service: Arc<AppService>,
+    interval: Duration,
+    shutdown_rx: watch::Receiver<bool>,
+}
+
+impl HealthRefreshWorker {
+    /// Create a refresher checking every `interval` (shorter than the cache TTL)
+    pub fn new(
+        service: Arc<AppService>,
+        interval: Duration,
+        shutdown_rx: watch::Receiver<bool>,
+    ) -> Self {
+        Self {
+            service,
+            interval,
+            shutdown_rx,
+        }
+    }
+
+    /// Run the refresh loop, checking once right away
+    pub async fn run(mut self) {
+        info!(interval = ?self.interval, "Starting health refresh worker");
+        let mut last_status = HealthStatus::Healthy;
+        loop {
+            let status = self.service.deep_health_check().await.status;
+            // Log transitions only; probes report the current state
+            if status != last_status {
+                if status == HealthStatus::Healthy {
+                    info!("Dependencies healthy again");
+                } else {
+                    warn!(status = ?status, "Dependency health changed");
+                }
+                last_status = status;
             }
-            Err(e) => {
-                error!(error = ?e, "Error processing pending submissions");
+            tokio::select! {
+                _ = self.service.clock().sleep(self.interval) => {}
+                result = self.shutdown_rx.changed() => {
+                    if result.is_ok() && *self.shutdown_rx.borrow() {
+                        info!("Health refresh worker shutting down");
+                        break;
+                    }
+                }
             }
         }
     }
 }
 
-/// Spawn the background worker as a tokio task
-pub fn spawn_worker(
+/// Spawn the health refresher as a tokio task
+pub fn spawn_health_refresh_worker(
     service: Arc<AppService>,
-    config: WorkerConfig,
+    interval: Duration,
 ) -> (tokio::task::JoinHandle<()>, watch::Sender<bool>) {
     let (shutdown_tx, shutdown_rx) = watch::channel(false);
-    let worker = BlockchainRetryWorker::new(service, config, shutdown_rx);
+    let worker = HealthRefreshWorker::new(service, interval, shutdown_rx);
     let handle = tokio::spawn(worker.run());
     (handle, shutdown_tx)
 }
@@ -126,8 +518,12 @@ pub fn spawn_worker(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{BlockchainStatus, CreateItemRequest, ItemRepository};
-    use crate::test_utils::{MockBlockchainClient, MockConfig, MockProvider, mock_repos};
+    use crate::domain::{
+        BlockchainStatus, Clock, CreateItemRequest, ItemRepository, SubmissionRepository,
+    };
+    use crate::test_utils::{
+        MockBlockchainClient, MockClock, MockConfig, MockMethod, MockProvider, MockStep, mock_repos,
+    };
 
     fn create_test_service() -> Arc<AppService> {
         let mock = Arc::new(MockProvider::new());
@@ -150,6 +546,7 @@ mod tests {
             poll_interval: Duration::from_secs(5),
             batch_size: 20,
             enabled: false,
+            ..WorkerConfig::default()
         };
         assert_eq!(config.poll_interval, Duration::from_secs(5));
         assert_eq!(config.batch_size, 20);
@@ -171,6 +568,7 @@ mod tests {
             poll_interval: Duration::from_secs(30),
             batch_size: 50,
             enabled: true,
+            ..WorkerConfig::default()
         };
         let config2 = config1.clone();
         assert_eq!(config1.poll_interval, config2.poll_interval);
@@ -185,19 +583,64 @@ mod tests {
             poll_interval: Duration::from_millis(100),
             batch_size: 10,
             enabled: false, // Disabled
+            ..WorkerConfig::default()
         };
-        let (_, shutdown_rx) = watch::channel(false);
-        let worker = BlockchainRetryWorker::new(service, config, shutdown_rx);
+        let (handle, _shutdown_tx) =
+            spawn_worker(service, config, Arc::new(WorkerMonitor::new(false)));
 
         // Should return immediately without blocking
         let start = std::time::Instant::now();
-        worker.run().await;
+        handle.await.unwrap();
         let elapsed = start.elapsed();
 
         // Should complete almost instantly (less than 50ms)
         assert!(elapsed < Duration::from_millis(50));
     }
 
+    #[tokio::test]
+    async fn test_worker_waits_and_retries_on_the_service_clock() {
+        let clock = Arc::new(MockClock::default());
+        let mock = Arc::new(MockProvider::new().with_clock(clock.clone()));
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::with_script(vec![
+            MockStep::Fail("rpc error".to_string()),
+            MockStep::Succeed,
+        ]));
+        let service =
+            Arc::new(AppService::new(item_repo, outbox_repo, bc).with_clock(clock.clone()));
+        let request = CreateItemRequest::new("Clocked".to_string(), "Content".to_string());
+        let created = service.create_and_submit_item(&request).await.unwrap();
+        let config = WorkerConfig {
+            poll_interval: Duration::from_secs(60),
+            ..WorkerConfig::default()
+        };
+        let monitor = Arc::new(WorkerMonitor::new(true));
+        let (handle, shutdown_tx) = spawn_worker(service, config, Arc::clone(&monitor));
+
+        // Each advance covers the poll interval plus the largest jitter, and the backoff
+        for batch in 1..=2 {
+            clock.wait_for_sleepers(1).await;
+            clock.advance(Duration::from_secs(120));
+            let ran = async {
+                while monitor.status().total_submitted + monitor.status().total_failed < batch {
+                    tokio::task::yield_now().await;
+                }
+            };
+            tokio::time::timeout(Duration::from_secs(5), ran)
+                .await
+                .unwrap_or_else(|_| panic!("worker did not run batch {} within 5s", batch));
+        }
+
+        let status = monitor.status();
+        assert_eq!((status.total_failed, status.total_submitted), (1, 1));
+        assert_eq!(status.last_batch_at, Some(clock.now()));
+        let item = mock.get_item(&created.id).await.unwrap().unwrap();
+        assert_eq!(item.blockchain_status, BlockchainStatus::Submitted);
+
+        shutdown_tx.send(true).unwrap();
+        handle.await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_worker_shutdown_via_channel() {
         let service = create_test_service();
@@ -205,12 +648,11 @@ mod tests {
             poll_interval: Duration::from_secs(60), // Long poll so it doesn't trigger
             batch_size: 10,
             enabled: true,
+            ..WorkerConfig::default()
         };
-        let (shutdown_tx, shutdown_rx) = watch::channel(false);
-        let worker = BlockchainRetryWorker::new(service, config, shutdown_rx);
-
         // Spawn worker in background
-        let handle = tokio::spawn(worker.run());
+        let (handle, shutdown_tx) =
+            spawn_worker(service, config, Arc::new(WorkerMonitor::new(true)));
 
         // Give it a moment to start
         tokio::time::sleep(Duration::from_millis(50)).await;
@@ -230,9 +672,11 @@ mod tests {
             poll_interval: Duration::from_secs(60),
             batch_size: 10,
             enabled: false, // Disabled so it returns immediately
+            ..WorkerConfig::default()
         };
 
-        let (handle, shutdown_tx) = spawn_worker(service, config);
+        let (handle, shutdown_tx) =
+            spawn_worker(service, config, Arc::new(WorkerMonitor::new(false)));
 
         // Wait for disabled worker to finish (it returns immediately when disabled)
         let result = tokio::time::timeout(Duration::from_secs(1), handle).await;
@@ -249,9 +693,7 @@ mod tests {
     async fn test_worker_new_construction() {
         let service = create_test_service();
         let config = WorkerConfig::default();
-        let (_, shutdown_rx) = watch::channel(false);
-
-        let worker = BlockchainRetryWorker::new(Arc::clone(&service), config.clone(), shutdown_rx);
+        let worker = BlockchainRetryWorker::new(Arc::clone(&service), config.clone());
 
         // Worker should be constructed without panicking
         // Since fields are private, we verify by running it (which tests all the fields were set)
@@ -267,9 +709,9 @@ mod tests {
             poll_interval: Duration::from_millis(100),
             batch_size: 10,
             enabled: false,
+            ..WorkerConfig::default()
         };
-        let (_, shutdown_rx) = watch::channel(false);
-        let worker = BlockchainRetryWorker::new(service, config, shutdown_rx);
+        let worker = BlockchainRetryWorker::new(service, config);
 
         let start = std::time::Instant::now();
         worker.run_once().await;
@@ -286,9 +728,9 @@ mod tests {
             poll_interval: Duration::from_secs(60),
             batch_size: 5,
             enabled: true,
+            ..WorkerConfig::default()
         };
-        let (_, shutdown_rx) = watch::channel(false);
-        let worker = BlockchainRetryWorker::new(service, config, shutdown_rx);
+        let worker = BlockchainRetryWorker::new(service, config);
 
         // run_once should complete without hanging (even with no pending items)
         let result = tokio::time::timeout(Duration::from_secs(1), worker.run_once()).await;
@@ -302,9 +744,9 @@ mod tests {
             poll_interval: Duration::from_secs(10),
             batch_size: 42,
             enabled: true,
+            ..WorkerConfig::default()
         };
-        let (_, shutdown_rx) = watch::channel(false);
-        let worker = BlockchainRetryWorker::new(service, config, shutdown_rx);
+        let worker = BlockchainRetryWorker::new(service, config);
 
         assert_eq!(worker.batch_size(), 42);
     }
@@ -316,9 +758,9 @@ mod tests {
             poll_interval: Duration::from_secs(10),
             batch_size: 10,
             enabled: true,
+            ..WorkerConfig::default()
         };
-        let (_, shutdown_rx) = watch::channel(false);
-        let worker = BlockchainRetryWorker::new(service, config, shutdown_rx);
+        let worker = BlockchainRetryWorker::new(service, config);
 
         // Should complete without panic when no items
         worker.process_batch().await;
@@ -338,14 +780,86 @@ mod tests {
             poll_interval: Duration::from_secs(10),
             batch_size: 10,
             enabled: true,
+            ..WorkerConfig::default()
         };
-        let (_, shutdown_rx) = watch::channel(false);
-        let worker = BlockchainRetryWorker::new(service, config, shutdown_rx);
+        let worker = BlockchainRetryWorker::new(service, config);
 
         // Should not panic - errors are logged
         worker.process_batch().await;
     }
 
+    #[tokio::test]
+    async fn test_monitor_records_errors_with_capped_backoff() {
+        let mock = Arc::new(MockProvider::with_config(MockConfig::failure(
+            "Database error",
+        )));
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = Arc::new(AppService::new(item_repo, outbox_repo, bc));
+        let config = WorkerConfig {
+            poll_interval: Duration::from_secs(100),
+            batch_size: 10,
+            enabled: true,
+            backoff: RetryPolicy {
+                base_delay: Duration::from_secs(100),
+                ..WorkerConfig::default().backoff
+            },
+        };
+        let worker = BlockchainRetryWorker::new(service, config);
+
+        let mut backoffs = Vec::new();
+        for _ in 0..4 {
+            worker.process_batch().await;
+            backoffs.push(worker.monitor().status().current_backoff_secs);
+        }
+        assert_eq!(backoffs, vec![100, 200, 300, 300]);
+
+        let status = worker.monitor().status();
+        assert_eq!(status.consecutive_errors, 4);
+        assert!(status.last_error.is_some());
+        assert!(status.last_batch_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_run_now_trigger_processes_batch_before_next_tick() {
+        tokio::time::pause();
+
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let request = CreateItemRequest::new("Test Item".to_string(), "Content".to_string());
+        mock.create_item(&request).await.unwrap();
+        let service = Arc::new(AppService::new(item_repo, outbox_repo, bc));
+
+        let config = WorkerConfig {
+            poll_interval: Duration::from_secs(3600),
+            batch_size: 10,
+            enabled: true,
+            ..WorkerConfig::default()
+        };
+        let monitor = Arc::new(WorkerMonitor::new(true));
+        assert!(matches!(monitor.trigger(), Err(WorkerError::NotRunning)));
+
+        let (handle, shutdown_tx) = spawn_worker(service, config, Arc::clone(&monitor));
+        while !monitor.status().leader {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(monitor.trigger().unwrap().manual_runs, 1);
+        while monitor.status().last_batch_at.is_none() {
+            tokio::task::yield_now().await;
+        }
+
+        let status = monitor.status();
+        assert_eq!(status.last_batch_claimed, 1);
+        assert_eq!(status.last_batch_submitted, 1);
+        assert_eq!(status.total_submitted, 1);
+        assert_eq!(status.current_backoff_secs, 0);
+
+        shutdown_tx.send(true).unwrap();
+        handle.await.unwrap();
+        assert!(!monitor.status().leader);
+    }
+
     #[tokio::test]
     async fn test_worker_with_tokio_time_pause() {
         tokio::time::pause();
@@ -355,11 +869,10 @@ mod tests {
             poll_interval: Duration::from_secs(60),
             batch_size: 10,
             enabled: true,
+            ..WorkerConfig::default()
         };
-        let (shutdown_tx, shutdown_rx) = watch::channel(false);
-        let worker = BlockchainRetryWorker::new(service, config, shutdown_rx);
-
-        let handle = tokio::spawn(worker.run());
+        let (handle, shutdown_tx) =
+            spawn_worker(service, config, Arc::new(WorkerMonitor::new(true)));
 
         // Advance time past the poll interval
         tokio::time::advance(Duration::from_secs(61)).await;
@@ -381,11 +894,10 @@ mod tests {
             poll_interval: Duration::from_secs(5),
             batch_size: 10,
             enabled: true,
+            ..WorkerConfig::default()
         };
-        let (shutdown_tx, shutdown_rx) = watch::channel(false);
-        let worker = BlockchainRetryWorker::new(service, config, shutdown_rx);
-
-        let handle = tokio::spawn(worker.run());
+        let (handle, shutdown_tx) =
+            spawn_worker(service, config, Arc::new(WorkerMonitor::new(true)));
 
         // Advance through multiple poll intervals
         for _ in 0..3 {
@@ -406,9 +918,11 @@ mod tests {
             poll_interval: Duration::from_secs(60),
             batch_size: 10,
             enabled: true,
+            ..WorkerConfig::default()
         };
 
-        let (handle, shutdown_tx) = spawn_worker(service, config);
+        let (handle, shutdown_tx) =
+            spawn_worker(service, config, Arc::new(WorkerMonitor::new(true)));
 
         // Give it a moment to start
         tokio::time::sleep(Duration::from_millis(50)).await;
@@ -428,21 +942,16 @@ mod tests {
             poll_interval: Duration::from_secs(60),
             batch_size: 10,
             enabled: true,
+            ..WorkerConfig::default()
         };
-        let (shutdown_tx, shutdown_rx) = watch::channel(false);
-        let worker = BlockchainRetryWorker::new(service, config, shutdown_rx);
-
-        let handle = tokio::spawn(worker.run());
+        let (handle, shutdown_tx) =
+            spawn_worker(service, config, Arc::new(WorkerMonitor::new(true)));
 
-        // Drop the sender - this should trigger an error in changed()
+        // Drop the sender - nothing can stop the worker any more, so it stops itself
         drop(shutdown_tx);
 
-        // Worker should eventually notice and handle the closed channel
-        // Give it a moment to process
-        tokio::time::sleep(Duration::from_millis(100)).await;
-
-        // Cancel the task since it won't shutdown naturally
-        handle.abort();
+        let result = tokio::time::timeout(Duration::from_secs(2), handle).await;
+        assert!(result.is_ok());
     }
 
     #[tokio::test]
@@ -457,6 +966,8 @@ mod tests {
             description: None,
             content: "Content".to_string(),
             metadata: None,
+            content_key: None,
+            content_type: None,
         };
         let item = mock.create_item(&request).await.unwrap();
 
@@ -477,9 +988,9 @@ mod tests {
             poll_interval: Duration::from_secs(10),
             batch_size: 10,
             enabled: true,
+            ..WorkerConfig::default()
         };
-        let (_, shutdown_rx) = watch::channel(false);
-        let worker = BlockchainRetryWorker::new(service, config, shutdown_rx);
+        let worker = BlockchainRetryWorker::new(service, config);
 
         // Process the pending item
         worker.run_once().await;
@@ -489,12 +1000,113 @@ mod tests {
         assert_eq!(updated.blockchain_status, BlockchainStatus::Submitted);
     }
 
+    #[tokio::test]
+    async fn test_confirmation_poller_confirms_landed_transactions() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = Arc::new(AppService::new(item_repo, outbox_repo, bc.clone()));
+        let request = CreateItemRequest::new("Test Item".to_string(), "Content".to_string());
+        let item = service.create_and_submit_item(&request).await.unwrap();
+        service.process_pending_submissions(10).await.unwrap();
+        let poller = ConfirmationPoller::new(Arc::clone(&service), ConfirmationConfig::default());
+
+        bc.fail_method(MockMethod::GetTransactionStatus, "rpc down");
+        poller.run().await.unwrap();
+        let unchanged = mock.get_item(&item.id).await.unwrap().unwrap();
+        assert_eq!(unchanged.blockchain_status, BlockchainStatus::Submitted);
+
+        bc.clear_method_failure(MockMethod::GetTransactionStatus);
+        poller.run().await.unwrap();
+        let confirmed = mock.get_item(&item.id).await.unwrap().unwrap();
+        assert_eq!(confirmed.blockchain_status, BlockchainStatus::Confirmed);
+        assert_eq!(
+            confirmed.blockchain_signature,
+            unchanged.blockchain_signature
+        );
+    }
+
+    #[tokio::test]
+    async fn test_purge_worker_removes_expired_soft_deleted_items() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = Arc::new(AppService::new(item_repo, outbox_repo, bc));
+
+        let request = CreateItemRequest::new("Doomed".to_string(), "Content".to_string());
+        let deleted = mock.create_item(&request).await.unwrap();
+        let kept = mock.create_item(&request).await.unwrap();
+        mock.soft_delete_item(&deleted.id).await.unwrap();
+
+        let (_, shutdown_rx) = watch::channel(false);
+        let long_retention = PurgeConfig {
+            retention: Duration::from_secs(3600),
+            ..PurgeConfig::default()
+        };
+        ItemPurgeWorker::new(Arc::clone(&service), long_retention, shutdown_rx.clone())
+            .run_once()
+            .await;
+        assert_eq!(mock.get_all_items().len(), 2);
+
+        let no_retention = PurgeConfig {
+            retention: Duration::ZERO,
+            ..PurgeConfig::default()
+        };
+        ItemPurgeWorker::new(service, no_retention, shutdown_rx)
+            .run_once()
+            .await;
+        let remaining = mock.get_all_items();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, kept.id);
+        assert!(
+            mock.get_all_outbox_entries()
+                .iter()
+                .all(|e| e.aggregate_id == kept.id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_spawn_purge_worker_shuts_down() {
+        let service = create_test_service();
+        let (handle, shutdown_tx) = spawn_purge_worker(service, PurgeConfig::default());
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        shutdown_tx.send(true).unwrap();
+
+        let result = tokio::time::timeout(Duration::from_secs(2), handle).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_health_refresher_updates_cached_health() {
+        let mock = Arc::new(MockProvider::new());
+        let (item_repo, outbox_repo) = mock_repos(&mock);
+        let bc = Arc::new(MockBlockchainClient::new());
+        let service = Arc::new(
+            AppService::new(item_repo, outbox_repo, bc.clone())
+                .with_health_cache_ttl(Duration::from_secs(3600)),
+        );
+        assert_eq!(service.health_check().await.status, HealthStatus::Healthy);
+
+        let (handle, shutdown_tx) =
+            spawn_health_refresh_worker(Arc::clone(&service), Duration::from_millis(10));
+        bc.set_healthy(false);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        // Served from cache, yet reflects the outage
+        assert_eq!(service.health_check().await.status, HealthStatus::Unhealthy);
+
+        shutdown_tx.send(true).unwrap();
+        let result = tokio::time::timeout(Duration::from_secs(2), handle).await;
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn test_worker_config_zero_batch_size() {
         let config = WorkerConfig {
             poll_interval: Duration::from_secs(10),
             batch_size: 0,
             enabled: true,
+            ..WorkerConfig::default()
         };
         assert_eq!(config.batch_size, 0);
     }
@@ -505,6 +1117,7 @@ mod tests {
             poll_interval: Duration::from_millis(1),
             batch_size: 10,
             enabled: true,
+            ..WorkerConfig::default()
         };
         assert_eq!(config.poll_interval, Duration::from_millis(1));
     }
@@ -0,0 +1,466 @@
+//! Composition root: the one place the production object graph is assembled.
+//!
+//! `main` reads the environment and opens connections; [`compose`] wires the resulting
+//! [`Infrastructure`] into the application state, the scheduled jobs and the router.
+//! Because it never does I/O itself, the unit test below builds the full graph and
+//! fails when a dependency (a worker, the auth key, the metrics handle) is left unwired.
+
+use std::env;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Router;
+use metrics_exporter_prometheus::PrometheusHandle;
+use secrecy::{ExposeSecret, SecretString};
+use tracing::{info, warn};
+
+use crate::api::{OpenApiConfig, RateLimitConfig, create_router, create_router_with_rate_limit};
+use crate::app::{
+    AbuseConfig, AbuseGuard, AppState, AuthPolicy, BlockchainRetryWorker, BodyLimits,
+    ConfirmationConfig, ConfirmationPoller, CorsConfig, CursorCodec, DEFAULT_CLAIM_TTL,
+    DEFAULT_CONTENT_OFFLOAD_THRESHOLD, DEFAULT_COUNT_ESTIMATE_ABOVE, DEFAULT_HEALTH_CACHE_TTL,
+    DEFAULT_JOB_JITTER, DEFAULT_MAINTENANCE_RETRY_AFTER, DEFAULT_MAX_METADATA_BYTES, IpBlocklist,
+    IssuerKeyRegistry, JobScheduler, RetryPolicy, SubmissionBudget, WorkerConfig, WorkerMonitor,
+};
+use crate::domain::{
+    ApiKeyStore, AuditLogger, BlockchainClient, EventLog, JobStore, LeaderElection,
+    MessagePublisher, ObjectStore, RequestJournal, SchemaStatus, SpendLedger, UsageLedger,
+    WebhookDeliveryLog,
+};
+use crate::infra::{DatabaseClient, TelemetrySinkKind, split_repositories};
+
+/// Which part of the application a process runs (`ROLE`), so blockchain processing can
+/// be scaled apart from request serving
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProcessRole {
+    /// The API only; submissions wait in the outbox for a worker process
+    Api,
+    /// The retry worker and confirmation poller only, without a listener
+    Worker,
+    /// Both in one process
+    #[default]
+    All,
+}
+
+impl ProcessRole {
+    /// Whether the process serves HTTP (and gRPC)
+    #[must_use]
+    pub fn serves_api(self) -> bool {
+        self != Self::Worker
+    }
+
+    /// Whether the process runs the scheduled blockchain jobs
+    #[must_use]
+    pub fn runs_workers(self) -> bool {
+        self != Self::Api
+    }
+}
+
+impl FromStr for ProcessRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "api" => Ok(Self::Api),
+            "worker" => Ok(Self::Worker),
+            "all" => Ok(Self::All),
+            other => Err(format!(
+                "Invalid role '{}': must be api, worker or all",
+                other
+            )),
+        }
+    }
+}
+
+/// Settings of everything [`compose`] wires (connections and spawned tasks stay in `main`)
+#[derive(Debug)]
+pub struct AppConfig {
+    /// Deployment mode of this process (`ROLE`)
+    pub role: ProcessRole,
+    /// Bootstrap key (`API_AUTH_KEY`)
+    pub api_auth_key: SecretString,
+    /// Token required for `/admin` (`ADMIN_AUTH_KEY`); None lets `API_AUTH_KEY` administer
+    pub admin_auth_key: Option<SecretString>,
+    pub enable_rate_limiting: bool,
+    pub rate_limit_config: RateLimitConfig,
+    /// Run the retry worker and confirmation poller (`ENABLE_BACKGROUND_WORKER`); never in
+    /// the API role
+    pub enable_background_worker: bool,
+    pub worker_config: WorkerConfig,
+    /// Confirmation poller of submitted items (`CONFIRMATION_*`)
+    pub confirmation_config: ConfirmationConfig,
+    /// Share of each scheduled job's interval added as random delay (`JOB_JITTER_PERCENT`)
+    pub job_jitter: f64,
+    /// How long a claimed outbox entry is reserved for this instance (`WORKER_CLAIM_TTL_SECS`)
+    pub claim_ttl: Duration,
+    /// Run singleton jobs on the instance holding their lease only (`LEADER_ELECTION`)
+    pub leader_election: bool,
+    pub blocklist: IpBlocklist,
+    /// Automatic temporary bans (`ABUSE_*`)
+    pub abuse: AbuseConfig,
+    /// `AUTH_POLICY` rules followed by the built-in ones
+    pub auth_policy: AuthPolicy,
+    /// CORS policy of `/items`, `/admin` and `/health` (`CORS_*`)
+    pub cors: CorsConfig,
+    /// Request body limit of each route group (`BODY_LIMIT_*_BYTES`)
+    pub body_limits: BodyLimits,
+    /// Overrides of the served OpenAPI document (`OPENAPI_*`)
+    pub openapi: OpenApiConfig,
+    /// Largest accepted item metadata in bytes of serialized JSON
+    pub max_metadata_bytes: usize,
+    /// Content above this many bytes goes to the object store, when there is one
+    /// (`CONTENT_OFFLOAD_THRESHOLD_BYTES`)
+    pub content_offload_threshold: usize,
+    /// Backoff between failed submissions and when they are dead-lettered
+    pub retry_policy: RetryPolicy,
+    /// Where business KPIs are reported (`TELEMETRY_SINK`)
+    pub telemetry: TelemetrySinkKind,
+    /// How long `/health` and `/health/ready` reuse a dependency check (`HEALTH_CACHE_TTL_SECS`)
+    pub health_cache_ttl: Duration,
+    /// Defer submissions below this fee payer balance (`MIN_WALLET_BALANCE`)
+    pub min_wallet_balance: Option<u64>,
+    /// Daily fee budget of the configured signer (`SUBMISSION_DAILY_BUDGET`)
+    pub submission_budget: Option<SubmissionBudget>,
+    /// HMAC key for pagination cursors (`CURSOR_SECRET`); None signs with a per-process key
+    pub cursor_secret: Option<SecretString>,
+    /// Page-number listings matching more rows report an estimated `total`
+    /// (`PAGINATION_COUNT_ESTIMATE_ABOVE`); None always counts
+    pub count_estimate_above: Option<u64>,
+    /// Keys `POST /verify/receipt` accepts: the Solana signer's plus `ISSUER_PUBLIC_KEYS`
+    /// and `ISSUER_RETIRED_PUBLIC_KEYS`
+    pub issuer_keys: IssuerKeyRegistry,
+    /// `Retry-After` of writes rejected in maintenance mode (`MAINTENANCE_RETRY_AFTER_SECS`)
+    pub maintenance_retry_after: Duration,
+}
+
+impl AppConfig {
+    /// Defaults of every setting, with the one that has none
+    pub fn new(api_auth_key: SecretString) -> Self {
+        Self {
+            role: ProcessRole::default(),
+            api_auth_key,
+            admin_auth_key: None,
+            enable_rate_limiting: false,
+            rate_limit_config: RateLimitConfig::default(),
+            enable_background_worker: true,
+            worker_config: WorkerConfig::default(),
+            confirmation_config: ConfirmationConfig::default(),
+            job_jitter: DEFAULT_JOB_JITTER,
+            claim_ttl: DEFAULT_CLAIM_TTL,
+            leader_election: false,
+            blocklist: IpBlocklist::default(),
+            abuse: AbuseConfig::default(),
+            auth_policy: AuthPolicy::default(),
+            cors: CorsConfig::default(),
+            body_limits: BodyLimits::default(),
+            openapi: OpenApiConfig::default(),
+            max_metadata_bytes: DEFAULT_MAX_METADATA_BYTES,
+            content_offload_threshold: DEFAULT_CONTENT_OFFLOAD_THRESHOLD,
+            retry_policy: RetryPolicy::default(),
+            telemetry: TelemetrySinkKind::Prometheus,
+            health_cache_ttl: DEFAULT_HEALTH_CACHE_TTL,
+            min_wallet_balance: None,
+            submission_budget: None,
+            cursor_secret: None,
+            count_estimate_above: Some(DEFAULT_COUNT_ESTIMATE_ABOVE),
+            issuer_keys: IssuerKeyRegistry::default(),
+            maintenance_retry_after: DEFAULT_MAINTENANCE_RETRY_AFTER,
+        }
+    }
+}
+
+/// Connected backends the graph is built on
+pub struct Infrastructure {
+    /// Implements every repository trait
+    pub db: Arc<dyn DatabaseClient>,
+    /// None when blockchain submission is disabled (`CHAIN_DISABLED=true`)
+    pub blockchain: Option<Arc<dyn BlockchainClient>>,
+    /// Prometheus handle for `GET /metrics` (None when no recorder could be installed)
+    pub metrics_handle: Option<Arc<PrometheusHandle>>,
+    /// Migration state found at startup
+    pub schema_status: SchemaStatus,
+    /// Message bus for item lifecycle events (None: events are not published)
+    pub publisher: Option<Arc<dyn MessagePublisher>>,
+    /// Store for large item content (None: all content stays in the database)
+    pub object_store: Option<Arc<dyn ObjectStore>>,
+}
+
+/// The wired application, ready to serve
+pub struct Application {
+    pub state: Arc<AppState>,
+    /// Retry worker and confirmation poller (not yet spawned; empty when workers are off)
+    pub scheduler: JobScheduler,
+    pub router: Router,
+}
+
+/// Wire `infra` into the application state, scheduled jobs and router; does no I/O
+pub fn compose(config: AppConfig, infra: Infrastructure) -> Application {
+    let Infrastructure {
+        db,
+        blockchain,
+        metrics_handle,
+        schema_status,
+        publisher,
+        object_store,
+    } = infra;
+    let schema_current = schema_status.is_current();
+
+    // The database client implements every repository trait
+    let (item_repo, outbox_repo) = split_repositories(&db);
+    let blocked_ranges = config.blocklist.ranges().len();
+    let abuse_guard = Arc::new(AbuseGuard::new(config.abuse));
+    let app_state = match blockchain {
+        Some(client) => AppState::new_with_metrics(
+            item_repo,
+            outbox_repo,
+            client,
+            config.api_auth_key,
+            metrics_handle,
+        ),
+        None => AppState::without_blockchain(
+            item_repo,
+            outbox_repo,
+            config.api_auth_key,
+            metrics_handle,
+        ),
+    };
+    // Workers write to the database, so they only run against the expected schema
+    let run_worker = config.role.runs_workers()
+        && config.enable_background_worker
+        && app_state.service.blockchain_enabled()
+        && schema_current;
+    let worker_monitor = Arc::new(WorkerMonitor::new(run_worker));
+    let app_state = match config.min_wallet_balance {
+        Some(balance) => app_state.with_min_wallet_balance(balance),
+        None => app_state,
+    };
+    let app_state = match config.submission_budget {
+        Some(budget) => {
+            info!(
+                "   ✓ Submission budget: {} per day for {}",
+                budget.daily_limit, budget.signer
+            );
+            app_state.with_submission_budget(budget, Arc::clone(&db) as Arc<dyn SpendLedger>)
+        }
+        None => app_state,
+    };
+    let app_state = match &config.cursor_secret {
+        Some(secret) => {
+            app_state.with_cursor_codec(CursorCodec::new(secret.expose_secret().as_bytes()))
+        }
+        None => {
+            warn!(
+                "   ⚠ CURSOR_SECRET not set: pagination cursors break on restart and across instances"
+            );
+            app_state
+        }
+    };
+    let app_state = app_state.with_claim_ttl(config.claim_ttl);
+    let app_state = match config.telemetry.sink() {
+        Some(sink) => {
+            info!("   ✓ Business KPIs reported to {:?}", config.telemetry);
+            app_state.with_telemetry(sink)
+        }
+        None => app_state,
+    };
+    let app_state = match publisher {
+        Some(publisher) => {
+            info!("   ✓ Item lifecycle events published to the message bus");
+            app_state.with_message_publisher(publisher)
+        }
+        None => app_state,
+    };
+    let app_state = match object_store {
+        Some(store) => {
+            info!(
+                "   ✓ Item content above {} bytes kept in the object store",
+                config.content_offload_threshold
+            );
+            app_state.with_object_store(store, config.content_offload_threshold)
+        }
+        None => app_state,
+    };
+    let app_state = match config.admin_auth_key {
+        Some(key) => {
+            info!("   ✓ Admin routes require ADMIN_AUTH_KEY");
+            app_state.with_admin_auth_key(key)
+        }
+        None => app_state,
+    };
+    let app_state = Arc::new(
+        app_state
+            .with_blocklist(Arc::new(config.blocklist))
+            .with_abuse_guard(Arc::clone(&abuse_guard))
+            .with_auth_policy(config.auth_policy)
+            .with_issuer_keys(config.issuer_keys)
+            .with_api_key_store(Arc::clone(&db) as Arc<dyn ApiKeyStore>)
+            .with_usage_ledger(Arc::clone(&db) as Arc<dyn UsageLedger>)
+            .with_request_journal(Arc::clone(&db) as Arc<dyn RequestJournal>)
+            .with_job_store(Arc::clone(&db) as Arc<dyn JobStore>)
+            .with_operational_logs(
+                Arc::clone(&db) as Arc<dyn EventLog>,
+                Arc::clone(&db) as Arc<dyn WebhookDeliveryLog>,
+            )
+            .with_audit_logger(Arc::clone(&db) as Arc<dyn AuditLogger>)
+            .with_max_metadata_bytes(config.max_metadata_bytes)
+            .with_count_estimate_above(config.count_estimate_above)
+            .with_retry_policy(config.retry_policy)
+            .with_health_cache_ttl(config.health_cache_ttl)
+            .with_maintenance_retry_after(config.maintenance_retry_after)
+            .with_worker_monitor(Arc::clone(&worker_monitor))
+            .with_cors(config.cors)
+            .with_body_limits(config.body_limits)
+            .with_openapi(config.openapi.document())
+            .with_schema_status(schema_status),
+    );
+    if blocked_ranges > 0 {
+        info!("   ✓ IP blocklist active ({} ranges)", blocked_ranges);
+    }
+    if abuse_guard.is_enabled() {
+        info!("   ✓ Automatic temporary bans active");
+    }
+
+    // Submission retries and confirmation polling run as scheduled jobs
+    let mut scheduler = JobScheduler::new()
+        .with_jitter(config.job_jitter)
+        .with_clock(Arc::clone(app_state.service.clock()));
+    if config.leader_election {
+        let holder = lease_holder();
+        info!("   ✓ Leader election for singleton jobs (as {})", holder);
+        scheduler =
+            scheduler.with_leader_election(Arc::clone(&db) as Arc<dyn LeaderElection>, holder);
+    }
+    if run_worker {
+        let worker =
+            BlockchainRetryWorker::new(Arc::clone(&app_state.service), config.worker_config)
+                .with_monitor(worker_monitor);
+        scheduler = scheduler.register(Arc::new(worker));
+        info!("   ✓ Background worker started");
+    } else if !config.role.runs_workers() {
+        info!("   ○ Background worker left to worker processes (ROLE=api)");
+    } else {
+        info!("   ○ Background worker disabled");
+    }
+    if run_worker && config.confirmation_config.enabled {
+        let interval = config.confirmation_config.interval;
+        let poller =
+            ConfirmationPoller::new(Arc::clone(&app_state.service), config.confirmation_config);
+        scheduler = scheduler.register(Arc::new(poller));
+        info!("   ✓ Confirmation poller started (every {:?})", interval);
+    } else {
+        info!("   ○ Confirmation poller disabled");
+    }
+
+    let router = if config.enable_rate_limiting {
+        info!("   ✓ Rate limiting enabled");
+        create_router_with_rate_limit(Arc::clone(&app_state), config.rate_limit_config)
+    } else {
+        info!("   ○ Rate limiting disabled");
+        create_router(Arc::clone(&app_state))
+    };
+
+    Application {
+        state: app_state,
+        scheduler,
+        router,
+    }
+}
+
+/// Lease holder name: unique per process, readable in the lease table
+fn lease_holder() -> String {
+    format!(
+        "{}-{}",
+        env::var("HOSTNAME").unwrap_or_else(|_| "instance".to_string()),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::{
+        BlockchainBackendConfig, PostgresClient, PostgresConfig, create_blockchain_client,
+    };
+    use metrics_exporter_prometheus::PrometheusBuilder;
+
+    /// The production graph on a pool that never connects and the no-op chain
+    fn infrastructure() -> Infrastructure {
+        let pool_config = PostgresConfig {
+            min_connections: 0,
+            ..PostgresConfig::default()
+        };
+        let db = PostgresClient::connect_lazy("postgres://localhost/unused", pool_config)
+            .expect("valid URL");
+        Infrastructure {
+            db: Arc::new(db),
+            blockchain: Some(
+                create_blockchain_client(BlockchainBackendConfig::Noop).expect("noop client"),
+            ),
+            // A handle without installing it as the global recorder
+            metrics_handle: Some(Arc::new(PrometheusBuilder::new().build_recorder().handle())),
+            schema_status: SchemaStatus::default(),
+            publisher: None,
+            object_store: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compose_wires_the_production_graph() {
+        let mut config = AppConfig::new(SecretString::from("test-api-key"));
+        config.admin_auth_key = Some(SecretString::from("test-admin-key"));
+        config.leader_election = true;
+
+        let app = compose(config, infrastructure());
+
+        assert_eq!(
+            app.scheduler.job_names(),
+            vec!["blockchain_retry", "confirmation_poller"]
+        );
+        assert_eq!(app.state.api_auth_key.expose_secret(), "test-api-key");
+        assert!(app.state.admin_auth_key.is_some());
+        assert!(app.state.metrics_handle.is_some());
+        assert!(app.state.blockchain_client.is_some());
+        assert!(app.state.api_key_store.is_some());
+        assert!(app.state.request_journal.is_some());
+        assert!(app.state.job_store.is_some());
+        assert!(app.state.openapi.is_some());
+        assert!(
+            app.state
+                .worker_monitor
+                .as_ref()
+                .is_some_and(|monitor| monitor.status().enabled)
+        );
+        assert!(app.state.service.blockchain_enabled());
+    }
+
+    #[tokio::test]
+    async fn test_compose_leaves_workers_to_worker_processes_in_the_api_role() {
+        let mut config = AppConfig::new(SecretString::from("key"));
+        config.role = "API".parse().unwrap();
+
+        let app = compose(config, infrastructure());
+
+        assert!(app.scheduler.is_empty());
+        assert!(!ProcessRole::Api.runs_workers());
+        assert!(!ProcessRole::Worker.serves_api());
+        assert!(ProcessRole::All.serves_api() && ProcessRole::All.runs_workers());
+        assert!("both".parse::<ProcessRole>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_compose_skips_workers_against_an_outdated_schema() {
+        let mut infra = infrastructure();
+        infra.schema_status = SchemaStatus::compare(&[1, 2], &[1]);
+
+        let app = compose(AppConfig::new(SecretString::from("key")), infra);
+
+        assert!(app.scheduler.is_empty());
+        assert!(
+            app.state
+                .worker_monitor
+                .as_ref()
+                .is_some_and(|monitor| !monitor.status().enabled)
+        );
+    }
+}
@@ -3,6 +3,8 @@
 
 use thiserror::Error;
 
+use super::types::FieldError;
+
 /// Item-related business logic and repository errors.
 #[derive(Error, Debug, Clone)]
 pub enum ItemError {
@@ -10,6 +12,22 @@ pub enum ItemError {
     NotFound(String),
     #[error("Invalid state: {0}")]
     InvalidState(String),
+    /// Pagination cursor that fails verification or no longer points at an item
+    #[error("Invalid cursor: {0}")]
+    InvalidCursor(String),
+    /// Item changed since the caller read the version it expected (optimistic concurrency)
+    #[error("Item {id} was modified: expected version {expected}, current version is {current}")]
+    Conflict {
+        id: String,
+        expected: i64,
+        current: i64,
+    },
+    /// An item with the same content hash already exists in the tenant
+    #[error("An item with the same content already exists")]
+    DuplicateContent,
+    /// The requested byte range lies outside content of `length` bytes
+    #[error("Requested range is outside the {length} bytes of content")]
+    RangeNotSatisfiable { length: u64 },
     #[error("Repository operation failed")]
     RepositoryFailure,
 }
@@ -33,6 +51,130 @@ pub enum BlockchainError {
     InsufficientFunds,
     #[error("Timeout: {message} (blockhash_used: {blockhash})")]
     Timeout { message: String, blockhash: String },
+    /// Rejected without contacting the RPC because the circuit breaker is open
+    #[error("Blockchain circuit open: RPC calls are temporarily suspended")]
+    CircuitOpen,
+}
+
+impl BlockchainError {
+    /// Low-cardinality label of the failure (e.g. for metrics)
+    #[must_use]
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::SubmissionFailed(_) | Self::SubmissionFailedWithBlockhash { .. } => {
+                "submission_failed"
+            }
+            Self::BlockhashExpired => "blockhash_expired",
+            Self::NetworkError { .. } => "network_error",
+            Self::InsufficientFunds => "insufficient_funds",
+            Self::Timeout { .. } => "timeout",
+            Self::CircuitOpen => "circuit_open",
+        }
+    }
+}
+
+/// API key store errors.
+#[derive(Error, Debug, Clone)]
+pub enum ApiKeyError {
+    #[error("API key not found: {0}")]
+    NotFound(String),
+    #[error("API key is revoked: {0}")]
+    Revoked(String),
+    #[error("API key store is not configured")]
+    StoreUnavailable,
+    #[error("Usage ledger is not configured")]
+    UsageUnavailable,
+    #[error("Repository operation failed")]
+    RepositoryFailure,
+}
+
+/// Request journal (idempotency) errors.
+#[derive(Error, Debug, Clone)]
+pub enum RequestJournalError {
+    #[error("Request journal is not configured")]
+    Unavailable,
+    #[error("Repository operation failed")]
+    RepositoryFailure,
+}
+
+/// Background job tracking errors.
+#[derive(Error, Debug, Clone)]
+pub enum JobError {
+    #[error("Job not found: {0}")]
+    NotFound(String),
+    #[error("Job store is not configured")]
+    StoreUnavailable,
+    #[error("Repository operation failed")]
+    RepositoryFailure,
+}
+
+/// Outbound notification (webhook) errors.
+#[derive(Error, Debug, Clone)]
+pub enum NotificationError {
+    #[error("Delivery to {url} failed after {attempts} attempts: {message}")]
+    DeliveryFailed {
+        url: String,
+        attempts: u32,
+        message: String,
+    },
+    #[error("Repository operation failed")]
+    RepositoryFailure,
+    #[error("Invalid cursor: {0}")]
+    InvalidCursor(String),
+    #[error("Event and delivery logs are not configured")]
+    LogUnavailable,
+}
+
+/// Message bus (domain event publishing) errors.
+#[derive(Error, Debug, Clone)]
+pub enum MessagingError {
+    #[error("Broker connection failed: {0}")]
+    Connection(String),
+    #[error("Publishing {event} failed: {message}")]
+    PublishFailed { event: String, message: String },
+    #[error("Subscribing to {subject} failed: {message}")]
+    SubscribeFailed { subject: String, message: String },
+}
+
+/// Object storage (large item content) errors.
+#[derive(Error, Debug, Clone)]
+pub enum ObjectStoreError {
+    #[error("Object not found: {0}")]
+    NotFound(String),
+    #[error("Object store unavailable: {0}")]
+    Unavailable(String),
+}
+
+/// Item content encryption errors.
+#[derive(Error, Debug, Clone)]
+pub enum EncryptionError {
+    /// The value was encrypted under a master key this instance does not have
+    #[error("Unknown encryption key: {0}")]
+    UnknownKey(String),
+    /// The master key (local or KMS) could not wrap or unwrap a data key
+    #[error("Encryption key unavailable: {0}")]
+    KeyUnavailable(String),
+    /// The stored value is not in the expected format or fails authentication
+    #[error("Encrypted value is corrupt or was tampered with")]
+    Corrupt,
+}
+
+/// Secret lookup errors.
+#[derive(Error, Debug, Clone)]
+pub enum SecretsError {
+    /// The secret store could not be reached or refused the request
+    #[error("Secret store unavailable: {0}")]
+    Unavailable(String),
+    /// The secret store answered with something that is not a usable secret
+    #[error("Invalid secret '{name}': {message}")]
+    Invalid { name: String, message: String },
+}
+
+/// Background worker control errors.
+#[derive(Error, Debug, Clone)]
+pub enum WorkerError {
+    #[error("Background worker is not running on this instance")]
+    NotRunning,
 }
 
 /// System health check errors.
@@ -68,8 +210,15 @@ pub enum ValidationError {
     MissingField(String),
     #[error("Invalid format: {0}")]
     InvalidFormat(String),
-    #[error("Validation failed: {0}")]
-    Multiple(String),
+    /// Field-level failures from `validator`, one entry per failed rule
+    #[error("Validation failed: {}", join_field_errors(.0))]
+    Multiple(Vec<FieldError>),
+    #[error("Field '{field}' is {size} bytes when serialized; the limit is {limit} bytes")]
+    TooLarge {
+        field: String,
+        size: usize,
+        limit: usize,
+    },
 }
 
 impl From<&str> for ValidationError {
@@ -78,10 +227,91 @@ impl From<&str> for ValidationError {
     }
 }
 
+impl ValidationError {
+    /// Per-field details for the error response
+    #[must_use]
+    pub fn field_errors(&self) -> Vec<FieldError> {
+        match self {
+            ValidationError::InvalidField { field, message } => {
+                vec![FieldError::new(field, "invalid", message)]
+            }
+            ValidationError::MissingField(field) => {
+                vec![FieldError::new(field, "required", self.to_string())]
+            }
+            ValidationError::Multiple(fields) => fields.clone(),
+            ValidationError::TooLarge { field, .. } => {
+                vec![FieldError::new(field, "too_large", self.to_string())]
+            }
+            ValidationError::InvalidFormat(_) => Vec::new(),
+        }
+    }
+}
+
+fn join_field_errors(fields: &[FieldError]) -> String {
+    fields
+        .iter()
+        .map(|f| format!("{}: {}", f.field, f.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 impl From<validator::ValidationErrors> for ValidationError {
     fn from(err: validator::ValidationErrors) -> Self {
-        ValidationError::Multiple(err.to_string())
+        let mut fields = Vec::new();
+        collect_field_errors(&err, "", &mut fields);
+        // validator keeps fields in a HashMap; sort for stable responses
+        fields.sort_by(|a, b| a.field.cmp(&b.field));
+        ValidationError::Multiple(fields)
+    }
+}
+
+/// Flatten nested `validator` errors into dotted / indexed field paths
+fn collect_field_errors(
+    errors: &validator::ValidationErrors,
+    prefix: &str,
+    out: &mut Vec<FieldError>,
+) {
+    use validator::ValidationErrorsKind;
+
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() {
+            (*field).to_string()
+        } else {
+            format!("{}.{}", prefix, field)
+        };
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                out.extend(errors.iter().map(|e| {
+                    let message = e
+                        .message
+                        .as_ref()
+                        .map_or_else(|| default_message(e), ToString::to_string);
+                    FieldError::new(&path, e.code.as_ref(), message)
+                }));
+            }
+            ValidationErrorsKind::Struct(nested) => collect_field_errors(nested, &path, out),
+            ValidationErrorsKind::List(entries) => {
+                for (index, nested) in entries {
+                    collect_field_errors(nested, &format!("{}[{}]", path, index), out);
+                }
+            }
+        }
+    }
+}
+
+/// Message for rules declared without one, e.g. "Failed 'length' validation (max = 255)"
+fn default_message(error: &validator::ValidationError) -> String {
+    let mut params: Vec<String> = error
+        .params
+        .iter()
+        .filter(|(name, _)| *name != "value")
+        .map(|(name, value)| format!("{} = {}", name, value))
+        .collect();
+    if params.is_empty() {
+        return format!("Failed '{}' validation", error.code);
     }
+    params.sort();
+    format!("Failed '{}' validation ({})", error.code, params.join(", "))
 }
 
 #[cfg(test)]
@@ -121,6 +351,14 @@ mod tests {
         assert!(err.to_string().contains("hash123"));
     }
 
+    #[test]
+    fn test_api_key_error_display() {
+        let err = ApiKeyError::NotFound("key_1".to_string());
+        assert_eq!(err.to_string(), "API key not found: key_1");
+        let err = ApiKeyError::StoreUnavailable;
+        assert_eq!(err.to_string(), "API key store is not configured");
+    }
+
     #[test]
     fn test_health_check_error_display() {
         let err = HealthCheckError::DatabaseUnavailable;
@@ -158,4 +396,75 @@ mod tests {
         let val_err = ValidationError::from(err);
         assert!(matches!(val_err, ValidationError::Multiple(_)));
     }
+
+    #[test]
+    fn test_validation_errors_are_mapped_per_field() {
+        use validator::Validate;
+
+        #[derive(Validate)]
+        struct Inner {
+            #[validate(length(max = 3))]
+            tag: String,
+        }
+
+        #[derive(Validate)]
+        struct Outer {
+            #[validate(length(min = 1, message = "Name is required"))]
+            name: String,
+            #[validate(range(min = 1, max = 10))]
+            limit: i64,
+            #[validate(nested)]
+            inner: Inner,
+        }
+
+        let outer = Outer {
+            name: String::new(),
+            limit: 0,
+            inner: Inner {
+                tag: "toolong".to_string(),
+            },
+        };
+        let err = ValidationError::from(outer.validate().unwrap_err());
+
+        let fields = err.field_errors();
+        assert_eq!(
+            fields,
+            vec![
+                FieldError::new(
+                    "inner.tag",
+                    "length",
+                    "Failed 'length' validation (max = 3)"
+                ),
+                FieldError::new(
+                    "limit",
+                    "range",
+                    "Failed 'range' validation (max = 10, min = 1)"
+                ),
+                FieldError::new("name", "length", "Name is required"),
+            ]
+        );
+        assert_eq!(
+            err.to_string(),
+            "Validation failed: inner.tag: Failed 'length' validation (max = 3); \
+             limit: Failed 'range' validation (max = 10, min = 1); name: Name is required"
+        );
+    }
+
+    #[test]
+    fn test_single_field_variants_have_field_errors() {
+        let err = ValidationError::MissingField("name".to_string());
+        assert_eq!(
+            err.field_errors(),
+            vec![FieldError::new(
+                "name",
+                "required",
+                "Missing required field: name"
+            )]
+        );
+        assert!(
+            ValidationError::InvalidFormat("bad".to_string())
+                .field_errors()
+                .is_empty()
+        );
+    }
 }
@@ -4,12 +4,38 @@ pub mod error;
 pub mod traits;
 pub mod types;
 
-pub use error::{BlockchainError, ConfigError, HealthCheckError, ItemError, ValidationError};
-pub use traits::{BlockchainClient, ItemRepository, OutboxRepository, TransactionSigner};
+pub use error::{
+    ApiKeyError, BlockchainError, ConfigError, EncryptionError, HealthCheckError, ItemError,
+    JobError, MessagingError, NotificationError, ObjectStoreError, RequestJournalError,
+    SecretsError, ValidationError, WorkerError,
+};
+#[allow(deprecated)]
+pub use traits::LegacyItemRepository;
+pub use traits::{
+    ApiKeyStore, AuditLogger, BlockchainClient, Clock, EncryptionService, EventLog, IdGenerator,
+    ItemRepository, JobStore, LeaderElection, MessagePublisher, MessageSubscriber,
+    NotificationClient, ObjectStore, OutboxRepository, RequestJournal, SecretsProvider,
+    SpendLedger, SubmissionRepository, TelemetrySink, TransactionSigner, UnitOfWork, UsageLedger,
+    WebhookDeliveryLog,
+};
 pub use types::{
-    BlockchainStatus, CreateItemRequest, ErrorDetail, ErrorResponse, HealthResponse, HealthStatus,
-    Item, ItemMetadata, ItemMetadataRequest, OutboxStatus, PaginatedResponse, PaginationParams,
-    RateLimitResponse, SolanaOutboxEntry, SolanaOutboxPayload,
-    build_solana_outbox_payload_from_item, build_solana_outbox_payload_from_request,
-    compute_blockchain_hash,
+    ApiKey, ApiKeyQuota, ApiKeyScope, AuditActor, AuditEntry, AuditFilter, AuditParams,
+    BlockchainStatus, BlocklistResponse, ContentHasher, CreateApiKeyRequest, CreateApiKeyResponse,
+    CreateItemParams, CreateItemRequest, DEFAULT_CONTENT_TYPE, DEFAULT_TENANT, DeadLetterParams,
+    DedupeMode, DependencyHealth, DomainEvent, DomainEventKind, ErrorDetail, ErrorResponse,
+    ExportBookmark, ExportFormat, ExportParams, FailedSubmission, FeeEstimate, FieldError,
+    HealthResponse, HealthStatus, ImportLineResult, ImportReport, ImportRow, ImportUpload,
+    InboundMessage, IssuerKeyStatus, Item, ItemCount, ItemField, ItemFields, ItemListFilter,
+    ItemMetadata, ItemMetadataRequest, ItemPosition, ItemSearchHit, ItemSortField, ItemStatusEvent,
+    ItemSummary, ItemTimeline, ItemVerification, ItemView, Job, JobStatus, JournalStatus, KeyUsage,
+    LiveFeedParams, LogPageParams, MaintenanceMode, OnChainTransaction, OutboxStatus,
+    PaginatedResponse, PaginationParams, Principal, QueueDepth, QuotaPeriod, QuotaUsage, RangeSpec,
+    RateLimitResponse, ReceiptVerification, ReencryptedBatch, RequestJournalEntry,
+    RequestStatusResponse, SchemaStatus, SearchParams, SearchResponse, SignatureScheme,
+    SigningContext, SolanaOutboxEntry, SolanaOutboxPayload, SortOrder, SubmissionAttempt,
+    SubmissionTrace, TagCount, TemporaryBan, TenantScope, TimeRange, TimelineEntry,
+    TimelineEntryKind, UpdateBlocklistRequest, UsageParams, UsageReport, VerifyReceiptRequest,
+    WebhookDelivery, WorkerStatus, build_solana_outbox_payload_from_item,
+    build_solana_outbox_payload_from_request, compute_blockchain_hash, month_start,
+    validate_content_type, validate_tenant_id,
 };
@@ -1,26 +1,44 @@
 //! Domain traits defining contracts for external systems.
 
 use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
 
-use super::error::{BlockchainError, HealthCheckError, ItemError};
+use super::error::{
+    ApiKeyError, BlockchainError, EncryptionError, HealthCheckError, ItemError, JobError,
+    MessagingError, NotificationError, ObjectStoreError, RequestJournalError, SecretsError,
+};
 use super::types::{
-    BlockchainStatus, CreateItemRequest, Item, OutboxStatus, PaginatedResponse, SolanaOutboxEntry,
-    SolanaOutboxPayload,
+    ApiKey, ApiKeyQuota, ApiKeyScope, AuditEntry, AuditFilter, BlockchainStatus, CreateItemRequest,
+    DomainEvent, ExportBookmark, FailedSubmission, FeeEstimate, InboundMessage, Item, ItemCount,
+    ItemListFilter, ItemPosition, ItemSearchHit, ItemStatusEvent, Job, JobStatus, KeyUsage,
+    OnChainTransaction, OutboxStatus, PaginatedResponse, QueueDepth, QuotaUsage, ReencryptedBatch,
+    RequestJournalEntry, SignatureScheme, SolanaOutboxEntry, SolanaOutboxPayload,
+    SubmissionAttempt, TagCount, TimeRange, WebhookDelivery,
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::ops::Range;
+use std::time::Duration;
 
 /// Transaction signer abstraction for chain operations.
 /// Decouples signing from the RPC client to support HSM, AWS KMS, and local keys.
 #[async_trait]
 pub trait TransactionSigner: Send + Sync {
-    /// Sign a message and return the signature as Base58.
+    /// Sign a message and return the signature as Base58. Secp256k1 signers expect the
+    /// 32-byte prehash as `message` (see [`SignatureScheme::Secp256k1`]).
     async fn sign_message(&self, message: &[u8]) -> Result<String, BlockchainError>;
 
-    /// Return the signer's public key as Base58 (e.g. Solana address).
+    /// Return the signer's public key as Base58 (e.g. Solana address; the compressed
+    /// SEC1 point for secp256k1).
     fn public_key(&self) -> String;
+
+    /// Signature algorithm (Ed25519 unless overridden)
+    fn scheme(&self) -> SignatureScheme {
+        SignatureScheme::Ed25519
+    }
 }
 
-/// Item repository for domain entity persistence (CRUD and blockchain status).
+/// Item repository for domain entity persistence (CRUD, listing and search).
 #[async_trait]
 pub trait ItemRepository: Send + Sync {
     /// Check database connectivity
@@ -29,24 +47,136 @@ pub trait ItemRepository: Send + Sync {
     /// Get a single item by ID
     async fn get_item(&self, id: &str) -> Result<Option<Item>, ItemError>;
 
-    /// Create a new item
+    /// Create a new item. Implementations must insert the matching outbox entry
+    /// in the same transaction so a crash can never lose the submission intent.
     async fn create_item(&self, data: &CreateItemRequest) -> Result<Item, ItemError>;
 
-    /// List items with cursor-based pagination
+    /// Create a new item in `pending` status without an outbox entry
+    /// (used when blockchain submission is disabled).
+    async fn create_item_without_outbox(&self, data: &CreateItemRequest)
+    -> Result<Item, ItemError>;
+
+    /// Start a unit of work whose writes become visible together on
+    /// [`UnitOfWork::commit`] and are discarded if it is dropped first
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork + '_>, ItemError>;
+
+    /// List items matching `filter` with cursor-based pagination, ordered by
+    /// `filter.sort` then ID. Soft-deleted items are skipped unless `filter.include_deleted`.
     async fn list_items(
         &self,
         limit: i64,
         cursor: Option<&str>,
+        filter: &ItemListFilter,
     ) -> Result<PaginatedResponse<Item>, ItemError>;
 
-    /// Update an existing item
-    async fn update_item(&self, id: &str, data: &CreateItemRequest) -> Result<Item, ItemError> {
-        let _ = (id, data);
+    /// Up to `limit` items matching `filter` after skipping `offset` of them, in the order
+    /// of [`Self::list_items`] (page-number pagination)
+    async fn list_items_at_offset(
+        &self,
+        offset: i64,
+        limit: i64,
+        filter: &ItemListFilter,
+    ) -> Result<Vec<Item>, ItemError> {
+        let _ = (offset, limit, filter);
+        Err(ItemError::InvalidState(
+            "list_items_at_offset not implemented".to_string(),
+        ))
+    }
+
+    /// Number of items matching `filter`. When the database expects more than
+    /// `estimate_above` rows it may answer with that estimate instead of counting them.
+    async fn count_items(
+        &self,
+        filter: &ItemListFilter,
+        estimate_above: Option<u64>,
+    ) -> Result<ItemCount, ItemError> {
+        let _ = (filter, estimate_above);
+        Err(ItemError::InvalidState(
+            "count_items not implemented".to_string(),
+        ))
+    }
+
+    /// Metadata tags of the current tenant's live items with how many items carry each,
+    /// most used first (ties by tag)
+    async fn list_tags(&self) -> Result<Vec<TagCount>, ItemError> {
+        Err(ItemError::InvalidState(
+            "list_tags not implemented".to_string(),
+        ))
+    }
+
+    /// Every live item, oldest first, read incrementally so an export of any size
+    /// never holds the whole table in memory. Errors end the stream.
+    fn stream_items(&self) -> BoxStream<'static, Result<Item, ItemError>>;
+
+    /// Live items positioned after `after` in `(updated_at, id)` order, read
+    /// incrementally like [`Self::stream_items`]. An item updated after it was read
+    /// appears again, which makes this a simple change feed (deletions are not reported).
+    fn stream_item_changes(
+        &self,
+        after: Option<ItemPosition>,
+    ) -> BoxStream<'static, Result<Item, ItemError>>;
+
+    /// Export bookmark `name`, registered without a position on first use
+    async fn export_bookmark(&self, name: &str) -> Result<ExportBookmark, ItemError>;
+
+    /// Move bookmark `name` to `position`; never moves it backwards.
+    /// Returns `NotFound` for a bookmark that was never registered.
+    async fn acknowledge_export_bookmark(
+        &self,
+        name: &str,
+        position: &ItemPosition,
+    ) -> Result<ExportBookmark, ItemError>;
+
+    /// Oldest live item of the current tenant whose content hash is `hash`
+    /// (see [`ContentHasher`](super::ContentHasher))
+    async fn find_item_by_hash(&self, hash: &str) -> Result<Option<Item>, ItemError> {
+        let _ = hash;
+        Err(ItemError::InvalidState(
+            "find_item_by_hash not implemented".to_string(),
+        ))
+    }
+
+    /// Full-text search over name, description and content, best match first.
+    /// Soft-deleted items are never returned.
+    async fn search_items(&self, query: &str, limit: i64) -> Result<Vec<ItemSearchHit>, ItemError>;
+
+    /// Replace the name, description, content and metadata of a live item and bump its
+    /// version, provided it is still at `expected_version`. Returns `NotFound` for a
+    /// missing or deleted item and `Conflict` when another write got there first.
+    async fn update_item(
+        &self,
+        id: &str,
+        data: &CreateItemRequest,
+        expected_version: i64,
+    ) -> Result<Item, ItemError> {
+        let _ = (id, data, expected_version);
         Err(ItemError::InvalidState(
             "update_item not implemented".to_string(),
         ))
     }
 
+    /// Soft-delete an item by setting `deleted_at`.
+    /// Returns `None` if the item does not exist or is already deleted.
+    async fn soft_delete_item(&self, id: &str) -> Result<Option<Item>, ItemError>;
+
+    /// Hard-delete items soft-deleted before `deleted_before` (outbox entries cascade).
+    /// Returns the number of rows removed.
+    async fn purge_deleted_items(&self, deleted_before: DateTime<Utc>) -> Result<u64, ItemError>;
+
+    /// Rewrite, under the current keys, the content of up to `limit` items after `after`
+    /// in ID order (deleted ones included) that is still plaintext or encrypted under a
+    /// retired master key (see [`EncryptionService::needs_reencryption`]). Fails with
+    /// `InvalidState` when content encryption is not configured.
+    async fn reencrypt_content(
+        &self,
+        _after: Option<&str>,
+        _limit: i64,
+    ) -> Result<ReencryptedBatch, ItemError> {
+        Err(ItemError::InvalidState(
+            "Content encryption is not configured".to_string(),
+        ))
+    }
+
     /// Delete an item
     async fn delete_item(&self, id: &str) -> Result<bool, ItemError> {
         let _ = id;
@@ -54,7 +184,35 @@ pub trait ItemRepository: Send + Sync {
             "delete_item not implemented".to_string(),
         ))
     }
+}
+
+/// Writes staged in one database transaction (see [`ItemRepository::begin`]).
+///
+/// Other readers see none of the writes until [`UnitOfWork::commit`] succeeds; an error
+/// part-way through is handled by returning early, which drops and rolls back the unit.
+#[async_trait]
+pub trait UnitOfWork: Send {
+    /// Insert a new item in `pending` status
+    async fn insert_item(&mut self, data: &CreateItemRequest) -> Result<Item, ItemError>;
+
+    /// Queue a blockchain submission for an item (inserted earlier in this unit or
+    /// already stored) and move it to `pending_submission`
+    async fn enqueue_solana_outbox(
+        &mut self,
+        item_id: &str,
+        payload: &SolanaOutboxPayload,
+    ) -> Result<Item, ItemError>;
+
+    /// Make every staged write visible at once
+    async fn commit(self: Box<Self>) -> Result<(), ItemError>;
 
+    /// Discard every staged write (same as dropping the unit, but reports failures)
+    async fn rollback(self: Box<Self>) -> Result<(), ItemError>;
+}
+
+/// Blockchain submission bookkeeping on items: status, retries and re-enqueueing.
+#[async_trait]
+pub trait SubmissionRepository: Send + Sync {
     /// Update blockchain status for an item
     async fn update_blockchain_status(
         &self,
@@ -72,24 +230,303 @@ pub trait ItemRepository: Send + Sync {
         payload: &SolanaOutboxPayload,
     ) -> Result<Item, ItemError>;
 
-    /// Get items pending blockchain submission
-    async fn get_pending_blockchain_items(&self, limit: i64) -> Result<Vec<Item>, ItemError>;
+    /// Claim items pending blockchain submission for `claim_ttl`: their next retry is
+    /// pushed past the claim, so concurrent callers on other instances get other items.
+    /// Not used by the retry worker, which drains the outbox via
+    /// [`OutboxRepository::claim_pending_solana_outbox`]; kept for inspection tooling.
+    async fn get_pending_blockchain_items(
+        &self,
+        limit: i64,
+        claim_ttl: Duration,
+    ) -> Result<Vec<Item>, ItemError>;
 
     /// Increment retry count for an item
     async fn increment_retry_count(&self, id: &str) -> Result<i32, ItemError>;
 }
 
-/// Outbox repository for worker queue processing (claim, complete, fail).
+/// The combined item repository from before submission bookkeeping moved into
+/// [`SubmissionRepository`]. Implementing it provides both traits: rename an existing
+/// `impl ItemRepository` to `impl LegacyItemRepository` and it keeps working unchanged.
+/// Methods added to [`ItemRepository`] since fall back to their defaults.
+#[deprecated(
+    since = "0.3.0",
+    note = "implement `ItemRepository` and `SubmissionRepository` instead"
+)]
+#[async_trait]
+pub trait LegacyItemRepository: Send + Sync {
+    /// See [`ItemRepository::health_check`]
+    async fn health_check(&self) -> Result<(), HealthCheckError>;
+
+    /// See [`ItemRepository::get_item`]
+    async fn get_item(&self, id: &str) -> Result<Option<Item>, ItemError>;
+
+    /// See [`ItemRepository::create_item`]
+    async fn create_item(&self, data: &CreateItemRequest) -> Result<Item, ItemError>;
+
+    /// See [`ItemRepository::create_item_without_outbox`]
+    async fn create_item_without_outbox(&self, data: &CreateItemRequest)
+    -> Result<Item, ItemError>;
+
+    /// See [`ItemRepository::begin`]
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork + '_>, ItemError>;
+
+    /// See [`ItemRepository::list_items`]
+    async fn list_items(
+        &self,
+        limit: i64,
+        cursor: Option<&str>,
+        filter: &ItemListFilter,
+    ) -> Result<PaginatedResponse<Item>, ItemError>;
+
+    /// See [`ItemRepository::stream_items`]
+    fn stream_items(&self) -> BoxStream<'static, Result<Item, ItemError>>;
+
+    /// See [`ItemRepository::stream_item_changes`]
+    fn stream_item_changes(
+        &self,
+        after: Option<ItemPosition>,
+    ) -> BoxStream<'static, Result<Item, ItemError>>;
+
+    /// See [`ItemRepository::export_bookmark`]
+    async fn export_bookmark(&self, name: &str) -> Result<ExportBookmark, ItemError>;
+
+    /// See [`ItemRepository::acknowledge_export_bookmark`]
+    async fn acknowledge_export_bookmark(
+        &self,
+        name: &str,
+        position: &ItemPosition,
+    ) -> Result<ExportBookmark, ItemError>;
+
+    /// See [`ItemRepository::find_item_by_hash`]
+    async fn find_item_by_hash(&self, hash: &str) -> Result<Option<Item>, ItemError> {
+        let _ = hash;
+        Err(ItemError::InvalidState(
+            "find_item_by_hash not implemented".to_string(),
+        ))
+    }
+
+    /// See [`ItemRepository::search_items`]
+    async fn search_items(&self, query: &str, limit: i64) -> Result<Vec<ItemSearchHit>, ItemError>;
+
+    /// See [`ItemRepository::update_item`]
+    async fn update_item(
+        &self,
+        id: &str,
+        data: &CreateItemRequest,
+        expected_version: i64,
+    ) -> Result<Item, ItemError> {
+        let _ = (id, data, expected_version);
+        Err(ItemError::InvalidState(
+            "update_item not implemented".to_string(),
+        ))
+    }
+
+    /// See [`ItemRepository::soft_delete_item`]
+    async fn soft_delete_item(&self, id: &str) -> Result<Option<Item>, ItemError>;
+
+    /// See [`ItemRepository::purge_deleted_items`]
+    async fn purge_deleted_items(&self, deleted_before: DateTime<Utc>) -> Result<u64, ItemError>;
+
+    /// See [`ItemRepository::reencrypt_content`]
+    async fn reencrypt_content(
+        &self,
+        _after: Option<&str>,
+        _limit: i64,
+    ) -> Result<ReencryptedBatch, ItemError> {
+        Err(ItemError::InvalidState(
+            "Content encryption is not configured".to_string(),
+        ))
+    }
+
+    /// See [`ItemRepository::delete_item`]
+    async fn delete_item(&self, id: &str) -> Result<bool, ItemError> {
+        let _ = id;
+        Err(ItemError::InvalidState(
+            "delete_item not implemented".to_string(),
+        ))
+    }
+
+    /// See [`SubmissionRepository::update_blockchain_status`]
+    async fn update_blockchain_status(
+        &self,
+        id: &str,
+        status: BlockchainStatus,
+        signature: Option<&str>,
+        error: Option<&str>,
+        next_retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), ItemError>;
+
+    /// See [`SubmissionRepository::enqueue_solana_outbox_for_item`]
+    async fn enqueue_solana_outbox_for_item(
+        &self,
+        item_id: &str,
+        payload: &SolanaOutboxPayload,
+    ) -> Result<Item, ItemError>;
+
+    /// See [`SubmissionRepository::get_pending_blockchain_items`]
+    async fn get_pending_blockchain_items(
+        &self,
+        limit: i64,
+        claim_ttl: Duration,
+    ) -> Result<Vec<Item>, ItemError>;
+
+    /// See [`SubmissionRepository::increment_retry_count`]
+    async fn increment_retry_count(&self, id: &str) -> Result<i32, ItemError>;
+}
+
+#[allow(deprecated)]
+#[async_trait]
+impl<T: LegacyItemRepository> ItemRepository for T {
+    async fn health_check(&self) -> Result<(), HealthCheckError> {
+        LegacyItemRepository::health_check(self).await
+    }
+
+    async fn get_item(&self, id: &str) -> Result<Option<Item>, ItemError> {
+        LegacyItemRepository::get_item(self, id).await
+    }
+
+    async fn create_item(&self, data: &CreateItemRequest) -> Result<Item, ItemError> {
+        LegacyItemRepository::create_item(self, data).await
+    }
+
+    async fn create_item_without_outbox(
+        &self,
+        data: &CreateItemRequest,
+    ) -> Result<Item, ItemError> {
+        LegacyItemRepository::create_item_without_outbox(self, data).await
+    }
+
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork + '_>, ItemError> {
+        LegacyItemRepository::begin(self).await
+    }
+
+    async fn list_items(
+        &self,
+        limit: i64,
+        cursor: Option<&str>,
+        filter: &ItemListFilter,
+    ) -> Result<PaginatedResponse<Item>, ItemError> {
+        LegacyItemRepository::list_items(self, limit, cursor, filter).await
+    }
+
+    fn stream_items(&self) -> BoxStream<'static, Result<Item, ItemError>> {
+        LegacyItemRepository::stream_items(self)
+    }
+
+    fn stream_item_changes(
+        &self,
+        after: Option<ItemPosition>,
+    ) -> BoxStream<'static, Result<Item, ItemError>> {
+        LegacyItemRepository::stream_item_changes(self, after)
+    }
+
+    async fn export_bookmark(&self, name: &str) -> Result<ExportBookmark, ItemError> {
+        LegacyItemRepository::export_bookmark(self, name).await
+    }
+
+    async fn acknowledge_export_bookmark(
+        &self,
+        name: &str,
+        position: &ItemPosition,
+    ) -> Result<ExportBookmark, ItemError> {
+        LegacyItemRepository::acknowledge_export_bookmark(self, name, position).await
+    }
+
+    async fn find_item_by_hash(&self, hash: &str) -> Result<Option<Item>, ItemError> {
+        LegacyItemRepository::find_item_by_hash(self, hash).await
+    }
+
+    async fn search_items(&self, query: &str, limit: i64) -> Result<Vec<ItemSearchHit>, ItemError> {
+        LegacyItemRepository::search_items(self, query, limit).await
+    }
+
+    async fn update_item(
+        &self,
+        id: &str,
+        data: &CreateItemRequest,
+        expected_version: i64,
+    ) -> Result<Item, ItemError> {
+        LegacyItemRepository::update_item(self, id, data, expected_version).await
+    }
+
+    async fn soft_delete_item(&self, id: &str) -> Result<Option<Item>, ItemError> {
+        LegacyItemRepository::soft_delete_item(self, id).await
+    }
+
+    async fn purge_deleted_items(&self, deleted_before: DateTime<Utc>) -> Result<u64, ItemError> {
+        LegacyItemRepository::purge_deleted_items(self, deleted_before).await
+    }
+
+    async fn reencrypt_content(
+        &self,
+        after: Option<&str>,
+        limit: i64,
+    ) -> Result<ReencryptedBatch, ItemError> {
+        LegacyItemRepository::reencrypt_content(self, after, limit).await
+    }
+
+    async fn delete_item(&self, id: &str) -> Result<bool, ItemError> {
+        LegacyItemRepository::delete_item(self, id).await
+    }
+}
+
+#[allow(deprecated)]
+#[async_trait]
+impl<T: LegacyItemRepository> SubmissionRepository for T {
+    async fn update_blockchain_status(
+        &self,
+        id: &str,
+        status: BlockchainStatus,
+        signature: Option<&str>,
+        error: Option<&str>,
+        next_retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), ItemError> {
+        LegacyItemRepository::update_blockchain_status(
+            self,
+            id,
+            status,
+            signature,
+            error,
+            next_retry_at,
+        )
+        .await
+    }
+
+    async fn enqueue_solana_outbox_for_item(
+        &self,
+        item_id: &str,
+        payload: &SolanaOutboxPayload,
+    ) -> Result<Item, ItemError> {
+        LegacyItemRepository::enqueue_solana_outbox_for_item(self, item_id, payload).await
+    }
+
+    async fn get_pending_blockchain_items(
+        &self,
+        limit: i64,
+        claim_ttl: Duration,
+    ) -> Result<Vec<Item>, ItemError> {
+        LegacyItemRepository::get_pending_blockchain_items(self, limit, claim_ttl).await
+    }
+
+    async fn increment_retry_count(&self, id: &str) -> Result<i32, ItemError> {
+        LegacyItemRepository::increment_retry_count(self, id).await
+    }
+}
+
+/// Outbox repository for worker queue processing (claim, complete, fail), on top of the
+/// submission bookkeeping it drives.
 #[async_trait]
 #[allow(clippy::too_many_arguments)]
-pub trait OutboxRepository: Send + Sync {
+pub trait OutboxRepository: SubmissionRepository {
     /// Check database connectivity
     async fn health_check(&self) -> Result<(), HealthCheckError>;
 
-    /// Claim pending Solana outbox entries for processing
+    /// Claim pending Solana outbox entries for processing. An entry left `processing`
+    /// for longer than `claim_ttl` belongs to a crashed worker and is claimed again.
     async fn claim_pending_solana_outbox(
         &self,
         limit: i64,
+        claim_ttl: Duration,
     ) -> Result<Vec<SolanaOutboxEntry>, ItemError>;
 
     /// Mark a Solana outbox entry as completed and update item status
@@ -121,6 +558,354 @@ pub trait OutboxRepository: Send + Sync {
         outbox_id: &str,
         blockhash: Option<&str>,
     ) -> Result<(), ItemError>;
+
+    /// Give up on an outbox entry that exhausted its retries: mark the entry and the item
+    /// failed (as [`fail_solana_outbox`](Self::fail_solana_outbox) does) and record the
+    /// submission, with its sticky blockhash, in the dead-letter queue, atomically.
+    async fn dead_letter_solana_outbox(
+        &self,
+        outbox_id: &str,
+        item_id: &str,
+        retry_count: i32,
+        error: &str,
+        attempt_blockhash: Option<Option<&str>>,
+    ) -> Result<FailedSubmission, ItemError>;
+
+    /// Dead-lettered submissions, most recent first; requeued ones only if `include_requeued`
+    async fn list_failed_submissions(
+        &self,
+        limit: i64,
+        include_requeued: bool,
+    ) -> Result<Vec<FailedSubmission>, ItemError>;
+
+    /// Queue a dead-lettered submission again: a new pending outbox entry is created for
+    /// its payload and the item goes back to `pending_submission` with retries reset.
+    /// Fails with `NotFound` for an unknown ID and `InvalidState` if it was already
+    /// requeued or the item is no longer `failed`.
+    async fn requeue_failed_submission(&self, id: &str) -> Result<Item, ItemError>;
+
+    /// Entries per queue state, for operators watching the backlog
+    async fn queue_depth(&self) -> Result<QueueDepth, ItemError>;
+
+    /// Append a submission attempt to the item's attempt history
+    async fn record_submission_attempt(
+        &self,
+        item_id: &str,
+        attempt: &SubmissionAttempt,
+    ) -> Result<(), ItemError>;
+
+    /// Submission attempts of an item, oldest first (empty for an unknown item)
+    async fn list_submission_attempts(
+        &self,
+        item_id: &str,
+    ) -> Result<Vec<SubmissionAttempt>, ItemError>;
+}
+
+/// API key persistence. Only SHA-256 hashes of key secrets are stored.
+#[async_trait]
+pub trait ApiKeyStore: Send + Sync {
+    /// Store a new key with the given secret hash, scopes and quota, confined to `tenant_id`
+    async fn create_api_key(
+        &self,
+        name: &str,
+        key_hash: &str,
+        scopes: &[ApiKeyScope],
+        tenant_id: &str,
+        quota: ApiKeyQuota,
+    ) -> Result<ApiKey, ApiKeyError>;
+
+    /// Look up a key (active or revoked) by its secret hash
+    async fn find_api_key_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>, ApiKeyError>;
+
+    /// List all keys, newest first
+    async fn list_api_keys(&self) -> Result<Vec<ApiKey>, ApiKeyError>;
+
+    /// Revoke a key; revoking an already revoked key keeps the original timestamp
+    async fn revoke_api_key(&self, id: &str) -> Result<ApiKey, ApiKeyError>;
+}
+
+/// Requests counted per API key and UTC day, shared by every instance (quotas)
+#[async_trait]
+pub trait UsageLedger: Send + Sync {
+    /// Count one request of `key_id` on `day` and return its usage for that day and month
+    async fn record_request(&self, key_id: &str, day: NaiveDate)
+    -> Result<QuotaUsage, ApiKeyError>;
+
+    /// Usage of every key that made requests in the month of `day`, up to `day`, by key
+    /// ID, with the name and quota of managed keys
+    async fn usage_on(&self, day: NaiveDate) -> Result<Vec<KeyUsage>, ApiKeyError>;
+}
+
+/// Journal of requests sent with an `Idempotency-Key`, so retries of an accepted
+/// POST replay the original outcome instead of executing twice.
+#[async_trait]
+pub trait RequestJournal: Send + Sync {
+    /// Claim `key` for processing. Returns `None` when this call owns the request
+    /// (new key, or a `processing` entry with the same hash last touched before
+    /// `reclaim_before`, i.e. interrupted by a crash); otherwise the existing entry.
+    async fn begin_request(
+        &self,
+        key: &str,
+        request_hash: &str,
+        reclaim_before: DateTime<Utc>,
+    ) -> Result<Option<RequestJournalEntry>, RequestJournalError>;
+
+    /// Store the response and mark the entry `completed`
+    async fn complete_request(
+        &self,
+        key: &str,
+        response_status: u16,
+        response_body: &str,
+    ) -> Result<(), RequestJournalError>;
+
+    /// Drop a `processing` entry so the request can be retried (e.g. after a server error)
+    async fn abandon_request(&self, key: &str) -> Result<(), RequestJournalError>;
+
+    /// Look up an entry by key
+    async fn get_request(
+        &self,
+        key: &str,
+    ) -> Result<Option<RequestJournalEntry>, RequestJournalError>;
+}
+
+/// Outbound notifications about item status changes (e.g. one webhook endpoint)
+#[async_trait]
+pub trait NotificationClient: Send + Sync {
+    /// Deliver `events` (ordered by log position) as one batch; `Ok` means the receiver
+    /// accepted all of them. Retrying within the call is up to the implementation.
+    async fn notify(&self, events: &[ItemStatusEvent]) -> Result<(), NotificationError>;
+}
+
+/// Message bus the service layer publishes [`DomainEvent`]s to (e.g. NATS). Publishing is
+/// best effort: the service logs and counts a failure, and the operation still succeeds.
+#[async_trait]
+pub trait MessagePublisher: Send + Sync {
+    async fn publish(&self, event: &DomainEvent) -> Result<(), MessagingError>;
+}
+
+/// Inbound side of the message bus (e.g. NATS), read by the message consumer
+#[async_trait]
+pub trait MessageSubscriber: Send + Sync {
+    /// Messages published to `subject` (with the broker's wildcards) from now on; the
+    /// stream ends when the subscription is closed
+    async fn subscribe(
+        &self,
+        subject: &str,
+    ) -> Result<BoxStream<'static, InboundMessage>, MessagingError>;
+
+    /// Park `message`, which could not be handled, on the dead-letter queue with `reason`
+    async fn dead_letter(
+        &self,
+        message: &InboundMessage,
+        reason: &str,
+    ) -> Result<(), MessagingError>;
+}
+
+/// Blob storage for item content too large to keep in the database row (e.g. S3 or MinIO)
+#[async_trait]
+pub trait ObjectStore: Send + Sync {
+    /// Store `content` under `key`, replacing any object already there
+    async fn put(&self, key: &str, content: Bytes) -> Result<(), ObjectStoreError>;
+
+    /// Stream the object stored under `key`, or only the bytes in `range` (end exclusive)
+    /// of it; chunks are fetched as the stream is polled
+    async fn get(
+        &self,
+        key: &str,
+        range: Option<Range<u64>>,
+    ) -> Result<BoxStream<'static, Result<Bytes, ObjectStoreError>>, ObjectStoreError>;
+}
+
+/// Source of configuration secrets (database URL, signing keys, API keys), resolved once
+/// at startup
+#[async_trait]
+pub trait SecretsProvider: Send + Sync {
+    /// Short name for logs, e.g. `env` or `aws-secrets-manager`
+    fn name(&self) -> &'static str;
+
+    /// The secret stored under `name` (e.g. `DATABASE_URL`), or None when there is none
+    async fn get_secret(&self, name: &str) -> Result<Option<String>, SecretsError>;
+}
+
+/// Encryption of item content at rest, applied by the repositories: content is encrypted
+/// before it is written and decrypted after it is read, so callers only see plaintext.
+#[async_trait]
+pub trait EncryptionService: Send + Sync {
+    /// Encrypt `plaintext` for storage under the current keys
+    async fn encrypt(&self, plaintext: &str) -> Result<String, EncryptionError>;
+
+    /// Decrypt a stored value; values stored before encryption was enabled are returned
+    /// as they are
+    async fn decrypt(&self, stored: &str) -> Result<String, EncryptionError>;
+
+    /// Whether `stored` is plaintext or encrypted under a retired master key, and should
+    /// be rewritten by [`ItemRepository::reencrypt_content`]
+    fn needs_reencryption(&self, stored: &str) -> bool;
+}
+
+/// Business KPIs reported by the service layer, for product analytics rather than
+/// operations. Calls must not block: implementations buffer or hand off to a recorder.
+pub trait TelemetrySink: Send + Sync {
+    /// An item was created for `tenant_id`
+    fn item_created(&self, tenant_id: &str);
+
+    /// A submitted item was confirmed on-chain `latency` after it was created
+    fn item_confirmed(&self, latency: Duration);
+
+    /// A submission attempt failed, `reason` being [`BlockchainError::reason`]
+    fn submission_failed(&self, reason: &str);
+}
+
+/// Append-only log of item status events with per-subscription delivery cursors.
+/// Repositories append to it in the same transaction as the status change, so an
+/// event exists if and only if the transition was committed.
+#[async_trait]
+pub trait EventLog: Send + Sync {
+    /// Events with a position greater than `position`, oldest first
+    async fn events_after(
+        &self,
+        position: i64,
+        limit: i64,
+    ) -> Result<Vec<ItemStatusEvent>, NotificationError>;
+
+    /// Position of the last event delivered to `subscription` (0 for a new one)
+    async fn subscription_cursor(&self, subscription: &str) -> Result<i64, NotificationError>;
+
+    /// Advance the cursor of `subscription`; never moves it backwards
+    async fn save_subscription_cursor(
+        &self,
+        subscription: &str,
+        position: i64,
+    ) -> Result<(), NotificationError>;
+
+    /// Events that occurred within `range`, newest first, starting below position
+    /// `before`. `next_cursor` holds the last returned position while more exist.
+    async fn list_events(
+        &self,
+        limit: i64,
+        before: Option<i64>,
+        range: &TimeRange,
+    ) -> Result<PaginatedResponse<ItemStatusEvent>, NotificationError>;
+
+    /// Every event of `item_id`, oldest first
+    async fn item_events(&self, item_id: &str) -> Result<Vec<ItemStatusEvent>, NotificationError>;
+}
+
+/// Audit trail of webhook delivery attempts
+#[async_trait]
+pub trait WebhookDeliveryLog: Send + Sync {
+    /// Record one delivery attempt, successful or not
+    async fn record_webhook_delivery(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> Result<(), NotificationError>;
+
+    /// Attempts made within `range`, newest first, starting below id `before`.
+    /// `next_cursor` holds the last returned id while more exist.
+    async fn list_webhook_deliveries(
+        &self,
+        limit: i64,
+        before: Option<i64>,
+        range: &TimeRange,
+    ) -> Result<PaginatedResponse<WebhookDelivery>, NotificationError>;
+
+    /// Every delivery attempt of the events of `item_id`, oldest first
+    async fn item_deliveries(
+        &self,
+        item_id: &str,
+    ) -> Result<Vec<WebhookDelivery>, NotificationError>;
+}
+
+/// Record of who created, changed or deleted what, listed by `GET /admin/audit`
+#[async_trait]
+pub trait AuditLogger: Send + Sync {
+    /// Append one entry
+    async fn record_audit_entry(&self, entry: &AuditEntry) -> Result<(), NotificationError>;
+
+    /// Entries matching `filter`, newest first, starting below id `before`.
+    /// `next_cursor` holds the last returned id while more exist.
+    async fn list_audit_entries(
+        &self,
+        limit: i64,
+        before: Option<i64>,
+        filter: &AuditFilter,
+    ) -> Result<PaginatedResponse<AuditEntry>, NotificationError>;
+}
+
+/// Fees spent per signer and UTC day, shared by every instance (submission budget)
+#[async_trait]
+pub trait SpendLedger: Send + Sync {
+    /// Amount `signer` has spent on `day` (0 when nothing was recorded)
+    async fn spent_on(&self, signer: &str, day: NaiveDate) -> Result<u64, ItemError>;
+
+    /// Add `amount` to the spend of `signer` on `day` and return the new total
+    async fn record_spend(
+        &self,
+        signer: &str,
+        day: NaiveDate,
+        amount: u64,
+    ) -> Result<u64, ItemError>;
+}
+
+/// Time-limited leases naming the one instance that runs a singleton job. A holder keeps
+/// the lease by renewing it before it expires; once it lapses any instance can take it.
+#[async_trait]
+pub trait LeaderElection: Send + Sync {
+    /// Take lease `name` for `holder`, or renew it when `holder` already has it, until
+    /// `ttl` from now. True when `holder` is the leader afterwards.
+    async fn try_acquire_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> Result<bool, ItemError>;
+
+    /// Give up lease `name` if `holder` has it, so another instance can take over at once
+    async fn release_lease(&self, name: &str, holder: &str) -> Result<(), ItemError>;
+}
+
+/// Status records of background jobs (`GET /jobs/{id}`)
+#[async_trait]
+pub trait JobStore: Send + Sync {
+    /// Record a new `queued` job of `kind`
+    async fn create_job(&self, kind: &str) -> Result<Job, JobError>;
+
+    /// Mark a job `running` and set its progress counters
+    async fn update_job_progress(
+        &self,
+        id: &str,
+        processed: i64,
+        failed: i64,
+    ) -> Result<(), JobError>;
+
+    /// Move a job to a finished status with its outcome (`result`) or failure (`error`)
+    async fn finish_job(
+        &self,
+        id: &str,
+        status: JobStatus,
+        result: Option<&serde_json::Value>,
+        error: Option<&str>,
+    ) -> Result<Job, JobError>;
+
+    async fn get_job(&self, id: &str) -> Result<Option<Job>, JobError>;
+}
+
+/// Source of the current time and of delays, so backoff and scheduling can be driven by
+/// a controllable clock in tests
+#[async_trait]
+pub trait Clock: Send + Sync {
+    /// Current wall-clock time
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Wait until `duration` has passed on this clock
+    async fn sleep(&self, duration: Duration);
+}
+
+/// Source of new row IDs, so their format can be chosen per deployment
+pub trait IdGenerator: Send + Sync {
+    /// New ID for a row of `kind` (e.g. `item`), without the `item_` prefix. IDs should
+    /// sort in creation order, which keeps inserts at the end of the primary key index.
+    fn generate(&self, kind: &'static str) -> String;
 }
 
 /// Blockchain client trait for chain operations
@@ -146,6 +931,17 @@ pub trait BlockchainClient: Send + Sync {
         ))
     }
 
+    /// Read a landed transaction back (None when the chain does not know the signature)
+    async fn get_transaction(
+        &self,
+        signature: &str,
+    ) -> Result<Option<OnChainTransaction>, BlockchainError> {
+        let _ = signature;
+        Err(BlockchainError::SubmissionFailed(
+            "get_transaction not implemented".to_string(),
+        ))
+    }
+
     /// Get current block height
     async fn get_block_height(&self) -> Result<u64, BlockchainError> {
         Err(BlockchainError::SubmissionFailed(
@@ -153,6 +949,20 @@ pub trait BlockchainClient: Send + Sync {
         ))
     }
 
+    /// Fee payer balance in the chain's smallest unit (lamports on Solana)
+    async fn get_balance(&self) -> Result<u64, BlockchainError> {
+        Err(BlockchainError::SubmissionFailed(
+            "get_balance not implemented".to_string(),
+        ))
+    }
+
+    /// Fee the next submission would pay, including the priority fee it would offer
+    async fn estimate_fee(&self) -> Result<FeeEstimate, BlockchainError> {
+        Err(BlockchainError::SubmissionFailed(
+            "estimate_fee not implemented".to_string(),
+        ))
+    }
+
     /// Get latest blockhash for transaction construction
     async fn get_latest_blockhash(&self) -> Result<String, BlockchainError> {
         Err(BlockchainError::SubmissionFailed(
@@ -194,14 +1004,74 @@ mod tests {
             Ok(Item::default())
         }
 
+        async fn create_item_without_outbox(
+            &self,
+            _data: &CreateItemRequest,
+        ) -> Result<Item, ItemError> {
+            Ok(Item::default())
+        }
+
+        async fn begin(&self) -> Result<Box<dyn UnitOfWork + '_>, ItemError> {
+            Err(ItemError::RepositoryFailure)
+        }
+
         async fn list_items(
             &self,
             _limit: i64,
             _cursor: Option<&str>,
+            _filter: &ItemListFilter,
         ) -> Result<PaginatedResponse<Item>, ItemError> {
             Ok(PaginatedResponse::empty())
         }
 
+        fn stream_items(&self) -> BoxStream<'static, Result<Item, ItemError>> {
+            Box::pin(futures::stream::empty())
+        }
+
+        fn stream_item_changes(
+            &self,
+            _after: Option<ItemPosition>,
+        ) -> BoxStream<'static, Result<Item, ItemError>> {
+            Box::pin(futures::stream::empty())
+        }
+
+        async fn export_bookmark(&self, name: &str) -> Result<ExportBookmark, ItemError> {
+            Err(ItemError::NotFound(name.to_string()))
+        }
+
+        async fn acknowledge_export_bookmark(
+            &self,
+            name: &str,
+            _position: &ItemPosition,
+        ) -> Result<ExportBookmark, ItemError> {
+            Err(ItemError::NotFound(name.to_string()))
+        }
+
+        async fn search_items(
+            &self,
+            _query: &str,
+            _limit: i64,
+        ) -> Result<Vec<ItemSearchHit>, ItemError> {
+            Ok(Vec::new())
+        }
+
+        async fn soft_delete_item(&self, _id: &str) -> Result<Option<Item>, ItemError> {
+            Ok(None)
+        }
+
+        async fn purge_deleted_items(
+            &self,
+            _deleted_before: DateTime<Utc>,
+        ) -> Result<u64, ItemError> {
+            Ok(0)
+        }
+    }
+
+    #[allow(dead_code)]
+    struct MinimalOutboxRepository;
+
+    #[async_trait]
+    impl SubmissionRepository for MinimalOutboxRepository {
         async fn update_blockchain_status(
             &self,
             _id: &str,
@@ -221,7 +1091,11 @@ mod tests {
             Ok(Item::default())
         }
 
-        async fn get_pending_blockchain_items(&self, _limit: i64) -> Result<Vec<Item>, ItemError> {
+        async fn get_pending_blockchain_items(
+            &self,
+            _limit: i64,
+            _claim_ttl: Duration,
+        ) -> Result<Vec<Item>, ItemError> {
             Ok(vec![])
         }
 
@@ -230,9 +1104,6 @@ mod tests {
         }
     }
 
-    #[allow(dead_code)]
-    struct MinimalOutboxRepository;
-
     #[async_trait]
     impl OutboxRepository for MinimalOutboxRepository {
         async fn health_check(&self) -> Result<(), HealthCheckError> {
@@ -242,6 +1113,7 @@ mod tests {
         async fn claim_pending_solana_outbox(
             &self,
             _limit: i64,
+            _claim_ttl: Duration,
         ) -> Result<Vec<SolanaOutboxEntry>, ItemError> {
             Ok(vec![])
         }
@@ -276,6 +1148,48 @@ mod tests {
         ) -> Result<(), ItemError> {
             Ok(())
         }
+
+        async fn dead_letter_solana_outbox(
+            &self,
+            outbox_id: &str,
+            _item_id: &str,
+            _retry_count: i32,
+            _error: &str,
+            _attempt_blockhash: Option<Option<&str>>,
+        ) -> Result<FailedSubmission, ItemError> {
+            Err(ItemError::NotFound(outbox_id.to_string()))
+        }
+
+        async fn list_failed_submissions(
+            &self,
+            _limit: i64,
+            _include_requeued: bool,
+        ) -> Result<Vec<FailedSubmission>, ItemError> {
+            Ok(vec![])
+        }
+
+        async fn requeue_failed_submission(&self, id: &str) -> Result<Item, ItemError> {
+            Err(ItemError::NotFound(id.to_string()))
+        }
+
+        async fn queue_depth(&self) -> Result<QueueDepth, ItemError> {
+            Ok(QueueDepth::default())
+        }
+
+        async fn record_submission_attempt(
+            &self,
+            _item_id: &str,
+            _attempt: &SubmissionAttempt,
+        ) -> Result<(), ItemError> {
+            Ok(())
+        }
+
+        async fn list_submission_attempts(
+            &self,
+            _item_id: &str,
+        ) -> Result<Vec<SubmissionAttempt>, ItemError> {
+            Ok(vec![])
+        }
     }
 
     struct MinimalBlockchainClient;
@@ -303,9 +1217,11 @@ mod tests {
             description: None,
             content: "content".to_string(),
             metadata: None,
+            content_key: None,
+            content_type: None,
         };
 
-        let result = repo.update_item("id", &request).await;
+        let result = repo.update_item("id", &request, 1).await;
         assert!(matches!(result, Err(ItemError::InvalidState(_))));
     }
 
@@ -323,6 +1239,13 @@ mod tests {
         assert!(matches!(result, Err(BlockchainError::SubmissionFailed(_))));
     }
 
+    #[tokio::test]
+    async fn test_blockchain_client_get_transaction_not_supported() {
+        let client = MinimalBlockchainClient;
+        let result = client.get_transaction("sig").await;
+        assert!(matches!(result, Err(BlockchainError::SubmissionFailed(_))));
+    }
+
     #[tokio::test]
     async fn test_blockchain_client_get_block_height_not_supported() {
         let client = MinimalBlockchainClient;
@@ -330,6 +1253,12 @@ mod tests {
         assert!(matches!(result, Err(BlockchainError::SubmissionFailed(_))));
     }
 
+    #[tokio::test]
+    async fn test_blockchain_client_get_balance_not_supported() {
+        let client = MinimalBlockchainClient;
+        assert!(client.get_balance().await.is_err());
+    }
+
     #[tokio::test]
     async fn test_blockchain_client_get_latest_blockhash_not_supported() {
         let client = MinimalBlockchainClient;
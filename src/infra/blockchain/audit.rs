@@ -0,0 +1,190 @@
+//! Auditing decorator for any [`TransactionSigner`].
+//!
+//! Every signing operation, successful or not, is written to the `audit` log target with the
+//! key ID, item ID, content hash and timestamp, and counted in `signatures_total`. Item ID and
+//! hash come from the caller's [`SigningContext`]; operations outside a submission (e.g. ad-hoc
+//! signing in tooling) are logged with `item_id="-"` and the SHA-256 of the signed message.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use crate::domain::{BlockchainError, SignatureScheme, SigningContext, TransactionSigner};
+
+/// Log target for key usage records (route it to the compliance sink)
+pub const AUDIT_LOG_TARGET: &str = "audit";
+
+/// Signer decorator that records each signing operation
+pub struct AuditingSigner {
+    inner: Arc<dyn TransactionSigner>,
+    key_id: String,
+}
+
+impl AuditingSigner {
+    /// Wrap `inner`; `key_id` identifies the key in audit records (e.g. the KMS key ID)
+    pub fn new(inner: Arc<dyn TransactionSigner>, key_id: impl Into<String>) -> Self {
+        Self {
+            inner,
+            key_id: key_id.into(),
+        }
+    }
+
+    /// Key ID written to audit records
+    #[must_use]
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for AuditingSigner {
+    async fn sign_message(&self, message: &[u8]) -> Result<String, BlockchainError> {
+        let context = SigningContext::current();
+        let result = self.inner.sign_message(message).await;
+
+        let outcome = if result.is_ok() { "success" } else { "failure" };
+        metrics::counter!(
+            "signatures_total",
+            "key_id" => self.key_id.clone(),
+            "outcome" => outcome
+        )
+        .increment(1);
+
+        let item_id = context.item_id.as_deref().unwrap_or("-");
+        let hash = context
+            .hash
+            .unwrap_or_else(|| format!("{:x}", Sha256::digest(message)));
+        let signed_at = Utc::now().to_rfc3339();
+        match &result {
+            Ok(_) => info!(
+                target: AUDIT_LOG_TARGET,
+                key_id = %self.key_id,
+                item_id = %item_id,
+                hash = %hash,
+                signed_at = %signed_at,
+                outcome,
+                "Signing operation"
+            ),
+            Err(e) => warn!(
+                target: AUDIT_LOG_TARGET,
+                key_id = %self.key_id,
+                item_id = %item_id,
+                hash = %hash,
+                signed_at = %signed_at,
+                outcome,
+                error = %e,
+                "Signing operation"
+            ),
+        }
+        result
+    }
+
+    fn public_key(&self) -> String {
+        self.inner.public_key()
+    }
+
+    fn scheme(&self) -> SignatureScheme {
+        self.inner.scheme()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::LocalSigner;
+    use secrecy::SecretString;
+    use std::sync::Mutex;
+
+    /// Collects formatted log lines written by the test subscriber
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl CapturedLogs {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    struct FailingSigner;
+
+    #[async_trait]
+    impl TransactionSigner for FailingSigner {
+        async fn sign_message(&self, _message: &[u8]) -> Result<String, BlockchainError> {
+            Err(BlockchainError::SubmissionFailed(
+                "KMS Sign failed".to_string(),
+            ))
+        }
+
+        fn public_key(&self) -> String {
+            "failing".to_string()
+        }
+    }
+
+    fn capture() -> (CapturedLogs, tracing::subscriber::DefaultGuard) {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+        (logs, tracing::subscriber::set_default(subscriber))
+    }
+
+    fn local_signer() -> Arc<dyn TransactionSigner> {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let secret = SecretString::from(bs58::encode(key.to_bytes()).into_string());
+        Arc::new(LocalSigner::new(secret).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_records_item_and_hash_from_context() {
+        let inner = local_signer();
+        let signer = AuditingSigner::new(Arc::clone(&inner), "local-key");
+        let (logs, _guard) = capture();
+
+        let signature = SigningContext::for_item("item_1", "hash_abc")
+            .scope(signer.sign_message(b"payload"))
+            .await
+            .unwrap();
+
+        // Signatures and public key pass through unchanged
+        assert_eq!(signature, inner.sign_message(b"payload").await.unwrap());
+        assert_eq!(signer.public_key(), inner.public_key());
+
+        let logs = logs.contents();
+        assert!(logs.contains("audit"));
+        assert!(logs.contains("key_id=local-key"));
+        assert!(logs.contains("item_id=item_1"));
+        assert!(logs.contains("hash=hash_abc"));
+        assert!(logs.contains("signed_at="));
+        assert!(logs.contains("outcome=\"success\""));
+    }
+
+    #[tokio::test]
+    async fn test_records_failures_and_unattributed_operations() {
+        let signer = AuditingSigner::new(Arc::new(FailingSigner), "kms-key");
+        let (logs, _guard) = capture();
+
+        assert!(signer.sign_message(b"payload").await.is_err());
+
+        let logs = logs.contents();
+        assert!(logs.contains("WARN"));
+        assert!(logs.contains("item_id=-"));
+        assert!(logs.contains(&format!("hash={:x}", Sha256::digest(b"payload"))));
+        assert!(logs.contains("outcome=\"failure\""));
+    }
+}
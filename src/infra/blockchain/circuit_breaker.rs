@@ -0,0 +1,361 @@
+//! Circuit breaker decorator for any [`BlockchainClient`].
+//!
+//! After `failure_threshold` consecutive connectivity failures (network errors and
+//! timeouts) the circuit opens and calls fail fast with [`BlockchainError::CircuitOpen`]
+//! instead of waiting out RPC timeouts. After `open_duration` a single trial call is let
+//! through (half-open): success closes the circuit, failure re-opens it. Outbox entries
+//! rejected while open are rescheduled by the retry worker.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use tracing::{info, warn};
+
+use crate::domain::{
+    BlockchainClient, BlockchainError, FeeEstimate, HealthCheckError, OnChainTransaction,
+};
+
+/// Circuit breaker configuration
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive connectivity failures before the circuit opens
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a half-open trial call
+    pub open_duration: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    /// Create config from environment variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let failure_threshold = std::env::var("BLOCKCHAIN_CB_FAILURE_THRESHOLD")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(defaults.failure_threshold);
+        let open_duration = std::env::var("BLOCKCHAIN_CB_OPEN_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(defaults.open_duration);
+        Self {
+            failure_threshold,
+            open_duration,
+        }
+    }
+}
+
+/// Circuit state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls pass through
+    Closed,
+    /// Calls fail fast
+    Open,
+    /// One trial call is in flight
+    HalfOpen,
+}
+
+impl CircuitState {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+
+    /// Gauge value for `blockchain_circuit_state`
+    fn as_gauge(self) -> f64 {
+        match self {
+            Self::Closed => 0.0,
+            Self::Open => 1.0,
+            Self::HalfOpen => 2.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Breaker {
+    state: CircuitState,
+    consecutive_failures: u32,
+    /// When the circuit entered Open/HalfOpen (a stalled trial call is retried after `open_duration`)
+    since: Option<Instant>,
+}
+
+/// Blockchain client decorator that fails fast while the RPC is down
+pub struct CircuitBreakerBlockchainClient {
+    inner: Arc<dyn BlockchainClient>,
+    config: CircuitBreakerConfig,
+    breaker: Mutex<Breaker>,
+}
+
+/// Only connectivity failures trip the breaker; RPC-level rejections
+/// (expired blockhash, insufficient funds) mean the node is reachable.
+fn is_connectivity_failure(e: &BlockchainError) -> bool {
+    matches!(
+        e,
+        BlockchainError::NetworkError { .. } | BlockchainError::Timeout { .. }
+    )
+}
+
+impl CircuitBreakerBlockchainClient {
+    #[must_use]
+    pub fn new(inner: Arc<dyn BlockchainClient>, config: CircuitBreakerConfig) -> Self {
+        metrics::gauge!("blockchain_circuit_state").set(CircuitState::Closed.as_gauge());
+        Self {
+            inner,
+            config,
+            breaker: Mutex::new(Breaker {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                since: None,
+            }),
+        }
+    }
+
+    /// Current circuit state
+    #[must_use]
+    pub fn state(&self) -> CircuitState {
+        self.breaker.lock().unwrap().state
+    }
+
+    fn transition(breaker: &mut Breaker, to: CircuitState) {
+        if breaker.state != to {
+            match to {
+                CircuitState::Open => warn!(
+                    failures = breaker.consecutive_failures,
+                    "Blockchain circuit opened"
+                ),
+                CircuitState::HalfOpen => {
+                    info!("Blockchain circuit half-open, sending trial call");
+                }
+                CircuitState::Closed => info!("Blockchain circuit closed"),
+            }
+            metrics::gauge!("blockchain_circuit_state").set(to.as_gauge());
+            metrics::counter!("blockchain_circuit_transitions_total", "to" => to.as_str())
+                .increment(1);
+        }
+        breaker.state = to;
+        breaker.since = (to != CircuitState::Closed).then(Instant::now);
+    }
+
+    /// Admit a call or reject it while the circuit is open
+    fn acquire(&self) -> Result<(), BlockchainError> {
+        let mut breaker = self.breaker.lock().unwrap();
+        match breaker.state {
+            CircuitState::Closed => Ok(()),
+            CircuitState::Open | CircuitState::HalfOpen
+                if breaker
+                    .since
+                    .is_some_and(|t| t.elapsed() >= self.config.open_duration) =>
+            {
+                Self::transition(&mut breaker, CircuitState::HalfOpen);
+                Ok(())
+            }
+            CircuitState::Open | CircuitState::HalfOpen => {
+                metrics::counter!("blockchain_circuit_rejections_total").increment(1);
+                Err(BlockchainError::CircuitOpen)
+            }
+        }
+    }
+
+    fn record<T>(&self, result: &Result<T, BlockchainError>) {
+        let mut breaker = self.breaker.lock().unwrap();
+        match result {
+            Err(e) if is_connectivity_failure(e) => {
+                breaker.consecutive_failures += 1;
+                if breaker.state == CircuitState::HalfOpen
+                    || breaker.consecutive_failures >= self.config.failure_threshold
+                {
+                    Self::transition(&mut breaker, CircuitState::Open);
+                }
+            }
+            _ => {
+                breaker.consecutive_failures = 0;
+                Self::transition(&mut breaker, CircuitState::Closed);
+            }
+        }
+    }
+
+    async fn call<T, F>(&self, f: F) -> Result<T, BlockchainError>
+    where
+        F: std::future::Future<Output = Result<T, BlockchainError>>,
+    {
+        self.acquire()?;
+        let result = f.await;
+        self.record(&result);
+        result
+    }
+}
+
+#[async_trait]
+impl BlockchainClient for CircuitBreakerBlockchainClient {
+    /// Health checks always reach the RPC so probes report the real state.
+    async fn health_check(&self) -> Result<(), HealthCheckError> {
+        self.inner.health_check().await
+    }
+
+    async fn submit_transaction(
+        &self,
+        hash: &str,
+        existing_blockhash: Option<&str>,
+    ) -> Result<(String, String), BlockchainError> {
+        self.call(self.inner.submit_transaction(hash, existing_blockhash))
+            .await
+    }
+
+    async fn get_transaction_status(&self, signature: &str) -> Result<bool, BlockchainError> {
+        self.call(self.inner.get_transaction_status(signature))
+            .await
+    }
+
+    async fn get_transaction(
+        &self,
+        signature: &str,
+    ) -> Result<Option<OnChainTransaction>, BlockchainError> {
+        self.call(self.inner.get_transaction(signature)).await
+    }
+
+    async fn get_block_height(&self) -> Result<u64, BlockchainError> {
+        self.call(self.inner.get_block_height()).await
+    }
+
+    async fn get_balance(&self) -> Result<u64, BlockchainError> {
+        self.call(self.inner.get_balance()).await
+    }
+
+    async fn estimate_fee(&self) -> Result<FeeEstimate, BlockchainError> {
+        self.call(self.inner.estimate_fee()).await
+    }
+
+    async fn get_latest_blockhash(&self) -> Result<String, BlockchainError> {
+        self.call(self.inner.get_latest_blockhash()).await
+    }
+
+    async fn wait_for_confirmation(
+        &self,
+        signature: &str,
+        timeout_secs: u64,
+    ) -> Result<bool, BlockchainError> {
+        self.call(self.inner.wait_for_confirmation(signature, timeout_secs))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// Inner client whose connectivity can be toggled; counts calls that reach it
+    struct FlakyClient {
+        down: AtomicBool,
+        calls: AtomicUsize,
+    }
+
+    impl FlakyClient {
+        fn new(down: bool) -> Arc<Self> {
+            Arc::new(Self {
+                down: AtomicBool::new(down),
+                calls: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl BlockchainClient for FlakyClient {
+        async fn health_check(&self) -> Result<(), HealthCheckError> {
+            Ok(())
+        }
+
+        async fn submit_transaction(
+            &self,
+            _hash: &str,
+            _existing_blockhash: Option<&str>,
+        ) -> Result<(String, String), BlockchainError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.down.load(Ordering::SeqCst) {
+                return Err(BlockchainError::NetworkError {
+                    message: "connection refused".to_string(),
+                    blockhash: String::new(),
+                });
+            }
+            Ok(("sig".to_string(), "blockhash".to_string()))
+        }
+    }
+
+    fn breaker(inner: Arc<FlakyClient>, open_duration: Duration) -> CircuitBreakerBlockchainClient {
+        CircuitBreakerBlockchainClient::new(
+            inner,
+            CircuitBreakerConfig {
+                failure_threshold: 2,
+                open_duration,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_opens_after_threshold_and_fails_fast() {
+        let inner = FlakyClient::new(true);
+        let client = breaker(Arc::clone(&inner), Duration::from_secs(60));
+
+        assert!(client.submit_transaction("h", None).await.is_err());
+        assert_eq!(client.state(), CircuitState::Closed);
+        assert!(client.submit_transaction("h", None).await.is_err());
+        assert_eq!(client.state(), CircuitState::Open);
+
+        let result = client.submit_transaction("h", None).await;
+        assert!(matches!(result, Err(BlockchainError::CircuitOpen)));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_half_open_success_closes_circuit() {
+        let inner = FlakyClient::new(true);
+        let client = breaker(Arc::clone(&inner), Duration::ZERO);
+        let _ = client.submit_transaction("h", None).await;
+        let _ = client.submit_transaction("h", None).await;
+        assert_eq!(client.state(), CircuitState::Open);
+
+        inner.down.store(false, Ordering::SeqCst);
+        assert!(client.submit_transaction("h", None).await.is_ok());
+        assert_eq!(client.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_half_open_failure_reopens_circuit() {
+        let inner = FlakyClient::new(true);
+        let client = breaker(Arc::clone(&inner), Duration::ZERO);
+        let _ = client.submit_transaction("h", None).await;
+        let _ = client.submit_transaction("h", None).await;
+
+        let result = client.submit_transaction("h", None).await;
+        assert!(matches!(result, Err(BlockchainError::NetworkError { .. })));
+        assert_eq!(client.state(), CircuitState::Open);
+    }
+
+    #[tokio::test]
+    async fn test_non_connectivity_errors_do_not_trip() {
+        let client = CircuitBreakerBlockchainClient::new(
+            Arc::new(crate::test_utils::MockBlockchainClient::failing("rejected")),
+            CircuitBreakerConfig {
+                failure_threshold: 1,
+                open_duration: Duration::from_secs(60),
+            },
+        );
+        let _ = client.submit_transaction("h", None).await;
+        let _ = client.submit_transaction("h", None).await;
+        assert_eq!(client.state(), CircuitState::Closed);
+    }
+}
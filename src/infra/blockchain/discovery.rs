@@ -0,0 +1,430 @@
+//! RPC endpoint list shared by the Solana client, and its periodic discovery.
+//!
+//! [`RpcEndpoints`] is the ordered list the client fails over across. It starts from
+//! `SOLANA_RPC_URL` (comma-separated) and, when `SOLANA_RPC_DISCOVERY` is set, an
+//! [`EndpointDiscovery`] task replaces it from one of:
+//!
+//! * `srv:_solana-rpc._tcp.example.com`: SRV records, lowest priority first and the
+//!   heaviest first within a priority, as `https://target:port` (`http` for `_http._tcp`)
+//! * `txt:rpc.example.com`: TXT records holding URLs separated by commas or spaces
+//! * `https://config.example.com/rpc.json`: a JSON array of URLs, or `{"endpoints": [...]}`
+//!
+//! A lookup that fails or finds no valid URL keeps the current list, so a DNS outage
+//! never leaves the client without endpoints.
+
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use hickory_resolver::TokioAsyncResolver;
+use reqwest::Client;
+use serde::Deserialize;
+use thiserror::Error;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+/// Default interval between discovery lookups
+pub const DEFAULT_DISCOVERY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Lookup failure; the endpoint list is left unchanged
+#[derive(Debug, Error)]
+#[error("RPC endpoint discovery failed: {0}")]
+pub struct DiscoveryError(String);
+
+#[derive(Debug)]
+struct EndpointList {
+    urls: Vec<String>,
+    /// Index of the endpoint that answered last; requests try it first
+    preferred: usize,
+}
+
+/// Ordered RPC endpoints, shared between the client and the discovery task
+#[derive(Debug, Clone)]
+pub struct RpcEndpoints {
+    inner: Arc<RwLock<EndpointList>>,
+}
+
+impl RpcEndpoints {
+    /// Endpoints in failover order
+    #[must_use]
+    pub fn new(urls: Vec<String>) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(EndpointList { urls, preferred: 0 })),
+        }
+    }
+
+    /// Parse a comma-separated list such as `SOLANA_RPC_URL`
+    #[must_use]
+    pub fn from_list(list: &str) -> Self {
+        Self::new(parse_endpoint_list(list))
+    }
+
+    /// Current endpoints in failover order
+    #[must_use]
+    pub fn urls(&self) -> Vec<String> {
+        self.inner.read().expect("endpoints lock").urls.clone()
+    }
+
+    /// Endpoints in the order a request tries them: the last one that answered first,
+    /// then the rest in list order after it
+    #[must_use]
+    pub fn attempt_order(&self) -> Vec<String> {
+        let list = self.inner.read().expect("endpoints lock");
+        let start = list.preferred.min(list.urls.len().saturating_sub(1));
+        list.urls[start..]
+            .iter()
+            .chain(&list.urls[..start])
+            .cloned()
+            .collect()
+    }
+
+    /// Remember `url` as the endpoint to try first
+    pub fn mark_answered(&self, url: &str) {
+        let mut list = self.inner.write().expect("endpoints lock");
+        if let Some(index) = list.urls.iter().position(|u| u == url) {
+            list.preferred = index;
+        }
+    }
+
+    /// Replace the list, keeping the preferred endpoint if it is still present.
+    /// Returns whether the list changed.
+    pub fn replace(&self, urls: Vec<String>) -> bool {
+        let mut list = self.inner.write().expect("endpoints lock");
+        if list.urls == urls {
+            return false;
+        }
+        let preferred = list.urls.get(list.preferred).cloned();
+        list.preferred = preferred
+            .and_then(|url| urls.iter().position(|u| *u == url))
+            .unwrap_or(0);
+        list.urls = urls;
+        true
+    }
+}
+
+/// Where [`EndpointDiscovery`] reads the endpoint list from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EndpointSource {
+    /// SRV records for this name
+    DnsSrv(String),
+    /// TXT records for this name
+    DnsTxt(String),
+    /// URL returning the list as JSON
+    Url(String),
+}
+
+impl std::str::FromStr for EndpointSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(name) = s.strip_prefix("srv:") {
+            Ok(Self::DnsSrv(name.to_string()))
+        } else if let Some(name) = s.strip_prefix("txt:") {
+            Ok(Self::DnsTxt(name.to_string()))
+        } else if is_endpoint_url(s) {
+            Ok(Self::Url(s.to_string()))
+        } else {
+            Err(format!(
+                "Invalid RPC discovery source '{}': use srv:<name>, txt:<name> or an http(s) URL",
+                s
+            ))
+        }
+    }
+}
+
+/// Body of a discovery URL
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DiscoveryDocument {
+    List(Vec<String>),
+    Object { endpoints: Vec<String> },
+}
+
+/// Periodically resolves an [`EndpointSource`] into an [`RpcEndpoints`] list
+pub struct EndpointDiscovery {
+    source: EndpointSource,
+    endpoints: RpcEndpoints,
+    http_client: Client,
+    resolver: Option<TokioAsyncResolver>,
+}
+
+impl EndpointDiscovery {
+    /// Discovery updating `endpoints` from `source` (DNS sources use the system resolver)
+    pub fn new(source: EndpointSource, endpoints: RpcEndpoints) -> Result<Self, DiscoveryError> {
+        let resolver = match source {
+            EndpointSource::Url(_) => None,
+            EndpointSource::DnsSrv(_) | EndpointSource::DnsTxt(_) => Some(
+                TokioAsyncResolver::tokio_from_system_conf()
+                    .map_err(|e| DiscoveryError(e.to_string()))?,
+            ),
+        };
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|e| DiscoveryError(e.to_string()))?;
+        Ok(Self {
+            source,
+            endpoints,
+            http_client,
+            resolver,
+        })
+    }
+
+    /// Look the endpoints up once, without changing the list
+    pub async fn lookup(&self) -> Result<Vec<String>, DiscoveryError> {
+        let urls = match (&self.source, &self.resolver) {
+            (EndpointSource::DnsSrv(name), Some(resolver)) => {
+                let lookup = resolver
+                    .srv_lookup(name.as_str())
+                    .await
+                    .map_err(|e| DiscoveryError(e.to_string()))?;
+                let records = lookup
+                    .iter()
+                    .map(|srv| SrvRecord {
+                        priority: srv.priority(),
+                        weight: srv.weight(),
+                        target: srv.target().to_utf8(),
+                        port: srv.port(),
+                    })
+                    .collect();
+                endpoints_from_srv(name, records)
+            }
+            (EndpointSource::DnsTxt(name), Some(resolver)) => {
+                let lookup = resolver
+                    .txt_lookup(name.as_str())
+                    .await
+                    .map_err(|e| DiscoveryError(e.to_string()))?;
+                let text: Vec<String> = lookup
+                    .iter()
+                    .map(|txt| {
+                        txt.iter()
+                            .map(|part| String::from_utf8_lossy(part).into_owned())
+                            .collect()
+                    })
+                    .collect();
+                parse_endpoint_list(&text.join(","))
+            }
+            (EndpointSource::Url(url), _) => {
+                let document: DiscoveryDocument = self
+                    .http_client
+                    .get(url)
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .map_err(|e| DiscoveryError(e.to_string()))?
+                    .json()
+                    .await
+                    .map_err(|e| DiscoveryError(e.to_string()))?;
+                let (DiscoveryDocument::List(urls) | DiscoveryDocument::Object { endpoints: urls }) =
+                    document;
+                parse_endpoint_list(&urls.join(","))
+            }
+            _ => return Err(DiscoveryError("DNS resolver not configured".to_string())),
+        };
+        if urls.is_empty() {
+            return Err(DiscoveryError(format!(
+                "{:?} returned no http(s) endpoints",
+                self.source
+            )));
+        }
+        Ok(urls)
+    }
+
+    /// Look the endpoints up and replace the list; returns whether it changed
+    pub async fn refresh(&self) -> Result<bool, DiscoveryError> {
+        let urls = self.lookup().await?;
+        let changed = self.endpoints.replace(urls.clone());
+        if changed {
+            info!(endpoints = ?urls, "RPC endpoint list updated");
+            metrics::counter!("rpc_endpoint_list_updates_total").increment(1);
+        }
+        metrics::gauge!("rpc_endpoints").set(urls.len() as f64);
+        Ok(changed)
+    }
+
+    /// Refresh every `interval` until shutdown; failures keep the current list
+    pub async fn run(self, interval: Duration, mut shutdown_rx: watch::Receiver<bool>) {
+        info!(source = ?self.source, interval_secs = interval.as_secs(), "RPC endpoint discovery started");
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(interval) => {
+                    if let Err(e) = self.refresh().await {
+                        metrics::counter!("rpc_endpoint_discovery_failures_total").increment(1);
+                        warn!(error = %e, "Keeping the current RPC endpoint list");
+                    }
+                }
+                _ = shutdown_rx.changed() => {
+                    info!("RPC endpoint discovery shutting down");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Spawn discovery as a tokio task
+pub fn spawn_endpoint_discovery(
+    discovery: EndpointDiscovery,
+    interval: Duration,
+) -> (tokio::task::JoinHandle<()>, watch::Sender<bool>) {
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let handle = tokio::spawn(discovery.run(interval, shutdown_rx));
+    (handle, shutdown_tx)
+}
+
+#[derive(Debug, Clone)]
+struct SrvRecord {
+    priority: u16,
+    weight: u16,
+    target: String,
+    port: u16,
+}
+
+/// SRV records as URLs, lowest priority first, then heaviest weight
+fn endpoints_from_srv(name: &str, mut records: Vec<SrvRecord>) -> Vec<String> {
+    let scheme = if name.starts_with("_http.") {
+        "http"
+    } else {
+        "https"
+    };
+    records.sort_by_key(|r| (r.priority, std::cmp::Reverse(r.weight)));
+    records
+        .into_iter()
+        .map(|r| format!("{}://{}:{}", scheme, r.target.trim_end_matches('.'), r.port))
+        .collect()
+}
+
+/// Split on commas and whitespace, keeping http(s) URLs once each in order
+fn parse_endpoint_list(raw: &str) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    for url in raw
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(str::trim)
+        .filter(|u| !u.is_empty())
+    {
+        if !is_endpoint_url(url) {
+            warn!(url = %url, "Ignoring RPC endpoint that is not an http(s) URL");
+        } else if !urls.iter().any(|u| u == url) {
+            urls.push(url.to_string());
+        }
+    }
+    urls
+}
+
+fn is_endpoint_url(s: &str) -> bool {
+    s.starts_with("https://") || s.starts_with("http://")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Json, Router, routing::get};
+
+    fn srv(priority: u16, weight: u16, target: &str) -> SrvRecord {
+        SrvRecord {
+            priority,
+            weight,
+            target: target.to_string(),
+            port: 8899,
+        }
+    }
+
+    #[test]
+    fn test_srv_records_order_by_priority_then_weight() {
+        let urls = endpoints_from_srv(
+            "_solana-rpc._tcp.example.com",
+            vec![
+                srv(20, 0, "backup.example.com."),
+                srv(10, 5, "light.example.com."),
+                srv(10, 50, "heavy.example.com."),
+            ],
+        );
+        assert_eq!(
+            urls,
+            [
+                "https://heavy.example.com:8899",
+                "https://light.example.com:8899",
+                "https://backup.example.com:8899"
+            ]
+        );
+        let urls = endpoints_from_srv("_http._tcp.rpc.local", vec![srv(0, 0, "node.")]);
+        assert_eq!(urls, ["http://node:8899"]);
+    }
+
+    #[test]
+    fn test_endpoint_list_parsing_and_sources() {
+        assert_eq!(
+            parse_endpoint_list("https://a.example, http://b.example  ftp://c https://a.example"),
+            ["https://a.example", "http://b.example"]
+        );
+        assert_eq!(
+            "srv:_rpc._tcp.example.com".parse(),
+            Ok(EndpointSource::DnsSrv("_rpc._tcp.example.com".to_string()))
+        );
+        assert_eq!(
+            "txt:rpc.example.com".parse(),
+            Ok(EndpointSource::DnsTxt("rpc.example.com".to_string()))
+        );
+        assert!("rpc.example.com".parse::<EndpointSource>().is_err());
+    }
+
+    #[test]
+    fn test_replace_keeps_the_preferred_endpoint() {
+        let endpoints = RpcEndpoints::from_list("https://a, https://b, https://c");
+        endpoints.mark_answered("https://b");
+        assert_eq!(
+            endpoints.attempt_order(),
+            ["https://b", "https://c", "https://a"]
+        );
+
+        assert!(endpoints.replace(vec!["https://d".into(), "https://b".into()]));
+        assert_eq!(endpoints.attempt_order(), ["https://b", "https://d"]);
+        assert!(!endpoints.replace(vec!["https://d".into(), "https://b".into()]));
+
+        // The preferred endpoint was rotated out: start from the top again
+        endpoints.replace(vec!["https://e".into(), "https://f".into()]);
+        assert_eq!(endpoints.attempt_order(), ["https://e", "https://f"]);
+        assert!(RpcEndpoints::new(Vec::new()).attempt_order().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_url_source_replaces_the_list() {
+        let app = Router::new()
+            .route(
+                "/list",
+                get(|| async { Json(vec!["https://one.example", "https://two.example"]) }),
+            )
+            .route(
+                "/object",
+                get(|| async {
+                    Json(serde_json::json!({ "endpoints": ["https://three.example"] }))
+                }),
+            )
+            .route("/empty", get(|| async { Json(Vec::<String>::new()) }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let endpoints = RpcEndpoints::from_list("https://static.example");
+        let discovery = |path: &str| {
+            EndpointDiscovery::new(
+                EndpointSource::Url(format!("http://{}{}", addr, path)),
+                endpoints.clone(),
+            )
+            .unwrap()
+        };
+
+        assert!(discovery("/list").refresh().await.unwrap());
+        assert_eq!(
+            endpoints.urls(),
+            ["https://one.example", "https://two.example"]
+        );
+        assert!(discovery("/object").refresh().await.unwrap());
+        assert_eq!(endpoints.urls(), ["https://three.example"]);
+
+        // An empty answer or a failed lookup keeps the list
+        assert!(discovery("/empty").refresh().await.is_err());
+        assert!(discovery("/missing").refresh().await.is_err());
+        assert_eq!(endpoints.urls(), ["https://three.example"]);
+    }
+}
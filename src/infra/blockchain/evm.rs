@@ -0,0 +1,999 @@
+//! Blockchain RPC client implementation for Ethereum-compatible chains.
+//!
+//! Item hashes are anchored as the `data` of a zero-value EIP-155 legacy transaction
+//! sent to the issuer's own address (or a configured anchor address) via
+//! `eth_sendRawTransaction`. Transactions are hashed with keccak here and the digest is
+//! signed by a secp256k1 [`TransactionSigner`] (local key or AWS KMS).
+//!
+//! The outbox's sticky "blockhash" holds `nonce:gas_price` for EVM submissions:
+//! a retry rebuilds the byte-identical transaction, so at most one can ever land.
+
+use async_trait::async_trait;
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use reqwest::Client;
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha3::{Digest, Keccak256};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, instrument, warn};
+
+use super::blockchain_error_type;
+use crate::domain::{
+    BlockchainClient, BlockchainError, FeeEstimate, HealthCheckError, OnChainTransaction,
+    SignatureScheme, SubmissionTrace, TransactionSigner,
+};
+
+/// Configuration for the EVM client
+#[derive(Debug, Clone)]
+pub struct EvmClientConfig {
+    /// EIP-155 chain ID (replay protection)
+    pub chain_id: u64,
+    /// Gas limit for anchor transactions (21000 + calldata)
+    pub gas_limit: u64,
+    /// Recipient of anchor transactions (None: the issuer's own address)
+    pub anchor_address: Option<[u8; 20]>,
+    pub timeout: Duration,
+    pub max_retries: u32,
+    pub retry_delay: Duration,
+}
+
+impl EvmClientConfig {
+    /// Default settings for the given chain
+    #[must_use]
+    pub fn for_chain(chain_id: u64) -> Self {
+        Self {
+            chain_id,
+            gas_limit: 50_000,
+            anchor_address: None,
+            timeout: Duration::from_secs(30),
+            max_retries: 3,
+            retry_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Abstract provider for EVM JSON-RPC interactions to enable testing.
+#[async_trait]
+pub trait EvmRpcProvider: Send + Sync {
+    /// Send a JSON-RPC request. A `null` result is returned as `Value::Null`
+    /// (e.g. `eth_getTransactionReceipt` for an unmined transaction).
+    async fn send_request(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, BlockchainError>;
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcRequest<'a> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'a str,
+    params: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    #[serde(default)]
+    result: serde_json::Value,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+/// HTTP-based EVM JSON-RPC provider
+pub struct HttpEvmRpcProvider {
+    http_client: Client,
+    rpc_url: String,
+}
+
+impl HttpEvmRpcProvider {
+    pub fn new(rpc_url: &str, timeout: Duration) -> Result<Self, BlockchainError> {
+        let http_client = Client::builder().timeout(timeout).build().map_err(|e| {
+            BlockchainError::NetworkError {
+                message: e.to_string(),
+                blockhash: String::new(),
+            }
+        })?;
+
+        Ok(Self {
+            http_client,
+            rpc_url: rpc_url.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl EvmRpcProvider for HttpEvmRpcProvider {
+    async fn send_request(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, BlockchainError> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: 1,
+            method,
+            params,
+        };
+
+        let response = self
+            .http_client
+            .post(&self.rpc_url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| {
+                SubmissionTrace::record_unreachable(&self.rpc_url);
+                if e.is_timeout() {
+                    BlockchainError::Timeout {
+                        message: e.to_string(),
+                        blockhash: String::new(),
+                    }
+                } else {
+                    BlockchainError::NetworkError {
+                        message: e.to_string(),
+                        blockhash: String::new(),
+                    }
+                }
+            })?;
+        SubmissionTrace::record_answered(&self.rpc_url);
+
+        let rpc_response: JsonRpcResponse = response
+            .json()
+            .await
+            .map_err(|e| BlockchainError::SubmissionFailed(e.to_string()))?;
+
+        match rpc_response.error {
+            Some(error) if error.message.to_lowercase().contains("insufficient funds") => {
+                Err(BlockchainError::InsufficientFunds)
+            }
+            Some(error) => Err(BlockchainError::SubmissionFailed(format!(
+                "{}: {}",
+                error.code, error.message
+            ))),
+            None => Ok(rpc_response.result),
+        }
+    }
+}
+
+/// Keccak-256 digest
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(s: &str) -> Result<Vec<u8>, BlockchainError> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    if !s.len().is_multiple_of(2) {
+        return Err(BlockchainError::SubmissionFailed(
+            "Invalid hex: odd length".to_string(),
+        ));
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&s[i..i + 2], 16)
+                .map_err(|e| BlockchainError::SubmissionFailed(format!("Invalid hex: {}", e)))
+        })
+        .collect()
+}
+
+/// Parse a JSON-RPC hex quantity (`"0x1a"`)
+fn parse_quantity(s: &str) -> Result<u128, BlockchainError> {
+    let digits = s.strip_prefix("0x").unwrap_or(s);
+    if digits.is_empty() {
+        return Ok(0);
+    }
+    u128::from_str_radix(digits, 16)
+        .map_err(|e| BlockchainError::SubmissionFailed(format!("Invalid quantity {}: {}", s, e)))
+}
+
+/// Parse a `0x`-prefixed 20-byte address
+pub fn parse_address(s: &str) -> Result<[u8; 20], BlockchainError> {
+    from_hex(s)?
+        .try_into()
+        .map_err(|_| BlockchainError::SubmissionFailed(format!("Invalid address: {}", s)))
+}
+
+/// Parse a hex-encoded (optionally `0x`-prefixed) secp256k1 private key
+pub fn evm_signing_key_from_hex(secret: &SecretString) -> Result<SigningKey, BlockchainError> {
+    let bytes = from_hex(secret.expose_secret().trim())
+        .map_err(|_| BlockchainError::SubmissionFailed("Invalid EVM private key".to_string()))?;
+    SigningKey::from_slice(&bytes)
+        .map_err(|_| BlockchainError::SubmissionFailed("Invalid EVM private key".to_string()))
+}
+
+/// Ethereum address of a secp256k1 key (last 20 bytes of keccak(uncompressed pubkey))
+#[must_use]
+pub fn evm_address(verifying_key: &VerifyingKey) -> [u8; 20] {
+    let point = verifying_key.to_encoded_point(false);
+    let hash = keccak256(&point.as_bytes()[1..]);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// Ethereum address of a secp256k1 signer; other schemes are rejected
+pub fn signer_address(signer: &dyn TransactionSigner) -> Result<[u8; 20], BlockchainError> {
+    if signer.scheme() != SignatureScheme::Secp256k1 {
+        return Err(BlockchainError::SubmissionFailed(
+            "The EVM backend needs a secp256k1 signer".to_string(),
+        ));
+    }
+    let invalid = || BlockchainError::SubmissionFailed("Invalid secp256k1 public key".to_string());
+    let bytes = bs58::decode(signer.public_key())
+        .into_vec()
+        .map_err(|_| invalid())?;
+    let verifying_key = VerifyingKey::from_sec1_bytes(&bytes).map_err(|_| invalid())?;
+    Ok(evm_address(&verifying_key))
+}
+
+/// Minimal RLP encoding (byte strings and lists)
+mod rlp {
+    fn length_prefix(len: usize, short: u8, long: u8) -> Vec<u8> {
+        if len <= 55 {
+            vec![short + len as u8]
+        } else {
+            let len_bytes = uint(len as u128);
+            let mut out = vec![long + len_bytes.len() as u8];
+            out.extend(len_bytes);
+            out
+        }
+    }
+
+    /// Big-endian bytes without leading zeros (zero encodes as the empty string)
+    pub fn uint(value: u128) -> Vec<u8> {
+        let bytes = value.to_be_bytes();
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+        bytes[start..].to_vec()
+    }
+
+    pub fn bytes(data: &[u8]) -> Vec<u8> {
+        if data.len() == 1 && data[0] < 0x80 {
+            return data.to_vec();
+        }
+        let mut out = length_prefix(data.len(), 0x80, 0xb7);
+        out.extend_from_slice(data);
+        out
+    }
+
+    pub fn list(items: &[Vec<u8>]) -> Vec<u8> {
+        let payload: Vec<u8> = items.concat();
+        let mut out = length_prefix(payload.len(), 0xc0, 0xf7);
+        out.extend(payload);
+        out
+    }
+}
+
+/// EIP-155 legacy transaction
+#[derive(Debug, Clone)]
+pub struct LegacyTransaction {
+    pub nonce: u64,
+    pub gas_price: u128,
+    pub gas_limit: u64,
+    pub to: [u8; 20],
+    pub value: u128,
+    pub data: Vec<u8>,
+    pub chain_id: u64,
+}
+
+impl LegacyTransaction {
+    fn fields(&self) -> Vec<Vec<u8>> {
+        vec![
+            rlp::bytes(&rlp::uint(u128::from(self.nonce))),
+            rlp::bytes(&rlp::uint(self.gas_price)),
+            rlp::bytes(&rlp::uint(u128::from(self.gas_limit))),
+            rlp::bytes(&self.to),
+            rlp::bytes(&rlp::uint(self.value)),
+            rlp::bytes(&self.data),
+        ]
+    }
+
+    /// Hash signed over (EIP-155: chain_id, 0, 0 appended)
+    #[must_use]
+    pub fn signing_hash(&self) -> [u8; 32] {
+        let mut fields = self.fields();
+        fields.push(rlp::bytes(&rlp::uint(u128::from(self.chain_id))));
+        fields.push(rlp::bytes(&[]));
+        fields.push(rlp::bytes(&[]));
+        keccak256(&rlp::list(&fields))
+    }
+
+    /// Sign [`Self::signing_hash`] with `signer` and return the raw transaction bytes for
+    /// `eth_sendRawTransaction`
+    pub async fn sign(&self, signer: &dyn TransactionSigner) -> Result<Vec<u8>, BlockchainError> {
+        let signature = signer.sign_message(&self.signing_hash()).await?;
+        let signature = bs58::decode(signature).into_vec().map_err(|e| {
+            BlockchainError::SubmissionFailed(format!("Invalid signature encoding: {}", e))
+        })?;
+        self.encode_signed(&signature)
+    }
+
+    /// Raw transaction bytes carrying a 65-byte `r || s || recovery_id` signature
+    pub fn encode_signed(&self, signature: &[u8]) -> Result<Vec<u8>, BlockchainError> {
+        let Some((recovery_id, signature)) = signature
+            .split_last()
+            .filter(|(recovery_id, signature)| signature.len() == 64 && **recovery_id <= 1)
+        else {
+            return Err(BlockchainError::SubmissionFailed(
+                "Expected a 65-byte recoverable secp256k1 signature".to_string(),
+            ));
+        };
+        let v = u128::from(self.chain_id) * 2 + 35 + u128::from(*recovery_id);
+
+        let mut fields = self.fields();
+        fields.push(rlp::bytes(&rlp::uint(v)));
+        fields.push(rlp::bytes(trim_leading_zeros(&signature[..32])));
+        fields.push(rlp::bytes(trim_leading_zeros(&signature[32..])));
+        Ok(rlp::list(&fields))
+    }
+}
+
+fn trim_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+/// Sticky submission parameters stored in the outbox `attempt_blockhash` column
+fn parse_sticky(s: &str) -> Option<(u64, u128)> {
+    let (nonce, gas_price) = s.split_once(':')?;
+    Some((nonce.parse().ok()?, gas_price.parse().ok()?))
+}
+
+/// The node already has this exact transaction in its mempool
+fn is_already_known(e: &BlockchainError) -> bool {
+    let BlockchainError::SubmissionFailed(msg) = e else {
+        return false;
+    };
+    let msg = msg.to_lowercase();
+    msg.contains("already known") || msg.contains("known transaction")
+}
+
+/// The nonce was consumed (by this transaction or another one from the same key)
+fn is_nonce_too_low(e: &BlockchainError) -> bool {
+    matches!(e, BlockchainError::SubmissionFailed(msg) if msg.to_lowercase().contains("nonce too low"))
+}
+
+#[derive(Debug, Deserialize)]
+struct TransactionReceipt {
+    status: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Block {
+    hash: String,
+}
+
+/// `eth_getTransactionByHash` result (only what verification reads)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionByHash {
+    /// Null while the transaction is pending
+    block_number: Option<String>,
+    input: String,
+}
+
+/// EVM JSON-RPC blockchain client
+pub struct EvmBlockchainClient {
+    provider: Box<dyn EvmRpcProvider>,
+    signer: Arc<dyn TransactionSigner>,
+    address: [u8; 20],
+    config: EvmClientConfig,
+}
+
+impl EvmBlockchainClient {
+    /// Create a new EVM client over HTTP
+    pub fn new(
+        rpc_url: &str,
+        signer: Arc<dyn TransactionSigner>,
+        config: EvmClientConfig,
+    ) -> Result<Self, BlockchainError> {
+        let provider = HttpEvmRpcProvider::new(rpc_url, config.timeout)?;
+        let client = Self::with_provider(Box::new(provider), signer, config)?;
+        info!(
+            rpc_url = %rpc_url,
+            chain_id = client.config.chain_id,
+            address = %client.address(),
+            "Created EVM blockchain client"
+        );
+        Ok(client)
+    }
+
+    /// Create a new client with a specific provider (useful for testing). Fails unless
+    /// `signer` is a secp256k1 signer.
+    pub fn with_provider(
+        provider: Box<dyn EvmRpcProvider>,
+        signer: Arc<dyn TransactionSigner>,
+        config: EvmClientConfig,
+    ) -> Result<Self, BlockchainError> {
+        let address = signer_address(signer.as_ref())?;
+        Ok(Self {
+            provider,
+            signer,
+            address,
+            config,
+        })
+    }
+
+    /// Issuer address as a `0x`-prefixed hex string
+    #[must_use]
+    pub fn address(&self) -> String {
+        format!("0x{}", to_hex(&self.address))
+    }
+
+    /// Make an RPC call with retries
+    #[instrument(skip(self, params))]
+    async fn rpc_call<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<R, BlockchainError> {
+        let start = Instant::now();
+        let mut last_error = None;
+        for attempt in 0..=self.config.max_retries {
+            if attempt > 0 {
+                tokio::time::sleep(self.config.retry_delay).await;
+            }
+            match self.provider.send_request(method, params.clone()).await {
+                Ok(result_value) => {
+                    metrics::histogram!(
+                        "evm_rpc_latency_seconds",
+                        "method" => method.to_string(),
+                    )
+                    .record(start.elapsed().as_secs_f64());
+                    return serde_json::from_value(result_value).map_err(|e| {
+                        BlockchainError::SubmissionFailed(format!("Deserialization: {}", e))
+                    });
+                }
+                // Deterministic rejections won't change on retry
+                Err(e @ BlockchainError::InsufficientFunds) => last_error = Some(e),
+                Err(e) if is_already_known(&e) || is_nonce_too_low(&e) => last_error = Some(e),
+                Err(e) => {
+                    warn!(attempt = attempt, error = ?e, method = %method, "EVM RPC call failed");
+                    last_error = Some(e);
+                    continue;
+                }
+            }
+            break;
+        }
+        let err = last_error
+            .unwrap_or_else(|| BlockchainError::SubmissionFailed("Unknown error".to_string()));
+        metrics::counter!(
+            "evm_rpc_errors_total",
+            "method" => method.to_string(),
+            "error_type" => blockchain_error_type(&err).to_string(),
+        )
+        .increment(1);
+        Err(err)
+    }
+
+    async fn quantity(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<u128, BlockchainError> {
+        let value: String = self.rpc_call(method, params).await?;
+        parse_quantity(&value)
+    }
+
+    async fn receipt(&self, tx_hash: &str) -> Result<Option<TransactionReceipt>, BlockchainError> {
+        self.rpc_call("eth_getTransactionReceipt", serde_json::json!([tx_hash]))
+            .await
+    }
+}
+
+#[async_trait]
+impl BlockchainClient for EvmBlockchainClient {
+    #[instrument(skip(self))]
+    async fn health_check(&self) -> Result<(), HealthCheckError> {
+        let chain_id = self
+            .quantity("eth_chainId", serde_json::json!([]))
+            .await
+            .map_err(|_| HealthCheckError::BlockchainUnavailable)?;
+        if chain_id != u128::from(self.config.chain_id) {
+            warn!(
+                expected = self.config.chain_id,
+                actual = chain_id,
+                "EVM RPC serves a different chain"
+            );
+            return Err(HealthCheckError::BlockchainUnavailable);
+        }
+
+        let _ = self.get_balance().await;
+
+        Ok(())
+    }
+
+    /// Sender balance in wei via `eth_getBalance`, saturating at `u64::MAX` (~18.4 ETH);
+    /// also updates `evm_wallet_balance_wei`
+    #[instrument(skip(self))]
+    async fn get_balance(&self) -> Result<u64, BlockchainError> {
+        let balance = self
+            .quantity(
+                "eth_getBalance",
+                serde_json::json!([self.address(), "latest"]),
+            )
+            .await?;
+        metrics::gauge!("evm_wallet_balance_wei").set(balance as f64);
+        Ok(u64::try_from(balance).unwrap_or(u64::MAX))
+    }
+
+    #[instrument(skip(self))]
+    async fn submit_transaction(
+        &self,
+        hash: &str,
+        existing_blockhash: Option<&str>,
+    ) -> Result<(String, String), BlockchainError> {
+        info!(hash = %hash, "Submitting EVM transaction");
+
+        // Sticky nonce/gas price: a retry re-sends the identical transaction
+        let (nonce, gas_price) = match existing_blockhash.and_then(parse_sticky) {
+            Some(sticky) => {
+                debug!(nonce = sticky.0, "Reusing existing nonce");
+                sticky
+            }
+            None => {
+                let nonce = self
+                    .quantity(
+                        "eth_getTransactionCount",
+                        serde_json::json!([self.address(), "pending"]),
+                    )
+                    .await?;
+                let gas_price = self.quantity("eth_gasPrice", serde_json::json!([])).await?;
+                let nonce = u64::try_from(nonce).map_err(|_| {
+                    BlockchainError::SubmissionFailed("Nonce out of range".to_string())
+                })?;
+                (nonce, gas_price)
+            }
+        };
+        let sticky = format!("{}:{}", nonce, gas_price);
+
+        let tx = LegacyTransaction {
+            nonce,
+            gas_price,
+            gas_limit: self.config.gas_limit,
+            to: self.config.anchor_address.unwrap_or(self.address),
+            value: 0,
+            data: hash.as_bytes().to_vec(),
+            chain_id: self.config.chain_id,
+        };
+        let raw = tx.sign(self.signer.as_ref()).await.map_err(|e| {
+            BlockchainError::SubmissionFailedWithBlockhash {
+                message: e.to_string(),
+                blockhash_used: sticky.clone(),
+            }
+        })?;
+        let tx_hash = format!("0x{}", to_hex(&keccak256(&raw)));
+
+        let params = serde_json::json!([format!("0x{}", to_hex(&raw))]);
+        match self
+            .rpc_call::<String>("eth_sendRawTransaction", params)
+            .await
+        {
+            Ok(signature) => {
+                info!(signature = %signature, "EVM transaction sent");
+                Ok((signature, sticky))
+            }
+            Err(e) if is_already_known(&e) => {
+                info!(signature = %tx_hash, "EVM transaction already in mempool");
+                Ok((tx_hash, sticky))
+            }
+            // Either our earlier send was mined, or another transaction took the nonce
+            // and ours can never land, so a fresh nonce is safe.
+            Err(e) if is_nonce_too_low(&e) => match self.receipt(&tx_hash).await? {
+                Some(_) => Ok((tx_hash, sticky)),
+                None => Err(BlockchainError::BlockhashExpired),
+            },
+            Err(BlockchainError::Timeout { message, .. }) => Err(BlockchainError::Timeout {
+                message,
+                blockhash: sticky,
+            }),
+            Err(BlockchainError::NetworkError { message, .. }) => {
+                Err(BlockchainError::NetworkError {
+                    message,
+                    blockhash: sticky,
+                })
+            }
+            Err(BlockchainError::InsufficientFunds) => Err(BlockchainError::InsufficientFunds),
+            Err(e) => Err(BlockchainError::SubmissionFailedWithBlockhash {
+                message: e.to_string(),
+                blockhash_used: sticky,
+            }),
+        }
+    }
+
+    /// `eth_gasPrice` for the configured gas limit (legacy transactions carry no separate
+    /// tip), saturating at `u64::MAX` wei
+    #[instrument(skip(self))]
+    async fn estimate_fee(&self) -> Result<FeeEstimate, BlockchainError> {
+        let gas_price = self.quantity("eth_gasPrice", serde_json::json!([])).await?;
+        let total = gas_price.saturating_mul(u128::from(self.config.gas_limit));
+        Ok(FeeEstimate {
+            base_fee: u64::try_from(total).unwrap_or(u64::MAX),
+            unit_price: u64::try_from(gas_price).unwrap_or(u64::MAX),
+            units: self.config.gas_limit,
+            priority_fee: 0,
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn get_block_height(&self) -> Result<u64, BlockchainError> {
+        let height = self
+            .quantity("eth_blockNumber", serde_json::json!([]))
+            .await?;
+        u64::try_from(height)
+            .map_err(|_| BlockchainError::SubmissionFailed("Block number out of range".into()))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_latest_blockhash(&self) -> Result<String, BlockchainError> {
+        let block: Block = self
+            .rpc_call("eth_getBlockByNumber", serde_json::json!(["latest", false]))
+            .await?;
+        Ok(block.hash)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_transaction_status(&self, signature: &str) -> Result<bool, BlockchainError> {
+        match self.receipt(signature).await? {
+            Some(receipt) if receipt.status.as_deref() == Some("0x0") => Err(
+                BlockchainError::SubmissionFailed("Transaction reverted".to_string()),
+            ),
+            Some(_) => Ok(true),
+            None => Ok(false),
+        }
+    }
+
+    /// `eth_getTransactionByHash` for the calldata and block; a pending transaction counts
+    /// as not landed and a reverted one as anchoring nothing
+    #[instrument(skip(self))]
+    async fn get_transaction(
+        &self,
+        signature: &str,
+    ) -> Result<Option<OnChainTransaction>, BlockchainError> {
+        let tx: Option<TransactionByHash> = self
+            .rpc_call("eth_getTransactionByHash", serde_json::json!([signature]))
+            .await?;
+        let Some(TransactionByHash {
+            block_number: Some(block),
+            input,
+        }) = tx
+        else {
+            return Ok(None);
+        };
+        let block = u64::try_from(parse_quantity(&block)?)
+            .map_err(|_| BlockchainError::SubmissionFailed("Block number out of range".into()))?;
+        let reverted = self
+            .receipt(signature)
+            .await?
+            .is_some_and(|receipt| receipt.status.as_deref() == Some("0x0"));
+        let memo = if reverted {
+            None
+        } else {
+            from_hex(&input)
+                .ok()
+                .and_then(|data| String::from_utf8(data).ok())
+        };
+        let current = self.get_block_height().await?;
+        Ok(Some(OnChainTransaction {
+            slot: block,
+            memo,
+            confirmation_depth: current.saturating_sub(block),
+        }))
+    }
+
+    #[instrument(skip(self))]
+    async fn wait_for_confirmation(
+        &self,
+        signature: &str,
+        timeout_secs: u64,
+    ) -> Result<bool, BlockchainError> {
+        let timeout = Duration::from_secs(timeout_secs);
+        let start = Instant::now();
+        let poll_interval = Duration::from_secs(1);
+
+        while start.elapsed() < timeout {
+            match self.get_transaction_status(signature).await {
+                Ok(true) => {
+                    info!(signature = %signature, "EVM transaction confirmed");
+                    return Ok(true);
+                }
+                Ok(false) => debug!(signature = %signature, "EVM transaction not yet mined"),
+                Err(BlockchainError::SubmissionFailed(msg)) => {
+                    return Err(BlockchainError::SubmissionFailed(msg));
+                }
+                Err(e) => {
+                    warn!(signature = %signature, error = ?e, "Error checking transaction status");
+                }
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+
+        Err(BlockchainError::Timeout {
+            message: format!(
+                "Transaction {} not mined within {}s",
+                signature, timeout_secs
+            ),
+            blockhash: String::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::blockchain::signer::{LocalSecp256k1Signer, LocalSigner};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Signer with the EIP-155 example key (0x4646...46)
+    fn test_signer() -> Arc<dyn TransactionSigner> {
+        let secret = SecretString::from("46".repeat(32));
+        Arc::new(LocalSecp256k1Signer::new(secret).unwrap())
+    }
+
+    /// Scripted provider: responses per method, consumed in order (last one repeats)
+    struct ScriptedProvider {
+        responses: Mutex<HashMap<String, Vec<Result<serde_json::Value, String>>>>,
+        calls: Mutex<Vec<(String, serde_json::Value)>>,
+    }
+
+    impl ScriptedProvider {
+        fn new(script: &[(&str, Result<serde_json::Value, &str>)]) -> Self {
+            let mut responses: HashMap<String, Vec<_>> = HashMap::new();
+            for (method, response) in script {
+                responses
+                    .entry((*method).to_string())
+                    .or_default()
+                    .push(response.clone().map_err(str::to_string));
+            }
+            Self {
+                responses: Mutex::new(responses),
+                calls: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl EvmRpcProvider for std::sync::Arc<ScriptedProvider> {
+        async fn send_request(
+            &self,
+            method: &str,
+            params: serde_json::Value,
+        ) -> Result<serde_json::Value, BlockchainError> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((method.to_string(), params));
+            let mut responses = self.responses.lock().unwrap();
+            let queue = responses
+                .get_mut(method)
+                .unwrap_or_else(|| panic!("unexpected call {}", method));
+            let response = if queue.len() > 1 {
+                queue.remove(0)
+            } else {
+                queue[0].clone()
+            };
+            response.map_err(BlockchainError::SubmissionFailed)
+        }
+    }
+
+    fn scripted_client(
+        script: &[(&str, Result<serde_json::Value, &str>)],
+    ) -> (EvmBlockchainClient, std::sync::Arc<ScriptedProvider>) {
+        let provider = std::sync::Arc::new(ScriptedProvider::new(script));
+        let config = EvmClientConfig {
+            retry_delay: Duration::ZERO,
+            ..EvmClientConfig::for_chain(1)
+        };
+        let client =
+            EvmBlockchainClient::with_provider(Box::new(provider.clone()), test_signer(), config)
+                .unwrap();
+        (client, provider)
+    }
+
+    #[tokio::test]
+    async fn test_eip155_reference_transaction() {
+        // https://eips.ethereum.org/EIPS/eip-155 example
+        let tx = LegacyTransaction {
+            nonce: 9,
+            gas_price: 20_000_000_000,
+            gas_limit: 21_000,
+            to: [0x35; 20],
+            value: 1_000_000_000_000_000_000,
+            data: Vec::new(),
+            chain_id: 1,
+        };
+        assert_eq!(
+            to_hex(&tx.signing_hash()),
+            "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+        );
+        assert_eq!(
+            to_hex(&tx.sign(test_signer().as_ref()).await.unwrap()),
+            "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+        );
+    }
+
+    #[test]
+    fn test_address_derivation() {
+        assert_eq!(
+            to_hex(&signer_address(test_signer().as_ref()).unwrap()),
+            "9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"
+        );
+    }
+
+    #[test]
+    fn test_ed25519_signer_is_rejected() {
+        let ed25519 =
+            LocalSigner::new(SecretString::from(bs58::encode([7u8; 32]).into_string())).unwrap();
+        let provider = std::sync::Arc::new(ScriptedProvider::new(&[]));
+        let result = EvmBlockchainClient::with_provider(
+            Box::new(provider),
+            Arc::new(ed25519),
+            EvmClientConfig::for_chain(1),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_signing_key_from_hex() {
+        let key = SecretString::from(format!("0x{}", "46".repeat(32)));
+        assert!(evm_signing_key_from_hex(&key).is_ok());
+        assert!(evm_signing_key_from_hex(&SecretString::from("0x1234")).is_err());
+        assert!(evm_signing_key_from_hex(&SecretString::from("zz")).is_err());
+    }
+
+    #[test]
+    fn test_rlp_encoding() {
+        assert_eq!(rlp::bytes(&[]), vec![0x80]);
+        assert_eq!(rlp::bytes(&[0x7f]), vec![0x7f]);
+        assert_eq!(rlp::bytes(b"dog"), vec![0x83, b'd', b'o', b'g']);
+        assert_eq!(rlp::uint(0), Vec::<u8>::new());
+        assert_eq!(rlp::uint(1024), vec![0x04, 0x00]);
+        let long = vec![0xaa; 56];
+        assert_eq!(&rlp::bytes(&long)[..2], &[0xb8, 56]);
+        assert_eq!(rlp::list(&[]), vec![0xc0]);
+    }
+
+    #[tokio::test]
+    async fn test_submit_fetches_nonce_and_returns_sticky_params() {
+        let (client, provider) = scripted_client(&[
+            ("eth_getTransactionCount", Ok(serde_json::json!("0x5"))),
+            ("eth_gasPrice", Ok(serde_json::json!("0x3b9aca00"))),
+            ("eth_sendRawTransaction", Ok(serde_json::json!("0xabc"))),
+        ]);
+        let (signature, sticky) = client.submit_transaction("hash_1", None).await.unwrap();
+        assert_eq!(signature, "0xabc");
+        assert_eq!(sticky, "5:1000000000");
+        assert_eq!(provider.calls.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_reuses_sticky_nonce_and_identical_transaction() {
+        let (client, provider) =
+            scripted_client(&[("eth_sendRawTransaction", Err("-32000: already known"))]);
+        let (first, _) = client
+            .submit_transaction("hash_1", Some("7:1"))
+            .await
+            .unwrap();
+        let (second, sticky) = client
+            .submit_transaction("hash_1", Some("7:1"))
+            .await
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(sticky, "7:1");
+        let calls = provider.calls.lock().unwrap();
+        assert!(calls.iter().all(|(m, _)| m == "eth_sendRawTransaction"));
+        assert_eq!(calls[0].1, calls[1].1);
+    }
+
+    #[tokio::test]
+    async fn test_nonce_taken_by_other_transaction_clears_sticky() {
+        let (client, _) = scripted_client(&[
+            ("eth_sendRawTransaction", Err("-32000: nonce too low")),
+            ("eth_getTransactionReceipt", Ok(serde_json::Value::Null)),
+        ]);
+        let result = client.submit_transaction("hash_1", Some("7:1")).await;
+        assert!(matches!(result, Err(BlockchainError::BlockhashExpired)));
+    }
+
+    #[tokio::test]
+    async fn test_send_failure_keeps_sticky_params() {
+        let (client, _) = scripted_client(&[("eth_sendRawTransaction", Err("-32000: rejected"))]);
+        let result = client.submit_transaction("hash_1", Some("7:1")).await;
+        match result {
+            Err(BlockchainError::SubmissionFailedWithBlockhash { blockhash_used, .. }) => {
+                assert_eq!(blockhash_used, "7:1");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transaction_status_from_receipt() {
+        let (client, _) = scripted_client(&[
+            ("eth_getTransactionReceipt", Ok(serde_json::Value::Null)),
+            (
+                "eth_getTransactionReceipt",
+                Ok(serde_json::json!({"status": "0x1"})),
+            ),
+            (
+                "eth_getTransactionReceipt",
+                Ok(serde_json::json!({"status": "0x0"})),
+            ),
+        ]);
+        assert!(!client.get_transaction_status("0x1").await.unwrap());
+        assert!(client.get_transaction_status("0x1").await.unwrap());
+        assert!(client.get_transaction_status("0x1").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_reads_calldata_and_depth() {
+        let tx = |block: serde_json::Value| serde_json::json!({"blockNumber": block, "input": format!("0x{}", to_hex(b"abc123"))});
+        let (client, _) = scripted_client(&[
+            (
+                "eth_getTransactionByHash",
+                Ok(tx(serde_json::json!("0x64"))),
+            ),
+            (
+                "eth_getTransactionReceipt",
+                Ok(serde_json::json!({"status": "0x1"})),
+            ),
+            ("eth_blockNumber", Ok(serde_json::json!("0x70"))),
+            (
+                "eth_getTransactionByHash",
+                Ok(tx(serde_json::json!("0x64"))),
+            ),
+            (
+                "eth_getTransactionReceipt",
+                Ok(serde_json::json!({"status": "0x0"})),
+            ),
+            ("eth_blockNumber", Ok(serde_json::json!("0x64"))),
+            ("eth_getTransactionByHash", Ok(tx(serde_json::Value::Null))),
+            ("eth_getTransactionByHash", Ok(serde_json::Value::Null)),
+        ]);
+
+        let landed = client.get_transaction("0x1").await.unwrap().unwrap();
+        assert_eq!(landed.slot, 100);
+        assert_eq!(landed.memo.as_deref(), Some("abc123"));
+        assert_eq!(landed.confirmation_depth, 12);
+
+        let reverted = client.get_transaction("0x1").await.unwrap().unwrap();
+        assert_eq!(reverted.memo, None);
+        assert_eq!(reverted.confirmation_depth, 0);
+
+        // Pending, then unknown
+        assert_eq!(client.get_transaction("0x1").await.unwrap(), None);
+        assert_eq!(client.get_transaction("0x1").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_health_check_rejects_wrong_chain() {
+        let (client, _) = scripted_client(&[
+            ("eth_chainId", Ok(serde_json::json!("0x89"))),
+            ("eth_getBalance", Ok(serde_json::json!("0x0"))),
+        ]);
+        assert!(client.health_check().await.is_err());
+
+        let (client, _) = scripted_client(&[
+            ("eth_chainId", Ok(serde_json::json!("0x1"))),
+            ("eth_getBalance", Ok(serde_json::json!("0xde0b6b3a7640000"))),
+        ]);
+        assert!(client.health_check().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_block_height_parses_quantity() {
+        let (client, _) = scripted_client(&[("eth_blockNumber", Ok(serde_json::json!("0x10")))]);
+        assert_eq!(client.get_block_height().await.unwrap(), 16);
+    }
+}
@@ -1,7 +1,197 @@
 //! Blockchain client implementations.
+//!
+//! The backend is selected by configuration (`BLOCKCHAIN_BACKEND=solana|evm|noop`);
+//! [`create_blockchain_client`] builds the matching [`BlockchainClient`].
 
+pub mod audit;
+pub mod circuit_breaker;
+pub mod discovery;
+pub mod evm;
+pub mod noop;
 pub mod signer;
 pub mod solana;
+pub mod vault;
 
-pub use signer::{AwsKmsSigner, LocalSigner};
-pub use solana::{RpcBlockchainClient, RpcClientConfig, signing_key_from_base58};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::domain::{BlockchainClient, BlockchainError, TransactionSigner};
+
+pub use audit::{AUDIT_LOG_TARGET, AuditingSigner};
+pub use circuit_breaker::{CircuitBreakerBlockchainClient, CircuitBreakerConfig, CircuitState};
+pub use discovery::{
+    DEFAULT_DISCOVERY_INTERVAL, DiscoveryError, EndpointDiscovery, EndpointSource, RpcEndpoints,
+    spawn_endpoint_discovery,
+};
+pub use evm::{EvmBlockchainClient, EvmClientConfig, evm_signing_key_from_hex};
+pub use noop::NoopBlockchainClient;
+pub use signer::{
+    AwsKmsSecp256k1Signer, AwsKmsSigner, LocalSecp256k1Signer, LocalSigner, generate_keypair,
+};
+pub use solana::{
+    DEFAULT_COMPUTE_UNIT_LIMIT, PriorityFee, RpcBlockchainClient, RpcClientConfig,
+    signing_key_from_base58,
+};
+pub use vault::{VaultAuth, VaultConfig, VaultTransitSigner, spawn_vault_token_renewal};
+
+/// Map BlockchainError to a stable label for metrics.
+pub(crate) fn blockchain_error_type(e: &BlockchainError) -> &'static str {
+    match e {
+        BlockchainError::SubmissionFailed(_) => "submission_failed",
+        BlockchainError::SubmissionFailedWithBlockhash { .. } => "submission_failed_with_blockhash",
+        BlockchainError::BlockhashExpired => "blockhash_expired",
+        BlockchainError::NetworkError { .. } => "network_error",
+        BlockchainError::InsufficientFunds => "insufficient_funds",
+        BlockchainError::Timeout { .. } => "timeout",
+        BlockchainError::CircuitOpen => "circuit_open",
+    }
+}
+
+/// Supported blockchain backends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlockchainBackend {
+    #[default]
+    Solana,
+    Evm,
+    /// Accepts every submission without a chain (database-only deployments)
+    Noop,
+}
+
+impl FromStr for BlockchainBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "solana" => Ok(Self::Solana),
+            "evm" => Ok(Self::Evm),
+            "noop" => Ok(Self::Noop),
+            other => Err(format!(
+                "Invalid blockchain backend '{}': must be solana, evm or noop",
+                other
+            )),
+        }
+    }
+}
+
+/// Everything needed to build a client for one backend
+pub enum BlockchainBackendConfig {
+    Solana {
+        /// Failover list, shared with [`EndpointDiscovery`] when it is enabled
+        endpoints: RpcEndpoints,
+        signer: Arc<dyn TransactionSigner>,
+        config: RpcClientConfig,
+    },
+    Evm {
+        rpc_url: String,
+        /// secp256k1 signer (local key or KMS)
+        signer: Arc<dyn TransactionSigner>,
+        config: EvmClientConfig,
+    },
+    Noop,
+}
+
+impl BlockchainBackendConfig {
+    #[must_use]
+    pub fn backend(&self) -> BlockchainBackend {
+        match self {
+            Self::Solana { .. } => BlockchainBackend::Solana,
+            Self::Evm { .. } => BlockchainBackend::Evm,
+            Self::Noop => BlockchainBackend::Noop,
+        }
+    }
+
+    /// Signer of submitted transactions (None for `noop`)
+    #[must_use]
+    pub fn signer(&self) -> Option<&Arc<dyn TransactionSigner>> {
+        match self {
+            Self::Solana { signer, .. } | Self::Evm { signer, .. } => Some(signer),
+            Self::Noop => None,
+        }
+    }
+
+    /// Identity of the fee payer: the Solana public key or the `0x` EVM address
+    pub fn signer_id(&self) -> Result<String, BlockchainError> {
+        match self {
+            Self::Solana { signer, .. } => Ok(signer.public_key()),
+            Self::Evm { signer, .. } => {
+                let address = evm::signer_address(signer.as_ref())?;
+                Ok(format!("0x{}", evm::to_hex(&address)))
+            }
+            Self::Noop => Ok("noop".to_string()),
+        }
+    }
+}
+
+/// Build the blockchain client for the configured backend
+pub fn create_blockchain_client(
+    config: BlockchainBackendConfig,
+) -> Result<Arc<dyn BlockchainClient>, BlockchainError> {
+    match config {
+        BlockchainBackendConfig::Solana {
+            endpoints,
+            signer,
+            config,
+        } => Ok(Arc::new(RpcBlockchainClient::with_endpoints(
+            endpoints, signer, config,
+        )?)),
+        BlockchainBackendConfig::Evm {
+            rpc_url,
+            signer,
+            config,
+        } => Ok(Arc::new(EvmBlockchainClient::new(
+            &rpc_url, signer, config,
+        )?)),
+        BlockchainBackendConfig::Noop => Ok(Arc::new(NoopBlockchainClient::new())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use secrecy::SecretString;
+
+    #[test]
+    fn test_backend_from_str() {
+        assert_eq!(
+            "solana".parse::<BlockchainBackend>(),
+            Ok(BlockchainBackend::Solana)
+        );
+        assert_eq!(
+            "EVM".parse::<BlockchainBackend>(),
+            Ok(BlockchainBackend::Evm)
+        );
+        assert_eq!(
+            "noop".parse::<BlockchainBackend>(),
+            Ok(BlockchainBackend::Noop)
+        );
+        assert!("bitcoin".parse::<BlockchainBackend>().is_err());
+    }
+
+    #[test]
+    fn test_create_evm_client_rejects_ed25519_signer() {
+        let signer =
+            LocalSigner::new(SecretString::from(bs58::encode([7u8; 32]).into_string())).unwrap();
+        let result = create_blockchain_client(BlockchainBackendConfig::Evm {
+            rpc_url: "http://localhost:8545".to_string(),
+            signer: Arc::new(signer),
+            config: EvmClientConfig::for_chain(1),
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_create_evm_client() {
+        let signer = LocalSecp256k1Signer::new(SecretString::from("46".repeat(32))).unwrap();
+        let config = BlockchainBackendConfig::Evm {
+            rpc_url: "http://localhost:8545".to_string(),
+            signer: Arc::new(signer),
+            config: EvmClientConfig::for_chain(1),
+        };
+        assert_eq!(config.backend(), BlockchainBackend::Evm);
+        assert_eq!(
+            config.signer_id().unwrap(),
+            "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"
+        );
+        assert!(create_blockchain_client(config).is_ok());
+    }
+}
@@ -0,0 +1,99 @@
+//! No-op blockchain client for database-only deployments.
+//!
+//! Every submission "succeeds" immediately with a locally derived signature, so the
+//! outbox drains and items reach `submitted` without any RPC or signing key. Nothing
+//! is anchored on-chain; use it for demos, examples and local development only.
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+
+use crate::domain::{
+    BlockchainClient, BlockchainError, FeeEstimate, HealthCheckError, OnChainTransaction,
+};
+
+/// Blockhash reported for no-op submissions
+const NOOP_BLOCKHASH: &str = "noop";
+
+/// Blockchain client that accepts every submission without touching a chain
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopBlockchainClient;
+
+impl NoopBlockchainClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl BlockchainClient for NoopBlockchainClient {
+    async fn health_check(&self) -> Result<(), HealthCheckError> {
+        Ok(())
+    }
+
+    /// Deterministic per hash, so retries of the same item return the same signature.
+    async fn submit_transaction(
+        &self,
+        hash: &str,
+        _existing_blockhash: Option<&str>,
+    ) -> Result<(String, String), BlockchainError> {
+        let digest = Sha256::digest(hash.as_bytes());
+        let signature = format!("noop_{}", bs58::encode(&digest[..16]).into_string());
+        Ok((signature, NOOP_BLOCKHASH.to_string()))
+    }
+
+    async fn get_transaction_status(&self, signature: &str) -> Result<bool, BlockchainError> {
+        Ok(signature.starts_with("noop_"))
+    }
+
+    /// Nothing is ever on-chain
+    async fn get_transaction(
+        &self,
+        _signature: &str,
+    ) -> Result<Option<OnChainTransaction>, BlockchainError> {
+        Ok(None)
+    }
+
+    async fn get_block_height(&self) -> Result<u64, BlockchainError> {
+        Ok(0)
+    }
+
+    /// Nothing is paid
+    async fn estimate_fee(&self) -> Result<FeeEstimate, BlockchainError> {
+        Ok(FeeEstimate::default())
+    }
+
+    async fn get_latest_blockhash(&self) -> Result<String, BlockchainError> {
+        Ok(NOOP_BLOCKHASH.to_string())
+    }
+
+    async fn wait_for_confirmation(
+        &self,
+        signature: &str,
+        _timeout_secs: u64,
+    ) -> Result<bool, BlockchainError> {
+        self.get_transaction_status(signature).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_submission_is_deterministic_per_hash() {
+        let client = NoopBlockchainClient::new();
+        let (first, blockhash) = client.submit_transaction("hash_a", None).await.unwrap();
+        let (again, _) = client
+            .submit_transaction("hash_a", Some("other"))
+            .await
+            .unwrap();
+        let (other, _) = client.submit_transaction("hash_b", None).await.unwrap();
+
+        assert_eq!(first, again);
+        assert_ne!(first, other);
+        assert_eq!(blockhash, NOOP_BLOCKHASH);
+        assert!(client.get_transaction_status(&first).await.unwrap());
+        assert!(client.health_check().await.is_ok());
+    }
+}
@@ -1,17 +1,22 @@
 //! Transaction signer strategies: local key (dev/legacy) and AWS KMS (production).
 //!
 //! Decouples signing from the RPC client so that raw private keys are not held
-//! in the client and remote signers (HSM, AWS KMS, Vault) can be used.
+//! in the client and remote signers (HSM, AWS KMS, Vault) can be used. Ed25519 signers
+//! serve the Solana backend; the secp256k1 ones serve the EVM backend, which hashes the
+//! transaction with keccak and hands the signer the 32-byte digest.
 
 use async_trait::async_trait;
 use aws_config::BehaviorVersion;
 use aws_sdk_kms::primitives::Blob;
 use aws_sdk_kms::types::{MessageType, SigningAlgorithmSpec};
 use ed25519_dalek::{Signer, SigningKey};
+use k256::ecdsa::{RecoveryId, Signature as EcdsaSignature, VerifyingKey as EcdsaVerifyingKey};
+use k256::pkcs8::DecodePublicKey;
 use secrecy::{ExposeSecret, SecretString};
 use tracing::{debug, info};
 
-use crate::domain::{BlockchainError, TransactionSigner};
+use super::evm::evm_signing_key_from_hex;
+use crate::domain::{BlockchainError, SignatureScheme, TransactionSigner};
 
 /// Parse base58-encoded private key into a SigningKey. Used only within local scope.
 fn signing_key_from_secret(secret: &SecretString) -> Result<SigningKey, BlockchainError> {
@@ -56,6 +61,14 @@ impl LocalSigner {
     }
 }
 
+/// New random Ed25519 keypair as Base58 of the 64-byte `secret || public` form (Solana CLI
+/// keypair layout), accepted by [`LocalSigner::new`] and `ISSUER_PRIVATE_KEY`
+#[must_use]
+pub fn generate_keypair() -> SecretString {
+    let signing_key = SigningKey::generate(&mut rand::rngs::OsRng);
+    SecretString::from(bs58::encode(signing_key.to_keypair_bytes()).into_string())
+}
+
 #[async_trait]
 impl TransactionSigner for LocalSigner {
     async fn sign_message(&self, message: &[u8]) -> Result<String, BlockchainError> {
@@ -209,3 +222,232 @@ impl TransactionSigner for AwsKmsSigner {
         self.pubkey_base58.clone()
     }
 }
+
+// ---------------------------------------------------------------------------
+// secp256k1 signers — EVM backend (local key and AWS KMS ECDSA_SHA_256)
+// ---------------------------------------------------------------------------
+
+/// The 32-byte prehash a secp256k1 signer signs
+fn prehash(message: &[u8]) -> Result<&[u8; 32], BlockchainError> {
+    message.try_into().map_err(|_| {
+        BlockchainError::SubmissionFailed(format!(
+            "secp256k1 signers sign a 32-byte digest, got {} bytes",
+            message.len()
+        ))
+    })
+}
+
+/// `r || s || recovery_id` as Base58
+fn encode_recoverable(signature: &EcdsaSignature, recovery_id: RecoveryId) -> String {
+    let mut bytes = signature.to_bytes().to_vec();
+    bytes.push(recovery_id.to_byte());
+    bs58::encode(bytes).into_string()
+}
+
+fn encode_public_key(key: &EcdsaVerifyingKey) -> String {
+    bs58::encode(key.to_encoded_point(true).as_bytes()).into_string()
+}
+
+/// Local secp256k1 signer (dev/legacy): holds the hex secret in memory, parses only when
+/// signing. Signatures are deterministic (RFC 6979) with a low `s` (EIP-2).
+pub struct LocalSecp256k1Signer {
+    secret: SecretString,
+    public_key_base58: String,
+}
+
+impl LocalSecp256k1Signer {
+    /// Build a local signer from a hex-encoded (optionally `0x`-prefixed) private key.
+    pub fn new(secret: SecretString) -> Result<Self, BlockchainError> {
+        let signing_key = evm_signing_key_from_hex(&secret)?;
+        let public_key_base58 = encode_public_key(signing_key.verifying_key());
+        Ok(Self {
+            secret,
+            public_key_base58,
+        })
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for LocalSecp256k1Signer {
+    async fn sign_message(&self, message: &[u8]) -> Result<String, BlockchainError> {
+        let signing_key = evm_signing_key_from_hex(&self.secret)?;
+        let (signature, recovery_id) = signing_key
+            .sign_prehash_recoverable(prehash(message)?)
+            .map_err(|e| BlockchainError::SubmissionFailed(format!("Signing failed: {}", e)))?;
+        Ok(encode_recoverable(&signature, recovery_id))
+    }
+
+    fn public_key(&self) -> String {
+        self.public_key_base58.clone()
+    }
+
+    fn scheme(&self) -> SignatureScheme {
+        SignatureScheme::Secp256k1
+    }
+}
+
+/// AWS KMS signer for an `ECC_SECG_P256K1` key. KMS signs the digest it is given
+/// (`MessageType::Digest`), so the keccak hash computed by the EVM backend is signed as-is.
+/// KMS returns a DER signature without recovery ID; it is normalized to a low `s` and the
+/// recovery ID is found by recovering the cached public key.
+pub struct AwsKmsSecp256k1Signer {
+    client: aws_sdk_kms::Client,
+    key_id: String,
+    verifying_key: EcdsaVerifyingKey,
+}
+
+impl AwsKmsSecp256k1Signer {
+    /// Create a KMS signer for the given key ID, fetching and caching its public key.
+    pub async fn new(key_id: String) -> Result<Self, BlockchainError> {
+        let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+        let client = aws_sdk_kms::Client::new(&config);
+
+        info!(key_id = %key_id, "Initializing AWS KMS secp256k1 signer");
+
+        let response = client
+            .get_public_key()
+            .key_id(&key_id)
+            .send()
+            .await
+            .map_err(|e| {
+                BlockchainError::SubmissionFailed(format!("KMS GetPublicKey failed: {e}"))
+            })?;
+        let spki_blob = response
+            .public_key
+            .ok_or_else(|| {
+                BlockchainError::SubmissionFailed("KMS returned no public key blob".to_string())
+            })?
+            .into_inner();
+        let verifying_key = EcdsaVerifyingKey::from_public_key_der(&spki_blob).map_err(|e| {
+            BlockchainError::SubmissionFailed(format!("KMS key is not secp256k1: {e}"))
+        })?;
+        info!(public_key = %encode_public_key(&verifying_key), "KMS signer initialized");
+
+        Ok(Self {
+            client,
+            key_id,
+            verifying_key,
+        })
+    }
+}
+
+/// Turn a KMS DER signature into a low-`s` recoverable signature for `verifying_key`
+fn recoverable_from_der(
+    der: &[u8],
+    digest: &[u8; 32],
+    verifying_key: &EcdsaVerifyingKey,
+) -> Result<String, BlockchainError> {
+    let signature = EcdsaSignature::from_der(der)
+        .map_err(|e| BlockchainError::SubmissionFailed(format!("Invalid KMS signature: {e}")))?;
+    let signature = signature.normalize_s().unwrap_or(signature);
+    let recovery_id = RecoveryId::trial_recovery_from_prehash(verifying_key, digest, &signature)
+        .map_err(|_| {
+            BlockchainError::SubmissionFailed(
+                "KMS signature does not match the key's public key".to_string(),
+            )
+        })?;
+    Ok(encode_recoverable(&signature, recovery_id))
+}
+
+#[async_trait]
+impl TransactionSigner for AwsKmsSecp256k1Signer {
+    async fn sign_message(&self, message: &[u8]) -> Result<String, BlockchainError> {
+        let digest = prehash(message)?;
+        debug!(key_id = %self.key_id, "Calling KMS Sign (ECDSA_SHA_256)");
+
+        let response = self
+            .client
+            .sign()
+            .key_id(&self.key_id)
+            .message(Blob::new(digest.as_slice()))
+            .message_type(MessageType::Digest)
+            .signing_algorithm(SigningAlgorithmSpec::EcdsaSha256)
+            .send()
+            .await
+            .map_err(|e| BlockchainError::SubmissionFailed(format!("KMS Sign failed: {e}")))?;
+        let signature_blob = response.signature.ok_or_else(|| {
+            BlockchainError::SubmissionFailed("KMS returned no signature blob".to_string())
+        })?;
+
+        recoverable_from_der(signature_blob.as_ref(), digest, &self.verifying_key)
+    }
+
+    fn public_key(&self) -> String {
+        encode_public_key(&self.verifying_key)
+    }
+
+    fn scheme(&self) -> SignatureScheme {
+        SignatureScheme::Secp256k1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::SigningKey as EcdsaSigningKey;
+
+    const DIGEST: [u8; 32] = [0xab; 32];
+
+    fn decode_recoverable(encoded: &str) -> (EcdsaSignature, RecoveryId) {
+        let bytes = bs58::decode(encoded).into_vec().unwrap();
+        assert_eq!(bytes.len(), 65);
+        (
+            EcdsaSignature::from_slice(&bytes[..64]).unwrap(),
+            RecoveryId::from_byte(bytes[64]).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_generated_keypair_loads_as_local_signer() {
+        let secret = generate_keypair();
+        let bytes = bs58::decode(secret.expose_secret()).into_vec().unwrap();
+        assert_eq!(bytes.len(), 64);
+
+        let signer = LocalSigner::new(secret).unwrap();
+        assert_eq!(
+            signer.public_key(),
+            bs58::encode(&bytes[32..]).into_string()
+        );
+        assert_ne!(
+            generate_keypair().expose_secret(),
+            bs58::encode(&bytes).into_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_local_secp256k1_signature_recovers_its_public_key() {
+        let signer = LocalSecp256k1Signer::new(SecretString::from("46".repeat(32))).unwrap();
+        assert_eq!(signer.scheme(), SignatureScheme::Secp256k1);
+
+        let (signature, recovery_id) =
+            decode_recoverable(&signer.sign_message(&DIGEST).await.unwrap());
+        let recovered =
+            EcdsaVerifyingKey::recover_from_prehash(&DIGEST, &signature, recovery_id).unwrap();
+        assert_eq!(encode_public_key(&recovered), signer.public_key());
+
+        assert!(signer.sign_message(b"not a digest").await.is_err());
+    }
+
+    #[test]
+    fn test_kms_der_signature_is_normalized_to_low_s() {
+        let key = EcdsaSigningKey::from_slice(&[0x46; 32]).unwrap();
+        let (low, _) = key.sign_prehash_recoverable(&DIGEST).unwrap();
+        // KMS may return either `s`; flip it to the high half
+        let (r, s) = low.split_scalars();
+        let high = EcdsaSignature::from_scalars(r, -s).unwrap();
+        assert!(high.normalize_s().is_some());
+
+        let encoded =
+            recoverable_from_der(high.to_der().as_bytes(), &DIGEST, key.verifying_key()).unwrap();
+        let (signature, recovery_id) = decode_recoverable(&encoded);
+        assert_eq!(signature, low);
+        let recovered =
+            EcdsaVerifyingKey::recover_from_prehash(&DIGEST, &signature, recovery_id).unwrap();
+        assert_eq!(&recovered, key.verifying_key());
+
+        let other = EcdsaSigningKey::from_slice(&[0x47; 32]).unwrap();
+        assert!(
+            recoverable_from_der(high.to_der().as_bytes(), &DIGEST, other.verifying_key()).is_err()
+        );
+    }
+}
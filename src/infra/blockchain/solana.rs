@@ -5,6 +5,7 @@
 
 use async_trait::async_trait;
 use ed25519_dalek::SigningKey;
+use rand::Rng;
 use reqwest::Client;
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
@@ -15,25 +16,18 @@ use tracing::{debug, info, instrument, warn};
 
 #[cfg(feature = "real-blockchain")]
 use solana_sdk::{
-    hash::Hash, instruction::Instruction, message::Message, pubkey::Pubkey, signature::Signature,
-    transaction::Transaction,
+    compute_budget::ComputeBudgetInstruction, hash::Hash, instruction::Instruction,
+    message::Message, pubkey::Pubkey, signature::Signature, transaction::Transaction,
 };
 #[cfg(feature = "real-blockchain")]
 use std::str::FromStr;
 
-use crate::domain::{BlockchainClient, BlockchainError, TransactionSigner};
-
-/// Map BlockchainError to a stable label for metrics.
-fn blockchain_error_type(e: &BlockchainError) -> &'static str {
-    match e {
-        BlockchainError::SubmissionFailed(_) => "submission_failed",
-        BlockchainError::SubmissionFailedWithBlockhash { .. } => "submission_failed_with_blockhash",
-        BlockchainError::BlockhashExpired => "blockhash_expired",
-        BlockchainError::NetworkError { .. } => "network_error",
-        BlockchainError::InsufficientFunds => "insufficient_funds",
-        BlockchainError::Timeout { .. } => "timeout",
-    }
-}
+use super::blockchain_error_type;
+use super::discovery::RpcEndpoints;
+use crate::domain::{
+    BlockchainClient, BlockchainError, FeeEstimate, OnChainTransaction, SubmissionTrace,
+    TransactionSigner,
+};
 
 /// Returns true if the error indicates the blockhash has expired or is invalid on-chain.
 #[cfg(feature = "real-blockchain")]
@@ -52,13 +46,113 @@ fn is_blockhash_expired(e: &BlockchainError) -> bool {
         || msg_lower.contains("block hash expired")
 }
 
+/// Returns true if the cluster has already processed this very transaction
+#[cfg(feature = "real-blockchain")]
+fn is_already_processed(e: &BlockchainError) -> bool {
+    let msg = match e {
+        BlockchainError::SubmissionFailed(s) => s.as_str(),
+        BlockchainError::SubmissionFailedWithBlockhash { message, .. } => message.as_str(),
+        _ => return false,
+    };
+    let msg_lower = msg.to_lowercase();
+    msg_lower.contains("already been processed") || msg_lower.contains("alreadyprocessed")
+}
+
+/// JSON-RPC error codes of a node that is behind, unhealthy or overloaded: the same call
+/// can succeed on a later attempt. `-32004` block not available, `-32005` node unhealthy,
+/// `-32014` block status not available yet, `-32016` minimum context slot not reached,
+/// `-32603` internal error, `-32429` rate limited (hosted RPC providers).
+const RETRYABLE_RPC_CODES: [i64; 6] = [-32004, -32005, -32014, -32016, -32603, -32429];
+
+/// JSON-RPC error code of a provider error (`<code>: <message>`), if it carries one
+fn rpc_error_code(message: &str) -> Option<i64> {
+    message.split_once(':')?.0.trim().parse().ok()
+}
+
+/// Whether a failed RPC call may succeed when repeated: connection failures, timeouts
+/// and [`RETRYABLE_RPC_CODES`]. Invalid params, rejected transactions, missing funds and
+/// responses that do not parse fail the same way every time.
+fn is_retryable(e: &BlockchainError) -> bool {
+    match e {
+        BlockchainError::NetworkError { .. } | BlockchainError::Timeout { .. } => true,
+        BlockchainError::SubmissionFailed(message) => {
+            rpc_error_code(message).is_some_and(|code| RETRYABLE_RPC_CODES.contains(&code))
+        }
+        _ => false,
+    }
+}
+
+/// Bytes of a durable nonce account, which its rent exemption is computed for
+#[cfg(feature = "real-blockchain")]
+const NONCE_ACCOUNT_LENGTH: usize = 80;
+
+/// Lamports every transaction signature pays
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// Compute units requested per submission: the memo and compute budget instructions,
+/// with headroom. The priority fee is paid on the whole limit.
+pub const DEFAULT_COMPUTE_UNIT_LIMIT: u32 = 50_000;
+
+/// Compute-unit price Solana submissions offer (`SOLANA_PRIORITY_FEE`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PriorityFee {
+    /// No compute budget instructions: the base fee only
+    #[default]
+    None,
+    /// This many micro-lamports per compute unit
+    Fixed(u64),
+    /// The `percentile` of `getRecentPrioritizationFees` for the fee payer, looked up for
+    /// every submission and capped at `max` micro-lamports per compute unit
+    Auto { percentile: u8, max: u64 },
+}
+
+impl PriorityFee {
+    pub const DEFAULT_PERCENTILE: u8 = 75;
+    pub const DEFAULT_MAX: u64 = 1_000_000;
+
+    /// `none`, `auto` (with the given percentile and cap) or a price in micro-lamports
+    /// per compute unit
+    pub fn parse(value: &str, percentile: u8, max: u64) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "none" | "0" => Ok(Self::None),
+            "auto" if percentile <= 100 => Ok(Self::Auto { percentile, max }),
+            "auto" => Err(format!(
+                "Priority fee percentile {} is above 100",
+                percentile
+            )),
+            price => price.parse().map(Self::Fixed).map_err(|_| {
+                format!(
+                    "Invalid priority fee '{}': must be none, auto or micro-lamports per compute unit",
+                    price
+                )
+            }),
+        }
+    }
+}
+
 /// Configuration for the RPC client
 #[derive(Debug, Clone)]
 pub struct RpcClientConfig {
     pub timeout: Duration,
+    /// Retries of an RPC call that failed with a retryable error
     pub max_retries: u32,
+    /// Delay before the first retry; it doubles with each further retry, and every delay
+    /// is randomized to between half and all of it so clients do not retry in lockstep
     pub retry_delay: Duration,
+    /// Longest delay between two attempts
+    pub max_retry_delay: Duration,
+    /// Total time one RPC call may spend, retries included: no retry starts that would
+    /// begin after it (None: only `max_retries` limits)
+    pub retry_budget: Option<Duration>,
     pub confirmation_timeout: Duration,
+    /// Compute-unit price of submissions
+    pub priority_fee: PriorityFee,
+    /// Compute units requested by submissions that carry a priority fee
+    pub compute_unit_limit: u32,
+    /// Durable nonce account (`SOLANA_NONCE_ACCOUNT`), controlled by the fee payer.
+    /// Submissions use its stored value instead of a recent blockhash, so a retry can
+    /// resend the identical transaction for as long as it has not landed.
+    pub nonce_account: Option<String>,
 }
 
 impl Default for RpcClientConfig {
@@ -67,11 +161,99 @@ impl Default for RpcClientConfig {
             timeout: Duration::from_secs(30),
             max_retries: 3,
             retry_delay: Duration::from_millis(500),
+            max_retry_delay: Duration::from_secs(5),
+            retry_budget: Some(Duration::from_secs(60)),
             confirmation_timeout: Duration::from_secs(60),
+            priority_fee: PriorityFee::None,
+            compute_unit_limit: DEFAULT_COMPUTE_UNIT_LIMIT,
+            nonce_account: None,
         }
     }
 }
 
+/// Compute budget instructions of one submission
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ComputeBudget {
+    unit_limit: u32,
+    /// Micro-lamports per compute unit
+    unit_price: u64,
+}
+
+impl ComputeBudget {
+    /// Priority fee in lamports, rounded up as the runtime does
+    fn priority_fee(self) -> u64 {
+        let micro_lamports = u128::from(self.unit_price) * u128::from(self.unit_limit);
+        u64::try_from(micro_lamports.div_ceil(1_000_000)).unwrap_or(u64::MAX)
+    }
+}
+
+/// What a retry needs to rebuild the identical transaction, even after the fee market,
+/// the nonce account or the configuration moved
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Sticky {
+    /// Durable nonce account whose value `blockhash` is, if the submission uses one
+    nonce_account: Option<String>,
+    blockhash: String,
+    budget: Option<ComputeBudget>,
+}
+
+impl Sticky {
+    /// The outbox's sticky value: the blockhash, prefixed with `nonce:<account>:` when it
+    /// is a durable nonce and followed by `:<unit_price>:<unit_limit>` when the submission
+    /// carries a priority fee
+    fn encode(&self) -> String {
+        let mut value = match &self.nonce_account {
+            Some(account) => format!("nonce:{}:{}", account, self.blockhash),
+            None => self.blockhash.clone(),
+        };
+        if let Some(budget) = self.budget {
+            value = format!("{}:{}:{}", value, budget.unit_price, budget.unit_limit);
+        }
+        value
+    }
+
+    /// Inverse of [`Sticky::encode`]; a bare blockhash (also from before priority fees)
+    /// has no compute budget
+    fn parse(sticky: &str) -> Self {
+        let (nonce_account, rest) = match sticky
+            .strip_prefix("nonce:")
+            .and_then(|rest| rest.split_once(':'))
+        {
+            Some((account, rest)) => (Some(account.to_string()), rest),
+            None => (None, sticky),
+        };
+        let mut parts = rest.splitn(3, ':');
+        if let (Some(blockhash), Some(price), Some(limit)) =
+            (parts.next(), parts.next(), parts.next())
+            && let (Ok(unit_price), Ok(unit_limit)) = (price.parse(), limit.parse())
+        {
+            return Self {
+                nonce_account,
+                blockhash: blockhash.to_string(),
+                budget: Some(ComputeBudget {
+                    unit_limit,
+                    unit_price,
+                }),
+            };
+        }
+        Self {
+            nonce_account,
+            blockhash: rest.to_string(),
+            budget: None,
+        }
+    }
+}
+
+/// Value at `percentile` (nearest rank, rounding down) of `values`; 0 when empty
+fn percentile(mut values: Vec<u64>, percentile: u8) -> u64 {
+    if values.is_empty() {
+        return 0;
+    }
+    values.sort_unstable();
+    let rank = (values.len() - 1) * usize::from(percentile.min(100)) / 100;
+    values[rank]
+}
+
 /// Abstract provider for Solana RPC interactions to enable testing.
 /// Signing is handled by a separate [TransactionSigner]; the provider is RPC-only.
 #[async_trait]
@@ -85,13 +267,25 @@ pub trait SolanaRpcProvider: Send + Sync {
 }
 
 /// HTTP-based Solana RPC provider (RPC only; no signing).
+///
+/// Requests go to the endpoint that answered last. Connection errors, timeouts and
+/// `429`/`5xx` responses fail over to the next endpoint in [`RpcEndpoints`] order;
+/// JSON-RPC errors come from a reachable node and are returned as-is.
 pub struct HttpSolanaRpcProvider {
     http_client: Client,
-    rpc_url: String,
+    endpoints: RpcEndpoints,
 }
 
 impl HttpSolanaRpcProvider {
     pub fn new(rpc_url: &str, timeout: Duration) -> Result<Self, BlockchainError> {
+        Self::with_endpoints(RpcEndpoints::new(vec![rpc_url.to_string()]), timeout)
+    }
+
+    /// Provider failing over across `endpoints` (which discovery may replace at runtime)
+    pub fn with_endpoints(
+        endpoints: RpcEndpoints,
+        timeout: Duration,
+    ) -> Result<Self, BlockchainError> {
         let http_client = Client::builder().timeout(timeout).build().map_err(|e| {
             BlockchainError::NetworkError {
                 message: e.to_string(),
@@ -101,29 +295,19 @@ impl HttpSolanaRpcProvider {
 
         Ok(Self {
             http_client,
-            rpc_url: rpc_url.to_string(),
+            endpoints,
         })
     }
-}
 
-#[async_trait]
-impl SolanaRpcProvider for HttpSolanaRpcProvider {
-    async fn send_request(
+    async fn send_to(
         &self,
-        method: &str,
-        params: serde_json::Value,
+        rpc_url: &str,
+        request: &JsonRpcRequest<serde_json::Value>,
     ) -> Result<serde_json::Value, BlockchainError> {
-        let request = JsonRpcRequest {
-            jsonrpc: "2.0",
-            id: 1,
-            method: method.to_string(),
-            params,
-        };
-
         let response = self
             .http_client
-            .post(&self.rpc_url)
-            .json(&request)
+            .post(rpc_url)
+            .json(request)
             .send()
             .await
             .map_err(|e| {
@@ -139,6 +323,13 @@ impl SolanaRpcProvider for HttpSolanaRpcProvider {
                     }
                 }
             })?;
+        let status = response.status();
+        if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(BlockchainError::NetworkError {
+                message: format!("RPC endpoint answered {}", status),
+                blockhash: String::new(),
+            });
+        }
 
         let rpc_response: JsonRpcResponse<serde_json::Value> = response
             .json()
@@ -162,6 +353,48 @@ impl SolanaRpcProvider for HttpSolanaRpcProvider {
     }
 }
 
+#[async_trait]
+impl SolanaRpcProvider for HttpSolanaRpcProvider {
+    async fn send_request(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, BlockchainError> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: 1,
+            method: method.to_string(),
+            params,
+        };
+
+        let endpoints = self.endpoints.attempt_order();
+        let mut last_error = BlockchainError::NetworkError {
+            message: "No RPC endpoints configured".to_string(),
+            blockhash: String::new(),
+        };
+        for (attempt, rpc_url) in endpoints.iter().enumerate() {
+            match self.send_to(rpc_url, &request).await {
+                Err(
+                    e @ (BlockchainError::NetworkError { .. } | BlockchainError::Timeout { .. }),
+                ) => {
+                    SubmissionTrace::record_unreachable(rpc_url);
+                    if attempt + 1 < endpoints.len() {
+                        metrics::counter!("solana_rpc_failovers_total").increment(1);
+                        warn!(rpc_url = %rpc_url, error = %e, method = %method, "RPC endpoint unreachable, failing over");
+                    }
+                    last_error = e;
+                }
+                result => {
+                    self.endpoints.mark_answered(rpc_url);
+                    SubmissionTrace::record_answered(rpc_url);
+                    return result;
+                }
+            }
+        }
+        Err(last_error)
+    }
+}
+
 /// Solana RPC blockchain client. Signing is delegated to a [TransactionSigner].
 pub struct RpcBlockchainClient {
     provider: Box<dyn SolanaRpcProvider>,
@@ -178,11 +411,27 @@ struct JsonRpcRequest<T: Serialize> {
 }
 
 #[derive(Debug, Deserialize)]
-struct JsonRpcResponse<T> {
+struct JsonRpcResponse<T: DeserializeOwned> {
+    #[serde(default, deserialize_with = "deserialize_result")]
     result: Option<T>,
     error: Option<JsonRpcError>,
 }
 
+/// A `null` result is kept when `T` accepts it (e.g. `getTransaction` for an unknown
+/// signature) and only reads as missing otherwise
+fn deserialize_result<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    match serde_json::from_value(value.clone()) {
+        Ok(result) => Ok(Some(result)),
+        Err(_) if value.is_null() => Ok(None),
+        Err(e) => Err(serde::de::Error::custom(e)),
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct JsonRpcError {
     code: i64,
@@ -199,6 +448,13 @@ struct BlockhashResult {
     value: BlockhashResponse,
 }
 
+/// One entry of `getRecentPrioritizationFees` (one per recent slot)
+#[derive(Debug, Deserialize)]
+struct PrioritizationFee {
+    #[serde(rename = "prioritizationFee")]
+    prioritization_fee: u64,
+}
+
 #[derive(Debug, Deserialize)]
 struct SignatureStatus {
     err: Option<serde_json::Value>,
@@ -211,6 +467,52 @@ struct SignatureStatusResult {
     value: Vec<Option<SignatureStatus>>,
 }
 
+/// `getTransaction` result in `jsonParsed` encoding (only what verification reads)
+#[derive(Debug, Deserialize)]
+struct TransactionResult {
+    slot: u64,
+    meta: Option<TransactionMeta>,
+    transaction: ParsedTransaction,
+}
+
+#[derive(Debug, Deserialize)]
+struct TransactionMeta {
+    err: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ParsedTransaction {
+    message: ParsedMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ParsedMessage {
+    instructions: Vec<ParsedInstruction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ParsedInstruction {
+    #[serde(default)]
+    program: Option<String>,
+    #[serde(default)]
+    parsed: Option<serde_json::Value>,
+}
+
+impl TransactionResult {
+    /// Text of the memo instruction; None for a failed transaction, which records nothing
+    fn memo(&self) -> Option<String> {
+        if self.meta.as_ref().is_some_and(|meta| meta.err.is_some()) {
+            return None;
+        }
+        self.transaction
+            .message
+            .instructions
+            .iter()
+            .find(|ix| ix.program.as_deref() == Some("spl-memo"))
+            .and_then(|ix| ix.parsed.as_ref()?.as_str().map(str::to_string))
+    }
+}
+
 impl RpcBlockchainClient {
     /// Create a new RPC blockchain client with custom configuration
     pub fn new(
@@ -227,6 +529,21 @@ impl RpcBlockchainClient {
         })
     }
 
+    /// Create a client that fails over across `endpoints`
+    pub fn with_endpoints(
+        endpoints: RpcEndpoints,
+        signer: Arc<dyn TransactionSigner>,
+        config: RpcClientConfig,
+    ) -> Result<Self, BlockchainError> {
+        info!(rpc_urls = ?endpoints.urls(), "Created blockchain client");
+        let provider = HttpSolanaRpcProvider::with_endpoints(endpoints, config.timeout)?;
+        Ok(Self {
+            provider: Box::new(provider),
+            signer,
+            config,
+        })
+    }
+
     /// Create a new RPC blockchain client with default configuration
     pub fn with_defaults(
         rpc_url: &str,
@@ -269,7 +586,14 @@ impl RpcBlockchainClient {
         let mut last_error = None;
         for attempt in 0..=self.config.max_retries {
             if attempt > 0 {
-                tokio::time::sleep(self.config.retry_delay).await;
+                let delay = self.retry_delay(attempt);
+                if let Some(budget) = self.config.retry_budget
+                    && start.elapsed() + delay > budget
+                {
+                    warn!(attempt = attempt, method = %method, ?budget, "RPC retry budget exhausted");
+                    break;
+                }
+                tokio::time::sleep(delay).await;
             }
             match self
                 .provider
@@ -295,7 +619,11 @@ impl RpcBlockchainClient {
                     )
                     .increment(1);
                     warn!(attempt = attempt, error = ?e, method = %method, "RPC call failed");
+                    let retryable = is_retryable(&e);
                     last_error = Some(e);
+                    if !retryable {
+                        break;
+                    }
                 }
             }
         }
@@ -310,27 +638,220 @@ impl RpcBlockchainClient {
         Err(err)
     }
 
+    /// Delay before retry number `attempt` (1-based): `retry_delay` doubled per earlier
+    /// retry, capped at `max_retry_delay`, then randomized to between half and all of it
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        let full = self
+            .config
+            .retry_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.config.max_retry_delay);
+        rand::thread_rng().gen_range(full / 2..=full)
+    }
+
+    /// Compute budget of a new submission: None without a priority fee, else the fixed
+    /// price or the configured percentile of the fee payer's recent prioritization fees
+    async fn compute_budget(&self) -> Result<Option<ComputeBudget>, BlockchainError> {
+        let unit_price = match self.config.priority_fee {
+            PriorityFee::None => return Ok(None),
+            PriorityFee::Fixed(price) => price,
+            PriorityFee::Auto {
+                percentile: rank,
+                max,
+            } => {
+                let params = serde_json::json!([[self.signer.public_key()]]);
+                let fees: Vec<PrioritizationFee> =
+                    self.rpc_call("getRecentPrioritizationFees", params).await?;
+                let recent = percentile(
+                    fees.into_iter().map(|fee| fee.prioritization_fee).collect(),
+                    rank,
+                );
+                if recent > max {
+                    warn!(
+                        recent,
+                        max, "Recent priority fees exceed the cap; submissions may be slow to land"
+                    );
+                }
+                recent.min(max)
+            }
+        };
+        metrics::gauge!("solana_priority_fee_micro_lamports").set(unit_price as f64);
+        Ok(Some(ComputeBudget {
+            unit_limit: self.config.compute_unit_limit,
+            unit_price,
+        }))
+    }
+
+    /// Current value of the durable nonce `account`, which must be initialized with the
+    /// fee payer as its authority
+    async fn nonce_value(&self, account: &str) -> Result<String, BlockchainError> {
+        let params =
+            serde_json::json!([account, {"encoding": "jsonParsed", "commitment": "confirmed"}]);
+        let result: NonceAccountResult = self.rpc_call("getAccountInfo", params).await?;
+        let info = result
+            .value
+            .map(|nonce| nonce.data.parsed)
+            .filter(|parsed| parsed.kind == "initialized")
+            .and_then(|parsed| parsed.info)
+            .ok_or_else(|| {
+                BlockchainError::SubmissionFailed(format!(
+                    "Nonce account {} does not exist or is not initialized",
+                    account
+                ))
+            })?;
+        let payer = self.signer.public_key();
+        if info.authority != payer {
+            return Err(BlockchainError::SubmissionFailed(format!(
+                "Nonce account {} is controlled by {}, not the fee payer {}",
+                account, info.authority, payer
+            )));
+        }
+        Ok(info.blockhash)
+    }
+
+    /// Advance the durable nonce account: every transaction signed against its current
+    /// value, such as a dead-lettered submission, can no longer land. Returns the signature.
+    #[instrument(skip(self))]
+    pub async fn advance_nonce(&self) -> Result<String, BlockchainError> {
+        let account = self.config.nonce_account.as_deref().ok_or_else(|| {
+            BlockchainError::SubmissionFailed("No durable nonce account is configured".to_string())
+        })?;
+        let value = self.nonce_value(account).await?;
+
+        #[cfg(feature = "real-blockchain")]
+        {
+            let payer = self.payer()?;
+            let nonce_pubkey = parse_pubkey(account)?;
+            let mut message = Message::new_with_nonce(vec![], Some(&payer), &nonce_pubkey, &payer);
+            message.recent_blockhash = parse_hash(&value)?;
+            let (tx, _) = self.sign_transaction(message, None).await?;
+            let params = serde_json::json!([tx, {"encoding": "base58"}]);
+            let signature: String = self.rpc_call("sendTransaction", params).await?;
+            info!(signature = %signature, nonce_account = %account, "Nonce advanced");
+            Ok(signature)
+        }
+
+        #[cfg(not(feature = "real-blockchain"))]
+        {
+            let _ = value;
+            Err(BlockchainError::SubmissionFailed(
+                "Durable nonce transactions need the real-blockchain feature".to_string(),
+            ))
+        }
+    }
+
+    /// Create a durable nonce account at `nonce_key`'s address, funded for rent exemption
+    /// and controlled by the fee payer. Returns the account address and the signature.
+    #[instrument(skip(self, nonce_key))]
+    pub async fn create_nonce_account(
+        &self,
+        nonce_key: &SigningKey,
+    ) -> Result<(String, String), BlockchainError> {
+        #[cfg(feature = "real-blockchain")]
+        {
+            let payer = self.payer()?;
+            let nonce_pubkey = Pubkey::from(nonce_key.verifying_key().to_bytes());
+            let lamports: u64 = self
+                .rpc_call(
+                    "getMinimumBalanceForRentExemption",
+                    serde_json::json!([NONCE_ACCOUNT_LENGTH]),
+                )
+                .await?;
+            let instructions = solana_system_interface::instruction::create_nonce_account(
+                &payer,
+                &nonce_pubkey,
+                &payer,
+                lamports,
+            );
+            let blockhash = parse_hash(&self.get_latest_blockhash().await?)?;
+            let message = Message::new_with_blockhash(&instructions, Some(&payer), &blockhash);
+            let (tx, _) = self.sign_transaction(message, Some(nonce_key)).await?;
+            let params = serde_json::json!([tx, {"encoding": "base58"}]);
+            let signature: String = self.rpc_call("sendTransaction", params).await?;
+            info!(signature = %signature, nonce_account = %nonce_pubkey, lamports, "Nonce account created");
+            Ok((nonce_pubkey.to_string(), signature))
+        }
+
+        #[cfg(not(feature = "real-blockchain"))]
+        {
+            let _ = nonce_key;
+            Err(BlockchainError::SubmissionFailed(
+                "Durable nonce transactions need the real-blockchain feature".to_string(),
+            ))
+        }
+    }
+
+    /// Whether `signature` landed without error, at any commitment
+    #[cfg(feature = "real-blockchain")]
+    async fn signature_landed(&self, signature: &str) -> Result<bool, BlockchainError> {
+        let params = serde_json::json!([[signature], {"searchTransactionHistory": true}]);
+        let result: SignatureStatusResult = self.rpc_call("getSignatureStatuses", params).await?;
+        Ok(matches!(result.value.first(), Some(Some(status)) if status.err.is_none()))
+    }
+
+    /// Fee payer public key (the signer's)
+    #[cfg(feature = "real-blockchain")]
+    fn payer(&self) -> Result<Pubkey, BlockchainError> {
+        parse_pubkey(&self.signer.public_key())
+    }
+
     /// Build and serialize a memo transaction using solana-sdk for protocol-compliant
-    /// construction. Supports memos of any length (no 127-byte truncation).
+    /// construction. Supports memos of any length (no 127-byte truncation). A compute
+    /// budget adds its limit and price instructions ahead of the memo; a durable nonce
+    /// puts the nonce advance first. Returns the transaction and its signature.
     #[cfg(feature = "real-blockchain")]
     async fn build_memo_transaction(
         &self,
         memo: &str,
-        recent_blockhash: &str,
-    ) -> Result<String, BlockchainError> {
+        sticky: &Sticky,
+    ) -> Result<(String, String), BlockchainError> {
         const MEMO_PROGRAM_ID: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
 
-        let memo_program_id = Pubkey::from_str(MEMO_PROGRAM_ID)
-            .map_err(|e| BlockchainError::SubmissionFailed(e.to_string()))?;
-        let blockhash = Hash::from_str(recent_blockhash)
-            .map_err(|e| BlockchainError::SubmissionFailed(e.to_string()))?;
-        let payer_pubkey = Pubkey::from_str(&self.signer.public_key())
-            .map_err(|e| BlockchainError::SubmissionFailed(e.to_string()))?;
-
-        let instruction = Instruction::new_with_bytes(memo_program_id, memo.as_bytes(), vec![]);
+        let memo_program_id = parse_pubkey(MEMO_PROGRAM_ID)?;
+        let blockhash = parse_hash(&sticky.blockhash)?;
+        let payer_pubkey = self.payer()?;
+
+        let mut instructions = Vec::with_capacity(3);
+        if let Some(budget) = sticky.budget {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(
+                budget.unit_limit,
+            ));
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+                budget.unit_price,
+            ));
+        }
+        instructions.push(Instruction::new_with_bytes(
+            memo_program_id,
+            memo.as_bytes(),
+            vec![],
+        ));
 
-        let message = Message::new_with_blockhash(&[instruction], Some(&payer_pubkey), &blockhash);
+        let message = match &sticky.nonce_account {
+            Some(account) => {
+                let nonce_pubkey = parse_pubkey(account)?;
+                let mut message = Message::new_with_nonce(
+                    instructions,
+                    Some(&payer_pubkey),
+                    &nonce_pubkey,
+                    &payer_pubkey,
+                );
+                message.recent_blockhash = blockhash;
+                message
+            }
+            None => Message::new_with_blockhash(&instructions, Some(&payer_pubkey), &blockhash),
+        };
+        self.sign_transaction(message, None).await
+    }
 
+    /// Sign `message` as the fee payer, and with `co_signer` when an account it creates
+    /// must sign too. Returns the serialized transaction and the fee payer's signature.
+    #[cfg(feature = "real-blockchain")]
+    async fn sign_transaction(
+        &self,
+        message: Message,
+        co_signer: Option<&SigningKey>,
+    ) -> Result<(String, String), BlockchainError> {
+        let payer_pubkey = self.payer()?;
         let mut tx = Transaction::new_unsigned(message);
         let message_data = tx.message_data();
         let signature_str = self.signer.sign_message(&message_data).await?;
@@ -340,15 +861,68 @@ impl RpcBlockchainClient {
         let signature = Signature::try_from(signature_bytes.as_slice())
             .map_err(|e| BlockchainError::SubmissionFailed(e.to_string()))?;
 
-        tx.replace_signatures(&[(payer_pubkey, signature)])
+        let mut signatures = vec![(payer_pubkey, signature)];
+        if let Some(key) = co_signer {
+            use ed25519_dalek::Signer;
+            signatures.push((
+                Pubkey::from(key.verifying_key().to_bytes()),
+                Signature::from(key.sign(&message_data).to_bytes()),
+            ));
+        }
+        tx.replace_signatures(&signatures)
             .map_err(|e| BlockchainError::SubmissionFailed(e.to_string()))?;
 
         let serialized = bincode::serialize(&tx)
             .map_err(|e| BlockchainError::SubmissionFailed(e.to_string()))?;
-        Ok(bs58::encode(&serialized).into_string())
+        Ok((
+            bs58::encode(&serialized).into_string(),
+            signature.to_string(),
+        ))
     }
 }
 
+#[cfg(feature = "real-blockchain")]
+fn parse_pubkey(value: &str) -> Result<Pubkey, BlockchainError> {
+    Pubkey::from_str(value).map_err(|e| BlockchainError::SubmissionFailed(e.to_string()))
+}
+
+#[cfg(feature = "real-blockchain")]
+fn parse_hash(value: &str) -> Result<Hash, BlockchainError> {
+    Hash::from_str(value).map_err(|e| BlockchainError::SubmissionFailed(e.to_string()))
+}
+
+/// `getAccountInfo` result of a durable nonce account (`jsonParsed` encoding)
+#[derive(Debug, Deserialize)]
+struct NonceAccountResult {
+    value: Option<NonceAccount>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NonceAccount {
+    data: NonceAccountData,
+}
+
+#[derive(Debug, Deserialize)]
+struct NonceAccountData {
+    parsed: ParsedNonceAccount,
+}
+
+#[derive(Debug, Deserialize)]
+struct ParsedNonceAccount {
+    /// `initialized` or `uninitialized`
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    info: Option<NonceInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NonceInfo {
+    authority: String,
+    /// The stored nonce, used as the recent blockhash of transactions against it
+    blockhash: String,
+}
+
 /// Solana getBalance RPC result (result.value = balance in lamports, or null if account missing).
 #[derive(Debug, Deserialize)]
 struct GetBalanceResult {
@@ -366,19 +940,22 @@ impl BlockchainClient for RpcBlockchainClient {
             .map_err(|_| crate::domain::HealthCheckError::BlockchainUnavailable)?;
 
         // CRITICAL: heartbeat on funds every time the liveness probe runs
-        let pubkey = self.signer.public_key();
-        let params = vec![pubkey];
-        if let Ok(balance_result) = self
-            .rpc_call::<_, GetBalanceResult>("getBalance", params)
-            .await
-        {
-            let lamports = balance_result.value.unwrap_or(0);
-            metrics::gauge!("solana_wallet_balance_lamports").set(lamports as f64);
-        }
+        let _ = self.get_balance().await;
 
         Ok(())
     }
 
+    /// Fee payer balance via `getBalance` (0 for an account that does not exist yet);
+    /// also updates `solana_wallet_balance_lamports`
+    #[instrument(skip(self))]
+    async fn get_balance(&self) -> Result<u64, BlockchainError> {
+        let params = vec![self.signer.public_key()];
+        let result: GetBalanceResult = self.rpc_call("getBalance", params).await?;
+        let lamports = result.value.unwrap_or(0);
+        metrics::gauge!("solana_wallet_balance_lamports").set(lamports as f64);
+        Ok(lamports)
+    }
+
     #[instrument(skip(self))]
     async fn submit_transaction(
         &self,
@@ -387,80 +964,120 @@ impl BlockchainClient for RpcBlockchainClient {
     ) -> Result<(String, String), BlockchainError> {
         info!(hash = %hash, "Submitting transaction");
 
+        // Sticky blockhash (or nonce) and compute budget: reuse them when provided
+        // (retries), so the retry is the identical transaction; else take the current ones
+        let sticky = match existing_blockhash {
+            Some(sticky) => {
+                let sticky = Sticky::parse(sticky);
+                debug!(?sticky, "Reusing existing blockhash");
+                sticky
+            }
+            None => {
+                let budget = self.compute_budget().await?;
+                let sticky = match &self.config.nonce_account {
+                    Some(account) => Sticky {
+                        nonce_account: Some(account.clone()),
+                        blockhash: self.nonce_value(account).await?,
+                        budget,
+                    },
+                    None => {
+                        #[cfg(feature = "real-blockchain")]
+                        let blockhash = self.get_latest_blockhash().await?;
+                        #[cfg(not(feature = "real-blockchain"))]
+                        let blockhash = "mock_blockhash".to_string();
+                        Sticky {
+                            nonce_account: None,
+                            blockhash,
+                            budget,
+                        }
+                    }
+                };
+                debug!(?sticky, "Got recent blockhash");
+                sticky
+            }
+        };
+        let encoded = sticky.encode();
+
         #[cfg(feature = "real-blockchain")]
         {
-            // Sticky blockhash: use existing when provided (retries), else fetch latest
-            let blockhash = match existing_blockhash {
-                Some(h) => {
-                    debug!(blockhash = %h, "Reusing existing blockhash");
-                    h.to_string()
-                }
-                None => {
-                    let h = self.get_latest_blockhash().await?;
-                    debug!(blockhash = %h, "Got recent blockhash");
-                    h
-                }
-            };
-
             // CV-01: Propagate blockhash_used on build failure so service can persist for retry.
-            let tx = self
-                .build_memo_transaction(hash, &blockhash)
-                .await
-                .map_err(|e| BlockchainError::SubmissionFailedWithBlockhash {
-                    message: e.to_string(),
-                    blockhash_used: blockhash.clone(),
-                })?;
-            debug!("Built memo transaction");
+            let (tx, tx_signature) =
+                self.build_memo_transaction(hash, &sticky)
+                    .await
+                    .map_err(|e| BlockchainError::SubmissionFailedWithBlockhash {
+                        message: e.to_string(),
+                        blockhash_used: encoded.clone(),
+                    })?;
+            debug!(signature = %tx_signature, "Built memo transaction");
 
             // CV-01: On send failure (Timeout, NetworkError, SubmissionFailed), we must
             // propagate blockhash_used so the service persists it and does not clear it.
             // Otherwise a retry would fetch a new blockhash and create a new signature,
             // risking double-spend if the original transaction actually landed.
             let params = serde_json::json!([tx, {"encoding": "base58"}]);
-            let signature: String =
-                self.rpc_call("sendTransaction", params)
-                    .await
-                    .map_err(|e| {
-                        if is_blockhash_expired(&e) {
-                            BlockchainError::BlockhashExpired
-                        } else {
-                            match e {
-                                BlockchainError::Timeout { message, .. } => {
-                                    BlockchainError::Timeout {
-                                        message,
-                                        blockhash: blockhash.clone(),
-                                    }
-                                }
-                                BlockchainError::NetworkError { message, .. } => {
-                                    BlockchainError::NetworkError {
-                                        message,
-                                        blockhash: blockhash.clone(),
-                                    }
-                                }
-                                _ => BlockchainError::SubmissionFailedWithBlockhash {
-                                    message: e.to_string(),
-                                    blockhash_used: blockhash.clone(),
-                                },
-                            }
+            match self.rpc_call::<_, String>("sendTransaction", params).await {
+                Ok(signature) => {
+                    info!(signature = %signature, "Transaction sent");
+                    Ok((signature, encoded))
+                }
+                Err(e) if is_already_processed(&e) => {
+                    info!(signature = %tx_signature, "Transaction already processed");
+                    Ok((tx_signature, encoded))
+                }
+                // The blockhash expired or the nonce moved on: either an earlier send of
+                // this very transaction landed, or it never can and a fresh one is safe.
+                Err(e) if is_blockhash_expired(&e) => {
+                    match self.signature_landed(&tx_signature).await {
+                        Ok(true) => {
+                            info!(signature = %tx_signature, "Transaction already landed");
+                            Ok((tx_signature, encoded))
                         }
-                    })?;
-            info!(signature = %signature, "Transaction sent");
-            Ok((signature, blockhash))
+                        Ok(false) => Err(BlockchainError::BlockhashExpired),
+                        Err(e) => Err(BlockchainError::SubmissionFailedWithBlockhash {
+                            message: e.to_string(),
+                            blockhash_used: encoded,
+                        }),
+                    }
+                }
+                Err(BlockchainError::Timeout { message, .. }) => Err(BlockchainError::Timeout {
+                    message,
+                    blockhash: encoded,
+                }),
+                Err(BlockchainError::NetworkError { message, .. }) => {
+                    Err(BlockchainError::NetworkError {
+                        message,
+                        blockhash: encoded,
+                    })
+                }
+                Err(e) => Err(BlockchainError::SubmissionFailedWithBlockhash {
+                    message: e.to_string(),
+                    blockhash_used: encoded,
+                }),
+            }
         }
 
         #[cfg(not(feature = "real-blockchain"))]
         {
             let signature = self.signer.sign_message(hash.as_bytes()).await?;
-            let blockhash_used = existing_blockhash
-                .map(String::from)
-                .unwrap_or_else(|| "mock_blockhash".to_string());
             Ok((
                 format!("tx_{}", &signature[..16.min(signature.len())]),
-                blockhash_used,
+                encoded,
             ))
         }
     }
 
+    /// One signature plus the priority fee the configured compute budget would offer
+    #[instrument(skip(self))]
+    async fn estimate_fee(&self) -> Result<FeeEstimate, BlockchainError> {
+        let budget = self.compute_budget().await?;
+        Ok(FeeEstimate {
+            base_fee: LAMPORTS_PER_SIGNATURE,
+            unit_price: budget.map_or(0, |budget| budget.unit_price),
+            units: budget.map_or(0, |budget| u64::from(budget.unit_limit)),
+            priority_fee: budget.map_or(0, ComputeBudget::priority_fee),
+        })
+    }
+
     #[instrument(skip(self))]
     async fn get_block_height(&self) -> Result<u64, BlockchainError> {
         self.rpc_call("getBlockHeight", Vec::<()>::new()).await
@@ -496,6 +1113,30 @@ impl BlockchainClient for RpcBlockchainClient {
         }
     }
 
+    /// `getTransaction` for the memo and slot; the depth is measured against `getSlot`
+    #[instrument(skip(self))]
+    async fn get_transaction(
+        &self,
+        signature: &str,
+    ) -> Result<Option<OnChainTransaction>, BlockchainError> {
+        let params = serde_json::json!([
+            signature,
+            {"encoding": "jsonParsed", "commitment": "confirmed", "maxSupportedTransactionVersion": 0}
+        ]);
+        let result: Option<TransactionResult> = self.rpc_call("getTransaction", params).await?;
+        let Some(tx) = result else {
+            return Ok(None);
+        };
+        let current_slot: u64 = self
+            .rpc_call("getSlot", serde_json::json!([{"commitment": "confirmed"}]))
+            .await?;
+        Ok(Some(OnChainTransaction {
+            slot: tx.slot,
+            memo: tx.memo(),
+            confirmation_depth: current_slot.saturating_sub(tx.slot),
+        }))
+    }
+
     #[instrument(skip(self))]
     async fn wait_for_confirmation(
         &self,
@@ -660,7 +1301,12 @@ mod tests {
             timeout: Duration::from_secs(60),
             max_retries: 5,
             retry_delay: Duration::from_millis(1000),
+            max_retry_delay: Duration::from_secs(10),
+            retry_budget: None,
             confirmation_timeout: Duration::from_secs(120),
+            priority_fee: PriorityFee::Fixed(1_000),
+            compute_unit_limit: 20_000,
+            nonce_account: None,
         };
         assert_eq!(config.timeout, Duration::from_secs(60));
         assert_eq!(config.max_retries, 5);
@@ -886,6 +1532,258 @@ mod tests {
         }
     }
 
+    // --- RETRY CLASSIFICATION AND BACKOFF TESTS ---
+
+    #[test]
+    fn test_only_transient_errors_are_retryable() {
+        let rpc = |message: &str| BlockchainError::SubmissionFailed(message.to_string());
+        assert!(is_retryable(&BlockchainError::NetworkError {
+            message: "connection reset".to_string(),
+            blockhash: String::new(),
+        }));
+        assert!(is_retryable(&BlockchainError::Timeout {
+            message: "timed out".to_string(),
+            blockhash: String::new(),
+        }));
+        assert!(is_retryable(&rpc("-32005: Node is unhealthy")));
+        assert!(is_retryable(&rpc(
+            "-32016: Minimum context slot has not been reached"
+        )));
+
+        assert!(!is_retryable(&rpc("-32602: Invalid params")));
+        assert!(!is_retryable(&rpc(
+            "-32003: Transaction signature verification failure"
+        )));
+        assert!(!is_retryable(&rpc(
+            "Deserialization: missing field `value`"
+        )));
+        assert!(!is_retryable(&rpc("Empty response")));
+        assert!(!is_retryable(&BlockchainError::InsufficientFunds));
+    }
+
+    #[test]
+    fn test_retry_delay_doubles_with_jitter_up_to_the_cap() {
+        let config = RpcClientConfig {
+            retry_delay: Duration::from_millis(100),
+            max_retry_delay: Duration::from_millis(500),
+            ..Default::default()
+        };
+        let signer = test_signer_with_key(&SigningKey::generate(&mut OsRng));
+        let client = RpcBlockchainClient::with_provider(
+            Box::new(ConfigurableMockProvider::new()),
+            signer,
+            config,
+        );
+
+        for (attempt, full_ms) in [(1, 100), (2, 200), (3, 400), (4, 500), (30, 500)] {
+            let full = Duration::from_millis(full_ms);
+            for _ in 0..20 {
+                let delay = client.retry_delay(attempt);
+                assert!(delay >= full / 2 && delay <= full, "{attempt}: {delay:?}");
+            }
+        }
+    }
+
+    // --- PRIORITY FEE TESTS ---
+
+    #[test]
+    fn test_priority_fee_parse() {
+        assert_eq!(PriorityFee::parse("", 75, 10), Ok(PriorityFee::None));
+        assert_eq!(PriorityFee::parse("none", 75, 10), Ok(PriorityFee::None));
+        assert_eq!(
+            PriorityFee::parse("2500", 75, 10),
+            Ok(PriorityFee::Fixed(2_500))
+        );
+        assert_eq!(
+            PriorityFee::parse("AUTO", 90, 10),
+            Ok(PriorityFee::Auto {
+                percentile: 90,
+                max: 10
+            })
+        );
+        assert!(PriorityFee::parse("auto", 101, 10).is_err());
+        assert!(PriorityFee::parse("fast", 75, 10).is_err());
+    }
+
+    #[test]
+    fn test_sticky_value_keeps_the_compute_budget() {
+        let budget = ComputeBudget {
+            unit_limit: 50_000,
+            unit_price: 1_500,
+        };
+        let sticky = Sticky {
+            nonce_account: None,
+            blockhash: "9sHcv6xwn9YkB8nxTUGKDwPwNnmqVp5oAXxU8Fdkm4J6".to_string(),
+            budget: Some(budget),
+        };
+        assert_eq!(Sticky::parse(&sticky.encode()), sticky);
+        // Entries written before priority fees hold the bare blockhash
+        assert_eq!(
+            Sticky::parse("sticky_hash"),
+            Sticky {
+                nonce_account: None,
+                blockhash: "sticky_hash".to_string(),
+                budget: None,
+            }
+        );
+        assert_eq!(budget.priority_fee(), 75);
+        assert_eq!(percentile(vec![0, 40, 10, 30, 20], 75), 30);
+        assert_eq!(percentile(Vec::new(), 75), 0);
+    }
+
+    #[test]
+    fn test_sticky_value_keeps_the_nonce_account() {
+        let mut sticky = Sticky {
+            nonce_account: Some("NonceAccount1111111111111111111111111111111".to_string()),
+            blockhash: "9sHcv6xwn9YkB8nxTUGKDwPwNnmqVp5oAXxU8Fdkm4J6".to_string(),
+            budget: None,
+        };
+        assert_eq!(
+            sticky.encode(),
+            "nonce:NonceAccount1111111111111111111111111111111:9sHcv6xwn9YkB8nxTUGKDwPwNnmqVp5oAXxU8Fdkm4J6"
+        );
+        assert_eq!(Sticky::parse(&sticky.encode()), sticky);
+        sticky.budget = Some(ComputeBudget {
+            unit_limit: 50_000,
+            unit_price: 1_500,
+        });
+        assert_eq!(Sticky::parse(&sticky.encode()), sticky);
+    }
+
+    #[tokio::test]
+    async fn test_auto_priority_fee_follows_recent_fees_up_to_the_cap() {
+        let recent_fees = serde_json::json!([
+            { "slot": 1, "prioritizationFee": 0 },
+            { "slot": 2, "prioritizationFee": 2_000 },
+            { "slot": 3, "prioritizationFee": 8_000 },
+            { "slot": 4, "prioritizationFee": 500_000 },
+        ]);
+        let provider = ConfigurableMockProvider::with_responses(vec![
+            Ok(recent_fees.clone()),
+            Ok(recent_fees),
+        ]);
+        let config = RpcClientConfig {
+            max_retries: 0,
+            priority_fee: PriorityFee::Auto {
+                percentile: 75,
+                max: 5_000,
+            },
+            compute_unit_limit: 40_000,
+            ..Default::default()
+        };
+        let signer = test_signer_with_key(&SigningKey::generate(&mut OsRng));
+        let client = RpcBlockchainClient::with_provider(Box::new(provider), signer, config);
+
+        let estimate = client.estimate_fee().await.unwrap();
+        assert_eq!(estimate.unit_price, 5_000);
+        assert_eq!(estimate.units, 40_000);
+        assert_eq!(estimate.priority_fee, 200);
+        assert_eq!(estimate.total(), 5_200);
+
+        // The price is kept with the blockhash; the retry reuses it without a lookup
+        #[cfg(not(feature = "real-blockchain"))]
+        {
+            let (_, sticky) = client.submit_transaction("hash", None).await.unwrap();
+            assert_eq!(sticky, "mock_blockhash:5000:40000");
+            let (_, retried) = client
+                .submit_transaction("hash", Some(&sticky))
+                .await
+                .unwrap();
+            assert_eq!(retried, sticky);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_estimate_fee_without_priority_fee_is_the_signature_fee() {
+        let provider = ConfigurableMockProvider::new();
+        let signer = test_signer_with_key(&SigningKey::generate(&mut OsRng));
+        let client =
+            RpcBlockchainClient::with_provider(Box::new(provider), signer, Default::default());
+
+        let estimate = client.estimate_fee().await.unwrap();
+        assert_eq!(estimate.total(), LAMPORTS_PER_SIGNATURE);
+        assert_eq!(estimate.priority_fee, 0);
+        #[cfg(not(feature = "real-blockchain"))]
+        assert_eq!(
+            client.submit_transaction("hash", None).await.unwrap().1,
+            "mock_blockhash"
+        );
+    }
+
+    fn nonce_account_response(authority: &str, blockhash: &str) -> serde_json::Value {
+        serde_json::json!({
+            "context": { "slot": 1 },
+            "value": {
+                "data": {
+                    "parsed": {
+                        "type": "initialized",
+                        "info": {
+                            "authority": authority,
+                            "blockhash": blockhash,
+                            "feeCalculator": { "lamportsPerSignature": "5000" }
+                        }
+                    },
+                    "program": "nonce",
+                    "space": 80
+                }
+            }
+        })
+    }
+
+    #[tokio::test]
+    #[cfg(not(feature = "real-blockchain"))]
+    async fn test_submission_against_a_durable_nonce_keeps_it_for_retries() {
+        let signer = test_signer_with_key(&SigningKey::generate(&mut OsRng));
+        let provider = ConfigurableMockProvider::with_responses(vec![Ok(nonce_account_response(
+            &signer.public_key(),
+            "NonceValue",
+        ))]);
+        let config = RpcClientConfig {
+            max_retries: 0,
+            nonce_account: Some("NonceAccount".to_string()),
+            ..Default::default()
+        };
+        let client = RpcBlockchainClient::with_provider(Box::new(provider), signer, config);
+
+        let (_, sticky) = client.submit_transaction("hash", None).await.unwrap();
+        assert_eq!(sticky, "nonce:NonceAccount:NonceValue");
+        // The retry resends against the same nonce without looking it up again
+        let (_, retried) = client
+            .submit_transaction("hash", Some(&sticky))
+            .await
+            .unwrap();
+        assert_eq!(retried, sticky);
+    }
+
+    #[tokio::test]
+    async fn test_nonce_account_must_be_controlled_by_the_fee_payer() {
+        let signer = test_signer_with_key(&SigningKey::generate(&mut OsRng));
+        let uninitialized = serde_json::json!({
+            "context": { "slot": 1 },
+            "value": { "data": { "parsed": { "type": "uninitialized" } } }
+        });
+        let provider = ConfigurableMockProvider::with_responses(vec![
+            Ok(nonce_account_response("SomeoneElse", "NonceValue")),
+            Ok(uninitialized),
+            Ok(serde_json::json!({ "context": { "slot": 1 }, "value": null })),
+        ]);
+        let config = RpcClientConfig {
+            max_retries: 0,
+            nonce_account: Some("NonceAccount".to_string()),
+            ..Default::default()
+        };
+        let client = RpcBlockchainClient::with_provider(Box::new(provider), signer, config);
+
+        for _ in 0..3 {
+            let result = client.submit_transaction("hash", None).await;
+            assert!(
+                matches!(result, Err(BlockchainError::SubmissionFailed(ref msg)) if msg.contains("NonceAccount")),
+                "{:?}",
+                result
+            );
+        }
+    }
+
     // --- ERROR HANDLING TESTS ---
 
     #[tokio::test]
@@ -1077,6 +1975,57 @@ mod tests {
         assert!(!result.unwrap()); // Not found = not confirmed
     }
 
+    #[tokio::test]
+    async fn test_get_transaction_reads_memo_and_depth() {
+        let transaction = |err: serde_json::Value| {
+            serde_json::json!({
+                "slot": 1_000,
+                "meta": {"err": err},
+                "transaction": {"message": {"instructions": [
+                    {"program": "system", "parsed": {"type": "transfer"}},
+                    {"program": "spl-memo", "programId": "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr", "parsed": "abc123"}
+                ]}}
+            })
+        };
+        let provider = ConfigurableMockProvider::with_responses(vec![
+            Ok(transaction(serde_json::Value::Null)),
+            Ok(serde_json::json!(1_032)),
+            Ok(transaction(
+                serde_json::json!({"InstructionError": [0, "Custom"]}),
+            )),
+            Ok(serde_json::json!(1_040)),
+            Ok(serde_json::Value::Null),
+        ]);
+        let signer = test_signer_with_key(&SigningKey::generate(&mut OsRng));
+        let client = RpcBlockchainClient::with_provider(
+            Box::new(provider),
+            signer,
+            RpcClientConfig::default(),
+        );
+
+        let tx = client.get_transaction("sig").await.unwrap().unwrap();
+        assert_eq!(tx.slot, 1_000);
+        assert_eq!(tx.memo.as_deref(), Some("abc123"));
+        assert_eq!(tx.confirmation_depth, 32);
+
+        // A failed transaction landed but anchored nothing
+        let failed = client.get_transaction("sig").await.unwrap().unwrap();
+        assert_eq!(failed.memo, None);
+        assert_eq!(failed.confirmation_depth, 40);
+
+        assert_eq!(client.get_transaction("unknown").await.unwrap(), None);
+    }
+
+    #[test]
+    fn test_json_rpc_null_result_is_kept_for_optional_results() {
+        let json = serde_json::json!({"result": null});
+        let response: JsonRpcResponse<serde_json::Value> = serde_json::from_value(json).unwrap();
+        assert_eq!(response.result, Some(serde_json::Value::Null));
+        let response: JsonRpcResponse<serde_json::Value> =
+            serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(response.result, None);
+    }
+
     #[tokio::test]
     async fn test_get_transaction_status_with_error() {
         let provider = ConfigurableMockProvider::with_responses(vec![Ok(serde_json::json!({
@@ -1124,6 +2073,24 @@ mod tests {
         assert_eq!(result.unwrap(), 123456789);
     }
 
+    #[tokio::test]
+    async fn test_get_balance() {
+        let provider = ConfigurableMockProvider::with_responses(vec![
+            Ok(serde_json::json!({"context": {"slot": 1}, "value": 5000})),
+            Ok(serde_json::json!({"context": {"slot": 2}, "value": null})),
+        ]);
+        let signer = test_signer_with_key(&SigningKey::generate(&mut OsRng));
+        let client = RpcBlockchainClient::with_provider(
+            Box::new(provider),
+            signer,
+            RpcClientConfig::default(),
+        );
+
+        assert_eq!(client.get_balance().await.unwrap(), 5000);
+        // Missing account: nothing to pay fees with
+        assert_eq!(client.get_balance().await.unwrap(), 0);
+    }
+
     // --- WAIT FOR CONFIRMATION TESTS ---
 
     #[tokio::test]
@@ -1247,7 +2214,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_no_retry_on_insufficient_funds() {
-        // InsufficientFunds should still trigger retries as per current implementation
+        // InsufficientFunds is terminal: the call fails after its first attempt
         let provider = ConfigurableMockProvider::with_responses(vec![
             Err(MockErrorKind::InsufficientFunds),
             Err(MockErrorKind::InsufficientFunds),
@@ -1300,6 +2267,48 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_http_provider_fails_over_to_the_next_endpoint() {
+        use axum::{Json, Router, http::StatusCode, routing::post};
+
+        let app = Router::new()
+            .route("/busy", post(|| async { StatusCode::SERVICE_UNAVAILABLE }))
+            .route(
+                "/rpc",
+                post(|| async {
+                    Json(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": 42 }))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        // Nothing listens on port 1; /busy answers 503
+        let healthy = format!("http://{}/rpc", addr);
+        let endpoints = RpcEndpoints::new(vec![
+            "http://127.0.0.1:1".to_string(),
+            format!("http://{}/busy", addr),
+            healthy.clone(),
+        ]);
+        let provider =
+            HttpSolanaRpcProvider::with_endpoints(endpoints.clone(), Duration::from_secs(5))
+                .unwrap();
+
+        let result = provider
+            .send_request("getBlockHeight", serde_json::json!([]))
+            .await
+            .unwrap();
+        assert_eq!(result, 42);
+        // The endpoint that answered is tried first from now on
+        assert_eq!(endpoints.attempt_order()[0], healthy);
+
+        endpoints.replace(vec!["http://127.0.0.1:1".to_string()]);
+        let result = provider
+            .send_request("getBlockHeight", serde_json::json!([]))
+            .await;
+        assert!(matches!(result, Err(BlockchainError::NetworkError { .. })));
+    }
+
     #[test]
     fn test_http_solana_rpc_provider_with_client_public_key() {
         let signing_key = SigningKey::generate(&mut OsRng);
@@ -1449,12 +2458,294 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // --- HTTP TESTS (against a canned JSON-RPC node) ---
+
+    #[cfg(feature = "test-utils")]
+    mod http_tests {
+        use super::*;
+        use crate::test_utils::solana_rpc::{
+            MockSolanaRpc, RpcReply, balance_result, blockhash_result, slot_result,
+        };
+
+        const BLOCKHASH: &str = "GHtXQBsoZHVnNFa9YevAzFr17DJjgHXk3ycTy5nRhVT3";
+
+        fn fast_config(max_retries: u32) -> RpcClientConfig {
+            RpcClientConfig {
+                timeout: Duration::from_millis(200),
+                max_retries,
+                retry_delay: Duration::from_millis(1),
+                ..Default::default()
+            }
+        }
+
+        #[tokio::test]
+        async fn test_health_check_reads_slot_and_balance() {
+            let rpc = MockSolanaRpc::start().await;
+            rpc.mock("getSlot", slot_result(42)).await;
+            rpc.mock("getBalance", balance_result(1_000_000)).await;
+            let client = rpc.client(fast_config(0));
+
+            client.health_check().await.unwrap();
+            assert_eq!(client.get_balance().await.unwrap(), 1_000_000);
+            assert_eq!(rpc.methods().await, ["getSlot", "getBalance", "getBalance"]);
+        }
+
+        #[tokio::test]
+        async fn test_latest_blockhash_is_parsed_from_the_node() {
+            let rpc = MockSolanaRpc::start().await;
+            rpc.mock("getLatestBlockhash", blockhash_result(BLOCKHASH))
+                .await;
+            let client = rpc.client(fast_config(0));
+
+            assert_eq!(client.get_latest_blockhash().await.unwrap(), BLOCKHASH);
+        }
+
+        #[tokio::test]
+        async fn test_unavailable_node_is_retried_until_it_answers() {
+            let rpc = MockSolanaRpc::start().await;
+            rpc.mock_once("getBlockHeight", RpcReply::Status(503)).await;
+            rpc.mock_once("getBlockHeight", RpcReply::Status(429)).await;
+            rpc.mock("getBlockHeight", RpcReply::result(serde_json::json!(7)))
+                .await;
+            let client = rpc.client(fast_config(3));
+
+            assert_eq!(client.get_block_height().await.unwrap(), 7);
+            assert_eq!(rpc.calls("getBlockHeight").await, 3);
+        }
+
+        #[tokio::test]
+        async fn test_retries_stop_after_max_retries() {
+            let rpc = MockSolanaRpc::start().await;
+            rpc.mock("getBlockHeight", RpcReply::Status(502)).await;
+            let client = rpc.client(fast_config(2));
+
+            let result = client.get_block_height().await;
+            assert!(
+                matches!(result, Err(BlockchainError::NetworkError { ref message, .. }) if message.contains("502")),
+                "{:?}",
+                result
+            );
+            assert_eq!(rpc.calls("getBlockHeight").await, 3);
+        }
+
+        #[tokio::test]
+        async fn test_terminal_rpc_errors_are_not_retried() {
+            let rpc = MockSolanaRpc::start().await;
+            rpc.mock("getBlockHeight", RpcReply::error(-32602, "Invalid params"))
+                .await;
+            rpc.mock_once(
+                "getSlot",
+                RpcReply::error(-32005, "Node is behind by 42 slots"),
+            )
+            .await;
+            rpc.mock("getSlot", slot_result(3)).await;
+            let client = rpc.client(fast_config(3));
+
+            let result = client.get_block_height().await;
+            assert!(
+                matches!(result, Err(BlockchainError::SubmissionFailed(ref msg)) if msg.starts_with("-32602")),
+                "{:?}",
+                result
+            );
+            assert_eq!(rpc.calls("getBlockHeight").await, 1);
+            // An unhealthy node is retried
+            let slot: u64 = client.rpc_call("getSlot", Vec::<()>::new()).await.unwrap();
+            assert_eq!(slot, 3);
+            assert_eq!(rpc.calls("getSlot").await, 2);
+        }
+
+        #[tokio::test]
+        async fn test_retry_budget_stops_retries_early() {
+            let rpc = MockSolanaRpc::start().await;
+            rpc.mock("getBlockHeight", RpcReply::Status(503)).await;
+            let client = rpc.client(RpcClientConfig {
+                retry_delay: Duration::from_millis(200),
+                retry_budget: Some(Duration::from_millis(300)),
+                ..fast_config(10)
+            });
+
+            let result = client.get_block_height().await;
+            assert!(matches!(result, Err(BlockchainError::NetworkError { .. })));
+            // 100-200ms, then 200-400ms would pass the budget: at most three attempts
+            let calls = rpc.calls("getBlockHeight").await;
+            assert!((2..=3).contains(&calls), "{calls} attempts");
+        }
+
+        #[tokio::test]
+        async fn test_slow_node_times_out() {
+            let rpc = MockSolanaRpc::start().await;
+            rpc.mock("getSlot", slot_result(1).delayed(Duration::from_secs(2)))
+                .await;
+            let client = rpc.client(fast_config(0));
+
+            let result: Result<u64, _> = client.rpc_call("getSlot", Vec::<()>::new()).await;
+            assert!(
+                matches!(result, Err(BlockchainError::Timeout { .. })),
+                "{:?}",
+                result
+            );
+        }
+
+        #[tokio::test]
+        async fn test_malformed_responses_are_submission_failures() {
+            let rpc = MockSolanaRpc::start().await;
+            rpc.mock_once(
+                "getLatestBlockhash",
+                RpcReply::Raw("{\"jsonrpc\":".to_string()),
+            )
+            .await;
+            rpc.mock_once(
+                "getLatestBlockhash",
+                RpcReply::result(serde_json::json!({ "value": { "hash": BLOCKHASH } })),
+            )
+            .await;
+            rpc.mock_once(
+                "getLatestBlockhash",
+                RpcReply::result(serde_json::Value::Null),
+            )
+            .await;
+            let client = rpc.client(fast_config(0));
+
+            for _ in 0..3 {
+                let result = client.get_latest_blockhash().await;
+                assert!(
+                    matches!(result, Err(BlockchainError::SubmissionFailed(_))),
+                    "{:?}",
+                    result
+                );
+            }
+        }
+
+        #[tokio::test]
+        async fn test_json_rpc_errors_are_mapped() {
+            let rpc = MockSolanaRpc::start().await;
+            rpc.mock_once(
+                "getBlockHeight",
+                RpcReply::error(-32003, "Transaction signature verification failure"),
+            )
+            .await;
+            rpc.mock_once(
+                "getBlockHeight",
+                RpcReply::error(-32000, "Attempt to debit an account but found no record of a prior credit; insufficient funds"),
+            )
+            .await;
+            let client = rpc.client(fast_config(0));
+
+            let result = client.get_block_height().await;
+            assert!(
+                matches!(result, Err(BlockchainError::SubmissionFailed(ref msg)) if msg.starts_with("-32003")),
+                "{:?}",
+                result
+            );
+            assert!(matches!(
+                client.get_block_height().await,
+                Err(BlockchainError::InsufficientFunds)
+            ));
+        }
+
+        #[tokio::test]
+        async fn test_unreachable_endpoint_fails_over_to_the_next() {
+            let down = MockSolanaRpc::start().await;
+            down.mock("getSlot", RpcReply::Status(503)).await;
+            let up = MockSolanaRpc::start().await;
+            up.mock("getSlot", slot_result(9)).await;
+            up.mock("getBalance", balance_result(0)).await;
+            let endpoints = RpcEndpoints::new(vec![down.url(), up.url()]);
+            let signer = test_signer_with_key(&SigningKey::generate(&mut OsRng));
+            let client =
+                RpcBlockchainClient::with_endpoints(endpoints, signer, fast_config(0)).unwrap();
+
+            client.health_check().await.unwrap();
+            client.health_check().await.unwrap();
+            // The endpoint that answered is tried first from then on
+            assert_eq!(down.calls("getSlot").await, 1);
+            assert_eq!(up.calls("getSlot").await, 2);
+        }
+
+        #[cfg(feature = "real-blockchain")]
+        #[tokio::test]
+        async fn test_submission_is_sent_to_the_node() {
+            use crate::test_utils::solana_rpc::signature_result;
+
+            let rpc = MockSolanaRpc::start().await;
+            rpc.mock("getLatestBlockhash", blockhash_result(BLOCKHASH))
+                .await;
+            rpc.mock("sendTransaction", signature_result("5igSignature"))
+                .await;
+            let client = rpc.client(fast_config(0));
+
+            let (signature, sticky) = client.submit_transaction("hash", None).await.unwrap();
+            assert_eq!(signature, "5igSignature");
+            assert_eq!(sticky, BLOCKHASH);
+        }
+
+        #[cfg(feature = "real-blockchain")]
+        #[tokio::test]
+        async fn test_rejected_submission_keeps_the_blockhash_for_retry() {
+            use crate::test_utils::solana_rpc::simulation_failed;
+
+            let rpc = MockSolanaRpc::start().await;
+            rpc.mock(
+                "sendTransaction",
+                simulation_failed("Error processing Instruction 0"),
+            )
+            .await;
+            let client = rpc.client(fast_config(0));
+
+            let result = client.submit_transaction("hash", Some(BLOCKHASH)).await;
+            assert!(
+                matches!(result, Err(BlockchainError::SubmissionFailedWithBlockhash { ref blockhash_used, .. }) if blockhash_used == BLOCKHASH),
+                "{:?}",
+                result
+            );
+            // The retry reused the sticky blockhash instead of fetching a new one
+            assert_eq!(rpc.methods().await, ["sendTransaction"]);
+        }
+    }
+
     // --- BUILD MEMO TRANSACTION TESTS (only with real-blockchain feature) ---
 
     #[cfg(feature = "real-blockchain")]
     mod real_blockchain_tests {
         use super::*;
 
+        fn sticky(blockhash: &str, nonce_account: Option<&str>) -> Sticky {
+            Sticky {
+                nonce_account: nonce_account.map(str::to_string),
+                blockhash: blockhash.to_string(),
+                budget: None,
+            }
+        }
+
+        #[tokio::test]
+        async fn test_build_memo_transaction_against_a_durable_nonce() {
+            let signing_key = SigningKey::generate(&mut OsRng);
+            let signer = test_signer_with_key(&signing_key);
+            let client =
+                RpcBlockchainClient::with_defaults("https://api.devnet.solana.com", signer)
+                    .unwrap();
+
+            let nonce_value = "GHtXQBsoZHVnNFa9YevAzFr17DJjgHXk3ycTy5nRhVT3";
+            let nonce_account = Pubkey::new_unique().to_string();
+            let (tx, signature) = client
+                .build_memo_transaction("test_memo", &sticky(nonce_value, Some(&nonce_account)))
+                .await
+                .unwrap();
+
+            let bytes = bs58::decode(&tx).into_vec().unwrap();
+            let tx: Transaction = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(tx.signatures[0].to_string(), signature);
+            assert_eq!(tx.message.recent_blockhash.to_string(), nonce_value);
+            // The nonce advance comes first, so the runtime accepts the stored nonce
+            let first = &tx.message.instructions[0];
+            let program = tx.message.account_keys[usize::from(first.program_id_index)];
+            assert_eq!(program, solana_system_interface::program::ID);
+            assert_eq!(
+                tx.message.account_keys[usize::from(first.accounts[0])].to_string(),
+                nonce_account
+            );
+        }
+
         #[tokio::test]
         async fn test_build_memo_transaction_success() {
             let signing_key = SigningKey::generate(&mut OsRng);
@@ -1465,10 +2756,12 @@ mod tests {
 
             // Use a valid base58 blockhash (32 bytes)
             let blockhash = "GHtXQBsoZHVnNFa9YevAzFr17DJjgHXk3ycTy5nRhVT3";
-            let result = client.build_memo_transaction("test_memo", blockhash).await;
+            let result = client
+                .build_memo_transaction("test_memo", &sticky(blockhash, None))
+                .await;
             assert!(result.is_ok());
 
-            let tx = result.unwrap();
+            let (tx, _) = result.unwrap();
             // Should be valid base58
             assert!(bs58::decode(&tx).into_vec().is_ok());
         }
@@ -1483,7 +2776,7 @@ mod tests {
 
             // Invalid base58 blockhash
             let result = client
-                .build_memo_transaction("test_memo", "invalid!!!")
+                .build_memo_transaction("test_memo", &sticky("invalid!!!", None))
                 .await;
             assert!(matches!(result, Err(BlockchainError::SubmissionFailed(_))));
         }
@@ -1501,9 +2794,11 @@ mod tests {
             let long_memo: String = (0..200).map(|i| ((i % 26) as u8 + b'a') as char).collect();
             assert!(long_memo.len() > 127, "test memo must exceed 127 bytes");
 
-            let result = client.build_memo_transaction(&long_memo, blockhash).await;
+            let result = client
+                .build_memo_transaction(&long_memo, &sticky(blockhash, None))
+                .await;
             assert!(result.is_ok(), "long memo must succeed without truncation");
-            let tx = result.unwrap();
+            let (tx, _) = result.unwrap();
             assert!(bs58::decode(&tx).into_vec().is_ok());
         }
 
@@ -1533,6 +2828,7 @@ mod tests {
             max_retries: 2,
             retry_delay: Duration::from_millis(250),
             confirmation_timeout: Duration::from_secs(30),
+            ..Default::default()
         };
         let result = RpcBlockchainClient::new("https://api.devnet.solana.com", signer, config);
         assert!(result.is_ok());
@@ -1577,6 +2873,7 @@ mod tests {
             max_retries: 0,
             retry_delay: Duration::from_millis(1),
             confirmation_timeout: Duration::from_millis(1),
+            ..Default::default()
         };
         assert_eq!(config.timeout, Duration::from_millis(1));
     }
@@ -0,0 +1,666 @@
+//! HashiCorp Vault transit signer (Ed25519).
+//!
+//! Signs through Vault's transit engine (`POST /v1/<mount>/sign/<key>`), so the private key
+//! never leaves Vault. The client authenticates with a static token or with AppRole
+//! (`role_id` + `secret_id`) and keeps its token alive: [`spawn_vault_token_renewal`]
+//! renews it once two thirds of the lease have passed, AppRole logs in again when renewal
+//! is no longer possible, and a sign request rejected with `403` re-authenticates once
+//! before giving up.
+//!
+//! The public key of the latest key version is fetched once at startup and every signature
+//! is pinned to that version (`key_version`), so rotating the key in Vault does not change
+//! the signer's address under a running process.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use reqwest::{Client, Method, StatusCode};
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use tokio::sync::{RwLock, watch};
+use tracing::{debug, info, warn};
+
+use crate::domain::{BlockchainError, ConfigError, TransactionSigner};
+
+/// Delay before retrying a failed token renewal
+const RENEWAL_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+/// How the signer authenticates to Vault
+#[derive(Debug, Clone)]
+pub enum VaultAuth {
+    /// Static token (`VAULT_TOKEN`); renewed while Vault allows it
+    Token(SecretString),
+    /// AppRole login (`VAULT_ROLE_ID`, `VAULT_SECRET_ID`); logs in again when needed
+    AppRole {
+        role_id: String,
+        secret_id: SecretString,
+    },
+}
+
+/// Configuration for [`VaultTransitSigner`]
+#[derive(Debug, Clone)]
+pub struct VaultConfig {
+    /// Vault address, e.g. `https://vault.internal:8200`
+    pub address: String,
+    /// Mount path of the transit engine
+    pub mount: String,
+    /// Name of the Ed25519 transit key
+    pub key_name: String,
+    pub auth: VaultAuth,
+    /// Enterprise namespace (`X-Vault-Namespace`)
+    pub namespace: Option<String>,
+    pub timeout: Duration,
+}
+
+impl VaultConfig {
+    #[must_use]
+    pub fn new(address: impl Into<String>, key_name: impl Into<String>, auth: VaultAuth) -> Self {
+        Self {
+            address: address.into().trim_end_matches('/').to_string(),
+            mount: "transit".to_string(),
+            key_name: key_name.into(),
+            auth,
+            namespace: None,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Create config from `VAULT_ADDR`, `VAULT_TRANSIT_KEY`, `VAULT_TRANSIT_MOUNT` (default
+    /// `transit`) and `VAULT_NAMESPACE`. AppRole is used when `VAULT_ROLE_ID` is set
+    /// (with `VAULT_SECRET_ID`), otherwise `VAULT_TOKEN`.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let required =
+            |name: &str| var(name).ok_or_else(|| ConfigError::MissingEnvVar(name.into()));
+        let auth = match var("VAULT_ROLE_ID") {
+            Some(role_id) => VaultAuth::AppRole {
+                role_id,
+                secret_id: SecretString::from(required("VAULT_SECRET_ID")?),
+            },
+            None => VaultAuth::Token(SecretString::from(required("VAULT_TOKEN")?)),
+        };
+        let mut config = Self::new(
+            required("VAULT_ADDR")?,
+            required("VAULT_TRANSIT_KEY")?,
+            auth,
+        );
+        if let Some(mount) = var("VAULT_TRANSIT_MOUNT") {
+            config.mount = mount.trim_matches('/').to_string();
+        }
+        config.namespace = var("VAULT_NAMESPACE");
+        Ok(config)
+    }
+}
+
+/// A Vault token and when it was issued
+struct VaultToken {
+    token: SecretString,
+    renewable: bool,
+    /// Zero for tokens that never expire (e.g. root tokens)
+    lease: Duration,
+    obtained_at: Instant,
+}
+
+impl VaultToken {
+    /// When the token should be renewed (None: it never expires)
+    fn renew_at(&self) -> Option<Instant> {
+        (!self.lease.is_zero()).then(|| self.obtained_at + self.lease * 2 / 3)
+    }
+}
+
+#[derive(Deserialize)]
+struct AuthResponse {
+    auth: AuthInfo,
+}
+
+#[derive(Deserialize)]
+struct AuthInfo {
+    client_token: String,
+    lease_duration: u64,
+    renewable: bool,
+}
+
+#[derive(Deserialize)]
+struct DataResponse<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct TokenLookup {
+    ttl: u64,
+    #[serde(default)]
+    renewable: bool,
+}
+
+#[derive(Deserialize)]
+struct TransitKey {
+    #[serde(rename = "type")]
+    key_type: String,
+    latest_version: u64,
+    keys: std::collections::HashMap<String, TransitKeyVersion>,
+}
+
+#[derive(Deserialize)]
+struct TransitKeyVersion {
+    public_key: String,
+}
+
+#[derive(Deserialize)]
+struct TransitSignature {
+    signature: String,
+}
+
+fn vault_error(message: impl std::fmt::Display) -> BlockchainError {
+    BlockchainError::SubmissionFailed(format!("Vault: {}", message))
+}
+
+/// Vault transit signer. Performs remote Ed25519 signing.
+pub struct VaultTransitSigner {
+    http: Client,
+    config: VaultConfig,
+    token: RwLock<VaultToken>,
+    key_version: u64,
+    public_key_base58: String,
+}
+
+impl VaultTransitSigner {
+    /// Authenticate, then fetch and cache the public key of the transit key's latest
+    /// version. Fails unless the key is Ed25519.
+    pub async fn new(config: VaultConfig) -> Result<Self, BlockchainError> {
+        let http = Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(vault_error)?;
+        info!(
+            address = %config.address,
+            key = %config.key_name,
+            "Initializing Vault transit signer"
+        );
+        let token = login(&http, &config).await?;
+
+        let path = format!("{}/keys/{}", config.mount, config.key_name);
+        let (status, key) = call::<DataResponse<TransitKey>>(
+            &http,
+            &config,
+            Method::GET,
+            &path,
+            &token.token,
+            None,
+        )
+        .await?;
+        let key =
+            key.ok_or_else(|| vault_error(format!("reading key {} returned {}", path, status)))?;
+        if key.data.key_type != "ed25519" {
+            return Err(vault_error(format!(
+                "transit key {} is {}, not ed25519",
+                config.key_name, key.data.key_type
+            )));
+        }
+        let key_version = key.data.latest_version;
+        let public_key = key
+            .data
+            .keys
+            .get(&key_version.to_string())
+            .and_then(|version| STANDARD.decode(&version.public_key).ok())
+            .filter(|bytes| bytes.len() == 32)
+            .ok_or_else(|| vault_error("transit key has no Ed25519 public key"))?;
+        let public_key_base58 = bs58::encode(public_key).into_string();
+        info!(public_key = %public_key_base58, key_version, "Vault signer initialized");
+
+        Ok(Self {
+            http,
+            config,
+            token: RwLock::new(token),
+            key_version,
+            public_key_base58,
+        })
+    }
+
+    /// Renew the token, or log in again with AppRole when it cannot be renewed
+    pub async fn renew_token(&self) -> Result<(), BlockchainError> {
+        let mut token = self.token.write().await;
+        let result = if token.renewable {
+            renew(&self.http, &self.config, &token.token).await
+        } else {
+            Err(vault_error("token is not renewable"))
+        };
+        let result = match (result, &self.config.auth) {
+            (Ok(renewed), _) => Ok(renewed),
+            (Err(e), VaultAuth::AppRole { .. }) => {
+                debug!(error = %e, "Vault token renewal failed; logging in again");
+                login(&self.http, &self.config).await
+            }
+            (Err(e), VaultAuth::Token(_)) => Err(e),
+        };
+        let outcome = if result.is_ok() { "success" } else { "failure" };
+        metrics::counter!("vault_token_renewals_total", "outcome" => outcome).increment(1);
+        *token = result?;
+        Ok(())
+    }
+
+    /// When the current token should next be renewed (None: it never expires)
+    async fn renew_at(&self) -> Option<Instant> {
+        self.token.read().await.renew_at()
+    }
+
+    /// Keep the token alive until shutdown
+    pub async fn run_token_renewal(self: Arc<Self>, mut shutdown_rx: watch::Receiver<bool>) {
+        let mut retry_at = None;
+        loop {
+            let Some(renew_at) = retry_at.or(self.renew_at().await) else {
+                info!("Vault token does not expire; renewal not needed");
+                let _ = shutdown_rx.changed().await;
+                return;
+            };
+            tokio::select! {
+                _ = tokio::time::sleep_until(renew_at.into()) => {
+                    retry_at = match self.renew_token().await {
+                        Ok(()) => {
+                            debug!("Vault token renewed");
+                            None
+                        }
+                        Err(e) => {
+                            warn!(error = %e, "Vault token renewal failed");
+                            Some(Instant::now() + RENEWAL_RETRY_DELAY)
+                        }
+                    };
+                }
+                _ = shutdown_rx.changed() => {
+                    info!("Vault token renewal shutting down");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Sign with the current token; `Ok(None)` when Vault rejects the token
+    async fn try_sign(&self, message: &[u8]) -> Result<Option<String>, BlockchainError> {
+        let path = format!("{}/sign/{}", self.config.mount, self.config.key_name);
+        let body = serde_json::json!({
+            "input": STANDARD.encode(message),
+            "key_version": self.key_version,
+        });
+        let token = self.token.read().await;
+        let (status, response) = call::<DataResponse<TransitSignature>>(
+            &self.http,
+            &self.config,
+            Method::POST,
+            &path,
+            &token.token,
+            Some(body),
+        )
+        .await?;
+        match response {
+            Some(response) => Ok(Some(response.data.signature)),
+            None if status == StatusCode::FORBIDDEN => Ok(None),
+            None => Err(vault_error(format!("sign returned {}", status))),
+        }
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for VaultTransitSigner {
+    async fn sign_message(&self, message: &[u8]) -> Result<String, BlockchainError> {
+        let signature = match self.try_sign(message).await? {
+            Some(signature) => signature,
+            None => {
+                warn!("Vault rejected the token; re-authenticating");
+                self.renew_token().await?;
+                self.try_sign(message)
+                    .await?
+                    .ok_or_else(|| vault_error("permission denied signing with the transit key"))?
+            }
+        };
+        // `vault:v<version>:<base64>`
+        let encoded = signature
+            .rsplit(':')
+            .next()
+            .ok_or_else(|| vault_error("malformed signature"))?;
+        let bytes = STANDARD
+            .decode(encoded)
+            .map_err(|e| vault_error(format!("malformed signature: {}", e)))?;
+        Ok(bs58::encode(bytes).into_string())
+    }
+
+    fn public_key(&self) -> String {
+        self.public_key_base58.clone()
+    }
+}
+
+/// Spawn token renewal as a tokio task
+pub fn spawn_vault_token_renewal(
+    signer: Arc<VaultTransitSigner>,
+) -> (tokio::task::JoinHandle<()>, watch::Sender<bool>) {
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let handle = tokio::spawn(signer.run_token_renewal(shutdown_rx));
+    (handle, shutdown_tx)
+}
+
+/// Call the Vault API. Returns the status with the parsed body of a 2xx response, or with
+/// None for any other status.
+async fn call<T: DeserializeOwned>(
+    http: &Client,
+    config: &VaultConfig,
+    method: Method,
+    path: &str,
+    token: &SecretString,
+    body: Option<serde_json::Value>,
+) -> Result<(StatusCode, Option<T>), BlockchainError> {
+    let url = format!("{}/v1/{}", config.address, path);
+    let mut request = http.request(method, &url);
+    if !token.expose_secret().is_empty() {
+        request = request.header("X-Vault-Token", token.expose_secret());
+    }
+    if let Some(namespace) = &config.namespace {
+        request = request.header("X-Vault-Namespace", namespace);
+    }
+    if let Some(body) = body {
+        request = request.json(&body);
+    }
+    let response = request.send().await.map_err(vault_error)?;
+    let status = response.status();
+    if !status.is_success() {
+        let detail = response.text().await.unwrap_or_default();
+        debug!(%status, path, detail = %detail, "Vault request failed");
+        return Ok((status, None));
+    }
+    let parsed = response.json::<T>().await.map_err(vault_error)?;
+    Ok((status, Some(parsed)))
+}
+
+fn token_from_auth(auth: AuthInfo) -> VaultToken {
+    VaultToken {
+        token: SecretString::from(auth.client_token),
+        renewable: auth.renewable,
+        lease: Duration::from_secs(auth.lease_duration),
+        obtained_at: Instant::now(),
+    }
+}
+
+/// Obtain a token: AppRole login, or a lookup of the static token's lease
+async fn login(http: &Client, config: &VaultConfig) -> Result<VaultToken, BlockchainError> {
+    match &config.auth {
+        VaultAuth::Token(token) => {
+            let (status, lookup) = call::<DataResponse<TokenLookup>>(
+                http,
+                config,
+                Method::GET,
+                "auth/token/lookup-self",
+                token,
+                None,
+            )
+            .await?;
+            let lookup =
+                lookup.ok_or_else(|| vault_error(format!("token lookup returned {}", status)))?;
+            Ok(VaultToken {
+                token: token.clone(),
+                renewable: lookup.data.renewable,
+                lease: Duration::from_secs(lookup.data.ttl),
+                obtained_at: Instant::now(),
+            })
+        }
+        VaultAuth::AppRole { role_id, secret_id } => {
+            let body = serde_json::json!({
+                "role_id": role_id,
+                "secret_id": secret_id.expose_secret(),
+            });
+            let (status, response) = call::<AuthResponse>(
+                http,
+                config,
+                Method::POST,
+                "auth/approle/login",
+                &SecretString::from(String::new()),
+                Some(body),
+            )
+            .await?;
+            let response = response
+                .ok_or_else(|| vault_error(format!("AppRole login returned {}", status)))?;
+            info!("Logged in to Vault with AppRole");
+            Ok(token_from_auth(response.auth))
+        }
+    }
+}
+
+async fn renew(
+    http: &Client,
+    config: &VaultConfig,
+    token: &SecretString,
+) -> Result<VaultToken, BlockchainError> {
+    let (status, response) = call::<AuthResponse>(
+        http,
+        config,
+        Method::POST,
+        "auth/token/renew-self",
+        token,
+        Some(serde_json::json!({})),
+    )
+    .await?;
+    let response =
+        response.ok_or_else(|| vault_error(format!("token renewal returned {}", status)))?;
+    Ok(token_from_auth(response.auth))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::{Path, State};
+    use axum::http::HeaderMap;
+    use axum::routing::{get, post};
+    use axum::{Json, Router};
+    use ed25519_dalek::{Signature, Signer, SigningKey, Verifier};
+    use std::sync::Mutex;
+
+    /// In-memory stand-in for Vault's AppRole, token and transit endpoints
+    #[derive(Clone)]
+    struct MockVault {
+        key: SigningKey,
+        key_type: &'static str,
+        /// Tokens Vault currently accepts
+        valid_tokens: Arc<Mutex<Vec<String>>>,
+        logins: Arc<Mutex<u32>>,
+        renewals: Arc<Mutex<u32>>,
+    }
+
+    fn token_of(headers: &HeaderMap) -> String {
+        headers
+            .get("x-vault-token")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    impl MockVault {
+        fn accepts(&self, headers: &HeaderMap) -> bool {
+            self.valid_tokens
+                .lock()
+                .unwrap()
+                .contains(&token_of(headers))
+        }
+    }
+
+    async fn approle_login(
+        State(vault): State<MockVault>,
+        Json(body): Json<serde_json::Value>,
+    ) -> Result<Json<serde_json::Value>, StatusCode> {
+        if body["role_id"] != "role" || body["secret_id"] != "secret" {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        let mut logins = vault.logins.lock().unwrap();
+        *logins += 1;
+        let token = format!("tok-{}", logins);
+        vault.valid_tokens.lock().unwrap().push(token.clone());
+        Ok(Json(serde_json::json!({
+            "auth": { "client_token": token, "lease_duration": 3600, "renewable": true }
+        })))
+    }
+
+    async fn renew_self(
+        State(vault): State<MockVault>,
+        headers: HeaderMap,
+    ) -> Result<Json<serde_json::Value>, StatusCode> {
+        if !vault.accepts(&headers) {
+            return Err(StatusCode::FORBIDDEN);
+        }
+        *vault.renewals.lock().unwrap() += 1;
+        Ok(Json(serde_json::json!({
+            "auth": { "client_token": token_of(&headers), "lease_duration": 3600, "renewable": true }
+        })))
+    }
+
+    async fn lookup_self(
+        State(vault): State<MockVault>,
+        headers: HeaderMap,
+    ) -> Result<Json<serde_json::Value>, StatusCode> {
+        if !vault.accepts(&headers) {
+            return Err(StatusCode::FORBIDDEN);
+        }
+        Ok(Json(
+            serde_json::json!({ "data": { "ttl": 0, "renewable": false } }),
+        ))
+    }
+
+    async fn read_key(
+        State(vault): State<MockVault>,
+        headers: HeaderMap,
+    ) -> Result<Json<serde_json::Value>, StatusCode> {
+        if !vault.accepts(&headers) {
+            return Err(StatusCode::FORBIDDEN);
+        }
+        let public_key = STANDARD.encode(vault.key.verifying_key().as_bytes());
+        Ok(Json(serde_json::json!({
+            "data": {
+                "type": vault.key_type,
+                "latest_version": 2,
+                "keys": { "1": { "public_key": "old" }, "2": { "public_key": public_key } }
+            }
+        })))
+    }
+
+    async fn sign(
+        State(vault): State<MockVault>,
+        Path(_key): Path<String>,
+        headers: HeaderMap,
+        Json(body): Json<serde_json::Value>,
+    ) -> Result<Json<serde_json::Value>, StatusCode> {
+        if !vault.accepts(&headers) {
+            return Err(StatusCode::FORBIDDEN);
+        }
+        assert_eq!(body["key_version"], 2);
+        let input = STANDARD.decode(body["input"].as_str().unwrap()).unwrap();
+        let signature = STANDARD.encode(vault.key.sign(&input).to_bytes());
+        Ok(Json(serde_json::json!({
+            "data": { "signature": format!("vault:v2:{}", signature) }
+        })))
+    }
+
+    async fn spawn_vault(key_type: &'static str) -> (String, MockVault) {
+        let vault = MockVault {
+            key: SigningKey::from_bytes(&[9; 32]),
+            key_type,
+            valid_tokens: Arc::new(Mutex::new(vec!["static-token".to_string()])),
+            logins: Arc::new(Mutex::new(0)),
+            renewals: Arc::new(Mutex::new(0)),
+        };
+        let app = Router::new()
+            .route("/v1/auth/approle/login", post(approle_login))
+            .route("/v1/auth/token/renew-self", post(renew_self))
+            .route("/v1/auth/token/lookup-self", get(lookup_self))
+            .route("/v1/transit/keys/{key}", get(read_key))
+            .route("/v1/transit/sign/{key}", post(sign))
+            .with_state(vault.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{}", addr), vault)
+    }
+
+    fn approle() -> VaultAuth {
+        VaultAuth::AppRole {
+            role_id: "role".to_string(),
+            secret_id: SecretString::from("secret"),
+        }
+    }
+
+    fn verify(vault: &MockVault, message: &[u8], signature: &str) {
+        let bytes: [u8; 64] = bs58::decode(signature)
+            .into_vec()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        vault
+            .key
+            .verifying_key()
+            .verify(message, &Signature::from_bytes(&bytes))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_approle_signer_caches_key_and_renews_token() {
+        let (address, vault) = spawn_vault("ed25519").await;
+        let signer = VaultTransitSigner::new(VaultConfig::new(&address, "issuer", approle()))
+            .await
+            .unwrap();
+        assert_eq!(
+            signer.public_key(),
+            bs58::encode(vault.key.verifying_key().as_bytes()).into_string()
+        );
+        assert!(signer.renew_at().await.is_some());
+
+        let signature = signer.sign_message(b"message").await.unwrap();
+        verify(&vault, b"message", &signature);
+
+        signer.renew_token().await.unwrap();
+        assert_eq!(*vault.renewals.lock().unwrap(), 1);
+        assert_eq!(*vault.logins.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_token_logs_in_again() {
+        let (address, vault) = spawn_vault("ed25519").await;
+        let signer = VaultTransitSigner::new(VaultConfig::new(&address, "issuer", approle()))
+            .await
+            .unwrap();
+        // The token is revoked behind the signer's back
+        vault.valid_tokens.lock().unwrap().clear();
+
+        let signature = signer.sign_message(b"after revoke").await.unwrap();
+        verify(&vault, b"after revoke", &signature);
+        assert_eq!(*vault.logins.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_static_token_and_key_type() {
+        let (address, vault) = spawn_vault("ed25519").await;
+        let auth = VaultAuth::Token(SecretString::from("static-token"));
+        let signer = VaultTransitSigner::new(VaultConfig::new(&address, "issuer", auth.clone()))
+            .await
+            .unwrap();
+        // A non-expiring static token needs no renewal
+        assert!(signer.renew_at().await.is_none());
+        let signature = signer.sign_message(b"message").await.unwrap();
+        verify(&vault, b"message", &signature);
+
+        let bad = VaultAuth::Token(SecretString::from("wrong"));
+        assert!(
+            VaultTransitSigner::new(VaultConfig::new(&address, "issuer", bad))
+                .await
+                .is_err()
+        );
+
+        let (address, _) = spawn_vault("ecdsa-p256").await;
+        assert!(
+            VaultTransitSigner::new(VaultConfig::new(&address, "issuer", auth))
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,942 @@
+//! Dual-write decorator for moving to a new database while the service keeps running.
+//!
+//! [`MigratingDatabaseClient`] sends every write to the primary (the backend being
+//! migrated away from) and, once it succeeds, repeats it on the secondary. IDs the
+//! backends generate (items, outbox entries, dead letters, API keys, jobs) are captured
+//! on the primary and reused on the secondary, so later writes by ID reach the same rows
+//! in both. Reads are served by the primary; a sample of them is repeated on the
+//! secondary and compared, ignoring timestamps, which each backend sets itself.
+//!
+//! The primary stays authoritative: a failed secondary write is logged and counted but
+//! never reported to the caller. Rows that existed before dual-writing started must be
+//! copied separately; until they are, writes touching them fail on the secondary and
+//! show up in `database_migration_secondary_failures_total`. Event log positions and
+//! webhook delivery IDs are assigned by each backend and can differ between the two.
+
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::stream::BoxStream;
+use serde::Serialize;
+use serde_json::Value;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::{DatabaseClient, DatabaseInitError};
+use crate::domain::{
+    ApiKey, ApiKeyError, ApiKeyQuota, ApiKeyScope, ApiKeyStore, AuditEntry, AuditFilter,
+    AuditLogger, BlockchainStatus, CreateItemRequest, EventLog, ExportBookmark, FailedSubmission,
+    HealthCheckError, IdGenerator, Item, ItemCount, ItemError, ItemListFilter, ItemPosition,
+    ItemRepository, ItemSearchHit, ItemStatusEvent, Job, JobError, JobStatus, JobStore, KeyUsage,
+    LeaderElection, NotificationError, OutboxRepository, OutboxStatus, PaginatedResponse,
+    QueueDepth, QuotaUsage, ReencryptedBatch, RequestJournal, RequestJournalEntry,
+    RequestJournalError, SchemaStatus, SolanaOutboxEntry, SolanaOutboxPayload, SpendLedger,
+    SubmissionAttempt, SubmissionRepository, TagCount, TimeRange, UnitOfWork, UsageLedger,
+    WebhookDelivery, WebhookDeliveryLog,
+};
+use crate::infra::UuidV7IdGenerator;
+
+/// Share of reads repeated on the secondary when `with_compare_rate` is not called
+pub const DEFAULT_MIGRATION_COMPARE_RATE: f64 = 0.01;
+
+tokio::task_local! {
+    static GENERATED_IDS: GeneratedIds;
+}
+
+/// IDs generated during one write, by row kind, in generation order
+#[derive(Clone, Default)]
+struct GeneratedIds {
+    ids: Arc<Mutex<Vec<(&'static str, String)>>>,
+    /// Hand out the captured IDs instead of recording new ones
+    replay: bool,
+}
+
+impl GeneratedIds {
+    fn next(&self, kind: &'static str, generator: &dyn IdGenerator) -> String {
+        let mut ids = self.ids.lock().unwrap();
+        if self.replay {
+            return match ids.iter().position(|(k, _)| *k == kind) {
+                Some(index) => ids.remove(index).1,
+                None => generator.generate(kind),
+            };
+        }
+        let id = generator.generate(kind);
+        ids.push((kind, id.clone()));
+        id
+    }
+
+    /// The same IDs, for repeating the write on another backend
+    fn replay(&self) -> Self {
+        Self {
+            ids: Arc::new(Mutex::new(self.ids.lock().unwrap().clone())),
+            replay: true,
+        }
+    }
+}
+
+/// New ID from `generator` for a row of `kind`. While a write is repeated on the
+/// secondary it is the ID the primary generated for the same kind.
+pub(crate) fn generate_id(kind: &'static str, generator: &dyn IdGenerator) -> String {
+    GENERATED_IDS
+        .try_with(|ids| ids.next(kind, generator))
+        .unwrap_or_else(|_| generator.generate(kind))
+}
+
+/// New outbox entry ID. Always a UUIDv7, since the outbox ID columns are UUIDs
+/// whatever the configured [`IdFormat`](crate::infra::IdFormat).
+pub(crate) fn generate_outbox_id() -> Uuid {
+    let id = generate_id("outbox", &UuidV7IdGenerator);
+    Uuid::parse_str(&id).unwrap_or_else(|_| Uuid::now_v7())
+}
+
+/// Run a write on the primary and, if it succeeded, on the secondary with the same
+/// generated IDs. Evaluates to the primary's result.
+macro_rules! dual_write {
+    ($self:ident . $method:ident ( $($arg:expr),* )) => {{
+        let ids = GeneratedIds::default();
+        let result = GENERATED_IDS
+            .scope(ids.clone(), $self.primary.$method($($arg),*))
+            .await;
+        if result.is_ok() {
+            let mirrored = GENERATED_IDS
+                .scope(ids.replay(), $self.secondary.$method($($arg),*))
+                .await;
+            secondary_failed(stringify!($method), mirrored.err());
+        }
+        result
+    }};
+}
+
+/// Run a read on the primary; for a sample of successful reads, run it on the secondary
+/// as well and compare. Evaluates to the primary's result.
+macro_rules! sampled_read {
+    ($self:ident . $method:ident ( $($arg:expr),* )) => {{
+        let result = $self.primary.$method($($arg),*).await;
+        if let Ok(expected) = &result
+            && $self.sampled()
+        {
+            let actual = $self.secondary.$method($($arg),*).await;
+            compare(stringify!($method), expected, actual);
+        }
+        result
+    }};
+}
+
+fn secondary_failed(operation: &'static str, error: Option<impl Display>) {
+    if let Some(e) = error {
+        metrics::counter!("database_migration_secondary_failures_total", "operation" => operation)
+            .increment(1);
+        warn!(operation, error = %e, "Write to the migration target failed");
+    }
+}
+
+fn compare<T: Serialize>(operation: &'static str, expected: &T, actual: Result<T, impl Display>) {
+    let outcome = match actual {
+        Ok(actual) if fingerprint(&actual) == fingerprint(expected) => "match",
+        Ok(_) => {
+            warn!(
+                operation,
+                "Migration target returned a different result than the primary"
+            );
+            "mismatch"
+        }
+        Err(e) => {
+            warn!(operation, error = %e, "Read from the migration target failed");
+            "error"
+        }
+    };
+    metrics::counter!(
+        "database_migration_comparisons_total",
+        "operation" => operation,
+        "outcome" => outcome
+    )
+    .increment(1);
+}
+
+/// `value` as JSON with every `*_at` timestamp reduced to whether it is set
+fn fingerprint(value: &impl Serialize) -> Value {
+    fn strip(value: &mut Value) {
+        match value {
+            Value::Object(fields) => {
+                for (name, field) in fields.iter_mut() {
+                    if name.ends_with("_at") {
+                        *field = Value::Bool(!field.is_null());
+                    } else {
+                        strip(field);
+                    }
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(strip),
+            _ => {}
+        }
+    }
+    let mut value = serde_json::to_value(value).unwrap_or(Value::Null);
+    strip(&mut value);
+    value
+}
+
+/// [`DatabaseClient`] writing to two backends and reading from the first
+pub struct MigratingDatabaseClient {
+    primary: Arc<dyn DatabaseClient>,
+    secondary: Arc<dyn DatabaseClient>,
+    compare_rate: f64,
+}
+
+impl MigratingDatabaseClient {
+    /// Dual-write to `primary` (authoritative) and `secondary` (migration target)
+    pub fn new(primary: Arc<dyn DatabaseClient>, secondary: Arc<dyn DatabaseClient>) -> Self {
+        Self {
+            primary,
+            secondary,
+            compare_rate: DEFAULT_MIGRATION_COMPARE_RATE,
+        }
+    }
+
+    /// Share of reads (0.0 to 1.0) also run on the secondary and compared. Sampled
+    /// reads wait for both backends.
+    #[must_use]
+    pub fn with_compare_rate(mut self, rate: f64) -> Self {
+        self.compare_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    fn sampled(&self) -> bool {
+        self.compare_rate > 0.0 && rand::random::<f64>() < self.compare_rate
+    }
+}
+
+#[async_trait]
+impl ItemRepository for MigratingDatabaseClient {
+    async fn health_check(&self) -> Result<(), HealthCheckError> {
+        ItemRepository::health_check(self.primary.as_ref()).await
+    }
+
+    async fn get_item(&self, id: &str) -> Result<Option<Item>, ItemError> {
+        sampled_read!(self.get_item(id))
+    }
+
+    async fn create_item(&self, data: &CreateItemRequest) -> Result<Item, ItemError> {
+        dual_write!(self.create_item(data))
+    }
+
+    async fn create_item_without_outbox(
+        &self,
+        data: &CreateItemRequest,
+    ) -> Result<Item, ItemError> {
+        dual_write!(self.create_item_without_outbox(data))
+    }
+
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork + '_>, ItemError> {
+        let primary = self.primary.begin().await?;
+        let secondary = match self.secondary.begin().await {
+            Ok(unit) => Some(unit),
+            Err(e) => {
+                secondary_failed("begin", Some(e));
+                None
+            }
+        };
+        Ok(Box::new(MigratingUnitOfWork { primary, secondary }))
+    }
+
+    async fn list_items(
+        &self,
+        limit: i64,
+        cursor: Option<&str>,
+        filter: &ItemListFilter,
+    ) -> Result<PaginatedResponse<Item>, ItemError> {
+        sampled_read!(self.list_items(limit, cursor, filter))
+    }
+
+    async fn list_items_at_offset(
+        &self,
+        offset: i64,
+        limit: i64,
+        filter: &ItemListFilter,
+    ) -> Result<Vec<Item>, ItemError> {
+        sampled_read!(self.list_items_at_offset(offset, limit, filter))
+    }
+
+    /// Not compared: the stores' estimates differ even when their rows match
+    async fn count_items(
+        &self,
+        filter: &ItemListFilter,
+        estimate_above: Option<u64>,
+    ) -> Result<ItemCount, ItemError> {
+        self.primary.count_items(filter, estimate_above).await
+    }
+
+    async fn list_tags(&self) -> Result<Vec<TagCount>, ItemError> {
+        sampled_read!(self.list_tags())
+    }
+
+    fn stream_items(&self) -> BoxStream<'static, Result<Item, ItemError>> {
+        self.primary.stream_items()
+    }
+
+    fn stream_item_changes(
+        &self,
+        after: Option<ItemPosition>,
+    ) -> BoxStream<'static, Result<Item, ItemError>> {
+        self.primary.stream_item_changes(after)
+    }
+
+    async fn export_bookmark(&self, name: &str) -> Result<ExportBookmark, ItemError> {
+        // Registers the bookmark on first use, so it is a write
+        dual_write!(self.export_bookmark(name))
+    }
+
+    async fn acknowledge_export_bookmark(
+        &self,
+        name: &str,
+        position: &ItemPosition,
+    ) -> Result<ExportBookmark, ItemError> {
+        dual_write!(self.acknowledge_export_bookmark(name, position))
+    }
+
+    async fn find_item_by_hash(&self, hash: &str) -> Result<Option<Item>, ItemError> {
+        sampled_read!(self.find_item_by_hash(hash))
+    }
+
+    async fn search_items(&self, query: &str, limit: i64) -> Result<Vec<ItemSearchHit>, ItemError> {
+        // Ranking differs between backends, so results are not compared
+        self.primary.search_items(query, limit).await
+    }
+
+    async fn update_item(
+        &self,
+        id: &str,
+        data: &CreateItemRequest,
+        expected_version: i64,
+    ) -> Result<Item, ItemError> {
+        dual_write!(self.update_item(id, data, expected_version))
+    }
+
+    async fn soft_delete_item(&self, id: &str) -> Result<Option<Item>, ItemError> {
+        dual_write!(self.soft_delete_item(id))
+    }
+
+    async fn purge_deleted_items(&self, deleted_before: DateTime<Utc>) -> Result<u64, ItemError> {
+        dual_write!(self.purge_deleted_items(deleted_before))
+    }
+
+    async fn reencrypt_content(
+        &self,
+        after: Option<&str>,
+        limit: i64,
+    ) -> Result<ReencryptedBatch, ItemError> {
+        dual_write!(self.reencrypt_content(after, limit))
+    }
+
+    async fn delete_item(&self, id: &str) -> Result<bool, ItemError> {
+        dual_write!(self.delete_item(id))
+    }
+}
+
+/// Unit of work staged on both backends. A failed secondary step drops the secondary
+/// unit (rolling it back) and the rest runs on the primary only.
+struct MigratingUnitOfWork<'a> {
+    primary: Box<dyn UnitOfWork + 'a>,
+    secondary: Option<Box<dyn UnitOfWork + 'a>>,
+}
+
+#[async_trait]
+impl UnitOfWork for MigratingUnitOfWork<'_> {
+    async fn insert_item(&mut self, data: &CreateItemRequest) -> Result<Item, ItemError> {
+        let ids = GeneratedIds::default();
+        let item = GENERATED_IDS
+            .scope(ids.clone(), self.primary.insert_item(data))
+            .await?;
+        if let Some(secondary) = &mut self.secondary
+            && let Err(e) = GENERATED_IDS
+                .scope(ids.replay(), secondary.insert_item(data))
+                .await
+        {
+            secondary_failed("insert_item", Some(e));
+            self.secondary = None;
+        }
+        Ok(item)
+    }
+
+    async fn enqueue_solana_outbox(
+        &mut self,
+        item_id: &str,
+        payload: &SolanaOutboxPayload,
+    ) -> Result<Item, ItemError> {
+        let ids = GeneratedIds::default();
+        let item = GENERATED_IDS
+            .scope(
+                ids.clone(),
+                self.primary.enqueue_solana_outbox(item_id, payload),
+            )
+            .await?;
+        if let Some(secondary) = &mut self.secondary
+            && let Err(e) = GENERATED_IDS
+                .scope(
+                    ids.replay(),
+                    secondary.enqueue_solana_outbox(item_id, payload),
+                )
+                .await
+        {
+            secondary_failed("enqueue_solana_outbox", Some(e));
+            self.secondary = None;
+        }
+        Ok(item)
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), ItemError> {
+        let Self { primary, secondary } = *self;
+        primary.commit().await?;
+        if let Some(secondary) = secondary {
+            secondary_failed("commit", secondary.commit().await.err());
+        }
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), ItemError> {
+        let Self { primary, secondary } = *self;
+        if let Some(secondary) = secondary {
+            secondary_failed("rollback", secondary.rollback().await.err());
+        }
+        primary.rollback().await
+    }
+}
+
+#[async_trait]
+impl SubmissionRepository for MigratingDatabaseClient {
+    async fn update_blockchain_status(
+        &self,
+        id: &str,
+        status: BlockchainStatus,
+        signature: Option<&str>,
+        error: Option<&str>,
+        next_retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), ItemError> {
+        dual_write!(self.update_blockchain_status(id, status, signature, error, next_retry_at))
+    }
+
+    async fn enqueue_solana_outbox_for_item(
+        &self,
+        item_id: &str,
+        payload: &SolanaOutboxPayload,
+    ) -> Result<Item, ItemError> {
+        dual_write!(self.enqueue_solana_outbox_for_item(item_id, payload))
+    }
+
+    async fn get_pending_blockchain_items(
+        &self,
+        limit: i64,
+        claim_ttl: Duration,
+    ) -> Result<Vec<Item>, ItemError> {
+        self.primary
+            .get_pending_blockchain_items(limit, claim_ttl)
+            .await
+    }
+
+    async fn increment_retry_count(&self, id: &str) -> Result<i32, ItemError> {
+        dual_write!(self.increment_retry_count(id))
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for MigratingDatabaseClient {
+    async fn health_check(&self) -> Result<(), HealthCheckError> {
+        OutboxRepository::health_check(self.primary.as_ref()).await
+    }
+
+    async fn claim_pending_solana_outbox(
+        &self,
+        limit: i64,
+        claim_ttl: Duration,
+    ) -> Result<Vec<SolanaOutboxEntry>, ItemError> {
+        // Not repeated: the secondary could claim different entries and leave them
+        // `processing`. Completing or failing an entry updates it by ID either way.
+        self.primary
+            .claim_pending_solana_outbox(limit, claim_ttl)
+            .await
+    }
+
+    async fn complete_solana_outbox(
+        &self,
+        outbox_id: &str,
+        item_id: &str,
+        signature: &str,
+    ) -> Result<(), ItemError> {
+        dual_write!(self.complete_solana_outbox(outbox_id, item_id, signature))
+    }
+
+    async fn fail_solana_outbox(
+        &self,
+        outbox_id: &str,
+        item_id: &str,
+        retry_count: i32,
+        outbox_status: OutboxStatus,
+        item_status: BlockchainStatus,
+        error: &str,
+        next_retry_at: Option<DateTime<Utc>>,
+        attempt_blockhash: Option<Option<&str>>,
+    ) -> Result<(), ItemError> {
+        dual_write!(self.fail_solana_outbox(
+            outbox_id,
+            item_id,
+            retry_count,
+            outbox_status,
+            item_status,
+            error,
+            next_retry_at,
+            attempt_blockhash
+        ))
+    }
+
+    async fn save_attempt_blockhash(
+        &self,
+        outbox_id: &str,
+        blockhash: Option<&str>,
+    ) -> Result<(), ItemError> {
+        dual_write!(self.save_attempt_blockhash(outbox_id, blockhash))
+    }
+
+    async fn dead_letter_solana_outbox(
+        &self,
+        outbox_id: &str,
+        item_id: &str,
+        retry_count: i32,
+        error: &str,
+        attempt_blockhash: Option<Option<&str>>,
+    ) -> Result<FailedSubmission, ItemError> {
+        dual_write!(self.dead_letter_solana_outbox(
+            outbox_id,
+            item_id,
+            retry_count,
+            error,
+            attempt_blockhash
+        ))
+    }
+
+    async fn list_failed_submissions(
+        &self,
+        limit: i64,
+        include_requeued: bool,
+    ) -> Result<Vec<FailedSubmission>, ItemError> {
+        sampled_read!(self.list_failed_submissions(limit, include_requeued))
+    }
+
+    async fn requeue_failed_submission(&self, id: &str) -> Result<Item, ItemError> {
+        dual_write!(self.requeue_failed_submission(id))
+    }
+
+    async fn queue_depth(&self) -> Result<QueueDepth, ItemError> {
+        self.primary.queue_depth().await
+    }
+
+    async fn record_submission_attempt(
+        &self,
+        item_id: &str,
+        attempt: &SubmissionAttempt,
+    ) -> Result<(), ItemError> {
+        dual_write!(self.record_submission_attempt(item_id, attempt))
+    }
+
+    async fn list_submission_attempts(
+        &self,
+        item_id: &str,
+    ) -> Result<Vec<SubmissionAttempt>, ItemError> {
+        sampled_read!(self.list_submission_attempts(item_id))
+    }
+}
+
+#[async_trait]
+impl ApiKeyStore for MigratingDatabaseClient {
+    async fn create_api_key(
+        &self,
+        name: &str,
+        key_hash: &str,
+        scopes: &[ApiKeyScope],
+        tenant_id: &str,
+        quota: ApiKeyQuota,
+    ) -> Result<ApiKey, ApiKeyError> {
+        dual_write!(self.create_api_key(name, key_hash, scopes, tenant_id, quota))
+    }
+
+    async fn find_api_key_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>, ApiKeyError> {
+        sampled_read!(self.find_api_key_by_hash(key_hash))
+    }
+
+    async fn list_api_keys(&self) -> Result<Vec<ApiKey>, ApiKeyError> {
+        sampled_read!(self.list_api_keys())
+    }
+
+    async fn revoke_api_key(&self, id: &str) -> Result<ApiKey, ApiKeyError> {
+        dual_write!(self.revoke_api_key(id))
+    }
+}
+
+#[async_trait]
+impl RequestJournal for MigratingDatabaseClient {
+    async fn begin_request(
+        &self,
+        key: &str,
+        request_hash: &str,
+        reclaim_before: DateTime<Utc>,
+    ) -> Result<Option<RequestJournalEntry>, RequestJournalError> {
+        dual_write!(self.begin_request(key, request_hash, reclaim_before))
+    }
+
+    async fn complete_request(
+        &self,
+        key: &str,
+        response_status: u16,
+        response_body: &str,
+    ) -> Result<(), RequestJournalError> {
+        dual_write!(self.complete_request(key, response_status, response_body))
+    }
+
+    async fn abandon_request(&self, key: &str) -> Result<(), RequestJournalError> {
+        dual_write!(self.abandon_request(key))
+    }
+
+    async fn get_request(
+        &self,
+        key: &str,
+    ) -> Result<Option<RequestJournalEntry>, RequestJournalError> {
+        self.primary.get_request(key).await
+    }
+}
+
+#[async_trait]
+impl EventLog for MigratingDatabaseClient {
+    async fn events_after(
+        &self,
+        position: i64,
+        limit: i64,
+    ) -> Result<Vec<ItemStatusEvent>, NotificationError> {
+        self.primary.events_after(position, limit).await
+    }
+
+    async fn subscription_cursor(&self, subscription: &str) -> Result<i64, NotificationError> {
+        self.primary.subscription_cursor(subscription).await
+    }
+
+    async fn save_subscription_cursor(
+        &self,
+        subscription: &str,
+        position: i64,
+    ) -> Result<(), NotificationError> {
+        dual_write!(self.save_subscription_cursor(subscription, position))
+    }
+
+    async fn list_events(
+        &self,
+        limit: i64,
+        before: Option<i64>,
+        range: &TimeRange,
+    ) -> Result<PaginatedResponse<ItemStatusEvent>, NotificationError> {
+        self.primary.list_events(limit, before, range).await
+    }
+
+    async fn item_events(&self, item_id: &str) -> Result<Vec<ItemStatusEvent>, NotificationError> {
+        self.primary.item_events(item_id).await
+    }
+}
+
+#[async_trait]
+impl WebhookDeliveryLog for MigratingDatabaseClient {
+    async fn record_webhook_delivery(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> Result<(), NotificationError> {
+        dual_write!(self.record_webhook_delivery(delivery))
+    }
+
+    async fn list_webhook_deliveries(
+        &self,
+        limit: i64,
+        before: Option<i64>,
+        range: &TimeRange,
+    ) -> Result<PaginatedResponse<WebhookDelivery>, NotificationError> {
+        self.primary
+            .list_webhook_deliveries(limit, before, range)
+            .await
+    }
+
+    async fn item_deliveries(
+        &self,
+        item_id: &str,
+    ) -> Result<Vec<WebhookDelivery>, NotificationError> {
+        self.primary.item_deliveries(item_id).await
+    }
+}
+
+#[async_trait]
+impl AuditLogger for MigratingDatabaseClient {
+    async fn record_audit_entry(&self, entry: &AuditEntry) -> Result<(), NotificationError> {
+        dual_write!(self.record_audit_entry(entry))
+    }
+
+    async fn list_audit_entries(
+        &self,
+        limit: i64,
+        before: Option<i64>,
+        filter: &AuditFilter,
+    ) -> Result<PaginatedResponse<AuditEntry>, NotificationError> {
+        self.primary.list_audit_entries(limit, before, filter).await
+    }
+}
+
+#[async_trait]
+impl UsageLedger for MigratingDatabaseClient {
+    async fn record_request(
+        &self,
+        key_id: &str,
+        day: NaiveDate,
+    ) -> Result<QuotaUsage, ApiKeyError> {
+        dual_write!(self.record_request(key_id, day))
+    }
+
+    async fn usage_on(&self, day: NaiveDate) -> Result<Vec<KeyUsage>, ApiKeyError> {
+        self.primary.usage_on(day).await
+    }
+}
+
+#[async_trait]
+impl SpendLedger for MigratingDatabaseClient {
+    async fn spent_on(&self, signer: &str, day: NaiveDate) -> Result<u64, ItemError> {
+        sampled_read!(self.spent_on(signer, day))
+    }
+
+    async fn record_spend(
+        &self,
+        signer: &str,
+        day: NaiveDate,
+        amount: u64,
+    ) -> Result<u64, ItemError> {
+        dual_write!(self.record_spend(signer, day, amount))
+    }
+}
+
+/// Leases coordinate the instances running now, which all share the primary
+#[async_trait]
+impl LeaderElection for MigratingDatabaseClient {
+    async fn try_acquire_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> Result<bool, ItemError> {
+        self.primary.try_acquire_lease(name, holder, ttl).await
+    }
+
+    async fn release_lease(&self, name: &str, holder: &str) -> Result<(), ItemError> {
+        self.primary.release_lease(name, holder).await
+    }
+}
+
+#[async_trait]
+impl JobStore for MigratingDatabaseClient {
+    async fn create_job(&self, kind: &str) -> Result<Job, JobError> {
+        dual_write!(self.create_job(kind))
+    }
+
+    async fn update_job_progress(
+        &self,
+        id: &str,
+        processed: i64,
+        failed: i64,
+    ) -> Result<(), JobError> {
+        dual_write!(self.update_job_progress(id, processed, failed))
+    }
+
+    async fn finish_job(
+        &self,
+        id: &str,
+        status: JobStatus,
+        result: Option<&serde_json::Value>,
+        error: Option<&str>,
+    ) -> Result<Job, JobError> {
+        dual_write!(self.finish_job(id, status, result, error))
+    }
+
+    async fn get_job(&self, id: &str) -> Result<Option<Job>, JobError> {
+        sampled_read!(self.get_job(id))
+    }
+}
+
+#[async_trait]
+impl DatabaseClient for MigratingDatabaseClient {
+    /// Migrate both schemas, so the secondary is ready to take writes
+    async fn run_migrations(&self) -> Result<(), DatabaseInitError> {
+        self.primary.run_migrations().await?;
+        self.secondary.run_migrations().await
+    }
+
+    async fn migration_status(&self) -> Result<SchemaStatus, DatabaseInitError> {
+        self.primary.migration_status().await
+    }
+
+    async fn set_unique_content_hash(&self, enabled: bool) -> Result<(), DatabaseInitError> {
+        self.primary.set_unique_content_hash(enabled).await?;
+        self.secondary.set_unique_content_hash(enabled).await
+    }
+
+    async fn close(&self) {
+        self.primary.close().await;
+        self.secondary.close().await;
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::app::DEFAULT_CLAIM_TTL;
+    use crate::domain::build_solana_outbox_payload_from_request;
+    use crate::infra::SqliteClient;
+
+    async fn backend() -> Arc<SqliteClient> {
+        let client = SqliteClient::new("sqlite::memory:").await.unwrap();
+        client.run_migrations().await.unwrap();
+        Arc::new(client)
+    }
+
+    #[tokio::test]
+    async fn test_writes_reach_both_backends_under_the_same_ids() {
+        let (primary, secondary) = (backend().await, backend().await);
+        let client =
+            MigratingDatabaseClient::new(primary.clone(), secondary.clone()).with_compare_rate(1.0);
+
+        let item = client
+            .create_item(&CreateItemRequest::new(
+                "Contract".to_string(),
+                "Signed terms".to_string(),
+            ))
+            .await
+            .unwrap();
+        let claimed = client
+            .claim_pending_solana_outbox(10, DEFAULT_CLAIM_TTL)
+            .await
+            .unwrap();
+        client
+            .complete_solana_outbox(&claimed[0].id, &item.id, "sig_1")
+            .await
+            .unwrap();
+
+        let mirrored = secondary.get_item(&item.id).await.unwrap().unwrap();
+        assert_eq!(mirrored.blockchain_status, BlockchainStatus::Submitted);
+        assert_eq!(mirrored.blockchain_signature.as_deref(), Some("sig_1"));
+        // The secondary never claimed the entry, so completing it by ID is what moved it
+        assert!(
+            secondary
+                .claim_pending_solana_outbox(10, DEFAULT_CLAIM_TTL)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            fingerprint(&client.get_item(&item.id).await.unwrap()),
+            fingerprint(&Some(mirrored))
+        );
+
+        let key = client
+            .create_api_key(
+                "ci",
+                "key-hash",
+                &[ApiKeyScope::ItemsRead],
+                "default",
+                ApiKeyQuota::default(),
+            )
+            .await
+            .unwrap();
+        let mirrored_key = secondary.find_api_key_by_hash("key-hash").await.unwrap();
+        assert_eq!(mirrored_key.map(|k| k.id), Some(key.id));
+
+        let job = client.create_job("import").await.unwrap();
+        assert!(secondary.get_job(&job.id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_unit_of_work_commits_on_both_backends() {
+        let (primary, secondary) = (backend().await, backend().await);
+        let client = MigratingDatabaseClient::new(primary.clone(), secondary.clone());
+
+        let request = CreateItemRequest::new("Staged".to_string(), "Content".to_string());
+        let mut unit = client.begin().await.unwrap();
+        let item = unit.insert_item(&request).await.unwrap();
+        unit.enqueue_solana_outbox(
+            &item.id,
+            &build_solana_outbox_payload_from_request(&item.id, &request),
+        )
+        .await
+        .unwrap();
+        unit.commit().await.unwrap();
+
+        let mirrored = secondary.get_item(&item.id).await.unwrap().unwrap();
+        assert_eq!(
+            mirrored.blockchain_status,
+            BlockchainStatus::PendingSubmission
+        );
+        let claimed = secondary
+            .claim_pending_solana_outbox(10, DEFAULT_CLAIM_TTL)
+            .await
+            .unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(
+            claimed[0].id,
+            primary
+                .claim_pending_solana_outbox(10, DEFAULT_CLAIM_TTL)
+                .await
+                .unwrap()[0]
+                .id
+        );
+    }
+
+    #[tokio::test]
+    async fn test_secondary_failures_do_not_fail_writes() {
+        let primary = backend().await;
+        // No migrations: every statement on the secondary fails
+        let secondary = Arc::new(SqliteClient::new("sqlite::memory:").await.unwrap());
+        let client =
+            MigratingDatabaseClient::new(primary.clone(), secondary).with_compare_rate(1.0);
+
+        let item = client
+            .create_item(&CreateItemRequest::new(
+                "Primary only".to_string(),
+                "Content".to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(client.get_item(&item.id).await.unwrap(), Some(item.clone()));
+
+        let mut unit = client.begin().await.unwrap();
+        let staged = unit
+            .insert_item(&CreateItemRequest::new(
+                "Staged".to_string(),
+                "Content".to_string(),
+            ))
+            .await
+            .unwrap();
+        unit.commit().await.unwrap();
+        assert!(primary.get_item(&staged.id).await.unwrap().is_some());
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_timestamp_values() {
+        let mut first = Item::new(
+            "item_1".to_string(),
+            "hash".to_string(),
+            "Name".to_string(),
+            "Content".to_string(),
+        );
+        let mut second = first.clone();
+        second.created_at = first.created_at + chrono::Duration::milliseconds(3);
+        assert_eq!(fingerprint(&first), fingerprint(&second));
+
+        first.deleted_at = Some(Utc::now());
+        assert_ne!(fingerprint(&first), fingerprint(&second));
+        second.deleted_at = Some(Utc::now() + chrono::Duration::seconds(1));
+        assert_eq!(fingerprint(&first), fingerprint(&second));
+
+        second.blockchain_signature = Some("sig".to_string());
+        assert_ne!(fingerprint(&first), fingerprint(&second));
+    }
+}
@@ -1,5 +1,446 @@
 //! Database client implementations.
 
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use thiserror::Error;
+use tracing::error;
+
+use sqlx::migrate::Migrator;
+
+use crate::domain::{
+    ApiKeyStore, AuditFilter, AuditLogger, EncryptionError, EncryptionService, EventLog,
+    IdGenerator, Item, ItemError, ItemField, ItemFields, ItemRepository, JobStore, LeaderElection,
+    OutboxRepository, RequestJournal, SchemaStatus, SpendLedger, UsageLedger, WebhookDeliveryLog,
+};
+
+pub mod migrating;
 pub mod postgres;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 
+pub use migrating::{DEFAULT_MIGRATION_COMPARE_RATE, MigratingDatabaseClient};
+use migrating::{generate_id, generate_outbox_id};
 pub use postgres::{PostgresClient, PostgresConfig, PostgresInitError};
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteClient;
+
+/// Partial unique index on live items' `hash`, present while unique content is enforced
+pub const CONTENT_HASH_UNIQUE_INDEX: &str = "idx_items_hash_unique";
+
+/// Items rewritten per transaction by the content hash backfill
+const CONTENT_HASH_BACKFILL_BATCH: i64 = 500;
+
+/// Append-only table listed newest first by an increasing integer key
+struct LogTable {
+    name: &'static str,
+    columns: &'static str,
+    /// Keyset column (cursors hold its value)
+    key: &'static str,
+    /// Timestamp column filtered by a `TimeRange`
+    time: &'static str,
+}
+
+/// Item status events (`GET /admin/events`)
+const ITEM_EVENTS_LOG: LogTable = LogTable {
+    name: "item_events",
+    columns: "position, id, item_id, sequence, event, status, signature, error, occurred_at",
+    key: "position",
+    time: "occurred_at",
+};
+
+/// Webhook delivery attempts (`GET /admin/webhook-deliveries`)
+const WEBHOOK_DELIVERIES_LOG: LogTable = LogTable {
+    name: "webhook_deliveries",
+    columns: "id, event_id, event, item_id, url, attempt, response_status, error, succeeded, \
+              attempted_at",
+    key: "id",
+    time: "attempted_at",
+};
+
+/// Audit log of item and admin changes (`GET /admin/audit`)
+const AUDIT_LOG: LogTable = LogTable {
+    name: "audit_log",
+    columns: "id, principal, action, resource_id, request_id, occurred_at, diff",
+    key: "id",
+    time: "occurred_at",
+};
+
+/// Equality conditions of an audit filter, as `(column, value)` pairs for `log_page`
+fn audit_filter_columns(filter: &AuditFilter) -> Vec<(&'static str, &str)> {
+    [
+        ("principal", filter.principal.as_deref()),
+        ("action", filter.action.as_deref()),
+        ("resource_id", filter.resource_id.as_deref()),
+    ]
+    .into_iter()
+    .filter_map(|(column, value)| Some((column, value?)))
+    .collect()
+}
+
+/// Quota as stored in a BIGINT column
+fn quota_value(limit: u64) -> i64 {
+    i64::try_from(limit).unwrap_or(i64::MAX)
+}
+
+/// Quota read back from a BIGINT column (NULL: unlimited)
+fn quota_column(limit: Option<i64>) -> Option<u64> {
+    limit.map(|limit| u64::try_from(limit).unwrap_or(0))
+}
+
+/// Item content on its way into and out of the `items` table, encrypted with the
+/// configured [`EncryptionService`]; without one, content is stored as it is
+#[derive(Clone, Default)]
+struct ContentCipher(Option<Arc<dyn EncryptionService>>);
+
+impl ContentCipher {
+    fn new(service: Arc<dyn EncryptionService>) -> Self {
+        Self(Some(service))
+    }
+
+    /// Content as written to the row. Empty content (kept in the object store, or not
+    /// selected) stays empty.
+    async fn seal<'a>(&self, content: &'a str) -> Result<Cow<'a, str>, ItemError> {
+        match &self.0 {
+            Some(service) if !content.is_empty() => service
+                .encrypt(content)
+                .await
+                .map(Cow::Owned)
+                .map_err(|e| content_encryption_failure("encrypt", &e)),
+            _ => Ok(Cow::Borrowed(content)),
+        }
+    }
+
+    /// Plaintext of content as read from the row
+    async fn open_content(&self, stored: String) -> Result<String, ItemError> {
+        match &self.0 {
+            Some(service) => service
+                .decrypt(&stored)
+                .await
+                .map_err(|e| content_encryption_failure("decrypt", &e)),
+            None => Ok(stored),
+        }
+    }
+
+    /// `item` with its stored content decrypted
+    async fn open(&self, mut item: Item) -> Result<Item, ItemError> {
+        item.content = self.open_content(std::mem::take(&mut item.content)).await?;
+        Ok(item)
+    }
+
+    async fn open_all(&self, items: Vec<Item>) -> Result<Vec<Item>, ItemError> {
+        let mut opened = Vec::with_capacity(items.len());
+        for item in items {
+            opened.push(self.open(item).await?);
+        }
+        Ok(opened)
+    }
+
+    /// `InvalidState` unless encryption is configured
+    fn ensure_enabled(&self) -> Result<(), ItemError> {
+        match self.0 {
+            Some(_) => Ok(()),
+            None => Err(ItemError::InvalidState(
+                "Content encryption is not configured".to_string(),
+            )),
+        }
+    }
+
+    /// Stored content rewritten under the current keys, or None when it is empty or
+    /// already current
+    async fn reencrypt(&self, stored: &str) -> Result<Option<String>, ItemError> {
+        let Some(service) = &self.0 else {
+            return Ok(None);
+        };
+        if stored.is_empty() || !service.needs_reencryption(stored) {
+            return Ok(None);
+        }
+        let plaintext = service
+            .decrypt(stored)
+            .await
+            .map_err(|e| content_encryption_failure("decrypt", &e))?;
+        service
+            .encrypt(&plaintext)
+            .await
+            .map(Some)
+            .map_err(|e| content_encryption_failure("encrypt", &e))
+    }
+}
+
+fn content_encryption_failure(operation: &'static str, e: &EncryptionError) -> ItemError {
+    metrics::counter!("content_encryption_failures_total", "operation" => operation).increment(1);
+    error!(operation, error = %e, "Item content encryption failed");
+    ItemError::RepositoryFailure
+}
+
+/// Columns of the `jobs` table, in the order the row mappers read them
+const JOB_COLUMNS: &str =
+    "id, kind, status, processed, failed, result, error, created_at, updated_at, finished_at";
+
+/// `items` select list of a listing that needs only `columns` (None: all). Every other
+/// column is read as a constant of its type, so the row mappers decode it unchanged and
+/// large columns never leave the database.
+fn item_select_list(columns: Option<&ItemFields>) -> String {
+    let select: Vec<String> = ItemField::ALL
+        .into_iter()
+        .map(|field| match columns {
+            Some(columns) if !columns.reads(field) => {
+                format!("{} AS {}", unread_column(field), field.as_str())
+            }
+            _ => field.as_str().to_string(),
+        })
+        .collect();
+    select.join(", ")
+}
+
+/// Constant read in place of an unselected `items` column (valid in Postgres and SQLite)
+fn unread_column(field: ItemField) -> &'static str {
+    match field {
+        ItemField::Id | ItemField::CreatedAt => field.as_str(),
+        ItemField::UpdatedAt => "created_at",
+        ItemField::Hash
+        | ItemField::Name
+        | ItemField::Content
+        | ItemField::ContentType
+        | ItemField::TenantId => "''",
+        ItemField::BlockchainStatus => "'pending'",
+        ItemField::Description
+        | ItemField::ContentKey
+        | ItemField::BlockchainSignature
+        | ItemField::BlockchainLastError => "CAST(NULL AS TEXT)",
+        ItemField::Metadata => "CAST(NULL AS JSONB)",
+        ItemField::BlockchainNextRetryAt | ItemField::DeletedAt => "CAST(NULL AS TIMESTAMPTZ)",
+        ItemField::BlockchainRetryCount => "0",
+        ItemField::ContentLength | ItemField::Version => "CAST(0 AS BIGINT)",
+    }
+}
+
+/// Compare the migrations embedded in this build with the versions the database applied
+fn schema_status(migrator: &Migrator, applied: &[i64]) -> SchemaStatus {
+    let known: Vec<i64> = migrator
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+        .map(|m| m.version)
+        .collect();
+    SchemaStatus::compare(&known, applied)
+}
+
+/// Error for opening a database backend and running its migrations (used by main only).
+#[derive(Error, Debug)]
+pub enum DatabaseInitError {
+    #[error("Connection failed: {0}")]
+    Connection(String),
+    #[error("Migration failed: {0}")]
+    Migration(String),
+    #[error("Unsupported DATABASE_URL: {0}")]
+    UnsupportedUrl(String),
+}
+
+impl From<PostgresInitError> for DatabaseInitError {
+    fn from(e: PostgresInitError) -> Self {
+        match e {
+            PostgresInitError::Connection(msg) => Self::Connection(msg),
+            PostgresInitError::Migration(msg) => Self::Migration(msg),
+        }
+    }
+}
+
+/// Everything the application needs from its database, so `main` can hold one
+/// backend and hand out the individual repositories
+#[async_trait]
+pub trait DatabaseClient:
+    ItemRepository
+    + OutboxRepository
+    + ApiKeyStore
+    + RequestJournal
+    + WebhookDeliveryLog
+    + AuditLogger
+    + EventLog
+    + SpendLedger
+    + UsageLedger
+    + JobStore
+    + LeaderElection
+{
+    /// Bring the schema up to date
+    async fn run_migrations(&self) -> Result<(), DatabaseInitError>;
+
+    /// Compare applied migrations with this build's, without changing the schema
+    async fn migration_status(&self) -> Result<SchemaStatus, DatabaseInitError>;
+
+    /// Create (true) or drop (false) [`CONTENT_HASH_UNIQUE_INDEX`]; creating fails while
+    /// live items share content
+    async fn set_unique_content_hash(&self, enabled: bool) -> Result<(), DatabaseInitError>;
+
+    /// Close the pool, waiting for checked-out connections to be returned
+    async fn close(&self);
+}
+
+#[async_trait]
+impl DatabaseClient for PostgresClient {
+    async fn run_migrations(&self) -> Result<(), DatabaseInitError> {
+        Ok(PostgresClient::run_migrations(self).await?)
+    }
+
+    async fn migration_status(&self) -> Result<SchemaStatus, DatabaseInitError> {
+        Ok(PostgresClient::migration_status(self).await?)
+    }
+
+    async fn set_unique_content_hash(&self, enabled: bool) -> Result<(), DatabaseInitError> {
+        Ok(PostgresClient::set_unique_content_hash(self, enabled).await?)
+    }
+
+    async fn close(&self) {
+        PostgresClient::close(self).await;
+    }
+}
+
+#[cfg(feature = "sqlite")]
+#[async_trait]
+impl DatabaseClient for SqliteClient {
+    async fn run_migrations(&self) -> Result<(), DatabaseInitError> {
+        SqliteClient::run_migrations(self).await
+    }
+
+    async fn migration_status(&self) -> Result<SchemaStatus, DatabaseInitError> {
+        SqliteClient::migration_status(self).await
+    }
+
+    async fn set_unique_content_hash(&self, enabled: bool) -> Result<(), DatabaseInitError> {
+        SqliteClient::set_unique_content_hash(self, enabled).await
+    }
+
+    async fn close(&self) {
+        SqliteClient::close(self).await;
+    }
+}
+
+/// Database backend, chosen by the `DATABASE_URL` scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseBackend {
+    Postgres,
+    /// Requires the `sqlite` feature
+    Sqlite,
+}
+
+impl DatabaseBackend {
+    /// `postgres://` / `postgresql://` or `sqlite:`
+    pub fn from_url(database_url: &str) -> Result<Self, DatabaseInitError> {
+        let scheme = database_url.split(':').next().unwrap_or_default();
+        match scheme {
+            "postgres" | "postgresql" => Ok(Self::Postgres),
+            "sqlite" => Ok(Self::Sqlite),
+            _ => Err(DatabaseInitError::UnsupportedUrl(format!(
+                "unknown scheme '{}' (expected postgres:// or sqlite:)",
+                scheme
+            ))),
+        }
+    }
+}
+
+/// The item and submission (outbox) repositories of one database client, in the shape
+/// `AppState::new` takes them
+#[must_use]
+pub fn split_repositories(
+    db: &Arc<dyn DatabaseClient>,
+) -> (Arc<dyn ItemRepository>, Arc<dyn OutboxRepository>) {
+    (
+        Arc::clone(db) as Arc<dyn ItemRepository>,
+        Arc::clone(db) as Arc<dyn OutboxRepository>,
+    )
+}
+
+/// Connect to the backend selected by `database_url` (`pool_config` applies to Postgres),
+/// reading items from the Postgres replica at `read_url` when given, generating new row
+/// IDs with `ids` and storing item content encrypted with `encryption` when given
+pub async fn connect_database(
+    database_url: &str,
+    read_url: Option<&str>,
+    pool_config: PostgresConfig,
+    ids: Arc<dyn IdGenerator>,
+    encryption: Option<Arc<dyn EncryptionService>>,
+) -> Result<Arc<dyn DatabaseClient>, DatabaseInitError> {
+    let backend = DatabaseBackend::from_url(database_url)?;
+    if let Some(url) = read_url
+        && (backend != DatabaseBackend::Postgres
+            || DatabaseBackend::from_url(url)? != DatabaseBackend::Postgres)
+    {
+        return Err(DatabaseInitError::UnsupportedUrl(
+            "a read replica needs Postgres for both the database and the replica".to_string(),
+        ));
+    }
+    match backend {
+        DatabaseBackend::Postgres => {
+            let mut client = PostgresClient::new(database_url, pool_config.clone())
+                .await?
+                .with_id_generator(ids);
+            if let Some(url) = read_url {
+                client = client.with_read_replica(url, &pool_config)?;
+            }
+            if let Some(interval) = pool_config.metrics_interval {
+                client.spawn_pool_metrics(interval);
+            }
+            Ok(Arc::new(match encryption {
+                Some(service) => client.with_encryption(service),
+                None => client,
+            }))
+        }
+        #[cfg(feature = "sqlite")]
+        DatabaseBackend::Sqlite => {
+            let client = SqliteClient::new(database_url)
+                .await?
+                .with_id_generator(ids);
+            Ok(Arc::new(match encryption {
+                Some(service) => client.with_encryption(service),
+                None => client,
+            }))
+        }
+        #[cfg(not(feature = "sqlite"))]
+        DatabaseBackend::Sqlite => Err(DatabaseInitError::UnsupportedUrl(
+            "SQLite support is not compiled in (build with --features sqlite)".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backend_from_url_scheme() {
+        assert_eq!(
+            DatabaseBackend::from_url("postgres://user@localhost/db").unwrap(),
+            DatabaseBackend::Postgres
+        );
+        assert_eq!(
+            DatabaseBackend::from_url("postgresql://localhost/db").unwrap(),
+            DatabaseBackend::Postgres
+        );
+        assert_eq!(
+            DatabaseBackend::from_url("sqlite://data/app.db").unwrap(),
+            DatabaseBackend::Sqlite
+        );
+        assert_eq!(
+            DatabaseBackend::from_url("sqlite::memory:").unwrap(),
+            DatabaseBackend::Sqlite
+        );
+        assert!(matches!(
+            DatabaseBackend::from_url("mysql://localhost/db"),
+            Err(DatabaseInitError::UnsupportedUrl(_))
+        ));
+    }
+
+    #[cfg(not(feature = "sqlite"))]
+    #[tokio::test]
+    async fn test_sqlite_url_without_feature_is_rejected() {
+        let result = connect_database(
+            "sqlite::memory:",
+            None,
+            PostgresConfig::default(),
+            Arc::new(crate::infra::UuidV7IdGenerator),
+            None,
+        )
+        .await;
+        assert!(matches!(result, Err(DatabaseInitError::UnsupportedUrl(_))));
+    }
+}
@@ -2,17 +2,43 @@
 //! Maps sqlx errors to domain ItemError / HealthCheckError; does not leak SQL or driver details.
 
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
-use sqlx::{PgPool, Row, postgres::PgPoolOptions, types::Json};
-use std::time::Duration;
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::stream::BoxStream;
+use log::LevelFilter;
+use sqlx::{
+    ConnectOptions, PgConnection, PgPool, Postgres, QueryBuilder, Row, Transaction,
+    migrate::Migrator,
+    postgres::{PgConnectOptions, PgPoolOptions},
+    types::Json,
+};
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use thiserror::Error;
-use tracing::{info, instrument};
+use tracing::{info, instrument, warn};
 
+use super::{
+    AUDIT_LOG, CONTENT_HASH_BACKFILL_BATCH, CONTENT_HASH_UNIQUE_INDEX, ContentCipher,
+    ITEM_EVENTS_LOG, JOB_COLUMNS, LogTable, WEBHOOK_DELIVERIES_LOG, audit_filter_columns,
+    generate_id, generate_outbox_id, item_select_list, quota_column, quota_value, schema_status,
+};
 use crate::domain::{
-    BlockchainStatus, CreateItemRequest, HealthCheckError, Item, ItemError, ItemMetadata,
-    ItemRepository, OutboxRepository, OutboxStatus, PaginatedResponse, SolanaOutboxEntry,
-    SolanaOutboxPayload, build_solana_outbox_payload_from_request,
+    ApiKey, ApiKeyError, ApiKeyQuota, ApiKeyScope, ApiKeyStore, AuditEntry, AuditFilter,
+    AuditLogger, BlockchainStatus, ContentHasher, CreateItemRequest, EncryptionService, EventLog,
+    ExportBookmark, FailedSubmission, HealthCheckError, IdGenerator, Item, ItemCount, ItemError,
+    ItemListFilter, ItemMetadata, ItemPosition, ItemRepository, ItemSearchHit, ItemSortField,
+    ItemStatusEvent, Job, JobError, JobStatus, JobStore, KeyUsage, LeaderElection,
+    NotificationError, OutboxRepository, OutboxStatus, PaginatedResponse, QueueDepth, QuotaUsage,
+    ReencryptedBatch, RequestJournal, RequestJournalEntry, RequestJournalError, SchemaStatus,
+    SolanaOutboxEntry, SolanaOutboxPayload, SortOrder, SpendLedger, SubmissionAttempt,
+    SubmissionRepository, TagCount, TenantScope, TimeRange, UnitOfWork, UsageLedger,
+    WebhookDelivery, WebhookDeliveryLog, build_solana_outbox_payload_from_request, month_start,
 };
+use crate::infra::UuidV7IdGenerator;
+
+/// Migrations embedded from `./migrations`
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
 
 /// Error for Postgres client construction and migrations (used by main only).
 #[derive(Error, Debug)]
@@ -28,6 +54,9 @@ fn map_sqlx_to_item_error(e: sqlx::Error) -> ItemError {
         sqlx::Error::RowNotFound => ItemError::NotFound("Row not found".to_string()),
         sqlx::Error::Database(db_err) => {
             if db_err.code().as_deref() == Some("23505") {
+                if db_err.constraint() == Some(CONTENT_HASH_UNIQUE_INDEX) {
+                    return ItemError::DuplicateContent;
+                }
                 return ItemError::InvalidState("Duplicate".to_string());
             }
             ItemError::RepositoryFailure
@@ -36,6 +65,16 @@ fn map_sqlx_to_item_error(e: sqlx::Error) -> ItemError {
     }
 }
 
+fn map_sqlx_to_api_key_error(e: sqlx::Error) -> ApiKeyError {
+    match e {
+        sqlx::Error::RowNotFound => ApiKeyError::NotFound("Row not found".to_string()),
+        _ => ApiKeyError::RepositoryFailure,
+    }
+}
+
+/// Advisory lock key serializing appends to `item_events`
+const ITEM_EVENTS_LOCK_KEY: i64 = 0x6974_656d_5f65_7674;
+
 /// PostgreSQL connection pool configuration
 #[derive(Debug, Clone)]
 pub struct PostgresConfig {
@@ -44,6 +83,13 @@ pub struct PostgresConfig {
     pub acquire_timeout: Duration,
     pub idle_timeout: Duration,
     pub max_lifetime: Duration,
+    /// Statements running at least this long are logged at `warn` on the `sqlx::query`
+    /// target, with their summary and duration, inside the span of the repository method
+    /// that ran them (None: not logged)
+    pub slow_query_threshold: Option<Duration>,
+    /// How often [`connect_database`](super::connect_database) refreshes the pool gauges
+    /// (see [`PostgresClient::spawn_pool_metrics`]; None: not reported)
+    pub metrics_interval: Option<Duration>,
 }
 
 impl Default for PostgresConfig {
@@ -54,13 +100,173 @@ impl Default for PostgresConfig {
             acquire_timeout: Duration::from_secs(3),
             idle_timeout: Duration::from_secs(600),
             max_lifetime: Duration::from_secs(1800),
+            slow_query_threshold: Some(Duration::from_millis(500)),
+            metrics_interval: None,
+        }
+    }
+}
+
+impl PostgresConfig {
+    /// Local development: a small pool that may shrink to nothing, patient acquires for a
+    /// database that is still starting, statements over 100ms logged and no pool gauges
+    #[must_use]
+    pub fn development() -> Self {
+        Self {
+            max_connections: 5,
+            min_connections: 0,
+            acquire_timeout: Duration::from_secs(10),
+            idle_timeout: Duration::from_secs(60),
+            max_lifetime: Duration::from_secs(1800),
+            slow_query_threshold: Some(Duration::from_millis(100)),
+            metrics_interval: None,
+        }
+    }
+
+    /// Deployed instances: [`Default`] sizing with acquires failing fast, statements over
+    /// 500ms logged and the pool gauges refreshed every 15s
+    #[must_use]
+    pub fn production() -> Self {
+        Self {
+            metrics_interval: Some(Duration::from_secs(15)),
+            ..Self::default()
+        }
+    }
+
+    /// The `DATABASE_POOL_PRESET` preset (`development` or `production`, the default)
+    /// overridden by `DATABASE_MAX_CONNECTIONS`, `DATABASE_MIN_CONNECTIONS`,
+    /// `DATABASE_ACQUIRE_TIMEOUT_SECS`, `DATABASE_IDLE_TIMEOUT_SECS`,
+    /// `DATABASE_MAX_LIFETIME_SECS`, `DATABASE_SLOW_QUERY_MS` and
+    /// `DATABASE_POOL_METRICS_INTERVAL_SECS` (`0` turns the last two off); malformed
+    /// values are ignored
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let defaults = match var("DATABASE_POOL_PRESET").as_deref() {
+            Some("development") => Self::development(),
+            _ => Self::production(),
+        };
+        let number = |name: &str| var(name).and_then(|v| v.parse::<u64>().ok());
+        let secs = |name: &str| number(name).map(Duration::from_secs);
+        let or_off = |value: Option<Duration>, default| match value {
+            Some(Duration::ZERO) => None,
+            Some(value) => Some(value),
+            None => default,
+        };
+        let max_connections = number("DATABASE_MAX_CONNECTIONS")
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(defaults.max_connections);
+        Self {
+            max_connections,
+            min_connections: number("DATABASE_MIN_CONNECTIONS")
+                .and_then(|v| u32::try_from(v).ok())
+                .unwrap_or(defaults.min_connections)
+                .min(max_connections),
+            acquire_timeout: secs("DATABASE_ACQUIRE_TIMEOUT_SECS")
+                .filter(|v| !v.is_zero())
+                .unwrap_or(defaults.acquire_timeout),
+            idle_timeout: secs("DATABASE_IDLE_TIMEOUT_SECS").unwrap_or(defaults.idle_timeout),
+            max_lifetime: secs("DATABASE_MAX_LIFETIME_SECS").unwrap_or(defaults.max_lifetime),
+            slow_query_threshold: or_off(
+                number("DATABASE_SLOW_QUERY_MS").map(Duration::from_millis),
+                defaults.slow_query_threshold,
+            ),
+            metrics_interval: or_off(
+                secs("DATABASE_POOL_METRICS_INTERVAL_SECS"),
+                defaults.metrics_interval,
+            ),
+        }
+    }
+
+    fn pool_options(&self) -> PgPoolOptions {
+        PgPoolOptions::new()
+            .max_connections(self.max_connections)
+            .min_connections(self.min_connections)
+            .acquire_timeout(self.acquire_timeout)
+            .idle_timeout(self.idle_timeout)
+            .max_lifetime(self.max_lifetime)
+    }
+
+    fn connect_options(&self, database_url: &str) -> Result<PgConnectOptions, PostgresInitError> {
+        let options = PgConnectOptions::from_str(database_url)
+            .map_err(|e| PostgresInitError::Connection(e.to_string()))?;
+        Ok(match self.slow_query_threshold {
+            Some(threshold) => options.log_slow_statements(LevelFilter::Warn, threshold),
+            None => options.log_slow_statements(LevelFilter::Off, Duration::MAX),
+        })
+    }
+}
+
+/// Record the pool's size, idle connections and how long a connection takes to acquire
+/// every `interval`, until the pool is closed
+async fn report_pool_metrics(pool: PgPool, max_connections: u32, interval: Duration) {
+    metrics::gauge!("db_pool_max_connections").set(f64::from(max_connections));
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        if pool.is_closed() {
+            break;
+        }
+        // Measured with a probe connection: the wait a query would see right now
+        let start = Instant::now();
+        match pool.acquire().await {
+            Ok(connection) => drop(connection),
+            Err(e) => warn!(error = %e, "Pool metrics probe could not acquire a connection"),
         }
+        metrics::gauge!("db_pool_acquire_wait_seconds").set(start.elapsed().as_secs_f64());
+        metrics::gauge!("db_pool_size").set(f64::from(pool.size()));
+        metrics::gauge!("db_pool_idle").set(pool.num_idle() as f64);
+    }
+}
+
+/// Rows fetched per round trip by [`ItemRepository::stream_items`]
+const EXPORT_FETCH_BATCH: i64 = 500;
+
+/// How long reads skip a replica that failed before trying it again
+const REPLICA_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// Errors meaning the server could not be reached, rather than that the query failed
+fn is_unavailable(e: &sqlx::Error) -> bool {
+    matches!(
+        e,
+        sqlx::Error::PoolTimedOut
+            | sqlx::Error::PoolClosed
+            | sqlx::Error::Io(_)
+            | sqlx::Error::Tls(_)
+            | sqlx::Error::Protocol(_)
+    )
+}
+
+/// Read-only replica serving item reads, skipped for a while after it was unreachable
+struct ReadReplica {
+    pool: PgPool,
+    down_until: Mutex<Option<Instant>>,
+}
+
+impl ReadReplica {
+    fn is_up(&self) -> bool {
+        self.down_until
+            .lock()
+            .unwrap()
+            .is_none_or(|until| Instant::now() >= until)
+    }
+
+    fn mark_down(&self, error: &sqlx::Error) {
+        warn!(error = %error, retry_after = ?REPLICA_RETRY_AFTER, "Read replica unreachable, reading from the primary");
+        metrics::counter!("db_replica_fallbacks_total").increment(1);
+        *self.down_until.lock().unwrap() = Some(Instant::now() + REPLICA_RETRY_AFTER);
     }
 }
 
 /// PostgreSQL database client with connection pooling
 pub struct PostgresClient {
     pool: PgPool,
+    replica: Option<ReadReplica>,
+    cipher: ContentCipher,
+    ids: Arc<dyn IdGenerator>,
 }
 
 impl PostgresClient {
@@ -70,17 +276,34 @@ impl PostgresClient {
         config: PostgresConfig,
     ) -> Result<Self, PostgresInitError> {
         info!("Connecting to PostgreSQL...");
-        let pool = PgPoolOptions::new()
-            .max_connections(config.max_connections)
-            .min_connections(config.min_connections)
-            .acquire_timeout(config.acquire_timeout)
-            .idle_timeout(config.idle_timeout)
-            .max_lifetime(config.max_lifetime)
-            .connect(database_url)
+        let pool = config
+            .pool_options()
+            .connect_with(config.connect_options(database_url)?)
             .await
             .map_err(|e| PostgresInitError::Connection(e.to_string()))?;
         info!("Connected to PostgreSQL");
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            replica: None,
+            cipher: ContentCipher::default(),
+            ids: Arc::new(UuidV7IdGenerator),
+        })
+    }
+
+    /// Create a client whose pool opens connections on first use, without any I/O here
+    pub fn connect_lazy(
+        database_url: &str,
+        config: PostgresConfig,
+    ) -> Result<Self, PostgresInitError> {
+        let pool = config
+            .pool_options()
+            .connect_lazy_with(config.connect_options(database_url)?);
+        Ok(Self {
+            pool,
+            replica: None,
+            cipher: ContentCipher::default(),
+            ids: Arc::new(UuidV7IdGenerator),
+        })
     }
 
     /// Create a new PostgreSQL client with default configuration
@@ -88,23 +311,198 @@ impl PostgresClient {
         Self::new(database_url, PostgresConfig::default()).await
     }
 
+    /// Serve `get_item`, `list_items` and `search_items` from the read-only replica at
+    /// `replica_url`, with `config` for its pool. Its connections are opened on first use;
+    /// while it is unreachable those reads go to the primary.
+    pub fn with_read_replica(
+        mut self,
+        replica_url: &str,
+        config: &PostgresConfig,
+    ) -> Result<Self, PostgresInitError> {
+        let pool = config
+            .pool_options()
+            .connect_lazy_with(config.connect_options(replica_url)?);
+        self.replica = Some(ReadReplica {
+            pool,
+            down_until: Mutex::new(None),
+        });
+        info!("Reading items from the read replica");
+        Ok(self)
+    }
+
+    /// Run the read `query` on the replica, if there is one and it is up, and on the
+    /// primary when there is none or it cannot be reached
+    async fn read<'p, T, F, Fut>(&'p self, query: F) -> Result<T, sqlx::Error>
+    where
+        F: Fn(&'p PgPool) -> Fut,
+        Fut: Future<Output = Result<T, sqlx::Error>>,
+    {
+        if let Some(replica) = self.replica.as_ref().filter(|replica| replica.is_up()) {
+            match query(&replica.pool).await {
+                Err(e) if is_unavailable(&e) => replica.mark_down(&e),
+                result => return result,
+            }
+        }
+        query(&self.pool).await
+    }
+
+    /// Report the pool as `db_pool_size`, `db_pool_idle`, `db_pool_max_connections` and
+    /// `db_pool_acquire_wait_seconds` every `interval`, in a task that ends with the pool
+    pub fn spawn_pool_metrics(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let max_connections = self.pool.options().get_max_connections();
+        tokio::spawn(report_pool_metrics(
+            self.pool.clone(),
+            max_connections,
+            interval,
+        ))
+    }
+
+    /// Store item content encrypted with `service` (see [`ItemRepository::reencrypt_content`]
+    /// for rows written before)
+    #[must_use]
+    pub fn with_encryption(mut self, service: Arc<dyn EncryptionService>) -> Self {
+        self.cipher = ContentCipher::new(service);
+        self
+    }
+
+    /// Generate the IDs of new items, dead letters, API keys and jobs with `ids`
+    /// (UUIDv7 by default). Outbox entries keep UUIDv7 IDs.
+    #[must_use]
+    pub fn with_id_generator(mut self, ids: Arc<dyn IdGenerator>) -> Self {
+        self.ids = ids;
+        self
+    }
+
     /// Run database migrations using sqlx migrate
     pub async fn run_migrations(&self) -> Result<(), PostgresInitError> {
         info!("Running database migrations...");
-        sqlx::migrate!("./migrations")
+        MIGRATOR
             .run(&self.pool)
             .await
             .map_err(|e| PostgresInitError::Migration(e.to_string()))?;
+        self.backfill_content_hashes().await?;
         info!("Database migrations completed successfully");
         Ok(())
     }
 
+    /// Compare the applied migrations (`_sqlx_migrations`, absent on a fresh database) with
+    /// the ones embedded in this build
+    pub async fn migration_status(&self) -> Result<SchemaStatus, PostgresInitError> {
+        let migration_error = |e: sqlx::Error| PostgresInitError::Migration(e.to_string());
+        let table: Option<String> =
+            sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations')::text")
+                .fetch_one(&self.pool)
+                .await
+                .map_err(migration_error)?;
+        let applied: Vec<i64> = match table {
+            Some(_) => sqlx::query_scalar(
+                "SELECT version FROM _sqlx_migrations WHERE success ORDER BY version",
+            )
+            .fetch_all(&self.pool)
+            .await
+            .map_err(migration_error)?,
+            None => Vec::new(),
+        };
+        Ok(schema_status(&MIGRATOR, &applied))
+    }
+
+    /// Replace the `hash_<uuid>` placeholders stored by earlier versions with content
+    /// hashes, in batches. Runs after the migrations; returns the number of items updated.
+    pub async fn backfill_content_hashes(&self) -> Result<u64, PostgresInitError> {
+        let migration_error = |e: sqlx::Error| PostgresInitError::Migration(e.to_string());
+        let mut updated = 0;
+        loop {
+            let rows = sqlx::query(
+                r#"
+                SELECT id, name, description, content FROM items
+                WHERE hash LIKE 'hash\_%' ESCAPE '\'
+                LIMIT $1
+                "#,
+            )
+            .bind(CONTENT_HASH_BACKFILL_BATCH)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(migration_error)?;
+            if rows.is_empty() {
+                break;
+            }
+
+            let mut tx = self.pool.begin().await.map_err(migration_error)?;
+            for row in &rows {
+                let content = self
+                    .cipher
+                    .open_content(row.get("content"))
+                    .await
+                    .map_err(|e| PostgresInitError::Migration(e.to_string()))?;
+                let hash = ContentHasher::hash(
+                    row.get("name"),
+                    &content,
+                    row.get::<Option<&str>, _>("description"),
+                );
+                sqlx::query("UPDATE items SET hash = $2 WHERE id = $1")
+                    .bind(row.get::<&str, _>("id"))
+                    .bind(hash)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(migration_error)?;
+            }
+            tx.commit().await.map_err(migration_error)?;
+            updated += rows.len() as u64;
+        }
+        if updated > 0 {
+            info!(updated, "Backfilled item content hashes");
+        }
+        Ok(updated)
+    }
+
+    /// Enforce (or stop enforcing) one live item per content hash and tenant
+    pub async fn set_unique_content_hash(&self, enabled: bool) -> Result<(), PostgresInitError> {
+        let statement = if enabled {
+            format!(
+                "CREATE UNIQUE INDEX IF NOT EXISTS {} ON items (tenant_id, hash) \
+                 WHERE deleted_at IS NULL",
+                CONTENT_HASH_UNIQUE_INDEX
+            )
+        } else {
+            format!("DROP INDEX IF EXISTS {}", CONTENT_HASH_UNIQUE_INDEX)
+        };
+        sqlx::query(&statement)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                PostgresInitError::Migration(format!(
+                    "cannot enforce unique content hashes (do live items share content?): {}",
+                    e
+                ))
+            })?;
+        Ok(())
+    }
+
+    /// Close the pools, waiting for checked-out connections to be returned
+    pub async fn close(&self) {
+        if let Some(replica) = &self.replica {
+            replica.pool.close().await;
+        }
+        self.pool.close().await;
+    }
+
     /// Get the underlying connection pool (for testing)
     #[must_use]
     pub fn pool(&self) -> &PgPool {
         &self.pool
     }
 
+    /// Parse and decrypt an optional item row
+    async fn open_item_row(
+        &self,
+        row: Option<sqlx::postgres::PgRow>,
+    ) -> Result<Option<Item>, ItemError> {
+        match row {
+            Some(row) => Ok(Some(self.cipher.open(Self::row_to_item(&row)?).await?)),
+            None => Ok(None),
+        }
+    }
+
     /// Parse a database row into an Item
     fn row_to_item(row: &sqlx::postgres::PgRow) -> Result<Item, ItemError> {
         let metadata: Option<serde_json::Value> = row.try_get("metadata").ok();
@@ -116,6 +514,9 @@ impl PostgresClient {
             name: row.get("name"),
             description: row.get("description"),
             content: row.get("content"),
+            content_key: row.get("content_key"),
+            content_type: row.get("content_type"),
+            content_length: row.get("content_length"),
             metadata: metadata.and_then(|v| serde_json::from_value(v).ok()),
             blockchain_status: status_str.parse().unwrap_or(BlockchainStatus::Pending),
             blockchain_signature: row.get("blockchain_signature"),
@@ -124,9 +525,185 @@ impl PostgresClient {
             blockchain_next_retry_at: row.get("blockchain_next_retry_at"),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
+            deleted_at: row.get("deleted_at"),
+            tenant_id: row.get("tenant_id"),
+            version: row.get("version"),
         })
     }
 
+    /// Append `AND ...` clauses for every filter that is set (values are always bound)
+    /// Rows of one `list_items` page on `pool`; `RowNotFound` when the cursor item is gone
+    async fn fetch_item_page(
+        pool: &PgPool,
+        fetch_limit: i64,
+        cursor: Option<&str>,
+        filter: &ItemListFilter,
+        tenant: Option<String>,
+    ) -> Result<Vec<sqlx::postgres::PgRow>, sqlx::Error> {
+        let mut query = QueryBuilder::<Postgres>::new(format!(
+            "SELECT {} FROM items WHERE TRUE",
+            item_select_list(filter.columns.as_ref())
+        ));
+        Self::push_item_filters(&mut query, filter);
+
+        if let Some(cursor_id) = cursor {
+            // Keyset pagination on (sort column, id) using the cursor item's sort value
+            let cursor_row = sqlx::query(
+                "SELECT created_at, updated_at, name FROM items \
+                     WHERE id = $1 AND ($2::text IS NULL OR tenant_id = $2)",
+            )
+            .bind(cursor_id)
+            .bind(tenant)
+            .fetch_one(pool)
+            .await?;
+
+            let comparison = match filter.order {
+                SortOrder::Asc => ">",
+                SortOrder::Desc => "<",
+            };
+            query.push(format_args!(
+                " AND ({}, id) {} (",
+                filter.sort.as_str(),
+                comparison
+            ));
+            match filter.sort {
+                ItemSortField::CreatedAt | ItemSortField::UpdatedAt => {
+                    query.push_bind(cursor_row.get::<DateTime<Utc>, _>(filter.sort.as_str()))
+                }
+                ItemSortField::Name => query.push_bind(cursor_row.get::<String, _>("name")),
+            };
+            query.push(", ").push_bind(cursor_id).push(")");
+        }
+
+        // Column and direction come from closed enums, never from raw input. Qualified, as
+        // an unread column's placeholder shares its name.
+        let direction = filter.order.as_str();
+        query.push(format_args!(
+            " ORDER BY items.{} {}, items.id {} LIMIT ",
+            filter.sort.as_str(),
+            direction,
+            direction
+        ));
+        query.push_bind(fetch_limit);
+
+        query.build().fetch_all(pool).await
+    }
+
+    async fn fetch_item_offset_page(
+        pool: &PgPool,
+        offset: i64,
+        limit: i64,
+        filter: &ItemListFilter,
+    ) -> Result<Vec<sqlx::postgres::PgRow>, sqlx::Error> {
+        let mut query = QueryBuilder::<Postgres>::new(format!(
+            "SELECT {} FROM items WHERE TRUE",
+            item_select_list(filter.columns.as_ref())
+        ));
+        Self::push_item_filters(&mut query, filter);
+        let direction = filter.order.as_str();
+        query.push(format_args!(
+            " ORDER BY items.{} {}, items.id {} LIMIT ",
+            filter.sort.as_str(),
+            direction,
+            direction
+        ));
+        query.push_bind(limit).push(" OFFSET ").push_bind(offset);
+        query.build().fetch_all(pool).await
+    }
+
+    /// The planner's row estimate when it exceeds `estimate_above`, else `COUNT(*)`,
+    /// which has to visit every matching row
+    async fn count_matching_items(
+        pool: &PgPool,
+        filter: &ItemListFilter,
+        estimate_above: Option<u64>,
+    ) -> Result<ItemCount, sqlx::Error> {
+        if let Some(threshold) = estimate_above {
+            let mut explain = QueryBuilder::<Postgres>::new(
+                "EXPLAIN (FORMAT JSON) SELECT 1 FROM items WHERE TRUE",
+            );
+            Self::push_item_filters(&mut explain, filter);
+            let plan: Json<serde_json::Value> =
+                explain.build().fetch_one(pool).await?.try_get(0)?;
+            let estimate = plan.0[0]["Plan"]["Plan Rows"].as_f64().unwrap_or(0.0);
+            if estimate > threshold as f64 {
+                return Ok(ItemCount {
+                    total: estimate as u64,
+                    estimated: true,
+                });
+            }
+        }
+        let mut query = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM items WHERE TRUE");
+        Self::push_item_filters(&mut query, filter);
+        let total: i64 = query.build().fetch_one(pool).await?.try_get(0)?;
+        Ok(ItemCount::exact(u64::try_from(total).unwrap_or(0)))
+    }
+
+    fn push_item_filters(query: &mut QueryBuilder<'_, Postgres>, filter: &ItemListFilter) {
+        if !filter.include_deleted {
+            query.push(" AND deleted_at IS NULL");
+        }
+        if let Some(tenant) = TenantScope::current() {
+            query.push(" AND tenant_id = ").push_bind(tenant);
+        }
+        if let Some(status) = filter.blockchain_status {
+            query
+                .push(" AND blockchain_status = ")
+                .push_bind(status.as_str());
+        }
+        if let Some(tag) = &filter.tag {
+            query
+                .push(
+                    " AND EXISTS (SELECT 1 FROM item_tags \
+                     WHERE item_tags.item_id = items.id AND item_tags.tag = ",
+                )
+                .push_bind(tag.clone())
+                .push(")");
+        }
+        if let Some(author) = &filter.author {
+            query
+                .push(" AND metadata ->> 'author' = ")
+                .push_bind(author.clone());
+        }
+        if let Some(after) = filter.created_after {
+            query.push(" AND created_at >= ").push_bind(after);
+        }
+        if let Some(before) = filter.created_before {
+            query.push(" AND created_at < ").push_bind(before);
+        }
+    }
+
+    /// Parse a database row into an API key (unknown scopes are dropped)
+    fn row_to_api_key(row: &sqlx::postgres::PgRow) -> ApiKey {
+        let scopes: Vec<String> = row.get("scopes");
+        ApiKey {
+            id: row.get("id"),
+            name: row.get("name"),
+            scopes: scopes.iter().filter_map(|s| s.parse().ok()).collect(),
+            created_at: row.get("created_at"),
+            revoked_at: row.get("revoked_at"),
+            tenant_id: row.get("tenant_id"),
+            quota: ApiKeyQuota {
+                daily: quota_column(row.get("daily_quota")),
+                monthly: quota_column(row.get("monthly_quota")),
+            },
+        }
+    }
+
+    /// Parse a database row into the usage of one API key
+    fn row_to_key_usage(row: &sqlx::postgres::PgRow) -> KeyUsage {
+        KeyUsage {
+            key_id: row.get("key_id"),
+            name: row.get("name"),
+            daily_requests: u64::try_from(row.get::<i64, _>("daily_requests")).unwrap_or(0),
+            monthly_requests: u64::try_from(row.get::<i64, _>("monthly_requests")).unwrap_or(0),
+            quota: ApiKeyQuota {
+                daily: quota_column(row.get("daily_quota")),
+                monthly: quota_column(row.get("monthly_quota")),
+            },
+        }
+    }
+
     /// Parse a database row into a Solana outbox entry
     fn row_to_outbox(row: &sqlx::postgres::PgRow) -> Result<SolanaOutboxEntry, ItemError> {
         let payload: Json<SolanaOutboxPayload> = row
@@ -145,49 +722,71 @@ impl PostgresClient {
             created_at: row.get("created_at"),
         })
     }
-}
 
-#[async_trait]
-impl ItemRepository for PostgresClient {
-    #[instrument(skip(self))]
-    async fn health_check(&self) -> Result<(), HealthCheckError> {
-        sqlx::query("SELECT 1")
-            .execute(&self.pool)
-            .await
-            .map_err(|_| HealthCheckError::DatabaseUnavailable)?;
-        Ok(())
+    /// Insert an item, plus its outbox entry in the same transaction when `enqueue` is set.
+    /// Without an outbox entry the item stays `pending` (blockchain disabled).
+    async fn insert_item(
+        &self,
+        data: &CreateItemRequest,
+        enqueue: bool,
+    ) -> Result<Item, ItemError> {
+        let status = if enqueue {
+            BlockchainStatus::PendingSubmission
+        } else {
+            BlockchainStatus::Pending
+        };
+
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+        let item =
+            Self::insert_item_row(&mut tx, &self.cipher, self.ids.as_ref(), data, status).await?;
+        if enqueue {
+            let payload = build_solana_outbox_payload_from_request(&item.id, data);
+            Self::insert_outbox(&mut tx, &item.id, &payload, None, item.created_at).await?;
+        }
+        tx.commit().await.map_err(map_sqlx_to_item_error)?;
+
+        Ok(item)
     }
 
-    #[instrument(skip(self))]
-    async fn get_item(&self, id: &str) -> Result<Option<Item>, ItemError> {
-        let row = sqlx::query(
-            r#"
-            SELECT id, hash, name, description, content, metadata, 
-                   blockchain_status, blockchain_signature, blockchain_retry_count,
-                   blockchain_last_error, blockchain_next_retry_at,
-                   created_at, updated_at 
-            FROM items 
-            WHERE id = $1
-            "#,
+    /// Replace the item's `item_tags` rows with `tags`. Must run in the transaction that
+    /// wrote its metadata.
+    async fn replace_item_tags(
+        conn: &mut PgConnection,
+        item_id: &str,
+        tags: &[String],
+    ) -> Result<(), ItemError> {
+        sqlx::query("DELETE FROM item_tags WHERE item_id = $1")
+            .bind(item_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+        if tags.is_empty() {
+            return Ok(());
+        }
+        sqlx::query(
+            "INSERT INTO item_tags (item_id, tag) SELECT $1, UNNEST($2::text[]) \
+             ON CONFLICT DO NOTHING",
         )
-        .bind(id)
-        .fetch_optional(&self.pool)
+        .bind(item_id)
+        .bind(tags)
+        .execute(conn)
         .await
         .map_err(map_sqlx_to_item_error)?;
-
-        match row {
-            Some(row) => Ok(Some(Self::row_to_item(&row)?)),
-            None => Ok(None),
-        }
+        Ok(())
     }
 
-    #[instrument(skip(self, data), fields(item_name = %data.name))]
-    async fn create_item(&self, data: &CreateItemRequest) -> Result<Item, ItemError> {
-        let id = format!("item_{}", uuid::Uuid::now_v7());
-        let hash = format!("hash_{}", uuid::Uuid::now_v7());
+    /// Insert an item row in `status` inside the caller's transaction
+    async fn insert_item_row(
+        conn: &mut PgConnection,
+        cipher: &ContentCipher,
+        ids: &dyn IdGenerator,
+        data: &CreateItemRequest,
+        status: BlockchainStatus,
+    ) -> Result<Item, ItemError> {
+        let id = format!("item_{}", generate_id("item", ids));
+        let hash = ContentHasher::hash_request(data);
         let now = Utc::now();
-        let outbox_id = uuid::Uuid::now_v7();
-        let outbox_payload = build_solana_outbox_payload_from_request(&id, data);
+        let tenant_id = TenantScope::for_new_item();
 
         let metadata_json = data
             .metadata
@@ -195,49 +794,35 @@ impl ItemRepository for PostgresClient {
             .map(serde_json::to_value)
             .transpose()
             .map_err(|_| ItemError::RepositoryFailure)?;
-
-        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+        let content = cipher.seal(data.stored_content()).await?;
 
         sqlx::query(
             r#"
             INSERT INTO items (id, hash, name, description, content, metadata, 
                                blockchain_status, blockchain_retry_count,
-                               created_at, updated_at) 
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                               created_at, updated_at, tenant_id, content_key,
+                               content_type, content_length) 
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
             "#,
         )
         .bind(&id)
         .bind(&hash)
         .bind(&data.name)
         .bind(&data.description)
-        .bind(&data.content)
+        .bind(content.as_ref())
         .bind(&metadata_json)
-        .bind(BlockchainStatus::PendingSubmission.as_str())
+        .bind(status.as_str())
         .bind(0i32)
         .bind(now)
         .bind(now)
-        .execute(&mut *tx)
-        .await
-        .map_err(map_sqlx_to_item_error)?;
-
-        sqlx::query(
-            r#"
-            INSERT INTO solana_outbox (id, aggregate_id, payload, status, created_at, retry_count, next_retry_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
-            "#,
-        )
-        .bind(outbox_id)
-        .bind(&id)
-        .bind(Json(outbox_payload))
-        .bind(OutboxStatus::Pending.as_str())
-        .bind(now)
-        .bind(0i32)
-        .bind(Option::<DateTime<Utc>>::None)
-        .execute(&mut *tx)
+        .bind(&tenant_id)
+        .bind(&data.content_key)
+        .bind(data.content_type())
+        .bind(data.content.len() as i64)
+        .execute(&mut *conn)
         .await
         .map_err(map_sqlx_to_item_error)?;
-
-        tx.commit().await.map_err(map_sqlx_to_item_error)?;
+        Self::replace_item_tags(conn, &id, data.tags()).await?;
 
         let metadata: Option<ItemMetadata> = data.metadata.as_ref().map(|m| ItemMetadata {
             author: m.author.clone(),
@@ -251,159 +836,233 @@ impl ItemRepository for PostgresClient {
             hash,
             name: data.name.clone(),
             description: data.description.clone(),
-            content: data.content.clone(),
+            content: data.stored_content().to_string(),
+            content_key: data.content_key.clone(),
+            content_type: data.content_type().to_string(),
+            content_length: data.content.len() as i64,
             metadata,
-            blockchain_status: BlockchainStatus::PendingSubmission,
+            blockchain_status: status,
             blockchain_signature: None,
             blockchain_retry_count: 0,
             blockchain_last_error: None,
             blockchain_next_retry_at: None,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
+            tenant_id,
+            version: 1,
         })
     }
 
-    #[instrument(skip(self))]
-    async fn list_items(
-        &self,
-        limit: i64,
-        cursor: Option<&str>,
-    ) -> Result<PaginatedResponse<Item>, ItemError> {
-        // Clamp limit to valid range
-        let limit = limit.clamp(1, 100);
-        // Fetch one extra to determine if there are more items
-        let fetch_limit = limit + 1;
+    /// Insert a pending outbox entry for `item_id` inside the caller's transaction,
+    /// optionally carrying over a sticky blockhash
+    async fn insert_outbox(
+        conn: &mut PgConnection,
+        item_id: &str,
+        payload: &SolanaOutboxPayload,
+        attempt_blockhash: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<(), ItemError> {
+        sqlx::query(
+            r#"
+            INSERT INTO solana_outbox (id, aggregate_id, payload, status, created_at, retry_count, next_retry_at, attempt_blockhash)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(generate_outbox_id())
+        .bind(item_id)
+        .bind(Json(payload.clone()))
+        .bind(OutboxStatus::Pending.as_str())
+        .bind(now)
+        .bind(0i32)
+        .bind(Option::<DateTime<Utc>>::None)
+        .bind(attempt_blockhash)
+        .execute(conn)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        Ok(())
+    }
 
-        let rows = match cursor {
-            Some(cursor_id) => {
-                // Get the created_at of the cursor item for proper pagination
-                let cursor_row = sqlx::query("SELECT created_at FROM items WHERE id = $1")
-                    .bind(cursor_id)
-                    .fetch_optional(&self.pool)
-                    .await
-                    .map_err(map_sqlx_to_item_error)?;
+    /// Parse a database row into an item status event
+    fn row_to_event(row: &sqlx::postgres::PgRow) -> ItemStatusEvent {
+        let status_str: String = row.get("status");
+        ItemStatusEvent {
+            id: row.get("id"),
+            position: row.get("position"),
+            event: row.get("event"),
+            item_id: row.get("item_id"),
+            sequence: row.get("sequence"),
+            status: status_str.parse().unwrap_or(BlockchainStatus::Pending),
+            signature: row.get("signature"),
+            error: row.get("error"),
+            occurred_at: row.get("occurred_at"),
+        }
+    }
 
-                let cursor_created_at: DateTime<Utc> = match cursor_row {
-                    Some(row) => row.get("created_at"),
-                    None => {
-                        return Err(ItemError::InvalidState("Invalid cursor".to_string()));
-                    }
-                };
+    /// Lock the item row for the rest of the transaction and return its status
+    /// (None: no such item)
+    async fn lock_item_status(
+        conn: &mut PgConnection,
+        item_id: &str,
+    ) -> Result<Option<BlockchainStatus>, ItemError> {
+        let status: Option<String> = sqlx::query_scalar(
+            "SELECT blockchain_status FROM items \
+                 WHERE id = $1 AND ($2::text IS NULL OR tenant_id = $2) FOR UPDATE",
+        )
+        .bind(item_id)
+        .bind(TenantScope::current())
+        .fetch_optional(conn)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        Ok(status.map(|s| s.parse().unwrap_or(BlockchainStatus::Pending)))
+    }
 
-                sqlx::query(
-                    r#"
-                    SELECT id, hash, name, description, content, metadata,
-                           blockchain_status, blockchain_signature, blockchain_retry_count,
-                           blockchain_last_error, blockchain_next_retry_at,
-                           created_at, updated_at
-                    FROM items
-                    WHERE (created_at, id) < ($1, $2)
-                    ORDER BY created_at DESC, id DESC
-                    LIMIT $3
-                    "#,
-                )
-                .bind(cursor_created_at)
-                .bind(cursor_id)
-                .bind(fetch_limit)
-                .fetch_all(&self.pool)
-                .await
-                .map_err(map_sqlx_to_item_error)?
-            }
-            None => sqlx::query(
-                r#"
-                    SELECT id, hash, name, description, content, metadata,
-                           blockchain_status, blockchain_signature, blockchain_retry_count,
-                           blockchain_last_error, blockchain_next_retry_at,
-                           created_at, updated_at
-                    FROM items
-                    ORDER BY created_at DESC, id DESC
-                    LIMIT $1
-                    "#,
-            )
-            .bind(fetch_limit)
-            .fetch_all(&self.pool)
-            .await
-            .map_err(map_sqlx_to_item_error)?,
-        };
-
-        let has_more = rows.len() > limit as usize;
-        let items: Vec<Item> = rows
-            .iter()
-            .take(limit as usize)
-            .map(Self::row_to_item)
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let next_cursor = if has_more {
-            items.last().map(|item| item.id.clone())
-        } else {
-            None
-        };
-
-        Ok(PaginatedResponse::new(items, next_cursor, has_more))
-    }
-
-    #[instrument(skip(self))]
-    async fn update_blockchain_status(
-        &self,
-        id: &str,
+    /// Append a status event if the item moved from `previous` into a notified status.
+    /// Must run in the transaction that changed the status (after [`Self::lock_item_status`]).
+    async fn append_status_event(
+        conn: &mut PgConnection,
+        item_id: &str,
+        previous: Option<BlockchainStatus>,
         status: BlockchainStatus,
         signature: Option<&str>,
         error: Option<&str>,
-        next_retry_at: Option<DateTime<Utc>>,
     ) -> Result<(), ItemError> {
-        let now = Utc::now();
+        if previous.is_none_or(|p| p == status) || !ItemStatusEvent::is_notified(status) {
+            return Ok(());
+        }
+
+        // Serialize appenders until commit so positions become visible in order and a
+        // dispatcher never skips past an event that commits later
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(ITEM_EVENTS_LOCK_KEY)
+            .execute(&mut *conn)
+            .await
+            .map_err(map_sqlx_to_item_error)?;
 
+        let event = ItemStatusEvent::new(item_id, status);
         sqlx::query(
             r#"
-            UPDATE items 
-            SET blockchain_status = $1,
-                blockchain_signature = COALESCE($2, blockchain_signature),
-                blockchain_last_error = $3,
-                blockchain_next_retry_at = $4,
-                updated_at = $5
-            WHERE id = $6
+            INSERT INTO item_events (id, item_id, sequence, event, status, signature, error, occurred_at)
+            VALUES (
+                $1, $2,
+                (SELECT COALESCE(MAX(sequence), 0) + 1 FROM item_events WHERE item_id = $2),
+                $3, $4, $5, $6, $7
+            )
             "#,
         )
+        .bind(&event.id)
+        .bind(item_id)
+        .bind(&event.event)
         .bind(status.as_str())
         .bind(signature)
         .bind(error)
-        .bind(next_retry_at)
-        .bind(now)
-        .bind(id)
-        .execute(&self.pool)
+        .bind(event.occurred_at)
+        .execute(&mut *conn)
         .await
         .map_err(map_sqlx_to_item_error)?;
-
         Ok(())
     }
 
-    #[instrument(skip(self, payload))]
-    async fn enqueue_solana_outbox_for_item(
-        &self,
+    /// Record a failed attempt on the outbox entry and the item (see
+    /// [`OutboxRepository::fail_solana_outbox`]) inside the caller's transaction
+    #[allow(clippy::too_many_arguments)]
+    async fn fail_outbox_entry(
+        conn: &mut PgConnection,
+        outbox_id: &str,
         item_id: &str,
-        payload: &SolanaOutboxPayload,
-    ) -> Result<Item, ItemError> {
+        retry_count: i32,
+        outbox_status: OutboxStatus,
+        item_status: BlockchainStatus,
+        error: &str,
+        next_retry_at: Option<DateTime<Utc>>,
+        attempt_blockhash: Option<Option<&str>>,
+    ) -> Result<(), ItemError> {
         let now = Utc::now();
-        let outbox_id = uuid::Uuid::now_v7();
-        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
 
+        // Update outbox: status, retry_count, next_retry_at, and optionally attempt_blockhash
+        let set_blockhash = attempt_blockhash.is_some();
+        let blockhash_bind: Option<String> =
+            attempt_blockhash.and_then(|o| o.map(std::string::ToString::to_string));
+
+        if set_blockhash {
+            sqlx::query(
+                r#"
+                UPDATE solana_outbox
+                SET status = $1,
+                    retry_count = $2,
+                    next_retry_at = $3,
+                    attempt_blockhash = $4,
+                    updated_at = NOW()
+                WHERE id = $5::uuid
+                "#,
+            )
+            .bind(outbox_status.as_str())
+            .bind(retry_count)
+            .bind(next_retry_at)
+            .bind(blockhash_bind)
+            .bind(outbox_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+        } else {
+            sqlx::query(
+                r#"
+                UPDATE solana_outbox
+                SET status = $1,
+                    retry_count = $2,
+                    next_retry_at = $3,
+                    updated_at = NOW()
+                WHERE id = $4::uuid
+                "#,
+            )
+            .bind(outbox_status.as_str())
+            .bind(retry_count)
+            .bind(next_retry_at)
+            .bind(outbox_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+        }
+
+        let previous = Self::lock_item_status(&mut *conn, item_id).await?;
         sqlx::query(
             r#"
-            INSERT INTO solana_outbox (id, aggregate_id, payload, status, created_at, retry_count, next_retry_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            UPDATE items
+            SET blockchain_status = $1,
+                blockchain_last_error = $2,
+                blockchain_next_retry_at = $3,
+                blockchain_retry_count = $4,
+                updated_at = $5
+            WHERE id = $6 AND ($7::text IS NULL OR tenant_id = $7)
             "#,
         )
-        .bind(outbox_id)
-        .bind(item_id)
-        .bind(Json(payload.clone()))
-        .bind(OutboxStatus::Pending.as_str())
+        .bind(item_status.as_str())
+        .bind(error)
+        .bind(next_retry_at)
+        .bind(retry_count)
         .bind(now)
-        .bind(0i32)
-        .bind(Option::<DateTime<Utc>>::None)
-        .execute(&mut *tx)
+        .bind(item_id)
+        .bind(TenantScope::current())
+        .execute(&mut *conn)
         .await
         .map_err(map_sqlx_to_item_error)?;
 
+        Self::append_status_event(conn, item_id, previous, item_status, None, Some(error)).await
+    }
+
+    /// Insert a pending outbox entry for `item_id` and reset the item to
+    /// `pending_submission` inside the caller's transaction. `attempt_blockhash` carries a
+    /// sticky blockhash over from an earlier attempt.
+    async fn enqueue_outbox_entry(
+        conn: &mut PgConnection,
+        cipher: &ContentCipher,
+        item_id: &str,
+        payload: &SolanaOutboxPayload,
+        attempt_blockhash: Option<&str>,
+    ) -> Result<Item, ItemError> {
+        let now = Utc::now();
+        Self::insert_outbox(&mut *conn, item_id, payload, attempt_blockhash, now).await?;
+
         let row = sqlx::query(
             r#"
             UPDATE items
@@ -412,283 +1071,1867 @@ impl ItemRepository for PostgresClient {
                 blockchain_next_retry_at = NULL,
                 blockchain_retry_count = 0,
                 updated_at = $2
-            WHERE id = $3
+            WHERE id = $3 AND ($4::text IS NULL OR tenant_id = $4)
             RETURNING id, hash, name, description, content, metadata,
                       blockchain_status, blockchain_signature, blockchain_retry_count,
                       blockchain_last_error, blockchain_next_retry_at,
-                      created_at, updated_at
+                      created_at, updated_at, deleted_at, tenant_id, version, content_key,
+                      content_type, content_length
+            "#,
+        )
+        .bind(BlockchainStatus::PendingSubmission.as_str())
+        .bind(now)
+        .bind(item_id)
+        .bind(TenantScope::current())
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        cipher.open(Self::row_to_item(&row)?).await
+    }
+
+    /// Parse a database row into a webhook delivery attempt
+    fn row_to_delivery(row: &sqlx::postgres::PgRow) -> WebhookDelivery {
+        WebhookDelivery {
+            id: row.get("id"),
+            event_id: row.get("event_id"),
+            event: row.get("event"),
+            item_id: row.get("item_id"),
+            url: row.get("url"),
+            attempt: u32::try_from(row.get::<i32, _>("attempt")).unwrap_or(0),
+            response_status: row
+                .get::<Option<i16>, _>("response_status")
+                .and_then(|s| u16::try_from(s).ok()),
+            error: row.get("error"),
+            succeeded: row.get("succeeded"),
+            attempted_at: row.get("attempted_at"),
+        }
+    }
+
+    /// Parse a database row into an audit log entry
+    fn row_to_audit_entry(row: &sqlx::postgres::PgRow) -> AuditEntry {
+        AuditEntry {
+            id: row.get("id"),
+            principal: row.get("principal"),
+            action: row.get("action"),
+            resource_id: row.get("resource_id"),
+            request_id: row.get("request_id"),
+            occurred_at: row.get("occurred_at"),
+            diff: row.get("diff"),
+        }
+    }
+
+    /// Newest-first keyset page of a log table: up to `limit + 1` rows keyed below
+    /// `before` within `range` whose `equal` columns hold the given values (column names
+    /// are constants, values are bound)
+    async fn log_page<T>(
+        &self,
+        table: &LogTable,
+        limit: i64,
+        before: Option<i64>,
+        range: &TimeRange,
+        equal: &[(&str, &str)],
+        parse: impl Fn(&sqlx::postgres::PgRow) -> T,
+    ) -> Result<Vec<T>, NotificationError> {
+        let LogTable {
+            name,
+            columns,
+            key,
+            time,
+        } = table;
+        let mut query =
+            QueryBuilder::<Postgres>::new(format!("SELECT {columns} FROM {name} WHERE 1 = 1"));
+        if let Some(before) = before {
+            query.push(format_args!(" AND {key} < ")).push_bind(before);
+        }
+        if let Some(since) = range.since {
+            query.push(format_args!(" AND {time} >= ")).push_bind(since);
+        }
+        if let Some(until) = range.until {
+            query.push(format_args!(" AND {time} < ")).push_bind(until);
+        }
+        for (column, value) in equal {
+            query
+                .push(format_args!(" AND {column} = "))
+                .push_bind(*value);
+        }
+        query
+            .push(format_args!(" ORDER BY {key} DESC LIMIT "))
+            .push_bind(limit + 1);
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|_| NotificationError::RepositoryFailure)?;
+        Ok(rows.iter().map(parse).collect())
+    }
+
+    /// Every row of a log table about `item_id`, oldest first
+    async fn item_log<T>(
+        &self,
+        table: &LogTable,
+        item_id: &str,
+        parse: impl Fn(&sqlx::postgres::PgRow) -> T,
+    ) -> Result<Vec<T>, NotificationError> {
+        let LogTable {
+            name, columns, key, ..
+        } = table;
+        let rows = sqlx::query(&format!(
+            "SELECT {columns} FROM {name} WHERE item_id = $1 ORDER BY {key}"
+        ))
+        .bind(item_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|_| NotificationError::RepositoryFailure)?;
+        Ok(rows.iter().map(parse).collect())
+    }
+
+    /// Parse a database row into a dead-lettered submission
+    fn row_to_failed_submission(row: &sqlx::postgres::PgRow) -> FailedSubmission {
+        FailedSubmission {
+            id: row.get("id"),
+            item_id: row.get("item_id"),
+            outbox_id: row.get::<uuid::Uuid, _>("outbox_id").to_string(),
+            hash: row.get("hash"),
+            retry_count: row.get("retry_count"),
+            last_error: row.get("last_error"),
+            failed_at: row.get("failed_at"),
+            requeued_at: row.get("requeued_at"),
+        }
+    }
+
+    /// Parse a database row into an export bookmark
+    fn row_to_export_bookmark(row: &sqlx::postgres::PgRow) -> ExportBookmark {
+        let updated_at: Option<DateTime<Utc>> = row.get("last_updated_at");
+        let item_id: Option<String> = row.get("last_item_id");
+        ExportBookmark {
+            name: row.get("name"),
+            position: updated_at
+                .zip(item_id)
+                .map(|(updated_at, item_id)| ItemPosition {
+                    updated_at,
+                    item_id,
+                }),
+            created_at: row.get("created_at"),
+            acknowledged_at: row.get("acknowledged_at"),
+        }
+    }
+
+    /// Parse a database row into a background job
+    fn row_to_job(row: &sqlx::postgres::PgRow) -> Result<Job, JobError> {
+        let status: String = row.get("status");
+        Ok(Job {
+            id: row.get("id"),
+            kind: row.get("kind"),
+            status: status.parse().map_err(|_| JobError::RepositoryFailure)?,
+            processed: row.get("processed"),
+            failed: row.get("failed"),
+            result: row.get("result"),
+            error: row.get("error"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            finished_at: row.get("finished_at"),
+        })
+    }
+}
+
+/// [`UnitOfWork`] over one Postgres transaction (rolled back by sqlx when dropped)
+pub struct PostgresUnitOfWork {
+    tx: Transaction<'static, Postgres>,
+    cipher: ContentCipher,
+    ids: Arc<dyn IdGenerator>,
+}
+
+#[async_trait]
+impl UnitOfWork for PostgresUnitOfWork {
+    async fn insert_item(&mut self, data: &CreateItemRequest) -> Result<Item, ItemError> {
+        PostgresClient::insert_item_row(
+            &mut self.tx,
+            &self.cipher,
+            self.ids.as_ref(),
+            data,
+            BlockchainStatus::Pending,
+        )
+        .await
+    }
+
+    async fn enqueue_solana_outbox(
+        &mut self,
+        item_id: &str,
+        payload: &SolanaOutboxPayload,
+    ) -> Result<Item, ItemError> {
+        PostgresClient::enqueue_outbox_entry(&mut self.tx, &self.cipher, item_id, payload, None)
+            .await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), ItemError> {
+        self.tx.commit().await.map_err(map_sqlx_to_item_error)
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), ItemError> {
+        self.tx.rollback().await.map_err(map_sqlx_to_item_error)
+    }
+}
+
+#[async_trait]
+impl ItemRepository for PostgresClient {
+    #[instrument(skip(self))]
+    async fn health_check(&self) -> Result<(), HealthCheckError> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map_err(|_| HealthCheckError::DatabaseUnavailable)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn get_item(&self, id: &str) -> Result<Option<Item>, ItemError> {
+        let tenant = TenantScope::current();
+        let row = self
+            .read(|pool| {
+                sqlx::query(
+                    r#"
+                    SELECT id, hash, name, description, content, metadata, 
+                           blockchain_status, blockchain_signature, blockchain_retry_count,
+                           blockchain_last_error, blockchain_next_retry_at,
+                           created_at, updated_at, deleted_at, tenant_id, version, content_key,
+                           content_type, content_length
+                    FROM items 
+                    WHERE id = $1 AND ($2::text IS NULL OR tenant_id = $2)
+                    "#,
+                )
+                .bind(id)
+                .bind(tenant.clone())
+                .fetch_optional(pool)
+            })
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+
+        self.open_item_row(row).await
+    }
+
+    #[instrument(skip(self, data), fields(item_name = %data.name))]
+    async fn create_item(&self, data: &CreateItemRequest) -> Result<Item, ItemError> {
+        self.insert_item(data, true).await
+    }
+
+    #[instrument(skip(self, data), fields(item_name = %data.name))]
+    async fn create_item_without_outbox(
+        &self,
+        data: &CreateItemRequest,
+    ) -> Result<Item, ItemError> {
+        self.insert_item(data, false).await
+    }
+
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork + '_>, ItemError> {
+        let tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+        Ok(Box::new(PostgresUnitOfWork {
+            tx,
+            cipher: self.cipher.clone(),
+            ids: self.ids.clone(),
+        }))
+    }
+
+    #[instrument(skip(self))]
+    async fn list_items(
+        &self,
+        limit: i64,
+        cursor: Option<&str>,
+        filter: &ItemListFilter,
+    ) -> Result<PaginatedResponse<Item>, ItemError> {
+        // Clamp limit to valid range
+        let limit = limit.clamp(1, 100);
+        // Fetch one extra to determine if there are more items
+        let fetch_limit = limit + 1;
+
+        let tenant = TenantScope::current();
+        let rows = self
+            .read(|pool| Self::fetch_item_page(pool, fetch_limit, cursor, filter, tenant.clone()))
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::RowNotFound => {
+                    ItemError::InvalidCursor("Cursor item no longer exists".to_string())
+                }
+                e => map_sqlx_to_item_error(e),
+            })?;
+
+        let has_more = rows.len() > limit as usize;
+        let items: Vec<Item> = rows
+            .iter()
+            .take(limit as usize)
+            .map(Self::row_to_item)
+            .collect::<Result<Vec<_>, _>>()?;
+        let items = self.cipher.open_all(items).await?;
+
+        let next_cursor = if has_more {
+            items.last().map(|item| item.id.clone())
+        } else {
+            None
+        };
+
+        Ok(PaginatedResponse::new(items, next_cursor, has_more))
+    }
+
+    #[instrument(skip(self))]
+    async fn list_items_at_offset(
+        &self,
+        offset: i64,
+        limit: i64,
+        filter: &ItemListFilter,
+    ) -> Result<Vec<Item>, ItemError> {
+        let rows = self
+            .read(|pool| Self::fetch_item_offset_page(pool, offset, limit, filter))
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+        let items = rows
+            .iter()
+            .map(Self::row_to_item)
+            .collect::<Result<Vec<_>, _>>()?;
+        self.cipher.open_all(items).await
+    }
+
+    #[instrument(skip(self))]
+    async fn count_items(
+        &self,
+        filter: &ItemListFilter,
+        estimate_above: Option<u64>,
+    ) -> Result<ItemCount, ItemError> {
+        self.read(|pool| Self::count_matching_items(pool, filter, estimate_above))
+            .await
+            .map_err(map_sqlx_to_item_error)
+    }
+
+    #[instrument(skip(self))]
+    async fn list_tags(&self) -> Result<Vec<TagCount>, ItemError> {
+        let tenant = TenantScope::current();
+        let rows = self
+            .read(|pool| {
+                sqlx::query(
+                    r#"
+                    SELECT item_tags.tag, COUNT(*) AS items
+                    FROM item_tags
+                    JOIN items ON items.id = item_tags.item_id
+                    WHERE items.deleted_at IS NULL
+                      AND ($1::text IS NULL OR items.tenant_id = $1)
+                    GROUP BY item_tags.tag
+                    ORDER BY items DESC, item_tags.tag
+                    "#,
+                )
+                .bind(tenant.clone())
+                .fetch_all(pool)
+            })
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+        Ok(rows
+            .iter()
+            .map(|row| TagCount {
+                tag: row.get("tag"),
+                items: u64::try_from(row.get::<i64, _>("items")).unwrap_or(0),
+            })
+            .collect())
+    }
+
+    /// Reads through a server-side cursor in batches of [`EXPORT_FETCH_BATCH`], inside a
+    /// read-only transaction that is rolled back when the stream ends or is dropped
+    fn stream_items(&self) -> BoxStream<'static, Result<Item, ItemError>> {
+        let pool = self.pool.clone();
+        let cipher = self.cipher.clone();
+        // The stream is polled outside the caller's tenant scope
+        let tenant = TenantScope::current();
+        Box::pin(async_stream::try_stream! {
+            let mut tx = pool.begin().await.map_err(map_sqlx_to_item_error)?;
+            // DECLARE takes no bind parameters, so the tenant travels in a
+            // transaction-local setting
+            sqlx::query("SELECT set_config('app.tenant_id', $1, true)")
+                .bind(tenant.unwrap_or_default())
+                .execute(&mut *tx)
+                .await
+                .map_err(map_sqlx_to_item_error)?;
+            sqlx::query(
+                r#"
+                DECLARE items_export NO SCROLL CURSOR FOR
+                SELECT id, hash, name, description, content, metadata,
+                       blockchain_status, blockchain_signature, blockchain_retry_count,
+                       blockchain_last_error, blockchain_next_retry_at,
+                       created_at, updated_at, deleted_at, tenant_id, version, content_key,
+                       content_type, content_length
+                FROM items
+                WHERE deleted_at IS NULL
+                  AND (NULLIF(current_setting('app.tenant_id', true), '') IS NULL
+                       OR tenant_id = current_setting('app.tenant_id', true))
+                ORDER BY created_at, id
+                "#,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+
+            let fetch = format!("FETCH {EXPORT_FETCH_BATCH} FROM items_export");
+            loop {
+                let rows = sqlx::query(&fetch)
+                    .fetch_all(&mut *tx)
+                    .await
+                    .map_err(map_sqlx_to_item_error)?;
+                if rows.is_empty() {
+                    break;
+                }
+                for row in &rows {
+                    yield cipher.open(Self::row_to_item(row)?).await?;
+                }
+            }
+            tx.rollback().await.map_err(map_sqlx_to_item_error)?;
+        })
+    }
+
+    /// Keyset pages of [`EXPORT_FETCH_BATCH`] over `(updated_at, id)`; unlike
+    /// [`Self::stream_items`] no transaction is held, so a consumer that reads slowly
+    /// sees the changes committed while it reads
+    fn stream_item_changes(
+        &self,
+        after: Option<ItemPosition>,
+    ) -> BoxStream<'static, Result<Item, ItemError>> {
+        let pool = self.pool.clone();
+        let cipher = self.cipher.clone();
+        let tenant = TenantScope::current();
+        Box::pin(async_stream::try_stream! {
+            let mut after = after;
+            loop {
+                let mut query = QueryBuilder::<Postgres>::new(
+                    r#"
+                    SELECT id, hash, name, description, content, metadata,
+                           blockchain_status, blockchain_signature, blockchain_retry_count,
+                           blockchain_last_error, blockchain_next_retry_at,
+                           created_at, updated_at, deleted_at, tenant_id, version, content_key,
+                           content_type, content_length
+                    FROM items
+                    WHERE deleted_at IS NULL
+                    "#,
+                );
+                if let Some(tenant) = &tenant {
+                    query.push(" AND tenant_id = ").push_bind(tenant.clone());
+                }
+                if let Some(position) = &after {
+                    query
+                        .push(" AND (updated_at, id) > (")
+                        .push_bind(position.updated_at)
+                        .push(", ")
+                        .push_bind(position.item_id.clone())
+                        .push(")");
+                }
+                query
+                    .push(" ORDER BY updated_at, id LIMIT ")
+                    .push_bind(EXPORT_FETCH_BATCH);
+                let rows = query
+                    .build()
+                    .fetch_all(&pool)
+                    .await
+                    .map_err(map_sqlx_to_item_error)?;
+
+                for row in &rows {
+                    let item = cipher.open(Self::row_to_item(row)?).await?;
+                    after = Some(ItemPosition::of(&item));
+                    yield item;
+                }
+                if rows.len() < EXPORT_FETCH_BATCH as usize {
+                    break;
+                }
+            }
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn export_bookmark(&self, name: &str) -> Result<ExportBookmark, ItemError> {
+        let row = sqlx::query(
+            r#"
+            WITH registered AS (
+                INSERT INTO export_bookmarks (name) VALUES ($1)
+                ON CONFLICT (name) DO NOTHING
+                RETURNING name, last_updated_at, last_item_id, created_at, acknowledged_at
+            )
+            SELECT * FROM registered
+            UNION ALL
+            SELECT name, last_updated_at, last_item_id, created_at, acknowledged_at
+            FROM export_bookmarks WHERE name = $1
+            "#,
+        )
+        .bind(name)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        Ok(Self::row_to_export_bookmark(&row))
+    }
+
+    #[instrument(skip(self))]
+    async fn acknowledge_export_bookmark(
+        &self,
+        name: &str,
+        position: &ItemPosition,
+    ) -> Result<ExportBookmark, ItemError> {
+        let row = sqlx::query(
+            r#"
+            UPDATE export_bookmarks
+            SET last_updated_at = $2, last_item_id = $3, acknowledged_at = NOW()
+            WHERE name = $1
+              AND (last_updated_at IS NULL OR (last_updated_at, last_item_id) < ($2, $3))
+            RETURNING name, last_updated_at, last_item_id, created_at, acknowledged_at
+            "#,
+        )
+        .bind(name)
+        .bind(position.updated_at)
+        .bind(&position.item_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        if let Some(row) = row {
+            return Ok(Self::row_to_export_bookmark(&row));
+        }
+        // Not moved: either unknown or already at or past `position`
+        let row = sqlx::query(
+            "SELECT name, last_updated_at, last_item_id, created_at, acknowledged_at \
+             FROM export_bookmarks WHERE name = $1",
+        )
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?
+        .ok_or_else(|| ItemError::NotFound(format!("export bookmark {}", name)))?;
+        Ok(Self::row_to_export_bookmark(&row))
+    }
+
+    #[instrument(skip(self))]
+    async fn find_item_by_hash(&self, hash: &str) -> Result<Option<Item>, ItemError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, hash, name, description, content, metadata,
+                   blockchain_status, blockchain_signature, blockchain_retry_count,
+                   blockchain_last_error, blockchain_next_retry_at,
+                   created_at, updated_at, deleted_at, tenant_id, version, content_key,
+                   content_type, content_length
+            FROM items
+            WHERE hash = $1 AND tenant_id = $2 AND deleted_at IS NULL
+            ORDER BY created_at, id
+            LIMIT 1
+            "#,
+        )
+        .bind(hash)
+        .bind(TenantScope::for_new_item())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        self.open_item_row(row).await
+    }
+
+    #[instrument(skip(self))]
+    async fn search_items(&self, query: &str, limit: i64) -> Result<Vec<ItemSearchHit>, ItemError> {
+        let limit = limit.clamp(1, 100);
+        let tenant = TenantScope::current();
+        // websearch_to_tsquery never fails on user input, unlike to_tsquery
+        let rows = self
+            .read(|pool| {
+                sqlx::query(
+                    r#"
+                    SELECT id, hash, name, description, content, metadata,
+                           blockchain_status, blockchain_signature, blockchain_retry_count,
+                           blockchain_last_error, blockchain_next_retry_at,
+                           created_at, updated_at, deleted_at, tenant_id, version, content_key,
+                           content_type, content_length,
+                           ts_rank(search_vector, query) AS rank,
+                           ts_headline('english',
+                                       CASE WHEN content LIKE 'enc:v1:%' THEN coalesce(description, '')
+                                            ELSE content END,
+                                       query, 'MaxFragments=1, MaxWords=35, MinWords=15') AS snippet
+                    FROM items, websearch_to_tsquery('english', $1) AS query
+                    WHERE search_vector @@ query AND deleted_at IS NULL
+                      AND ($3::text IS NULL OR tenant_id = $3)
+                    ORDER BY rank DESC, id
+                    LIMIT $2
+                    "#,
+                )
+                .bind(query)
+                .bind(limit)
+                .bind(tenant.clone())
+                .fetch_all(pool)
+            })
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+
+        let mut hits = Vec::with_capacity(rows.len());
+        for row in &rows {
+            hits.push(ItemSearchHit {
+                item: self.cipher.open(Self::row_to_item(row)?).await?,
+                rank: row.get("rank"),
+                snippet: row.get("snippet"),
+            });
+        }
+        Ok(hits)
+    }
+
+    #[instrument(skip(self, data), fields(item_name = %data.name))]
+    async fn update_item(
+        &self,
+        id: &str,
+        data: &CreateItemRequest,
+        expected_version: i64,
+    ) -> Result<Item, ItemError> {
+        let metadata_json = data
+            .metadata
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|_| ItemError::RepositoryFailure)?;
+        let content = self.cipher.seal(data.stored_content()).await?;
+
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+        let row = sqlx::query(
+            r#"
+            UPDATE items
+            SET hash = $1,
+                name = $2,
+                description = $3,
+                content = $4,
+                metadata = $5,
+                version = version + 1,
+                updated_at = $6,
+                content_key = $10,
+                content_type = $11,
+                content_length = $12
+            WHERE id = $7 AND deleted_at IS NULL AND ($8::text IS NULL OR tenant_id = $8)
+              AND version = $9
+            RETURNING id, hash, name, description, content, metadata,
+                      blockchain_status, blockchain_signature, blockchain_retry_count,
+                      blockchain_last_error, blockchain_next_retry_at,
+                      created_at, updated_at, deleted_at, tenant_id, version, content_key,
+                      content_type, content_length
+            "#,
+        )
+        .bind(ContentHasher::hash_request(data))
+        .bind(&data.name)
+        .bind(&data.description)
+        .bind(content.as_ref())
+        .bind(&metadata_json)
+        .bind(Utc::now())
+        .bind(id)
+        .bind(TenantScope::current())
+        .bind(expected_version)
+        .bind(&data.content_key)
+        .bind(data.content_type())
+        .bind(data.content.len() as i64)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        if let Some(row) = row {
+            Self::replace_item_tags(&mut tx, id, data.tags()).await?;
+            tx.commit().await.map_err(map_sqlx_to_item_error)?;
+            return self.cipher.open(Self::row_to_item(&row)?).await;
+        }
+        drop(tx);
+        // Nothing matched: either the item is gone or its version moved on
+        match self.get_item(id).await? {
+            Some(item) if !item.is_deleted() => Err(ItemError::Conflict {
+                id: id.to_string(),
+                expected: expected_version,
+                current: item.version,
+            }),
+            _ => Err(ItemError::NotFound(id.to_string())),
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn soft_delete_item(&self, id: &str) -> Result<Option<Item>, ItemError> {
+        let now = Utc::now();
+        let row = sqlx::query(
+            r#"
+            UPDATE items
+            SET deleted_at = $1,
+                updated_at = $1
+            WHERE id = $2 AND deleted_at IS NULL AND ($3::text IS NULL OR tenant_id = $3)
+            RETURNING id, hash, name, description, content, metadata,
+                      blockchain_status, blockchain_signature, blockchain_retry_count,
+                      blockchain_last_error, blockchain_next_retry_at,
+                      created_at, updated_at, deleted_at, tenant_id, version, content_key,
+                      content_type, content_length
+            "#,
+        )
+        .bind(now)
+        .bind(id)
+        .bind(TenantScope::current())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        self.open_item_row(row).await
+    }
+
+    #[instrument(skip(self))]
+    async fn purge_deleted_items(&self, deleted_before: DateTime<Utc>) -> Result<u64, ItemError> {
+        let result =
+            sqlx::query("DELETE FROM items WHERE deleted_at IS NOT NULL AND deleted_at < $1")
+                .bind(deleted_before)
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlx_to_item_error)?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip(self))]
+    async fn reencrypt_content(
+        &self,
+        after: Option<&str>,
+        limit: i64,
+    ) -> Result<ReencryptedBatch, ItemError> {
+        self.cipher.ensure_enabled()?;
+        let rows = sqlx::query(
+            "SELECT id, content FROM items WHERE ($1::text IS NULL OR id > $1) ORDER BY id LIMIT $2",
+        )
+        .bind(after)
+        .bind(limit.clamp(1, 1000))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        let mut batch = ReencryptedBatch {
+            scanned: rows.len() as u64,
+            last_id: rows.last().map(|row| row.get("id")),
+            ..ReencryptedBatch::default()
+        };
+        for row in &rows {
+            let stored: &str = row.get("content");
+            let Some(content) = self.cipher.reencrypt(stored).await? else {
+                continue;
+            };
+            // Skipped when a write replaced the content in the meantime
+            let result =
+                sqlx::query("UPDATE items SET content = $2 WHERE id = $1 AND content = $3")
+                    .bind(row.get::<&str, _>("id"))
+                    .bind(&content)
+                    .bind(stored)
+                    .execute(&self.pool)
+                    .await
+                    .map_err(map_sqlx_to_item_error)?;
+            batch.reencrypted += result.rows_affected();
+        }
+        Ok(batch)
+    }
+}
+
+#[async_trait]
+impl SubmissionRepository for PostgresClient {
+    #[instrument(skip(self))]
+    async fn update_blockchain_status(
+        &self,
+        id: &str,
+        status: BlockchainStatus,
+        signature: Option<&str>,
+        error: Option<&str>,
+        next_retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), ItemError> {
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+        let previous = Self::lock_item_status(&mut tx, id).await?;
+
+        let stored_signature: Option<Option<String>> = sqlx::query_scalar(
+            r#"
+            UPDATE items 
+            SET blockchain_status = $1,
+                blockchain_signature = COALESCE($2, blockchain_signature),
+                blockchain_last_error = $3,
+                blockchain_next_retry_at = $4,
+                updated_at = $5
+            WHERE id = $6 AND ($7::text IS NULL OR tenant_id = $7)
+            RETURNING blockchain_signature
+            "#,
+        )
+        .bind(status.as_str())
+        .bind(signature)
+        .bind(error)
+        .bind(next_retry_at)
+        .bind(now)
+        .bind(id)
+        .bind(TenantScope::current())
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        Self::append_status_event(
+            &mut tx,
+            id,
+            previous,
+            status,
+            stored_signature.flatten().as_deref(),
+            error,
+        )
+        .await?;
+        tx.commit().await.map_err(map_sqlx_to_item_error)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self, payload))]
+    async fn enqueue_solana_outbox_for_item(
+        &self,
+        item_id: &str,
+        payload: &SolanaOutboxPayload,
+    ) -> Result<Item, ItemError> {
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+        let item =
+            Self::enqueue_outbox_entry(&mut tx, &self.cipher, item_id, payload, None).await?;
+        tx.commit().await.map_err(map_sqlx_to_item_error)?;
+
+        Ok(item)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_pending_blockchain_items(
+        &self,
+        limit: i64,
+        claim_ttl: Duration,
+    ) -> Result<Vec<Item>, ItemError> {
+        let now = Utc::now();
+        let claimed_until = now + chrono::Duration::from_std(claim_ttl).unwrap_or_default();
+        let rows = sqlx::query(
+            r#"
+            WITH candidate AS (
+                SELECT id
+                FROM items
+                WHERE blockchain_status = 'pending_submission'
+                  AND (blockchain_next_retry_at IS NULL OR blockchain_next_retry_at <= $1)
+                  AND blockchain_retry_count < 10
+                  AND ($3::text IS NULL OR tenant_id = $3)
+                ORDER BY blockchain_next_retry_at ASC NULLS FIRST, created_at ASC
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE items
+            SET updated_at = $1,
+                blockchain_next_retry_at = $4
+            FROM candidate
+            WHERE items.id = candidate.id
+            RETURNING items.id, items.hash, items.name, items.description, items.content, items.metadata,
+                      items.blockchain_status, items.blockchain_signature, items.blockchain_retry_count,
+                      items.blockchain_last_error, items.blockchain_next_retry_at,
+                      items.created_at, items.updated_at, items.deleted_at, items.tenant_id,
+                      items.version, items.content_key, items.content_type, items.content_length
+            "#,
+        )
+        .bind(now)
+        .bind(limit)
+        .bind(TenantScope::current())
+        .bind(claimed_until)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        let items = rows
+            .iter()
+            .map(Self::row_to_item)
+            .collect::<Result<Vec<_>, _>>()?;
+        self.cipher.open_all(items).await
+    }
+
+    #[instrument(skip(self))]
+    async fn increment_retry_count(&self, id: &str) -> Result<i32, ItemError> {
+        let row = sqlx::query(
+            r#"
+            UPDATE items 
+            SET blockchain_retry_count = blockchain_retry_count + 1,
+                updated_at = NOW()
+            WHERE id = $1 AND ($2::text IS NULL OR tenant_id = $2)
+            RETURNING blockchain_retry_count
+            "#,
+        )
+        .bind(id)
+        .bind(TenantScope::current())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        Ok(row.get("blockchain_retry_count"))
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for PostgresClient {
+    #[instrument(skip(self))]
+    async fn health_check(&self) -> Result<(), HealthCheckError> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map_err(|_| HealthCheckError::DatabaseUnavailable)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn claim_pending_solana_outbox(
+        &self,
+        limit: i64,
+        claim_ttl: Duration,
+    ) -> Result<Vec<SolanaOutboxEntry>, ItemError> {
+        let now = Utc::now();
+        // Entries stuck in `processing` past the claim belong to a crashed worker
+        let stale_before = now - chrono::Duration::from_std(claim_ttl).unwrap_or_default();
+        let rows = sqlx::query(
+            r#"
+            WITH candidate AS (
+                SELECT id
+                FROM solana_outbox
+                WHERE (
+                    status = 'pending'
+                    OR (status = 'processing' AND updated_at < $3)
+                )
+                  AND (next_retry_at IS NULL OR next_retry_at <= $1)
+                ORDER BY created_at ASC
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE solana_outbox o
+            SET status = 'processing',
+                updated_at = NOW()
+            FROM candidate
+            WHERE o.id = candidate.id
+            RETURNING o.id, o.aggregate_id, o.payload, o.status, o.retry_count, o.attempt_blockhash, o.created_at
+            "#,
+        )
+        .bind(now)
+        .bind(limit)
+        .bind(stale_before)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        rows.iter().map(Self::row_to_outbox).collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn complete_solana_outbox(
+        &self,
+        outbox_id: &str,
+        item_id: &str,
+        signature: &str,
+    ) -> Result<(), ItemError> {
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+
+        sqlx::query(
+            r#"
+            UPDATE solana_outbox
+            SET status = $1,
+                updated_at = NOW()
+            WHERE id = $2::uuid
+            "#,
+        )
+        .bind(OutboxStatus::Completed.as_str())
+        .bind(outbox_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        let previous = Self::lock_item_status(&mut tx, item_id).await?;
+        sqlx::query(
+            r#"
+            UPDATE items
+            SET blockchain_status = $1,
+                blockchain_signature = $2,
+                blockchain_last_error = NULL,
+                blockchain_next_retry_at = NULL,
+                updated_at = $3
+            WHERE id = $4
+            "#,
+        )
+        .bind(BlockchainStatus::Submitted.as_str())
+        .bind(signature)
+        .bind(now)
+        .bind(item_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        Self::append_status_event(
+            &mut tx,
+            item_id,
+            previous,
+            BlockchainStatus::Submitted,
+            Some(signature),
+            None,
+        )
+        .await?;
+
+        tx.commit().await.map_err(map_sqlx_to_item_error)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn fail_solana_outbox(
+        &self,
+        outbox_id: &str,
+        item_id: &str,
+        retry_count: i32,
+        outbox_status: OutboxStatus,
+        item_status: BlockchainStatus,
+        error: &str,
+        next_retry_at: Option<DateTime<Utc>>,
+        attempt_blockhash: Option<Option<&str>>,
+    ) -> Result<(), ItemError> {
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+        Self::fail_outbox_entry(
+            &mut tx,
+            outbox_id,
+            item_id,
+            retry_count,
+            outbox_status,
+            item_status,
+            error,
+            next_retry_at,
+            attempt_blockhash,
+        )
+        .await?;
+        tx.commit().await.map_err(map_sqlx_to_item_error)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn save_attempt_blockhash(
+        &self,
+        outbox_id: &str,
+        blockhash: Option<&str>,
+    ) -> Result<(), ItemError> {
+        sqlx::query(
+            r#"
+            UPDATE solana_outbox
+            SET attempt_blockhash = $1
+            WHERE id = $2::uuid
+            "#,
+        )
+        .bind(blockhash)
+        .bind(outbox_id)
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn dead_letter_solana_outbox(
+        &self,
+        outbox_id: &str,
+        item_id: &str,
+        retry_count: i32,
+        error: &str,
+        attempt_blockhash: Option<Option<&str>>,
+    ) -> Result<FailedSubmission, ItemError> {
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+        Self::fail_outbox_entry(
+            &mut tx,
+            outbox_id,
+            item_id,
+            retry_count,
+            OutboxStatus::Failed,
+            BlockchainStatus::Failed,
+            error,
+            None,
+            attempt_blockhash,
+        )
+        .await?;
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO failed_submissions (id, item_id, outbox_id, hash, payload, retry_count,
+                                            last_error, attempt_blockhash, failed_at)
+            SELECT $1, aggregate_id, id, payload->>'hash', payload, retry_count,
+                   $2, attempt_blockhash, NOW()
+            FROM solana_outbox
+            WHERE id = $3::uuid
+            RETURNING id, item_id, outbox_id, hash, retry_count, last_error, failed_at, requeued_at
+            "#,
+        )
+        .bind(format!("dlq_{}", generate_id("dlq", self.ids.as_ref())))
+        .bind(error)
+        .bind(outbox_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(map_sqlx_to_item_error)?
+        .ok_or_else(|| ItemError::NotFound(outbox_id.to_string()))?;
+
+        tx.commit().await.map_err(map_sqlx_to_item_error)?;
+
+        Ok(Self::row_to_failed_submission(&row))
+    }
+
+    #[instrument(skip(self))]
+    async fn list_failed_submissions(
+        &self,
+        limit: i64,
+        include_requeued: bool,
+    ) -> Result<Vec<FailedSubmission>, ItemError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, item_id, outbox_id, hash, retry_count, last_error, failed_at, requeued_at
+            FROM failed_submissions
+            WHERE $1 OR requeued_at IS NULL
+            ORDER BY failed_at DESC, id DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(include_requeued)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        Ok(rows.iter().map(Self::row_to_failed_submission).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn requeue_failed_submission(&self, id: &str) -> Result<Item, ItemError> {
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT item_id, payload, attempt_blockhash, requeued_at
+            FROM failed_submissions
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(map_sqlx_to_item_error)?
+        .ok_or_else(|| ItemError::NotFound(id.to_string()))?;
+
+        if row.get::<Option<DateTime<Utc>>, _>("requeued_at").is_some() {
+            return Err(ItemError::InvalidState(
+                "Submission was already requeued".to_string(),
+            ));
+        }
+        let item_id: String = row.get("item_id");
+        let payload: Json<SolanaOutboxPayload> = row
+            .try_get("payload")
+            .map_err(|_| ItemError::RepositoryFailure)?;
+        let attempt_blockhash: Option<String> = row.get("attempt_blockhash");
+
+        match Self::lock_item_status(&mut tx, &item_id).await? {
+            Some(BlockchainStatus::Failed) => {}
+            Some(_) => {
+                return Err(ItemError::InvalidState(
+                    "Item is no longer failed".to_string(),
+                ));
+            }
+            None => return Err(ItemError::NotFound(item_id)),
+        }
+
+        let item = Self::enqueue_outbox_entry(
+            &mut tx,
+            &self.cipher,
+            &item_id,
+            &payload.0,
+            attempt_blockhash.as_deref(),
+        )
+        .await?;
+
+        sqlx::query("UPDATE failed_submissions SET requeued_at = NOW() WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+
+        tx.commit().await.map_err(map_sqlx_to_item_error)?;
+
+        Ok(item)
+    }
+
+    #[instrument(skip(self))]
+    async fn queue_depth(&self) -> Result<QueueDepth, ItemError> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE status = 'pending') AS pending,
+                COUNT(*) FILTER (
+                    WHERE status = 'pending' AND (next_retry_at IS NULL OR next_retry_at <= NOW())
+                ) AS due,
+                COUNT(*) FILTER (WHERE status = 'processing') AS processing,
+                (SELECT COUNT(*) FROM failed_submissions WHERE requeued_at IS NULL) AS dead_lettered
+            FROM solana_outbox
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        let oldest_pending_at = sqlx::query_scalar(
+            "SELECT created_at FROM solana_outbox WHERE status = 'pending' ORDER BY created_at LIMIT 1",
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        Ok(QueueDepth {
+            pending: row.get("pending"),
+            due: row.get("due"),
+            processing: row.get("processing"),
+            dead_lettered: row.get("dead_lettered"),
+            oldest_pending_at,
+        })
+    }
+
+    #[instrument(skip(self, attempt))]
+    async fn record_submission_attempt(
+        &self,
+        item_id: &str,
+        attempt: &SubmissionAttempt,
+    ) -> Result<(), ItemError> {
+        let attempt = serde_json::to_value(std::slice::from_ref(attempt))
+            .map_err(|_| ItemError::RepositoryFailure)?;
+        sqlx::query(
+            "UPDATE items SET submission_attempts = submission_attempts || $1 \
+             WHERE id = $2 AND ($3::text IS NULL OR tenant_id = $3)",
+        )
+        .bind(attempt)
+        .bind(item_id)
+        .bind(TenantScope::current())
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_submission_attempts(
+        &self,
+        item_id: &str,
+    ) -> Result<Vec<SubmissionAttempt>, ItemError> {
+        let attempts: Option<serde_json::Value> = sqlx::query_scalar(
+            "SELECT submission_attempts FROM items \
+                 WHERE id = $1 AND ($2::text IS NULL OR tenant_id = $2)",
+        )
+        .bind(item_id)
+        .bind(TenantScope::current())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        attempts
+            .map(serde_json::from_value)
+            .transpose()
+            .map(Option::unwrap_or_default)
+            .map_err(|_| ItemError::RepositoryFailure)
+    }
+}
+
+#[async_trait]
+impl ApiKeyStore for PostgresClient {
+    #[instrument(skip(self, key_hash))]
+    async fn create_api_key(
+        &self,
+        name: &str,
+        key_hash: &str,
+        scopes: &[ApiKeyScope],
+        tenant_id: &str,
+        quota: ApiKeyQuota,
+    ) -> Result<ApiKey, ApiKeyError> {
+        let id = format!("key_{}", generate_id("key", self.ids.as_ref()));
+        let scopes: Vec<String> = scopes.iter().map(|s| s.as_str().to_string()).collect();
+        let row = sqlx::query(
+            r#"
+            INSERT INTO api_keys
+                (id, name, key_hash, scopes, tenant_id, daily_quota, monthly_quota, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
+            RETURNING id, name, scopes, tenant_id, created_at, revoked_at, daily_quota, monthly_quota
+            "#,
+        )
+        .bind(&id)
+        .bind(name)
+        .bind(key_hash)
+        .bind(&scopes)
+        .bind(tenant_id)
+        .bind(quota.daily.map(quota_value))
+        .bind(quota.monthly.map(quota_value))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_to_api_key_error)?;
+        Ok(Self::row_to_api_key(&row))
+    }
+
+    #[instrument(skip(self, key_hash))]
+    async fn find_api_key_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>, ApiKeyError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, name, scopes, tenant_id, created_at, revoked_at, daily_quota, monthly_quota
+            FROM api_keys
+            WHERE key_hash = $1
+            "#,
+        )
+        .bind(key_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_to_api_key_error)?;
+        Ok(row.as_ref().map(Self::row_to_api_key))
+    }
+
+    #[instrument(skip(self))]
+    async fn list_api_keys(&self) -> Result<Vec<ApiKey>, ApiKeyError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, name, scopes, tenant_id, created_at, revoked_at, daily_quota, monthly_quota
+            FROM api_keys
+            ORDER BY created_at DESC, id DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_to_api_key_error)?;
+        Ok(rows.iter().map(Self::row_to_api_key).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn revoke_api_key(&self, id: &str) -> Result<ApiKey, ApiKeyError> {
+        let row = sqlx::query(
+            r#"
+            UPDATE api_keys
+            SET revoked_at = COALESCE(revoked_at, NOW())
+            WHERE id = $1
+            RETURNING id, name, scopes, tenant_id, created_at, revoked_at, daily_quota, monthly_quota
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_to_api_key_error)?;
+        row.as_ref()
+            .map(Self::row_to_api_key)
+            .ok_or_else(|| ApiKeyError::NotFound(id.to_string()))
+    }
+}
+
+#[async_trait]
+impl WebhookDeliveryLog for PostgresClient {
+    #[instrument(skip(self, delivery), fields(event_id = %delivery.event_id))]
+    async fn record_webhook_delivery(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> Result<(), NotificationError> {
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_deliveries
+                (event_id, event, item_id, url, attempt, response_status, error, succeeded, attempted_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(&delivery.event_id)
+        .bind(&delivery.event)
+        .bind(&delivery.item_id)
+        .bind(&delivery.url)
+        .bind(i32::try_from(delivery.attempt).unwrap_or(i32::MAX))
+        .bind(delivery.response_status.map(|s| s as i16))
+        .bind(&delivery.error)
+        .bind(delivery.succeeded)
+        .bind(delivery.attempted_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|_| NotificationError::RepositoryFailure)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_webhook_deliveries(
+        &self,
+        limit: i64,
+        before: Option<i64>,
+        range: &TimeRange,
+    ) -> Result<PaginatedResponse<WebhookDelivery>, NotificationError> {
+        let limit = limit.clamp(1, 100);
+        let deliveries = self
+            .log_page(
+                &WEBHOOK_DELIVERIES_LOG,
+                limit,
+                before,
+                range,
+                &[],
+                Self::row_to_delivery,
+            )
+            .await?;
+        Ok(PaginatedResponse::from_lookahead(deliveries, limit, |d| {
+            d.id.to_string()
+        }))
+    }
+
+    #[instrument(skip(self))]
+    async fn item_deliveries(
+        &self,
+        item_id: &str,
+    ) -> Result<Vec<WebhookDelivery>, NotificationError> {
+        self.item_log(&WEBHOOK_DELIVERIES_LOG, item_id, Self::row_to_delivery)
+            .await
+    }
+}
+
+#[async_trait]
+impl JobStore for PostgresClient {
+    #[instrument(skip(self))]
+    async fn create_job(&self, kind: &str) -> Result<Job, JobError> {
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO jobs (id, kind, status, created_at, updated_at)
+            VALUES ($1, $2, $3, NOW(), NOW())
+            RETURNING {JOB_COLUMNS}
+            "#
+        ))
+        .bind(format!("job_{}", generate_id("job", self.ids.as_ref())))
+        .bind(kind)
+        .bind(JobStatus::Queued.as_str())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|_| JobError::RepositoryFailure)?;
+        Self::row_to_job(&row)
+    }
+
+    #[instrument(skip(self))]
+    async fn update_job_progress(
+        &self,
+        id: &str,
+        processed: i64,
+        failed: i64,
+    ) -> Result<(), JobError> {
+        let updated = sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = $1, processed = $2, failed = $3, updated_at = NOW()
+            WHERE id = $4
+            "#,
+        )
+        .bind(JobStatus::Running.as_str())
+        .bind(processed)
+        .bind(failed)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|_| JobError::RepositoryFailure)?;
+        if updated.rows_affected() == 0 {
+            return Err(JobError::NotFound(id.to_string()));
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self, result))]
+    async fn finish_job(
+        &self,
+        id: &str,
+        status: JobStatus,
+        result: Option<&serde_json::Value>,
+        error: Option<&str>,
+    ) -> Result<Job, JobError> {
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE jobs
+            SET status = $1, result = $2, error = $3, updated_at = NOW(), finished_at = NOW()
+            WHERE id = $4
+            RETURNING {JOB_COLUMNS}
+            "#
+        ))
+        .bind(status.as_str())
+        .bind(result)
+        .bind(error)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|_| JobError::RepositoryFailure)?
+        .ok_or_else(|| JobError::NotFound(id.to_string()))?;
+        Self::row_to_job(&row)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_job(&self, id: &str) -> Result<Option<Job>, JobError> {
+        let row = sqlx::query(&format!("SELECT {JOB_COLUMNS} FROM jobs WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|_| JobError::RepositoryFailure)?;
+        row.as_ref().map(Self::row_to_job).transpose()
+    }
+}
+
+#[async_trait]
+impl LeaderElection for PostgresClient {
+    #[instrument(skip(self))]
+    async fn try_acquire_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> Result<bool, ItemError> {
+        // Expiry is judged by the database clock, which every instance shares
+        let leader: Option<String> = sqlx::query_scalar(
+            r#"
+            INSERT INTO leader_leases (name, holder, expires_at)
+            VALUES ($1, $2, NOW() + make_interval(secs => $3))
+            ON CONFLICT (name)
+            DO UPDATE SET holder = excluded.holder, expires_at = excluded.expires_at
+            WHERE leader_leases.holder = excluded.holder OR leader_leases.expires_at <= NOW()
+            RETURNING holder
+            "#,
+        )
+        .bind(name)
+        .bind(holder)
+        .bind(ttl.as_secs_f64())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|_| ItemError::RepositoryFailure)?;
+        Ok(leader.is_some())
+    }
+
+    #[instrument(skip(self))]
+    async fn release_lease(&self, name: &str, holder: &str) -> Result<(), ItemError> {
+        sqlx::query("DELETE FROM leader_leases WHERE name = $1 AND holder = $2")
+            .bind(name)
+            .bind(holder)
+            .execute(&self.pool)
+            .await
+            .map_err(|_| ItemError::RepositoryFailure)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AuditLogger for PostgresClient {
+    #[instrument(skip(self, entry), fields(action = %entry.action))]
+    async fn record_audit_entry(&self, entry: &AuditEntry) -> Result<(), NotificationError> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (principal, action, resource_id, request_id, occurred_at, diff)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(&entry.principal)
+        .bind(&entry.action)
+        .bind(&entry.resource_id)
+        .bind(&entry.request_id)
+        .bind(entry.occurred_at)
+        .bind(&entry.diff)
+        .execute(&self.pool)
+        .await
+        .map_err(|_| NotificationError::RepositoryFailure)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_audit_entries(
+        &self,
+        limit: i64,
+        before: Option<i64>,
+        filter: &AuditFilter,
+    ) -> Result<PaginatedResponse<AuditEntry>, NotificationError> {
+        let limit = limit.clamp(1, 100);
+        let entries = self
+            .log_page(
+                &AUDIT_LOG,
+                limit,
+                before,
+                &filter.range,
+                &audit_filter_columns(filter),
+                Self::row_to_audit_entry,
+            )
+            .await?;
+        Ok(PaginatedResponse::from_lookahead(entries, limit, |e| {
+            e.id.to_string()
+        }))
+    }
+}
+
+#[async_trait]
+impl UsageLedger for PostgresClient {
+    #[instrument(skip(self))]
+    async fn record_request(
+        &self,
+        key_id: &str,
+        day: NaiveDate,
+    ) -> Result<QuotaUsage, ApiKeyError> {
+        // The CTE's snapshot does not see today's row, so the month adds it separately
+        let row = sqlx::query(
+            r#"
+            WITH counted AS (
+                INSERT INTO api_key_usage (key_id, day, requests)
+                VALUES ($1, $2, 1)
+                ON CONFLICT (key_id, day)
+                DO UPDATE SET requests = api_key_usage.requests + 1
+                RETURNING requests
+            )
+            SELECT counted.requests AS daily,
+                   (counted.requests + COALESCE((
+                       SELECT SUM(requests) FROM api_key_usage
+                       WHERE key_id = $1 AND day >= $3 AND day < $2
+                   ), 0))::BIGINT AS monthly
+            FROM counted
             "#,
         )
-        .bind(BlockchainStatus::PendingSubmission.as_str())
-        .bind(now)
-        .bind(item_id)
-        .fetch_one(&mut *tx)
+        .bind(key_id)
+        .bind(day)
+        .bind(month_start(day))
+        .fetch_one(&self.pool)
         .await
-        .map_err(map_sqlx_to_item_error)?;
-
-        tx.commit().await.map_err(map_sqlx_to_item_error)?;
-
-        Self::row_to_item(&row)
+        .map_err(map_sqlx_to_api_key_error)?;
+        Ok(QuotaUsage {
+            daily: u64::try_from(row.get::<i64, _>("daily")).unwrap_or(0),
+            monthly: u64::try_from(row.get::<i64, _>("monthly")).unwrap_or(0),
+        })
     }
 
     #[instrument(skip(self))]
-    async fn get_pending_blockchain_items(&self, limit: i64) -> Result<Vec<Item>, ItemError> {
-        let now = Utc::now();
+    async fn usage_on(&self, day: NaiveDate) -> Result<Vec<KeyUsage>, ApiKeyError> {
         let rows = sqlx::query(
             r#"
-            WITH candidate AS (
-                SELECT id
-                FROM items
-                WHERE blockchain_status = 'pending_submission'
-                  AND (blockchain_next_retry_at IS NULL OR blockchain_next_retry_at <= $1)
-                  AND blockchain_retry_count < 10
-                ORDER BY blockchain_next_retry_at ASC NULLS FIRST, created_at ASC
-                LIMIT $2
-                FOR UPDATE SKIP LOCKED
-            )
-            UPDATE items
-            SET updated_at = $1
-            FROM candidate
-            WHERE items.id = candidate.id
-            RETURNING items.id, items.hash, items.name, items.description, items.content, items.metadata,
-                      items.blockchain_status, items.blockchain_signature, items.blockchain_retry_count,
-                      items.blockchain_last_error, items.blockchain_next_retry_at,
-                      items.created_at, items.updated_at
+            SELECT u.key_id, k.name, k.daily_quota, k.monthly_quota,
+                   SUM(CASE WHEN u.day = $1 THEN u.requests ELSE 0 END)::BIGINT AS daily_requests,
+                   SUM(u.requests)::BIGINT AS monthly_requests
+            FROM api_key_usage u
+            LEFT JOIN api_keys k ON k.id = u.key_id
+            WHERE u.day >= $2 AND u.day <= $1
+            GROUP BY u.key_id, k.name, k.daily_quota, k.monthly_quota
+            ORDER BY u.key_id
             "#,
         )
-        .bind(now)
-        .bind(limit)
+        .bind(day)
+        .bind(month_start(day))
         .fetch_all(&self.pool)
         .await
-        .map_err(map_sqlx_to_item_error)?;
+        .map_err(map_sqlx_to_api_key_error)?;
+        Ok(rows.iter().map(Self::row_to_key_usage).collect())
+    }
+}
 
-        rows.iter().map(Self::row_to_item).collect()
+#[async_trait]
+impl SpendLedger for PostgresClient {
+    #[instrument(skip(self))]
+    async fn spent_on(&self, signer: &str, day: NaiveDate) -> Result<u64, ItemError> {
+        let spent: Option<i64> =
+            sqlx::query_scalar("SELECT spent FROM blockchain_spend WHERE signer = $1 AND day = $2")
+                .bind(signer)
+                .bind(day)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|_| ItemError::RepositoryFailure)?;
+        Ok(spent.map_or(0, |spent| u64::try_from(spent).unwrap_or(0)))
     }
 
     #[instrument(skip(self))]
-    async fn increment_retry_count(&self, id: &str) -> Result<i32, ItemError> {
-        let row = sqlx::query(
+    async fn record_spend(
+        &self,
+        signer: &str,
+        day: NaiveDate,
+        amount: u64,
+    ) -> Result<u64, ItemError> {
+        let spent: i64 = sqlx::query_scalar(
             r#"
-            UPDATE items 
-            SET blockchain_retry_count = blockchain_retry_count + 1,
-                updated_at = NOW()
-            WHERE id = $1
-            RETURNING blockchain_retry_count
+            INSERT INTO blockchain_spend (signer, day, spent, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (signer, day)
+            DO UPDATE SET spent = blockchain_spend.spent + excluded.spent, updated_at = excluded.updated_at
+            RETURNING spent
             "#,
         )
-        .bind(id)
+        .bind(signer)
+        .bind(day)
+        .bind(i64::try_from(amount).unwrap_or(i64::MAX))
         .fetch_one(&self.pool)
         .await
-        .map_err(map_sqlx_to_item_error)?;
-
-        Ok(row.get("blockchain_retry_count"))
+        .map_err(|_| ItemError::RepositoryFailure)?;
+        Ok(u64::try_from(spent).unwrap_or(0))
     }
 }
 
 #[async_trait]
-impl OutboxRepository for PostgresClient {
-    #[instrument(skip(self))]
-    async fn health_check(&self) -> Result<(), HealthCheckError> {
-        sqlx::query("SELECT 1")
-            .execute(&self.pool)
-            .await
-            .map_err(|_| HealthCheckError::DatabaseUnavailable)?;
-        Ok(())
-    }
-
+impl EventLog for PostgresClient {
     #[instrument(skip(self))]
-    async fn claim_pending_solana_outbox(
+    async fn events_after(
         &self,
+        position: i64,
         limit: i64,
-    ) -> Result<Vec<SolanaOutboxEntry>, ItemError> {
-        let now = Utc::now();
+    ) -> Result<Vec<ItemStatusEvent>, NotificationError> {
         let rows = sqlx::query(
             r#"
-            WITH candidate AS (
-                SELECT id
-                FROM solana_outbox
-                WHERE (
-                    status = 'pending'
-                    OR (
-                        status = 'processing'
-                        AND updated_at < NOW() - INTERVAL '5 minutes'
-                    )
-                )
-                  AND (next_retry_at IS NULL OR next_retry_at <= $1)
-                ORDER BY created_at ASC
-                LIMIT $2
-                FOR UPDATE SKIP LOCKED
-            )
-            UPDATE solana_outbox o
-            SET status = 'processing',
-                updated_at = NOW()
-            FROM candidate
-            WHERE o.id = candidate.id
-            RETURNING o.id, o.aggregate_id, o.payload, o.status, o.retry_count, o.attempt_blockhash, o.created_at
+            SELECT position, id, item_id, sequence, event, status, signature, error, occurred_at
+            FROM item_events
+            WHERE position > $1
+            ORDER BY position
+            LIMIT $2
             "#,
         )
-        .bind(now)
+        .bind(position)
         .bind(limit)
         .fetch_all(&self.pool)
         .await
-        .map_err(map_sqlx_to_item_error)?;
+        .map_err(|_| NotificationError::RepositoryFailure)?;
+        Ok(rows.iter().map(Self::row_to_event).collect())
+    }
 
-        rows.iter().map(Self::row_to_outbox).collect()
+    #[instrument(skip(self))]
+    async fn subscription_cursor(&self, subscription: &str) -> Result<i64, NotificationError> {
+        let position: Option<i64> =
+            sqlx::query_scalar("SELECT last_position FROM webhook_subscriptions WHERE name = $1")
+                .bind(subscription)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|_| NotificationError::RepositoryFailure)?;
+        Ok(position.unwrap_or(0))
     }
 
     #[instrument(skip(self))]
-    async fn complete_solana_outbox(
+    async fn save_subscription_cursor(
         &self,
-        outbox_id: &str,
-        item_id: &str,
-        signature: &str,
-    ) -> Result<(), ItemError> {
-        let now = Utc::now();
-        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
-
+        subscription: &str,
+        position: i64,
+    ) -> Result<(), NotificationError> {
         sqlx::query(
             r#"
-            UPDATE solana_outbox
-            SET status = $1,
+            INSERT INTO webhook_subscriptions (name, last_position, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (name) DO UPDATE
+            SET last_position = GREATEST(webhook_subscriptions.last_position, EXCLUDED.last_position),
                 updated_at = NOW()
-            WHERE id = $2
-            "#,
-        )
-        .bind(OutboxStatus::Completed.as_str())
-        .bind(outbox_id)
-        .execute(&mut *tx)
-        .await
-        .map_err(map_sqlx_to_item_error)?;
-
-        sqlx::query(
-            r#"
-            UPDATE items
-            SET blockchain_status = $1,
-                blockchain_signature = $2,
-                blockchain_last_error = NULL,
-                blockchain_next_retry_at = NULL,
-                updated_at = $3
-            WHERE id = $4
             "#,
         )
-        .bind(BlockchainStatus::Submitted.as_str())
-        .bind(signature)
-        .bind(now)
-        .bind(item_id)
-        .execute(&mut *tx)
+        .bind(subscription)
+        .bind(position)
+        .execute(&self.pool)
         .await
-        .map_err(map_sqlx_to_item_error)?;
-
-        tx.commit().await.map_err(map_sqlx_to_item_error)?;
-
+        .map_err(|_| NotificationError::RepositoryFailure)?;
         Ok(())
     }
 
     #[instrument(skip(self))]
-    async fn fail_solana_outbox(
+    async fn list_events(
         &self,
-        outbox_id: &str,
-        item_id: &str,
-        retry_count: i32,
-        outbox_status: OutboxStatus,
-        item_status: BlockchainStatus,
-        error: &str,
-        next_retry_at: Option<DateTime<Utc>>,
-        attempt_blockhash: Option<Option<&str>>,
-    ) -> Result<(), ItemError> {
-        let now = Utc::now();
-        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
-
-        // Update outbox: status, retry_count, next_retry_at, and optionally attempt_blockhash
-        let set_blockhash = attempt_blockhash.is_some();
-        let blockhash_bind: Option<String> =
-            attempt_blockhash.and_then(|o| o.map(std::string::ToString::to_string));
-
-        if set_blockhash {
-            sqlx::query(
-                r#"
-                UPDATE solana_outbox
-                SET status = $1,
-                    retry_count = $2,
-                    next_retry_at = $3,
-                    attempt_blockhash = $4,
-                    updated_at = NOW()
-                WHERE id = $5
-                "#,
-            )
-            .bind(outbox_status.as_str())
-            .bind(retry_count)
-            .bind(next_retry_at)
-            .bind(blockhash_bind)
-            .bind(outbox_id)
-            .execute(&mut *tx)
-            .await
-            .map_err(map_sqlx_to_item_error)?;
-        } else {
-            sqlx::query(
-                r#"
-                UPDATE solana_outbox
-                SET status = $1,
-                    retry_count = $2,
-                    next_retry_at = $3,
-                    updated_at = NOW()
-                WHERE id = $4
-                "#,
+        limit: i64,
+        before: Option<i64>,
+        range: &TimeRange,
+    ) -> Result<PaginatedResponse<ItemStatusEvent>, NotificationError> {
+        let limit = limit.clamp(1, 100);
+        let events = self
+            .log_page(
+                &ITEM_EVENTS_LOG,
+                limit,
+                before,
+                range,
+                &[],
+                Self::row_to_event,
             )
-            .bind(outbox_status.as_str())
-            .bind(retry_count)
-            .bind(next_retry_at)
-            .bind(outbox_id)
-            .execute(&mut *tx)
+            .await?;
+        Ok(PaginatedResponse::from_lookahead(events, limit, |e| {
+            e.position.to_string()
+        }))
+    }
+
+    #[instrument(skip(self))]
+    async fn item_events(&self, item_id: &str) -> Result<Vec<ItemStatusEvent>, NotificationError> {
+        self.item_log(&ITEM_EVENTS_LOG, item_id, Self::row_to_event)
             .await
-            .map_err(map_sqlx_to_item_error)?;
+    }
+}
+
+#[async_trait]
+impl RequestJournal for PostgresClient {
+    #[instrument(skip(self))]
+    async fn begin_request(
+        &self,
+        key: &str,
+        request_hash: &str,
+        reclaim_before: DateTime<Utc>,
+    ) -> Result<Option<RequestJournalEntry>, RequestJournalError> {
+        // Insert, or take over a same-request entry left `processing` by a crashed attempt
+        let claimed = sqlx::query(
+            r#"
+            INSERT INTO request_journal (key, request_hash, status, created_at, updated_at)
+            VALUES ($1, $2, 'processing', NOW(), NOW())
+            ON CONFLICT (key) DO UPDATE
+            SET updated_at = NOW()
+            WHERE request_journal.status = 'processing'
+              AND request_journal.request_hash = EXCLUDED.request_hash
+              AND request_journal.updated_at < $3
+            RETURNING key
+            "#,
+        )
+        .bind(key)
+        .bind(request_hash)
+        .bind(reclaim_before)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|_| RequestJournalError::RepositoryFailure)?;
+
+        if claimed.is_some() {
+            return Ok(None);
         }
+        self.get_request(key).await
+    }
 
+    #[instrument(skip(self, response_body))]
+    async fn complete_request(
+        &self,
+        key: &str,
+        response_status: u16,
+        response_body: &str,
+    ) -> Result<(), RequestJournalError> {
         sqlx::query(
             r#"
-            UPDATE items
-            SET blockchain_status = $1,
-                blockchain_last_error = $2,
-                blockchain_next_retry_at = $3,
-                blockchain_retry_count = $4,
-                updated_at = $5
-            WHERE id = $6
+            UPDATE request_journal
+            SET status = 'completed',
+                response_status = $1,
+                response_body = $2,
+                updated_at = NOW()
+            WHERE key = $3
             "#,
         )
-        .bind(item_status.as_str())
-        .bind(error)
-        .bind(next_retry_at)
-        .bind(retry_count)
-        .bind(now)
-        .bind(item_id)
-        .execute(&mut *tx)
+        .bind(response_status as i16)
+        .bind(response_body)
+        .bind(key)
+        .execute(&self.pool)
         .await
-        .map_err(map_sqlx_to_item_error)?;
-
-        tx.commit().await.map_err(map_sqlx_to_item_error)?;
+        .map_err(|_| RequestJournalError::RepositoryFailure)?;
+        Ok(())
+    }
 
+    #[instrument(skip(self))]
+    async fn abandon_request(&self, key: &str) -> Result<(), RequestJournalError> {
+        sqlx::query("DELETE FROM request_journal WHERE key = $1 AND status = 'processing'")
+            .bind(key)
+            .execute(&self.pool)
+            .await
+            .map_err(|_| RequestJournalError::RepositoryFailure)?;
         Ok(())
     }
 
     #[instrument(skip(self))]
-    async fn save_attempt_blockhash(
+    async fn get_request(
         &self,
-        outbox_id: &str,
-        blockhash: Option<&str>,
-    ) -> Result<(), ItemError> {
-        sqlx::query(
+        key: &str,
+    ) -> Result<Option<RequestJournalEntry>, RequestJournalError> {
+        let row = sqlx::query(
             r#"
-            UPDATE solana_outbox
-            SET attempt_blockhash = $1
-            WHERE id = $2
+            SELECT key, request_hash, status, response_status, response_body, created_at, updated_at
+            FROM request_journal
+            WHERE key = $1
             "#,
         )
-        .bind(blockhash)
-        .bind(outbox_id)
-        .execute(&self.pool)
+        .bind(key)
+        .fetch_optional(&self.pool)
         .await
-        .map_err(map_sqlx_to_item_error)?;
-        Ok(())
+        .map_err(|_| RequestJournalError::RepositoryFailure)?;
+
+        row.map(|row| {
+            let status: String = row.get("status");
+            let response_status: Option<i16> = row.get("response_status");
+            Ok(RequestJournalEntry {
+                key: row.get("key"),
+                request_hash: row.get("request_hash"),
+                status: status
+                    .parse()
+                    .map_err(|_| RequestJournalError::RepositoryFailure)?,
+                response_status: response_status.map(|s| s as u16),
+                response_body: row.get("response_body"),
+                created_at: row.get("created_at"),
+                updated_at: row.get("updated_at"),
+            })
+        })
+        .transpose()
     }
 }
 
@@ -704,6 +2947,10 @@ mod tests {
         assert_eq!(config.acquire_timeout, Duration::from_secs(3));
         assert_eq!(config.idle_timeout, Duration::from_secs(600));
         assert_eq!(config.max_lifetime, Duration::from_secs(1800));
+        assert_eq!(
+            config.slow_query_threshold,
+            Some(Duration::from_millis(500))
+        );
     }
 
     #[test]
@@ -714,6 +2961,8 @@ mod tests {
             acquire_timeout: Duration::from_secs(10),
             idle_timeout: Duration::from_secs(300),
             max_lifetime: Duration::from_secs(3600),
+            slow_query_threshold: None,
+            metrics_interval: Some(Duration::from_secs(15)),
         };
         assert_eq!(config.max_connections, 20);
         assert_eq!(config.min_connections, 5);
@@ -721,4 +2970,83 @@ mod tests {
         assert_eq!(config.idle_timeout, Duration::from_secs(300));
         assert_eq!(config.max_lifetime, Duration::from_secs(3600));
     }
+
+    #[test]
+    fn test_postgres_config_presets() {
+        let development = PostgresConfig::development();
+        let production = PostgresConfig::production();
+        assert!(development.max_connections < production.max_connections);
+        assert_eq!(development.min_connections, 0);
+        assert!(development.acquire_timeout > production.acquire_timeout);
+        assert!(development.idle_timeout < production.idle_timeout);
+        assert!(development.slow_query_threshold < production.slow_query_threshold);
+        assert_eq!(development.metrics_interval, None);
+        assert_eq!(production.metrics_interval, Some(Duration::from_secs(15)));
+        assert_eq!(
+            production.max_connections,
+            PostgresConfig::default().max_connections
+        );
+    }
+
+    fn from_vars(vars: &[(&str, &str)]) -> PostgresConfig {
+        PostgresConfig::from_vars(|name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn test_postgres_config_from_env_defaults_to_production() {
+        let config = from_vars(&[]);
+        let production = PostgresConfig::production();
+        assert_eq!(config.max_connections, production.max_connections);
+        assert_eq!(config.metrics_interval, production.metrics_interval);
+
+        let config = from_vars(&[("DATABASE_POOL_PRESET", "development")]);
+        assert_eq!(config.max_connections, 5);
+        assert_eq!(config.metrics_interval, None);
+    }
+
+    #[test]
+    fn test_postgres_config_from_env_overrides_fields() {
+        let config = from_vars(&[
+            ("DATABASE_POOL_PRESET", "development"),
+            ("DATABASE_MAX_CONNECTIONS", "8"),
+            ("DATABASE_MIN_CONNECTIONS", "20"),
+            ("DATABASE_ACQUIRE_TIMEOUT_SECS", "not-a-number"),
+            ("DATABASE_MAX_LIFETIME_SECS", "900"),
+            ("DATABASE_SLOW_QUERY_MS", "0"),
+            ("DATABASE_POOL_METRICS_INTERVAL_SECS", "30"),
+        ]);
+        assert_eq!(config.max_connections, 8);
+        // Never more idle connections than the pool may hold
+        assert_eq!(config.min_connections, 8);
+        assert_eq!(config.acquire_timeout, Duration::from_secs(10));
+        assert_eq!(config.idle_timeout, Duration::from_secs(60));
+        assert_eq!(config.max_lifetime, Duration::from_secs(900));
+        assert_eq!(config.slow_query_threshold, None);
+        assert_eq!(config.metrics_interval, Some(Duration::from_secs(30)));
+    }
+
+    #[tokio::test]
+    async fn test_read_replica_is_skipped_after_a_connection_error() {
+        let replica = ReadReplica {
+            pool: {
+                let config = PostgresConfig::default();
+                config.pool_options().connect_lazy_with(
+                    config
+                        .connect_options("postgres://127.0.0.1:1/app")
+                        .unwrap(),
+                )
+            },
+            down_until: Mutex::new(None),
+        };
+        assert!(replica.is_up());
+        assert!(!is_unavailable(&sqlx::Error::RowNotFound));
+        assert!(is_unavailable(&sqlx::Error::PoolTimedOut));
+
+        replica.mark_down(&sqlx::Error::PoolTimedOut);
+        assert!(!replica.is_up());
+    }
 }
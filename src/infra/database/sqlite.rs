@@ -0,0 +1,3607 @@
+//! SQLite database client for local development (`sqlite` feature).
+//! Implements the same repositories as [`super::PostgresClient`] against a single file
+//! (or in-memory) database, so `cargo run` needs no database server.
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::stream::BoxStream;
+use sqlx::migrate::Migrator;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteRow};
+use sqlx::{QueryBuilder, Row, Sqlite, SqliteConnection, SqlitePool, Transaction};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, instrument};
+
+use super::{
+    AUDIT_LOG, CONTENT_HASH_BACKFILL_BATCH, CONTENT_HASH_UNIQUE_INDEX, ContentCipher,
+    DatabaseInitError, ITEM_EVENTS_LOG, JOB_COLUMNS, LogTable, WEBHOOK_DELIVERIES_LOG,
+    audit_filter_columns, generate_id, generate_outbox_id, item_select_list, quota_column,
+    quota_value, schema_status,
+};
+use crate::domain::{
+    ApiKey, ApiKeyError, ApiKeyQuota, ApiKeyScope, ApiKeyStore, AuditEntry, AuditFilter,
+    AuditLogger, BlockchainStatus, ContentHasher, CreateItemRequest, EncryptionService, EventLog,
+    ExportBookmark, FailedSubmission, HealthCheckError, IdGenerator, Item, ItemCount, ItemError,
+    ItemListFilter, ItemPosition, ItemRepository, ItemSearchHit, ItemSortField, ItemStatusEvent,
+    Job, JobError, JobStatus, JobStore, KeyUsage, LeaderElection, NotificationError,
+    OutboxRepository, OutboxStatus, PaginatedResponse, QueueDepth, QuotaUsage, ReencryptedBatch,
+    RequestJournal, RequestJournalEntry, RequestJournalError, SchemaStatus, SolanaOutboxEntry,
+    SolanaOutboxPayload, SortOrder, SpendLedger, SubmissionAttempt, SubmissionRepository, TagCount,
+    TenantScope, TimeRange, UnitOfWork, UsageLedger, WebhookDelivery, WebhookDeliveryLog,
+    build_solana_outbox_payload_from_request, month_start,
+};
+use crate::infra::UuidV7IdGenerator;
+
+/// Migrations embedded from `./migrations/sqlite`
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations/sqlite");
+
+const ITEM_COLUMNS: &str = "id, hash, name, description, content, metadata, \
+     blockchain_status, blockchain_signature, blockchain_retry_count, \
+     blockchain_last_error, blockchain_next_retry_at, created_at, updated_at, deleted_at, tenant_id, version, content_key, \
+     content_type, content_length";
+
+const EXPORT_BOOKMARK_COLUMNS: &str =
+    "name, last_updated_at, last_item_id, created_at, acknowledged_at";
+
+/// Items read per page by [`ItemRepository::stream_items`]
+const EXPORT_FETCH_BATCH: i64 = 500;
+
+fn map_sqlx_to_item_error(e: sqlx::Error) -> ItemError {
+    match &e {
+        sqlx::Error::RowNotFound => ItemError::NotFound("Row not found".to_string()),
+        // SQLite names the column, not the index: "UNIQUE constraint failed: items.hash"
+        sqlx::Error::Database(db_err)
+            if db_err.is_unique_violation() && db_err.message().ends_with("items.hash") =>
+        {
+            ItemError::DuplicateContent
+        }
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+            ItemError::InvalidState("Duplicate".to_string())
+        }
+        _ => ItemError::RepositoryFailure,
+    }
+}
+
+fn map_sqlx_to_api_key_error(e: sqlx::Error) -> ApiKeyError {
+    match e {
+        sqlx::Error::RowNotFound => ApiKeyError::NotFound("Row not found".to_string()),
+        _ => ApiKeyError::RepositoryFailure,
+    }
+}
+
+/// Escape `%`, `_` and `\` for a `LIKE ... ESCAPE '\'` pattern
+fn like_pattern(term: &str) -> String {
+    let mut pattern = String::with_capacity(term.len() + 2);
+    pattern.push('%');
+    for c in term.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+/// SQLite database client
+pub struct SqliteClient {
+    pool: SqlitePool,
+    cipher: ContentCipher,
+    ids: Arc<dyn IdGenerator>,
+}
+
+impl SqliteClient {
+    /// Open (creating if missing) the database at `database_url`, e.g. `sqlite://data/app.db`
+    /// or `sqlite::memory:`.
+    ///
+    /// The pool holds a single connection: SQLite allows one writer at a time anyway, and
+    /// an in-memory database lives exactly as long as its connection.
+    pub async fn new(database_url: &str) -> Result<Self, DatabaseInitError> {
+        info!("Opening SQLite database...");
+        let options = SqliteConnectOptions::from_str(database_url)
+            .map_err(|e| DatabaseInitError::Connection(e.to_string()))?
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .foreign_keys(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await
+            .map_err(|e| DatabaseInitError::Connection(e.to_string()))?;
+        info!("Opened SQLite database");
+        Ok(Self {
+            pool,
+            cipher: ContentCipher::default(),
+            ids: Arc::new(UuidV7IdGenerator),
+        })
+    }
+
+    /// Store item content encrypted with `service` (see
+    /// [`super::PostgresClient::with_encryption`])
+    #[must_use]
+    pub fn with_encryption(mut self, service: Arc<dyn EncryptionService>) -> Self {
+        self.cipher = ContentCipher::new(service);
+        self
+    }
+
+    /// Generate new row IDs with `ids` (see [`super::PostgresClient::with_id_generator`])
+    #[must_use]
+    pub fn with_id_generator(mut self, ids: Arc<dyn IdGenerator>) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Run the SQLite migrations (`migrations/sqlite`)
+    pub async fn run_migrations(&self) -> Result<(), DatabaseInitError> {
+        info!("Running database migrations...");
+        MIGRATOR
+            .run(&self.pool)
+            .await
+            .map_err(|e| DatabaseInitError::Migration(e.to_string()))?;
+        self.backfill_content_hashes().await?;
+        info!("Database migrations completed successfully");
+        Ok(())
+    }
+
+    /// Compare applied migrations with this build's (see
+    /// [`super::PostgresClient::migration_status`])
+    pub async fn migration_status(&self) -> Result<SchemaStatus, DatabaseInitError> {
+        let migration_error = |e: sqlx::Error| DatabaseInitError::Migration(e.to_string());
+        let table: Option<String> = sqlx::query_scalar(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations'",
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(migration_error)?;
+        let applied: Vec<i64> = match table {
+            Some(_) => sqlx::query_scalar(
+                "SELECT version FROM _sqlx_migrations WHERE success ORDER BY version",
+            )
+            .fetch_all(&self.pool)
+            .await
+            .map_err(migration_error)?,
+            None => Vec::new(),
+        };
+        Ok(schema_status(&MIGRATOR, &applied))
+    }
+
+    /// Replace `hash_<uuid>` placeholders with content hashes (see
+    /// [`super::PostgresClient::backfill_content_hashes`])
+    pub async fn backfill_content_hashes(&self) -> Result<u64, DatabaseInitError> {
+        let migration_error = |e: sqlx::Error| DatabaseInitError::Migration(e.to_string());
+        let mut updated = 0;
+        loop {
+            let rows = sqlx::query(
+                r#"
+                SELECT id, name, description, content FROM items
+                WHERE hash LIKE 'hash\_%' ESCAPE '\'
+                LIMIT ?
+                "#,
+            )
+            .bind(CONTENT_HASH_BACKFILL_BATCH)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(migration_error)?;
+            if rows.is_empty() {
+                break;
+            }
+
+            let mut tx = self.pool.begin().await.map_err(migration_error)?;
+            for row in &rows {
+                let content = self
+                    .cipher
+                    .open_content(row.get("content"))
+                    .await
+                    .map_err(|e| DatabaseInitError::Migration(e.to_string()))?;
+                let hash = ContentHasher::hash(
+                    row.get("name"),
+                    &content,
+                    row.get::<Option<&str>, _>("description"),
+                );
+                sqlx::query("UPDATE items SET hash = ? WHERE id = ?")
+                    .bind(hash)
+                    .bind(row.get::<&str, _>("id"))
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(migration_error)?;
+            }
+            tx.commit().await.map_err(migration_error)?;
+            updated += rows.len() as u64;
+        }
+        if updated > 0 {
+            info!(updated, "Backfilled item content hashes");
+        }
+        Ok(updated)
+    }
+
+    /// Enforce (or stop enforcing) one live item per content hash and tenant
+    pub async fn set_unique_content_hash(&self, enabled: bool) -> Result<(), DatabaseInitError> {
+        let statement = if enabled {
+            format!(
+                "CREATE UNIQUE INDEX IF NOT EXISTS {} ON items (tenant_id, hash) \
+                 WHERE deleted_at IS NULL",
+                CONTENT_HASH_UNIQUE_INDEX
+            )
+        } else {
+            format!("DROP INDEX IF EXISTS {}", CONTENT_HASH_UNIQUE_INDEX)
+        };
+        sqlx::query(&statement)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                DatabaseInitError::Migration(format!(
+                    "cannot enforce unique content hashes (do live items share content?): {}",
+                    e
+                ))
+            })?;
+        Ok(())
+    }
+
+    /// Close the pool, waiting for the connection to be returned
+    pub async fn close(&self) {
+        self.pool.close().await;
+    }
+
+    /// Get the underlying connection pool (for testing)
+    #[must_use]
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    /// Parse and decrypt an optional item row
+    async fn open_item_row(&self, row: Option<SqliteRow>) -> Result<Option<Item>, ItemError> {
+        match row {
+            Some(row) => Ok(Some(self.cipher.open(Self::row_to_item(&row)?).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Parse a database row into an Item
+    fn row_to_item(row: &SqliteRow) -> Result<Item, ItemError> {
+        let metadata: Option<String> = row.get("metadata");
+        let status_str: String = row.get("blockchain_status");
+
+        Ok(Item {
+            id: row.get("id"),
+            hash: row.get("hash"),
+            name: row.get("name"),
+            description: row.get("description"),
+            content: row.get("content"),
+            content_key: row.get("content_key"),
+            content_type: row.get("content_type"),
+            content_length: row.get("content_length"),
+            metadata: metadata.and_then(|v| serde_json::from_str(&v).ok()),
+            blockchain_status: status_str.parse().unwrap_or(BlockchainStatus::Pending),
+            blockchain_signature: row.get("blockchain_signature"),
+            blockchain_retry_count: row.get("blockchain_retry_count"),
+            blockchain_last_error: row.get("blockchain_last_error"),
+            blockchain_next_retry_at: row.get("blockchain_next_retry_at"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            deleted_at: row.get("deleted_at"),
+            tenant_id: row.get("tenant_id"),
+            version: row.get("version"),
+        })
+    }
+
+    /// Append `AND ...` clauses for every filter that is set (values are always bound)
+    fn push_item_filters(query: &mut QueryBuilder<'_, Sqlite>, filter: &ItemListFilter) {
+        if !filter.include_deleted {
+            query.push(" AND deleted_at IS NULL");
+        }
+        if let Some(tenant) = TenantScope::current() {
+            query.push(" AND tenant_id = ").push_bind(tenant);
+        }
+        if let Some(status) = filter.blockchain_status {
+            query
+                .push(" AND blockchain_status = ")
+                .push_bind(status.as_str());
+        }
+        if let Some(tag) = &filter.tag {
+            query
+                .push(
+                    " AND EXISTS (SELECT 1 FROM item_tags \
+                     WHERE item_tags.item_id = items.id AND item_tags.tag = ",
+                )
+                .push_bind(tag.clone())
+                .push(")");
+        }
+        if let Some(author) = &filter.author {
+            query
+                .push(" AND json_extract(metadata, '$.author') = ")
+                .push_bind(author.clone());
+        }
+        if let Some(after) = filter.created_after {
+            query.push(" AND created_at >= ").push_bind(after);
+        }
+        if let Some(before) = filter.created_before {
+            query.push(" AND created_at < ").push_bind(before);
+        }
+    }
+
+    /// Parse a database row into an API key (unknown scopes are dropped)
+    fn row_to_api_key(row: &SqliteRow) -> ApiKey {
+        let scopes: String = row.get("scopes");
+        let scopes: Vec<String> = serde_json::from_str(&scopes).unwrap_or_default();
+        ApiKey {
+            id: row.get("id"),
+            name: row.get("name"),
+            scopes: scopes.iter().filter_map(|s| s.parse().ok()).collect(),
+            created_at: row.get("created_at"),
+            revoked_at: row.get("revoked_at"),
+            tenant_id: row.get("tenant_id"),
+            quota: ApiKeyQuota {
+                daily: quota_column(row.get("daily_quota")),
+                monthly: quota_column(row.get("monthly_quota")),
+            },
+        }
+    }
+
+    /// Parse a database row into the usage of one API key
+    fn row_to_key_usage(row: &SqliteRow) -> KeyUsage {
+        KeyUsage {
+            key_id: row.get("key_id"),
+            name: row.get("name"),
+            daily_requests: u64::try_from(row.get::<i64, _>("daily_requests")).unwrap_or(0),
+            monthly_requests: u64::try_from(row.get::<i64, _>("monthly_requests")).unwrap_or(0),
+            quota: ApiKeyQuota {
+                daily: quota_column(row.get("daily_quota")),
+                monthly: quota_column(row.get("monthly_quota")),
+            },
+        }
+    }
+
+    /// Parse a database row into a Solana outbox entry
+    fn row_to_outbox(row: &SqliteRow) -> Result<SolanaOutboxEntry, ItemError> {
+        let payload: String = row.get("payload");
+        let payload: SolanaOutboxPayload =
+            serde_json::from_str(&payload).map_err(|_| ItemError::RepositoryFailure)?;
+        let status_str: String = row.get("status");
+
+        Ok(SolanaOutboxEntry {
+            id: row.get("id"),
+            aggregate_id: row.get("aggregate_id"),
+            payload,
+            status: status_str.parse().unwrap_or(OutboxStatus::Pending),
+            retry_count: row.get("retry_count"),
+            attempt_blockhash: row.get("attempt_blockhash"),
+            created_at: row.get("created_at"),
+        })
+    }
+
+    /// Parse a database row into an item status event
+    fn row_to_event(row: &SqliteRow) -> ItemStatusEvent {
+        let status_str: String = row.get("status");
+        ItemStatusEvent {
+            id: row.get("id"),
+            position: row.get("position"),
+            event: row.get("event"),
+            item_id: row.get("item_id"),
+            sequence: row.get("sequence"),
+            status: status_str.parse().unwrap_or(BlockchainStatus::Pending),
+            signature: row.get("signature"),
+            error: row.get("error"),
+            occurred_at: row.get("occurred_at"),
+        }
+    }
+
+    /// Insert an outbox entry for `item_id`, optionally carrying over a sticky blockhash
+    async fn insert_outbox(
+        conn: &mut SqliteConnection,
+        item_id: &str,
+        payload: &SolanaOutboxPayload,
+        attempt_blockhash: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<(), ItemError> {
+        let payload = serde_json::to_string(payload).map_err(|_| ItemError::RepositoryFailure)?;
+        sqlx::query(
+            r#"
+            INSERT INTO solana_outbox (id, aggregate_id, payload, status, retry_count,
+                                       attempt_blockhash, created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, 0, ?5, ?6, ?6)
+            "#,
+        )
+        .bind(generate_outbox_id().to_string())
+        .bind(item_id)
+        .bind(payload)
+        .bind(OutboxStatus::Pending.as_str())
+        .bind(attempt_blockhash)
+        .bind(now)
+        .execute(conn)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        Ok(())
+    }
+
+    /// Insert an item, plus its outbox entry in the same transaction when `enqueue` is set.
+    /// Without an outbox entry the item stays `pending` (blockchain disabled).
+    async fn insert_item(
+        &self,
+        data: &CreateItemRequest,
+        enqueue: bool,
+    ) -> Result<Item, ItemError> {
+        let status = if enqueue {
+            BlockchainStatus::PendingSubmission
+        } else {
+            BlockchainStatus::Pending
+        };
+
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+        let item =
+            Self::insert_item_row(&mut tx, &self.cipher, self.ids.as_ref(), data, status).await?;
+        if enqueue {
+            let payload = build_solana_outbox_payload_from_request(&item.id, data);
+            Self::insert_outbox(&mut tx, &item.id, &payload, None, item.created_at).await?;
+        }
+        tx.commit().await.map_err(map_sqlx_to_item_error)?;
+
+        Ok(item)
+    }
+
+    /// Replace the item's `item_tags` rows with `tags`. Must run in the transaction that
+    /// wrote its metadata.
+    async fn replace_item_tags(
+        conn: &mut SqliteConnection,
+        item_id: &str,
+        tags: &[String],
+    ) -> Result<(), ItemError> {
+        sqlx::query("DELETE FROM item_tags WHERE item_id = ?1")
+            .bind(item_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+        if tags.is_empty() {
+            return Ok(());
+        }
+        let tags = serde_json::to_string(tags).map_err(|_| ItemError::RepositoryFailure)?;
+        sqlx::query(
+            "INSERT OR IGNORE INTO item_tags (item_id, tag) SELECT ?1, value FROM json_each(?2)",
+        )
+        .bind(item_id)
+        .bind(tags)
+        .execute(conn)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        Ok(())
+    }
+
+    /// Insert an item row in `status` inside the caller's transaction
+    async fn insert_item_row(
+        conn: &mut SqliteConnection,
+        cipher: &ContentCipher,
+        ids: &dyn IdGenerator,
+        data: &CreateItemRequest,
+        status: BlockchainStatus,
+    ) -> Result<Item, ItemError> {
+        let id = format!("item_{}", generate_id("item", ids));
+        let hash = ContentHasher::hash_request(data);
+        let now = Utc::now();
+        let metadata_json = data
+            .metadata
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|_| ItemError::RepositoryFailure)?;
+        let content = cipher.seal(data.stored_content()).await?;
+
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO items (id, hash, name, description, content, metadata,
+                               blockchain_status, blockchain_retry_count, created_at, updated_at,
+                               tenant_id, content_key, content_type, content_length)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, ?8, ?8, ?9, ?10, ?11, ?12)
+            RETURNING {ITEM_COLUMNS}
+            "#
+        ))
+        .bind(&id)
+        .bind(&hash)
+        .bind(&data.name)
+        .bind(&data.description)
+        .bind(content.as_ref())
+        .bind(&metadata_json)
+        .bind(status.as_str())
+        .bind(now)
+        .bind(TenantScope::for_new_item())
+        .bind(&data.content_key)
+        .bind(data.content_type())
+        .bind(data.content.len() as i64)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        Self::replace_item_tags(conn, &id, data.tags()).await?;
+
+        cipher.open(Self::row_to_item(&row)?).await
+    }
+
+    /// Current status of the item (None: no such item). The pool's single connection
+    /// already serializes writers, so no row lock is needed.
+    async fn item_status(
+        conn: &mut SqliteConnection,
+        item_id: &str,
+    ) -> Result<Option<BlockchainStatus>, ItemError> {
+        let status: Option<String> = sqlx::query_scalar(
+            "SELECT blockchain_status FROM items \
+                 WHERE id = ?1 AND (?2 IS NULL OR tenant_id = ?2)",
+        )
+        .bind(item_id)
+        .bind(TenantScope::current())
+        .fetch_optional(conn)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        Ok(status.map(|s| s.parse().unwrap_or(BlockchainStatus::Pending)))
+    }
+
+    /// Append a status event if the item moved from `previous` into a notified status.
+    /// Must run in the transaction that changed the status.
+    async fn append_status_event(
+        conn: &mut SqliteConnection,
+        item_id: &str,
+        previous: Option<BlockchainStatus>,
+        status: BlockchainStatus,
+        signature: Option<&str>,
+        error: Option<&str>,
+    ) -> Result<(), ItemError> {
+        if previous.is_none_or(|p| p == status) || !ItemStatusEvent::is_notified(status) {
+            return Ok(());
+        }
+        let event = ItemStatusEvent::new(item_id, status);
+        sqlx::query(
+            r#"
+            INSERT INTO item_events (id, item_id, sequence, event, status, signature, error, occurred_at)
+            VALUES (
+                ?1, ?2,
+                (SELECT COALESCE(MAX(sequence), 0) + 1 FROM item_events WHERE item_id = ?2),
+                ?3, ?4, ?5, ?6, ?7
+            )
+            "#,
+        )
+        .bind(&event.id)
+        .bind(item_id)
+        .bind(&event.event)
+        .bind(status.as_str())
+        .bind(signature)
+        .bind(error)
+        .bind(event.occurred_at)
+        .execute(conn)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        Ok(())
+    }
+
+    /// Record a failed attempt on the outbox entry and the item inside the caller's
+    /// transaction
+    #[allow(clippy::too_many_arguments)]
+    async fn fail_outbox_entry(
+        conn: &mut SqliteConnection,
+        outbox_id: &str,
+        item_id: &str,
+        retry_count: i32,
+        outbox_status: OutboxStatus,
+        item_status: BlockchainStatus,
+        error: &str,
+        next_retry_at: Option<DateTime<Utc>>,
+        attempt_blockhash: Option<Option<&str>>,
+    ) -> Result<(), ItemError> {
+        let now = Utc::now();
+
+        // attempt_blockhash: None keeps the stored value, Some(x) overwrites it with x
+        sqlx::query(
+            r#"
+            UPDATE solana_outbox
+            SET status = ?1,
+                retry_count = ?2,
+                next_retry_at = ?3,
+                attempt_blockhash = CASE WHEN ?4 THEN ?5 ELSE attempt_blockhash END,
+                updated_at = ?6
+            WHERE id = ?7
+            "#,
+        )
+        .bind(outbox_status.as_str())
+        .bind(retry_count)
+        .bind(next_retry_at)
+        .bind(attempt_blockhash.is_some())
+        .bind(attempt_blockhash.flatten())
+        .bind(now)
+        .bind(outbox_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        let previous = Self::item_status(&mut *conn, item_id).await?;
+        sqlx::query(
+            r#"
+            UPDATE items
+            SET blockchain_status = ?1,
+                blockchain_last_error = ?2,
+                blockchain_next_retry_at = ?3,
+                blockchain_retry_count = ?4,
+                updated_at = ?5
+            WHERE id = ?6 AND (?7 IS NULL OR tenant_id = ?7)
+            "#,
+        )
+        .bind(item_status.as_str())
+        .bind(error)
+        .bind(next_retry_at)
+        .bind(retry_count)
+        .bind(now)
+        .bind(item_id)
+        .bind(TenantScope::current())
+        .execute(&mut *conn)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        Self::append_status_event(conn, item_id, previous, item_status, None, Some(error)).await
+    }
+
+    /// Insert a pending outbox entry for `item_id` and reset the item to
+    /// `pending_submission` inside the caller's transaction
+    async fn enqueue_outbox_entry(
+        conn: &mut SqliteConnection,
+        cipher: &ContentCipher,
+        item_id: &str,
+        payload: &SolanaOutboxPayload,
+        attempt_blockhash: Option<&str>,
+    ) -> Result<Item, ItemError> {
+        let now = Utc::now();
+        Self::insert_outbox(&mut *conn, item_id, payload, attempt_blockhash, now).await?;
+
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE items
+            SET blockchain_status = ?1,
+                blockchain_last_error = NULL,
+                blockchain_next_retry_at = NULL,
+                blockchain_retry_count = 0,
+                updated_at = ?2
+            WHERE id = ?3 AND (?4 IS NULL OR tenant_id = ?4)
+            RETURNING {ITEM_COLUMNS}
+            "#
+        ))
+        .bind(BlockchainStatus::PendingSubmission.as_str())
+        .bind(now)
+        .bind(item_id)
+        .bind(TenantScope::current())
+        .fetch_one(conn)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        cipher.open(Self::row_to_item(&row)?).await
+    }
+
+    /// Parse a database row into an audit log entry
+    fn row_to_audit_entry(row: &SqliteRow) -> AuditEntry {
+        let diff: Option<String> = row.get("diff");
+        AuditEntry {
+            id: row.get("id"),
+            principal: row.get("principal"),
+            action: row.get("action"),
+            resource_id: row.get("resource_id"),
+            request_id: row.get("request_id"),
+            occurred_at: row.get("occurred_at"),
+            diff: diff.and_then(|v| serde_json::from_str(&v).ok()),
+        }
+    }
+
+    /// Parse a database row into a webhook delivery attempt
+    fn row_to_delivery(row: &SqliteRow) -> WebhookDelivery {
+        WebhookDelivery {
+            id: row.get("id"),
+            event_id: row.get("event_id"),
+            event: row.get("event"),
+            item_id: row.get("item_id"),
+            url: row.get("url"),
+            attempt: u32::try_from(row.get::<i64, _>("attempt")).unwrap_or(0),
+            response_status: row
+                .get::<Option<i64>, _>("response_status")
+                .and_then(|s| u16::try_from(s).ok()),
+            error: row.get("error"),
+            succeeded: row.get("succeeded"),
+            attempted_at: row.get("attempted_at"),
+        }
+    }
+
+    /// Newest-first keyset page of a log table: up to `limit + 1` rows keyed below
+    /// `before` within `range` whose `equal` columns hold the given values (column names
+    /// are constants, values are bound)
+    async fn log_page<T>(
+        &self,
+        table: &LogTable,
+        limit: i64,
+        before: Option<i64>,
+        range: &TimeRange,
+        equal: &[(&str, &str)],
+        parse: impl Fn(&SqliteRow) -> T,
+    ) -> Result<Vec<T>, NotificationError> {
+        let LogTable {
+            name,
+            columns,
+            key,
+            time,
+        } = table;
+        let mut query =
+            QueryBuilder::<Sqlite>::new(format!("SELECT {columns} FROM {name} WHERE 1 = 1"));
+        if let Some(before) = before {
+            query.push(format_args!(" AND {key} < ")).push_bind(before);
+        }
+        if let Some(since) = range.since {
+            query.push(format_args!(" AND {time} >= ")).push_bind(since);
+        }
+        if let Some(until) = range.until {
+            query.push(format_args!(" AND {time} < ")).push_bind(until);
+        }
+        for (column, value) in equal {
+            query
+                .push(format_args!(" AND {column} = "))
+                .push_bind(*value);
+        }
+        query
+            .push(format_args!(" ORDER BY {key} DESC LIMIT "))
+            .push_bind(limit + 1);
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|_| NotificationError::RepositoryFailure)?;
+        Ok(rows.iter().map(parse).collect())
+    }
+
+    /// Every row of a log table about `item_id`, oldest first
+    async fn item_log<T>(
+        &self,
+        table: &LogTable,
+        item_id: &str,
+        parse: impl Fn(&SqliteRow) -> T,
+    ) -> Result<Vec<T>, NotificationError> {
+        let LogTable {
+            name, columns, key, ..
+        } = table;
+        let rows = sqlx::query(&format!(
+            "SELECT {columns} FROM {name} WHERE item_id = ?1 ORDER BY {key}"
+        ))
+        .bind(item_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|_| NotificationError::RepositoryFailure)?;
+        Ok(rows.iter().map(parse).collect())
+    }
+
+    /// Parse a database row into a dead-lettered submission
+    fn row_to_failed_submission(row: &SqliteRow) -> FailedSubmission {
+        FailedSubmission {
+            id: row.get("id"),
+            item_id: row.get("item_id"),
+            outbox_id: row.get("outbox_id"),
+            hash: row.get("hash"),
+            retry_count: row.get("retry_count"),
+            last_error: row.get("last_error"),
+            failed_at: row.get("failed_at"),
+            requeued_at: row.get("requeued_at"),
+        }
+    }
+
+    /// Parse a database row into an export bookmark
+    fn row_to_export_bookmark(row: &SqliteRow) -> ExportBookmark {
+        let updated_at: Option<DateTime<Utc>> = row.get("last_updated_at");
+        let item_id: Option<String> = row.get("last_item_id");
+        ExportBookmark {
+            name: row.get("name"),
+            position: updated_at
+                .zip(item_id)
+                .map(|(updated_at, item_id)| ItemPosition {
+                    updated_at,
+                    item_id,
+                }),
+            created_at: row.get("created_at"),
+            acknowledged_at: row.get("acknowledged_at"),
+        }
+    }
+
+    /// Parse a database row into a background job
+    fn row_to_job(row: &SqliteRow) -> Result<Job, JobError> {
+        let status: String = row.get("status");
+        let result: Option<String> = row.get("result");
+        Ok(Job {
+            id: row.get("id"),
+            kind: row.get("kind"),
+            status: status.parse().map_err(|_| JobError::RepositoryFailure)?,
+            processed: row.get("processed"),
+            failed: row.get("failed"),
+            result: result.and_then(|v| serde_json::from_str(&v).ok()),
+            error: row.get("error"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            finished_at: row.get("finished_at"),
+        })
+    }
+}
+
+/// [`UnitOfWork`] over one SQLite transaction (rolled back by sqlx when dropped)
+pub struct SqliteUnitOfWork {
+    tx: Transaction<'static, Sqlite>,
+    cipher: ContentCipher,
+    ids: Arc<dyn IdGenerator>,
+}
+
+#[async_trait]
+impl UnitOfWork for SqliteUnitOfWork {
+    async fn insert_item(&mut self, data: &CreateItemRequest) -> Result<Item, ItemError> {
+        SqliteClient::insert_item_row(
+            &mut self.tx,
+            &self.cipher,
+            self.ids.as_ref(),
+            data,
+            BlockchainStatus::Pending,
+        )
+        .await
+    }
+
+    async fn enqueue_solana_outbox(
+        &mut self,
+        item_id: &str,
+        payload: &SolanaOutboxPayload,
+    ) -> Result<Item, ItemError> {
+        SqliteClient::enqueue_outbox_entry(&mut self.tx, &self.cipher, item_id, payload, None).await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), ItemError> {
+        self.tx.commit().await.map_err(map_sqlx_to_item_error)
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), ItemError> {
+        self.tx.rollback().await.map_err(map_sqlx_to_item_error)
+    }
+}
+
+#[async_trait]
+impl ItemRepository for SqliteClient {
+    #[instrument(skip(self))]
+    async fn health_check(&self) -> Result<(), HealthCheckError> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map_err(|_| HealthCheckError::DatabaseUnavailable)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn get_item(&self, id: &str) -> Result<Option<Item>, ItemError> {
+        let row = sqlx::query(&format!(
+            "SELECT {ITEM_COLUMNS} FROM items WHERE id = ?1 AND (?2 IS NULL OR tenant_id = ?2)"
+        ))
+        .bind(id)
+        .bind(TenantScope::current())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        self.open_item_row(row).await
+    }
+
+    #[instrument(skip(self, data), fields(item_name = %data.name))]
+    async fn create_item(&self, data: &CreateItemRequest) -> Result<Item, ItemError> {
+        self.insert_item(data, true).await
+    }
+
+    #[instrument(skip(self, data), fields(item_name = %data.name))]
+    async fn create_item_without_outbox(
+        &self,
+        data: &CreateItemRequest,
+    ) -> Result<Item, ItemError> {
+        self.insert_item(data, false).await
+    }
+
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork + '_>, ItemError> {
+        let tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+        Ok(Box::new(SqliteUnitOfWork {
+            tx,
+            cipher: self.cipher.clone(),
+            ids: self.ids.clone(),
+        }))
+    }
+
+    #[instrument(skip(self))]
+    async fn list_items(
+        &self,
+        limit: i64,
+        cursor: Option<&str>,
+        filter: &ItemListFilter,
+    ) -> Result<PaginatedResponse<Item>, ItemError> {
+        let limit = limit.clamp(1, 100);
+        let fetch_limit = limit + 1;
+
+        let mut query = QueryBuilder::<Sqlite>::new(format!(
+            "SELECT {} FROM items WHERE 1 = 1",
+            item_select_list(filter.columns.as_ref())
+        ));
+        Self::push_item_filters(&mut query, filter);
+
+        if let Some(cursor_id) = cursor {
+            // Keyset pagination on (sort column, id) using the cursor item's sort value
+            let cursor_row = sqlx::query(
+                "SELECT created_at, updated_at, name FROM items \
+                     WHERE id = ?1 AND (?2 IS NULL OR tenant_id = ?2)",
+            )
+            .bind(cursor_id)
+            .bind(TenantScope::current())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlx_to_item_error)?
+            .ok_or_else(|| ItemError::InvalidCursor("Cursor item no longer exists".to_string()))?;
+
+            let comparison = match filter.order {
+                SortOrder::Asc => ">",
+                SortOrder::Desc => "<",
+            };
+            query.push(format_args!(
+                " AND ({}, id) {} (",
+                filter.sort.as_str(),
+                comparison
+            ));
+            match filter.sort {
+                ItemSortField::CreatedAt | ItemSortField::UpdatedAt => {
+                    query.push_bind(cursor_row.get::<DateTime<Utc>, _>(filter.sort.as_str()))
+                }
+                ItemSortField::Name => query.push_bind(cursor_row.get::<String, _>("name")),
+            };
+            query.push(", ").push_bind(cursor_id).push(")");
+        }
+
+        // Column and direction come from closed enums, never from raw input. Qualified, as
+        // an unread column's placeholder shares its name.
+        let direction = filter.order.as_str();
+        query.push(format_args!(
+            " ORDER BY items.{} {}, items.id {} LIMIT ",
+            filter.sort.as_str(),
+            direction,
+            direction
+        ));
+        query.push_bind(fetch_limit);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+
+        let has_more = rows.len() > limit as usize;
+        let items: Vec<Item> = rows
+            .iter()
+            .take(limit as usize)
+            .map(Self::row_to_item)
+            .collect::<Result<Vec<_>, _>>()?;
+        let items = self.cipher.open_all(items).await?;
+        let next_cursor = if has_more {
+            items.last().map(|item| item.id.clone())
+        } else {
+            None
+        };
+
+        Ok(PaginatedResponse::new(items, next_cursor, has_more))
+    }
+
+    #[instrument(skip(self))]
+    async fn list_items_at_offset(
+        &self,
+        offset: i64,
+        limit: i64,
+        filter: &ItemListFilter,
+    ) -> Result<Vec<Item>, ItemError> {
+        let mut query = QueryBuilder::<Sqlite>::new(format!(
+            "SELECT {} FROM items WHERE 1 = 1",
+            item_select_list(filter.columns.as_ref())
+        ));
+        Self::push_item_filters(&mut query, filter);
+        let direction = filter.order.as_str();
+        query.push(format_args!(
+            " ORDER BY items.{} {}, items.id {} LIMIT ",
+            filter.sort.as_str(),
+            direction,
+            direction
+        ));
+        query.push_bind(limit).push(" OFFSET ").push_bind(offset);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+        let items = rows
+            .iter()
+            .map(Self::row_to_item)
+            .collect::<Result<Vec<_>, _>>()?;
+        self.cipher.open_all(items).await
+    }
+
+    /// Always an exact count: SQLite keeps no row estimates
+    #[instrument(skip(self))]
+    async fn count_items(
+        &self,
+        filter: &ItemListFilter,
+        _estimate_above: Option<u64>,
+    ) -> Result<ItemCount, ItemError> {
+        let mut query = QueryBuilder::<Sqlite>::new("SELECT COUNT(*) FROM items WHERE 1 = 1");
+        Self::push_item_filters(&mut query, filter);
+        let total: i64 = query
+            .build()
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlx_to_item_error)?
+            .get(0);
+        Ok(ItemCount::exact(u64::try_from(total).unwrap_or(0)))
+    }
+
+    #[instrument(skip(self))]
+    async fn list_tags(&self) -> Result<Vec<TagCount>, ItemError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT item_tags.tag, COUNT(*) AS items
+            FROM item_tags
+            JOIN items ON items.id = item_tags.item_id
+            WHERE items.deleted_at IS NULL AND (?1 IS NULL OR items.tenant_id = ?1)
+            GROUP BY item_tags.tag
+            ORDER BY items DESC, item_tags.tag
+            "#,
+        )
+        .bind(TenantScope::current())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        Ok(rows
+            .iter()
+            .map(|row| TagCount {
+                tag: row.get("tag"),
+                items: u64::try_from(row.get::<i64, _>("items")).unwrap_or(0),
+            })
+            .collect())
+    }
+
+    /// SQLite has no server-side cursors, and holding the pool's single connection for a
+    /// slow download would block every other query. Items are read in keyset pages of
+    /// [`EXPORT_FETCH_BATCH`] instead, releasing the connection between pages.
+    fn stream_items(&self) -> BoxStream<'static, Result<Item, ItemError>> {
+        let pool = self.pool.clone();
+        let cipher = self.cipher.clone();
+        // The stream is polled outside the caller's tenant scope
+        let tenant = TenantScope::current();
+        Box::pin(async_stream::try_stream! {
+            let mut after: Option<(DateTime<Utc>, String)> = None;
+            loop {
+                let mut query = QueryBuilder::<Sqlite>::new(format!(
+                    "SELECT {ITEM_COLUMNS} FROM items WHERE deleted_at IS NULL"
+                ));
+                if let Some(tenant) = &tenant {
+                    query.push(" AND tenant_id = ").push_bind(tenant.clone());
+                }
+                if let Some((created_at, id)) = &after {
+                    query
+                        .push(" AND (created_at, id) > (")
+                        .push_bind(*created_at)
+                        .push(", ")
+                        .push_bind(id.clone())
+                        .push(")");
+                }
+                query
+                    .push(" ORDER BY created_at, id LIMIT ")
+                    .push_bind(EXPORT_FETCH_BATCH);
+                let rows = query
+                    .build()
+                    .fetch_all(&pool)
+                    .await
+                    .map_err(map_sqlx_to_item_error)?;
+
+                let mut last = None;
+                for row in &rows {
+                    let item = cipher.open(Self::row_to_item(row)?).await?;
+                    last = Some((item.created_at, item.id.clone()));
+                    yield item;
+                }
+                if rows.len() < EXPORT_FETCH_BATCH as usize {
+                    break;
+                }
+                after = last;
+            }
+        })
+    }
+
+    /// Keyset pages over `(updated_at, id)`, like [`Self::stream_items`]
+    fn stream_item_changes(
+        &self,
+        after: Option<ItemPosition>,
+    ) -> BoxStream<'static, Result<Item, ItemError>> {
+        let pool = self.pool.clone();
+        let cipher = self.cipher.clone();
+        let tenant = TenantScope::current();
+        Box::pin(async_stream::try_stream! {
+            let mut after = after;
+            loop {
+                let mut query = QueryBuilder::<Sqlite>::new(format!(
+                    "SELECT {ITEM_COLUMNS} FROM items WHERE deleted_at IS NULL"
+                ));
+                if let Some(tenant) = &tenant {
+                    query.push(" AND tenant_id = ").push_bind(tenant.clone());
+                }
+                if let Some(position) = &after {
+                    query
+                        .push(" AND (updated_at, id) > (")
+                        .push_bind(position.updated_at)
+                        .push(", ")
+                        .push_bind(position.item_id.clone())
+                        .push(")");
+                }
+                query
+                    .push(" ORDER BY updated_at, id LIMIT ")
+                    .push_bind(EXPORT_FETCH_BATCH);
+                let rows = query
+                    .build()
+                    .fetch_all(&pool)
+                    .await
+                    .map_err(map_sqlx_to_item_error)?;
+
+                for row in &rows {
+                    let item = cipher.open(Self::row_to_item(row)?).await?;
+                    after = Some(ItemPosition::of(&item));
+                    yield item;
+                }
+                if rows.len() < EXPORT_FETCH_BATCH as usize {
+                    break;
+                }
+            }
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn export_bookmark(&self, name: &str) -> Result<ExportBookmark, ItemError> {
+        sqlx::query("INSERT OR IGNORE INTO export_bookmarks (name, created_at) VALUES (?1, ?2)")
+            .bind(name)
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+        let row = sqlx::query(&format!(
+            "SELECT {EXPORT_BOOKMARK_COLUMNS} FROM export_bookmarks WHERE name = ?1"
+        ))
+        .bind(name)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        Ok(Self::row_to_export_bookmark(&row))
+    }
+
+    #[instrument(skip(self))]
+    async fn acknowledge_export_bookmark(
+        &self,
+        name: &str,
+        position: &ItemPosition,
+    ) -> Result<ExportBookmark, ItemError> {
+        sqlx::query(
+            r#"
+            UPDATE export_bookmarks
+            SET last_updated_at = ?2, last_item_id = ?3, acknowledged_at = ?4
+            WHERE name = ?1
+              AND (last_updated_at IS NULL OR (last_updated_at, last_item_id) < (?2, ?3))
+            "#,
+        )
+        .bind(name)
+        .bind(position.updated_at)
+        .bind(&position.item_id)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        let row = sqlx::query(&format!(
+            "SELECT {EXPORT_BOOKMARK_COLUMNS} FROM export_bookmarks WHERE name = ?1"
+        ))
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?
+        .ok_or_else(|| ItemError::NotFound(format!("export bookmark {}", name)))?;
+        Ok(Self::row_to_export_bookmark(&row))
+    }
+
+    #[instrument(skip(self))]
+    async fn find_item_by_hash(&self, hash: &str) -> Result<Option<Item>, ItemError> {
+        let row = sqlx::query(&format!(
+            "SELECT {ITEM_COLUMNS} FROM items \
+             WHERE hash = ?1 AND tenant_id = ?2 AND deleted_at IS NULL \
+             ORDER BY created_at, id LIMIT 1"
+        ))
+        .bind(hash)
+        .bind(TenantScope::for_new_item())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        self.open_item_row(row).await
+    }
+
+    /// Every whitespace-separated term must appear (case-insensitively) in the name,
+    /// description or content. Name hits outrank description hits, which outrank content.
+    #[instrument(skip(self))]
+    async fn search_items(&self, query: &str, limit: i64) -> Result<Vec<ItemSearchHit>, ItemError> {
+        let patterns: Vec<String> = query.split_whitespace().map(like_pattern).collect();
+        if patterns.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut sql = QueryBuilder::<Sqlite>::new(format!("SELECT {ITEM_COLUMNS}, (0"));
+        for pattern in &patterns {
+            sql.push(" + CASE WHEN name LIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' THEN 1.0 WHEN description LIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' THEN 0.4 ELSE 0.2 END");
+        }
+        sql.push(
+            ") AS rank, CASE WHEN content LIKE 'enc:v1:%' THEN substr(coalesce(description, ''), 1, 160) \
+             ELSE substr(content, 1, 160) END AS snippet FROM items WHERE deleted_at IS NULL",
+        );
+        if let Some(tenant) = TenantScope::current() {
+            sql.push(" AND tenant_id = ").push_bind(tenant);
+        }
+        for pattern in &patterns {
+            sql.push(" AND (name LIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' OR description LIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' OR (content NOT LIKE 'enc:v1:%' AND content LIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\'))");
+        }
+        sql.push(" ORDER BY rank DESC, id LIMIT ")
+            .push_bind(limit.clamp(1, 100));
+
+        let rows = sql
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+
+        let mut hits = Vec::with_capacity(rows.len());
+        for row in &rows {
+            hits.push(ItemSearchHit {
+                item: self.cipher.open(Self::row_to_item(row)?).await?,
+                rank: row.get::<f64, _>("rank") as f32,
+                snippet: row.get("snippet"),
+            });
+        }
+        Ok(hits)
+    }
+
+    #[instrument(skip(self, data), fields(item_name = %data.name))]
+    async fn update_item(
+        &self,
+        id: &str,
+        data: &CreateItemRequest,
+        expected_version: i64,
+    ) -> Result<Item, ItemError> {
+        let metadata_json = data
+            .metadata
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|_| ItemError::RepositoryFailure)?;
+        let content = self.cipher.seal(data.stored_content()).await?;
+
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE items
+            SET hash = ?1,
+                name = ?2,
+                description = ?3,
+                content = ?4,
+                metadata = ?5,
+                version = version + 1,
+                updated_at = ?6,
+                content_key = ?10,
+                content_type = ?11,
+                content_length = ?12
+            WHERE id = ?7 AND deleted_at IS NULL AND (?8 IS NULL OR tenant_id = ?8)
+              AND version = ?9
+            RETURNING {ITEM_COLUMNS}
+            "#
+        ))
+        .bind(ContentHasher::hash_request(data))
+        .bind(&data.name)
+        .bind(&data.description)
+        .bind(content.as_ref())
+        .bind(&metadata_json)
+        .bind(Utc::now())
+        .bind(id)
+        .bind(TenantScope::current())
+        .bind(expected_version)
+        .bind(&data.content_key)
+        .bind(data.content_type())
+        .bind(data.content.len() as i64)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        if let Some(row) = row {
+            Self::replace_item_tags(&mut tx, id, data.tags()).await?;
+            tx.commit().await.map_err(map_sqlx_to_item_error)?;
+            return self.cipher.open(Self::row_to_item(&row)?).await;
+        }
+        // Release the pool's connection before looking the item up
+        drop(tx);
+        // Nothing matched: either the item is gone or its version moved on
+        match self.get_item(id).await? {
+            Some(item) if !item.is_deleted() => Err(ItemError::Conflict {
+                id: id.to_string(),
+                expected: expected_version,
+                current: item.version,
+            }),
+            _ => Err(ItemError::NotFound(id.to_string())),
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn soft_delete_item(&self, id: &str) -> Result<Option<Item>, ItemError> {
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE items
+            SET deleted_at = ?1,
+                updated_at = ?1
+            WHERE id = ?2 AND deleted_at IS NULL AND (?3 IS NULL OR tenant_id = ?3)
+            RETURNING {ITEM_COLUMNS}
+            "#
+        ))
+        .bind(Utc::now())
+        .bind(id)
+        .bind(TenantScope::current())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        self.open_item_row(row).await
+    }
+
+    #[instrument(skip(self))]
+    async fn purge_deleted_items(&self, deleted_before: DateTime<Utc>) -> Result<u64, ItemError> {
+        let result =
+            sqlx::query("DELETE FROM items WHERE deleted_at IS NOT NULL AND deleted_at < ?1")
+                .bind(deleted_before)
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlx_to_item_error)?;
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip(self))]
+    async fn reencrypt_content(
+        &self,
+        after: Option<&str>,
+        limit: i64,
+    ) -> Result<ReencryptedBatch, ItemError> {
+        self.cipher.ensure_enabled()?;
+        let rows = sqlx::query(
+            "SELECT id, content FROM items WHERE (?1 IS NULL OR id > ?1) ORDER BY id LIMIT ?2",
+        )
+        .bind(after)
+        .bind(limit.clamp(1, 1000))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        let mut batch = ReencryptedBatch {
+            scanned: rows.len() as u64,
+            last_id: rows.last().map(|row| row.get("id")),
+            ..ReencryptedBatch::default()
+        };
+        for row in &rows {
+            let stored: &str = row.get("content");
+            let Some(content) = self.cipher.reencrypt(stored).await? else {
+                continue;
+            };
+            // Skipped when a write replaced the content in the meantime
+            let result =
+                sqlx::query("UPDATE items SET content = ?2 WHERE id = ?1 AND content = ?3")
+                    .bind(row.get::<&str, _>("id"))
+                    .bind(&content)
+                    .bind(stored)
+                    .execute(&self.pool)
+                    .await
+                    .map_err(map_sqlx_to_item_error)?;
+            batch.reencrypted += result.rows_affected();
+        }
+        Ok(batch)
+    }
+}
+
+#[async_trait]
+impl SubmissionRepository for SqliteClient {
+    #[instrument(skip(self))]
+    async fn update_blockchain_status(
+        &self,
+        id: &str,
+        status: BlockchainStatus,
+        signature: Option<&str>,
+        error: Option<&str>,
+        next_retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), ItemError> {
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+        let previous = Self::item_status(&mut tx, id).await?;
+
+        let stored_signature: Option<Option<String>> = sqlx::query_scalar(
+            r#"
+            UPDATE items
+            SET blockchain_status = ?1,
+                blockchain_signature = COALESCE(?2, blockchain_signature),
+                blockchain_last_error = ?3,
+                blockchain_next_retry_at = ?4,
+                updated_at = ?5
+            WHERE id = ?6 AND (?7 IS NULL OR tenant_id = ?7)
+            RETURNING blockchain_signature
+            "#,
+        )
+        .bind(status.as_str())
+        .bind(signature)
+        .bind(error)
+        .bind(next_retry_at)
+        .bind(Utc::now())
+        .bind(id)
+        .bind(TenantScope::current())
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        Self::append_status_event(
+            &mut tx,
+            id,
+            previous,
+            status,
+            stored_signature.flatten().as_deref(),
+            error,
+        )
+        .await?;
+        tx.commit().await.map_err(map_sqlx_to_item_error)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self, payload))]
+    async fn enqueue_solana_outbox_for_item(
+        &self,
+        item_id: &str,
+        payload: &SolanaOutboxPayload,
+    ) -> Result<Item, ItemError> {
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+        let item =
+            Self::enqueue_outbox_entry(&mut tx, &self.cipher, item_id, payload, None).await?;
+        tx.commit().await.map_err(map_sqlx_to_item_error)?;
+        Ok(item)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_pending_blockchain_items(
+        &self,
+        limit: i64,
+        claim_ttl: Duration,
+    ) -> Result<Vec<Item>, ItemError> {
+        let now = Utc::now();
+        let rows = sqlx::query(&format!(
+            r#"
+            UPDATE items
+            SET updated_at = ?1,
+                blockchain_next_retry_at = ?4
+            WHERE id IN (
+                SELECT id
+                FROM items
+                WHERE blockchain_status = 'pending_submission'
+                  AND (blockchain_next_retry_at IS NULL OR blockchain_next_retry_at <= ?1)
+                  AND blockchain_retry_count < 10
+                  AND (?3 IS NULL OR tenant_id = ?3)
+                ORDER BY blockchain_next_retry_at ASC NULLS FIRST, created_at ASC
+                LIMIT ?2
+            )
+            RETURNING {ITEM_COLUMNS}
+            "#
+        ))
+        .bind(now)
+        .bind(limit)
+        .bind(TenantScope::current())
+        .bind(now + chrono::Duration::from_std(claim_ttl).unwrap_or_default())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        let items = rows
+            .iter()
+            .map(Self::row_to_item)
+            .collect::<Result<Vec<_>, _>>()?;
+        self.cipher.open_all(items).await
+    }
+
+    #[instrument(skip(self))]
+    async fn increment_retry_count(&self, id: &str) -> Result<i32, ItemError> {
+        sqlx::query_scalar(
+            r#"
+            UPDATE items
+            SET blockchain_retry_count = blockchain_retry_count + 1,
+                updated_at = ?1
+            WHERE id = ?2 AND (?3 IS NULL OR tenant_id = ?3)
+            RETURNING blockchain_retry_count
+            "#,
+        )
+        .bind(Utc::now())
+        .bind(id)
+        .bind(TenantScope::current())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for SqliteClient {
+    #[instrument(skip(self))]
+    async fn health_check(&self) -> Result<(), HealthCheckError> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map_err(|_| HealthCheckError::DatabaseUnavailable)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn claim_pending_solana_outbox(
+        &self,
+        limit: i64,
+        claim_ttl: Duration,
+    ) -> Result<Vec<SolanaOutboxEntry>, ItemError> {
+        let now = Utc::now();
+        // Entries stuck in `processing` past the claim belong to a crashed worker
+        let stale_before = now - chrono::Duration::from_std(claim_ttl).unwrap_or_default();
+        let rows = sqlx::query(
+            r#"
+            UPDATE solana_outbox
+            SET status = 'processing',
+                updated_at = ?1
+            WHERE id IN (
+                SELECT id
+                FROM solana_outbox
+                WHERE (status = 'pending' OR (status = 'processing' AND updated_at < ?2))
+                  AND (next_retry_at IS NULL OR next_retry_at <= ?1)
+                ORDER BY created_at ASC
+                LIMIT ?3
+            )
+            RETURNING id, aggregate_id, payload, status, retry_count, attempt_blockhash, created_at
+            "#,
+        )
+        .bind(now)
+        .bind(stale_before)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        rows.iter().map(Self::row_to_outbox).collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn complete_solana_outbox(
+        &self,
+        outbox_id: &str,
+        item_id: &str,
+        signature: &str,
+    ) -> Result<(), ItemError> {
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+
+        sqlx::query("UPDATE solana_outbox SET status = ?1, updated_at = ?2 WHERE id = ?3")
+            .bind(OutboxStatus::Completed.as_str())
+            .bind(now)
+            .bind(outbox_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+
+        let previous = Self::item_status(&mut tx, item_id).await?;
+        sqlx::query(
+            r#"
+            UPDATE items
+            SET blockchain_status = ?1,
+                blockchain_signature = ?2,
+                blockchain_last_error = NULL,
+                blockchain_next_retry_at = NULL,
+                updated_at = ?3
+            WHERE id = ?4
+            "#,
+        )
+        .bind(BlockchainStatus::Submitted.as_str())
+        .bind(signature)
+        .bind(now)
+        .bind(item_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        Self::append_status_event(
+            &mut tx,
+            item_id,
+            previous,
+            BlockchainStatus::Submitted,
+            Some(signature),
+            None,
+        )
+        .await?;
+        tx.commit().await.map_err(map_sqlx_to_item_error)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn fail_solana_outbox(
+        &self,
+        outbox_id: &str,
+        item_id: &str,
+        retry_count: i32,
+        outbox_status: OutboxStatus,
+        item_status: BlockchainStatus,
+        error: &str,
+        next_retry_at: Option<DateTime<Utc>>,
+        attempt_blockhash: Option<Option<&str>>,
+    ) -> Result<(), ItemError> {
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+        Self::fail_outbox_entry(
+            &mut tx,
+            outbox_id,
+            item_id,
+            retry_count,
+            outbox_status,
+            item_status,
+            error,
+            next_retry_at,
+            attempt_blockhash,
+        )
+        .await?;
+        tx.commit().await.map_err(map_sqlx_to_item_error)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn save_attempt_blockhash(
+        &self,
+        outbox_id: &str,
+        blockhash: Option<&str>,
+    ) -> Result<(), ItemError> {
+        sqlx::query("UPDATE solana_outbox SET attempt_blockhash = ?1 WHERE id = ?2")
+            .bind(blockhash)
+            .bind(outbox_id)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn dead_letter_solana_outbox(
+        &self,
+        outbox_id: &str,
+        item_id: &str,
+        retry_count: i32,
+        error: &str,
+        attempt_blockhash: Option<Option<&str>>,
+    ) -> Result<FailedSubmission, ItemError> {
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+        Self::fail_outbox_entry(
+            &mut tx,
+            outbox_id,
+            item_id,
+            retry_count,
+            OutboxStatus::Failed,
+            BlockchainStatus::Failed,
+            error,
+            None,
+            attempt_blockhash,
+        )
+        .await?;
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO failed_submissions (id, item_id, outbox_id, hash, payload, retry_count,
+                                            last_error, attempt_blockhash, failed_at)
+            SELECT ?1, aggregate_id, id, json_extract(payload, '$.hash'), payload, retry_count,
+                   ?2, attempt_blockhash, ?3
+            FROM solana_outbox
+            WHERE id = ?4
+            RETURNING id, item_id, outbox_id, hash, retry_count, last_error, failed_at, requeued_at
+            "#,
+        )
+        .bind(format!("dlq_{}", generate_id("dlq", self.ids.as_ref())))
+        .bind(error)
+        .bind(Utc::now())
+        .bind(outbox_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(map_sqlx_to_item_error)?
+        .ok_or_else(|| ItemError::NotFound(outbox_id.to_string()))?;
+        tx.commit().await.map_err(map_sqlx_to_item_error)?;
+
+        Ok(Self::row_to_failed_submission(&row))
+    }
+
+    #[instrument(skip(self))]
+    async fn list_failed_submissions(
+        &self,
+        limit: i64,
+        include_requeued: bool,
+    ) -> Result<Vec<FailedSubmission>, ItemError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, item_id, outbox_id, hash, retry_count, last_error, failed_at, requeued_at
+            FROM failed_submissions
+            WHERE ?1 OR requeued_at IS NULL
+            ORDER BY failed_at DESC, id DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(include_requeued)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        Ok(rows.iter().map(Self::row_to_failed_submission).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn requeue_failed_submission(&self, id: &str) -> Result<Item, ItemError> {
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_item_error)?;
+
+        let row = sqlx::query(
+            "SELECT item_id, payload, attempt_blockhash, requeued_at FROM failed_submissions WHERE id = ?1",
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(map_sqlx_to_item_error)?
+        .ok_or_else(|| ItemError::NotFound(id.to_string()))?;
+
+        if row.get::<Option<DateTime<Utc>>, _>("requeued_at").is_some() {
+            return Err(ItemError::InvalidState(
+                "Submission was already requeued".to_string(),
+            ));
+        }
+        let item_id: String = row.get("item_id");
+        let payload: String = row.get("payload");
+        let payload: SolanaOutboxPayload =
+            serde_json::from_str(&payload).map_err(|_| ItemError::RepositoryFailure)?;
+        let attempt_blockhash: Option<String> = row.get("attempt_blockhash");
+
+        match Self::item_status(&mut tx, &item_id).await? {
+            Some(BlockchainStatus::Failed) => {}
+            Some(_) => {
+                return Err(ItemError::InvalidState(
+                    "Item is no longer failed".to_string(),
+                ));
+            }
+            None => return Err(ItemError::NotFound(item_id)),
+        }
+
+        let item = Self::enqueue_outbox_entry(
+            &mut tx,
+            &self.cipher,
+            &item_id,
+            &payload,
+            attempt_blockhash.as_deref(),
+        )
+        .await?;
+
+        sqlx::query("UPDATE failed_submissions SET requeued_at = ?1 WHERE id = ?2")
+            .bind(Utc::now())
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx_to_item_error)?;
+        tx.commit().await.map_err(map_sqlx_to_item_error)?;
+
+        Ok(item)
+    }
+
+    #[instrument(skip(self))]
+    async fn queue_depth(&self) -> Result<QueueDepth, ItemError> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE status = 'pending') AS pending,
+                COUNT(*) FILTER (
+                    WHERE status = 'pending' AND (next_retry_at IS NULL OR next_retry_at <= ?1)
+                ) AS due,
+                COUNT(*) FILTER (WHERE status = 'processing') AS processing,
+                (SELECT COUNT(*) FROM failed_submissions WHERE requeued_at IS NULL) AS dead_lettered
+            FROM solana_outbox
+            "#,
+        )
+        .bind(Utc::now())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        let oldest_pending_at = sqlx::query_scalar(
+            "SELECT created_at FROM solana_outbox WHERE status = 'pending' ORDER BY created_at LIMIT 1",
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+
+        Ok(QueueDepth {
+            pending: row.get("pending"),
+            due: row.get("due"),
+            processing: row.get("processing"),
+            dead_lettered: row.get("dead_lettered"),
+            oldest_pending_at,
+        })
+    }
+
+    #[instrument(skip(self, attempt))]
+    async fn record_submission_attempt(
+        &self,
+        item_id: &str,
+        attempt: &SubmissionAttempt,
+    ) -> Result<(), ItemError> {
+        let attempt = serde_json::to_string(attempt).map_err(|_| ItemError::RepositoryFailure)?;
+        sqlx::query(
+            "UPDATE items SET submission_attempts = json_insert(submission_attempts, '$[#]', json(?1)) \
+             WHERE id = ?2 AND (?3 IS NULL OR tenant_id = ?3)",
+        )
+        .bind(attempt)
+        .bind(item_id)
+        .bind(TenantScope::current())
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_submission_attempts(
+        &self,
+        item_id: &str,
+    ) -> Result<Vec<SubmissionAttempt>, ItemError> {
+        let attempts: Option<String> = sqlx::query_scalar(
+            "SELECT submission_attempts FROM items \
+                 WHERE id = ?1 AND (?2 IS NULL OR tenant_id = ?2)",
+        )
+        .bind(item_id)
+        .bind(TenantScope::current())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_to_item_error)?;
+        attempts
+            .map(|attempts| serde_json::from_str(&attempts))
+            .transpose()
+            .map(Option::unwrap_or_default)
+            .map_err(|_| ItemError::RepositoryFailure)
+    }
+}
+
+#[async_trait]
+impl ApiKeyStore for SqliteClient {
+    #[instrument(skip(self, key_hash))]
+    async fn create_api_key(
+        &self,
+        name: &str,
+        key_hash: &str,
+        scopes: &[ApiKeyScope],
+        tenant_id: &str,
+        quota: ApiKeyQuota,
+    ) -> Result<ApiKey, ApiKeyError> {
+        let id = format!("key_{}", generate_id("key", self.ids.as_ref()));
+        let scopes: Vec<&str> = scopes.iter().map(|s| s.as_str()).collect();
+        let scopes = serde_json::to_string(&scopes).map_err(|_| ApiKeyError::RepositoryFailure)?;
+        let row = sqlx::query(
+            r#"
+            INSERT INTO api_keys
+                (id, name, key_hash, scopes, tenant_id, daily_quota, monthly_quota, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            RETURNING id, name, scopes, tenant_id, created_at, revoked_at, daily_quota, monthly_quota
+            "#,
+        )
+        .bind(&id)
+        .bind(name)
+        .bind(key_hash)
+        .bind(scopes)
+        .bind(tenant_id)
+        .bind(quota.daily.map(quota_value))
+        .bind(quota.monthly.map(quota_value))
+        .bind(Utc::now())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_to_api_key_error)?;
+        Ok(Self::row_to_api_key(&row))
+    }
+
+    #[instrument(skip(self, key_hash))]
+    async fn find_api_key_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>, ApiKeyError> {
+        let row = sqlx::query(
+            "SELECT id, name, scopes, tenant_id, created_at, revoked_at, daily_quota, monthly_quota FROM api_keys WHERE key_hash = ?1",
+        )
+        .bind(key_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_to_api_key_error)?;
+        Ok(row.as_ref().map(Self::row_to_api_key))
+    }
+
+    #[instrument(skip(self))]
+    async fn list_api_keys(&self) -> Result<Vec<ApiKey>, ApiKeyError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, name, scopes, tenant_id, created_at, revoked_at, daily_quota, monthly_quota
+            FROM api_keys
+            ORDER BY created_at DESC, id DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_to_api_key_error)?;
+        Ok(rows.iter().map(Self::row_to_api_key).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn revoke_api_key(&self, id: &str) -> Result<ApiKey, ApiKeyError> {
+        let row = sqlx::query(
+            r#"
+            UPDATE api_keys
+            SET revoked_at = COALESCE(revoked_at, ?1)
+            WHERE id = ?2
+            RETURNING id, name, scopes, tenant_id, created_at, revoked_at, daily_quota, monthly_quota
+            "#,
+        )
+        .bind(Utc::now())
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_to_api_key_error)?;
+        row.as_ref()
+            .map(Self::row_to_api_key)
+            .ok_or_else(|| ApiKeyError::NotFound(id.to_string()))
+    }
+}
+
+#[async_trait]
+impl WebhookDeliveryLog for SqliteClient {
+    #[instrument(skip(self, delivery), fields(event_id = %delivery.event_id))]
+    async fn record_webhook_delivery(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> Result<(), NotificationError> {
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_deliveries
+                (event_id, event, item_id, url, attempt, response_status, error, succeeded, attempted_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            "#,
+        )
+        .bind(&delivery.event_id)
+        .bind(&delivery.event)
+        .bind(&delivery.item_id)
+        .bind(&delivery.url)
+        .bind(delivery.attempt)
+        .bind(delivery.response_status)
+        .bind(&delivery.error)
+        .bind(delivery.succeeded)
+        .bind(delivery.attempted_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|_| NotificationError::RepositoryFailure)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_webhook_deliveries(
+        &self,
+        limit: i64,
+        before: Option<i64>,
+        range: &TimeRange,
+    ) -> Result<PaginatedResponse<WebhookDelivery>, NotificationError> {
+        let limit = limit.clamp(1, 100);
+        let deliveries = self
+            .log_page(
+                &WEBHOOK_DELIVERIES_LOG,
+                limit,
+                before,
+                range,
+                &[],
+                Self::row_to_delivery,
+            )
+            .await?;
+        Ok(PaginatedResponse::from_lookahead(deliveries, limit, |d| {
+            d.id.to_string()
+        }))
+    }
+
+    #[instrument(skip(self))]
+    async fn item_deliveries(
+        &self,
+        item_id: &str,
+    ) -> Result<Vec<WebhookDelivery>, NotificationError> {
+        self.item_log(&WEBHOOK_DELIVERIES_LOG, item_id, Self::row_to_delivery)
+            .await
+    }
+}
+
+#[async_trait]
+impl AuditLogger for SqliteClient {
+    #[instrument(skip(self, entry), fields(action = %entry.action))]
+    async fn record_audit_entry(&self, entry: &AuditEntry) -> Result<(), NotificationError> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (principal, action, resource_id, request_id, occurred_at, diff)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+        )
+        .bind(&entry.principal)
+        .bind(&entry.action)
+        .bind(&entry.resource_id)
+        .bind(&entry.request_id)
+        .bind(entry.occurred_at)
+        .bind(entry.diff.as_ref().map(serde_json::Value::to_string))
+        .execute(&self.pool)
+        .await
+        .map_err(|_| NotificationError::RepositoryFailure)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_audit_entries(
+        &self,
+        limit: i64,
+        before: Option<i64>,
+        filter: &AuditFilter,
+    ) -> Result<PaginatedResponse<AuditEntry>, NotificationError> {
+        let limit = limit.clamp(1, 100);
+        let entries = self
+            .log_page(
+                &AUDIT_LOG,
+                limit,
+                before,
+                &filter.range,
+                &audit_filter_columns(filter),
+                Self::row_to_audit_entry,
+            )
+            .await?;
+        Ok(PaginatedResponse::from_lookahead(entries, limit, |e| {
+            e.id.to_string()
+        }))
+    }
+}
+
+#[async_trait]
+impl JobStore for SqliteClient {
+    #[instrument(skip(self))]
+    async fn create_job(&self, kind: &str) -> Result<Job, JobError> {
+        let now = Utc::now();
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO jobs (id, kind, status, created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?4)
+            RETURNING {JOB_COLUMNS}
+            "#
+        ))
+        .bind(format!("job_{}", generate_id("job", self.ids.as_ref())))
+        .bind(kind)
+        .bind(JobStatus::Queued.as_str())
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|_| JobError::RepositoryFailure)?;
+        Self::row_to_job(&row)
+    }
+
+    #[instrument(skip(self))]
+    async fn update_job_progress(
+        &self,
+        id: &str,
+        processed: i64,
+        failed: i64,
+    ) -> Result<(), JobError> {
+        let updated = sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = ?1, processed = ?2, failed = ?3, updated_at = ?4
+            WHERE id = ?5
+            "#,
+        )
+        .bind(JobStatus::Running.as_str())
+        .bind(processed)
+        .bind(failed)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|_| JobError::RepositoryFailure)?;
+        if updated.rows_affected() == 0 {
+            return Err(JobError::NotFound(id.to_string()));
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self, result))]
+    async fn finish_job(
+        &self,
+        id: &str,
+        status: JobStatus,
+        result: Option<&serde_json::Value>,
+        error: Option<&str>,
+    ) -> Result<Job, JobError> {
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE jobs
+            SET status = ?1, result = ?2, error = ?3, updated_at = ?4, finished_at = ?4
+            WHERE id = ?5
+            RETURNING {JOB_COLUMNS}
+            "#
+        ))
+        .bind(status.as_str())
+        .bind(result.map(serde_json::Value::to_string))
+        .bind(error)
+        .bind(Utc::now())
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|_| JobError::RepositoryFailure)?
+        .ok_or_else(|| JobError::NotFound(id.to_string()))?;
+        Self::row_to_job(&row)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_job(&self, id: &str) -> Result<Option<Job>, JobError> {
+        let row = sqlx::query(&format!("SELECT {JOB_COLUMNS} FROM jobs WHERE id = ?1"))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|_| JobError::RepositoryFailure)?;
+        row.as_ref().map(Self::row_to_job).transpose()
+    }
+}
+
+#[async_trait]
+impl UsageLedger for SqliteClient {
+    #[instrument(skip(self))]
+    async fn record_request(
+        &self,
+        key_id: &str,
+        day: NaiveDate,
+    ) -> Result<QuotaUsage, ApiKeyError> {
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_to_api_key_error)?;
+        let daily: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO api_key_usage (key_id, day, requests)
+            VALUES (?1, ?2, 1)
+            ON CONFLICT (key_id, day) DO UPDATE SET requests = api_key_usage.requests + 1
+            RETURNING requests
+            "#,
+        )
+        .bind(key_id)
+        .bind(day)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_sqlx_to_api_key_error)?;
+        let monthly: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(requests), 0) FROM api_key_usage \
+             WHERE key_id = ?1 AND day >= ?2 AND day <= ?3",
+        )
+        .bind(key_id)
+        .bind(month_start(day))
+        .bind(day)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_sqlx_to_api_key_error)?;
+        tx.commit().await.map_err(map_sqlx_to_api_key_error)?;
+        Ok(QuotaUsage {
+            daily: u64::try_from(daily).unwrap_or(0),
+            monthly: u64::try_from(monthly).unwrap_or(0),
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn usage_on(&self, day: NaiveDate) -> Result<Vec<KeyUsage>, ApiKeyError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT u.key_id, k.name, k.daily_quota, k.monthly_quota,
+                   SUM(CASE WHEN u.day = ?1 THEN u.requests ELSE 0 END) AS daily_requests,
+                   SUM(u.requests) AS monthly_requests
+            FROM api_key_usage u
+            LEFT JOIN api_keys k ON k.id = u.key_id
+            WHERE u.day >= ?2 AND u.day <= ?1
+            GROUP BY u.key_id, k.name, k.daily_quota, k.monthly_quota
+            ORDER BY u.key_id
+            "#,
+        )
+        .bind(day)
+        .bind(month_start(day))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_to_api_key_error)?;
+        Ok(rows.iter().map(Self::row_to_key_usage).collect())
+    }
+}
+
+#[async_trait]
+impl SpendLedger for SqliteClient {
+    #[instrument(skip(self))]
+    async fn spent_on(&self, signer: &str, day: NaiveDate) -> Result<u64, ItemError> {
+        let spent: Option<i64> =
+            sqlx::query_scalar("SELECT spent FROM blockchain_spend WHERE signer = ?1 AND day = ?2")
+                .bind(signer)
+                .bind(day)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|_| ItemError::RepositoryFailure)?;
+        Ok(spent.map_or(0, |spent| u64::try_from(spent).unwrap_or(0)))
+    }
+
+    #[instrument(skip(self))]
+    async fn record_spend(
+        &self,
+        signer: &str,
+        day: NaiveDate,
+        amount: u64,
+    ) -> Result<u64, ItemError> {
+        let spent: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO blockchain_spend (signer, day, spent, updated_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (signer, day)
+            DO UPDATE SET spent = blockchain_spend.spent + excluded.spent, updated_at = excluded.updated_at
+            RETURNING spent
+            "#,
+        )
+        .bind(signer)
+        .bind(day)
+        .bind(i64::try_from(amount).unwrap_or(i64::MAX))
+        .bind(Utc::now())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|_| ItemError::RepositoryFailure)?;
+        Ok(u64::try_from(spent).unwrap_or(0))
+    }
+}
+
+#[async_trait]
+impl LeaderElection for SqliteClient {
+    #[instrument(skip(self))]
+    async fn try_acquire_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> Result<bool, ItemError> {
+        let now = Utc::now();
+        let leader: Option<String> = sqlx::query_scalar(
+            r#"
+            INSERT INTO leader_leases (name, holder, expires_at)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT (name)
+            DO UPDATE SET holder = excluded.holder, expires_at = excluded.expires_at
+            WHERE leader_leases.holder = excluded.holder OR leader_leases.expires_at <= ?4
+            RETURNING holder
+            "#,
+        )
+        .bind(name)
+        .bind(holder)
+        .bind(now + chrono::Duration::from_std(ttl).unwrap_or_default())
+        .bind(now)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|_| ItemError::RepositoryFailure)?;
+        Ok(leader.is_some())
+    }
+
+    #[instrument(skip(self))]
+    async fn release_lease(&self, name: &str, holder: &str) -> Result<(), ItemError> {
+        sqlx::query("DELETE FROM leader_leases WHERE name = ?1 AND holder = ?2")
+            .bind(name)
+            .bind(holder)
+            .execute(&self.pool)
+            .await
+            .map_err(|_| ItemError::RepositoryFailure)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventLog for SqliteClient {
+    #[instrument(skip(self))]
+    async fn events_after(
+        &self,
+        position: i64,
+        limit: i64,
+    ) -> Result<Vec<ItemStatusEvent>, NotificationError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT position, id, item_id, sequence, event, status, signature, error, occurred_at
+            FROM item_events
+            WHERE position > ?1
+            ORDER BY position
+            LIMIT ?2
+            "#,
+        )
+        .bind(position)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|_| NotificationError::RepositoryFailure)?;
+        Ok(rows.iter().map(Self::row_to_event).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn subscription_cursor(&self, subscription: &str) -> Result<i64, NotificationError> {
+        let position: Option<i64> =
+            sqlx::query_scalar("SELECT last_position FROM webhook_subscriptions WHERE name = ?1")
+                .bind(subscription)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|_| NotificationError::RepositoryFailure)?;
+        Ok(position.unwrap_or(0))
+    }
+
+    #[instrument(skip(self))]
+    async fn save_subscription_cursor(
+        &self,
+        subscription: &str,
+        position: i64,
+    ) -> Result<(), NotificationError> {
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_subscriptions (name, last_position, updated_at)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT (name) DO UPDATE
+            SET last_position = MAX(last_position, excluded.last_position),
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(subscription)
+        .bind(position)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await
+        .map_err(|_| NotificationError::RepositoryFailure)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_events(
+        &self,
+        limit: i64,
+        before: Option<i64>,
+        range: &TimeRange,
+    ) -> Result<PaginatedResponse<ItemStatusEvent>, NotificationError> {
+        let limit = limit.clamp(1, 100);
+        let events = self
+            .log_page(
+                &ITEM_EVENTS_LOG,
+                limit,
+                before,
+                range,
+                &[],
+                Self::row_to_event,
+            )
+            .await?;
+        Ok(PaginatedResponse::from_lookahead(events, limit, |e| {
+            e.position.to_string()
+        }))
+    }
+
+    #[instrument(skip(self))]
+    async fn item_events(&self, item_id: &str) -> Result<Vec<ItemStatusEvent>, NotificationError> {
+        self.item_log(&ITEM_EVENTS_LOG, item_id, Self::row_to_event)
+            .await
+    }
+}
+
+#[async_trait]
+impl RequestJournal for SqliteClient {
+    #[instrument(skip(self))]
+    async fn begin_request(
+        &self,
+        key: &str,
+        request_hash: &str,
+        reclaim_before: DateTime<Utc>,
+    ) -> Result<Option<RequestJournalEntry>, RequestJournalError> {
+        // Insert, or take over a same-request entry left `processing` by a crashed attempt
+        let claimed: Option<String> = sqlx::query_scalar(
+            r#"
+            INSERT INTO request_journal (key, request_hash, status, created_at, updated_at)
+            VALUES (?1, ?2, 'processing', ?3, ?3)
+            ON CONFLICT (key) DO UPDATE
+            SET updated_at = excluded.updated_at
+            WHERE request_journal.status = 'processing'
+              AND request_journal.request_hash = excluded.request_hash
+              AND request_journal.updated_at < ?4
+            RETURNING key
+            "#,
+        )
+        .bind(key)
+        .bind(request_hash)
+        .bind(Utc::now())
+        .bind(reclaim_before)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|_| RequestJournalError::RepositoryFailure)?;
+
+        if claimed.is_some() {
+            return Ok(None);
+        }
+        self.get_request(key).await
+    }
+
+    #[instrument(skip(self, response_body))]
+    async fn complete_request(
+        &self,
+        key: &str,
+        response_status: u16,
+        response_body: &str,
+    ) -> Result<(), RequestJournalError> {
+        sqlx::query(
+            r#"
+            UPDATE request_journal
+            SET status = 'completed',
+                response_status = ?1,
+                response_body = ?2,
+                updated_at = ?3
+            WHERE key = ?4
+            "#,
+        )
+        .bind(response_status)
+        .bind(response_body)
+        .bind(Utc::now())
+        .bind(key)
+        .execute(&self.pool)
+        .await
+        .map_err(|_| RequestJournalError::RepositoryFailure)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn abandon_request(&self, key: &str) -> Result<(), RequestJournalError> {
+        sqlx::query("DELETE FROM request_journal WHERE key = ?1 AND status = 'processing'")
+            .bind(key)
+            .execute(&self.pool)
+            .await
+            .map_err(|_| RequestJournalError::RepositoryFailure)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn get_request(
+        &self,
+        key: &str,
+    ) -> Result<Option<RequestJournalEntry>, RequestJournalError> {
+        let row = sqlx::query(
+            r#"
+            SELECT key, request_hash, status, response_status, response_body, created_at, updated_at
+            FROM request_journal
+            WHERE key = ?1
+            "#,
+        )
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|_| RequestJournalError::RepositoryFailure)?;
+
+        row.map(|row| {
+            let status: String = row.get("status");
+            let response_status: Option<u16> = row.get("response_status");
+            Ok(RequestJournalEntry {
+                key: row.get("key"),
+                request_hash: row.get("request_hash"),
+                status: status
+                    .parse()
+                    .map_err(|_| RequestJournalError::RepositoryFailure)?,
+                response_status,
+                response_body: row.get("response_body"),
+                created_at: row.get("created_at"),
+                updated_at: row.get("updated_at"),
+            })
+        })
+        .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::DEFAULT_CLAIM_TTL;
+    use crate::domain::{DEFAULT_CONTENT_TYPE, ItemFields};
+
+    async fn client() -> SqliteClient {
+        let client = SqliteClient::new("sqlite::memory:").await.unwrap();
+        client.run_migrations().await.unwrap();
+        client
+    }
+
+    #[test]
+    fn test_like_pattern_escapes_wildcards() {
+        assert_eq!(like_pattern("50%_off"), "%50\\%\\_off%");
+        assert_eq!(like_pattern("a\\b"), "%a\\\\b%");
+    }
+
+    #[tokio::test]
+    async fn test_item_lifecycle_with_outbox() {
+        let client = client().await;
+        let item = client
+            .create_item(&CreateItemRequest::new(
+                "Local".to_string(),
+                "Runs on SQLite".to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(item.blockchain_status, BlockchainStatus::PendingSubmission);
+        assert_eq!(client.get_item(&item.id).await.unwrap(), Some(item.clone()));
+
+        let claimed = client
+            .claim_pending_solana_outbox(10, DEFAULT_CLAIM_TTL)
+            .await
+            .unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].aggregate_id, item.id);
+        assert!(
+            client
+                .claim_pending_solana_outbox(10, DEFAULT_CLAIM_TTL)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        client
+            .complete_solana_outbox(&claimed[0].id, &item.id, "sig_1")
+            .await
+            .unwrap();
+        let stored = client.get_item(&item.id).await.unwrap().unwrap();
+        assert_eq!(stored.blockchain_status, BlockchainStatus::Submitted);
+        assert_eq!(stored.blockchain_signature.as_deref(), Some("sig_1"));
+
+        let events = client.events_after(0, 10).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!((events[0].position, events[0].sequence), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_update_item_checks_the_expected_version() {
+        let client = client().await;
+        let item = client
+            .create_item(&CreateItemRequest::new(
+                "Draft".to_string(),
+                "First".to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(item.version, 1);
+
+        let edit = CreateItemRequest::new("Final".to_string(), "Second".to_string());
+        let updated = client.update_item(&item.id, &edit, 1).await.unwrap();
+        assert_eq!((updated.version, updated.name.as_str()), (2, "Final"));
+        assert_eq!(updated.hash, ContentHasher::hash_request(&edit));
+        assert_eq!(updated.blockchain_status, item.blockchain_status);
+
+        assert!(matches!(
+            client.update_item(&item.id, &edit, 1).await,
+            Err(ItemError::Conflict {
+                expected: 1,
+                current: 2,
+                ..
+            })
+        ));
+        client.soft_delete_item(&item.id).await.unwrap();
+        assert!(matches!(
+            client.update_item(&item.id, &edit, 2).await,
+            Err(ItemError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_offloaded_content_stores_only_the_key() {
+        let client = client().await;
+        let request = CreateItemRequest {
+            content_key: Some("items/abc".to_string()),
+            content_type: Some("text/markdown".to_string()),
+            ..CreateItemRequest::new("Large".to_string(), "Large content".to_string())
+        };
+        let item = client.create_item(&request).await.unwrap();
+        assert_eq!(item.hash, ContentHasher::hash_request(&request));
+
+        let stored = client.get_item(&item.id).await.unwrap().unwrap();
+        assert_eq!(stored.content, "");
+        assert_eq!(stored.content_key.as_deref(), Some("items/abc"));
+        // The size still covers the full content
+        assert_eq!(stored.content_type, "text/markdown");
+        assert_eq!(stored.content_length, 13);
+
+        // Content that fits the row again clears the key
+        let edit = CreateItemRequest::new("Small".to_string(), "Small content".to_string());
+        let updated = client.update_item(&item.id, &edit, 1).await.unwrap();
+        assert_eq!(updated.content, "Small content");
+        assert!(updated.content_key.is_none());
+        assert_eq!(updated.content_type, DEFAULT_CONTENT_TYPE);
+    }
+
+    #[tokio::test]
+    async fn test_submission_attempts_append_in_order() {
+        let client = client().await;
+        let item = client
+            .create_item(&CreateItemRequest::new(
+                "Attempts".to_string(),
+                "Content".to_string(),
+            ))
+            .await
+            .unwrap();
+        assert!(
+            client
+                .list_submission_attempts(&item.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let failed = SubmissionAttempt {
+            attempted_at: Utc::now(),
+            duration_ms: 30_000,
+            endpoint: Some("rpc.ankr.com".to_string()),
+            failed_over: vec!["api.mainnet-beta.solana.com".to_string()],
+            succeeded: false,
+            signature: None,
+            error: Some("Timeout: deadline exceeded".to_string()),
+        };
+        let succeeded = SubmissionAttempt {
+            endpoint: Some("api.mainnet-beta.solana.com".to_string()),
+            failed_over: Vec::new(),
+            succeeded: true,
+            signature: Some("sig_1".to_string()),
+            error: None,
+            ..failed.clone()
+        };
+        for attempt in [&failed, &succeeded] {
+            client
+                .record_submission_attempt(&item.id, attempt)
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            client.list_submission_attempts(&item.id).await.unwrap(),
+            vec![failed, succeeded]
+        );
+        assert!(
+            client
+                .list_submission_attempts("missing")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unit_of_work_commits_or_rolls_back_together() {
+        let client = client().await;
+        let request = CreateItemRequest::new("Atomic".to_string(), "Content".to_string());
+
+        let mut tx = client.begin().await.unwrap();
+        let discarded = tx.insert_item(&request).await.unwrap();
+        drop(tx);
+        assert_eq!(client.get_item(&discarded.id).await.unwrap(), None);
+
+        let mut tx = client.begin().await.unwrap();
+        let item = tx.insert_item(&request).await.unwrap();
+        assert_eq!(item.blockchain_status, BlockchainStatus::Pending);
+        let payload = build_solana_outbox_payload_from_request(&item.id, &request);
+        let item = tx.enqueue_solana_outbox(&item.id, &payload).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let stored = client.get_item(&item.id).await.unwrap().unwrap();
+        assert_eq!(
+            stored.blockchain_status,
+            BlockchainStatus::PendingSubmission
+        );
+        let claimed = client
+            .claim_pending_solana_outbox(10, DEFAULT_CLAIM_TTL)
+            .await
+            .unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].payload, payload);
+    }
+
+    #[tokio::test]
+    async fn test_stream_items_pages_through_live_items_in_order() {
+        use futures::TryStreamExt;
+
+        let client = client().await;
+        // One more than a page, so the export resumes from a keyset position
+        for i in 0..=EXPORT_FETCH_BATCH {
+            client
+                .create_item(&CreateItemRequest::new(
+                    format!("Item {i}"),
+                    "Content".to_string(),
+                ))
+                .await
+                .unwrap();
+        }
+        let deleted = client
+            .create_item(&CreateItemRequest::new(
+                "Gone".to_string(),
+                "Content".to_string(),
+            ))
+            .await
+            .unwrap();
+        client.soft_delete_item(&deleted.id).await.unwrap();
+
+        let items: Vec<Item> = client.stream_items().try_collect().await.unwrap();
+        assert_eq!(items.len(), EXPORT_FETCH_BATCH as usize + 1);
+        assert_eq!(items[0].name, "Item 0");
+        assert_eq!(
+            items.last().unwrap().name,
+            format!("Item {EXPORT_FETCH_BATCH}")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_bookmark_resumes_after_acknowledged_changes() {
+        use futures::TryStreamExt;
+
+        let client = client().await;
+        for name in ["First", "Second", "Third"] {
+            client
+                .create_item(&CreateItemRequest::new(
+                    name.to_string(),
+                    "Content".to_string(),
+                ))
+                .await
+                .unwrap();
+        }
+
+        let bookmark = client.export_bookmark("analytics").await.unwrap();
+        assert!(bookmark.position.is_none());
+        let changes: Vec<Item> = client
+            .stream_item_changes(bookmark.position)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(changes.len(), 3);
+
+        let acked = ItemPosition::of(&changes[1]);
+        let bookmark = client
+            .acknowledge_export_bookmark("analytics", &acked)
+            .await
+            .unwrap();
+        assert_eq!(bookmark.position.as_ref(), Some(&acked));
+        assert!(bookmark.acknowledged_at.is_some());
+
+        // An older position does not move the bookmark back
+        let bookmark = client
+            .acknowledge_export_bookmark("analytics", &ItemPosition::of(&changes[0]))
+            .await
+            .unwrap();
+        assert_eq!(bookmark.position.as_ref(), Some(&acked));
+
+        // Only the unacknowledged item, plus any item changed since
+        client
+            .update_blockchain_status(
+                &changes[0].id,
+                BlockchainStatus::Submitted,
+                Some("sig"),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let bookmark = client.export_bookmark("analytics").await.unwrap();
+        let changes: Vec<String> = client
+            .stream_item_changes(bookmark.position)
+            .map_ok(|item| item.name)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(changes, ["Third", "First"]);
+
+        assert!(matches!(
+            client.acknowledge_export_bookmark("unknown", &acked).await,
+            Err(ItemError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_spend_ledger_accumulates_per_signer_and_day() {
+        let client = client().await;
+        let today = Utc::now().date_naive();
+        assert_eq!(client.spent_on("payer", today).await.unwrap(), 0);
+        assert_eq!(
+            client.record_spend("payer", today, 5_000).await.unwrap(),
+            5_000
+        );
+        assert_eq!(
+            client.record_spend("payer", today, 5_000).await.unwrap(),
+            10_000
+        );
+        assert_eq!(client.record_spend("other", today, 1).await.unwrap(), 1);
+        let tomorrow = today.succ_opt().unwrap();
+        assert_eq!(client.spent_on("payer", tomorrow).await.unwrap(), 0);
+        assert_eq!(client.spent_on("payer", today).await.unwrap(), 10_000);
+    }
+
+    #[tokio::test]
+    async fn test_claims_are_reserved_for_the_claim_ttl() {
+        let client = client().await;
+        let item = client
+            .create_item(&CreateItemRequest::new(
+                "Claimed".to_string(),
+                "Reserved for one worker".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        let claimed = client
+            .claim_pending_solana_outbox(10, DEFAULT_CLAIM_TTL)
+            .await
+            .unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert!(
+            client
+                .claim_pending_solana_outbox(10, DEFAULT_CLAIM_TTL)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        // Past the claim the entry counts as abandoned by a crashed worker
+        let reclaimed = client
+            .claim_pending_solana_outbox(10, Duration::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(reclaimed[0].id, claimed[0].id);
+
+        let pending = client
+            .get_pending_blockchain_items(10, DEFAULT_CLAIM_TTL)
+            .await
+            .unwrap();
+        assert_eq!(pending[0].id, item.id);
+        assert!(pending[0].blockchain_next_retry_at.unwrap() > Utc::now());
+        assert!(
+            client
+                .get_pending_blockchain_items(10, DEFAULT_CLAIM_TTL)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_leader_lease_changes_hands_on_expiry_or_release() {
+        let client = client().await;
+        let ttl = Duration::from_secs(60);
+        assert!(client.try_acquire_lease("poller", "a", ttl).await.unwrap());
+        assert!(client.try_acquire_lease("poller", "a", ttl).await.unwrap());
+        assert!(!client.try_acquire_lease("poller", "b", ttl).await.unwrap());
+        assert!(client.try_acquire_lease("other", "b", ttl).await.unwrap());
+
+        // Releasing someone else's lease does nothing
+        client.release_lease("poller", "b").await.unwrap();
+        assert!(!client.try_acquire_lease("poller", "b", ttl).await.unwrap());
+        client.release_lease("poller", "a").await.unwrap();
+        assert!(client.try_acquire_lease("poller", "b", ttl).await.unwrap());
+
+        // An expired lease is free to take
+        assert!(
+            client
+                .try_acquire_lease("poller", "b", Duration::ZERO)
+                .await
+                .unwrap()
+        );
+        assert!(client.try_acquire_lease("poller", "a", ttl).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_job_lifecycle() {
+        let client = client().await;
+        let job = client.create_job("dlq_requeue").await.unwrap();
+        assert_eq!(job.status, JobStatus::Queued);
+        assert!(job.id.starts_with("job_"));
+
+        client.update_job_progress(&job.id, 5, 1).await.unwrap();
+        let running = client.get_job(&job.id).await.unwrap().unwrap();
+        assert_eq!(running.status, JobStatus::Running);
+        assert_eq!((running.processed, running.failed), (5, 1));
+
+        let result = serde_json::json!({ "requeued": 5 });
+        let finished = client
+            .finish_job(&job.id, JobStatus::Succeeded, Some(&result), None)
+            .await
+            .unwrap();
+        assert_eq!(finished.status, JobStatus::Succeeded);
+        assert_eq!(finished.result, Some(result));
+        assert!(finished.finished_at.is_some());
+
+        assert!(client.get_job("job_missing").await.unwrap().is_none());
+        assert!(matches!(
+            client.update_job_progress("job_missing", 1, 0).await,
+            Err(JobError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_migration_status_reports_pending_and_unknown() {
+        let client = SqliteClient::new("sqlite::memory:").await.unwrap();
+        let fresh = client.migration_status().await.unwrap();
+        assert!(!fresh.pending.is_empty());
+        assert!(fresh.unknown.is_empty());
+
+        client.run_migrations().await.unwrap();
+        assert!(client.migration_status().await.unwrap().is_current());
+
+        // A migration applied by a newer build
+        sqlx::query(
+            "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time) \
+             VALUES (29990101000000, 'future', 1, X'00', 0)",
+        )
+        .execute(&client.pool)
+        .await
+        .unwrap();
+        let status = client.migration_status().await.unwrap();
+        assert!(status.pending.is_empty());
+        assert_eq!(status.unknown, vec![29990101000000]);
+    }
+
+    #[tokio::test]
+    async fn test_content_hash_backfill_and_uniqueness() {
+        let client = client().await;
+        let request = CreateItemRequest::new("Same".to_string(), "Content".to_string());
+        let item = client.create_item(&request).await.unwrap();
+        assert_eq!(item.hash, ContentHasher::hash_request(&request));
+
+        // Rows written before content hashing are rewritten by the backfill
+        sqlx::query("UPDATE items SET hash = 'hash_legacy' WHERE id = ?")
+            .bind(&item.id)
+            .execute(&client.pool)
+            .await
+            .unwrap();
+        assert_eq!(client.backfill_content_hashes().await.unwrap(), 1);
+        assert_eq!(client.backfill_content_hashes().await.unwrap(), 0);
+        let stored = client.get_item(&item.id).await.unwrap().unwrap();
+        assert_eq!(stored.hash, item.hash);
+
+        // Duplicates are allowed until uniqueness is enabled...
+        let duplicate = client.create_item(&request).await.unwrap();
+        assert!(client.set_unique_content_hash(true).await.is_err());
+        client.soft_delete_item(&duplicate.id).await.unwrap();
+
+        // ...and rejected after (soft-deleted items do not count)
+        client.set_unique_content_hash(true).await.unwrap();
+        let result = client.create_item(&request).await;
+        assert!(matches!(result, Err(ItemError::DuplicateContent)));
+
+        client.set_unique_content_hash(false).await.unwrap();
+        assert!(client.create_item(&request).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_find_item_by_hash_returns_oldest_live_item() {
+        let client = client().await;
+        let request = CreateItemRequest::new("Item".to_string(), "Content".to_string());
+        let hash = ContentHasher::hash_request(&request);
+        assert!(client.find_item_by_hash(&hash).await.unwrap().is_none());
+
+        let first = client.create_item(&request).await.unwrap();
+        let second = client.create_item(&request).await.unwrap();
+        let found = client.find_item_by_hash(&hash).await.unwrap().unwrap();
+        assert_eq!(found.id, first.id);
+
+        client.soft_delete_item(&first.id).await.unwrap();
+        let found = client.find_item_by_hash(&hash).await.unwrap().unwrap();
+        assert_eq!(found.id, second.id);
+
+        let other_tenant =
+            TenantScope::scope(Some("acme".to_string()), client.find_item_by_hash(&hash))
+                .await
+                .unwrap();
+        assert!(other_tenant.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_dead_letter_and_requeue() {
+        let client = client().await;
+        let item = client
+            .create_item(&CreateItemRequest::new(
+                "Doomed".to_string(),
+                "Content".to_string(),
+            ))
+            .await
+            .unwrap();
+        let entry = client
+            .claim_pending_solana_outbox(10, DEFAULT_CLAIM_TTL)
+            .await
+            .unwrap()
+            .remove(0);
+
+        let failed = client
+            .dead_letter_solana_outbox(&entry.id, &item.id, 10, "rpc error", Some(Some("bh_1")))
+            .await
+            .unwrap();
+        assert_eq!(failed.hash, entry.payload.hash);
+        assert_eq!(failed.retry_count, 10);
+        let stored = client.get_item(&item.id).await.unwrap().unwrap();
+        assert_eq!(stored.blockchain_status, BlockchainStatus::Failed);
+        assert_eq!(
+            client.events_after(0, 10).await.unwrap()[0].event,
+            "item.failed"
+        );
+        assert_eq!(
+            client.list_failed_submissions(10, false).await.unwrap(),
+            vec![failed.clone()]
+        );
+
+        let requeued = client.requeue_failed_submission(&failed.id).await.unwrap();
+        assert_eq!(
+            requeued.blockchain_status,
+            BlockchainStatus::PendingSubmission
+        );
+        let claimed = client
+            .claim_pending_solana_outbox(10, DEFAULT_CLAIM_TTL)
+            .await
+            .unwrap();
+        assert_eq!(claimed[0].attempt_blockhash.as_deref(), Some("bh_1"));
+        assert!(
+            client
+                .list_failed_submissions(10, false)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(matches!(
+            client.requeue_failed_submission(&failed.id).await,
+            Err(ItemError::InvalidState(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_and_keyset_pagination() {
+        let client = client().await;
+        for i in 0..3 {
+            let mut request = CreateItemRequest::new(format!("Item {}", i), "Content".to_string());
+            if i == 1 {
+                request.metadata = Some(crate::domain::ItemMetadataRequest {
+                    author: Some("ada".to_string()),
+                    version: None,
+                    tags: vec!["rust".to_string()],
+                    custom_fields: Default::default(),
+                });
+            }
+            client.create_item_without_outbox(&request).await.unwrap();
+        }
+
+        let filter = ItemListFilter::default();
+        let first = client.list_items(2, None, &filter).await.unwrap();
+        assert_eq!(first.items.len(), 2);
+        assert!(first.has_more);
+        let rest = client
+            .list_items(2, first.next_cursor.as_deref(), &filter)
+            .await
+            .unwrap();
+        assert_eq!(rest.items.len(), 1);
+        assert!(!rest.has_more);
+
+        let tagged = ItemListFilter {
+            tag: Some("rust".to_string()),
+            author: Some("ada".to_string()),
+            ..ItemListFilter::default()
+        };
+        let hits = client.list_items(10, None, &tagged).await.unwrap();
+        assert_eq!(hits.items.len(), 1);
+        assert_eq!(hits.items[0].name, "Item 1");
+    }
+
+    #[tokio::test]
+    async fn test_offset_pages_and_count() {
+        let client = client().await;
+        for i in 0..5 {
+            let mut request = CreateItemRequest::new(format!("Item {}", i), "Content".to_string());
+            if i % 2 == 0 {
+                request.metadata = Some(crate::domain::ItemMetadataRequest {
+                    author: None,
+                    version: None,
+                    tags: vec!["rust".to_string()],
+                    custom_fields: Default::default(),
+                });
+            }
+            client.create_item_without_outbox(&request).await.unwrap();
+        }
+
+        let filter = ItemListFilter {
+            sort: ItemSortField::Name,
+            order: SortOrder::Asc,
+            ..ItemListFilter::default()
+        };
+        let page = client.list_items_at_offset(2, 2, &filter).await.unwrap();
+        let names: Vec<&str> = page.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["Item 2", "Item 3"]);
+        assert!(
+            client
+                .list_items_at_offset(10, 2, &filter)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let count = client.count_items(&filter, Some(0)).await.unwrap();
+        assert_eq!(count, ItemCount::exact(5));
+        let tagged = ItemListFilter {
+            tag: Some("rust".to_string()),
+            ..ItemListFilter::default()
+        };
+        assert_eq!(client.count_items(&tagged, None).await.unwrap().total, 3);
+    }
+
+    #[tokio::test]
+    async fn test_item_tags_follow_metadata_writes() {
+        let client = client().await;
+        let tagged = |name: &str, tags: &[&str]| {
+            let mut request = CreateItemRequest::new(name.to_string(), "Content".to_string());
+            request.metadata = Some(crate::domain::ItemMetadataRequest {
+                author: None,
+                version: None,
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                custom_fields: Default::default(),
+            });
+            request
+        };
+        let tag_counts = |tags: Vec<TagCount>| {
+            tags.into_iter()
+                .map(|t| (t.tag, t.items))
+                .collect::<Vec<_>>()
+        };
+        let first = client
+            .create_item(&tagged("First", &["rust", "db", "rust"]))
+            .await
+            .unwrap();
+        client
+            .create_item(&tagged("Second", &["rust"]))
+            .await
+            .unwrap();
+        let third = client
+            .create_item(&tagged("Third", &["web"]))
+            .await
+            .unwrap();
+        assert_eq!(
+            tag_counts(client.list_tags().await.unwrap()),
+            [
+                ("rust".to_string(), 2),
+                ("db".to_string(), 1),
+                ("web".to_string(), 1)
+            ]
+        );
+
+        // An update replaces the item's tags
+        client
+            .update_item(&first.id, &tagged("First", &["web"]), first.version)
+            .await
+            .unwrap();
+        let web = ItemListFilter {
+            tag: Some("web".to_string()),
+            ..ItemListFilter::default()
+        };
+        let page = client.list_items(10, None, &web).await.unwrap();
+        assert_eq!(page.items.len(), 2);
+
+        // Deleted items no longer count
+        client.soft_delete_item(&third.id).await.unwrap();
+        assert_eq!(
+            tag_counts(client.list_tags().await.unwrap()),
+            [("rust".to_string(), 1), ("web".to_string(), 1)]
+        );
+        assert_eq!(client.count_items(&web, None).await.unwrap().total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_items_reads_only_selected_columns() {
+        let client = client().await;
+        for name in ["Bravo", "Alpha", "Charlie"] {
+            let request = CreateItemRequest::new(name.to_string(), format!("{name} content"));
+            client.create_item(&request).await.unwrap();
+        }
+
+        let filter = ItemListFilter {
+            sort: ItemSortField::Name,
+            order: SortOrder::Asc,
+            columns: Some("id,blockchain_status".to_string().try_into().unwrap()),
+            ..ItemListFilter::default()
+        };
+        let first = client.list_items(2, None, &filter).await.unwrap();
+        // Sorted by the stored name even though it is not read
+        assert!(first.items.iter().all(|item| item.name.is_empty()));
+        assert!(first.items.iter().all(|item| item.content.is_empty()));
+        let rest = client
+            .list_items(2, first.next_cursor.as_deref(), &filter)
+            .await
+            .unwrap();
+        let ids: Vec<String> = first
+            .items
+            .iter()
+            .chain(&rest.items)
+            .map(|i| i.id.clone())
+            .collect();
+        let full = ItemListFilter {
+            sort: ItemSortField::Name,
+            order: SortOrder::Asc,
+            ..ItemListFilter::default()
+        };
+        let expected = client.list_items(10, None, &full).await.unwrap();
+        let names: Vec<&str> = expected.items.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["Alpha", "Bravo", "Charlie"]);
+        let expected: Vec<String> = expected.items.into_iter().map(|i| i.id).collect();
+        assert_eq!(ids, expected);
+
+        let summary = ItemListFilter {
+            columns: Some(ItemFields::summary()),
+            ..ItemListFilter::default()
+        };
+        let page = client.list_items(10, None, &summary).await.unwrap();
+        assert!(page.items.iter().all(|item| item.content.is_empty()));
+        assert!(page.items.iter().all(|item| !item.name.is_empty()));
+        assert!(page.items.iter().all(|item| item.content_length > 0));
+    }
+
+    #[tokio::test]
+    async fn test_items_are_isolated_per_tenant() {
+        let client = client().await;
+        client.set_unique_content_hash(true).await.unwrap();
+        let request = CreateItemRequest::new("Shared".to_string(), "Content".to_string());
+        let acme = Some("acme".to_string());
+        let globex = Some("globex".to_string());
+
+        let item = TenantScope::scope(acme.clone(), client.create_item(&request))
+            .await
+            .unwrap();
+        assert_eq!(item.tenant_id, "acme");
+        // Content hashes are unique per tenant only
+        let other = TenantScope::scope(globex.clone(), client.create_item(&request))
+            .await
+            .unwrap();
+        assert_eq!(other.tenant_id, "globex");
+
+        TenantScope::scope(globex, async {
+            assert!(client.get_item(&item.id).await.unwrap().is_none());
+            let page = client
+                .list_items(10, None, &ItemListFilter::default())
+                .await
+                .unwrap();
+            assert_eq!(page.items.len(), 1);
+            assert_eq!(page.items[0].id, other.id);
+            assert_eq!(client.search_items("shared", 10).await.unwrap().len(), 1);
+            assert!(client.soft_delete_item(&item.id).await.unwrap().is_none());
+            client
+                .update_blockchain_status(&item.id, BlockchainStatus::Failed, None, None, None)
+                .await
+                .unwrap();
+        })
+        .await;
+
+        let stored = TenantScope::scope(acme, client.get_item(&item.id))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!stored.is_deleted());
+        assert_eq!(
+            stored.blockchain_status,
+            BlockchainStatus::PendingSubmission
+        );
+        // Workers run unscoped and see every tenant
+        let all = client
+            .list_items(10, None, &ItemListFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(all.items.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_ranks_name_matches_first() {
+        let client = client().await;
+        for (name, content) in [("Notes", "about the outbox"), ("Outbox", "worker")] {
+            client
+                .create_item_without_outbox(&CreateItemRequest::new(
+                    name.to_string(),
+                    content.to_string(),
+                ))
+                .await
+                .unwrap();
+        }
+        let hits = client.search_items("OUTBOX", 10).await.unwrap();
+        let names: Vec<&str> = hits.iter().map(|h| h.item.name.as_str()).collect();
+        assert_eq!(names, vec!["Outbox", "Notes"]);
+        assert!(client.search_items("   ", 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_request_journal_and_cursor() {
+        let client = client().await;
+        let reclaim_before = Utc::now() - chrono::Duration::minutes(5);
+        assert!(
+            client
+                .begin_request("k1", "h1", reclaim_before)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        client.complete_request("k1", 201, "{}").await.unwrap();
+        let entry = client
+            .begin_request("k1", "h1", reclaim_before)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.response_status, Some(201));
+
+        client.save_subscription_cursor("hook", 5).await.unwrap();
+        client.save_subscription_cursor("hook", 3).await.unwrap();
+        assert_eq!(client.subscription_cursor("hook").await.unwrap(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_log_pages_newest_first_within_range() {
+        let client = client().await;
+        let item = client
+            .create_item_without_outbox(&CreateItemRequest::new(
+                "Logged".to_string(),
+                "Content".to_string(),
+            ))
+            .await
+            .unwrap();
+        for status in [BlockchainStatus::Submitted, BlockchainStatus::Confirmed] {
+            client
+                .update_blockchain_status(&item.id, status, Some("sig"), None, None)
+                .await
+                .unwrap();
+        }
+        for attempt in 1..=3 {
+            client
+                .record_webhook_delivery(&WebhookDelivery {
+                    id: 0,
+                    event_id: "evt_1".to_string(),
+                    event: "item.confirmed".to_string(),
+                    item_id: item.id.clone(),
+                    url: "https://hooks.example.com".to_string(),
+                    attempt,
+                    response_status: Some(503),
+                    error: None,
+                    succeeded: false,
+                    attempted_at: Utc::now(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let all = TimeRange::default();
+        let events = client.list_events(10, None, &all).await.unwrap();
+        assert_eq!(
+            events.items.iter().map(|e| e.status).collect::<Vec<_>>(),
+            [BlockchainStatus::Confirmed, BlockchainStatus::Submitted]
+        );
+
+        let first = client.list_webhook_deliveries(2, None, &all).await.unwrap();
+        assert_eq!(
+            first.items.iter().map(|d| d.attempt).collect::<Vec<_>>(),
+            [3, 2]
+        );
+        assert!(first.has_more);
+        let before = first.next_cursor.unwrap().parse().unwrap();
+        let rest = client
+            .list_webhook_deliveries(2, Some(before), &all)
+            .await
+            .unwrap();
+        assert_eq!(rest.items.len(), 1);
+        assert_eq!(rest.items[0].attempt, 1);
+        assert!(!rest.has_more);
+
+        let future = TimeRange {
+            since: Some(Utc::now() + chrono::Duration::hours(1)),
+            until: None,
+        };
+        assert!(
+            client
+                .list_webhook_deliveries(10, None, &future)
+                .await
+                .unwrap()
+                .items
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_audit_entries_filter_and_page() {
+        let client = client().await;
+        for (principal, action, resource) in [
+            ("key_a", "item.create", "item_1"),
+            ("key_a", "item.update", "item_1"),
+            ("key_b", "api_key.revoke", "key_a"),
+        ] {
+            let mut entry = AuditEntry::new(action, Some(resource), None);
+            entry.principal = principal.to_string();
+            entry.diff = Some(serde_json::json!({ "name": { "from": null, "to": "x" } }));
+            client.record_audit_entry(&entry).await.unwrap();
+        }
+
+        let all = AuditFilter::default();
+        let first = client.list_audit_entries(2, None, &all).await.unwrap();
+        assert_eq!(
+            first
+                .items
+                .iter()
+                .map(|e| e.action.as_str())
+                .collect::<Vec<_>>(),
+            ["api_key.revoke", "item.update"]
+        );
+        assert_eq!(first.items[0].diff.as_ref().unwrap()["name"]["to"], "x");
+        let before = first.next_cursor.unwrap().parse().unwrap();
+        let rest = client
+            .list_audit_entries(2, Some(before), &all)
+            .await
+            .unwrap();
+        assert_eq!(rest.items.len(), 1);
+        assert!(!rest.has_more);
+
+        let filter = AuditFilter {
+            principal: Some("key_a".to_string()),
+            resource_id: Some("item_1".to_string()),
+            ..AuditFilter::default()
+        };
+        let page = client.list_audit_entries(10, None, &filter).await.unwrap();
+        assert_eq!(page.items.len(), 2);
+        let filter = AuditFilter {
+            action: Some("api_key.revoke".to_string()),
+            ..AuditFilter::default()
+        };
+        let page = client.list_audit_entries(10, None, &filter).await.unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].principal, "key_b");
+    }
+
+    #[tokio::test]
+    async fn test_content_is_encrypted_at_rest_and_reencrypted() {
+        use crate::infra::{
+            ENCRYPTED_CONTENT_PREFIX, EnvelopeEncryption, LocalMasterKey, MasterKey,
+        };
+        use base64::{Engine, engine::general_purpose::STANDARD};
+        use secrecy::SecretString;
+
+        fn local_key(byte: u8) -> Arc<dyn MasterKey> {
+            let key = SecretString::from(STANDARD.encode([byte; 32]));
+            Arc::new(LocalMasterKey::from_base64(&key).unwrap())
+        }
+        async fn stored_content(client: &SqliteClient, id: &str) -> String {
+            sqlx::query_scalar("SELECT content FROM items WHERE id = ?1")
+                .bind(id)
+                .fetch_one(client.pool())
+                .await
+                .unwrap()
+        }
+
+        let client = client().await;
+        assert!(matches!(
+            client.reencrypt_content(None, 10).await,
+            Err(ItemError::InvalidState(_))
+        ));
+        let legacy = client
+            .create_item(&CreateItemRequest::new(
+                "Legacy".to_string(),
+                "written before encryption".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        let old_key = local_key(1);
+        let client = client.with_encryption(Arc::new(EnvelopeEncryption::new(
+            Arc::clone(&old_key),
+            Vec::new(),
+        )));
+        let item = client
+            .create_item(&CreateItemRequest::new(
+                "Secret".to_string(),
+                "classified text".to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(item.content, "classified text");
+        let stored = stored_content(&client, &item.id).await;
+        assert!(stored.starts_with(ENCRYPTED_CONTENT_PREFIX));
+        assert!(!stored.contains("classified"));
+        let read = client.get_item(&item.id).await.unwrap().unwrap();
+        assert_eq!(read.content, "classified text");
+        assert_eq!(read.hash, item.hash);
+        // Encrypted content is not searchable; plaintext rows still are
+        assert!(
+            client
+                .search_items("classified", 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(client.search_items("before", 10).await.unwrap().len(), 1);
+
+        // Rotating the master key keeps old values readable until they are re-encrypted
+        let client = client.with_encryption(Arc::new(EnvelopeEncryption::new(
+            local_key(2),
+            vec![old_key],
+        )));
+        assert_eq!(
+            client.get_item(&item.id).await.unwrap().unwrap().content,
+            "classified text"
+        );
+        let first = client.reencrypt_content(None, 1).await.unwrap();
+        assert_eq!(first.scanned, 1);
+        let rest = client
+            .reencrypt_content(first.last_id.as_deref(), 10)
+            .await
+            .unwrap();
+        assert_eq!(rest.scanned, 1);
+        assert_eq!(first.reencrypted + rest.reencrypted, 2);
+        assert_ne!(stored_content(&client, &item.id).await, stored);
+        assert!(
+            stored_content(&client, &legacy.id)
+                .await
+                .starts_with(ENCRYPTED_CONTENT_PREFIX)
+        );
+        assert_eq!(
+            client.get_item(&legacy.id).await.unwrap().unwrap().content,
+            "written before encryption"
+        );
+        let again = client.reencrypt_content(None, 10).await.unwrap();
+        assert_eq!((again.scanned, again.reencrypted), (2, 0));
+    }
+
+    #[tokio::test]
+    async fn test_usage_ledger_counts_per_day_and_month() {
+        let client = client().await;
+        let quota = ApiKeyQuota {
+            daily: Some(2),
+            monthly: Some(10),
+        };
+        let key = client
+            .create_api_key(
+                "ci",
+                &"a".repeat(64),
+                &[ApiKeyScope::ItemsRead],
+                "acme",
+                quota,
+            )
+            .await
+            .unwrap();
+        assert_eq!(key.quota, quota);
+        assert_eq!(
+            client
+                .find_api_key_by_hash(&"a".repeat(64))
+                .await
+                .unwrap()
+                .unwrap()
+                .quota,
+            quota
+        );
+
+        let day = NaiveDate::from_ymd_opt(2026, 5, 20).unwrap();
+        let last_month = NaiveDate::from_ymd_opt(2026, 4, 30).unwrap();
+        client.record_request(&key.id, last_month).await.unwrap();
+        client
+            .record_request(&key.id, NaiveDate::from_ymd_opt(2026, 5, 1).unwrap())
+            .await
+            .unwrap();
+        client.record_request(&key.id, day).await.unwrap();
+        let usage = client.record_request(&key.id, day).await.unwrap();
+        assert_eq!(
+            usage,
+            QuotaUsage {
+                daily: 2,
+                monthly: 3
+            }
+        );
+        client.record_request("key_gone", day).await.unwrap();
+
+        let report = client.usage_on(day).await.unwrap();
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].key_id, key.id);
+        assert_eq!(report[0].name.as_deref(), Some("ci"));
+        assert_eq!(report[0].daily_requests, 2);
+        assert_eq!(report[0].monthly_requests, 3);
+        assert_eq!(report[0].quota, quota);
+        assert_eq!(report[1].key_id, "key_gone");
+        assert!(report[1].name.is_none());
+        assert_eq!(
+            client.usage_on(last_month).await.unwrap()[0].daily_requests,
+            1
+        );
+    }
+}
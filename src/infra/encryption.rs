@@ -0,0 +1,488 @@
+//! Envelope encryption of item content at rest ([`EncryptionService`]).
+//!
+//! [`EnvelopeEncryption`] encrypts content with AES-256-GCM under a data key and stores
+//! the data key next to the ciphertext, wrapped by a master key: a local 256-bit key
+//! ([`LocalMasterKey`]) or an AWS KMS key ([`KmsMasterKey`]). A stored value reads
+//!
+//! ```text
+//! enc:v1:<master key ID>:<wrapped data key>:<nonce and ciphertext>
+//! ```
+//!
+//! with the binary parts in base64. Each instance creates a data key on its first write
+//! and replaces it once it is older than the configured lifetime. Unwrapped data keys are
+//! cached, so KMS is called once per data key rather than once per item.
+//!
+//! Rotating the master key means configuring a new one and keeping the old one as
+//! retired: values under retired keys still decrypt, and
+//! [`ItemRepository::reencrypt_content`](crate::domain::ItemRepository::reencrypt_content)
+//! rewrites them, together with plaintext stored before encryption was enabled, under
+//! the new key.
+
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use async_trait::async_trait;
+use aws_config::BehaviorVersion;
+use aws_sdk_kms::primitives::Blob;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use lru::LruCache;
+use secrecy::{ExposeSecret, SecretString};
+use sha2::{Digest, Sha256};
+use tracing::info;
+
+use crate::domain::{EncryptionError, EncryptionService};
+
+/// Prefix of every encrypted value; content without it was stored as plaintext
+pub const ENCRYPTED_CONTENT_PREFIX: &str = "enc:v1:";
+
+/// Data key lifetime when `CONTENT_ENCRYPTION_DATA_KEY_TTL_SECS` is unset
+pub const DEFAULT_DATA_KEY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Unwrapped data keys kept in memory
+const DATA_KEY_CACHE_CAPACITY: NonZeroUsize = NonZeroUsize::new(1024).unwrap();
+
+/// AES-GCM nonce length in bytes
+const NONCE_LEN: usize = 12;
+
+/// Key that wraps (encrypts) the data keys
+#[async_trait]
+pub trait MasterKey: Send + Sync {
+    /// ID stored with every value, used to find the key again on decryption
+    fn id(&self) -> &str;
+
+    async fn wrap(&self, data_key: &[u8]) -> Result<Vec<u8>, EncryptionError>;
+
+    async fn unwrap(&self, wrapped: &[u8]) -> Result<Vec<u8>, EncryptionError>;
+}
+
+/// First bytes of the SHA-256 of `input`, in hex: a stable ID that does not reveal it
+fn fingerprint(input: &[u8]) -> String {
+    Sha256::digest(input)[..4]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Master key held in memory (AES-256-GCM), ID `local-<fingerprint>`
+pub struct LocalMasterKey {
+    id: String,
+    cipher: Aes256Gcm,
+}
+
+impl LocalMasterKey {
+    /// Build from a base64-encoded 32-byte key
+    pub fn from_base64(key: &SecretString) -> Result<Self, EncryptionError> {
+        let bytes = STANDARD
+            .decode(key.expose_secret().trim())
+            .ok()
+            .filter(|bytes| bytes.len() == 32)
+            .ok_or_else(|| {
+                EncryptionError::KeyUnavailable(
+                    "local master key must be 32 bytes in base64".to_string(),
+                )
+            })?;
+        Ok(Self {
+            id: format!("local-{}", fingerprint(&bytes)),
+            cipher: Aes256Gcm::new_from_slice(&bytes).map_err(|_| EncryptionError::Corrupt)?,
+        })
+    }
+}
+
+#[async_trait]
+impl MasterKey for LocalMasterKey {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn wrap(&self, data_key: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        seal(&self.cipher, data_key)
+    }
+
+    async fn unwrap(&self, wrapped: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        open(&self.cipher, wrapped)
+    }
+}
+
+/// Master key in AWS KMS (`Encrypt` / `Decrypt`), ID `kms-<fingerprint of the key ID>`
+pub struct KmsMasterKey {
+    id: String,
+    key_id: String,
+    client: aws_sdk_kms::Client,
+}
+
+impl KmsMasterKey {
+    /// Use the KMS key `key_id` (ID, ARN or alias); AWS configuration comes from the
+    /// environment as for the KMS signers
+    pub async fn new(key_id: String) -> Self {
+        let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+        Self {
+            id: format!("kms-{}", fingerprint(key_id.as_bytes())),
+            key_id,
+            client: aws_sdk_kms::Client::new(&config),
+        }
+    }
+}
+
+#[async_trait]
+impl MasterKey for KmsMasterKey {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn wrap(&self, data_key: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let response = self
+            .client
+            .encrypt()
+            .key_id(&self.key_id)
+            .plaintext(Blob::new(data_key))
+            .send()
+            .await
+            .map_err(|e| EncryptionError::KeyUnavailable(format!("KMS Encrypt failed: {e}")))?;
+        response
+            .ciphertext_blob
+            .map(Blob::into_inner)
+            .ok_or_else(|| EncryptionError::KeyUnavailable("KMS returned no ciphertext".into()))
+    }
+
+    async fn unwrap(&self, wrapped: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let response = self
+            .client
+            .decrypt()
+            .key_id(&self.key_id)
+            .ciphertext_blob(Blob::new(wrapped))
+            .send()
+            .await
+            .map_err(|e| EncryptionError::KeyUnavailable(format!("KMS Decrypt failed: {e}")))?;
+        response
+            .plaintext
+            .map(Blob::into_inner)
+            .ok_or_else(|| EncryptionError::KeyUnavailable("KMS returned no plaintext".into()))
+    }
+}
+
+/// Master key setting: `kms:<key ID, ARN or alias>` or a base64-encoded 32-byte key
+#[derive(Debug, Clone)]
+pub enum MasterKeySpec {
+    Local(SecretString),
+    Kms(String),
+}
+
+impl MasterKeySpec {
+    #[must_use]
+    pub fn parse(value: &str) -> Self {
+        match value.trim().strip_prefix("kms:") {
+            Some(key_id) => Self::Kms(key_id.to_string()),
+            None => Self::Local(SecretString::from(value.trim().to_string())),
+        }
+    }
+
+    /// Build the key (KMS keys are not contacted until the first wrap or unwrap)
+    pub async fn build(&self) -> Result<Arc<dyn MasterKey>, EncryptionError> {
+        Ok(match self {
+            Self::Local(key) => Arc::new(LocalMasterKey::from_base64(key)?),
+            Self::Kms(key_id) => Arc::new(KmsMasterKey::new(key_id.clone()).await),
+        })
+    }
+}
+
+/// Content encryption settings (`CONTENT_ENCRYPTION_KEY`, `CONTENT_ENCRYPTION_RETIRED_KEYS`,
+/// `CONTENT_ENCRYPTION_DATA_KEY_TTL_SECS`)
+#[derive(Debug, Clone)]
+pub struct EncryptionConfig {
+    /// Key new data keys are wrapped with
+    pub master_key: MasterKeySpec,
+    /// Earlier master keys, kept to decrypt values not yet re-encrypted
+    pub retired_keys: Vec<MasterKeySpec>,
+    /// How long an instance keeps encrypting with one data key
+    pub data_key_ttl: Duration,
+}
+
+impl EncryptionConfig {
+    /// Create config from environment variables (None when `CONTENT_ENCRYPTION_KEY` is unset)
+    pub fn from_env() -> Option<Self> {
+        let master_key = std::env::var("CONTENT_ENCRYPTION_KEY")
+            .ok()
+            .filter(|v| !v.trim().is_empty())?;
+        let retired_keys = std::env::var("CONTENT_ENCRYPTION_RETIRED_KEYS")
+            .unwrap_or_default()
+            .split(',')
+            .filter(|v| !v.trim().is_empty())
+            .map(MasterKeySpec::parse)
+            .collect();
+        let data_key_ttl = std::env::var("CONTENT_ENCRYPTION_DATA_KEY_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v > 0)
+            .map_or(DEFAULT_DATA_KEY_TTL, Duration::from_secs);
+        Some(Self {
+            master_key: MasterKeySpec::parse(&master_key),
+            retired_keys,
+            data_key_ttl,
+        })
+    }
+}
+
+/// Data key new values are encrypted with
+struct DataKey {
+    cipher: Aes256Gcm,
+    /// Base64 of the key wrapped by the current master key
+    wrapped: String,
+    created_at: Instant,
+}
+
+/// [`EncryptionService`] with per-instance data keys wrapped by a master key
+pub struct EnvelopeEncryption {
+    master: Arc<dyn MasterKey>,
+    retired: Vec<Arc<dyn MasterKey>>,
+    data_key_ttl: Duration,
+    current: tokio::sync::Mutex<Option<DataKey>>,
+    /// Unwrapped data keys by wrapped key (base64)
+    data_keys: Mutex<LruCache<String, Aes256Gcm>>,
+}
+
+impl EnvelopeEncryption {
+    #[must_use]
+    pub fn new(master: Arc<dyn MasterKey>, retired: Vec<Arc<dyn MasterKey>>) -> Self {
+        Self {
+            master,
+            retired,
+            data_key_ttl: DEFAULT_DATA_KEY_TTL,
+            current: tokio::sync::Mutex::new(None),
+            data_keys: Mutex::new(LruCache::new(DATA_KEY_CACHE_CAPACITY)),
+        }
+    }
+
+    /// Build the master keys named in `config`
+    pub async fn from_config(config: &EncryptionConfig) -> Result<Self, EncryptionError> {
+        let master = config.master_key.build().await?;
+        let mut retired = Vec::with_capacity(config.retired_keys.len());
+        for spec in &config.retired_keys {
+            retired.push(spec.build().await?);
+        }
+        Ok(Self::new(master, retired).with_data_key_ttl(config.data_key_ttl))
+    }
+
+    /// Replace the data key after `ttl` (default [`DEFAULT_DATA_KEY_TTL`])
+    #[must_use]
+    pub fn with_data_key_ttl(mut self, ttl: Duration) -> Self {
+        self.data_key_ttl = ttl;
+        self
+    }
+
+    /// ID of the master key new values are wrapped with
+    #[must_use]
+    pub fn master_key_id(&self) -> &str {
+        self.master.id()
+    }
+
+    async fn create_data_key(&self) -> Result<DataKey, EncryptionError> {
+        let key = Aes256Gcm::generate_key(OsRng);
+        let wrapped = STANDARD.encode(self.master.wrap(&key).await?);
+        let cipher = Aes256Gcm::new(&key);
+        self.cache_data_key(&wrapped, cipher.clone());
+        metrics::counter!("content_data_keys_created_total").increment(1);
+        info!(master_key = %self.master.id(), "Created content data key");
+        Ok(DataKey {
+            cipher,
+            wrapped,
+            created_at: Instant::now(),
+        })
+    }
+
+    fn cache_data_key(&self, wrapped: &str, cipher: Aes256Gcm) {
+        if let Ok(mut keys) = self.data_keys.lock() {
+            keys.put(wrapped.to_string(), cipher);
+        }
+    }
+
+    /// Data key `wrapped` by the master key `master_id`, from the cache or unwrapped
+    async fn data_key(&self, master_id: &str, wrapped: &str) -> Result<Aes256Gcm, EncryptionError> {
+        let cached = self
+            .data_keys
+            .lock()
+            .ok()
+            .and_then(|mut keys| keys.get(wrapped).cloned());
+        if let Some(cipher) = cached {
+            return Ok(cipher);
+        }
+        let master = std::iter::once(&self.master)
+            .chain(&self.retired)
+            .find(|key| key.id() == master_id)
+            .ok_or_else(|| EncryptionError::UnknownKey(master_id.to_string()))?;
+        let bytes = STANDARD
+            .decode(wrapped)
+            .map_err(|_| EncryptionError::Corrupt)?;
+        let key = master.unwrap(&bytes).await?;
+        let cipher = Aes256Gcm::new_from_slice(&key).map_err(|_| EncryptionError::Corrupt)?;
+        self.cache_data_key(wrapped, cipher.clone());
+        Ok(cipher)
+    }
+}
+
+/// Random nonce followed by the AES-GCM ciphertext of `plaintext`
+fn seal(cipher: &Aes256Gcm, plaintext: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| EncryptionError::Corrupt)?;
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+/// Inverse of [`seal`]; fails on a wrong key or altered bytes
+fn open(cipher: &Aes256Gcm, sealed: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+    if sealed.len() < NONCE_LEN {
+        return Err(EncryptionError::Corrupt);
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| EncryptionError::Corrupt)
+}
+
+#[async_trait]
+impl EncryptionService for EnvelopeEncryption {
+    async fn encrypt(&self, plaintext: &str) -> Result<String, EncryptionError> {
+        let mut current = self.current.lock().await;
+        let key = match current.take() {
+            Some(key) if key.created_at.elapsed() < self.data_key_ttl => key,
+            _ => self.create_data_key().await?,
+        };
+        let sealed = seal(&key.cipher, plaintext.as_bytes());
+        let value = sealed.map(|sealed| {
+            format!(
+                "{ENCRYPTED_CONTENT_PREFIX}{}:{}:{}",
+                self.master.id(),
+                key.wrapped,
+                STANDARD.encode(sealed)
+            )
+        });
+        *current = Some(key);
+        value
+    }
+
+    async fn decrypt(&self, stored: &str) -> Result<String, EncryptionError> {
+        let Some(rest) = stored.strip_prefix(ENCRYPTED_CONTENT_PREFIX) else {
+            return Ok(stored.to_string());
+        };
+        let mut parts = rest.splitn(3, ':');
+        let (Some(master_id), Some(wrapped), Some(sealed)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(EncryptionError::Corrupt);
+        };
+        let cipher = self.data_key(master_id, wrapped).await?;
+        let sealed = STANDARD
+            .decode(sealed)
+            .map_err(|_| EncryptionError::Corrupt)?;
+        String::from_utf8(open(&cipher, &sealed)?).map_err(|_| EncryptionError::Corrupt)
+    }
+
+    fn needs_reencryption(&self, stored: &str) -> bool {
+        stored
+            .strip_prefix(ENCRYPTED_CONTENT_PREFIX)
+            .and_then(|rest| rest.split(':').next())
+            .is_none_or(|master_id| master_id != self.master.id())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local_key(byte: u8) -> Arc<dyn MasterKey> {
+        let key = SecretString::from(STANDARD.encode([byte; 32]));
+        Arc::new(LocalMasterKey::from_base64(&key).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_and_plaintext_passthrough() {
+        let service = EnvelopeEncryption::new(local_key(1), Vec::new());
+        let stored = service.encrypt("secret content").await.unwrap();
+        assert!(stored.starts_with(ENCRYPTED_CONTENT_PREFIX));
+        assert!(stored.contains(service.master_key_id()));
+        assert!(!stored.contains("secret"));
+        assert_ne!(service.encrypt("secret content").await.unwrap(), stored);
+        assert_eq!(service.decrypt(&stored).await.unwrap(), "secret content");
+        assert!(!service.needs_reencryption(&stored));
+
+        // Rows from before encryption was enabled read as they are
+        assert_eq!(service.decrypt("plain").await.unwrap(), "plain");
+        assert!(service.needs_reencryption("plain"));
+    }
+
+    #[tokio::test]
+    async fn test_tampered_values_are_rejected() {
+        let service = EnvelopeEncryption::new(local_key(1), Vec::new());
+        let stored = service.encrypt("secret content").await.unwrap();
+        let (head, sealed) = stored.rsplit_once(':').unwrap();
+        let mut bytes = STANDARD.decode(sealed).unwrap();
+        bytes[NONCE_LEN] ^= 1;
+        let tampered = format!("{head}:{}", STANDARD.encode(bytes));
+        assert!(matches!(
+            service.decrypt(&tampered).await,
+            Err(EncryptionError::Corrupt)
+        ));
+        assert!(matches!(
+            service.decrypt("enc:v1:truncated").await,
+            Err(EncryptionError::Corrupt)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_master_key_rotation() {
+        let old = EnvelopeEncryption::new(local_key(1), Vec::new());
+        let stored = old.encrypt("rotated").await.unwrap();
+
+        let rotated = EnvelopeEncryption::new(local_key(2), vec![local_key(1)]);
+        assert_eq!(rotated.decrypt(&stored).await.unwrap(), "rotated");
+        assert!(rotated.needs_reencryption(&stored));
+        let rewritten = rotated.encrypt("rotated").await.unwrap();
+        assert!(!rotated.needs_reencryption(&rewritten));
+
+        let without_old = EnvelopeEncryption::new(local_key(2), Vec::new());
+        assert!(matches!(
+            without_old.decrypt(&stored).await,
+            Err(EncryptionError::UnknownKey(id)) if id == old.master_key_id()
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_data_key_is_replaced_after_ttl() {
+        let wrapped_key = |stored: &str| stored.split(':').nth(3).unwrap().to_string();
+        let service = EnvelopeEncryption::new(local_key(1), Vec::new());
+        let first = service.encrypt("a").await.unwrap();
+        assert_eq!(
+            wrapped_key(&first),
+            wrapped_key(&service.encrypt("b").await.unwrap())
+        );
+
+        let service = service.with_data_key_ttl(Duration::ZERO);
+        let second = service.encrypt("c").await.unwrap();
+        assert_ne!(wrapped_key(&first), wrapped_key(&second));
+        assert_eq!(service.decrypt(&first).await.unwrap(), "a");
+        assert_eq!(service.decrypt(&second).await.unwrap(), "c");
+    }
+
+    #[tokio::test]
+    async fn test_master_key_spec() {
+        assert!(matches!(
+            MasterKeySpec::parse("kms:alias/content"),
+            MasterKeySpec::Kms(id) if id == "alias/content"
+        ));
+        assert!(matches!(
+            MasterKeySpec::parse("c2hvcnQ=").build().await,
+            Err(EncryptionError::KeyUnavailable(_))
+        ));
+        let key = MasterKeySpec::parse(&STANDARD.encode([7u8; 32]))
+            .build()
+            .await
+            .unwrap();
+        assert!(key.id().starts_with("local-"));
+    }
+}
@@ -0,0 +1,105 @@
+//! [`IdGenerator`] implementations: UUIDv7 (the default) and ULID.
+//!
+//! Both embed the creation time in their leading bits, so new rows land at the end of
+//! the primary key index and IDs generated by one process sort in creation order. A
+//! ULID is the shorter of the two (26 Crockford base32 characters against 36).
+
+use std::sync::{Arc, Mutex};
+
+use uuid::Uuid;
+
+use crate::domain::IdGenerator;
+
+/// Format of new row IDs (`ID_FORMAT`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdFormat {
+    /// e.g. `item_01927b0e-8f3a-7c21-9a4e-5d2f1b3c4d5e`
+    #[default]
+    UuidV7,
+    /// e.g. `item_01J9XQ3M7Z8K4N2P5R6S7T8V9W`
+    Ulid,
+}
+
+impl std::str::FromStr for IdFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "uuidv7" | "uuid" => Ok(Self::UuidV7),
+            "ulid" => Ok(Self::Ulid),
+            other => Err(format!(
+                "unknown ID format '{other}' (expected uuidv7 or ulid)"
+            )),
+        }
+    }
+}
+
+impl IdFormat {
+    /// A generator of this format
+    #[must_use]
+    pub fn generator(self) -> Arc<dyn IdGenerator> {
+        match self {
+            Self::UuidV7 => Arc::new(UuidV7IdGenerator),
+            Self::Ulid => Arc::new(UlidIdGenerator::default()),
+        }
+    }
+}
+
+/// Hyphenated UUIDv7s
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UuidV7IdGenerator;
+
+impl IdGenerator for UuidV7IdGenerator {
+    fn generate(&self, _kind: &'static str) -> String {
+        Uuid::now_v7().to_string()
+    }
+}
+
+/// ULIDs, strictly increasing within this generator (several in one millisecond
+/// increment the random part)
+#[derive(Default)]
+pub struct UlidIdGenerator {
+    generator: Mutex<ulid::Generator>,
+}
+
+impl IdGenerator for UlidIdGenerator {
+    fn generate(&self, _kind: &'static str) -> String {
+        self.generator
+            .lock()
+            .unwrap()
+            .generate()
+            // The random part only overflows after 2^80 IDs in one millisecond
+            .unwrap_or_else(|_| ulid::Ulid::new())
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id_format_parses_case_insensitively() {
+        assert_eq!("ULID".parse::<IdFormat>(), Ok(IdFormat::Ulid));
+        assert_eq!("uuidv7".parse::<IdFormat>(), Ok(IdFormat::UuidV7));
+        assert!("snowflake".parse::<IdFormat>().is_err());
+    }
+
+    #[test]
+    fn test_generated_ids_sort_in_creation_order() {
+        for format in [IdFormat::UuidV7, IdFormat::Ulid] {
+            let generator = format.generator();
+            let ids: Vec<String> = (0..1000).map(|_| generator.generate("item")).collect();
+            let mut sorted = ids.clone();
+            sorted.sort();
+            assert_eq!(ids, sorted, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn test_ulid_ids_are_canonical() {
+        let id = UlidIdGenerator::default().generate("item");
+        assert_eq!(id.len(), 26);
+        assert!(id.parse::<ulid::Ulid>().is_ok());
+    }
+}
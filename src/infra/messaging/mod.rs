@@ -0,0 +1,117 @@
+//! [`MessagePublisher`](crate::domain::MessagePublisher) and
+//! [`MessageSubscriber`](crate::domain::MessageSubscriber) implementations.
+//!
+//! With the `nats` feature, [`NatsPublisher`] publishes each [`DomainEvent`] as JSON to
+//! `<prefix>.<event name>`, e.g. `items.item.created`, with the event ID in `Nats-Msg-Id`
+//! so a JetStream stream on those subjects drops duplicates. [`NatsSubscriber`] reads
+//! them back and dead-letters to `<dead-letter prefix>.<original subject>`, e.g.
+//! `dlq.items.item.created`, outside the consumed subjects.
+
+#[cfg(feature = "nats")]
+mod nats;
+
+#[cfg(feature = "nats")]
+pub use nats::{NatsPublisher, NatsSubscriber};
+
+use crate::domain::DomainEvent;
+
+/// Subject prefix used when `NATS_SUBJECT_PREFIX` is unset
+pub const DEFAULT_NATS_SUBJECT_PREFIX: &str = "items";
+
+/// Dead-letter subject prefix used when `NATS_DEAD_LETTER_PREFIX` is unset
+pub const DEFAULT_NATS_DEAD_LETTER_PREFIX: &str = "dlq";
+
+/// NATS connection settings (`NATS_URL`, `NATS_SUBJECT_PREFIX`,
+/// `NATS_DEAD_LETTER_PREFIX`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NatsConfig {
+    /// Server URL(s), comma-separated (e.g. `nats://localhost:4222`)
+    pub url: String,
+    pub subject_prefix: String,
+    pub dead_letter_prefix: String,
+}
+
+impl NatsConfig {
+    #[must_use]
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            subject_prefix: DEFAULT_NATS_SUBJECT_PREFIX.to_string(),
+            dead_letter_prefix: DEFAULT_NATS_DEAD_LETTER_PREFIX.to_string(),
+        }
+    }
+
+    /// Create config from environment variables (None when `NATS_URL` is unset)
+    pub fn from_env() -> Option<Self> {
+        let url = std::env::var("NATS_URL").ok().filter(|v| !v.is_empty())?;
+        let prefix = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().trim_end_matches('.').to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let mut config = Self::new(url);
+        if let Some(prefix) = prefix("NATS_SUBJECT_PREFIX") {
+            config.subject_prefix = prefix;
+        }
+        if let Some(prefix) = prefix("NATS_DEAD_LETTER_PREFIX") {
+            config.dead_letter_prefix = prefix;
+        }
+        Some(config)
+    }
+
+    /// Subject `event` is published to
+    #[must_use]
+    pub fn subject(&self, event: &DomainEvent) -> String {
+        format!("{}.{}", self.subject_prefix, event.name())
+    }
+
+    /// Subject a message received on `subject` is dead-lettered to
+    #[must_use]
+    pub fn dead_letter_subject(&self, subject: &str) -> String {
+        format!("{}.{}", self.dead_letter_prefix, subject)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::DomainEventKind;
+
+    #[test]
+    fn test_subject_is_prefix_and_event_name() {
+        let mut config = NatsConfig::new("nats://localhost:4222");
+        let event = DomainEvent::new(
+            "item_1",
+            DomainEventKind::ItemConfirmed {
+                signature: "sig".to_string(),
+            },
+        );
+        assert_eq!(config.subject(&event), "items.item.confirmed");
+
+        config.subject_prefix = "prod.events".to_string();
+        assert_eq!(config.subject(&event), "prod.events.item.confirmed");
+        assert_eq!(
+            config.dead_letter_subject("prod.events.item.confirmed"),
+            "dlq.prod.events.item.confirmed"
+        );
+    }
+
+    #[test]
+    fn test_event_serializes_flat_with_type_tag() {
+        let event = DomainEvent::new(
+            "item_1",
+            DomainEventKind::ItemFailed {
+                error: "Insufficient funds for transaction".to_string(),
+            },
+        );
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "item_failed");
+        assert_eq!(json["item_id"], "item_1");
+        assert_eq!(json["error"], "Insufficient funds for transaction");
+        assert!(json["id"].as_str().unwrap().starts_with("evt_"));
+
+        let parsed: DomainEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, event);
+    }
+}
@@ -0,0 +1,118 @@
+//! NATS implementations of [`MessagePublisher`] and [`MessageSubscriber`] (core NATS
+//! publish and subscribe; subjects may be captured by a JetStream stream for
+//! durability).
+
+use std::time::Duration;
+
+use async_nats::{Client, ConnectOptions, HeaderMap, header::NATS_MESSAGE_ID};
+use async_trait::async_trait;
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use tracing::instrument;
+
+use super::NatsConfig;
+use crate::domain::{
+    DomainEvent, InboundMessage, MessagePublisher, MessageSubscriber, MessagingError,
+};
+
+/// Header carrying the reason a message was dead-lettered
+const DEAD_LETTER_REASON_HEADER: &str = "Dead-Letter-Reason";
+
+/// Publishes domain events to NATS as JSON
+pub struct NatsPublisher {
+    client: Client,
+    config: NatsConfig,
+}
+
+impl NatsPublisher {
+    /// Connect to `config.url`; reconnects happen in the background afterwards, with
+    /// publishes buffered by the client meanwhile
+    pub async fn connect(config: NatsConfig) -> Result<Self, MessagingError> {
+        let client = ConnectOptions::new()
+            .name(env!("CARGO_PKG_NAME"))
+            .connection_timeout(Duration::from_secs(5))
+            .connect(config.url.as_str())
+            .await
+            .map_err(|e| MessagingError::Connection(e.to_string()))?;
+        Ok(Self { client, config })
+    }
+
+    /// Subscriber sharing this publisher's connection
+    #[must_use]
+    pub fn subscriber(&self) -> NatsSubscriber {
+        NatsSubscriber {
+            client: self.client.clone(),
+            config: self.config.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl MessagePublisher for NatsPublisher {
+    #[instrument(skip(self, event), fields(event = event.name(), item_id = %event.item_id))]
+    async fn publish(&self, event: &DomainEvent) -> Result<(), MessagingError> {
+        let failed = |message: String| MessagingError::PublishFailed {
+            event: event.name().to_string(),
+            message,
+        };
+        let payload = serde_json::to_vec(event).map_err(|e| failed(e.to_string()))?;
+        let mut headers = HeaderMap::new();
+        headers.insert(NATS_MESSAGE_ID, event.id.as_str());
+        self.client
+            .publish_with_headers(self.config.subject(event), headers, payload.into())
+            .await
+            .map_err(|e| failed(e.to_string()))
+    }
+}
+
+/// Reads messages from NATS and dead-letters the ones that cannot be handled
+pub struct NatsSubscriber {
+    client: Client,
+    config: NatsConfig,
+}
+
+#[async_trait]
+impl MessageSubscriber for NatsSubscriber {
+    async fn subscribe(
+        &self,
+        subject: &str,
+    ) -> Result<BoxStream<'static, InboundMessage>, MessagingError> {
+        let subscriber = self
+            .client
+            .subscribe(subject.to_string())
+            .await
+            .map_err(|e| MessagingError::SubscribeFailed {
+                subject: subject.to_string(),
+                message: e.to_string(),
+            })?;
+        Ok(subscriber
+            .map(|message| InboundMessage {
+                subject: message.subject.to_string(),
+                payload: message.payload.to_vec(),
+            })
+            .boxed())
+    }
+
+    #[instrument(skip(self, message), fields(subject = %message.subject))]
+    async fn dead_letter(
+        &self,
+        message: &InboundMessage,
+        reason: &str,
+    ) -> Result<(), MessagingError> {
+        // Header values must stay on one line
+        let reason = reason.replace(['\r', '\n'], " ");
+        let mut headers = HeaderMap::new();
+        headers.insert(DEAD_LETTER_REASON_HEADER, reason.as_str());
+        self.client
+            .publish_with_headers(
+                self.config.dead_letter_subject(&message.subject),
+                headers,
+                message.payload.clone().into(),
+            )
+            .await
+            .map_err(|e| MessagingError::PublishFailed {
+                event: "dead_letter".to_string(),
+                message: e.to_string(),
+            })
+    }
+}
@@ -2,10 +2,46 @@
 
 pub mod blockchain;
 pub mod database;
+pub mod encryption;
+pub mod ids;
+pub mod messaging;
 pub mod observability;
+pub mod secrets;
+pub mod storage;
+pub mod telemetry;
+pub mod webhook;
 
 pub use blockchain::{
-    AwsKmsSigner, LocalSigner, RpcBlockchainClient, RpcClientConfig, signing_key_from_base58,
+    AUDIT_LOG_TARGET, AuditingSigner, AwsKmsSecp256k1Signer, AwsKmsSigner, BlockchainBackend,
+    BlockchainBackendConfig, CircuitBreakerBlockchainClient, CircuitBreakerConfig, CircuitState,
+    DEFAULT_COMPUTE_UNIT_LIMIT, DEFAULT_DISCOVERY_INTERVAL, DiscoveryError, EndpointDiscovery,
+    EndpointSource, EvmBlockchainClient, EvmClientConfig, LocalSecp256k1Signer, LocalSigner,
+    NoopBlockchainClient, PriorityFee, RpcBlockchainClient, RpcClientConfig, RpcEndpoints,
+    VaultAuth, VaultConfig, VaultTransitSigner, create_blockchain_client, generate_keypair,
+    signing_key_from_base58, spawn_endpoint_discovery, spawn_vault_token_renewal,
 };
-pub use database::{PostgresClient, PostgresConfig, PostgresInitError};
+#[cfg(feature = "sqlite")]
+pub use database::SqliteClient;
+pub use database::{
+    DEFAULT_MIGRATION_COMPARE_RATE, DatabaseBackend, DatabaseClient, DatabaseInitError,
+    MigratingDatabaseClient, PostgresClient, PostgresConfig, PostgresInitError, connect_database,
+    split_repositories,
+};
+pub use encryption::{
+    DEFAULT_DATA_KEY_TTL, ENCRYPTED_CONTENT_PREFIX, EncryptionConfig, EnvelopeEncryption,
+    KmsMasterKey, LocalMasterKey, MasterKey, MasterKeySpec,
+};
+pub use ids::{IdFormat, UlidIdGenerator, UuidV7IdGenerator};
+pub use messaging::{DEFAULT_NATS_DEAD_LETTER_PREFIX, DEFAULT_NATS_SUBJECT_PREFIX, NatsConfig};
+#[cfg(feature = "nats")]
+pub use messaging::{NatsPublisher, NatsSubscriber};
 pub use observability::{PrometheusHandle, init_metrics, init_metrics_handle};
+pub use secrets::{
+    AwsSecretsManager, DEFAULT_SECRETS_DIR, EnvSecrets, FileSecrets, SecretsConfig, VaultSecrets,
+    VaultSecretsConfig, resolve_secret,
+};
+#[cfg(feature = "s3")]
+pub use storage::S3ObjectStore;
+pub use storage::{DEFAULT_OBJECT_STORE_REGION, ObjectStoreConfig};
+pub use telemetry::{PrometheusTelemetrySink, StdoutTelemetrySink, TelemetrySinkKind};
+pub use webhook::{WebhookConfig, WebhookNotifier, sign_webhook_payload};
@@ -0,0 +1,210 @@
+//! AWS Secrets Manager [`SecretsProvider`].
+//!
+//! Calls `GetSecretValue` once at startup with a SigV4-signed request and keeps the
+//! secret's JSON object in memory.
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use aws_config::BehaviorVersion;
+use aws_credential_types::Credentials;
+use aws_credential_types::provider::ProvideCredentials;
+use aws_sigv4::http_request::{SignableBody, SignableRequest, SigningSettings, sign};
+use aws_sigv4::sign::v4;
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use tracing::info;
+
+use super::secret_map;
+use crate::domain::{SecretsError, SecretsProvider};
+
+#[derive(Deserialize)]
+struct GetSecretValueResponse {
+    #[serde(rename = "SecretString")]
+    secret_string: Option<String>,
+}
+
+fn aws_error(message: impl std::fmt::Display) -> SecretsError {
+    SecretsError::Unavailable(format!("AWS Secrets Manager: {}", message))
+}
+
+/// Secrets from one Secrets Manager secret whose value is a JSON object of names to values
+pub struct AwsSecretsManager {
+    values: HashMap<String, SecretString>,
+}
+
+impl AwsSecretsManager {
+    /// Fetch `secret_id` (name or ARN) with credentials and region from the AWS environment;
+    /// `endpoint` replaces the regional endpoint (e.g. LocalStack)
+    pub async fn load(secret_id: &str, endpoint: Option<&str>) -> Result<Self, SecretsError> {
+        let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+        let region = config
+            .region()
+            .map(ToString::to_string)
+            .ok_or_else(|| aws_error("no AWS region configured"))?;
+        let credentials = config
+            .credentials_provider()
+            .ok_or_else(|| aws_error("no AWS credentials configured"))?
+            .provide_credentials()
+            .await
+            .map_err(aws_error)?;
+        let endpoint = endpoint.map_or_else(
+            || format!("https://secretsmanager.{}.amazonaws.com", region),
+            |endpoint| endpoint.trim_end_matches('/').to_string(),
+        );
+        info!(secret_id, %endpoint, "Loading secrets from AWS Secrets Manager");
+        let value = get_secret_value(&endpoint, &region, credentials, secret_id).await?;
+        Ok(Self {
+            values: secret_map(secret_id, value)?,
+        })
+    }
+}
+
+/// `GetSecretValue`, parsed as a JSON object
+async fn get_secret_value(
+    endpoint: &str,
+    region: &str,
+    credentials: Credentials,
+    secret_id: &str,
+) -> Result<serde_json::Map<String, serde_json::Value>, SecretsError> {
+    let url = format!("{}/", endpoint);
+    let body = serde_json::json!({ "SecretId": secret_id }).to_string();
+    let headers = [
+        ("content-type", "application/x-amz-json-1.1"),
+        ("x-amz-target", "secretsmanager.GetSecretValue"),
+    ];
+
+    let identity = credentials.into();
+    let params = v4::SigningParams::builder()
+        .identity(&identity)
+        .region(region)
+        .name("secretsmanager")
+        .time(SystemTime::now())
+        .settings(SigningSettings::default())
+        .build()
+        .map_err(aws_error)?
+        .into();
+    let signable = SignableRequest::new(
+        "POST",
+        url.as_str(),
+        headers.iter().copied(),
+        SignableBody::Bytes(body.as_bytes()),
+    )
+    .map_err(aws_error)?;
+    let (instructions, _signature) = sign(signable, &params).map_err(aws_error)?.into_parts();
+
+    let http = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(aws_error)?;
+    let mut request = http.post(&url).body(body);
+    for (name, value) in headers.iter().copied().chain(instructions.headers()) {
+        request = request.header(name, value);
+    }
+    let response = request.send().await.map_err(aws_error)?;
+    let status = response.status();
+    if !status.is_success() {
+        let detail = response.text().await.unwrap_or_default();
+        return Err(aws_error(format!(
+            "GetSecretValue returned {}: {}",
+            status, detail
+        )));
+    }
+    let secret_string = response
+        .json::<GetSecretValueResponse>()
+        .await
+        .map_err(aws_error)?
+        .secret_string
+        .ok_or_else(|| aws_error(format!("secret {} has no SecretString", secret_id)))?;
+    match serde_json::from_str(&secret_string) {
+        Ok(serde_json::Value::Object(object)) => Ok(object),
+        _ => Err(SecretsError::Invalid {
+            name: secret_id.to_string(),
+            message: "SecretString is not a JSON object".to_string(),
+        }),
+    }
+}
+
+#[async_trait]
+impl SecretsProvider for AwsSecretsManager {
+    fn name(&self) -> &'static str {
+        "aws-secrets-manager"
+    }
+
+    async fn get_secret(&self, name: &str) -> Result<Option<String>, SecretsError> {
+        Ok(self
+            .values
+            .get(name)
+            .map(|value| value.expose_secret().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::{Json, Router};
+
+    /// Stand-in for the `GetSecretValue` endpoint
+    async fn get_secret_value_handler(
+        headers: HeaderMap,
+        body: String,
+    ) -> Result<Json<serde_json::Value>, StatusCode> {
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+        assert_eq!(header("content-type"), Some("application/x-amz-json-1.1"));
+        assert_eq!(
+            header("x-amz-target"),
+            Some("secretsmanager.GetSecretValue")
+        );
+        let authorization = header("authorization").unwrap_or_default();
+        assert!(authorization.starts_with("AWS4-HMAC-SHA256 Credential=AKIDTEST/"));
+        assert!(authorization.contains("/eu-west-1/secretsmanager/aws4_request"));
+        match body["SecretId"].as_str() {
+            Some("prod/app") => Ok(Json(serde_json::json!({
+                "SecretString": r#"{"DATABASE_URL": "postgres://db/app"}"#
+            }))),
+            Some("prod/plain") => Ok(Json(serde_json::json!({ "SecretString": "hunter2" }))),
+            _ => Err(StatusCode::BAD_REQUEST),
+        }
+    }
+
+    async fn spawn_secrets_manager() -> String {
+        let app = Router::new().route("/", post(get_secret_value_handler));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    fn credentials() -> Credentials {
+        Credentials::new("AKIDTEST", "secret", None, None, "test")
+    }
+
+    #[tokio::test]
+    async fn test_get_secret_value_signs_and_parses_the_secret() {
+        let endpoint = spawn_secrets_manager().await;
+        let value = get_secret_value(&endpoint, "eu-west-1", credentials(), "prod/app")
+            .await
+            .unwrap();
+        let secrets = AwsSecretsManager {
+            values: secret_map("prod/app", value).unwrap(),
+        };
+        assert_eq!(
+            secrets.get_secret("DATABASE_URL").await.unwrap().as_deref(),
+            Some("postgres://db/app")
+        );
+        assert_eq!(secrets.get_secret("API_AUTH_KEY").await.unwrap(), None);
+
+        assert!(matches!(
+            get_secret_value(&endpoint, "eu-west-1", credentials(), "prod/plain").await,
+            Err(SecretsError::Invalid { .. })
+        ));
+        assert!(matches!(
+            get_secret_value(&endpoint, "eu-west-1", credentials(), "prod/missing").await,
+            Err(SecretsError::Unavailable(_))
+        ));
+    }
+}
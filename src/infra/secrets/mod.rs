@@ -0,0 +1,231 @@
+//! [`SecretsProvider`] implementations for configuration secrets.
+//!
+//! `SECRETS_PROVIDER` picks where `DATABASE_URL`, `ISSUER_PRIVATE_KEY` and the other
+//! secrets are read from at startup: the environment (default), files in a directory
+//! (Docker and Kubernetes secrets), one AWS Secrets Manager secret holding a JSON object,
+//! or one Vault KV v2 secret. Names map to files or JSON keys as they are (e.g. the
+//! `DATABASE_URL` key). [`resolve_secret`] falls back to the environment for names the
+//! provider does not have, so a deployment can move secrets over one at a time.
+
+mod aws;
+mod vault;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use secrecy::SecretString;
+use tracing::info;
+
+pub use aws::AwsSecretsManager;
+pub use vault::{VaultSecrets, VaultSecretsConfig};
+
+use crate::domain::{ConfigError, SecretsError, SecretsProvider};
+
+/// Directory read by the file provider when `SECRETS_DIR` is unset
+pub const DEFAULT_SECRETS_DIR: &str = "/run/secrets";
+
+/// Reads secrets from environment variables of the same name
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnvSecrets;
+
+#[async_trait]
+impl SecretsProvider for EnvSecrets {
+    fn name(&self) -> &'static str {
+        "env"
+    }
+
+    async fn get_secret(&self, name: &str) -> Result<Option<String>, SecretsError> {
+        Ok(std::env::var(name).ok().filter(|v| !v.is_empty()))
+    }
+}
+
+/// Reads each secret from the file of the same name in a directory, without the trailing
+/// newline
+#[derive(Debug, Clone)]
+pub struct FileSecrets {
+    dir: PathBuf,
+}
+
+impl FileSecrets {
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl SecretsProvider for FileSecrets {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn get_secret(&self, name: &str) -> Result<Option<String>, SecretsError> {
+        // Names are environment variable names; anything else could escape the directory
+        if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(SecretsError::Invalid {
+                name: name.to_string(),
+                message: "not a valid secret name".to_string(),
+            });
+        }
+        match tokio::fs::read_to_string(self.dir.join(name)).await {
+            Ok(value) => Ok(
+                Some(value.trim_end_matches(['\r', '\n']).to_string()).filter(|v| !v.is_empty())
+            ),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(SecretsError::Unavailable(format!(
+                "reading {}: {}",
+                self.dir.join(name).display(),
+                e
+            ))),
+        }
+    }
+}
+
+/// Parse a secret holding a JSON object of string values (AWS and Vault both store them so)
+fn secret_map(
+    source: &str,
+    object: serde_json::Map<String, serde_json::Value>,
+) -> Result<HashMap<String, SecretString>, SecretsError> {
+    object
+        .into_iter()
+        .map(|(name, value)| match value {
+            serde_json::Value::String(value) => Ok((name, SecretString::from(value))),
+            _ => Err(SecretsError::Invalid {
+                name,
+                message: format!("value in {} is not a string", source),
+            }),
+        })
+        .collect()
+}
+
+/// Which [`SecretsProvider`] to use (`SECRETS_PROVIDER`)
+#[derive(Debug, Clone)]
+pub enum SecretsConfig {
+    /// `env` (default)
+    Env,
+    /// `file`: one file per secret in `SECRETS_DIR` (default [`DEFAULT_SECRETS_DIR`])
+    File(PathBuf),
+    /// `aws`: the JSON secret `SECRETS_AWS_SECRET_ID` (name or ARN), with an optional
+    /// `SECRETS_AWS_ENDPOINT` (e.g. LocalStack); credentials and region from the standard
+    /// AWS sources
+    Aws {
+        secret_id: String,
+        endpoint: Option<String>,
+    },
+    /// `vault`: a KV v2 secret (see [`VaultSecretsConfig::from_env`])
+    Vault(VaultSecretsConfig),
+}
+
+impl SecretsConfig {
+    /// Create config from environment variables
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        match var("SECRETS_PROVIDER").as_deref().map(str::to_lowercase) {
+            None => Ok(Self::Env),
+            Some(provider) => match provider.as_str() {
+                "env" => Ok(Self::Env),
+                "file" => Ok(Self::File(
+                    var("SECRETS_DIR")
+                        .unwrap_or_else(|| DEFAULT_SECRETS_DIR.to_string())
+                        .into(),
+                )),
+                "aws" => Ok(Self::Aws {
+                    secret_id: var("SECRETS_AWS_SECRET_ID").ok_or_else(|| {
+                        ConfigError::MissingEnvVar("SECRETS_AWS_SECRET_ID".to_string())
+                    })?,
+                    endpoint: var("SECRETS_AWS_ENDPOINT"),
+                }),
+                "vault" => Ok(Self::Vault(VaultSecretsConfig::from_env()?)),
+                other => Err(ConfigError::InvalidValue {
+                    key: "SECRETS_PROVIDER".to_string(),
+                    message: format!("'{}' is not one of env, file, aws, vault", other),
+                }),
+            },
+        }
+    }
+
+    /// Build the provider. AWS and Vault secrets are fetched here, once.
+    pub async fn build(&self) -> Result<Arc<dyn SecretsProvider>, SecretsError> {
+        let provider: Arc<dyn SecretsProvider> = match self {
+            Self::Env => Arc::new(EnvSecrets),
+            Self::File(dir) => Arc::new(FileSecrets::new(dir.clone())),
+            Self::Aws {
+                secret_id,
+                endpoint,
+            } => Arc::new(AwsSecretsManager::load(secret_id, endpoint.as_deref()).await?),
+            Self::Vault(config) => Arc::new(VaultSecrets::load(config).await?),
+        };
+        info!(provider = provider.name(), "Secrets provider ready");
+        Ok(provider)
+    }
+}
+
+/// Look `name` up with `provider`, then in the environment; empty values count as unset
+pub async fn resolve_secret(
+    provider: &dyn SecretsProvider,
+    name: &str,
+) -> Result<Option<String>, SecretsError> {
+    match provider.get_secret(name).await? {
+        Some(value) if !value.is_empty() => Ok(Some(value)),
+        _ => EnvSecrets.get_secret(name).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_secrets_read_one_file_per_name() {
+        let dir = std::env::temp_dir().join(format!("secrets-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("DATABASE_URL"), "postgres://db/app\n").unwrap();
+        let secrets = FileSecrets::new(&dir);
+
+        assert_eq!(
+            secrets.get_secret("DATABASE_URL").await.unwrap().as_deref(),
+            Some("postgres://db/app")
+        );
+        assert_eq!(secrets.get_secret("API_AUTH_KEY").await.unwrap(), None);
+        assert!(matches!(
+            secrets.get_secret("../etc/passwd").await,
+            Err(SecretsError::Invalid { .. })
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_resolve_secret_falls_back_to_env() {
+        let secrets = FileSecrets::new(std::env::temp_dir().join("no-such-secrets-dir"));
+        assert_eq!(
+            resolve_secret(&secrets, "PATH").await.unwrap(),
+            std::env::var("PATH").ok()
+        );
+        assert_eq!(
+            resolve_secret(&secrets, "SECRETS_TEST_UNSET_VARIABLE")
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_secret_map_requires_string_values() {
+        let object = serde_json::json!({ "DATABASE_URL": "postgres://db/app" });
+        let map = secret_map("test", object.as_object().unwrap().clone()).unwrap();
+        assert!(map.contains_key("DATABASE_URL"));
+
+        let object = serde_json::json!({ "PORT": 5432 });
+        assert!(matches!(
+            secret_map("test", object.as_object().unwrap().clone()),
+            Err(SecretsError::Invalid { name, .. }) if name == "PORT"
+        ));
+    }
+}
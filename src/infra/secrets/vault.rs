@@ -0,0 +1,252 @@
+//! HashiCorp Vault KV v2 [`SecretsProvider`].
+//!
+//! Reads one secret (`GET /v1/<mount>/data/<path>`) at startup with a static token or an
+//! AppRole login, using the same `VAULT_*` variables as the transit signer. Tokens are not
+//! renewed: the secret is not read again.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use tracing::info;
+
+use super::secret_map;
+use crate::domain::{ConfigError, SecretsError, SecretsProvider};
+use crate::infra::blockchain::VaultAuth;
+
+/// Configuration for [`VaultSecrets`]
+#[derive(Debug, Clone)]
+pub struct VaultSecretsConfig {
+    /// Vault address, e.g. `https://vault.internal:8200`
+    pub address: String,
+    /// Mount path of the KV v2 engine
+    pub mount: String,
+    /// Path of the secret within the mount, e.g. `app/prod`
+    pub path: String,
+    pub auth: VaultAuth,
+    /// Enterprise namespace (`X-Vault-Namespace`)
+    pub namespace: Option<String>,
+}
+
+impl VaultSecretsConfig {
+    #[must_use]
+    pub fn new(address: impl Into<String>, path: impl Into<String>, auth: VaultAuth) -> Self {
+        Self {
+            address: address.into().trim_end_matches('/').to_string(),
+            mount: "secret".to_string(),
+            path: path.into().trim_matches('/').to_string(),
+            auth,
+            namespace: None,
+        }
+    }
+
+    /// Create config from `VAULT_ADDR`, `SECRETS_VAULT_PATH`, `SECRETS_VAULT_MOUNT` (default
+    /// `secret`) and `VAULT_NAMESPACE`. AppRole is used when `VAULT_ROLE_ID` is set (with
+    /// `VAULT_SECRET_ID`), otherwise `VAULT_TOKEN`.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let required =
+            |name: &str| var(name).ok_or_else(|| ConfigError::MissingEnvVar(name.into()));
+        let auth = match var("VAULT_ROLE_ID") {
+            Some(role_id) => VaultAuth::AppRole {
+                role_id,
+                secret_id: SecretString::from(required("VAULT_SECRET_ID")?),
+            },
+            None => VaultAuth::Token(SecretString::from(required("VAULT_TOKEN")?)),
+        };
+        let mut config = Self::new(
+            required("VAULT_ADDR")?,
+            required("SECRETS_VAULT_PATH")?,
+            auth,
+        );
+        if let Some(mount) = var("SECRETS_VAULT_MOUNT") {
+            config.mount = mount.trim_matches('/').to_string();
+        }
+        config.namespace = var("VAULT_NAMESPACE");
+        Ok(config)
+    }
+}
+
+#[derive(Deserialize)]
+struct AuthResponse {
+    auth: AuthInfo,
+}
+
+#[derive(Deserialize)]
+struct AuthInfo {
+    client_token: String,
+}
+
+/// `{"data": {"data": {...}, "metadata": {...}}}`
+#[derive(Deserialize)]
+struct KvResponse {
+    data: KvData,
+}
+
+#[derive(Deserialize)]
+struct KvData {
+    data: serde_json::Map<String, serde_json::Value>,
+}
+
+fn vault_error(message: impl std::fmt::Display) -> SecretsError {
+    SecretsError::Unavailable(format!("Vault: {}", message))
+}
+
+/// Secrets from the latest version of one Vault KV v2 secret
+pub struct VaultSecrets {
+    values: HashMap<String, SecretString>,
+}
+
+impl VaultSecrets {
+    /// Authenticate and read the secret
+    pub async fn load(config: &VaultSecretsConfig) -> Result<Self, SecretsError> {
+        let http = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(vault_error)?;
+        let with_namespace = |request: reqwest::RequestBuilder| match &config.namespace {
+            Some(namespace) => request.header("X-Vault-Namespace", namespace),
+            None => request,
+        };
+        info!(
+            address = %config.address,
+            mount = %config.mount,
+            path = %config.path,
+            "Loading secrets from Vault"
+        );
+
+        let token = match &config.auth {
+            VaultAuth::Token(token) => token.clone(),
+            VaultAuth::AppRole { role_id, secret_id } => {
+                let response =
+                    with_namespace(http.post(format!("{}/v1/auth/approle/login", config.address)))
+                        .json(&serde_json::json!({
+                            "role_id": role_id,
+                            "secret_id": secret_id.expose_secret(),
+                        }))
+                        .send()
+                        .await
+                        .map_err(vault_error)?;
+                if !response.status().is_success() {
+                    return Err(vault_error(format!(
+                        "AppRole login returned {}",
+                        response.status()
+                    )));
+                }
+                let auth = response.json::<AuthResponse>().await.map_err(vault_error)?;
+                SecretString::from(auth.auth.client_token)
+            }
+        };
+
+        let url = format!(
+            "{}/v1/{}/data/{}",
+            config.address, config.mount, config.path
+        );
+        let response = with_namespace(http.get(&url))
+            .header("X-Vault-Token", token.expose_secret())
+            .send()
+            .await
+            .map_err(vault_error)?;
+        if !response.status().is_success() {
+            return Err(vault_error(format!(
+                "reading {}/{} returned {}",
+                config.mount,
+                config.path,
+                response.status()
+            )));
+        }
+        let secret = response.json::<KvResponse>().await.map_err(vault_error)?;
+        Ok(Self {
+            values: secret_map(&config.path, secret.data.data)?,
+        })
+    }
+}
+
+#[async_trait]
+impl SecretsProvider for VaultSecrets {
+    fn name(&self) -> &'static str {
+        "vault"
+    }
+
+    async fn get_secret(&self, name: &str) -> Result<Option<String>, SecretsError> {
+        Ok(self
+            .values
+            .get(name)
+            .map(|value| value.expose_secret().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::{get, post};
+    use axum::{Json, Router};
+
+    /// Stand-in for Vault's AppRole login and a KV v2 secret at `secret/app/prod`
+    async fn spawn_vault() -> String {
+        async fn approle_login(
+            Json(body): Json<serde_json::Value>,
+        ) -> Result<Json<serde_json::Value>, StatusCode> {
+            if body["role_id"] != "role" || body["secret_id"] != "secret" {
+                return Err(StatusCode::BAD_REQUEST);
+            }
+            Ok(Json(serde_json::json!({
+                "auth": { "client_token": "tok-1", "lease_duration": 3600, "renewable": true }
+            })))
+        }
+        async fn read_secret(headers: HeaderMap) -> Result<Json<serde_json::Value>, StatusCode> {
+            if headers.get("x-vault-token").and_then(|v| v.to_str().ok()) != Some("tok-1") {
+                return Err(StatusCode::FORBIDDEN);
+            }
+            Ok(Json(serde_json::json!({
+                "data": {
+                    "data": { "ISSUER_PRIVATE_KEY": "base58key" },
+                    "metadata": { "version": 3 }
+                }
+            })))
+        }
+        let app = Router::new()
+            .route("/v1/auth/approle/login", post(approle_login))
+            .route("/v1/secret/data/app/prod", get(read_secret));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_reads_kv_secret_with_approle() {
+        let address = spawn_vault().await;
+        let approle = VaultAuth::AppRole {
+            role_id: "role".to_string(),
+            secret_id: SecretString::from("secret"),
+        };
+        let secrets = VaultSecrets::load(&VaultSecretsConfig::new(&address, "/app/prod/", approle))
+            .await
+            .unwrap();
+        assert_eq!(
+            secrets
+                .get_secret("ISSUER_PRIVATE_KEY")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("base58key")
+        );
+        assert_eq!(secrets.get_secret("DATABASE_URL").await.unwrap(), None);
+
+        let static_token = VaultAuth::Token(SecretString::from("expired"));
+        assert!(matches!(
+            VaultSecrets::load(&VaultSecretsConfig::new(&address, "app/prod", static_token)).await,
+            Err(SecretsError::Unavailable(_))
+        ));
+    }
+}
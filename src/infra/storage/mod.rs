@@ -0,0 +1,52 @@
+//! [`ObjectStore`](crate::domain::ObjectStore) implementations for large item content.
+//!
+//! With the `s3` feature, [`S3ObjectStore`] keeps content in an S3 bucket or an
+//! S3-compatible store such as MinIO. Objects are keyed by the SHA-256 of the content
+//! (`items/<hex>`), so items with equal content share one object.
+
+#[cfg(feature = "s3")]
+mod s3;
+
+#[cfg(feature = "s3")]
+pub use s3::S3ObjectStore;
+
+/// Region used with a custom endpoint when `OBJECT_STORE_REGION` is unset (MinIO ignores it)
+pub const DEFAULT_OBJECT_STORE_REGION: &str = "us-east-1";
+
+/// Object store settings (`OBJECT_STORE_BUCKET`, `OBJECT_STORE_ENDPOINT`,
+/// `OBJECT_STORE_REGION`); credentials come from the standard AWS sources
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectStoreConfig {
+    pub bucket: String,
+    /// S3-compatible endpoint (e.g. `http://localhost:9000` for MinIO); None uses AWS S3
+    pub endpoint: Option<String>,
+    /// None uses the AWS default region chain (or [`DEFAULT_OBJECT_STORE_REGION`] with
+    /// a custom endpoint)
+    pub region: Option<String>,
+}
+
+impl ObjectStoreConfig {
+    #[must_use]
+    pub fn new(bucket: impl Into<String>) -> Self {
+        Self {
+            bucket: bucket.into(),
+            endpoint: None,
+            region: None,
+        }
+    }
+
+    /// Create config from environment variables (None when `OBJECT_STORE_BUCKET` is unset)
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        Some(Self {
+            bucket: var("OBJECT_STORE_BUCKET")?,
+            endpoint: var("OBJECT_STORE_ENDPOINT"),
+            region: var("OBJECT_STORE_REGION"),
+        })
+    }
+}
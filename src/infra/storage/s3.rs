@@ -0,0 +1,98 @@
+//! S3 implementation of [`ObjectStore`] (also MinIO and other S3-compatible stores).
+
+use async_trait::async_trait;
+use aws_config::{BehaviorVersion, Region};
+use aws_sdk_s3::Client;
+use aws_sdk_s3::error::DisplayErrorContext;
+use aws_sdk_s3::primitives::ByteStream;
+use bytes::Bytes;
+use futures::stream::{self, BoxStream, StreamExt};
+use std::ops::Range;
+use tracing::{info, instrument};
+
+use super::{DEFAULT_OBJECT_STORE_REGION, ObjectStoreConfig};
+use crate::domain::{ObjectStore, ObjectStoreError};
+
+/// Keeps item content as objects in one bucket
+pub struct S3ObjectStore {
+    client: Client,
+    bucket: String,
+}
+
+impl S3ObjectStore {
+    /// Build a client from the AWS environment (credentials, region) and `config`. A custom
+    /// endpoint switches to path-style addressing, which MinIO expects.
+    pub async fn new(config: ObjectStoreConfig) -> Self {
+        let mut loader = aws_config::defaults(BehaviorVersion::latest());
+        let region = config.region.clone().or_else(|| {
+            config
+                .endpoint
+                .as_ref()
+                .map(|_| DEFAULT_OBJECT_STORE_REGION.to_string())
+        });
+        if let Some(region) = region {
+            loader = loader.region(Region::new(region));
+        }
+        let shared = loader.load().await;
+        let mut builder = aws_sdk_s3::config::Builder::from(&shared);
+        if let Some(endpoint) = &config.endpoint {
+            builder = builder.endpoint_url(endpoint).force_path_style(true);
+        }
+        info!(bucket = %config.bucket, endpoint = ?config.endpoint, "Initializing S3 object store");
+        Self {
+            client: Client::from_conf(builder.build()),
+            bucket: config.bucket,
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for S3ObjectStore {
+    #[instrument(skip(self, content), fields(bytes = content.len()))]
+    async fn put(&self, key: &str, content: Bytes) -> Result<(), ObjectStoreError> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type("text/plain; charset=utf-8")
+            .body(ByteStream::from(content))
+            .send()
+            .await
+            .map_err(|e| ObjectStoreError::Unavailable(DisplayErrorContext(&e).to_string()))?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn get(
+        &self,
+        key: &str,
+        range: Option<Range<u64>>,
+    ) -> Result<BoxStream<'static, Result<Bytes, ObjectStoreError>>, ObjectStoreError> {
+        let output = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .set_range(range.map(|r| format!("bytes={}-{}", r.start, r.end - 1)))
+            .send()
+            .await
+            .map_err(|e| {
+                if e.as_service_error().is_some_and(|e| e.is_no_such_key()) {
+                    ObjectStoreError::NotFound(key.to_string())
+                } else {
+                    ObjectStoreError::Unavailable(DisplayErrorContext(&e).to_string())
+                }
+            })?;
+        // Chunks are read from the response body as the stream is polled; it ends after
+        // the first error
+        Ok(stream::unfold(Some(output.body), |body| async move {
+            let mut body = body?;
+            match body.try_next().await {
+                Ok(Some(chunk)) => Some((Ok(chunk), Some(body))),
+                Ok(None) => None,
+                Err(e) => Some((Err(ObjectStoreError::Unavailable(e.to_string())), None)),
+            }
+        })
+        .boxed())
+    }
+}
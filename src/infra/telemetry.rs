@@ -0,0 +1,194 @@
+//! [`TelemetrySink`] implementations for business KPIs.
+//!
+//! [`PrometheusTelemetrySink`] records the KPIs as `kpi_*` series next to the operational
+//! metrics served by `GET /metrics`. [`StdoutTelemetrySink`] writes one JSON object per
+//! event to stdout, for a log shipper to forward to an analytics pipeline.
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::domain::TelemetrySink;
+
+/// Where business KPIs go (`TELEMETRY_SINK`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TelemetrySinkKind {
+    Prometheus,
+    Stdout,
+    /// KPIs are not reported
+    None,
+}
+
+impl std::str::FromStr for TelemetrySinkKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "prometheus" => Ok(Self::Prometheus),
+            "stdout" => Ok(Self::Stdout),
+            "none" | "off" => Ok(Self::None),
+            other => Err(format!(
+                "unknown telemetry sink '{other}' (expected prometheus, stdout or none)"
+            )),
+        }
+    }
+}
+
+impl TelemetrySinkKind {
+    /// The sink of this kind (None for [`Self::None`])
+    #[must_use]
+    pub fn sink(self) -> Option<Arc<dyn TelemetrySink>> {
+        match self {
+            Self::Prometheus => Some(Arc::new(PrometheusTelemetrySink)),
+            Self::Stdout => Some(Arc::new(StdoutTelemetrySink::new())),
+            Self::None => None,
+        }
+    }
+}
+
+/// Records KPIs through the global `metrics` recorder (exported by `GET /metrics`)
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PrometheusTelemetrySink;
+
+impl TelemetrySink for PrometheusTelemetrySink {
+    fn item_created(&self, tenant_id: &str) {
+        metrics::counter!("kpi_items_created_total", "tenant" => tenant_id.to_string())
+            .increment(1);
+    }
+
+    fn item_confirmed(&self, latency: Duration) {
+        metrics::histogram!("kpi_confirmation_latency_seconds").record(latency.as_secs_f64());
+    }
+
+    fn submission_failed(&self, reason: &str) {
+        metrics::counter!("kpi_submission_failures_total", "reason" => reason.to_string())
+            .increment(1);
+    }
+}
+
+/// One KPI event as written by [`StdoutTelemetrySink`]
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum TelemetryEvent<'a> {
+    ItemCreated { tenant_id: &'a str },
+    ItemConfirmed { latency_ms: u64 },
+    SubmissionFailed { reason: &'a str },
+}
+
+#[derive(Serialize)]
+struct TelemetryLine<'a> {
+    at: DateTime<Utc>,
+    #[serde(flatten)]
+    event: TelemetryEvent<'a>,
+}
+
+/// Writes each KPI event as a JSON line (stdout unless built [`Self::with_writer`])
+pub struct StdoutTelemetrySink {
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl Default for StdoutTelemetrySink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StdoutTelemetrySink {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_writer(std::io::stdout())
+    }
+
+    /// Write the JSON lines to `out` instead of stdout
+    pub fn with_writer(out: impl Write + Send + 'static) -> Self {
+        Self {
+            out: Mutex::new(Box::new(out)),
+        }
+    }
+
+    fn emit(&self, event: TelemetryEvent<'_>) {
+        let line = TelemetryLine {
+            at: Utc::now(),
+            event,
+        };
+        let Ok(json) = serde_json::to_string(&line) else {
+            return;
+        };
+        // A KPI that cannot be written is dropped rather than failing the caller
+        let mut out = self.out.lock().unwrap();
+        let _ = writeln!(out, "{json}");
+    }
+}
+
+impl TelemetrySink for StdoutTelemetrySink {
+    fn item_created(&self, tenant_id: &str) {
+        self.emit(TelemetryEvent::ItemCreated { tenant_id });
+    }
+
+    fn item_confirmed(&self, latency: Duration) {
+        self.emit(TelemetryEvent::ItemConfirmed {
+            latency_ms: latency.as_millis() as u64,
+        });
+    }
+
+    fn submission_failed(&self, reason: &str) {
+        self.emit(TelemetryEvent::SubmissionFailed { reason });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shared buffer standing in for stdout
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_stdout_sink_writes_one_json_line_per_event() {
+        let buffer = Buffer::default();
+        let sink = StdoutTelemetrySink::with_writer(buffer.clone());
+        sink.item_created("acme");
+        sink.item_confirmed(Duration::from_millis(1500));
+        sink.submission_failed("timeout");
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["event"], "item_created");
+        assert_eq!(lines[0]["tenant_id"], "acme");
+        assert_eq!(lines[1]["event"], "item_confirmed");
+        assert_eq!(lines[1]["latency_ms"], 1500);
+        assert_eq!(lines[2]["event"], "submission_failed");
+        assert_eq!(lines[2]["reason"], "timeout");
+        assert!(lines.iter().all(|line| line["at"].is_string()));
+    }
+
+    #[test]
+    fn test_sink_kind_parsing() {
+        assert_eq!(
+            "Prometheus".parse::<TelemetrySinkKind>(),
+            Ok(TelemetrySinkKind::Prometheus)
+        );
+        assert_eq!("stdout".parse(), Ok(TelemetrySinkKind::Stdout));
+        assert_eq!("none".parse(), Ok(TelemetrySinkKind::None));
+        assert!("kafka".parse::<TelemetrySinkKind>().is_err());
+        assert!(TelemetrySinkKind::None.sink().is_none());
+    }
+}
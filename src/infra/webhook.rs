@@ -0,0 +1,431 @@
+//! HTTP webhook implementation of [`NotificationClient`].
+//!
+//! Events are POSTed in batches (`{"events": [...]}`) with an HMAC-SHA256 signature over
+//! `"<timestamp>.<body>"`, so receivers can verify the sender and reject replays:
+//!
+//! ```text
+//! X-Webhook-Id: dlv_...
+//! X-Webhook-Timestamp: 1767225600
+//! X-Webhook-Signature: sha256=<hex>
+//! ```
+//!
+//! Non-2xx responses and network errors are retried with exponential backoff; every attempt
+//! is recorded through the optional [`WebhookDeliveryLog`]. Ordering and redelivery across
+//! restarts are handled by the event dispatcher, which only advances a subscription's cursor
+//! once its batch is accepted.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tracing::{info, instrument, warn};
+
+use crate::domain::{
+    ItemStatusEvent, NotificationClient, NotificationError, WebhookDelivery, WebhookDeliveryLog,
+};
+
+/// Header carrying the delivery ID (the same for every retry of a batch)
+pub const WEBHOOK_ID_HEADER: &str = "x-webhook-id";
+/// Header carrying the Unix timestamp included in the signature
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "x-webhook-timestamp";
+/// Header carrying `sha256=<hex HMAC>`
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "x-webhook-signature";
+
+/// Configuration for webhook delivery
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    /// Endpoints that receive every event; each is a subscription with its own cursor
+    pub urls: Vec<String>,
+    /// Shared HMAC secret
+    pub secret: SecretString,
+    /// Attempts per URL before the delivery is given up
+    pub max_attempts: u32,
+    /// Delay before the second attempt; doubles for each further attempt
+    pub initial_backoff: Duration,
+    pub timeout: Duration,
+}
+
+impl WebhookConfig {
+    #[must_use]
+    pub fn new(urls: Vec<String>, secret: SecretString) -> Self {
+        Self {
+            urls,
+            secret,
+            max_attempts: 5,
+            initial_backoff: Duration::from_secs(1),
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Create config from environment variables (None when `WEBHOOK_URLS` is unset or empty)
+    pub fn from_env() -> Option<Self> {
+        let urls: Vec<String> = std::env::var("WEBHOOK_URLS")
+            .ok()?
+            .split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(str::to_string)
+            .collect();
+        if urls.is_empty() {
+            return None;
+        }
+        let secret = SecretString::from(std::env::var("WEBHOOK_SECRET").unwrap_or_default());
+        let defaults = Self::new(urls, secret);
+        let max_attempts = std::env::var("WEBHOOK_MAX_ATTEMPTS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(defaults.max_attempts);
+        let timeout = std::env::var("WEBHOOK_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(defaults.timeout);
+        Some(Self {
+            max_attempts,
+            timeout,
+            ..defaults
+        })
+    }
+}
+
+/// `sha256=<hex>` HMAC of `"<timestamp>.<body>"` (what receivers recompute to verify)
+#[must_use]
+pub fn sign_webhook_payload(secret: &SecretString, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.expose_secret().as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={:x}", mac.finalize().into_bytes())
+}
+
+/// Body of a webhook request: events in log order (per item, ascending `sequence`)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookBatch {
+    pub events: Vec<ItemStatusEvent>,
+}
+
+/// Webhook endpoint receiving signed event batches (one per configured URL)
+pub struct WebhookNotifier {
+    http_client: Client,
+    url: String,
+    config: WebhookConfig,
+    deliveries: Option<Arc<dyn WebhookDeliveryLog>>,
+}
+
+impl WebhookNotifier {
+    /// Notifier for `url` using the signing and retry settings in `config`
+    pub fn new(url: impl Into<String>, config: WebhookConfig) -> Result<Self, NotificationError> {
+        let url = url.into();
+        let http_client = Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| NotificationError::DeliveryFailed {
+                url: url.clone(),
+                attempts: 0,
+                message: e.to_string(),
+            })?;
+        Ok(Self {
+            http_client,
+            url,
+            config,
+            deliveries: None,
+        })
+    }
+
+    /// Endpoint this notifier delivers to (also its subscription name)
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Record every delivery attempt (e.g. in the `webhook_deliveries` table)
+    #[must_use]
+    pub fn with_delivery_log(mut self, deliveries: Arc<dyn WebhookDeliveryLog>) -> Self {
+        self.deliveries = Some(deliveries);
+        self
+    }
+
+    /// One signed POST; returns the response status and an error unless it was 2xx
+    async fn send(&self, delivery_id: &str, body: &[u8]) -> (Option<u16>, Option<String>) {
+        let timestamp = Utc::now().timestamp();
+        let result = self
+            .http_client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(WEBHOOK_ID_HEADER, delivery_id)
+            .header(WEBHOOK_TIMESTAMP_HEADER, timestamp.to_string())
+            .header(
+                WEBHOOK_SIGNATURE_HEADER,
+                sign_webhook_payload(&self.config.secret, timestamp, body),
+            )
+            .body(body.to_vec())
+            .send()
+            .await;
+        match result {
+            Ok(response) if response.status().is_success() => {
+                (Some(response.status().as_u16()), None)
+            }
+            Ok(response) => (
+                Some(response.status().as_u16()),
+                Some(format!("HTTP {}", response.status())),
+            ),
+            Err(e) => (None, Some(e.to_string())),
+        }
+    }
+
+    /// Record the attempt for every event in the batch
+    async fn record(
+        &self,
+        events: &[ItemStatusEvent],
+        attempt: u32,
+        response_status: Option<u16>,
+        error: Option<&str>,
+    ) {
+        let Some(deliveries) = &self.deliveries else {
+            return;
+        };
+        let attempted_at = Utc::now();
+        for event in events {
+            let delivery = WebhookDelivery {
+                id: 0,
+                event_id: event.id.clone(),
+                event: event.event.clone(),
+                item_id: event.item_id.clone(),
+                url: self.url.clone(),
+                attempt,
+                response_status,
+                error: error.map(str::to_string),
+                succeeded: error.is_none(),
+                attempted_at,
+            };
+            // Losing an audit row must not stop delivery
+            if let Err(e) = deliveries.record_webhook_delivery(&delivery).await {
+                warn!(event_id = %event.id, error = %e, "Failed to record webhook delivery");
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationClient for WebhookNotifier {
+    /// POST the batch, retrying with exponential backoff until it is accepted (2xx)
+    /// or attempts run out
+    #[instrument(skip(self, events), fields(url = %self.url, events = events.len()))]
+    async fn notify(&self, events: &[ItemStatusEvent]) -> Result<(), NotificationError> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        let body = serde_json::to_vec(&WebhookBatch {
+            events: events.to_vec(),
+        })
+        .map_err(|e| NotificationError::DeliveryFailed {
+            url: self.url.clone(),
+            attempts: 0,
+            message: e.to_string(),
+        })?;
+        // Retries of this batch share one delivery ID
+        let delivery_id = format!("dlv_{}", uuid::Uuid::now_v7());
+
+        let mut backoff = self.config.initial_backoff;
+        let mut last_error = String::new();
+        for attempt in 1..=self.config.max_attempts {
+            if attempt > 1 {
+                tokio::time::sleep(backoff).await;
+                backoff = backoff.saturating_mul(2);
+            }
+            let (response_status, error) = self.send(&delivery_id, &body).await;
+            self.record(events, attempt, response_status, error.as_deref())
+                .await;
+            metrics::counter!(
+                "webhook_deliveries_total",
+                "outcome" => if error.is_none() { "success" } else { "failure" }
+            )
+            .increment(1);
+            match error {
+                None => {
+                    info!(url = %self.url, attempt, events = events.len(), "Webhook batch delivered");
+                    return Ok(());
+                }
+                Some(e) => {
+                    warn!(url = %self.url, attempt, error = %e, "Webhook delivery failed");
+                    last_error = e;
+                }
+            }
+        }
+        Err(NotificationError::DeliveryFailed {
+            url: self.url.clone(),
+            attempts: self.config.max_attempts,
+            message: last_error,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::BlockchainStatus;
+    use crate::test_utils::MockProvider;
+    use axum::{Router, body::Bytes, extract::State, http::HeaderMap, http::StatusCode};
+    use std::sync::Mutex;
+
+    /// Local endpoint answering with scripted statuses (last one repeats)
+    #[derive(Clone)]
+    struct Receiver {
+        statuses: Arc<Mutex<Vec<StatusCode>>>,
+        received: Arc<Mutex<Vec<(HeaderMap, Bytes)>>>,
+    }
+
+    async fn receive(
+        State(receiver): State<Receiver>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> StatusCode {
+        receiver.received.lock().unwrap().push((headers, body));
+        let mut statuses = receiver.statuses.lock().unwrap();
+        if statuses.len() > 1 {
+            statuses.remove(0)
+        } else {
+            statuses[0]
+        }
+    }
+
+    async fn spawn_receiver(statuses: &[StatusCode]) -> (String, Receiver) {
+        let receiver = Receiver {
+            statuses: Arc::new(Mutex::new(statuses.to_vec())),
+            received: Arc::new(Mutex::new(Vec::new())),
+        };
+        let app = Router::new()
+            .route("/hook", axum::routing::post(receive))
+            .with_state(receiver.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{}/hook", addr), receiver)
+    }
+
+    fn config(url: &str) -> WebhookConfig {
+        WebhookConfig {
+            initial_backoff: Duration::from_millis(10),
+            max_attempts: 3,
+            ..WebhookConfig::new(
+                vec![url.to_string()],
+                SecretString::from("whsec_test".to_string()),
+            )
+        }
+    }
+
+    fn events() -> Vec<ItemStatusEvent> {
+        let mut submitted =
+            ItemStatusEvent::new("item_1", BlockchainStatus::Submitted).with_signature("sig");
+        submitted.position = 7;
+        submitted.sequence = 1;
+        let mut failed =
+            ItemStatusEvent::new("item_2", BlockchainStatus::Failed).with_error("boom");
+        failed.position = 8;
+        failed.sequence = 1;
+        vec![submitted, failed]
+    }
+
+    #[tokio::test]
+    async fn test_delivers_signed_batch() {
+        let (url, receiver) = spawn_receiver(&[StatusCode::NO_CONTENT]).await;
+        let notifier = WebhookNotifier::new(&url, config(&url)).unwrap();
+        let events = events();
+
+        notifier.notify(&events).await.unwrap();
+
+        let received = receiver.received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        let (headers, body) = &received[0];
+        let batch: WebhookBatch = serde_json::from_slice(body).unwrap();
+        assert_eq!(batch.events, events);
+        assert_eq!(batch.events[0].event, "item.submitted");
+        assert!(
+            headers[WEBHOOK_ID_HEADER]
+                .to_str()
+                .unwrap()
+                .starts_with("dlv_")
+        );
+        let timestamp: i64 = headers[WEBHOOK_TIMESTAMP_HEADER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let expected = sign_webhook_payload(
+            &SecretString::from("whsec_test".to_string()),
+            timestamp,
+            body,
+        );
+        assert_eq!(headers[WEBHOOK_SIGNATURE_HEADER], expected.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_retries_and_records_each_attempt() {
+        let (url, receiver) =
+            spawn_receiver(&[StatusCode::SERVICE_UNAVAILABLE, StatusCode::OK]).await;
+        let log = Arc::new(MockProvider::new());
+        let notifier = WebhookNotifier::new(&url, config(&url))
+            .unwrap()
+            .with_delivery_log(Arc::clone(&log) as Arc<dyn WebhookDeliveryLog>);
+
+        notifier.notify(&events()).await.unwrap();
+
+        // Both attempts carry the same delivery ID
+        let received = receiver.received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        assert_eq!(
+            received[0].0[WEBHOOK_ID_HEADER],
+            received[1].0[WEBHOOK_ID_HEADER]
+        );
+        // One audit row per event and attempt
+        let deliveries = log.get_webhook_deliveries();
+        assert_eq!(deliveries.len(), 4);
+        assert!(
+            deliveries[..2]
+                .iter()
+                .all(|d| d.attempt == 1 && d.response_status == Some(503) && !d.succeeded)
+        );
+        assert!(
+            deliveries[2..]
+                .iter()
+                .all(|d| d.attempt == 2 && d.succeeded)
+        );
+        assert_eq!(deliveries[3].url, url);
+        assert_eq!(deliveries[3].event, "item.failed");
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let (url, receiver) = spawn_receiver(&[StatusCode::INTERNAL_SERVER_ERROR]).await;
+        let notifier = WebhookNotifier::new(&url, config(&url)).unwrap();
+
+        let err = notifier.notify(&events()).await.unwrap_err();
+
+        assert!(matches!(
+            err,
+            NotificationError::DeliveryFailed { attempts: 3, .. }
+        ));
+        assert_eq!(receiver.received.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_signature_depends_on_timestamp_and_secret() {
+        let secret = SecretString::from("a".to_string());
+        let signature = sign_webhook_payload(&secret, 1, b"{}");
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+        assert_ne!(signature, sign_webhook_payload(&secret, 2, b"{}"));
+        assert_ne!(
+            signature,
+            sign_webhook_payload(&SecretString::from("b".to_string()), 1, b"{}")
+        );
+    }
+}
@@ -30,11 +30,21 @@
 //! │   Database, blockchain, external APIs   │
 //! └─────────────────────────────────────────┘
 //! ```
+//!
+//! Every layer but the domain is behind the default `server` feature. With
+//! `default-features = false` the crate is just [`domain`] (types, traits, errors and
+//! validation) without sqlx or tokio, and compiles to `wasm32-unknown-unknown` so a
+//! browser frontend can share validation and content hashing with the service.
 
+#[cfg(feature = "server")]
 pub mod api;
+#[cfg(feature = "server")]
 pub mod app;
+#[cfg(feature = "server")]
+pub mod composition_root;
 pub mod domain;
+#[cfg(feature = "server")]
 pub mod infra;
 
-#[cfg(any(test, feature = "test-utils"))]
+#[cfg(all(feature = "server", any(test, feature = "test-utils")))]
 pub mod test_utils;
@@ -1,87 +1,548 @@
-//! Application entry point.
+//! Application entry point: `serve` (the default) and the operational subcommands.
 
 use std::env;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
 use dotenvy::dotenv;
 use rand::rngs::OsRng;
-use secrecy::SecretString;
+use secrecy::{ExposeSecret, SecretString};
 use tokio::signal;
-use tracing::{info, warn};
+use tokio::sync::watch;
+use tracing::{error, info, warn};
 use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
+use utoipa::Modify;
 
 use testable_rust_architecture_template::api::{
-    RateLimitConfig, create_router, create_router_with_rate_limit,
+    self, ApiSecurity, OpenApiConfig, RateLimitConfig, typescript_types,
 };
-use testable_rust_architecture_template::app::{AppState, WorkerConfig, spawn_worker};
-use testable_rust_architecture_template::domain::TransactionSigner;
+use testable_rust_architecture_template::app::{
+    AbuseConfig, AppState, AuthPolicy, BodyLimits, ConfirmationConfig, ConsumerConfig, CorsConfig,
+    DEFAULT_CLAIM_TTL, DEFAULT_CONTENT_OFFLOAD_THRESHOLD, DEFAULT_COUNT_ESTIMATE_ABOVE,
+    DEFAULT_HEALTH_CACHE_TTL, DEFAULT_JOB_JITTER, DEFAULT_MAINTENANCE_RETRY_AFTER,
+    DEFAULT_MAX_METADATA_BYTES, DEFAULT_SUBMISSION_COST, DispatcherConfig, DoctorReport,
+    IpBlocklist, IssuerKeyRegistry, LogEventHandler, MessageConsumer, PurgeConfig, RetryPolicy,
+    Shutdown, ShutdownConfig, ShutdownPhase, SubmissionBudget, Subscription, WorkerConfig,
+    balance_status, spawn_event_dispatcher, spawn_health_refresh_worker, spawn_message_consumer,
+    spawn_purge_worker, verify_signer,
+};
+use testable_rust_architecture_template::composition_root::{
+    AppConfig, Application, Infrastructure, ProcessRole, compose,
+};
+use testable_rust_architecture_template::domain::{
+    BlockchainClient, EncryptionService, EventLog, IssuerKeyStatus, ItemRepository,
+    MessagePublisher, MessageSubscriber, ObjectStore, SchemaStatus, SecretsProvider,
+    TransactionSigner, WebhookDeliveryLog,
+};
+#[cfg(feature = "nats")]
+use testable_rust_architecture_template::infra::NatsPublisher;
+#[cfg(feature = "s3")]
+use testable_rust_architecture_template::infra::S3ObjectStore;
+use testable_rust_architecture_template::infra::blockchain::evm::parse_address;
 use testable_rust_architecture_template::infra::{
-    AwsKmsSigner, LocalSigner, PostgresClient, PostgresConfig, RpcBlockchainClient,
-    init_metrics_handle,
+    AuditingSigner, AwsKmsSecp256k1Signer, AwsKmsSigner, BlockchainBackend,
+    BlockchainBackendConfig, CircuitBreakerBlockchainClient, CircuitBreakerConfig,
+    DEFAULT_COMPUTE_UNIT_LIMIT, DEFAULT_DISCOVERY_INTERVAL, DEFAULT_MIGRATION_COMPARE_RATE,
+    DatabaseBackend, DatabaseClient, EncryptionConfig, EndpointDiscovery, EndpointSource,
+    EnvelopeEncryption, EvmClientConfig, IdFormat, LocalSecp256k1Signer, LocalSigner,
+    MigratingDatabaseClient, NatsConfig, ObjectStoreConfig, PostgresConfig, PriorityFee,
+    RpcBlockchainClient, RpcClientConfig, RpcEndpoints, SecretsConfig, TelemetrySinkKind,
+    VaultConfig, VaultTransitSigner, WebhookConfig, WebhookNotifier, connect_database,
+    create_blockchain_client, generate_keypair, init_metrics_handle, resolve_secret,
+    signing_key_from_base58, spawn_endpoint_discovery, spawn_vault_token_renewal,
 };
 
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the process `ROLE` selects: the HTTP (and gRPC) server, the workers, or both
+    /// (the default)
+    Serve,
+    /// Apply pending database migrations and exit
+    Migrate,
+    /// Run only the submission retry worker and confirmation poller (same as `ROLE=worker`)
+    Worker,
+    /// Queue a failed item for blockchain submission again, as `POST /items/{id}/retry` does
+    Submit {
+        /// ID of the item to resubmit
+        item_id: String,
+    },
+    /// Generate an Ed25519 keypair and print it as Base58
+    Keygen,
+    /// Manage the Solana durable nonce account submissions are signed against
+    Nonce {
+        #[command(subcommand)]
+        action: NonceAction,
+    },
+    /// Print the OpenAPI spec
+    Openapi {
+        /// Write TypeScript types for every schema to this file instead
+        #[arg(long, value_name = "PATH")]
+        typescript: Option<PathBuf>,
+    },
+    /// Check configuration and every dependency without starting the server
+    Doctor,
+}
+
+#[derive(Subcommand)]
+enum NonceAction {
+    /// Create a nonce account controlled by the fee payer and print its address
+    Create,
+    /// Advance `SOLANA_NONCE_ACCOUNT`, so transactions signed against it can no longer land
+    Advance,
+}
+
 /// Application configuration
 struct Config {
     database_url: String,
-    blockchain_rpc_url: String,
-    signer: Arc<dyn TransactionSigner>,
-    api_auth_key: SecretString,
+    /// Postgres replica that serves item reads (`DATABASE_READ_URL`); the primary takes over
+    /// while it is unreachable
+    database_read_url: Option<String>,
+    /// Pool of `DATABASE_URL` when it is Postgres: the `DATABASE_POOL_PRESET` sizing, slow
+    /// statement logging and pool gauges, with per-field `DATABASE_*` overrides
+    pool_config: PostgresConfig,
+    /// Format of new item, dead letter, API key and job IDs (`ID_FORMAT`)
+    id_format: IdFormat,
+    /// Database dual-written while migrating to it (`DATABASE_MIGRATION_URL`), with the
+    /// share of reads compared against it
+    migration_target: Option<(String, f64)>,
+    /// None when `CHAIN_DISABLED=true` (no signer or RPC client is configured)
+    blockchain: Option<BlockchainBackendConfig>,
+    /// Vault transit signer whose token is renewed in the background (`SIGNER_TYPE=VAULT`)
+    vault_signer: Option<Arc<VaultTransitSigner>>,
     host: String,
     port: u16,
-    enable_rate_limiting: bool,
-    rate_limit_config: RateLimitConfig,
-    enable_background_worker: bool,
-    worker_config: WorkerConfig,
+    /// Everything the composition root wires
+    app: AppConfig,
+    purge_config: PurgeConfig,
+    circuit_breaker_config: CircuitBreakerConfig,
+    /// Refreshes the Solana RPC endpoint list (`SOLANA_RPC_DISCOVERY`), with its interval
+    rpc_discovery: Option<(EndpointDiscovery, Duration)>,
+    /// None when `WEBHOOK_URLS` is unset (no status notifications)
+    webhook_config: Option<WebhookConfig>,
+    /// None when `NATS_URL` is unset (domain events are not published)
+    nats_config: Option<NatsConfig>,
+    /// Handles domain events read back from NATS (`MESSAGE_CONSUMER_ENABLED`)
+    consumer_config: ConsumerConfig,
+    /// None when `OBJECT_STORE_BUCKET` is unset (all content stays in the database)
+    object_store_config: Option<ObjectStoreConfig>,
+    /// None when `CONTENT_ENCRYPTION_KEY` is unset (item content is stored as plaintext)
+    content_encryption: Option<EncryptionConfig>,
+    dispatcher_config: DispatcherConfig,
+    /// Deadline of each shutdown phase after SIGTERM/Ctrl+C
+    shutdown_config: ShutdownConfig,
+    /// Re-check dependencies in the background before the cache expires
+    /// (`HEALTH_BACKGROUND_REFRESH`)
+    health_background_refresh: bool,
+    /// Reject a new item whose content matches a live item (`ITEM_HASH_UNIQUE`)
+    unique_content_hash: bool,
+    /// Apply migrations at startup (`AUTO_MIGRATE`); when false only check them
+    auto_migrate: bool,
+    /// gRPC listen port (`GRPC_PORT`) and how often `WatchItem` polls (`GRPC_WATCH_INTERVAL_MS`)
+    #[cfg(feature = "grpc")]
+    grpc: (u16, Duration),
 }
 
 impl Config {
     async fn from_env() -> Result<Self> {
-        let database_url = env::var("DATABASE_URL").context("DATABASE_URL not set")?;
-        let blockchain_rpc_url = env::var("SOLANA_RPC_URL")
-            .unwrap_or_else(|_| "https://api.devnet.solana.com".to_string());
-        let signer = Self::load_signer().await?;
+        let secrets = SecretsConfig::from_env()
+            .context("Invalid SECRETS_* configuration")?
+            .build()
+            .await
+            .context("Failed to initialize secrets provider")?;
+        let secrets = secrets.as_ref();
+        let database_url = secret(secrets, "DATABASE_URL")
+            .await?
+            .context("DATABASE_URL not set")?;
+        let database_read_url = secret(secrets, "DATABASE_READ_URL").await?;
+        let pool_config = PostgresConfig::from_env();
+        let id_format = match env::var("ID_FORMAT") {
+            Ok(format) if !format.is_empty() => format
+                .parse()
+                .map_err(anyhow::Error::msg)
+                .context("Invalid ID_FORMAT")?,
+            _ => IdFormat::default(),
+        };
+        let migration_target = secret(secrets, "DATABASE_MIGRATION_URL").await?.map(|url| {
+            let compare_rate = env::var("DATABASE_MIGRATION_COMPARE_RATE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_MIGRATION_COMPARE_RATE);
+            (url, compare_rate)
+        });
+        let chain_disabled = env::var("CHAIN_DISABLED")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+        let (blockchain, vault_signer) = if chain_disabled {
+            (None, None)
+        } else {
+            let (blockchain, vault_signer) = Self::load_blockchain(secrets).await?;
+            (Some(blockchain), vault_signer)
+        };
         let host = env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
         let port = env::var("PORT")
             .ok()
             .and_then(|p| p.parse().ok())
             .unwrap_or(3000);
+        #[cfg(feature = "grpc")]
+        let grpc = (
+            env::var("GRPC_PORT")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(50051),
+            env::var("GRPC_WATCH_INTERVAL_MS")
+                .ok()
+                .and_then(|ms| ms.parse().ok())
+                .filter(|ms| *ms > 0)
+                .map_or(
+                    testable_rust_architecture_template::api::grpc::DEFAULT_WATCH_INTERVAL,
+                    Duration::from_millis,
+                ),
+        );
         let enable_rate_limiting = env::var("ENABLE_RATE_LIMITING")
             .map(|v| v == "true" || v == "1")
             .unwrap_or(false);
+        let role = match env::var("ROLE") {
+            Ok(role) if !role.is_empty() => role
+                .parse()
+                .map_err(anyhow::Error::msg)
+                .context("Invalid ROLE")?,
+            _ => ProcessRole::All,
+        };
         let enable_background_worker = env::var("ENABLE_BACKGROUND_WORKER")
             .map(|v| v == "true" || v == "1")
             .unwrap_or(true);
+        let unique_content_hash = env::var("ITEM_HASH_UNIQUE")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+        let min_wallet_balance = env::var("MIN_WALLET_BALANCE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v > 0);
+        let auto_migrate = env::var("AUTO_MIGRATE")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(true);
+        let daily_budget = env::var("SUBMISSION_DAILY_BUDGET")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v > 0);
+        let submission_budget = match (&blockchain, daily_budget) {
+            (Some(blockchain), Some(daily_limit)) => Some(SubmissionBudget {
+                signer: blockchain
+                    .signer_id()
+                    .context("Failed to identify the submission signer")?,
+                daily_limit,
+                cost_per_submission: env::var("SUBMISSION_COST")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .filter(|v| *v > 0)
+                    .unwrap_or(DEFAULT_SUBMISSION_COST),
+            }),
+            _ => None,
+        };
+
+        let cursor_secret = secret(secrets, "CURSOR_SECRET")
+            .await?
+            .map(SecretString::from);
 
-        let api_auth_key = env::var("API_AUTH_KEY")
-            .context("API_AUTH_KEY not set - security requires this environment variable")?;
+        let api_auth_key = secret(secrets, "API_AUTH_KEY")
+            .await?
+            .context("API_AUTH_KEY not set - security requires this secret")?;
         let api_auth_key = SecretString::from(api_auth_key);
+        let admin_auth_key = secret(secrets, "ADMIN_AUTH_KEY")
+            .await?
+            .map(SecretString::from);
 
         let rate_limit_config = RateLimitConfig::from_env();
+        let blocklist = IpBlocklist::from_env().context("Invalid IP_BLOCKLIST")?;
+        let auth_policy = AuthPolicy::from_env().context("Invalid AUTH_POLICY")?;
+        let cors = CorsConfig::from_env().context("Invalid CORS_* configuration")?;
+        if cors.allows_any_origin() {
+            warn!("CORS allows any origin on some routes; use explicit origins in production");
+        }
+        let mut issuer_keys =
+            IssuerKeyRegistry::from_env().context("Invalid issuer public keys")?;
+        if let Some(BlockchainBackendConfig::Solana { signer, .. }) = &blockchain {
+            issuer_keys = issuer_keys
+                .with_key(&signer.public_key(), IssuerKeyStatus::Current)
+                .context("Signer public key is not an Ed25519 key")?;
+        }
+        let circuit_breaker_config = CircuitBreakerConfig::from_env();
+        let rpc_discovery = match (&blockchain, env::var("SOLANA_RPC_DISCOVERY")) {
+            (Some(BlockchainBackendConfig::Solana { endpoints, .. }), Ok(source))
+                if !source.is_empty() =>
+            {
+                let source: EndpointSource = source
+                    .parse()
+                    .map_err(anyhow::Error::msg)
+                    .context("Invalid SOLANA_RPC_DISCOVERY")?;
+                let interval = env::var("SOLANA_RPC_DISCOVERY_INTERVAL_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .filter(|v| *v > 0)
+                    .map_or(DEFAULT_DISCOVERY_INTERVAL, Duration::from_secs);
+                let discovery = EndpointDiscovery::new(source, endpoints.clone())
+                    .context("Cannot set up RPC endpoint discovery")?;
+                Some((discovery, interval))
+            }
+            _ => None,
+        };
+        let webhook_config = WebhookConfig::from_env();
+        let nats_config = NatsConfig::from_env();
+        let consumer_config = ConsumerConfig::from_env();
+        let object_store_config = ObjectStoreConfig::from_env();
+        let content_encryption = EncryptionConfig::from_env();
+        let content_offload_threshold = env::var("CONTENT_OFFLOAD_THRESHOLD_BYTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_CONTENT_OFFLOAD_THRESHOLD);
+        let dispatcher_config = DispatcherConfig::from_env();
+        let max_metadata_bytes = env::var("MAX_METADATA_BYTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_MAX_METADATA_BYTES);
+        // 0 turns estimates off
+        let count_estimate_above = env::var("PAGINATION_COUNT_ESTIMATE_ABOVE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .map_or(Some(DEFAULT_COUNT_ESTIMATE_ABOVE), |v: u64| {
+                (v > 0).then_some(v)
+            });
+        let shutdown_config = ShutdownConfig::from_env();
+        let health_cache_ttl = env::var("HEALTH_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .map_or(DEFAULT_HEALTH_CACHE_TTL, Duration::from_secs);
+        let health_background_refresh = env::var("HEALTH_BACKGROUND_REFRESH")
+            .map(|v| v.to_lowercase() != "false")
+            .unwrap_or(true);
+        let maintenance_retry_after = env::var("MAINTENANCE_RETRY_AFTER_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v > 0)
+            .map_or(DEFAULT_MAINTENANCE_RETRY_AFTER, Duration::from_secs);
+        let retry_policy = RetryPolicy::from_env("SUBMISSION_RETRY", RetryPolicy::default());
+        let telemetry = match env::var("TELEMETRY_SINK") {
+            Ok(sink) if !sink.is_empty() => sink
+                .parse()
+                .map_err(anyhow::Error::msg)
+                .context("Invalid TELEMETRY_SINK")?,
+            _ => TelemetrySinkKind::Prometheus,
+        };
+        let worker_defaults = WorkerConfig::default();
         let worker_config = WorkerConfig {
             enabled: enable_background_worker,
-            ..Default::default()
+            backoff: RetryPolicy::from_env("WORKER_BACKOFF", worker_defaults.backoff.clone()),
+            ..worker_defaults
+        };
+        let confirmation_config = ConfirmationConfig {
+            enabled: enable_background_worker,
+            ..ConfirmationConfig::from_env()
+        };
+        let job_jitter = env::var("JOB_JITTER_PERCENT")
+            .ok()
+            .and_then(|v| v.parse::<f64>().ok())
+            .map_or(DEFAULT_JOB_JITTER, |percent| percent / 100.0);
+        let claim_ttl = env::var("WORKER_CLAIM_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v > 0)
+            .map_or(DEFAULT_CLAIM_TTL, Duration::from_secs);
+        let leader_election = env::var("LEADER_ELECTION")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+        let purge_config = PurgeConfig {
+            enabled: enable_background_worker,
+            ..PurgeConfig::from_env()
         };
 
         Ok(Self {
             database_url,
-            blockchain_rpc_url,
-            signer,
-            api_auth_key,
+            database_read_url,
+            pool_config,
+            id_format,
+            migration_target,
+            blockchain,
+            vault_signer,
             host,
             port,
-            enable_rate_limiting,
-            rate_limit_config,
-            enable_background_worker,
-            worker_config,
+            app: AppConfig {
+                role,
+                api_auth_key,
+                admin_auth_key,
+                enable_rate_limiting,
+                rate_limit_config,
+                enable_background_worker,
+                worker_config,
+                confirmation_config,
+                job_jitter,
+                claim_ttl,
+                leader_election,
+                blocklist,
+                abuse: AbuseConfig::from_env(),
+                auth_policy,
+                cors,
+                body_limits: BodyLimits::from_env(),
+                openapi: OpenApiConfig::from_env(),
+                max_metadata_bytes,
+                content_offload_threshold,
+                retry_policy,
+                telemetry,
+                health_cache_ttl,
+                min_wallet_balance,
+                submission_budget,
+                cursor_secret,
+                count_estimate_above,
+                issuer_keys,
+                maintenance_retry_after,
+            },
+            purge_config,
+            circuit_breaker_config,
+            rpc_discovery,
+            webhook_config,
+            nats_config,
+            consumer_config,
+            object_store_config,
+            content_encryption,
+            dispatcher_config,
+            shutdown_config,
+            health_background_refresh,
+            unique_content_hash,
+            auto_migrate,
+            #[cfg(feature = "grpc")]
+            grpc,
         })
     }
 
-    async fn load_signer() -> Result<Arc<dyn TransactionSigner>> {
-        let signer_type = env::var("SIGNER_TYPE").unwrap_or_else(|_| "LOCAL".to_string());
-        let signer: Arc<dyn TransactionSigner> = match signer_type.to_uppercase().as_str() {
+    /// Backend configuration plus the Vault signer, if any, whose token needs renewing
+    async fn load_blockchain(
+        secrets: &dyn SecretsProvider,
+    ) -> Result<(BlockchainBackendConfig, Option<Arc<VaultTransitSigner>>)> {
+        let backend: BlockchainBackend = env::var("BLOCKCHAIN_BACKEND")
+            .unwrap_or_else(|_| "solana".to_string())
+            .parse()
+            .map_err(anyhow::Error::msg)?;
+        match backend {
+            BlockchainBackend::Solana => {
+                // Comma-separated: later URLs are failover endpoints
+                let endpoints = RpcEndpoints::from_list(
+                    &env::var("SOLANA_RPC_URL")
+                        .unwrap_or_else(|_| "https://api.devnet.solana.com".to_string()),
+                );
+                let (signer, vault_signer) = Self::load_signer(secrets).await?;
+                info!("🔑 Public key: {}", signer.public_key());
+                let priority_fee = PriorityFee::parse(
+                    &env::var("SOLANA_PRIORITY_FEE").unwrap_or_default(),
+                    env::var("SOLANA_PRIORITY_FEE_PERCENTILE")
+                        .ok()
+                        .and_then(|v| v.parse().ok())
+                        .unwrap_or(PriorityFee::DEFAULT_PERCENTILE),
+                    env::var("SOLANA_PRIORITY_FEE_MAX")
+                        .ok()
+                        .and_then(|v| v.parse().ok())
+                        .unwrap_or(PriorityFee::DEFAULT_MAX),
+                )
+                .map_err(anyhow::Error::msg)
+                .context("Invalid SOLANA_PRIORITY_FEE")?;
+                let compute_unit_limit = env::var("SOLANA_COMPUTE_UNIT_LIMIT")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .filter(|limit| *limit > 0)
+                    .unwrap_or(DEFAULT_COMPUTE_UNIT_LIMIT);
+                if priority_fee != PriorityFee::None {
+                    info!(
+                        "   ✓ Priority fee {:?}, {} compute units per submission",
+                        priority_fee, compute_unit_limit
+                    );
+                }
+                let defaults = RpcClientConfig::default();
+                let config = BlockchainBackendConfig::Solana {
+                    endpoints,
+                    signer,
+                    config: RpcClientConfig {
+                        priority_fee,
+                        compute_unit_limit,
+                        nonce_account: env::var("SOLANA_NONCE_ACCOUNT")
+                            .ok()
+                            .filter(|v| !v.is_empty()),
+                        max_retries: env::var("SOLANA_RPC_MAX_RETRIES")
+                            .ok()
+                            .and_then(|v| v.parse().ok())
+                            .unwrap_or(defaults.max_retries),
+                        // 0 lifts the budget: only SOLANA_RPC_MAX_RETRIES limits retries
+                        retry_budget: match env::var("SOLANA_RPC_RETRY_BUDGET_SECS")
+                            .ok()
+                            .and_then(|v| v.parse::<u64>().ok())
+                        {
+                            Some(0) => None,
+                            Some(secs) => Some(Duration::from_secs(secs)),
+                            None => defaults.retry_budget,
+                        },
+                        ..defaults
+                    },
+                };
+                Ok((config, vault_signer))
+            }
+            BlockchainBackend::Evm => {
+                let rpc_url =
+                    env::var("EVM_RPC_URL").context("EVM_RPC_URL required for evm backend")?;
+                let chain_id = env::var("EVM_CHAIN_ID")
+                    .context("EVM_CHAIN_ID required for evm backend")?
+                    .parse()
+                    .context("Invalid EVM_CHAIN_ID")?;
+                let anchor_address = env::var("EVM_ANCHOR_ADDRESS")
+                    .ok()
+                    .filter(|v| !v.is_empty())
+                    .map(|v| parse_address(&v))
+                    .transpose()
+                    .context("Invalid EVM_ANCHOR_ADDRESS")?;
+                let signer = Self::load_evm_signer(secrets).await?;
+                let config = BlockchainBackendConfig::Evm {
+                    rpc_url,
+                    signer,
+                    config: EvmClientConfig {
+                        anchor_address,
+                        ..EvmClientConfig::for_chain(chain_id)
+                    },
+                };
+                Ok((config, None))
+            }
+            BlockchainBackend::Noop => {
+                warn!("BLOCKCHAIN_BACKEND=noop: submissions are accepted without a chain");
+                Ok((BlockchainBackendConfig::Noop, None))
+            }
+        }
+    }
+
+    /// `SIGNER_TYPE` (or its alias `SIGNER_BACKEND`), `LOCAL` by default
+    fn signer_type() -> String {
+        env::var("SIGNER_TYPE")
+            .or_else(|_| env::var("SIGNER_BACKEND"))
+            .unwrap_or_else(|_| "LOCAL".to_string())
+            .to_uppercase()
+    }
+
+    /// Ed25519 signer for the Solana backend: a local key, AWS KMS or Vault transit
+    async fn load_signer(
+        secrets: &dyn SecretsProvider,
+    ) -> Result<(Arc<dyn TransactionSigner>, Option<Arc<VaultTransitSigner>>)> {
+        let mut vault_signer = None;
+        // Every signer is audited; the key ID identifies it in the audit records
+        let (signer, key_id): (Arc<dyn TransactionSigner>, String) = match Self::signer_type()
+            .as_str()
+        {
             "LOCAL" => {
-                let key_str = match env::var("ISSUER_PRIVATE_KEY").ok() {
+                let key_str = match secret(secrets, "ISSUER_PRIVATE_KEY").await? {
                     Some(s) if !s.is_empty() && s != "YOUR_BASE58_ENCODED_PRIVATE_KEY_HERE" => s,
                     _ => {
                         warn!("No valid ISSUER_PRIVATE_KEY, generating ephemeral keypair");
@@ -90,22 +551,72 @@ impl Config {
                     }
                 };
                 let secret = SecretString::from(key_str);
-                Arc::new(LocalSigner::new(secret).context("Failed to parse ISSUER_PRIVATE_KEY")?)
+                let local =
+                    LocalSigner::new(secret).context("Failed to parse ISSUER_PRIVATE_KEY")?;
+                let key_id = format!("local:{}", local.public_key());
+                (Arc::new(local), key_id)
             }
             "KMS" => {
                 let key_id =
                     env::var("KMS_KEY_ID").context("KMS_KEY_ID required when SIGNER_TYPE=KMS")?;
                 info!(key_id = %key_id, "Initializing AWS KMS signer...");
-                let kms_signer = AwsKmsSigner::new(key_id)
+                let kms_signer = AwsKmsSigner::new(key_id.clone())
                     .await
                     .context("Failed to initialize AWS KMS signer")?;
-                Arc::new(kms_signer)
+                (Arc::new(kms_signer), format!("kms:{}", key_id))
+            }
+            "VAULT" => {
+                let config =
+                    VaultConfig::from_env().context("Invalid Vault signer configuration")?;
+                let key_id = format!("vault:{}/{}", config.mount, config.key_name);
+                info!(key_id = %key_id, "Initializing Vault transit signer...");
+                let vault = Arc::new(
+                    VaultTransitSigner::new(config)
+                        .await
+                        .context("Failed to initialize Vault transit signer")?,
+                );
+                vault_signer = Some(Arc::clone(&vault));
+                (vault, key_id)
             }
             other => {
-                anyhow::bail!("Invalid SIGNER_TYPE '{}': must be LOCAL or KMS", other);
+                anyhow::bail!(
+                    "Invalid SIGNER_TYPE '{}': must be LOCAL, KMS or VAULT",
+                    other
+                );
             }
         };
-        Ok(signer)
+        Ok((Arc::new(AuditingSigner::new(signer, key_id)), vault_signer))
+    }
+
+    /// secp256k1 signer for the EVM backend: `EVM_PRIVATE_KEY` or a KMS `ECC_SECG_P256K1` key
+    async fn load_evm_signer(secrets: &dyn SecretsProvider) -> Result<Arc<dyn TransactionSigner>> {
+        let (signer, key_id): (Arc<dyn TransactionSigner>, String) =
+            match Self::signer_type().as_str() {
+                "LOCAL" => {
+                    let private_key = secret(secrets, "EVM_PRIVATE_KEY")
+                        .await?
+                        .context("EVM_PRIVATE_KEY required for evm backend")?;
+                    let local = LocalSecp256k1Signer::new(SecretString::from(private_key))
+                        .context("Failed to parse EVM_PRIVATE_KEY")?;
+                    let key_id = format!("local:{}", local.public_key());
+                    (Arc::new(local), key_id)
+                }
+                "KMS" => {
+                    let key_id = env::var("KMS_KEY_ID")
+                        .context("KMS_KEY_ID required when SIGNER_TYPE=KMS")?;
+                    let kms_signer = AwsKmsSecp256k1Signer::new(key_id.clone())
+                        .await
+                        .context("Failed to initialize AWS KMS signer")?;
+                    (Arc::new(kms_signer), format!("kms:{}", key_id))
+                }
+                "VAULT" => {
+                    anyhow::bail!("Vault transit keys are Ed25519; use LOCAL or KMS for evm");
+                }
+                other => {
+                    anyhow::bail!("Invalid SIGNER_TYPE '{}': must be LOCAL or KMS", other);
+                }
+            };
+        Ok(Arc::new(AuditingSigner::new(signer, key_id)))
     }
 }
 
@@ -143,8 +654,545 @@ async fn shutdown_signal() {
     }
 }
 
+/// Flip maintenance mode on every SIGHUP, so operators can switch it without an API key
+#[cfg(unix)]
+async fn maintenance_signal(state: Arc<AppState>) {
+    let mut hangup = match signal::unix::signal(signal::unix::SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(e) => {
+            warn!(error = %e, "Failed to install SIGHUP handler; maintenance toggle disabled");
+            return;
+        }
+    };
+    while hangup.recv().await.is_some() {
+        let enabled = state.toggle_maintenance();
+        info!(enabled, "Received SIGHUP: maintenance mode toggled");
+    }
+}
+
+/// Secret from the `SECRETS_PROVIDER`, falling back to the environment
+async fn secret(secrets: &dyn SecretsProvider, name: &str) -> Result<Option<String>> {
+    resolve_secret(secrets, name)
+        .await
+        .with_context(|| format!("Failed to read secret {}", name))
+}
+
+/// `openapi [--typescript <path>]`: print the OpenAPI spec, or write TypeScript types
+fn openapi_command(typescript: Option<PathBuf>) -> Result<()> {
+    let mut spec = OpenApiConfig::from_env().document();
+    let auth_policy = AuthPolicy::from_env().context("Invalid AUTH_POLICY")?;
+    ApiSecurity::new(auth_policy).modify(&mut spec);
+    match typescript {
+        None => println!("{}", spec.to_pretty_json()?),
+        Some(path) => {
+            std::fs::write(&path, typescript_types(&spec))
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!("Wrote TypeScript types to {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// `keygen`: print a new keypair for `ISSUER_PRIVATE_KEY` and its public key
+fn keygen_command() -> Result<()> {
+    let keypair = generate_keypair();
+    let signer = LocalSigner::new(keypair.clone()).context("Generated keypair is invalid")?;
+    println!("public key:  {}", signer.public_key());
+    println!("private key: {}", keypair.expose_secret());
+    Ok(())
+}
+
+/// `nonce create`: fund a new durable nonce account from the fee payer;
+/// `nonce advance`: invalidate every transaction signed against the configured one
+async fn nonce_command(action: NonceAction) -> Result<()> {
+    dotenv().ok();
+    init_tracing();
+    let config = Config::from_env().await?;
+    let Some(BlockchainBackendConfig::Solana {
+        endpoints,
+        signer,
+        config: client_config,
+    }) = config.blockchain
+    else {
+        anyhow::bail!("Durable nonces need BLOCKCHAIN_BACKEND=solana");
+    };
+    let client = RpcBlockchainClient::with_endpoints(endpoints, signer, client_config)?;
+    match action {
+        NonceAction::Create => {
+            let nonce_key = signing_key_from_base58(&generate_keypair())?;
+            let (address, signature) = client
+                .create_nonce_account(&nonce_key)
+                .await
+                .context("Failed to create the nonce account")?;
+            println!("nonce account: {}", address);
+            println!("signature:     {}", signature);
+            println!(
+                "Set SOLANA_NONCE_ACCOUNT={} to sign submissions against it",
+                address
+            );
+        }
+        NonceAction::Advance => {
+            let signature = client
+                .advance_nonce()
+                .await
+                .context("Failed to advance the nonce account")?;
+            println!("signature: {}", signature);
+        }
+    }
+    Ok(())
+}
+
+/// Connect to `DATABASE_URL` (and the migration target, dual-written) with content
+/// encryption when it is configured
+async fn open_database(config: &Config) -> Result<(Arc<dyn DatabaseClient>, DatabaseBackend)> {
+    let backend = DatabaseBackend::from_url(&config.database_url)?;
+    let encryption: Option<Arc<dyn EncryptionService>> = match &config.content_encryption {
+        Some(encryption_config) => {
+            let encryption = EnvelopeEncryption::from_config(encryption_config)
+                .await
+                .context("Invalid CONTENT_ENCRYPTION_* configuration")?;
+            info!(
+                "   ✓ Item content encrypted at rest (master key {})",
+                encryption.master_key_id()
+            );
+            Some(Arc::new(encryption))
+        }
+        None => None,
+    };
+    let db = connect_database(
+        &config.database_url,
+        config.database_read_url.as_deref(),
+        config.pool_config.clone(),
+        config.id_format.generator(),
+        encryption.clone(),
+    )
+    .await?;
+    let db = match &config.migration_target {
+        Some((url, compare_rate)) => {
+            let target_backend = DatabaseBackend::from_url(url)?;
+            // The pool gauges describe the primary
+            let target_pool = PostgresConfig {
+                metrics_interval: None,
+                ..config.pool_config.clone()
+            };
+            let target = connect_database(
+                url,
+                None,
+                target_pool,
+                config.id_format.generator(),
+                encryption.clone(),
+            )
+            .await?;
+            info!(
+                "   ✓ Dual-writing to migration target ({:?}), comparing {:.1}% of reads",
+                target_backend,
+                compare_rate * 100.0
+            );
+            Arc::new(MigratingDatabaseClient::new(db, target).with_compare_rate(*compare_rate))
+                as Arc<dyn DatabaseClient>
+        }
+        None => db,
+    };
+    Ok((db, backend))
+}
+
+/// Migration state of `db`; the worker commands refuse to write to any other schema
+async fn require_current_schema(db: &dyn DatabaseClient) -> Result<SchemaStatus> {
+    let status = db.migration_status().await?;
+    anyhow::ensure!(
+        status.is_current(),
+        "Database schema does not match this version (pending {:?}, unknown {:?}); run `migrate` first",
+        status.pending,
+        status.unknown
+    );
+    Ok(status)
+}
+
+/// Client of the configured chain behind the circuit breaker; None when
+/// `CHAIN_DISABLED=true`
+async fn open_blockchain(config: &mut Config) -> Result<Option<Arc<dyn BlockchainClient>>> {
+    // Resolve the RPC endpoints before the client's first request; a failed lookup
+    // leaves the SOLANA_RPC_URL list in place
+    if let Some((discovery, _)) = &config.rpc_discovery {
+        match discovery.refresh().await {
+            Ok(_) => info!("   ✓ RPC endpoints discovered"),
+            Err(e) => warn!(error = %e, "   ⚠ Using SOLANA_RPC_URL until discovery succeeds"),
+        }
+    }
+
+    match config.blockchain.take() {
+        Some(backend_config) => {
+            let backend = backend_config.backend();
+            let rpc_client = create_blockchain_client(backend_config)?;
+            // Fail fast while the RPC is down; the retry worker picks entries up once it recovers
+            let client = CircuitBreakerBlockchainClient::new(
+                rpc_client,
+                config.circuit_breaker_config.clone(),
+            );
+            info!(
+                "   ✓ Blockchain client created ({:?}, circuit breaker enabled)",
+                backend
+            );
+            Ok(Some(Arc::new(client)))
+        }
+        None => {
+            info!("   ○ Blockchain disabled (CHAIN_DISABLED=true), items stay pending");
+            Ok(None)
+        }
+    }
+}
+
+/// Both sides of the message bus, sharing one connection
+type MessageBus = (
+    Option<Arc<dyn MessagePublisher>>,
+    Option<Arc<dyn MessageSubscriber>>,
+);
+
+async fn open_message_bus(config: &mut Config) -> Result<MessageBus> {
+    match config.nats_config.take() {
+        #[cfg(feature = "nats")]
+        Some(nats_config) => {
+            let url = nats_config.url.clone();
+            let publisher = NatsPublisher::connect(nats_config)
+                .await
+                .with_context(|| format!("Failed to connect to NATS at {url}"))?;
+            info!("   ✓ Connected to NATS at {}", url);
+            let subscriber = publisher.subscriber();
+            Ok((Some(Arc::new(publisher)), Some(Arc::new(subscriber))))
+        }
+        #[cfg(not(feature = "nats"))]
+        Some(_) => {
+            warn!(
+                "   ⚠ NATS_URL is set but this build lacks the `nats` feature; events are not published"
+            );
+            Ok((None, None))
+        }
+        None => Ok((None, None)),
+    }
+}
+
+async fn open_object_store(config: &mut Config) -> Option<Arc<dyn ObjectStore>> {
+    match config.object_store_config.take() {
+        #[cfg(feature = "s3")]
+        Some(object_store_config) => {
+            let bucket = object_store_config.bucket.clone();
+            let store = S3ObjectStore::new(object_store_config).await;
+            info!("   ✓ Large item content stored in bucket {}", bucket);
+            Some(Arc::new(store))
+        }
+        #[cfg(not(feature = "s3"))]
+        Some(_) => {
+            warn!(
+                "   ⚠ OBJECT_STORE_BUCKET is set but this build lacks the `s3` feature; content stays in the database"
+            );
+            None
+        }
+        None => None,
+    }
+}
+
+/// Renew the Vault token and refresh the RPC endpoint list while the process runs
+fn spawn_signer_tasks(
+    shutdown: &Shutdown,
+    vault_signer: Option<Arc<VaultTransitSigner>>,
+    rpc_discovery: Option<(EndpointDiscovery, Duration)>,
+) {
+    if let Some(vault_signer) = vault_signer {
+        shutdown.register(
+            "vault_token_renewal",
+            spawn_vault_token_renewal(vault_signer),
+        );
+        info!("   ✓ Vault token renewal started");
+    }
+
+    if let Some((discovery, interval)) = rpc_discovery {
+        shutdown.register(
+            "rpc_endpoint_discovery",
+            spawn_endpoint_discovery(discovery, interval),
+        );
+        info!("   ✓ RPC endpoint discovery every {:?}", interval);
+    }
+}
+
+/// Wait for SIGTERM/Ctrl+C (or a task triggering shutdown), then stop everything in phases
+async fn run_until_shutdown(shutdown: Arc<Shutdown>) {
+    tokio::spawn({
+        let shutdown = Arc::clone(&shutdown);
+        async move {
+            shutdown_signal().await;
+            shutdown.trigger();
+        }
+    });
+    shutdown.wait().await;
+    let report = shutdown.shutdown().await;
+    if report.aborted.is_empty() {
+        info!("Shutdown complete");
+    } else {
+        warn!(aborted = ?report.aborted, "Shutdown complete; some tasks were aborted");
+    }
+}
+
+/// `migrate`: apply pending migrations, to the migration target as well, and exit
+async fn migrate_command() -> Result<()> {
+    dotenv().ok();
+    init_tracing();
+    let config = Config::from_env().await?;
+    let (db, backend) = open_database(&config).await?;
+    let status = db.migration_status().await?;
+    db.run_migrations().await?;
+    info!(
+        applied = ?status.pending,
+        "Database migrated ({:?})",
+        backend
+    );
+    db.close().await;
+    Ok(())
+}
+
+/// The worker role: run the submission retry worker and confirmation poller without the
+/// API, so they can be scaled apart from it. `ENABLE_BACKGROUND_WORKER` is ignored.
+async fn run_worker(mut config: Config) -> Result<()> {
+    let (db, backend) = open_database(&config).await?;
+    let schema_status = require_current_schema(db.as_ref()).await?;
+    info!("   ✓ Database connected ({:?})", backend);
+    let blockchain = open_blockchain(&mut config)
+        .await?
+        .context("The worker has nothing to do while CHAIN_DISABLED=true")?;
+    let (publisher, _) = open_message_bus(&mut config).await?;
+    let object_store = open_object_store(&mut config).await;
+
+    let mut app_config = config.app;
+    app_config.enable_background_worker = true;
+    let Application { scheduler, .. } = compose(
+        app_config,
+        Infrastructure {
+            db: Arc::clone(&db),
+            blockchain: Some(blockchain),
+            metrics_handle: None,
+            schema_status,
+            publisher,
+            object_store,
+        },
+    );
+
+    let shutdown = Arc::new(Shutdown::with_config(config.shutdown_config));
+    shutdown.register("job_scheduler", scheduler.spawn());
+    spawn_signer_tasks(&shutdown, config.vault_signer, config.rpc_discovery);
+    shutdown.on_close("database_pool", {
+        let db = Arc::clone(&db);
+        async move { db.close().await }
+    });
+    info!("🔁 Worker running");
+    run_until_shutdown(shutdown).await;
+    Ok(())
+}
+
+/// `submit <item_id>`: queue a failed item for submission again; a running worker picks
+/// it up on its next pass
+async fn submit_command(item_id: &str) -> Result<()> {
+    dotenv().ok();
+    init_tracing();
+    let mut config = Config::from_env().await?;
+    let (db, _) = open_database(&config).await?;
+    let schema_status = require_current_schema(db.as_ref()).await?;
+    let blockchain = open_blockchain(&mut config)
+        .await?
+        .context("Items cannot be submitted while CHAIN_DISABLED=true")?;
+    let object_store = open_object_store(&mut config).await;
+
+    let mut app_config = config.app;
+    app_config.enable_background_worker = false;
+    let Application { state, .. } = compose(
+        app_config,
+        Infrastructure {
+            db: Arc::clone(&db),
+            blockchain: Some(blockchain),
+            metrics_handle: None,
+            schema_status,
+            publisher: None,
+            object_store,
+        },
+    );
+    let result = state.service.retry_blockchain_submission(item_id).await;
+    db.close().await;
+    let item = result.with_context(|| format!("Failed to resubmit item {}", item_id))?;
+    println!(
+        "Item {} is {:?}; the retry worker submits it on its next pass",
+        item.id, item.blockchain_status
+    );
+    Ok(())
+}
+
+/// `doctor`: check configuration and every dependency the server needs, print a report
+/// and exit non-zero if a check failed. Nothing is written: migrations are not applied.
+async fn doctor_command() -> Result<()> {
+    dotenv().ok();
+    init_tracing();
+    let mut report = DoctorReport::default();
+    let config = match Config::from_env().await {
+        Ok(config) => {
+            report.pass("configuration", "environment and secrets loaded");
+            config
+        }
+        Err(e) => {
+            report.fail("configuration", format!("{:#}", e));
+            print!("{}", report);
+            anyhow::bail!("doctor: configuration is invalid");
+        }
+    };
+
+    let encryption: Option<Arc<dyn EncryptionService>> = match &config.content_encryption {
+        Some(encryption_config) => match EnvelopeEncryption::from_config(encryption_config).await {
+            Ok(encryption) => {
+                let master_key_id = encryption.master_key_id().to_string();
+                let round_trip = match encryption.encrypt("doctor").await {
+                    Ok(sealed) => encryption.decrypt(&sealed).await,
+                    Err(e) => Err(e),
+                };
+                report.check(
+                    "content encryption",
+                    round_trip.map(|_| format!("master key {} wraps and unwraps", master_key_id)),
+                );
+                Some(Arc::new(encryption))
+            }
+            Err(e) => {
+                report.fail("content encryption", e.to_string());
+                None
+            }
+        },
+        None => {
+            report.skip("content encryption", "CONTENT_ENCRYPTION_KEY not set");
+            None
+        }
+    };
+
+    let mut databases = vec![("database", config.database_url.as_str())];
+    if let Some((url, _)) = &config.migration_target {
+        databases.push(("migration target", url.as_str()));
+    }
+    for (name, url) in databases {
+        let backend = DatabaseBackend::from_url(url)?;
+        let db = match connect_database(
+            url,
+            None,
+            PostgresConfig::default(),
+            config.id_format.generator(),
+            encryption.clone(),
+        )
+        .await
+        {
+            Ok(db) => db,
+            Err(e) => {
+                report.fail(name, e.to_string());
+                continue;
+            }
+        };
+        report.check(
+            name,
+            ItemRepository::health_check(db.as_ref())
+                .await
+                .map(|()| format!("connected ({:?})", backend)),
+        );
+        let migrations = format!("{} migrations", name);
+        match db.migration_status().await {
+            Ok(status) if status.is_current() => report.pass(migrations, "up to date"),
+            Ok(status) if status.unknown.is_empty() && config.auto_migrate => report.warn(
+                migrations,
+                format!(
+                    "{} pending, applied at startup (AUTO_MIGRATE=true)",
+                    status.pending.len()
+                ),
+            ),
+            Ok(status) => report.fail(
+                migrations,
+                format!(
+                    "pending {:?}, unknown {:?}; the API would serve reads only",
+                    status.pending, status.unknown
+                ),
+            ),
+            Err(e) => report.fail(migrations, e.to_string()),
+        }
+    }
+
+    match config.blockchain {
+        Some(backend_config) => {
+            let backend = backend_config.backend();
+            let signer = backend_config.signer().cloned();
+            if let Some((discovery, _)) = &config.rpc_discovery {
+                report.check(
+                    "rpc discovery",
+                    discovery
+                        .refresh()
+                        .await
+                        .map(|_| "endpoints resolved".to_string()),
+                );
+            }
+            match create_blockchain_client(backend_config) {
+                Ok(client) => {
+                    let rpc = match client.health_check().await {
+                        Ok(()) => client
+                            .get_block_height()
+                            .await
+                            .map(|height| {
+                                format!("{:?} node reachable, block height {}", backend, height)
+                            })
+                            .map_err(|e| e.to_string()),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    report.check("rpc", rpc);
+                    match signer {
+                        Some(signer) => {
+                            report.check(
+                                "signer",
+                                verify_signer(signer.as_ref()).await.map(|public_key| {
+                                    format!("{} (test signature verified)", public_key)
+                                }),
+                            );
+                            match client.get_balance().await {
+                                Ok(balance) => {
+                                    let (status, detail) =
+                                        balance_status(balance, config.app.min_wallet_balance);
+                                    report.record("wallet balance", status, detail);
+                                }
+                                Err(e) => report.fail("wallet balance", e.to_string()),
+                            }
+                        }
+                        None => {
+                            report.skip("signer", "noop backend");
+                            report.skip("wallet balance", "noop backend");
+                        }
+                    }
+                }
+                Err(e) => report.fail("rpc", e.to_string()),
+            }
+        }
+        None => report.skip("blockchain", "disabled (CHAIN_DISABLED=true)"),
+    }
+
+    print!("{}", report);
+    if !report.is_ok() {
+        anyhow::bail!("doctor: {} checks failed", report.failures());
+    }
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    match Cli::parse().command.unwrap_or(Command::Serve) {
+        Command::Serve => serve(None).await,
+        Command::Migrate => migrate_command().await,
+        Command::Worker => serve(Some(ProcessRole::Worker)).await,
+        Command::Submit { item_id } => submit_command(&item_id).await,
+        Command::Keygen => keygen_command(),
+        Command::Nonce { action } => nonce_command(action).await,
+        Command::Openapi { typescript } => openapi_command(typescript),
+        Command::Doctor => doctor_command().await,
+    }
+}
+
+/// `serve`: the API with every background task the configuration enables, the workers
+/// alone, or both, as `role` (default `ROLE`) says
+async fn serve(role: Option<ProcessRole>) -> Result<()> {
     dotenv().ok();
     init_tracing();
 
@@ -153,58 +1201,208 @@ async fn main() -> Result<()> {
         env!("CARGO_PKG_VERSION")
     );
 
-    let config = Config::from_env().await?;
-
-    let public_key = config.signer.public_key();
-    info!("🔑 Public key: {}", public_key);
+    let mut config = Config::from_env().await?;
+    if let Some(role) = role {
+        config.app.role = role;
+    }
 
-    info!("📦 Initializing infrastructure...");
+    info!(
+        "📦 Initializing infrastructure ({:?} role)...",
+        config.app.role
+    );
+    if !config.app.role.serves_api() {
+        return run_worker(config).await;
+    }
 
-    // Initialize database
-    let db_config = PostgresConfig::default();
-    let postgres_client = PostgresClient::new(&config.database_url, db_config).await?;
-    postgres_client.run_migrations().await?;
-    info!("   ✓ Database connected and migrations applied");
+    // Initialize database (backend chosen by the DATABASE_URL scheme)
+    let (db, backend) = open_database(&config).await?;
+    let schema_status = if config.auto_migrate {
+        db.run_migrations().await?;
+        info!(
+            "   ✓ Database connected ({:?}) and migrations applied",
+            backend
+        );
+        SchemaStatus::default()
+    } else {
+        // Migrations are run out of band; a mismatch leaves the API read-only
+        let status = db.migration_status().await?;
+        if status.is_current() {
+            info!(
+                "   ✓ Database connected ({:?}), migrations up to date",
+                backend
+            );
+        } else {
+            warn!(
+                pending = ?status.pending,
+                unknown = ?status.unknown,
+                "Database schema does not match this version; serving reads only"
+            );
+        }
+        status
+    };
+    let schema_current = schema_status.is_current();
+    if schema_current {
+        db.set_unique_content_hash(config.unique_content_hash)
+            .await?;
+    }
 
-    // Initialize blockchain client (signer injected; no raw key in client)
-    let blockchain_client =
-        RpcBlockchainClient::with_defaults(&config.blockchain_rpc_url, Arc::clone(&config.signer))?;
-    info!("   ✓ Blockchain client created");
+    // Initialize blockchain client for the configured backend
+    let blockchain_client = open_blockchain(&mut config).await?;
 
-    // Create application state (PostgresClient implements both ItemRepository and OutboxRepository)
-    let db = Arc::new(postgres_client);
-    let item_repo =
-        Arc::clone(&db) as Arc<dyn testable_rust_architecture_template::domain::ItemRepository>;
-    let outbox_repo =
-        Arc::clone(&db) as Arc<dyn testable_rust_architecture_template::domain::OutboxRepository>;
     let metrics_handle = init_metrics_handle();
-    let app_state = Arc::new(AppState::new_with_metrics(
-        item_repo,
-        outbox_repo,
-        Arc::new(blockchain_client),
-        config.api_auth_key,
-        metrics_handle,
-    ));
-
-    // Start background worker if enabled
-    let worker_shutdown_tx = if config.enable_background_worker {
-        let (_handle, shutdown_tx) =
-            spawn_worker(Arc::clone(&app_state.service), config.worker_config);
-        info!("   ✓ Background worker started");
-        Some(shutdown_tx)
+    metrics::gauge!("schema_migrations_mismatched")
+        .set((schema_status.pending.len() + schema_status.unknown.len()) as f64);
+    // One subscription (and cursor) per webhook URL
+    let mut subscriptions = Vec::new();
+    if let Some(webhook_config) = &config.webhook_config {
+        for url in &webhook_config.urls {
+            let notifier = WebhookNotifier::new(url.clone(), webhook_config.clone())?
+                .with_delivery_log(Arc::clone(&db) as Arc<dyn WebhookDeliveryLog>);
+            subscriptions.push(Subscription::new(url.clone(), Arc::new(notifier)));
+        }
+    }
+    let (publisher, subscriber) = open_message_bus(&mut config).await?;
+    let object_store = open_object_store(&mut config).await;
+    let health_cache_ttl = config.app.health_cache_ttl;
+    let Application {
+        state: app_state,
+        scheduler,
+        router,
+    } = compose(
+        config.app,
+        Infrastructure {
+            db: Arc::clone(&db),
+            blockchain: blockchain_client,
+            metrics_handle,
+            schema_status,
+            publisher,
+            object_store,
+        },
+    );
+
+    // The server, workers, event dispatch and the pool stop in phases through one coordinator
+    let shutdown = Arc::new(Shutdown::with_config(config.shutdown_config));
+
+    // Submission retries and confirmation polling run as scheduled jobs
+    if !scheduler.is_empty() {
+        shutdown.register("job_scheduler", scheduler.spawn());
+    }
+
+    // Purge soft-deleted items (independent of blockchain submission)
+    if config.purge_config.enabled && schema_current {
+        let retention_days = config.purge_config.retention.as_secs() / 86_400;
+        shutdown.register(
+            "item_purge_worker",
+            spawn_purge_worker(Arc::clone(&app_state.service), config.purge_config),
+        );
+        info!(
+            "   ✓ Item purge worker started (retention: {} days)",
+            retention_days
+        );
     } else {
-        info!("   ○ Background worker disabled");
-        None
-    };
+        info!("   ○ Item purge worker disabled");
+    }
 
-    // Create router
-    let router = if config.enable_rate_limiting {
-        info!("   ✓ Rate limiting enabled");
-        create_router_with_rate_limit(app_state, config.rate_limit_config)
+    // Keep the health snapshot fresh so probes are answered from cache
+    if config.health_background_refresh && !health_cache_ttl.is_zero() {
+        let interval = (health_cache_ttl / 2).max(Duration::from_secs(1));
+        shutdown.register(
+            "health_refresh_worker",
+            spawn_health_refresh_worker(Arc::clone(&app_state.service), interval),
+        );
+        info!(
+            "   ✓ Health checks cached for {:?}, refreshed every {:?}",
+            health_cache_ttl, interval
+        );
     } else {
-        info!("   ○ Rate limiting disabled");
-        create_router(app_state)
-    };
+        info!("   ○ Background health refresh disabled");
+    }
+
+    spawn_signer_tasks(&shutdown, config.vault_signer, config.rpc_discovery);
+
+    // Deliver the item status event log to webhook subscribers
+    if subscriptions.is_empty() || !schema_current {
+        info!("   ○ Webhook notifications disabled");
+    } else {
+        let endpoints = subscriptions.len();
+        shutdown.register_in(
+            ShutdownPhase::Flush,
+            "event_dispatcher",
+            spawn_event_dispatcher(
+                Arc::clone(&db) as Arc<dyn EventLog>,
+                subscriptions,
+                config.dispatcher_config,
+            ),
+        );
+        info!(
+            "   ✓ Webhook notifications enabled ({} endpoints)",
+            endpoints
+        );
+    }
+    // Read domain events back from the bus
+    match subscriber.filter(|_| config.consumer_config.enabled) {
+        Some(subscriber) => {
+            let subject = config.consumer_config.subject.clone();
+            let consumer = [
+                "item.created",
+                "item.submitted",
+                "item.confirmed",
+                "item.failed",
+            ]
+            .into_iter()
+            .fold(
+                MessageConsumer::new(subscriber, config.consumer_config),
+                |consumer, event| consumer.with_handler(event, Arc::new(LogEventHandler)),
+            );
+            shutdown.register("message_consumer", spawn_message_consumer(consumer));
+            info!("   ✓ Message consumer subscribed to {}", subject);
+        }
+        None if config.consumer_config.enabled => {
+            warn!("   ⚠ MESSAGE_CONSUMER_ENABLED is set without a NATS connection; not consuming");
+        }
+        None => info!("   ○ Message consumer disabled"),
+    }
+    shutdown.on_close("database_pool", {
+        let db = Arc::clone(&db);
+        async move { db.close().await }
+    });
+
+    // SIGHUP switches maintenance mode on and off
+    #[cfg(unix)]
+    tokio::spawn(maintenance_signal(Arc::clone(&app_state)));
+
+    // gRPC on its own port, drained in the same phase as HTTP
+    #[cfg(feature = "grpc")]
+    {
+        use testable_rust_architecture_template::api::grpc::grpc_service;
+
+        let (port, watch_interval) = config.grpc;
+        let addr = format!("{}:{}", config.host, port);
+        let listener = tokio::net::TcpListener::bind(&addr).await?;
+        let (stop_grpc, mut stop_grpc_rx) = watch::channel(false);
+        let service = grpc_service(Arc::clone(&app_state), stop_grpc_rx.clone(), watch_interval);
+        let server = tokio::spawn({
+            let shutdown = Arc::clone(&shutdown);
+            async move {
+                let stopped = async move {
+                    let _ = stop_grpc_rx.wait_for(|stop| *stop).await;
+                };
+                if let Err(e) = tonic::transport::Server::builder()
+                    .add_service(service)
+                    .serve_with_incoming_shutdown(
+                        tokio_stream::wrappers::TcpListenerStream::new(listener),
+                        stopped,
+                    )
+                    .await
+                {
+                    error!(error = %e, "gRPC server failed");
+                    shutdown.trigger();
+                }
+            }
+        });
+        shutdown.register_in(ShutdownPhase::Http, "grpc_server", (server, stop_grpc));
+        info!("   ✓ gRPC server on {}", addr);
+    }
 
     let addr = format!("{}:{}", config.host, config.port);
     let listener = tokio::net::TcpListener::bind(&addr).await?;
@@ -213,15 +1411,27 @@ async fn main() -> Result<()> {
     info!("📖 Swagger UI available at http://{}/swagger-ui", addr);
     info!("📄 OpenAPI spec at http://{}/api-docs/openapi.json", addr);
 
-    axum::serve(listener, router)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
-
-    // Signal worker to shutdown
-    if let Some(tx) = worker_shutdown_tx {
-        let _ = tx.send(true);
-    }
+    // First phase: stop accepting connections and drain in-flight requests
+    let (stop_http, mut stop_http_rx) = watch::channel(false);
+    let server = tokio::spawn({
+        let shutdown = Arc::clone(&shutdown);
+        async move {
+            let stopped = async move {
+                let _ = stop_http_rx.wait_for(|stop| *stop).await;
+            };
+            if let Err(e) = api::serve(listener, router, stopped).await {
+                error!(error = %e, "HTTP server failed");
+                shutdown.trigger();
+            }
+        }
+    });
+    shutdown.register_in(ShutdownPhase::Http, "http_server", (server, stop_http));
+    // Upgraded WebSocket connections are not drained with the server; close them here
+    shutdown.on_phase(ShutdownPhase::Http, "live_feed", {
+        let feed = app_state.service.feed().clone();
+        async move { feed.close() }
+    });
 
-    info!("Server shutdown complete");
+    run_until_shutdown(shutdown).await;
     Ok(())
 }
@@ -0,0 +1,164 @@
+//! Span capture for observability tests.
+//!
+//! [`TraceCapture`] records every span opened while a future runs, with its parent and
+//! fields, so tests can assert that e.g. `create_and_submit_item` records the `item_id`:
+//!
+//! ```ignore
+//! let capture = TraceCapture::default();
+//! let item = capture.run(service.create_and_submit_item(&request)).await?;
+//! let span = capture.span("create_and_submit_item").unwrap();
+//! assert_eq!(span.field("item_id"), Some(item.id.as_str()));
+//! ```
+//!
+//! The subscriber only follows the future it wraps: spans opened in tasks started with
+//! `tokio::spawn` are not captured.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::instrument::WithSubscriber;
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Subscriber, subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{Layer, Registry};
+
+/// A span opened under a [`TraceCapture`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedSpan {
+    pub name: String,
+    pub target: String,
+    /// Name of the enclosing span, if any
+    pub parent: Option<String>,
+    /// Field values: strings as-is, everything else in its `Debug` form
+    pub fields: BTreeMap<String, String>,
+}
+
+impl CapturedSpan {
+    /// Value of `name`, if the span declared and recorded it
+    #[must_use]
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+}
+
+/// Collects the spans opened while futures run under it; clones share the same record
+#[derive(Debug, Clone, Default)]
+pub struct TraceCapture {
+    spans: Arc<Mutex<Vec<CapturedSpan>>>,
+}
+
+impl TraceCapture {
+    /// Subscriber recording into this capture, e.g. for `tracing::subscriber::with_default`
+    #[must_use]
+    pub fn subscriber(&self) -> impl Subscriber + Send + Sync + 'static {
+        Registry::default().with(CaptureLayer {
+            spans: Arc::clone(&self.spans),
+        })
+    }
+
+    /// Run `future` with spans recorded into this capture
+    pub async fn run<F: Future>(&self, future: F) -> F::Output {
+        future.with_subscriber(self.subscriber()).await
+    }
+
+    /// Run `f` with spans recorded into this capture
+    pub fn run_sync<T>(&self, f: impl FnOnce() -> T) -> T {
+        subscriber::with_default(self.subscriber(), f)
+    }
+
+    /// Spans captured so far, in the order they were opened
+    #[must_use]
+    pub fn spans(&self) -> Vec<CapturedSpan> {
+        self.spans.lock().expect("capture lock").clone()
+    }
+
+    /// First captured span named `name`
+    #[must_use]
+    pub fn span(&self, name: &str) -> Option<CapturedSpan> {
+        self.spans().into_iter().find(|span| span.name == name)
+    }
+}
+
+/// Index of a span's entry in the capture, kept in the registry's span extensions
+struct SpanIndex(usize);
+
+struct CaptureLayer {
+    spans: Arc<Mutex<Vec<CapturedSpan>>>,
+}
+
+impl<S> Layer<S> for CaptureLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = BTreeMap::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        let mut spans = self.spans.lock().expect("capture lock");
+        spans.push(CapturedSpan {
+            name: span.name().to_string(),
+            target: span.metadata().target().to_string(),
+            parent: span.parent().map(|parent| parent.name().to_string()),
+            fields,
+        });
+        span.extensions_mut().insert(SpanIndex(spans.len() - 1));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let extensions = span.extensions();
+        let Some(SpanIndex(index)) = extensions.get::<SpanIndex>() else {
+            return;
+        };
+        let mut spans = self.spans.lock().expect("capture lock");
+        if let Some(captured) = spans.get_mut(*index) {
+            values.record(&mut FieldVisitor(&mut captured.fields));
+        }
+    }
+}
+
+struct FieldVisitor<'a>(&'a mut BTreeMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_nesting_and_late_fields() {
+        let capture = TraceCapture::default();
+        capture.run_sync(|| {
+            let outer = tracing::info_span!("outer", id = tracing::field::Empty, n = 3);
+            let _guard = outer.enter();
+            tracing::info_span!("inner", label = "x").in_scope(|| {});
+            outer.record("id", "abc");
+        });
+
+        let outer = capture.span("outer").unwrap();
+        assert_eq!(outer.parent, None);
+        assert_eq!(outer.field("id"), Some("abc"));
+        assert_eq!(outer.field("n"), Some("3"));
+        let inner = capture.span("inner").unwrap();
+        assert_eq!(inner.parent.as_deref(), Some("outer"));
+        assert_eq!(inner.field("label"), Some("x"));
+        assert_eq!(capture.spans().len(), 2);
+    }
+}
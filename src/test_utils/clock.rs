@@ -0,0 +1,149 @@
+//! Controllable clock for time-based tests.
+//!
+//! [`MockClock`] stands still until the test moves it: sleeps on it end only once
+//! [`MockClock::advance`] has moved it past their deadline, so backoff and worker
+//! intervals can be fast-forwarded without waiting on real time.
+//!
+//! ```ignore
+//! let clock = Arc::new(MockClock::default());
+//! let mock = Arc::new(MockProvider::new().with_clock(clock.clone()));
+//! let service = AppService::new(item_repo, outbox_repo, blockchain).with_clock(clock.clone());
+//! service.process_pending_submissions(10).await?; // fails, retry scheduled in 2s
+//! clock.advance(Duration::from_secs(2));
+//! service.process_pending_submissions(10).await?; // retried
+//! ```
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use tokio::sync::watch;
+
+use crate::domain::Clock;
+
+/// How long [`MockClock::wait_for_sleepers`] waits before failing the test
+pub const SLEEPER_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Clock that only moves when told to
+pub struct MockClock {
+    now: watch::Sender<DateTime<Utc>>,
+}
+
+impl Default for MockClock {
+    /// Starts at 2025-01-01T00:00:00Z
+    fn default() -> Self {
+        Self::new(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap())
+    }
+}
+
+impl MockClock {
+    #[must_use]
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            now: watch::Sender::new(start),
+        }
+    }
+
+    /// Move the clock forward by `by`, waking the sleeps that are now due
+    pub fn advance(&self, by: Duration) {
+        let by = chrono::Duration::from_std(by).expect("advance fits a chrono duration");
+        self.now.send_modify(|now| *now += by);
+    }
+
+    /// Set the clock to `to` (which may be in the past), waking the sleeps that are due
+    pub fn set(&self, to: DateTime<Utc>) {
+        self.now.send_replace(to);
+    }
+
+    /// Number of sleeps waiting on this clock
+    #[must_use]
+    pub fn sleepers(&self) -> usize {
+        self.now.receiver_count()
+    }
+
+    /// Wait until at least `count` sleeps are waiting, so an [`Self::advance`] that
+    /// follows reaches them instead of racing their start
+    ///
+    /// # Panics
+    ///
+    /// If fewer than `count` sleeps are waiting after [`SLEEPER_WAIT_TIMEOUT`] of tokio
+    /// time, so a task that stopped sleeping on the clock fails the test instead of
+    /// hanging it. Under `tokio::time::pause()` that time passes as soon as the runtime
+    /// is idle, so a task still blocked on non-timer work (e.g. `spawn_blocking`) can
+    /// fail the wait early; keep paused-time tests to tasks that only wait on timers.
+    pub async fn wait_for_sleepers(&self, count: usize) {
+        let wait = async {
+            while self.sleepers() < count {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        };
+        if tokio::time::timeout(SLEEPER_WAIT_TIMEOUT, wait)
+            .await
+            .is_err()
+        {
+            panic!(
+                "expected {} sleeps on the MockClock, found {} after {:?}",
+                count,
+                self.sleepers(),
+                SLEEPER_WAIT_TIMEOUT
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.borrow()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        let mut rx = self.now.subscribe();
+        let deadline = chrono::Duration::from_std(duration)
+            .ok()
+            .and_then(|duration| rx.borrow().checked_add_signed(duration));
+        let Some(deadline) = deadline else {
+            // Past the end of time: never due
+            return std::future::pending().await;
+        };
+        // The sender lives as long as `self`, so this only returns once the deadline passed
+        let _ = rx.wait_for(|now| *now >= deadline).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_sleep_ends_only_when_advanced_past_the_deadline() {
+        let clock = Arc::new(MockClock::default());
+        let start = clock.now();
+        let sleeper = Arc::clone(&clock);
+        let sleep = tokio::spawn(async move { sleeper.sleep(Duration::from_secs(10)).await });
+
+        clock.wait_for_sleepers(1).await;
+        clock.advance(Duration::from_secs(9));
+        tokio::task::yield_now().await;
+        assert!(!sleep.is_finished());
+
+        clock.advance(Duration::from_secs(1));
+        sleep.await.unwrap();
+        assert_eq!(clock.now(), start + chrono::Duration::seconds(10));
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "expected 1 sleeps on the MockClock, found 0")]
+    async fn test_wait_for_sleepers_fails_instead_of_hanging() {
+        tokio::time::pause();
+        MockClock::default().wait_for_sleepers(1).await;
+    }
+
+    #[tokio::test]
+    async fn test_zero_sleep_returns_immediately() {
+        let clock = MockClock::default();
+        clock.sleep(Duration::ZERO).await;
+        assert_eq!(clock.sleepers(), 0);
+    }
+}
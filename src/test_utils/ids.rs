@@ -0,0 +1,48 @@
+//! Predictable IDs for tests that assert on them.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::domain::IdGenerator;
+
+/// Zero-padded counter shared by all row kinds: `0000000001`, `0000000002`, ...
+#[derive(Debug, Default)]
+pub struct SequenceIdGenerator {
+    last: AtomicU64,
+}
+
+impl SequenceIdGenerator {
+    /// Start after `last`, so the first ID is `last + 1`
+    #[must_use]
+    pub fn starting_after(last: u64) -> Self {
+        Self {
+            last: AtomicU64::new(last),
+        }
+    }
+}
+
+impl IdGenerator for SequenceIdGenerator {
+    fn generate(&self, _kind: &'static str) -> String {
+        format!("{:010}", self.last.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::domain::{CreateItemRequest, ItemRepository};
+    use crate::test_utils::MockProvider;
+
+    #[tokio::test]
+    async fn test_mock_provider_uses_sequence_ids() {
+        let mock = MockProvider::new().with_id_generator(Arc::new(SequenceIdGenerator::default()));
+        let request = CreateItemRequest::new("Item".to_string(), "content".to_string());
+
+        let first = mock.create_item(&request).await.unwrap();
+        let second = mock.create_item(&request).await.unwrap();
+
+        assert_eq!(first.id, "item_0000000001");
+        assert_eq!(second.id, "item_0000000002");
+    }
+}
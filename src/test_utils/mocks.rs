@@ -1,16 +1,32 @@
 //! Mock implementations for testing.
 
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
-use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, Ordering};
+use bytes::Bytes;
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::stream::{self, BoxStream};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::instrument;
 
+use crate::app::SystemClock;
 use crate::domain::{
-    BlockchainClient, BlockchainError, BlockchainStatus, CreateItemRequest, HealthCheckError, Item,
-    ItemError, ItemMetadata, ItemRepository, OutboxRepository, OutboxStatus, PaginatedResponse,
-    SolanaOutboxEntry, SolanaOutboxPayload, build_solana_outbox_payload_from_request,
+    ApiKey, ApiKeyError, ApiKeyQuota, ApiKeyScope, ApiKeyStore, AuditEntry, AuditFilter,
+    AuditLogger, BlockchainClient, BlockchainError, BlockchainStatus, Clock, ContentHasher,
+    CreateItemRequest, DomainEvent, EventLog, ExportBookmark, FailedSubmission, HealthCheckError,
+    IdGenerator, InboundMessage, Item, ItemCount, ItemError, ItemListFilter, ItemMetadata,
+    ItemPosition, ItemRepository, ItemSearchHit, ItemStatusEvent, Job, JobError, JobStatus,
+    JobStore, JournalStatus, KeyUsage, LeaderElection, MessagePublisher, MessageSubscriber,
+    MessagingError, NotificationClient, NotificationError, ObjectStore, ObjectStoreError,
+    OnChainTransaction, OutboxRepository, OutboxStatus, PaginatedResponse, QueueDepth, QuotaUsage,
+    RequestJournal, RequestJournalEntry, RequestJournalError, SolanaOutboxEntry,
+    SolanaOutboxPayload, SpendLedger, SubmissionAttempt, SubmissionRepository, SubmissionTrace,
+    TagCount, TelemetrySink, TenantScope, TimeRange, UnitOfWork, UsageLedger, WebhookDelivery,
+    WebhookDeliveryLog, build_solana_outbox_payload_from_request, month_start,
 };
+use crate::infra::UuidV7IdGenerator;
 
 /// Configuration for mock behavior
 #[derive(Debug, Clone, Default)]
@@ -19,6 +35,8 @@ pub struct MockConfig {
     pub error_message: Option<String>,
     pub fail_with_timeout: bool,
     pub timeout_blockhash: Option<String>,
+    /// Delay added to every mock call, in milliseconds (0: none)
+    pub latency_ms: u64,
 }
 
 impl MockConfig {
@@ -32,10 +50,36 @@ impl MockConfig {
         Self {
             should_fail: true,
             error_message: Some(message.into()),
-            fail_with_timeout: false,
-            timeout_blockhash: None,
+            ..Self::default()
         }
     }
+
+    /// Delay every call by `latency_ms` (e.g. to exercise timeouts)
+    #[must_use]
+    pub fn with_latency_ms(mut self, latency_ms: u64) -> Self {
+        self.latency_ms = latency_ms;
+        self
+    }
+
+    /// Sleep on the tokio clock, so tests can skip the delay with `tokio::time::pause`
+    async fn simulate_latency(&self) {
+        if self.latency_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(self.latency_ms)).await;
+        }
+    }
+}
+
+/// Holder of a leader lease and when it expires
+type Lease = (String, DateTime<Utc>);
+
+/// Calls to [`MockProvider`] methods by method name, and the failures injected into them
+#[derive(Debug, Default)]
+struct FailureInjection {
+    calls: HashMap<&'static str, u64>,
+    /// Methods failing on every call
+    methods: HashSet<&'static str>,
+    /// Calls failing once, as (method, 1-based call number)
+    nth_calls: HashSet<(&'static str, u64)>,
 }
 
 /// Mock provider implementing both ItemRepository and OutboxRepository with shared state.
@@ -43,8 +87,43 @@ impl MockConfig {
 pub struct MockProvider {
     storage: Arc<Mutex<HashMap<String, Item>>>,
     outbox: Arc<Mutex<HashMap<String, SolanaOutboxEntry>>>,
+    /// API keys by id, with the stored secret hash
+    api_keys: Arc<Mutex<HashMap<String, (String, ApiKey)>>>,
+    /// Idempotent request journal by key
+    journal: Arc<Mutex<HashMap<String, RequestJournalEntry>>>,
+    /// Webhook delivery attempts in the order they were recorded
+    webhook_deliveries: Arc<Mutex<Vec<WebhookDelivery>>>,
+    /// Audit log entries in the order they were recorded
+    audit_entries: Arc<Mutex<Vec<AuditEntry>>>,
+    /// Item status event log (index + 1 is the position)
+    events: Arc<Mutex<Vec<ItemStatusEvent>>>,
+    /// Event log cursors by subscription
+    subscription_cursors: Arc<Mutex<HashMap<String, i64>>>,
+    /// Dead-letter queue, with the outbox entry as it was when dead-lettered
+    failed_submissions: Arc<Mutex<Vec<(FailedSubmission, SolanaOutboxEntry)>>>,
+    /// Submission budget spend by (signer, day)
+    spend: Arc<Mutex<HashMap<(String, NaiveDate), u64>>>,
+    /// Requests counted per API key and day
+    usage: Arc<Mutex<BTreeMap<(String, NaiveDate), u64>>>,
+    /// Background jobs by id
+    jobs: Arc<Mutex<HashMap<String, Job>>>,
+    /// Export bookmarks by name
+    export_bookmarks: Arc<Mutex<HashMap<String, ExportBookmark>>>,
+    /// Submission attempt history by item id
+    submission_attempts: Arc<Mutex<HashMap<String, Vec<SubmissionAttempt>>>>,
+    /// Leader leases by name
+    leader_leases: Arc<Mutex<HashMap<String, Lease>>>,
+    /// Time source when deciding whether a retry is due (the system clock by default)
+    clock: Arc<dyn Clock>,
+    /// IDs of new items, dead letters, jobs and API keys (UUIDv7 by default)
+    ids: Arc<dyn IdGenerator>,
+    /// Added to `clock` when deciding whether a retry is due
+    clock_offset: Arc<Mutex<chrono::Duration>>,
     config: MockConfig,
     is_healthy: AtomicBool,
+    /// Reject outbox inserts (to exercise rollback of the surrounding unit of work)
+    fail_outbox_writes: AtomicBool,
+    injection: Mutex<FailureInjection>,
 }
 
 impl MockProvider {
@@ -58,8 +137,26 @@ impl MockProvider {
         Self {
             storage: Arc::new(Mutex::new(HashMap::new())),
             outbox: Arc::new(Mutex::new(HashMap::new())),
+            api_keys: Arc::new(Mutex::new(HashMap::new())),
+            journal: Arc::new(Mutex::new(HashMap::new())),
+            webhook_deliveries: Arc::new(Mutex::new(Vec::new())),
+            audit_entries: Arc::new(Mutex::new(Vec::new())),
+            events: Arc::new(Mutex::new(Vec::new())),
+            subscription_cursors: Arc::new(Mutex::new(HashMap::new())),
+            failed_submissions: Arc::new(Mutex::new(Vec::new())),
+            spend: Arc::new(Mutex::new(HashMap::new())),
+            usage: Arc::new(Mutex::new(BTreeMap::new())),
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+            export_bookmarks: Arc::new(Mutex::new(HashMap::new())),
+            submission_attempts: Arc::new(Mutex::new(HashMap::new())),
+            leader_leases: Arc::new(Mutex::new(HashMap::new())),
+            clock: Arc::new(SystemClock),
+            ids: Arc::new(UuidV7IdGenerator),
+            clock_offset: Arc::new(Mutex::new(chrono::Duration::zero())),
             config,
             is_healthy: AtomicBool::new(true),
+            fail_outbox_writes: AtomicBool::new(false),
+            injection: Mutex::new(FailureInjection::default()),
         }
     }
 
@@ -76,17 +173,224 @@ impl MockProvider {
         self.is_healthy.store(healthy, Ordering::Relaxed);
     }
 
+    /// Make every outbox insert fail with `RepositoryFailure` until reset
+    pub fn set_outbox_writes_failing(&self, failing: bool) {
+        self.fail_outbox_writes.store(failing, Ordering::Relaxed);
+    }
+
+    /// Make every call to `method` (e.g. `"create_item"`) fail with the repository error
+    /// of its trait until [`Self::clear_failures`]
+    ///
+    /// ```
+    /// use testable_rust_architecture_template::domain::{CreateItemRequest, ItemRepository};
+    /// use testable_rust_architecture_template::test_utils::MockProvider;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mock = MockProvider::new();
+    /// mock.fail_on("create_item");
+    /// let request = CreateItemRequest::new("Item".into(), "Content".into());
+    /// assert!(mock.create_item(&request).await.is_err());
+    /// assert!(mock.get_item("item_1").await.is_ok());
+    /// # });
+    /// ```
+    pub fn fail_on(&self, method: &'static str) {
+        self.injection.lock().unwrap().methods.insert(method);
+    }
+
+    /// Make only the `n`th call to `method` fail (counting from 1 and including the calls
+    /// made so far), e.g. to exercise the retry of a transient failure
+    pub fn fail_nth_call(&self, method: &'static str, n: u64) {
+        self.injection.lock().unwrap().nth_calls.insert((method, n));
+    }
+
+    /// Remove the failures added by [`Self::fail_on`] and [`Self::fail_nth_call`]
+    pub fn clear_failures(&self) {
+        let mut injection = self.injection.lock().unwrap();
+        injection.methods.clear();
+        injection.nth_calls.clear();
+    }
+
+    /// Number of calls to `method` so far, failed ones included
+    pub fn call_count(&self, method: &str) -> u64 {
+        self.injection
+            .lock()
+            .unwrap()
+            .calls
+            .get(method)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Get recorded webhook delivery attempts (for testing)
+    pub fn get_webhook_deliveries(&self) -> Vec<WebhookDelivery> {
+        self.webhook_deliveries.lock().unwrap().clone()
+    }
+
+    /// Get recorded audit log entries (for testing)
+    pub fn get_audit_entries(&self) -> Vec<AuditEntry> {
+        self.audit_entries.lock().unwrap().clone()
+    }
+
     /// Get all stored items (for testing)
     pub fn get_all_items(&self) -> Vec<Item> {
         self.storage.lock().unwrap().values().cloned().collect()
     }
 
-    fn check_should_fail(&self) -> Result<(), ItemError> {
-        if self.config.should_fail {
+    /// Edit a stored item in place, bypassing the repository (simulates an out-of-band change)
+    pub fn edit_item(&self, id: &str, edit: impl FnOnce(&mut Item)) {
+        if let Some(item) = self.storage.lock().unwrap().get_mut(id) {
+            edit(item);
+        }
+    }
+
+    /// Get dead-lettered submissions, including requeued ones (for testing)
+    pub fn get_failed_submissions(&self) -> Vec<FailedSubmission> {
+        self.failed_submissions
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(submission, _)| submission.clone())
+            .collect()
+    }
+
+    /// Decide whether retries are due on `clock` (e.g. the [`MockClock`](super::MockClock)
+    /// shared with the service) instead of the system clock
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Generate new row IDs with `ids` (e.g. a
+    /// [`SequenceIdGenerator`](super::SequenceIdGenerator) for predictable IDs)
+    #[must_use]
+    pub fn with_id_generator(mut self, ids: Arc<dyn IdGenerator>) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// New ID of a row of `kind`, prefixed like the database backends do (`item_...`)
+    fn new_id(&self, kind: &'static str) -> String {
+        format!("{}_{}", kind, self.ids.generate(kind))
+    }
+
+    /// Move the mock's clock forward so scheduled retries become due without waiting
+    /// (for long-running tests on virtual time)
+    pub fn advance_clock(&self, by: chrono::Duration) {
+        *self.clock_offset.lock().unwrap() += by;
+    }
+
+    /// Current time as seen by retry scheduling
+    fn now(&self) -> DateTime<Utc> {
+        self.clock.now() + *self.clock_offset.lock().unwrap()
+    }
+
+    /// Count a call to `method`; true when a failure was injected into it
+    fn injected(&self, method: &'static str) -> bool {
+        let mut injection = self.injection.lock().unwrap();
+        let calls = injection.calls.entry(method).or_insert(0);
+        *calls += 1;
+        let n = *calls;
+        injection.methods.contains(method) || injection.nth_calls.contains(&(method, n))
+    }
+
+    /// Count a call to `method`; true when it should fail (injected or configured)
+    fn injected_failure(&self, method: &'static str) -> bool {
+        self.injected(method) || self.config.should_fail
+    }
+
+    fn check_should_fail(&self, method: &'static str) -> Result<(), ItemError> {
+        if self.injected_failure(method) {
+            return Err(ItemError::RepositoryFailure);
+        }
+        Ok(())
+    }
+
+    fn check_outbox_write(&self, method: &'static str) -> Result<(), ItemError> {
+        let failed = self.injected_failure(method);
+        if failed || self.fail_outbox_writes.load(Ordering::Relaxed) {
             return Err(ItemError::RepositoryFailure);
         }
         Ok(())
     }
+
+    /// New item `id` in `status`, as the repositories build it from a create request
+    fn new_item(id: String, data: &CreateItemRequest, status: BlockchainStatus) -> Item {
+        let now = Utc::now();
+        Item {
+            id,
+            hash: ContentHasher::hash_request(data),
+            name: data.name.clone(),
+            description: data.description.clone(),
+            content: data.stored_content().to_string(),
+            content_key: data.content_key.clone(),
+            content_type: data.content_type().to_string(),
+            content_length: data.content.len() as i64,
+            metadata: data.metadata.as_ref().map(|m| ItemMetadata {
+                author: m.author.clone(),
+                version: m.version.clone(),
+                tags: m.tags.clone(),
+                custom_fields: m.custom_fields.clone(),
+            }),
+            blockchain_status: status,
+            created_at: now,
+            updated_at: now,
+            tenant_id: TenantScope::for_new_item(),
+            ..Item::default()
+        }
+    }
+
+    /// Stored item `id` if the current tenant may see it (mirrors the repositories'
+    /// tenant filter)
+    fn visible_item<'a>(storage: &'a mut HashMap<String, Item>, id: &str) -> Option<&'a mut Item> {
+        storage
+            .get_mut(id)
+            .filter(|item| TenantScope::permits(&item.tenant_id))
+    }
+
+    /// New pending outbox entry for `item_id`
+    fn new_outbox_entry(item_id: &str, payload: SolanaOutboxPayload) -> SolanaOutboxEntry {
+        SolanaOutboxEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            aggregate_id: item_id.to_string(),
+            payload,
+            status: OutboxStatus::Pending,
+            retry_count: 0,
+            attempt_blockhash: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Reset `item` to `pending_submission` after an outbox entry was queued for it
+    fn mark_pending_submission(item: &mut Item) {
+        item.blockchain_status = BlockchainStatus::PendingSubmission;
+        item.blockchain_last_error = None;
+        item.blockchain_next_retry_at = None;
+        item.blockchain_retry_count = 0;
+        item.updated_at = Utc::now();
+    }
+
+    /// Append a status event when `item` enters a notified status (mirrors the
+    /// in-transaction append of the Postgres repository)
+    fn record_transition(
+        &self,
+        item_id: &str,
+        previous: BlockchainStatus,
+        status: BlockchainStatus,
+        signature: Option<&str>,
+        error: Option<&str>,
+    ) {
+        if previous == status || !ItemStatusEvent::is_notified(status) {
+            return;
+        }
+        let mut events = self.events.lock().unwrap();
+        let mut event = ItemStatusEvent::new(item_id, status);
+        event.position = events.len() as i64 + 1;
+        event.sequence = events.iter().filter(|e| e.item_id == item_id).count() as i64 + 1;
+        event.signature = signature.map(str::to_string);
+        event.error = error.map(str::to_string);
+        events.push(event);
+    }
 }
 
 impl Default for MockProvider {
@@ -106,85 +410,170 @@ pub fn mock_repos(
     )
 }
 
+/// Unit of work over a [`MockProvider`]: writes are staged and applied under the
+/// provider's locks on commit, so readers never see part of one
+pub struct MockUnitOfWork<'a> {
+    provider: &'a MockProvider,
+    /// Items inserted or changed in this unit, by id
+    items: HashMap<String, Item>,
+    outbox: Vec<SolanaOutboxEntry>,
+}
+
+#[async_trait]
+impl UnitOfWork for MockUnitOfWork<'_> {
+    #[instrument(skip(self, data), fields(item_name = %data.name))]
+    async fn insert_item(&mut self, data: &CreateItemRequest) -> Result<Item, ItemError> {
+        self.provider.config.simulate_latency().await;
+        self.provider.check_should_fail("insert_item")?;
+        let item = MockProvider::new_item(
+            self.provider.new_id("item"),
+            data,
+            BlockchainStatus::Pending,
+        );
+        self.items.insert(item.id.clone(), item.clone());
+        Ok(item)
+    }
+
+    #[instrument(skip(self, payload))]
+    async fn enqueue_solana_outbox(
+        &mut self,
+        item_id: &str,
+        payload: &SolanaOutboxPayload,
+    ) -> Result<Item, ItemError> {
+        self.provider.config.simulate_latency().await;
+        self.provider.check_outbox_write("enqueue_solana_outbox")?;
+        if !self.items.contains_key(item_id) {
+            let mut storage = self.provider.storage.lock().unwrap();
+            let stored = MockProvider::visible_item(&mut storage, item_id).map(|i| i.clone());
+            let item = stored.ok_or_else(|| ItemError::NotFound(item_id.to_string()))?;
+            self.items.insert(item_id.to_string(), item);
+        }
+        let item = self.items.get_mut(item_id).expect("staged above");
+        MockProvider::mark_pending_submission(item);
+        self.outbox
+            .push(MockProvider::new_outbox_entry(item_id, payload.clone()));
+        Ok(item.clone())
+    }
+
+    #[instrument(skip(self))]
+    async fn commit(self: Box<Self>) -> Result<(), ItemError> {
+        self.provider.config.simulate_latency().await;
+        self.provider.check_should_fail("commit")?;
+        let mut storage = self.provider.storage.lock().unwrap();
+        let mut outbox = self.provider.outbox.lock().unwrap();
+        storage.extend(self.items);
+        outbox.extend(self.outbox.into_iter().map(|e| (e.id.clone(), e)));
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn rollback(self: Box<Self>) -> Result<(), ItemError> {
+        self.provider.config.simulate_latency().await;
+        if self.provider.injected("rollback") {
+            return Err(ItemError::RepositoryFailure);
+        }
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl ItemRepository for MockProvider {
+    #[instrument(skip(self))]
     async fn health_check(&self) -> Result<(), HealthCheckError> {
+        self.config.simulate_latency().await;
         if !self.is_healthy.load(Ordering::Relaxed) {
             return Err(HealthCheckError::DatabaseUnavailable);
         }
-        self.check_should_fail()
+        self.check_should_fail("health_check")
             .map_err(|_| HealthCheckError::DatabaseUnavailable)
     }
 
+    #[instrument(skip(self))]
     async fn get_item(&self, id: &str) -> Result<Option<Item>, ItemError> {
-        self.check_should_fail()?;
-        let storage = self.storage.lock().unwrap();
-        Ok(storage.get(id).cloned())
+        self.config.simulate_latency().await;
+        self.check_should_fail("get_item")?;
+        let mut storage = self.storage.lock().unwrap();
+        Ok(Self::visible_item(&mut storage, id).map(|i| i.clone()))
     }
 
+    #[instrument(skip(self, data), fields(item_name = %data.name))]
     async fn create_item(&self, data: &CreateItemRequest) -> Result<Item, ItemError> {
-        self.check_should_fail()?;
-        let id = format!("item_{}", uuid::Uuid::new_v4());
-        let now = Utc::now();
-        let metadata = data.metadata.as_ref().map(|m| ItemMetadata {
-            author: m.author.clone(),
-            version: m.version.clone(),
-            tags: m.tags.clone(),
-            custom_fields: m.custom_fields.clone(),
-        });
-        let item = Item {
-            id: id.clone(),
-            hash: format!("hash_{}", id),
-            name: data.name.clone(),
-            description: data.description.clone(),
-            content: data.content.clone(),
-            metadata,
-            blockchain_status: BlockchainStatus::PendingSubmission,
-            blockchain_signature: None,
-            blockchain_retry_count: 0,
-            blockchain_last_error: None,
-            blockchain_next_retry_at: None,
-            created_at: now,
-            updated_at: now,
-        };
-        let outbox_entry = SolanaOutboxEntry {
-            id: uuid::Uuid::new_v4().to_string(),
-            aggregate_id: id.clone(),
-            payload: build_solana_outbox_payload_from_request(&id, data),
-            status: OutboxStatus::Pending,
-            retry_count: 0,
-            attempt_blockhash: None,
-            created_at: now,
-        };
+        self.config.simulate_latency().await;
+        self.check_outbox_write("create_item")?;
+        let item = Self::new_item(
+            self.new_id("item"),
+            data,
+            BlockchainStatus::PendingSubmission,
+        );
+        let outbox_entry = Self::new_outbox_entry(
+            &item.id,
+            build_solana_outbox_payload_from_request(&item.id, data),
+        );
         let mut storage = self.storage.lock().unwrap();
-        storage.insert(id, item.clone());
+        storage.insert(item.id.clone(), item.clone());
         let mut outbox = self.outbox.lock().unwrap();
         outbox.insert(outbox_entry.id.clone(), outbox_entry);
         Ok(item)
     }
 
+    #[instrument(skip(self, data), fields(item_name = %data.name))]
+    async fn create_item_without_outbox(
+        &self,
+        data: &CreateItemRequest,
+    ) -> Result<Item, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("create_item_without_outbox")?;
+        let item = Self::new_item(self.new_id("item"), data, BlockchainStatus::Pending);
+        self.storage
+            .lock()
+            .unwrap()
+            .insert(item.id.clone(), item.clone());
+        Ok(item)
+    }
+
+    #[instrument(skip(self))]
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork + '_>, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("begin")?;
+        Ok(Box::new(MockUnitOfWork {
+            provider: self,
+            items: HashMap::new(),
+            outbox: Vec::new(),
+        }))
+    }
+
+    #[instrument(skip(self))]
     async fn list_items(
         &self,
         limit: i64,
         cursor: Option<&str>,
+        filter: &ItemListFilter,
     ) -> Result<PaginatedResponse<Item>, ItemError> {
-        self.check_should_fail()?;
+        self.config.simulate_latency().await;
+        self.check_should_fail("list_items")?;
         let storage = self.storage.lock().unwrap();
-        let mut items: Vec<Item> = storage.values().cloned().collect();
-        items.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        let mut items: Vec<Item> = storage
+            .values()
+            .filter(|i| TenantScope::permits(&i.tenant_id))
+            .cloned()
+            .collect();
+        items.sort_by(|a, b| filter.compare(a, b));
 
-        // Apply cursor
-        let items = if let Some(cursor_id) = cursor {
+        // Apply cursor before filtering (the cursor item itself may be filtered out)
+        let items: Vec<Item> = if let Some(cursor_id) = cursor {
             let pos = items.iter().position(|i| i.id == cursor_id);
             match pos {
                 Some(p) => items.into_iter().skip(p + 1).collect(),
                 None => {
-                    return Err(ItemError::InvalidState("Invalid cursor".to_string()));
+                    return Err(ItemError::InvalidCursor(
+                        "Cursor item no longer exists".to_string(),
+                    ));
                 }
             }
         } else {
             items
         };
+        let items: Vec<Item> = items.into_iter().filter(|i| filter.matches(i)).collect();
 
         let limit = limit.clamp(1, 100) as usize;
         let has_more = items.len() > limit;
@@ -198,6 +587,291 @@ impl ItemRepository for MockProvider {
         Ok(PaginatedResponse::new(items, next_cursor, has_more))
     }
 
+    #[instrument(skip(self))]
+    async fn list_items_at_offset(
+        &self,
+        offset: i64,
+        limit: i64,
+        filter: &ItemListFilter,
+    ) -> Result<Vec<Item>, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("list_items_at_offset")?;
+        let storage = self.storage.lock().unwrap();
+        let mut items: Vec<Item> = storage
+            .values()
+            .filter(|i| TenantScope::permits(&i.tenant_id) && filter.matches(i))
+            .cloned()
+            .collect();
+        items.sort_by(|a, b| filter.compare(a, b));
+        Ok(items
+            .into_iter()
+            .skip(usize::try_from(offset).unwrap_or(0))
+            .take(usize::try_from(limit).unwrap_or(0))
+            .collect())
+    }
+
+    /// Always an exact count
+    #[instrument(skip(self))]
+    async fn count_items(
+        &self,
+        filter: &ItemListFilter,
+        _estimate_above: Option<u64>,
+    ) -> Result<ItemCount, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("count_items")?;
+        let storage = self.storage.lock().unwrap();
+        let total = storage
+            .values()
+            .filter(|i| TenantScope::permits(&i.tenant_id) && filter.matches(i))
+            .count();
+        Ok(ItemCount::exact(total as u64))
+    }
+
+    #[instrument(skip(self))]
+    async fn list_tags(&self) -> Result<Vec<TagCount>, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("list_tags")?;
+        let storage = self.storage.lock().unwrap();
+        let mut counts: BTreeMap<&str, u64> = BTreeMap::new();
+        for item in storage
+            .values()
+            .filter(|i| TenantScope::permits(&i.tenant_id) && !i.is_deleted())
+        {
+            let mut tags: Vec<&str> = item
+                .metadata
+                .iter()
+                .flat_map(|m| m.tags.iter().map(String::as_str))
+                .collect();
+            tags.sort_unstable();
+            tags.dedup();
+            for tag in tags {
+                *counts.entry(tag).or_default() += 1;
+            }
+        }
+        let mut tags: Vec<TagCount> = counts
+            .into_iter()
+            .map(|(tag, items)| TagCount {
+                tag: tag.to_string(),
+                items,
+            })
+            .collect();
+        // Stable sort keeps the map's tag order among equal counts
+        tags.sort_by_key(|t| std::cmp::Reverse(t.items));
+        Ok(tags)
+    }
+
+    /// Snapshot of the live items, so later writes do not show up in a running export
+    #[instrument(skip(self))]
+    fn stream_items(&self) -> BoxStream<'static, Result<Item, ItemError>> {
+        if let Err(e) = self.check_should_fail("stream_items") {
+            return Box::pin(stream::once(async { Err(e) }));
+        }
+        let mut items: Vec<Item> = self
+            .storage
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|i| i.deleted_at.is_none() && TenantScope::permits(&i.tenant_id))
+            .cloned()
+            .collect();
+        items.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        Box::pin(stream::iter(items.into_iter().map(Ok)))
+    }
+
+    fn stream_item_changes(
+        &self,
+        after: Option<ItemPosition>,
+    ) -> BoxStream<'static, Result<Item, ItemError>> {
+        if let Err(e) = self.check_should_fail("stream_item_changes") {
+            return Box::pin(stream::once(async { Err(e) }));
+        }
+        let after = after.map(|p| (p.updated_at, p.item_id));
+        let mut items: Vec<Item> = self
+            .storage
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|i| i.deleted_at.is_none() && TenantScope::permits(&i.tenant_id))
+            .filter(|i| {
+                after
+                    .as_ref()
+                    .is_none_or(|(at, id)| (i.updated_at, &i.id) > (*at, id))
+            })
+            .cloned()
+            .collect();
+        items.sort_by(|a, b| (a.updated_at, &a.id).cmp(&(b.updated_at, &b.id)));
+        Box::pin(stream::iter(items.into_iter().map(Ok)))
+    }
+
+    #[instrument(skip(self))]
+    async fn export_bookmark(&self, name: &str) -> Result<ExportBookmark, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("export_bookmark")?;
+        Ok(self
+            .export_bookmarks
+            .lock()
+            .unwrap()
+            .entry(name.to_string())
+            .or_insert_with(|| ExportBookmark {
+                name: name.to_string(),
+                position: None,
+                created_at: Utc::now(),
+                acknowledged_at: None,
+            })
+            .clone())
+    }
+
+    #[instrument(skip(self))]
+    async fn acknowledge_export_bookmark(
+        &self,
+        name: &str,
+        position: &ItemPosition,
+    ) -> Result<ExportBookmark, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("acknowledge_export_bookmark")?;
+        let mut bookmarks = self.export_bookmarks.lock().unwrap();
+        let bookmark = bookmarks
+            .get_mut(name)
+            .ok_or_else(|| ItemError::NotFound(format!("export bookmark {}", name)))?;
+        let behind = bookmark.position.as_ref().is_none_or(|current| {
+            (current.updated_at, &current.item_id) < (position.updated_at, &position.item_id)
+        });
+        if behind {
+            bookmark.position = Some(position.clone());
+            bookmark.acknowledged_at = Some(Utc::now());
+        }
+        Ok(bookmark.clone())
+    }
+
+    #[instrument(skip(self))]
+    async fn find_item_by_hash(&self, hash: &str) -> Result<Option<Item>, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("find_item_by_hash")?;
+        let tenant = TenantScope::for_new_item();
+        let storage = self.storage.lock().unwrap();
+        Ok(storage
+            .values()
+            .filter(|item| item.hash == hash && item.tenant_id == tenant && !item.is_deleted())
+            .min_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)))
+            .cloned())
+    }
+
+    /// Case-insensitive substring search: every whitespace-separated term must appear in the
+    /// name, description or content. Name hits outrank description hits, which outrank content.
+    #[instrument(skip(self))]
+    async fn search_items(&self, query: &str, limit: i64) -> Result<Vec<ItemSearchHit>, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("search_items")?;
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        let storage = self.storage.lock().unwrap();
+        let mut hits: Vec<ItemSearchHit> = storage
+            .values()
+            .filter(|item| !item.is_deleted() && TenantScope::permits(&item.tenant_id))
+            .filter_map(|item| {
+                let name = item.name.to_lowercase();
+                let description = item.description.as_deref().unwrap_or("").to_lowercase();
+                let content = item.content.to_lowercase();
+                let mut rank = 0.0;
+                for term in &terms {
+                    let weight = if name.contains(term.as_str()) {
+                        1.0
+                    } else if description.contains(term.as_str()) {
+                        0.4
+                    } else if content.contains(term.as_str()) {
+                        0.2
+                    } else {
+                        return None;
+                    };
+                    rank += weight;
+                }
+                Some(ItemSearchHit {
+                    item: item.clone(),
+                    rank,
+                    snippet: item.content.chars().take(160).collect(),
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.rank
+                .total_cmp(&a.rank)
+                .then_with(|| a.item.id.cmp(&b.item.id))
+        });
+        hits.truncate(limit.clamp(1, 100) as usize);
+        Ok(hits)
+    }
+
+    #[instrument(skip(self, data), fields(item_name = %data.name))]
+    async fn update_item(
+        &self,
+        id: &str,
+        data: &CreateItemRequest,
+        expected_version: i64,
+    ) -> Result<Item, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("update_item")?;
+        let mut storage = self.storage.lock().unwrap();
+        let item = Self::visible_item(&mut storage, id)
+            .filter(|item| !item.is_deleted())
+            .ok_or_else(|| ItemError::NotFound(id.to_string()))?;
+        if item.version != expected_version {
+            return Err(ItemError::Conflict {
+                id: id.to_string(),
+                expected: expected_version,
+                current: item.version,
+            });
+        }
+        let updated = Self::new_item(item.id.clone(), data, item.blockchain_status);
+        item.hash = updated.hash;
+        item.name = updated.name;
+        item.description = updated.description;
+        item.content = updated.content;
+        item.content_key = updated.content_key;
+        item.content_type = updated.content_type;
+        item.content_length = updated.content_length;
+        item.metadata = updated.metadata;
+        item.version += 1;
+        item.updated_at = Utc::now();
+        Ok(item.clone())
+    }
+
+    #[instrument(skip(self))]
+    async fn soft_delete_item(&self, id: &str) -> Result<Option<Item>, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("soft_delete_item")?;
+        let mut storage = self.storage.lock().unwrap();
+        match Self::visible_item(&mut storage, id) {
+            Some(item) if !item.is_deleted() => {
+                let now = Utc::now();
+                item.deleted_at = Some(now);
+                item.updated_at = now;
+                Ok(Some(item.clone()))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn purge_deleted_items(&self, deleted_before: DateTime<Utc>) -> Result<u64, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("purge_deleted_items")?;
+        let mut storage = self.storage.lock().unwrap();
+        let before = storage.len();
+        storage.retain(|_, item| item.deleted_at.is_none_or(|at| at >= deleted_before));
+        // Mirror ON DELETE CASCADE on the outbox
+        self.outbox
+            .lock()
+            .unwrap()
+            .retain(|_, entry| storage.contains_key(&entry.aggregate_id));
+        Ok((before - storage.len()) as u64)
+    }
+}
+
+#[async_trait]
+impl SubmissionRepository for MockProvider {
+    #[instrument(skip(self))]
     async fn update_blockchain_status(
         &self,
         id: &str,
@@ -206,9 +880,11 @@ impl ItemRepository for MockProvider {
         error: Option<&str>,
         next_retry_at: Option<DateTime<Utc>>,
     ) -> Result<(), ItemError> {
-        self.check_should_fail()?;
+        self.config.simulate_latency().await;
+        self.check_should_fail("update_blockchain_status")?;
         let mut storage = self.storage.lock().unwrap();
-        if let Some(item) = storage.get_mut(id) {
+        if let Some(item) = Self::visible_item(&mut storage, id) {
+            let previous = item.blockchain_status;
             item.blockchain_status = status;
             if let Some(sig) = signature {
                 item.blockchain_signature = Some(sig.to_string());
@@ -216,64 +892,71 @@ impl ItemRepository for MockProvider {
             item.blockchain_last_error = error.map(|e| e.to_string());
             item.blockchain_next_retry_at = next_retry_at;
             item.updated_at = Utc::now();
+            let signature = item.blockchain_signature.clone();
+            self.record_transition(id, previous, status, signature.as_deref(), error);
         }
         Ok(())
     }
 
+    #[instrument(skip(self, payload))]
     async fn enqueue_solana_outbox_for_item(
         &self,
         item_id: &str,
         payload: &SolanaOutboxPayload,
     ) -> Result<Item, ItemError> {
-        self.check_should_fail()?;
-        let now = Utc::now();
+        self.config.simulate_latency().await;
+        self.check_outbox_write("enqueue_solana_outbox_for_item")?;
         let mut storage = self.storage.lock().unwrap();
-        let item = storage
-            .get_mut(item_id)
+        let item = Self::visible_item(&mut storage, item_id)
             .ok_or_else(|| ItemError::NotFound(item_id.to_string()))?;
 
-        let outbox_entry = SolanaOutboxEntry {
-            id: uuid::Uuid::new_v4().to_string(),
-            aggregate_id: item_id.to_string(),
-            payload: payload.clone(),
-            status: OutboxStatus::Pending,
-            retry_count: 0,
-            attempt_blockhash: None,
-            created_at: now,
-        };
+        let outbox_entry = Self::new_outbox_entry(item_id, payload.clone());
         let mut outbox = self.outbox.lock().unwrap();
         outbox.insert(outbox_entry.id.clone(), outbox_entry);
-
-        item.blockchain_status = BlockchainStatus::PendingSubmission;
-        item.blockchain_last_error = None;
-        item.blockchain_next_retry_at = None;
-        item.blockchain_retry_count = 0;
-        item.updated_at = now;
+        Self::mark_pending_submission(item);
 
         Ok(item.clone())
     }
 
-    async fn get_pending_blockchain_items(&self, limit: i64) -> Result<Vec<Item>, ItemError> {
-        self.check_should_fail()?;
-        let storage = self.storage.lock().unwrap();
-        let now = Utc::now();
+    #[instrument(skip(self))]
+    async fn get_pending_blockchain_items(
+        &self,
+        limit: i64,
+        claim_ttl: Duration,
+    ) -> Result<Vec<Item>, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("get_pending_blockchain_items")?;
+        let mut storage = self.storage.lock().unwrap();
+        let now = self.now();
         let mut items: Vec<Item> = storage
             .values()
             .filter(|i| {
                 i.blockchain_status == BlockchainStatus::PendingSubmission
                     && i.blockchain_retry_count < 10
+                    && TenantScope::permits(&i.tenant_id)
                     && i.blockchain_next_retry_at.map(|t| t <= now).unwrap_or(true)
             })
             .cloned()
             .collect();
-        items.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-        Ok(items.into_iter().take(limit as usize).collect())
+        items.sort_by_key(|i| i.created_at);
+        items.truncate(limit as usize);
+
+        let claimed_until = now + chrono::Duration::from_std(claim_ttl).unwrap_or_default();
+        for item in &mut items {
+            item.blockchain_next_retry_at = Some(claimed_until);
+            if let Some(stored) = storage.get_mut(&item.id) {
+                stored.blockchain_next_retry_at = Some(claimed_until);
+            }
+        }
+        Ok(items)
     }
 
+    #[instrument(skip(self))]
     async fn increment_retry_count(&self, id: &str) -> Result<i32, ItemError> {
-        self.check_should_fail()?;
+        self.config.simulate_latency().await;
+        self.check_should_fail("increment_retry_count")?;
         let mut storage = self.storage.lock().unwrap();
-        if let Some(item) = storage.get_mut(id) {
+        if let Some(item) = Self::visible_item(&mut storage, id) {
             item.blockchain_retry_count += 1;
             item.updated_at = Utc::now();
             Ok(item.blockchain_retry_count)
@@ -285,20 +968,26 @@ impl ItemRepository for MockProvider {
 
 #[async_trait]
 impl OutboxRepository for MockProvider {
+    #[instrument(skip(self))]
     async fn health_check(&self) -> Result<(), HealthCheckError> {
+        self.config.simulate_latency().await;
         if !self.is_healthy.load(Ordering::Relaxed) {
             return Err(HealthCheckError::DatabaseUnavailable);
         }
-        self.check_should_fail()
+        self.check_should_fail("health_check")
             .map_err(|_| HealthCheckError::DatabaseUnavailable)
     }
 
+    #[instrument(skip(self))]
     async fn claim_pending_solana_outbox(
         &self,
         limit: i64,
+        _claim_ttl: Duration,
     ) -> Result<Vec<SolanaOutboxEntry>, ItemError> {
-        self.check_should_fail()?;
-        let now = Utc::now();
+        // Claims never go stale here: nothing crashes mid-batch in a mock
+        self.config.simulate_latency().await;
+        self.check_should_fail("claim_pending_solana_outbox")?;
+        let now = self.now();
         let storage = self.storage.lock().unwrap();
         let mut outbox = self.outbox.lock().unwrap();
         let mut entries: Vec<SolanaOutboxEntry> = outbox
@@ -312,7 +1001,7 @@ impl OutboxRepository for MockProvider {
             })
             .cloned()
             .collect();
-        entries.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        entries.sort_by_key(|e| e.created_at);
 
         let mut selected: Vec<SolanaOutboxEntry> =
             entries.into_iter().take(limit as usize).collect();
@@ -327,20 +1016,30 @@ impl OutboxRepository for MockProvider {
         Ok(selected)
     }
 
+    #[instrument(skip(self))]
     async fn complete_solana_outbox(
         &self,
         outbox_id: &str,
         item_id: &str,
         signature: &str,
     ) -> Result<(), ItemError> {
-        self.check_should_fail()?;
+        self.config.simulate_latency().await;
+        self.check_should_fail("complete_solana_outbox")?;
         let mut storage = self.storage.lock().unwrap();
         if let Some(item) = storage.get_mut(item_id) {
+            let previous = item.blockchain_status;
             item.blockchain_status = BlockchainStatus::Submitted;
             item.blockchain_signature = Some(signature.to_string());
             item.blockchain_last_error = None;
             item.blockchain_next_retry_at = None;
             item.updated_at = Utc::now();
+            self.record_transition(
+                item_id,
+                previous,
+                BlockchainStatus::Submitted,
+                Some(signature),
+                None,
+            );
         }
         drop(storage);
 
@@ -351,6 +1050,7 @@ impl OutboxRepository for MockProvider {
         Ok(())
     }
 
+    #[instrument(skip(self))]
     async fn fail_solana_outbox(
         &self,
         outbox_id: &str,
@@ -362,14 +1062,17 @@ impl OutboxRepository for MockProvider {
         next_retry_at: Option<DateTime<Utc>>,
         attempt_blockhash: Option<Option<&str>>,
     ) -> Result<(), ItemError> {
-        self.check_should_fail()?;
+        self.config.simulate_latency().await;
+        self.check_should_fail("fail_solana_outbox")?;
         let mut storage = self.storage.lock().unwrap();
         if let Some(item) = storage.get_mut(item_id) {
+            let previous = item.blockchain_status;
             item.blockchain_status = item_status;
             item.blockchain_last_error = Some(error.to_string());
             item.blockchain_next_retry_at = next_retry_at;
             item.blockchain_retry_count = retry_count;
             item.updated_at = Utc::now();
+            self.record_transition(item_id, previous, item_status, None, Some(error));
         }
         drop(storage);
 
@@ -384,30 +1087,804 @@ impl OutboxRepository for MockProvider {
         Ok(())
     }
 
+    #[instrument(skip(self))]
     async fn save_attempt_blockhash(
         &self,
         outbox_id: &str,
         blockhash: Option<&str>,
     ) -> Result<(), ItemError> {
-        self.check_should_fail()?;
+        self.config.simulate_latency().await;
+        self.check_should_fail("save_attempt_blockhash")?;
         let mut outbox = self.outbox.lock().unwrap();
         if let Some(entry) = outbox.get_mut(outbox_id) {
             entry.attempt_blockhash = blockhash.map(std::string::ToString::to_string);
         }
         Ok(())
     }
-}
-
-/// Mock blockchain client for testing
-pub struct MockBlockchainClient {
-    transactions: Arc<Mutex<Vec<String>>>,
-    config: MockConfig,
-    is_healthy: AtomicBool,
-}
 
-impl MockBlockchainClient {
-    #[must_use]
-    pub fn new() -> Self {
+    #[instrument(skip(self))]
+    async fn dead_letter_solana_outbox(
+        &self,
+        outbox_id: &str,
+        item_id: &str,
+        retry_count: i32,
+        error: &str,
+        attempt_blockhash: Option<Option<&str>>,
+    ) -> Result<FailedSubmission, ItemError> {
+        if self.injected("dead_letter_solana_outbox") {
+            return Err(ItemError::RepositoryFailure);
+        }
+        self.fail_solana_outbox(
+            outbox_id,
+            item_id,
+            retry_count,
+            OutboxStatus::Failed,
+            BlockchainStatus::Failed,
+            error,
+            None,
+            attempt_blockhash,
+        )
+        .await?;
+        let entry = self
+            .outbox
+            .lock()
+            .unwrap()
+            .get(outbox_id)
+            .cloned()
+            .ok_or_else(|| ItemError::NotFound(outbox_id.to_string()))?;
+        let submission = FailedSubmission {
+            id: self.new_id("dlq"),
+            item_id: item_id.to_string(),
+            outbox_id: outbox_id.to_string(),
+            hash: entry.payload.hash.clone(),
+            retry_count,
+            last_error: error.to_string(),
+            failed_at: Utc::now(),
+            requeued_at: None,
+        };
+        self.failed_submissions
+            .lock()
+            .unwrap()
+            .push((submission.clone(), entry));
+        Ok(submission)
+    }
+
+    #[instrument(skip(self))]
+    async fn list_failed_submissions(
+        &self,
+        limit: i64,
+        include_requeued: bool,
+    ) -> Result<Vec<FailedSubmission>, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("list_failed_submissions")?;
+        Ok(self
+            .failed_submissions
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .map(|(submission, _)| submission)
+            .filter(|s| include_requeued || s.requeued_at.is_none())
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn requeue_failed_submission(&self, id: &str) -> Result<Item, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("requeue_failed_submission")?;
+        let mut failed = self.failed_submissions.lock().unwrap();
+        let (submission, dead_entry) = failed
+            .iter_mut()
+            .find(|(s, _)| s.id == id)
+            .ok_or_else(|| ItemError::NotFound(id.to_string()))?;
+        if submission.requeued_at.is_some() {
+            return Err(ItemError::InvalidState(
+                "Submission was already requeued".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        let mut storage = self.storage.lock().unwrap();
+        let item = storage
+            .get_mut(&submission.item_id)
+            .ok_or_else(|| ItemError::NotFound(submission.item_id.clone()))?;
+        if item.blockchain_status != BlockchainStatus::Failed {
+            return Err(ItemError::InvalidState(
+                "Item is no longer failed".to_string(),
+            ));
+        }
+
+        let outbox_entry = SolanaOutboxEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            aggregate_id: item.id.clone(),
+            payload: dead_entry.payload.clone(),
+            status: OutboxStatus::Pending,
+            retry_count: 0,
+            attempt_blockhash: dead_entry.attempt_blockhash.clone(),
+            created_at: now,
+        };
+        self.outbox
+            .lock()
+            .unwrap()
+            .insert(outbox_entry.id.clone(), outbox_entry);
+
+        item.blockchain_status = BlockchainStatus::PendingSubmission;
+        item.blockchain_last_error = None;
+        item.blockchain_next_retry_at = None;
+        item.blockchain_retry_count = 0;
+        item.updated_at = now;
+        submission.requeued_at = Some(now);
+
+        Ok(item.clone())
+    }
+
+    #[instrument(skip(self))]
+    async fn queue_depth(&self) -> Result<QueueDepth, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("queue_depth")?;
+        // Released before `storage` is locked, the order `requeue_failed_submission` uses
+        let dead_lettered = self
+            .failed_submissions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(s, _)| s.requeued_at.is_none())
+            .count() as i64;
+        let now = self.now();
+        let storage = self.storage.lock().unwrap();
+        let outbox = self.outbox.lock().unwrap();
+        let pending: Vec<&SolanaOutboxEntry> = outbox
+            .values()
+            .filter(|e| e.status == OutboxStatus::Pending)
+            .collect();
+        let due = pending
+            .iter()
+            .filter(|e| {
+                storage
+                    .get(&e.aggregate_id)
+                    .is_some_and(|i| i.blockchain_next_retry_at.is_none_or(|t| t <= now))
+            })
+            .count();
+        Ok(QueueDepth {
+            pending: pending.len() as i64,
+            due: due as i64,
+            processing: outbox
+                .values()
+                .filter(|e| e.status == OutboxStatus::Processing)
+                .count() as i64,
+            dead_lettered,
+            oldest_pending_at: pending.iter().map(|e| e.created_at).min(),
+        })
+    }
+
+    #[instrument(skip(self, attempt))]
+    async fn record_submission_attempt(
+        &self,
+        item_id: &str,
+        attempt: &SubmissionAttempt,
+    ) -> Result<(), ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("record_submission_attempt")?;
+        if Self::visible_item(&mut self.storage.lock().unwrap(), item_id).is_some() {
+            self.submission_attempts
+                .lock()
+                .unwrap()
+                .entry(item_id.to_string())
+                .or_default()
+                .push(attempt.clone());
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_submission_attempts(
+        &self,
+        item_id: &str,
+    ) -> Result<Vec<SubmissionAttempt>, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("list_submission_attempts")?;
+        if Self::visible_item(&mut self.storage.lock().unwrap(), item_id).is_none() {
+            return Ok(Vec::new());
+        }
+        Ok(self
+            .submission_attempts
+            .lock()
+            .unwrap()
+            .get(item_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl RequestJournal for MockProvider {
+    #[instrument(skip(self))]
+    async fn begin_request(
+        &self,
+        key: &str,
+        request_hash: &str,
+        reclaim_before: DateTime<Utc>,
+    ) -> Result<Option<RequestJournalEntry>, RequestJournalError> {
+        self.config.simulate_latency().await;
+        if self.injected_failure("begin_request") {
+            return Err(RequestJournalError::RepositoryFailure);
+        }
+        let now = Utc::now();
+        let mut journal = self.journal.lock().unwrap();
+        match journal.get_mut(key) {
+            Some(entry)
+                if entry.status == JournalStatus::Processing
+                    && entry.request_hash == request_hash
+                    && entry.updated_at < reclaim_before =>
+            {
+                entry.updated_at = now;
+                Ok(None)
+            }
+            Some(entry) => Ok(Some(entry.clone())),
+            None => {
+                journal.insert(
+                    key.to_string(),
+                    RequestJournalEntry {
+                        key: key.to_string(),
+                        request_hash: request_hash.to_string(),
+                        status: JournalStatus::Processing,
+                        response_status: None,
+                        response_body: None,
+                        created_at: now,
+                        updated_at: now,
+                    },
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    async fn complete_request(
+        &self,
+        key: &str,
+        response_status: u16,
+        response_body: &str,
+    ) -> Result<(), RequestJournalError> {
+        self.config.simulate_latency().await;
+        if self.injected("complete_request") {
+            return Err(RequestJournalError::RepositoryFailure);
+        }
+        if let Some(entry) = self.journal.lock().unwrap().get_mut(key) {
+            entry.status = JournalStatus::Completed;
+            entry.response_status = Some(response_status);
+            entry.response_body = Some(response_body.to_string());
+            entry.updated_at = Utc::now();
+        }
+        Ok(())
+    }
+
+    async fn abandon_request(&self, key: &str) -> Result<(), RequestJournalError> {
+        self.config.simulate_latency().await;
+        if self.injected("abandon_request") {
+            return Err(RequestJournalError::RepositoryFailure);
+        }
+        let mut journal = self.journal.lock().unwrap();
+        if journal
+            .get(key)
+            .is_some_and(|e| e.status == JournalStatus::Processing)
+        {
+            journal.remove(key);
+        }
+        Ok(())
+    }
+
+    async fn get_request(
+        &self,
+        key: &str,
+    ) -> Result<Option<RequestJournalEntry>, RequestJournalError> {
+        self.config.simulate_latency().await;
+        if self.injected("get_request") {
+            return Err(RequestJournalError::RepositoryFailure);
+        }
+        Ok(self.journal.lock().unwrap().get(key).cloned())
+    }
+}
+
+#[async_trait]
+impl AuditLogger for MockProvider {
+    async fn record_audit_entry(&self, entry: &AuditEntry) -> Result<(), NotificationError> {
+        self.config.simulate_latency().await;
+        if self.injected_failure("record_audit_entry") {
+            return Err(NotificationError::RepositoryFailure);
+        }
+        let mut entries = self.audit_entries.lock().unwrap();
+        let mut entry = entry.clone();
+        entry.id = entries.len() as i64 + 1;
+        entries.push(entry);
+        Ok(())
+    }
+
+    async fn list_audit_entries(
+        &self,
+        limit: i64,
+        before: Option<i64>,
+        filter: &AuditFilter,
+    ) -> Result<PaginatedResponse<AuditEntry>, NotificationError> {
+        self.config.simulate_latency().await;
+        if self.injected_failure("list_audit_entries") {
+            return Err(NotificationError::RepositoryFailure);
+        }
+        let limit = limit.clamp(1, 100);
+        let entries = self
+            .audit_entries
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|e| before.is_none_or(|b| e.id < b) && filter.matches(e))
+            .take(limit as usize + 1)
+            .cloned()
+            .collect();
+        Ok(PaginatedResponse::from_lookahead(entries, limit, |e| {
+            e.id.to_string()
+        }))
+    }
+}
+
+#[async_trait]
+impl WebhookDeliveryLog for MockProvider {
+    async fn record_webhook_delivery(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> Result<(), NotificationError> {
+        self.config.simulate_latency().await;
+        if self.injected_failure("record_webhook_delivery") {
+            return Err(NotificationError::RepositoryFailure);
+        }
+        let mut deliveries = self.webhook_deliveries.lock().unwrap();
+        let mut delivery = delivery.clone();
+        delivery.id = deliveries.len() as i64 + 1;
+        deliveries.push(delivery);
+        Ok(())
+    }
+
+    async fn list_webhook_deliveries(
+        &self,
+        limit: i64,
+        before: Option<i64>,
+        range: &TimeRange,
+    ) -> Result<PaginatedResponse<WebhookDelivery>, NotificationError> {
+        self.config.simulate_latency().await;
+        if self.injected_failure("list_webhook_deliveries") {
+            return Err(NotificationError::RepositoryFailure);
+        }
+        let limit = limit.clamp(1, 100);
+        let deliveries = self
+            .webhook_deliveries
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|d| before.is_none_or(|b| d.id < b) && range.contains(d.attempted_at))
+            .take(limit as usize + 1)
+            .cloned()
+            .collect();
+        Ok(PaginatedResponse::from_lookahead(deliveries, limit, |d| {
+            d.id.to_string()
+        }))
+    }
+
+    async fn item_deliveries(
+        &self,
+        item_id: &str,
+    ) -> Result<Vec<WebhookDelivery>, NotificationError> {
+        self.config.simulate_latency().await;
+        if self.injected_failure("item_deliveries") {
+            return Err(NotificationError::RepositoryFailure);
+        }
+        Ok(self
+            .webhook_deliveries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|d| d.item_id == item_id)
+            .cloned()
+            .collect())
+    }
+}
+
+#[async_trait]
+impl JobStore for MockProvider {
+    async fn create_job(&self, kind: &str) -> Result<Job, JobError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("create_job")
+            .map_err(|_| JobError::RepositoryFailure)?;
+        let now = Utc::now();
+        let job = Job {
+            id: self.new_id("job"),
+            kind: kind.to_string(),
+            status: JobStatus::Queued,
+            processed: 0,
+            failed: 0,
+            result: None,
+            error: None,
+            created_at: now,
+            updated_at: now,
+            finished_at: None,
+        };
+        self.jobs
+            .lock()
+            .unwrap()
+            .insert(job.id.clone(), job.clone());
+        Ok(job)
+    }
+
+    async fn update_job_progress(
+        &self,
+        id: &str,
+        processed: i64,
+        failed: i64,
+    ) -> Result<(), JobError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("update_job_progress")
+            .map_err(|_| JobError::RepositoryFailure)?;
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs
+            .get_mut(id)
+            .ok_or_else(|| JobError::NotFound(id.to_string()))?;
+        job.status = JobStatus::Running;
+        job.processed = processed;
+        job.failed = failed;
+        job.updated_at = Utc::now();
+        Ok(())
+    }
+
+    async fn finish_job(
+        &self,
+        id: &str,
+        status: JobStatus,
+        result: Option<&serde_json::Value>,
+        error: Option<&str>,
+    ) -> Result<Job, JobError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("finish_job")
+            .map_err(|_| JobError::RepositoryFailure)?;
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs
+            .get_mut(id)
+            .ok_or_else(|| JobError::NotFound(id.to_string()))?;
+        let now = Utc::now();
+        job.status = status;
+        job.result = result.cloned();
+        job.error = error.map(str::to_string);
+        job.updated_at = now;
+        job.finished_at = Some(now);
+        Ok(job.clone())
+    }
+
+    async fn get_job(&self, id: &str) -> Result<Option<Job>, JobError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("get_job")
+            .map_err(|_| JobError::RepositoryFailure)?;
+        Ok(self.jobs.lock().unwrap().get(id).cloned())
+    }
+}
+
+#[async_trait]
+impl SpendLedger for MockProvider {
+    async fn spent_on(&self, signer: &str, day: NaiveDate) -> Result<u64, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("spent_on")?;
+        let spend = self.spend.lock().unwrap();
+        Ok(spend.get(&(signer.to_string(), day)).copied().unwrap_or(0))
+    }
+
+    async fn record_spend(
+        &self,
+        signer: &str,
+        day: NaiveDate,
+        amount: u64,
+    ) -> Result<u64, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("record_spend")?;
+        let mut spend = self.spend.lock().unwrap();
+        let spent = spend.entry((signer.to_string(), day)).or_insert(0);
+        *spent += amount;
+        Ok(*spent)
+    }
+}
+
+#[async_trait]
+impl UsageLedger for MockProvider {
+    async fn record_request(
+        &self,
+        key_id: &str,
+        day: NaiveDate,
+    ) -> Result<QuotaUsage, ApiKeyError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("record_request")
+            .map_err(|_| ApiKeyError::RepositoryFailure)?;
+        let mut usage = self.usage.lock().unwrap();
+        *usage.entry((key_id.to_string(), day)).or_insert(0) += 1;
+        let month = (key_id.to_string(), month_start(day))..=(key_id.to_string(), day);
+        Ok(QuotaUsage {
+            daily: usage[&(key_id.to_string(), day)],
+            monthly: usage.range(month).map(|(_, requests)| requests).sum(),
+        })
+    }
+
+    async fn usage_on(&self, day: NaiveDate) -> Result<Vec<KeyUsage>, ApiKeyError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("usage_on")
+            .map_err(|_| ApiKeyError::RepositoryFailure)?;
+        let keys = self.api_keys.lock().unwrap();
+        let mut report: BTreeMap<&str, KeyUsage> = BTreeMap::new();
+        let usage = self.usage.lock().unwrap();
+        for ((key_id, requests_day), requests) in usage.iter() {
+            if *requests_day < month_start(day) || *requests_day > day {
+                continue;
+            }
+            let entry = report.entry(key_id).or_insert_with(|| {
+                let key = keys.get(key_id).map(|(_, key)| key);
+                KeyUsage {
+                    key_id: key_id.clone(),
+                    name: key.map(|key| key.name.clone()),
+                    daily_requests: 0,
+                    monthly_requests: 0,
+                    quota: key.map(|key| key.quota).unwrap_or_default(),
+                }
+            });
+            entry.monthly_requests += requests;
+            if *requests_day == day {
+                entry.daily_requests += requests;
+            }
+        }
+        Ok(report.into_values().collect())
+    }
+}
+
+#[async_trait]
+impl LeaderElection for MockProvider {
+    async fn try_acquire_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> Result<bool, ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("try_acquire_lease")?;
+        let now = self.now();
+        let mut leases = self.leader_leases.lock().unwrap();
+        if let Some((current, expires_at)) = leases.get(name)
+            && current != holder
+            && *expires_at > now
+        {
+            return Ok(false);
+        }
+        let expires_at = now + chrono::Duration::from_std(ttl).unwrap_or_default();
+        leases.insert(name.to_string(), (holder.to_string(), expires_at));
+        Ok(true)
+    }
+
+    async fn release_lease(&self, name: &str, holder: &str) -> Result<(), ItemError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("release_lease")?;
+        let mut leases = self.leader_leases.lock().unwrap();
+        if leases
+            .get(name)
+            .is_some_and(|(current, _)| current == holder)
+        {
+            leases.remove(name);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventLog for MockProvider {
+    async fn events_after(
+        &self,
+        position: i64,
+        limit: i64,
+    ) -> Result<Vec<ItemStatusEvent>, NotificationError> {
+        self.config.simulate_latency().await;
+        if self.injected_failure("events_after") {
+            return Err(NotificationError::RepositoryFailure);
+        }
+        Ok(self
+            .events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|e| e.position > position)
+            .take(usize::try_from(limit).unwrap_or(0))
+            .cloned()
+            .collect())
+    }
+
+    async fn subscription_cursor(&self, subscription: &str) -> Result<i64, NotificationError> {
+        self.config.simulate_latency().await;
+        if self.injected("subscription_cursor") {
+            return Err(NotificationError::RepositoryFailure);
+        }
+        Ok(self
+            .subscription_cursors
+            .lock()
+            .unwrap()
+            .get(subscription)
+            .copied()
+            .unwrap_or(0))
+    }
+
+    async fn save_subscription_cursor(
+        &self,
+        subscription: &str,
+        position: i64,
+    ) -> Result<(), NotificationError> {
+        self.config.simulate_latency().await;
+        if self.injected("save_subscription_cursor") {
+            return Err(NotificationError::RepositoryFailure);
+        }
+        let mut cursors = self.subscription_cursors.lock().unwrap();
+        let cursor = cursors.entry(subscription.to_string()).or_insert(0);
+        *cursor = (*cursor).max(position);
+        Ok(())
+    }
+
+    async fn list_events(
+        &self,
+        limit: i64,
+        before: Option<i64>,
+        range: &TimeRange,
+    ) -> Result<PaginatedResponse<ItemStatusEvent>, NotificationError> {
+        self.config.simulate_latency().await;
+        if self.injected_failure("list_events") {
+            return Err(NotificationError::RepositoryFailure);
+        }
+        let limit = limit.clamp(1, 100);
+        let events = self
+            .events
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|e| before.is_none_or(|b| e.position < b) && range.contains(e.occurred_at))
+            .take(limit as usize + 1)
+            .cloned()
+            .collect();
+        Ok(PaginatedResponse::from_lookahead(events, limit, |e| {
+            e.position.to_string()
+        }))
+    }
+
+    async fn item_events(&self, item_id: &str) -> Result<Vec<ItemStatusEvent>, NotificationError> {
+        self.config.simulate_latency().await;
+        if self.injected_failure("item_events") {
+            return Err(NotificationError::RepositoryFailure);
+        }
+        Ok(self
+            .events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|e| e.item_id == item_id)
+            .cloned()
+            .collect())
+    }
+}
+
+#[async_trait]
+impl ApiKeyStore for MockProvider {
+    async fn create_api_key(
+        &self,
+        name: &str,
+        key_hash: &str,
+        scopes: &[ApiKeyScope],
+        tenant_id: &str,
+        quota: ApiKeyQuota,
+    ) -> Result<ApiKey, ApiKeyError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("create_api_key")
+            .map_err(|_| ApiKeyError::RepositoryFailure)?;
+        let key = ApiKey {
+            id: self.new_id("key"),
+            name: name.to_string(),
+            scopes: scopes.to_vec(),
+            created_at: Utc::now(),
+            revoked_at: None,
+            tenant_id: tenant_id.to_string(),
+            quota,
+        };
+        self.api_keys
+            .lock()
+            .unwrap()
+            .insert(key.id.clone(), (key_hash.to_string(), key.clone()));
+        Ok(key)
+    }
+
+    async fn find_api_key_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>, ApiKeyError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("find_api_key_by_hash")
+            .map_err(|_| ApiKeyError::RepositoryFailure)?;
+        let keys = self.api_keys.lock().unwrap();
+        Ok(keys
+            .values()
+            .find(|(hash, _)| hash == key_hash)
+            .map(|(_, key)| key.clone()))
+    }
+
+    async fn list_api_keys(&self) -> Result<Vec<ApiKey>, ApiKeyError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("list_api_keys")
+            .map_err(|_| ApiKeyError::RepositoryFailure)?;
+        let mut keys: Vec<ApiKey> = self
+            .api_keys
+            .lock()
+            .unwrap()
+            .values()
+            .map(|(_, key)| key.clone())
+            .collect();
+        keys.sort_by_key(|k| std::cmp::Reverse(k.created_at));
+        Ok(keys)
+    }
+
+    async fn revoke_api_key(&self, id: &str) -> Result<ApiKey, ApiKeyError> {
+        self.config.simulate_latency().await;
+        self.check_should_fail("revoke_api_key")
+            .map_err(|_| ApiKeyError::RepositoryFailure)?;
+        let mut keys = self.api_keys.lock().unwrap();
+        let (_, key) = keys
+            .get_mut(id)
+            .ok_or_else(|| ApiKeyError::NotFound(id.to_string()))?;
+        if key.revoked_at.is_none() {
+            key.revoked_at = Some(Utc::now());
+        }
+        Ok(key.clone())
+    }
+}
+
+/// Outcome of one scripted `submit_transaction` call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockStep {
+    /// Land the transaction
+    Succeed,
+    /// Fail with `SubmissionFailed(message)`
+    Fail(String),
+    /// Fail with `Timeout` carrying this blockhash (the transaction may have landed)
+    Timeout(String),
+}
+
+/// `BlockchainClient` method, for per-method failure configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MockMethod {
+    HealthCheck,
+    SubmitTransaction,
+    GetTransactionStatus,
+    GetTransaction,
+    GetBlockHeight,
+    GetBalance,
+    GetLatestBlockhash,
+    WaitForConfirmation,
+}
+
+/// RPC endpoint [`MockBlockchainClient`] reports to the [`SubmissionTrace`]
+const MOCK_RPC_URL: &str = "https://mock-rpc.local";
+
+/// Mock blockchain client for testing
+pub struct MockBlockchainClient {
+    transactions: Arc<Mutex<Vec<String>>>,
+    config: MockConfig,
+    is_healthy: AtomicBool,
+    /// Reported by `get_balance` (default: 1 SOL in lamports)
+    balance: AtomicU64,
+    /// Outcomes of the next `submit_transaction` calls; `config` applies once it runs out
+    script: Mutex<VecDeque<MockStep>>,
+    /// Methods failing with `SubmissionFailed(message)` regardless of script and config
+    method_failures: Mutex<HashMap<MockMethod, String>>,
+    /// `submit_transaction` calls, including failed ones
+    submit_calls: AtomicU64,
+}
+
+impl MockBlockchainClient {
+    #[must_use]
+    pub fn new() -> Self {
         Self::with_config(MockConfig::success())
     }
 
@@ -417,9 +1894,59 @@ impl MockBlockchainClient {
             transactions: Arc::new(Mutex::new(Vec::new())),
             config,
             is_healthy: AtomicBool::new(true),
+            balance: AtomicU64::new(1_000_000_000),
+            script: Mutex::new(VecDeque::new()),
+            method_failures: Mutex::new(HashMap::new()),
+            submit_calls: AtomicU64::new(0),
         }
     }
 
+    /// Play `steps` in order on successive `submit_transaction` calls, then succeed
+    ///
+    /// ```
+    /// use testable_rust_architecture_template::test_utils::{MockBlockchainClient, MockStep::*};
+    ///
+    /// let client = MockBlockchainClient::with_script(vec![
+    ///     Fail("timeout".into()),
+    ///     Fail("rate limit".into()),
+    ///     Succeed,
+    /// ]);
+    /// assert_eq!(client.script_remaining(), 3);
+    /// ```
+    #[must_use]
+    pub fn with_script(steps: Vec<MockStep>) -> Self {
+        let client = Self::new();
+        client.push_script(steps);
+        client
+    }
+
+    /// Append `steps` to the script
+    pub fn push_script(&self, steps: impl IntoIterator<Item = MockStep>) {
+        self.script.lock().unwrap().extend(steps);
+    }
+
+    /// Scripted steps not yet played
+    pub fn script_remaining(&self) -> usize {
+        self.script.lock().unwrap().len()
+    }
+
+    /// Make `method` fail with `message` until [`Self::clear_method_failure`]
+    pub fn fail_method(&self, method: MockMethod, message: impl Into<String>) {
+        self.method_failures
+            .lock()
+            .unwrap()
+            .insert(method, message.into());
+    }
+
+    pub fn clear_method_failure(&self, method: MockMethod) {
+        self.method_failures.lock().unwrap().remove(&method);
+    }
+
+    /// Number of `submit_transaction` calls so far, failed ones included
+    pub fn submit_calls(&self) -> u64 {
+        self.submit_calls.load(Ordering::Relaxed)
+    }
+
     #[must_use]
     pub fn failing(message: impl Into<String>) -> Self {
         Self::with_config(MockConfig::failure(message))
@@ -439,10 +1966,40 @@ impl MockBlockchainClient {
         self.is_healthy.store(healthy, Ordering::Relaxed);
     }
 
+    /// Balance returned by `get_balance`
+    pub fn set_balance(&self, balance: u64) {
+        self.balance.store(balance, Ordering::Relaxed);
+    }
+
     pub fn get_transactions(&self) -> Vec<String> {
         self.transactions.lock().unwrap().clone()
     }
 
+    /// Per-method failure of `method`, then (for submissions) the next scripted step,
+    /// then the configured behavior
+    fn check(&self, method: MockMethod) -> Result<(), BlockchainError> {
+        if let Some(message) = self.method_failures.lock().unwrap().get(&method) {
+            return Err(BlockchainError::SubmissionFailed(message.clone()));
+        }
+        if method == MockMethod::SubmitTransaction {
+            self.submit_calls.fetch_add(1, Ordering::Relaxed);
+            match self.script.lock().unwrap().pop_front() {
+                Some(MockStep::Succeed) => return Ok(()),
+                Some(MockStep::Fail(message)) => {
+                    return Err(BlockchainError::SubmissionFailed(message));
+                }
+                Some(MockStep::Timeout(blockhash)) => {
+                    return Err(BlockchainError::Timeout {
+                        message: "Mock timeout".to_string(),
+                        blockhash,
+                    });
+                }
+                None => {}
+            }
+        }
+        self.check_should_fail()
+    }
+
     fn check_should_fail(&self) -> Result<(), BlockchainError> {
         if self.config.should_fail {
             if self.config.fail_with_timeout {
@@ -470,20 +2027,25 @@ impl Default for MockBlockchainClient {
 
 #[async_trait]
 impl BlockchainClient for MockBlockchainClient {
+    #[instrument(skip(self))]
     async fn health_check(&self) -> Result<(), HealthCheckError> {
+        self.config.simulate_latency().await;
         if !self.is_healthy.load(Ordering::Relaxed) {
             return Err(HealthCheckError::BlockchainUnavailable);
         }
-        self.check_should_fail()
+        self.check(MockMethod::HealthCheck)
             .map_err(|_| HealthCheckError::BlockchainUnavailable)
     }
 
+    #[instrument(skip(self))]
     async fn submit_transaction(
         &self,
         hash: &str,
         existing_blockhash: Option<&str>,
     ) -> Result<(String, String), BlockchainError> {
-        self.check_should_fail()?;
+        self.config.simulate_latency().await;
+        SubmissionTrace::record_answered(MOCK_RPC_URL);
+        self.check(MockMethod::SubmitTransaction)?;
         let signature = format!("sig_{}", hash);
         let blockhash_used = existing_blockhash
             .map(std::string::ToString::to_string)
@@ -493,29 +2055,347 @@ impl BlockchainClient for MockBlockchainClient {
         Ok((signature, blockhash_used))
     }
 
+    #[instrument(skip(self))]
     async fn get_transaction_status(&self, signature: &str) -> Result<bool, BlockchainError> {
-        self.check_should_fail()?;
+        self.config.simulate_latency().await;
+        self.check(MockMethod::GetTransactionStatus)?;
         let transactions = self.transactions.lock().unwrap();
         Ok(transactions.iter().any(|t| signature.contains(t)))
     }
 
+    /// Transactions land one slot apart, the first at slot 12345600; the memo is the hash
+    #[instrument(skip(self))]
+    async fn get_transaction(
+        &self,
+        signature: &str,
+    ) -> Result<Option<OnChainTransaction>, BlockchainError> {
+        self.config.simulate_latency().await;
+        self.check(MockMethod::GetTransaction)?;
+        let transactions = self.transactions.lock().unwrap();
+        Ok(transactions
+            .iter()
+            .position(|t| signature == format!("sig_{}", t))
+            .map(|index| {
+                let slot = 12_345_600 + index as u64;
+                OnChainTransaction {
+                    slot,
+                    memo: Some(transactions[index].clone()),
+                    confirmation_depth: 12_345_678u64.saturating_sub(slot),
+                }
+            }))
+    }
+
+    #[instrument(skip(self))]
     async fn get_block_height(&self) -> Result<u64, BlockchainError> {
-        self.check_should_fail()?;
+        self.config.simulate_latency().await;
+        self.check(MockMethod::GetBlockHeight)?;
         Ok(12345678)
     }
 
+    #[instrument(skip(self))]
+    async fn get_balance(&self) -> Result<u64, BlockchainError> {
+        self.config.simulate_latency().await;
+        self.check(MockMethod::GetBalance)?;
+        Ok(self.balance.load(Ordering::Relaxed))
+    }
+
+    #[instrument(skip(self))]
     async fn get_latest_blockhash(&self) -> Result<String, BlockchainError> {
-        self.check_should_fail()?;
+        self.config.simulate_latency().await;
+        self.check(MockMethod::GetLatestBlockhash)?;
         Ok("mock_blockhash_abc123".to_string())
     }
 
+    #[instrument(skip(self))]
     async fn wait_for_confirmation(
         &self,
         signature: &str,
         _timeout_secs: u64,
     ) -> Result<bool, BlockchainError> {
-        self.check_should_fail()?;
+        self.config.simulate_latency().await;
+        self.check(MockMethod::WaitForConfirmation)?;
         let transactions = self.transactions.lock().unwrap();
         Ok(transactions.iter().any(|t| signature.contains(t)))
     }
 }
+
+/// Mock notification client recording every batch it accepts
+#[derive(Default)]
+pub struct MockNotificationClient {
+    batches: Mutex<Vec<Vec<ItemStatusEvent>>>,
+    should_fail: AtomicBool,
+}
+
+impl MockNotificationClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject (true) or accept (false) subsequent batches
+    pub fn set_failing(&self, failing: bool) {
+        self.should_fail.store(failing, Ordering::Relaxed);
+    }
+
+    /// Accepted batches in delivery order
+    pub fn get_batches(&self) -> Vec<Vec<ItemStatusEvent>> {
+        self.batches.lock().unwrap().clone()
+    }
+
+    /// All accepted events in delivery order
+    pub fn get_events(&self) -> Vec<ItemStatusEvent> {
+        self.batches.lock().unwrap().concat()
+    }
+}
+
+#[async_trait]
+impl NotificationClient for MockNotificationClient {
+    #[instrument(skip(self, events), fields(events = events.len()))]
+    async fn notify(&self, events: &[ItemStatusEvent]) -> Result<(), NotificationError> {
+        if self.should_fail.load(Ordering::Relaxed) {
+            return Err(NotificationError::DeliveryFailed {
+                url: "mock".to_string(),
+                attempts: 1,
+                message: "Mock delivery failure".to_string(),
+            });
+        }
+        self.batches.lock().unwrap().push(events.to_vec());
+        Ok(())
+    }
+}
+
+/// KPI reported to a [`MockTelemetrySink`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TelemetryRecord {
+    ItemCreated(String),
+    ItemConfirmed(std::time::Duration),
+    SubmissionFailed(String),
+}
+
+/// Mock telemetry sink recording every KPI it is given
+#[derive(Default)]
+pub struct MockTelemetrySink {
+    records: Mutex<Vec<TelemetryRecord>>,
+}
+
+impl MockTelemetrySink {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reported KPIs in order
+    pub fn get_records(&self) -> Vec<TelemetryRecord> {
+        self.records.lock().unwrap().clone()
+    }
+}
+
+impl TelemetrySink for MockTelemetrySink {
+    fn item_created(&self, tenant_id: &str) {
+        self.records
+            .lock()
+            .unwrap()
+            .push(TelemetryRecord::ItemCreated(tenant_id.to_string()));
+    }
+
+    fn item_confirmed(&self, latency: std::time::Duration) {
+        self.records
+            .lock()
+            .unwrap()
+            .push(TelemetryRecord::ItemConfirmed(latency));
+    }
+
+    fn submission_failed(&self, reason: &str) {
+        self.records
+            .lock()
+            .unwrap()
+            .push(TelemetryRecord::SubmissionFailed(reason.to_string()));
+    }
+}
+
+/// Mock message bus capturing every published event
+#[derive(Default)]
+pub struct MockMessagePublisher {
+    events: Mutex<Vec<DomainEvent>>,
+    should_fail: AtomicBool,
+}
+
+impl MockMessagePublisher {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject (true) or accept (false) subsequent events
+    pub fn set_failing(&self, failing: bool) {
+        self.should_fail.store(failing, Ordering::Relaxed);
+    }
+
+    /// Accepted events in publish order
+    pub fn get_events(&self) -> Vec<DomainEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// Names of the accepted events (e.g. `item.created`) in publish order
+    pub fn event_names(&self) -> Vec<&'static str> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .map(DomainEvent::name)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl MessagePublisher for MockMessagePublisher {
+    async fn publish(&self, event: &DomainEvent) -> Result<(), MessagingError> {
+        if self.should_fail.load(Ordering::Relaxed) {
+            return Err(MessagingError::PublishFailed {
+                event: event.name().to_string(),
+                message: "Mock publish failure".to_string(),
+            });
+        }
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+}
+
+/// Mock message bus subscription fed by [`MockMessageSubscriber::deliver`]
+pub struct MockMessageSubscriber {
+    sender: tokio::sync::mpsc::UnboundedSender<InboundMessage>,
+    receiver: Mutex<Option<tokio::sync::mpsc::UnboundedReceiver<InboundMessage>>>,
+    subjects: Mutex<Vec<String>>,
+    dead_letters: Mutex<Vec<(InboundMessage, String)>>,
+}
+
+impl Default for MockMessageSubscriber {
+    fn default() -> Self {
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        Self {
+            sender,
+            receiver: Mutex::new(Some(receiver)),
+            subjects: Mutex::default(),
+            dead_letters: Mutex::default(),
+        }
+    }
+}
+
+impl MockMessageSubscriber {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue `message` on the subscription (only one subscription is supported)
+    pub fn deliver(&self, message: InboundMessage) {
+        let _ = self.sender.send(message);
+    }
+
+    /// Subjects subscribed to, in order
+    pub fn subjects(&self) -> Vec<String> {
+        self.subjects.lock().unwrap().clone()
+    }
+
+    /// Dead-lettered messages with their reasons, in order
+    pub fn get_dead_letters(&self) -> Vec<(InboundMessage, String)> {
+        self.dead_letters.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl MessageSubscriber for MockMessageSubscriber {
+    async fn subscribe(
+        &self,
+        subject: &str,
+    ) -> Result<BoxStream<'static, InboundMessage>, MessagingError> {
+        let Some(receiver) = self.receiver.lock().unwrap().take() else {
+            return Err(MessagingError::SubscribeFailed {
+                subject: subject.to_string(),
+                message: "Mock supports a single subscription".to_string(),
+            });
+        };
+        self.subjects.lock().unwrap().push(subject.to_string());
+        Ok(Box::pin(stream::unfold(receiver, |mut receiver| async {
+            receiver.recv().await.map(|message| (message, receiver))
+        })))
+    }
+
+    async fn dead_letter(
+        &self,
+        message: &InboundMessage,
+        reason: &str,
+    ) -> Result<(), MessagingError> {
+        self.dead_letters
+            .lock()
+            .unwrap()
+            .push((message.clone(), reason.to_string()));
+        Ok(())
+    }
+}
+
+/// In-memory object store
+#[derive(Default)]
+pub struct MockObjectStore {
+    objects: Mutex<HashMap<String, Bytes>>,
+    should_fail: AtomicBool,
+}
+
+impl MockObjectStore {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail (true) or serve (false) subsequent calls
+    pub fn set_failing(&self, failing: bool) {
+        self.should_fail.store(failing, Ordering::Relaxed);
+    }
+
+    /// Object stored under `key`
+    pub fn get_object(&self, key: &str) -> Option<Bytes> {
+        self.objects.lock().unwrap().get(key).cloned()
+    }
+
+    /// Keys of the stored objects
+    pub fn keys(&self) -> Vec<String> {
+        self.objects.lock().unwrap().keys().cloned().collect()
+    }
+
+    fn check_should_fail(&self) -> Result<(), ObjectStoreError> {
+        if self.should_fail.load(Ordering::Relaxed) {
+            return Err(ObjectStoreError::Unavailable(
+                "Mock object store failure".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ObjectStore for MockObjectStore {
+    async fn put(&self, key: &str, content: Bytes) -> Result<(), ObjectStoreError> {
+        self.check_should_fail()?;
+        self.objects
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), content);
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        key: &str,
+        range: Option<Range<u64>>,
+    ) -> Result<BoxStream<'static, Result<Bytes, ObjectStoreError>>, ObjectStoreError> {
+        self.check_should_fail()?;
+        let mut content = self
+            .get_object(key)
+            .ok_or_else(|| ObjectStoreError::NotFound(key.to_string()))?;
+        if let Some(range) = range {
+            let end = (range.end as usize).min(content.len());
+            content = content.slice((range.start as usize).min(end)..end);
+        }
+        Ok(Box::pin(stream::iter([Ok(content)])))
+    }
+}
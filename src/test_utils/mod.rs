@@ -1,8 +1,22 @@
 //! Test utilities and mock implementations.
 
+pub mod capture;
+pub mod clock;
+pub mod ids;
 pub mod mocks;
+#[cfg(feature = "test-utils")]
+pub mod solana_rpc;
 
-pub use mocks::{MockBlockchainClient, MockConfig, MockProvider, mock_repos};
+pub use capture::{CapturedSpan, TraceCapture};
+pub use clock::MockClock;
+pub use ids::SequenceIdGenerator;
+pub use mocks::{
+    MockBlockchainClient, MockConfig, MockMessagePublisher, MockMessageSubscriber, MockMethod,
+    MockNotificationClient, MockObjectStore, MockProvider, MockStep, MockTelemetrySink,
+    MockUnitOfWork, TelemetryRecord, mock_repos,
+};
+#[cfg(feature = "test-utils")]
+pub use solana_rpc::{MockSolanaRpc, RpcReply};
 
 use secrecy::SecretString;
 
@@ -0,0 +1,217 @@
+//! Canned Solana JSON-RPC node for exercising [`RpcBlockchainClient`] over real HTTP.
+//!
+//! [`MockSolanaRpc`] runs a local wiremock server that answers JSON-RPC requests by
+//! `method`, so tests cover what the in-process provider mocks cannot: status codes,
+//! slow nodes, malformed bodies and failover between endpoints.
+//!
+//! ```ignore
+//! let rpc = MockSolanaRpc::start().await;
+//! rpc.mock("getSlot", RpcReply::result(json!(42))).await;
+//! rpc.mock_once("getLatestBlockhash", RpcReply::Status(503)).await;
+//! let client = rpc.client(RpcClientConfig::default());
+//! ```
+//!
+//! Replies mounted with [`MockSolanaRpc::mock_once`] or [`MockSolanaRpc::mock_times`] are
+//! used up in the order they were mounted, before any reply mounted with
+//! [`MockSolanaRpc::mock`] for the same method.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use ed25519_dalek::SigningKey;
+use rand::rngs::OsRng;
+use secrecy::SecretString;
+use serde_json::{Value, json};
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::domain::TransactionSigner;
+use crate::infra::blockchain::signer::LocalSigner;
+use crate::infra::blockchain::solana::{RpcBlockchainClient, RpcClientConfig};
+
+/// Priority of replies that are used up, ahead of the standing ones (wiremock: 1 is first)
+const ONCE_PRIORITY: u8 = 1;
+/// Priority of standing replies
+const STANDING_PRIORITY: u8 = 5;
+
+/// What the node answers to one JSON-RPC request
+#[derive(Debug, Clone)]
+pub enum RpcReply {
+    /// `200` with `{"jsonrpc":"2.0","id":1,"result":...}`
+    Result(Value),
+    /// `200` with a JSON-RPC error object
+    Error { code: i64, message: String },
+    /// A bare HTTP status with no body, e.g. `429` or `503`
+    Status(u16),
+    /// `200` with this body verbatim, e.g. truncated or non-JSON
+    Raw(String),
+    /// `reply`, after `delay`
+    Delayed(Box<RpcReply>, Duration),
+}
+
+impl RpcReply {
+    #[must_use]
+    pub fn result(value: Value) -> Self {
+        Self::Result(value)
+    }
+
+    #[must_use]
+    pub fn error(code: i64, message: impl Into<String>) -> Self {
+        Self::Error {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// This reply, sent only after `delay` (e.g. to exceed the client timeout)
+    #[must_use]
+    pub fn delayed(self, delay: Duration) -> Self {
+        Self::Delayed(Box::new(self), delay)
+    }
+
+    fn template(&self) -> ResponseTemplate {
+        match self {
+            Self::Result(result) => ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": result,
+            })),
+            Self::Error { code, message } => ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": code, "message": message },
+            })),
+            Self::Status(status) => ResponseTemplate::new(*status),
+            Self::Raw(body) => ResponseTemplate::new(200)
+                .insert_header("content-type", "application/json")
+                .set_body_string(body.clone()),
+            Self::Delayed(reply, delay) => reply.template().set_delay(*delay),
+        }
+    }
+}
+
+/// `getSlot` result
+#[must_use]
+pub fn slot_result(slot: u64) -> RpcReply {
+    RpcReply::result(json!(slot))
+}
+
+/// `getLatestBlockhash` result
+#[must_use]
+pub fn blockhash_result(blockhash: &str) -> RpcReply {
+    RpcReply::result(json!({
+        "context": { "slot": 1 },
+        "value": { "blockhash": blockhash, "lastValidBlockHeight": 150 },
+    }))
+}
+
+/// `getBalance` result
+#[must_use]
+pub fn balance_result(lamports: u64) -> RpcReply {
+    RpcReply::result(json!({ "context": { "slot": 1 }, "value": lamports }))
+}
+
+/// `sendTransaction` result: the transaction signature
+#[must_use]
+pub fn signature_result(signature: &str) -> RpcReply {
+    RpcReply::result(json!(signature))
+}
+
+/// `getSignatureStatuses` result for one signature; None for an unknown signature
+#[must_use]
+pub fn signature_status_result(confirmation_status: Option<&str>) -> RpcReply {
+    let status = confirmation_status.map(|status| {
+        json!({ "slot": 1, "confirmations": null, "err": null, "confirmationStatus": status })
+    });
+    RpcReply::result(json!({ "context": { "slot": 1 }, "value": [status] }))
+}
+
+/// `sendTransaction` preflight failure, as nodes report it (code `-32002`)
+#[must_use]
+pub fn simulation_failed(reason: &str) -> RpcReply {
+    RpcReply::error(-32002, format!("Transaction simulation failed: {}", reason))
+}
+
+/// A local Solana JSON-RPC node with canned replies
+pub struct MockSolanaRpc {
+    server: MockServer,
+}
+
+impl MockSolanaRpc {
+    /// Start a node on a random local port; it answers `404` until replies are mounted
+    pub async fn start() -> Self {
+        Self {
+            server: MockServer::start().await,
+        }
+    }
+
+    /// JSON-RPC endpoint URL
+    #[must_use]
+    pub fn url(&self) -> String {
+        self.server.uri()
+    }
+
+    /// Client against this node, signing with a fresh local key
+    #[must_use]
+    pub fn client(&self, config: RpcClientConfig) -> RpcBlockchainClient {
+        RpcBlockchainClient::new(&self.url(), test_signer(), config)
+            .expect("mock RPC client builds")
+    }
+
+    /// Answer every `rpc_method` request with `reply`
+    pub async fn mock(&self, rpc_method: &str, reply: RpcReply) {
+        Self::given(rpc_method)
+            .respond_with(reply.template())
+            .with_priority(STANDING_PRIORITY)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Answer the next `rpc_method` request with `reply`
+    pub async fn mock_once(&self, rpc_method: &str, reply: RpcReply) {
+        self.mock_times(rpc_method, 1, reply).await;
+    }
+
+    /// Answer the next `times` `rpc_method` requests with `reply`
+    pub async fn mock_times(&self, rpc_method: &str, times: u64, reply: RpcReply) {
+        Self::given(rpc_method)
+            .respond_with(reply.template())
+            .up_to_n_times(times)
+            .with_priority(ONCE_PRIORITY)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// JSON-RPC methods received so far, in order
+    pub async fn methods(&self) -> Vec<String> {
+        self.server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|request| {
+                let body: Value = request.body_json().ok()?;
+                body["method"].as_str().map(str::to_string)
+            })
+            .collect()
+    }
+
+    /// Number of `rpc_method` requests received so far
+    pub async fn calls(&self, rpc_method: &str) -> usize {
+        self.methods()
+            .await
+            .iter()
+            .filter(|received| *received == rpc_method)
+            .count()
+    }
+
+    fn given(rpc_method: &str) -> wiremock::MockBuilder {
+        Mock::given(method("POST")).and(body_partial_json(json!({ "method": rpc_method })))
+    }
+}
+
+fn test_signer() -> Arc<dyn TransactionSigner> {
+    let key = SigningKey::generate(&mut OsRng);
+    let secret = SecretString::from(bs58::encode(key.to_bytes()).into_string());
+    Arc::new(LocalSigner::new(secret).expect("generated key is valid"))
+}
@@ -10,7 +10,9 @@ use tower::ServiceExt;
 
 use testable_rust_architecture_template::api::create_router;
 use testable_rust_architecture_template::app::AppState;
-use testable_rust_architecture_template::domain::{CreateItemRequest, Item, PaginatedResponse};
+use testable_rust_architecture_template::domain::{
+    CreateItemRequest, ErrorResponse, Item, ItemSummary, PaginatedResponse,
+};
 use testable_rust_architecture_template::test_utils::{
     MockBlockchainClient, MockProvider, mock_repos, test_api_key,
 };
@@ -90,7 +92,7 @@ async fn test_full_item_lifecycle_flow() {
         .await
         .unwrap()
         .to_bytes();
-    let list_result: PaginatedResponse<Item> = serde_json::from_slice(&body_bytes).unwrap();
+    let list_result: PaginatedResponse<ItemSummary> = serde_json::from_slice(&body_bytes).unwrap();
     assert!(list_result.items.iter().any(|i| i.id == item_id));
 }
 
@@ -113,3 +115,43 @@ async fn test_post_bad_request_validation() {
     let response = router.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
+
+#[tokio::test]
+async fn test_post_malformed_json_returns_error_envelope() {
+    let state = create_test_state();
+    let router = create_router(state);
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/items")
+        .header("Content-Type", "application/json")
+        .header("x-api-key", "test-api-key")
+        .body(Body::from("{\"name\": "))
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body: ErrorResponse = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(body.error.r#type, "invalid_json");
+}
+
+#[tokio::test]
+async fn test_list_invalid_query_returns_error_envelope() {
+    let state = create_test_state();
+    let router = create_router(state);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/items?limit=many")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body: ErrorResponse = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(body.error.r#type, "invalid_query");
+}
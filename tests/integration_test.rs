@@ -6,17 +6,29 @@ use axum::{
     body::Body,
     http::{Request, StatusCode},
 };
+use futures::stream::BoxStream;
 use http_body_util::BodyExt;
 use tower::ServiceExt;
 
-use testable_rust_architecture_template::api::create_router;
-use testable_rust_architecture_template::app::AppState;
+use testable_rust_architecture_template::api::{OpenApiConfig, create_router};
+use testable_rust_architecture_template::app::{
+    AppState, BlockchainRetryWorker, BodyLimits, IssuerKeyRegistry, WorkerConfig,
+};
+#[allow(deprecated)]
+use testable_rust_architecture_template::domain::LegacyItemRepository;
 use testable_rust_architecture_template::domain::{
-    BlockchainStatus, CreateItemRequest, HealthResponse, HealthStatus, Item, ItemRepository,
-    PaginatedResponse,
+    ApiKey, ApiKeyStore, AuditEntry, AuditLogger, BlockchainClient, BlockchainStatus,
+    CreateApiKeyResponse, CreateItemRequest, ErrorResponse, EventLog, ExportBookmark,
+    HealthCheckError, HealthResponse, HealthStatus, ImportReport, IssuerKeyStatus, Item, ItemError,
+    ItemListFilter, ItemMetadataRequest, ItemPosition, ItemRepository, ItemSearchHit, ItemSummary,
+    ItemTimeline, ItemVerification, Job, JobStatus, JobStore, MaintenanceMode, ObjectStore,
+    OutboxRepository, OutboxStatus, PaginatedResponse, QueueDepth, ReceiptVerification,
+    SchemaStatus, SolanaOutboxPayload, SubmissionAttempt, SubmissionRepository, TagCount,
+    TimelineEntryKind, UnitOfWork, UsageLedger, UsageReport, WebhookDelivery, WebhookDeliveryLog,
 };
 use testable_rust_architecture_template::test_utils::{
-    MockBlockchainClient, MockProvider, mock_repos, test_api_key,
+    MockBlockchainClient, MockMethod, MockObjectStore, MockProvider, MockStep, mock_repos,
+    test_api_key,
 };
 
 fn create_test_state() -> Arc<AppState> {
@@ -58,6 +70,41 @@ async fn test_create_item_success() {
     assert_eq!(item.blockchain_status, BlockchainStatus::PendingSubmission);
 }
 
+#[tokio::test]
+async fn test_create_item_dedupe_returns_existing_item() {
+    let router = create_router(create_test_state());
+    let payload = CreateItemRequest::new("Test Item".to_string(), "Content".to_string());
+    let create = |uri: &str| {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("Content-Type", "application/json")
+            .header(API_KEY_HEADER, TEST_KEY)
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .unwrap()
+    };
+
+    let mut ids = Vec::new();
+    for _ in 0..2 {
+        let response = router
+            .clone()
+            .oneshot(create("/items?dedupe=return_existing"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let item: Item = serde_json::from_slice(&body_bytes).unwrap();
+        ids.push(item.id);
+    }
+    assert_eq!(ids[0], ids[1]);
+
+    let response = router
+        .oneshot(create("/items?dedupe=sometimes"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
 #[tokio::test]
 async fn test_create_item_validation_error() {
     let state = create_test_state();
@@ -75,6 +122,13 @@ async fn test_create_item_validation_error() {
 
     let response = router.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let error: ErrorResponse = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(error.error.r#type, "validation_error");
+    assert_eq!(error.error.fields.len(), 1);
+    assert_eq!(error.error.fields[0].field, "name");
+    assert_eq!(error.error.fields[0].code, "length");
 }
 
 #[tokio::test]
@@ -92,12 +146,173 @@ async fn test_list_items_empty() {
     assert_eq!(response.status(), StatusCode::OK);
 
     let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
-    let result: PaginatedResponse<Item> = serde_json::from_slice(&body_bytes).unwrap();
+    let result: PaginatedResponse<ItemSummary> = serde_json::from_slice(&body_bytes).unwrap();
     assert!(result.items.is_empty());
     assert!(!result.has_more);
     assert!(result.next_cursor.is_none());
 }
 
+/// An implementor written against the combined repository from before the split
+struct LegacyRepository(Arc<MockProvider>);
+
+#[allow(deprecated)]
+#[async_trait::async_trait]
+impl LegacyItemRepository for LegacyRepository {
+    async fn health_check(&self) -> Result<(), HealthCheckError> {
+        ItemRepository::health_check(self.0.as_ref()).await
+    }
+
+    async fn get_item(&self, id: &str) -> Result<Option<Item>, ItemError> {
+        self.0.get_item(id).await
+    }
+
+    async fn create_item(&self, data: &CreateItemRequest) -> Result<Item, ItemError> {
+        self.0.create_item(data).await
+    }
+
+    async fn create_item_without_outbox(
+        &self,
+        data: &CreateItemRequest,
+    ) -> Result<Item, ItemError> {
+        self.0.create_item_without_outbox(data).await
+    }
+
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork + '_>, ItemError> {
+        self.0.begin().await
+    }
+
+    async fn list_items(
+        &self,
+        limit: i64,
+        cursor: Option<&str>,
+        filter: &ItemListFilter,
+    ) -> Result<PaginatedResponse<Item>, ItemError> {
+        self.0.list_items(limit, cursor, filter).await
+    }
+
+    fn stream_items(&self) -> BoxStream<'static, Result<Item, ItemError>> {
+        self.0.stream_items()
+    }
+
+    fn stream_item_changes(
+        &self,
+        after: Option<ItemPosition>,
+    ) -> BoxStream<'static, Result<Item, ItemError>> {
+        self.0.stream_item_changes(after)
+    }
+
+    async fn export_bookmark(&self, name: &str) -> Result<ExportBookmark, ItemError> {
+        self.0.export_bookmark(name).await
+    }
+
+    async fn acknowledge_export_bookmark(
+        &self,
+        name: &str,
+        position: &ItemPosition,
+    ) -> Result<ExportBookmark, ItemError> {
+        self.0.acknowledge_export_bookmark(name, position).await
+    }
+
+    async fn search_items(&self, query: &str, limit: i64) -> Result<Vec<ItemSearchHit>, ItemError> {
+        self.0.search_items(query, limit).await
+    }
+
+    async fn soft_delete_item(&self, id: &str) -> Result<Option<Item>, ItemError> {
+        self.0.soft_delete_item(id).await
+    }
+
+    async fn purge_deleted_items(
+        &self,
+        deleted_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, ItemError> {
+        self.0.purge_deleted_items(deleted_before).await
+    }
+
+    async fn update_blockchain_status(
+        &self,
+        id: &str,
+        status: BlockchainStatus,
+        signature: Option<&str>,
+        error: Option<&str>,
+        next_retry_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<(), ItemError> {
+        self.0
+            .update_blockchain_status(id, status, signature, error, next_retry_at)
+            .await
+    }
+
+    async fn enqueue_solana_outbox_for_item(
+        &self,
+        item_id: &str,
+        payload: &SolanaOutboxPayload,
+    ) -> Result<Item, ItemError> {
+        self.0
+            .enqueue_solana_outbox_for_item(item_id, payload)
+            .await
+    }
+
+    async fn get_pending_blockchain_items(
+        &self,
+        limit: i64,
+        claim_ttl: std::time::Duration,
+    ) -> Result<Vec<Item>, ItemError> {
+        self.0.get_pending_blockchain_items(limit, claim_ttl).await
+    }
+
+    async fn increment_retry_count(&self, id: &str) -> Result<i32, ItemError> {
+        self.0.increment_retry_count(id).await
+    }
+}
+
+#[tokio::test]
+async fn test_legacy_item_repository_works_with_app_state() {
+    let mock = Arc::new(MockProvider::new());
+    let legacy = Arc::new(LegacyRepository(Arc::clone(&mock)));
+    let state = Arc::new(AppState::new(
+        Arc::clone(&legacy) as Arc<dyn ItemRepository>,
+        Arc::clone(&mock) as Arc<dyn OutboxRepository>,
+        Arc::new(MockBlockchainClient::new()),
+        test_api_key(),
+    ));
+
+    let payload = CreateItemRequest::new("Legacy".to_string(), "Content".to_string());
+    let item = state
+        .service
+        .create_and_submit_item(&payload)
+        .await
+        .unwrap();
+    let response = create_router(state)
+        .oneshot(
+            Request::builder()
+                .uri(format!("/items/{}", item.id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The submission bookkeeping is reachable through the new trait as well
+    let submissions = legacy as Arc<dyn SubmissionRepository>;
+    submissions
+        .update_blockchain_status(
+            &item.id,
+            BlockchainStatus::Submitted,
+            Some("sig_legacy"),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        submissions.increment_retry_count(&item.id).await.unwrap(),
+        1
+    );
+    let stored = mock.get_item(&item.id).await.unwrap().unwrap();
+    assert_eq!(stored.blockchain_status, BlockchainStatus::Submitted);
+    assert_eq!(stored.blockchain_signature.as_deref(), Some("sig_legacy"));
+}
+
 #[tokio::test]
 async fn test_list_items_with_pagination() {
     let mock = Arc::new(MockProvider::new());
@@ -133,7 +348,7 @@ async fn test_list_items_with_pagination() {
     assert_eq!(response.status(), StatusCode::OK);
 
     let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
-    let result: PaginatedResponse<Item> = serde_json::from_slice(&body_bytes).unwrap();
+    let result: PaginatedResponse<ItemSummary> = serde_json::from_slice(&body_bytes).unwrap();
     assert_eq!(result.items.len(), 2);
     assert!(result.has_more);
     assert!(result.next_cursor.is_some());
@@ -150,13 +365,13 @@ async fn test_list_items_with_pagination() {
     assert_eq!(response.status(), StatusCode::OK);
 
     let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
-    let result: PaginatedResponse<Item> = serde_json::from_slice(&body_bytes).unwrap();
+    let result: PaginatedResponse<ItemSummary> = serde_json::from_slice(&body_bytes).unwrap();
     assert_eq!(result.items.len(), 2);
     assert!(result.has_more);
 }
 
 #[tokio::test]
-async fn test_get_item_success() {
+async fn test_list_items_page_number_mode() {
     let mock = Arc::new(MockProvider::new());
     let (item_repo, outbox_repo) = mock_repos(&mock);
     let blockchain = Arc::new(MockBlockchainClient::new());
@@ -166,133 +381,347 @@ async fn test_get_item_success() {
         blockchain,
         test_api_key(),
     ));
-
-    // Create an item
-    let payload = CreateItemRequest::new("Test Item".to_string(), "Content".to_string());
-    let created_item = state
-        .service
-        .create_and_submit_item(&payload)
-        .await
-        .unwrap();
-
+    for i in 0..5 {
+        let payload = CreateItemRequest::new(format!("Item {}", i), "Content".to_string());
+        state
+            .service
+            .create_and_submit_item(&payload)
+            .await
+            .unwrap();
+    }
     let router = create_router(state);
 
-    let request = Request::builder()
-        .method("GET")
-        .uri(format!("/items/{}", created_item.id))
-        .body(Body::empty())
+    let get = |uri: &str| {
+        router.clone().oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    let response = get("/items?page=2&per_page=2&sort=name&order=asc")
+        .await
         .unwrap();
-
-    let response = router.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let result: PaginatedResponse<ItemSummary> = serde_json::from_slice(&body_bytes).unwrap();
+    let names: Vec<&str> = result.items.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(names, ["Item 2", "Item 3"]);
+    assert_eq!(result.total, Some(5));
+    assert!(!result.total_estimated);
+    assert!(result.has_more);
+    assert!(result.next_cursor.is_none());
 
+    let response = get("/items?page=3&per_page=2").await.unwrap();
     let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
-    let item: Item = serde_json::from_slice(&body_bytes).unwrap();
-    assert_eq!(item.id, created_item.id);
+    let result: PaginatedResponse<ItemSummary> = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(result.items.len(), 1);
+    assert!(!result.has_more);
+
+    // Cursor mode stays the default and reports no total
+    let response = get("/items?limit=2").await.unwrap();
+    let body: serde_json::Value =
+        serde_json::from_slice(&response.into_body().collect().await.unwrap().to_bytes()).unwrap();
+    assert!(body.get("total").is_none());
+    let cursor = body["next_cursor"].as_str().unwrap().to_string();
+
+    let response = get(&format!("/items?page=2&cursor={}", cursor))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
 
 #[tokio::test]
-async fn test_get_item_not_found() {
+async fn test_list_tags_with_usage_counts() {
     let state = create_test_state();
+    for (name, tags) in [
+        ("First", vec!["rust", "db"]),
+        ("Second", vec!["rust"]),
+        ("Third", vec![]),
+    ] {
+        let payload = CreateItemRequest {
+            metadata: Some(ItemMetadataRequest {
+                author: None,
+                version: None,
+                tags: tags.into_iter().map(String::from).collect(),
+                custom_fields: Default::default(),
+            }),
+            ..CreateItemRequest::new(name.to_string(), "Content".to_string())
+        };
+        state
+            .service
+            .create_and_submit_item(&payload)
+            .await
+            .unwrap();
+    }
     let router = create_router(state);
 
-    let request = Request::builder()
-        .method("GET")
-        .uri("/items/nonexistent_id")
-        .body(Body::empty())
+    let response = router
+        .clone()
+        .oneshot(Request::builder().uri("/tags").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let tags: Vec<TagCount> = serde_json::from_slice(&body_bytes).unwrap();
+    let tags: Vec<(&str, u64)> = tags.iter().map(|t| (t.tag.as_str(), t.items)).collect();
+    assert_eq!(tags, [("rust", 2), ("db", 1)]);
+
+    let response = router
+        .oneshot(
+            Request::builder()
+                .uri("/items?tag=db")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
         .unwrap();
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let result: PaginatedResponse<ItemSummary> = serde_json::from_slice(&body_bytes).unwrap();
+    let names: Vec<&str> = result.items.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(names, ["First"]);
+}
 
-    let response = router.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+#[tokio::test]
+async fn test_list_items_views_and_field_selection() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let state = Arc::new(AppState::new(
+        item_repo,
+        outbox_repo,
+        Arc::new(MockBlockchainClient::new()),
+        test_api_key(),
+    ));
+    let request = CreateItemRequest::new("Item".to_string(), "Item content".to_string());
+    state
+        .service
+        .create_and_submit_item(&request)
+        .await
+        .unwrap();
+    let router = create_router(state);
+    let list = |uri: &'static str| {
+        let router = router.clone();
+        async move {
+            let response = router
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let status = response.status();
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            (
+                status,
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+            )
+        }
+    };
+
+    // Summaries by default
+    let (status, page) = list("/items").await;
+    assert_eq!(status, StatusCode::OK);
+    let item = &page["items"][0];
+    assert!(item.get("content").is_none());
+    assert_eq!(item["name"], "Item");
+    assert_eq!(item["content_length"], 12);
+
+    let (_, page) = list("/items?view=full").await;
+    assert_eq!(page["items"][0]["content"], "Item content");
+
+    let (status, page) = list("/items?fields=id,name,blockchain_status").await;
+    assert_eq!(status, StatusCode::OK);
+    let item = page["items"][0].as_object().unwrap();
+    let mut keys: Vec<&str> = item.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    assert_eq!(keys, ["blockchain_status", "id", "name"]);
+
+    let (status, body) = list("/items?fields=id,secret").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["type"], "invalid_query");
 }
 
 #[tokio::test]
-async fn test_graceful_degradation_blockchain_failure() {
+async fn test_list_items_rejects_tampered_cursor() {
     let mock = Arc::new(MockProvider::new());
     let (item_repo, outbox_repo) = mock_repos(&mock);
-    let blockchain = Arc::new(MockBlockchainClient::failing("RPC error"));
+    let blockchain = Arc::new(MockBlockchainClient::new());
     let state = Arc::new(AppState::new(
         item_repo,
         outbox_repo,
         blockchain,
         test_api_key(),
     ));
-    let router = create_router(state);
 
-    let payload = CreateItemRequest::new("Test".to_string(), "Content".to_string());
+    let mut ids = Vec::new();
+    for i in 0..3 {
+        let payload = CreateItemRequest::new(format!("Item {}", i), "Content".to_string());
+        ids.push(
+            state
+                .service
+                .create_and_submit_item(&payload)
+                .await
+                .unwrap()
+                .id,
+        );
+    }
 
+    let router = create_router(state);
     let request = Request::builder()
-        .method("POST")
-        .uri("/items")
-        .header("Content-Type", "application/json")
-        .header(API_KEY_HEADER, TEST_KEY)
-        .body(Body::from(serde_json::to_string(&payload).unwrap()))
+        .uri("/items?limit=1")
+        .body(Body::empty())
         .unwrap();
-
-    let response = router.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
-
+    let response = router.clone().oneshot(request).await.unwrap();
     let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
-    let item: Item = serde_json::from_slice(&body_bytes).unwrap();
-
-    // Item should be created and queued for submission
-    assert_eq!(item.blockchain_status, BlockchainStatus::PendingSubmission);
-    assert!(item.blockchain_last_error.is_none());
+    let result: PaginatedResponse<ItemSummary> = serde_json::from_slice(&body_bytes).unwrap();
+    let cursor = result.next_cursor.unwrap();
+    assert!(!cursor.contains(&result.items[0].id));
+
+    // A raw item ID and a cursor with its signature altered are both rejected
+    let mut tampered = cursor.clone();
+    let last = if tampered.ends_with('A') { "B" } else { "A" };
+    tampered.replace_range(tampered.len() - 1.., last);
+    for bad in [ids[0].as_str(), tampered.as_str()] {
+        let request = Request::builder()
+            .uri(format!("/items?limit=1&cursor={}", bad))
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{bad}");
+        let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let error: ErrorResponse = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(error.error.r#type, "invalid_cursor");
+    }
 }
 
 #[tokio::test]
-async fn test_health_check() {
-    let state = create_test_state();
+async fn test_export_items_streams_ndjson_and_csv() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let blockchain = Arc::new(MockBlockchainClient::new());
+    let state = Arc::new(AppState::new(
+        item_repo,
+        outbox_repo,
+        blockchain,
+        test_api_key(),
+    ));
+    for name in ["First", "Second, with comma"] {
+        let payload = CreateItemRequest::new(name.to_string(), "Content".to_string());
+        state
+            .service
+            .create_and_submit_item(&payload)
+            .await
+            .unwrap();
+    }
     let router = create_router(state);
 
     let request = Request::builder()
-        .method("GET")
-        .uri("/health")
+        .uri("/items/export")
         .body(Body::empty())
         .unwrap();
-
-    let response = router.oneshot(request).await.unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
-
+    assert_eq!(response.headers()["content-type"], "application/x-ndjson");
     let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
-    let health: HealthResponse = serde_json::from_slice(&body_bytes).unwrap();
-    assert_eq!(health.status, HealthStatus::Healthy);
-}
-
-#[tokio::test]
-async fn test_liveness() {
-    let state = create_test_state();
-    let router = create_router(state);
+    let items: Vec<Item> = std::str::from_utf8(&body_bytes)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(items.len(), 2);
 
     let request = Request::builder()
-        .method("GET")
-        .uri("/health/live")
+        .uri("/items/export?format=csv")
         .body(Body::empty())
         .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        response.headers()["content-disposition"]
+            .to_str()
+            .unwrap()
+            .contains("items.csv")
+    );
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let csv = String::from_utf8(body_bytes.to_vec()).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("id,hash,name,"));
+    assert!(csv.contains("\"Second, with comma\""));
 
+    let request = Request::builder()
+        .uri("/items/export?format=xml")
+        .body(Body::empty())
+        .unwrap();
     let response = router.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
 
 #[tokio::test]
-async fn test_readiness_healthy() {
+async fn test_export_bookmark_resumes_after_acknowledgment() {
     let state = create_test_state();
+    for name in ["First", "Second", "Third"] {
+        let payload = CreateItemRequest::new(name.to_string(), "Content".to_string());
+        state
+            .service
+            .create_and_submit_item(&payload)
+            .await
+            .unwrap();
+    }
     let router = create_router(state);
+    let export = |uri: &'static str| {
+        let router = router.clone();
+        async move {
+            let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            let response = router.oneshot(request).await.unwrap();
+            let status = response.status();
+            let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+            let items: Vec<Item> = std::str::from_utf8(&body_bytes)
+                .unwrap()
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect();
+            (status, items)
+        }
+    };
+    let ack = |name: &str, item: &Item| {
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/items/export/bookmarks/{name}/ack"))
+            .header("content-type", "application/json")
+            .header(API_KEY_HEADER, TEST_KEY)
+            .body(Body::from(
+                serde_json::to_string(&ItemPosition::of(item)).unwrap(),
+            ))
+            .unwrap();
+        router.clone().oneshot(request)
+    };
 
-    let request = Request::builder()
-        .method("GET")
-        .uri("/health/ready")
-        .body(Body::empty())
-        .unwrap();
+    let (status, items) = export("/items/export?bookmark=analytics").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(items.len(), 3);
 
-    let response = router.oneshot(request).await.unwrap();
+    let response = ack("analytics", &items[1]).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let bookmark: ExportBookmark = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(bookmark.position, Some(ItemPosition::of(&items[1])));
+
+    // The bookmark resumes after the acknowledged item; an unbookmarked export is complete
+    let (_, remaining) = export("/items/export?bookmark=analytics").await;
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].id, items[2].id);
+    let (_, all) = export("/items/export").await;
+    assert_eq!(all.len(), 3);
+
+    let response = ack("never-exported", &items[0]).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let (status, _) = export("/items/export?bookmark=no%20spaces").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
 }
 
 #[tokio::test]
-async fn test_readiness_unhealthy() {
+async fn test_get_item_success() {
     let mock = Arc::new(MockProvider::new());
-    mock.set_healthy(false);
     let (item_repo, outbox_repo) = mock_repos(&mock);
     let blockchain = Arc::new(MockBlockchainClient::new());
     let state = Arc::new(AppState::new(
@@ -301,29 +730,301 @@ async fn test_readiness_unhealthy() {
         blockchain,
         test_api_key(),
     ));
+
+    // Create an item
+    let payload = CreateItemRequest::new("Test Item".to_string(), "Content".to_string());
+    let created_item = state
+        .service
+        .create_and_submit_item(&payload)
+        .await
+        .unwrap();
+
     let router = create_router(state);
 
     let request = Request::builder()
         .method("GET")
-        .uri("/health/ready")
+        .uri(format!("/items/{}", created_item.id))
         .body(Body::empty())
         .unwrap();
 
     let response = router.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let item: Item = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(item.id, created_item.id);
 }
 
 #[tokio::test]
-async fn test_database_failure() {
-    let mock = Arc::new(MockProvider::failing("DB error"));
-    let (item_repo, outbox_repo) = mock_repos(&mock);
-    let blockchain = Arc::new(MockBlockchainClient::new());
-    let state = Arc::new(AppState::new(
-        item_repo,
-        outbox_repo,
-        blockchain,
-        test_api_key(),
-    ));
+async fn test_update_item_with_if_match() {
+    let state = create_test_state();
+    let payload = CreateItemRequest::new("Draft".to_string(), "First".to_string());
+    let created = state
+        .service
+        .create_and_submit_item(&payload)
+        .await
+        .unwrap();
+    let router = create_router(state);
+
+    let response = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/items/{}", created.id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let etag = response.headers()["etag"].to_str().unwrap().to_string();
+    assert_eq!(etag, "\"1\"");
+
+    let update = |if_match: Option<&str>, content: &str| {
+        let body = CreateItemRequest::new("Final".to_string(), content.to_string());
+        let mut request = Request::builder()
+            .method("PUT")
+            .uri(format!("/items/{}", created.id))
+            .header("Content-Type", "application/json")
+            .header(API_KEY_HEADER, TEST_KEY);
+        if let Some(if_match) = if_match {
+            request = request.header("If-Match", if_match);
+        }
+        request
+            .body(Body::from(serde_json::to_string(&body).unwrap()))
+            .unwrap()
+    };
+
+    let response = router
+        .clone()
+        .oneshot(update(None, "Second"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PRECONDITION_REQUIRED);
+
+    let response = router
+        .clone()
+        .oneshot(update(Some(&etag), "Second"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["etag"], "\"2\"");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let item: Item = serde_json::from_slice(&body).unwrap();
+    assert_eq!((item.version, item.content.as_str()), (2, "Second"));
+
+    // The same If-Match again: the item moved on
+    let response = router.oneshot(update(Some(&etag), "Third")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let error: ErrorResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error.error.r#type, "conflict");
+}
+
+#[tokio::test]
+async fn test_get_item_not_found() {
+    let state = create_test_state();
+    let router = create_router(state);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/items/nonexistent_id")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_get_item_content_from_row_and_object_store() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let store = Arc::new(MockObjectStore::new());
+    let state = Arc::new(
+        AppState::new(
+            item_repo,
+            outbox_repo,
+            Arc::new(MockBlockchainClient::new()),
+            test_api_key(),
+        )
+        .with_object_store(Arc::clone(&store) as Arc<dyn ObjectStore>, 1024),
+    );
+    let large = "large content ".repeat(100);
+    let mut ids = Vec::new();
+    for content in ["small content", large.as_str()] {
+        let request = CreateItemRequest::new("Item".to_string(), content.to_string());
+        ids.push(
+            state
+                .service
+                .create_and_submit_item(&request)
+                .await
+                .unwrap()
+                .id,
+        );
+    }
+    let router = create_router(state);
+    let get = |uri: String| {
+        let router = router.clone();
+        async move {
+            let response = router
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let status = response.status();
+            let content_type = response
+                .headers()
+                .get("content-type")
+                .map(|v| v.to_str().unwrap().to_string());
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            (status, content_type, body)
+        }
+    };
+
+    let (status, content_type, body) = get(format!("/items/{}/content", ids[0])).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type.as_deref(), Some("text/plain; charset=utf-8"));
+    assert_eq!(body, "small content");
+
+    // The item itself only carries the key of large content
+    let (_, _, body) = get(format!("/items/{}", ids[1])).await;
+    let item: Item = serde_json::from_slice(&body).unwrap();
+    assert_eq!(item.content, "");
+    assert!(item.content_key.is_some());
+    let (status, _, body) = get(format!("/items/{}/content", ids[1])).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, large.as_bytes());
+
+    store.set_failing(true);
+    let (status, _, _) = get(format!("/items/{}/content", ids[1])).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    let (status, _, _) = get("/items/nonexistent_id/content".to_string()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_get_item_content_headers_and_ranges() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let state = Arc::new(AppState::new(
+        item_repo,
+        outbox_repo,
+        Arc::new(MockBlockchainClient::new()),
+        test_api_key(),
+    ));
+    let router = create_router(state);
+    let create = Request::builder()
+        .method("POST")
+        .uri("/items")
+        .header("Content-Type", "application/json")
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(Body::from(
+            serde_json::json!({
+                "name": "Data",
+                "content": r#"{"a":1,"b":2}"#,
+                "content_type": "application/json"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let response = router.clone().oneshot(create).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let item: Item = serde_json::from_slice(&body).unwrap();
+    assert_eq!(item.content_type, "application/json");
+    assert_eq!(item.content_length, 13);
+
+    let get = |range: Option<&str>| {
+        let router = router.clone();
+        let mut request = Request::builder().uri(format!("/items/{}/content", item.id));
+        if let Some(range) = range {
+            request = request.header("range", range);
+        }
+        async move {
+            let response = router
+                .oneshot(request.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            (status, headers, body)
+        }
+    };
+
+    let (status, headers, body) = get(None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["content-type"], "application/json");
+    assert_eq!(
+        headers["content-disposition"],
+        format!("attachment; filename=\"{}.json\"", item.id).as_str()
+    );
+    assert_eq!(headers["accept-ranges"], "bytes");
+    assert_eq!(headers["content-length"], "13");
+    assert_eq!(body, r#"{"a":1,"b":2}"#);
+
+    let (status, headers, body) = get(Some("bytes=1-5")).await;
+    assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+    assert_eq!(headers["content-range"], "bytes 1-5/13");
+    assert_eq!(headers["content-length"], "5");
+    assert_eq!(body, r#""a":1"#);
+
+    let (status, headers, body) = get(Some("bytes=-3")).await;
+    assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+    assert_eq!(headers["content-range"], "bytes 10-12/13");
+    assert_eq!(body, ":2}");
+
+    let (status, headers, _) = get(Some("bytes=13-")).await;
+    assert_eq!(status, StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(headers["content-range"], "bytes */13");
+
+    // Several ranges are not supported: the whole content is returned
+    let (status, _, body) = get(Some("bytes=0-1,4-5")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.len(), 13);
+}
+
+#[tokio::test]
+async fn test_create_item_rejects_invalid_content_type() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let state = Arc::new(AppState::new(
+        item_repo,
+        outbox_repo,
+        Arc::new(MockBlockchainClient::new()),
+        test_api_key(),
+    ));
+    let router = create_router(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/items")
+        .header("Content-Type", "application/json")
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(Body::from(
+            serde_json::json!({
+                "name": "Data",
+                "content": "x",
+                "content_type": "text/plain\r\nX-Injected: 1"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(mock.get_all_items().is_empty());
+}
+
+#[tokio::test]
+async fn test_graceful_degradation_blockchain_failure() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let blockchain = Arc::new(MockBlockchainClient::failing("RPC error"));
+    let state = Arc::new(AppState::new(
+        item_repo,
+        outbox_repo,
+        blockchain,
+        test_api_key(),
+    ));
     let router = create_router(state);
 
     let payload = CreateItemRequest::new("Test".to_string(), "Content".to_string());
@@ -337,64 +1038,69 @@ async fn test_database_failure() {
         .unwrap();
 
     let response = router.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let item: Item = serde_json::from_slice(&body_bytes).unwrap();
+
+    // Item should be created and queued for submission
+    assert_eq!(item.blockchain_status, BlockchainStatus::PendingSubmission);
+    assert!(item.blockchain_last_error.is_none());
 }
 
 #[tokio::test]
-async fn test_swagger_ui_available() {
+async fn test_health_check() {
     let state = create_test_state();
     let router = create_router(state);
 
     let request = Request::builder()
         .method("GET")
-        .uri("/swagger-ui/")
+        .uri("/health")
         .body(Body::empty())
         .unwrap();
 
     let response = router.oneshot(request).await.unwrap();
-    // Swagger UI redirects or returns 200
-    assert!(response.status().is_success() || response.status().is_redirection());
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let health: HealthResponse = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(health.status, HealthStatus::Healthy);
 }
 
 #[tokio::test]
-async fn test_openapi_spec_available() {
+async fn test_liveness() {
     let state = create_test_state();
     let router = create_router(state);
 
     let request = Request::builder()
         .method("GET")
-        .uri("/api-docs/openapi.json")
+        .uri("/health/live")
         .body(Body::empty())
         .unwrap();
 
     let response = router.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
-
-    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
-    let spec: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
-    assert!(spec.get("openapi").is_some());
-    assert!(spec.get("paths").is_some());
 }
 
 #[tokio::test]
-async fn test_retry_handler_item_not_found() {
+async fn test_readiness_healthy() {
     let state = create_test_state();
     let router = create_router(state);
 
     let request = Request::builder()
-        .method("POST")
-        .uri("/items/nonexistent_id/retry")
-        .header(API_KEY_HEADER, TEST_KEY)
+        .method("GET")
+        .uri("/health/ready")
         .body(Body::empty())
         .unwrap();
 
     let response = router.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.status(), StatusCode::OK);
 }
 
 #[tokio::test]
-async fn test_retry_handler_not_eligible() {
+async fn test_readiness_unhealthy() {
     let mock = Arc::new(MockProvider::new());
+    mock.set_healthy(false);
     let (item_repo, outbox_repo) = mock_repos(&mock);
     let blockchain = Arc::new(MockBlockchainClient::new());
     let state = Arc::new(AppState::new(
@@ -403,118 +1109,108 @@ async fn test_retry_handler_not_eligible() {
         blockchain,
         test_api_key(),
     ));
-
-    // Create an item and force it to Submitted status (not eligible for retry)
-    let payload = CreateItemRequest::new("Test Item".to_string(), "Content".to_string());
-    let created_item = state
-        .service
-        .create_and_submit_item(&payload)
-        .await
-        .unwrap();
-    mock.update_blockchain_status(
-        &created_item.id,
-        BlockchainStatus::Submitted,
-        Some("sig"),
-        None,
-        None,
-    )
-    .await
-    .unwrap();
-
     let router = create_router(state);
 
     let request = Request::builder()
-        .method("POST")
-        .uri(format!("/items/{}/retry", created_item.id))
-        .header(API_KEY_HEADER, TEST_KEY)
+        .method("GET")
+        .uri("/health/ready")
         .body(Body::empty())
         .unwrap();
 
     let response = router.oneshot(request).await.unwrap();
-    // Item is already submitted, not eligible for retry
-    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
 }
 
 #[tokio::test]
-async fn test_create_item_malformed_json() {
-    let state = create_test_state();
+async fn test_liveness_ignores_unhealthy_dependencies() {
+    let mock = Arc::new(MockProvider::new());
+    mock.set_healthy(false);
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let blockchain = Arc::new(MockBlockchainClient::new());
+    blockchain.set_healthy(false);
+    let state = Arc::new(AppState::new(
+        item_repo,
+        outbox_repo,
+        blockchain,
+        test_api_key(),
+    ));
     let router = create_router(state);
 
     let request = Request::builder()
-        .method("POST")
-        .uri("/items")
-        .header("Content-Type", "application/json")
-        .header(API_KEY_HEADER, TEST_KEY)
-        .body(Body::from("{ invalid json }"))
+        .method("GET")
+        .uri("/health/live")
+        .body(Body::empty())
         .unwrap();
 
     let response = router.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.status(), StatusCode::OK);
 }
 
 #[tokio::test]
-async fn test_list_items_invalid_limit() {
+async fn test_deep_health_requires_api_key() {
     let state = create_test_state();
     let router = create_router(state);
 
-    // Limit is clamped, so this should still work
     let request = Request::builder()
         .method("GET")
-        .uri("/items?limit=999999")
+        .uri("/health/deep")
         .body(Body::empty())
         .unwrap();
 
     let response = router.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 }
 
 #[tokio::test]
-async fn test_health_check_degraded() {
+async fn test_deep_health_bypasses_readiness_cache() {
     let mock = Arc::new(MockProvider::new());
     let (item_repo, outbox_repo) = mock_repos(&mock);
     let blockchain = Arc::new(MockBlockchainClient::new());
-    blockchain.set_healthy(false);
     let state = Arc::new(AppState::new(
         item_repo,
         outbox_repo,
-        blockchain,
+        blockchain.clone(),
         test_api_key(),
     ));
     let router = create_router(state);
 
-    let request = Request::builder()
+    let ready = Request::builder()
         .method("GET")
-        .uri("/health")
+        .uri("/health/ready")
         .body(Body::empty())
         .unwrap();
+    let response = router.clone().oneshot(ready).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
 
+    blockchain.set_healthy(false);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/health/deep")
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(Body::empty())
+        .unwrap();
     let response = router.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
 
     let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
     let health: HealthResponse = serde_json::from_slice(&body_bytes).unwrap();
-    assert_eq!(health.status, HealthStatus::Unhealthy);
-    assert_eq!(health.database, HealthStatus::Healthy);
     assert_eq!(health.blockchain, HealthStatus::Unhealthy);
 }
 
 #[tokio::test]
-async fn test_create_item_with_metadata() {
-    let state = create_test_state();
+async fn test_chain_disabled_items_stay_pending() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let state = Arc::new(AppState::without_blockchain(
+        item_repo,
+        outbox_repo,
+        test_api_key(),
+        None,
+    ));
     let router = create_router(state);
 
-    let payload = serde_json::json!({
-        "name": "Item with Metadata",
-        "content": "Content here",
-        "description": "A description",
-        "metadata": {
-            "author": "Test Author",
-            "version": "1.0.0",
-            "tags": ["tag1", "tag2"],
-            "custom_fields": {"key": "value"}
-        }
-    });
-
+    let payload = CreateItemRequest::new("Preview".to_string(), "Content".to_string());
     let request = Request::builder()
         .method("POST")
         .uri("/items")
@@ -522,12 +1218,1492 @@ async fn test_create_item_with_metadata() {
         .header(API_KEY_HEADER, TEST_KEY)
         .body(Body::from(serde_json::to_string(&payload).unwrap()))
         .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let item: Item = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(item.blockchain_status, BlockchainStatus::Pending);
+    assert!(mock.get_all_outbox_entries().is_empty());
 
+    let request = Request::builder()
+        .uri("/health")
+        .body(Body::empty())
+        .unwrap();
     let response = router.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
-
     let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
-    let item: Item = serde_json::from_slice(&body_bytes).unwrap();
-    assert_eq!(item.name, "Item with Metadata");
-    assert!(item.metadata.is_some());
+    let health: HealthResponse = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(health.blockchain, HealthStatus::Disabled);
+    assert_eq!(health.status, HealthStatus::Healthy);
+}
+
+#[tokio::test]
+async fn test_database_failure() {
+    let mock = Arc::new(MockProvider::failing("DB error"));
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let blockchain = Arc::new(MockBlockchainClient::new());
+    let state = Arc::new(AppState::new(
+        item_repo,
+        outbox_repo,
+        blockchain,
+        test_api_key(),
+    ));
+    let router = create_router(state);
+
+    let payload = CreateItemRequest::new("Test".to_string(), "Content".to_string());
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/items")
+        .header("Content-Type", "application/json")
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(Body::from(serde_json::to_string(&payload).unwrap()))
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[tokio::test]
+async fn test_swagger_ui_available() {
+    let state = create_test_state();
+    let router = create_router(state);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/swagger-ui/")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    // Swagger UI redirects or returns 200
+    assert!(response.status().is_success() || response.status().is_redirection());
+}
+
+#[tokio::test]
+async fn test_openapi_spec_available() {
+    let state = create_test_state();
+    let router = create_router(state);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api-docs/openapi.json")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let spec: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+    assert!(spec.get("openapi").is_some());
+    assert!(spec.get("paths").is_some());
+}
+
+#[tokio::test]
+async fn test_openapi_spec_uses_configured_metadata() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let config = OpenApiConfig {
+        environment: Some("staging".to_string()),
+        servers: OpenApiConfig::parse_servers("https://{tenant}.api.example.com|Tenant API"),
+        server_variables: OpenApiConfig::parse_server_variables("tenant=demo"),
+        ..OpenApiConfig::default()
+    };
+    let state = AppState::new(
+        item_repo,
+        outbox_repo,
+        Arc::new(MockBlockchainClient::new()),
+        test_api_key(),
+    )
+    .with_openapi(config.document());
+    let router = create_router(Arc::new(state));
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api-docs/openapi.json")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let spec: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+    assert!(
+        spec["info"]["title"]
+            .as_str()
+            .unwrap()
+            .ends_with("(staging)")
+    );
+    assert_eq!(
+        spec["servers"][0]["url"],
+        "https://{tenant}.api.example.com"
+    );
+    assert_eq!(spec["servers"][0]["variables"]["tenant"]["default"], "demo");
+}
+
+#[tokio::test]
+async fn test_retry_handler_item_not_found() {
+    let state = create_test_state();
+    let router = create_router(state);
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/items/nonexistent_id/retry")
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(Body::empty())
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_retry_handler_not_eligible() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let blockchain = Arc::new(MockBlockchainClient::new());
+    let state = Arc::new(AppState::new(
+        item_repo,
+        outbox_repo,
+        blockchain,
+        test_api_key(),
+    ));
+
+    // Create an item and force it to Submitted status (not eligible for retry)
+    let payload = CreateItemRequest::new("Test Item".to_string(), "Content".to_string());
+    let created_item = state
+        .service
+        .create_and_submit_item(&payload)
+        .await
+        .unwrap();
+    mock.update_blockchain_status(
+        &created_item.id,
+        BlockchainStatus::Submitted,
+        Some("sig"),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let router = create_router(state);
+
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/items/{}/retry", created_item.id))
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(Body::empty())
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    // Item is already submitted, not eligible for retry
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_verify_item_against_chain() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let blockchain = Arc::new(MockBlockchainClient::new());
+    let state = Arc::new(AppState::new(
+        item_repo,
+        outbox_repo,
+        Arc::clone(&blockchain) as Arc<dyn BlockchainClient>,
+        test_api_key(),
+    ));
+    let payload = CreateItemRequest::new("Contract".to_string(), "Signed terms".to_string());
+    let item = state
+        .service
+        .create_and_submit_item(&payload)
+        .await
+        .unwrap();
+    state.service.process_pending_submissions(10).await.unwrap();
+    let router = create_router(Arc::clone(&state));
+
+    let verify = |id: String| {
+        let router = router.clone();
+        async move {
+            let request = Request::builder()
+                .uri(format!("/items/{}/verify", id))
+                .body(Body::empty())
+                .unwrap();
+            router.oneshot(request).await.unwrap()
+        }
+    };
+
+    let response = verify(item.id.clone()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let report: ItemVerification = serde_json::from_slice(&body).unwrap();
+    assert!(report.verified);
+    assert!(report.found_on_chain && report.hash_matches && report.content_hash_matches);
+    assert_eq!(report.on_chain_hash, Some(report.expected_hash.clone()));
+    assert_eq!(report.slot, Some(12_345_600));
+    assert_eq!(report.confirmation_depth, Some(78));
+
+    // Content edited after anchoring no longer matches either hash
+    mock.edit_item(&item.id, |item| item.content = "Altered terms".to_string());
+    let body = verify(item.id.clone())
+        .await
+        .into_body()
+        .collect()
+        .await
+        .unwrap()
+        .to_bytes();
+    let report: ItemVerification = serde_json::from_slice(&body).unwrap();
+    assert!(!report.verified);
+    assert!(!report.content_hash_matches && !report.hash_matches);
+    assert!(report.found_on_chain);
+
+    blockchain.fail_method(MockMethod::GetTransaction, "rpc down");
+    let response = verify(item.id.clone()).await;
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    let response = verify("item_missing".to_string()).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_submission_attempts_endpoint() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let state = Arc::new(AppState::new(
+        item_repo,
+        outbox_repo,
+        Arc::new(MockBlockchainClient::new()),
+        test_api_key(),
+    ));
+    let payload = CreateItemRequest::new("Contract".to_string(), "Signed terms".to_string());
+    let item = state
+        .service
+        .create_and_submit_item(&payload)
+        .await
+        .unwrap();
+    state.service.process_pending_submissions(10).await.unwrap();
+    let router = create_router(state);
+
+    let request = Request::builder()
+        .uri(format!("/items/{}/attempts", item.id))
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let attempts: Vec<SubmissionAttempt> = serde_json::from_slice(&body).unwrap();
+    assert_eq!(attempts.len(), 1);
+    assert!(attempts[0].succeeded);
+    assert_eq!(attempts[0].endpoint.as_deref(), Some("mock-rpc.local"));
+
+    let request = Request::builder()
+        .uri("/items/item_missing/attempts")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_item_timeline_endpoint() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let state = Arc::new(
+        AppState::new(
+            item_repo,
+            outbox_repo,
+            Arc::new(MockBlockchainClient::new()),
+            test_api_key(),
+        )
+        .with_operational_logs(
+            Arc::clone(&mock) as Arc<dyn EventLog>,
+            Arc::clone(&mock) as Arc<dyn WebhookDeliveryLog>,
+        ),
+    );
+    let payload = CreateItemRequest::new("Contract".to_string(), "Signed terms".to_string());
+    let item = state
+        .service
+        .create_and_submit_item(&payload)
+        .await
+        .unwrap();
+    state.service.process_pending_submissions(10).await.unwrap();
+    mock.record_webhook_delivery(&WebhookDelivery {
+        id: 0,
+        event_id: "evt_1".to_string(),
+        event: "item.submitted".to_string(),
+        item_id: item.id.clone(),
+        url: "https://hooks.example.com".to_string(),
+        attempt: 1,
+        response_status: Some(200),
+        error: None,
+        succeeded: true,
+        attempted_at: chrono::Utc::now(),
+    })
+    .await
+    .unwrap();
+    let router = create_router(state);
+
+    let request = Request::builder()
+        .uri(format!("/items/{}/timeline", item.id))
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let timeline: ItemTimeline = serde_json::from_slice(&body).unwrap();
+    assert_eq!(timeline.item_id, item.id);
+    assert_eq!(
+        timeline.entries.iter().map(|e| e.kind).collect::<Vec<_>>(),
+        [
+            TimelineEntryKind::Created,
+            TimelineEntryKind::SubmissionAttempt,
+            TimelineEntryKind::StatusChanged,
+            TimelineEntryKind::WebhookDelivery,
+        ]
+    );
+    assert!(timeline.entries.is_sorted_by_key(|e| e.at));
+    assert_eq!(
+        timeline.entries[2].event.as_ref().unwrap().status,
+        BlockchainStatus::Submitted
+    );
+
+    let request = Request::builder()
+        .uri("/items/item_missing/timeline")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_create_item_malformed_json() {
+    let state = create_test_state();
+    let router = create_router(state);
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/items")
+        .header("Content-Type", "application/json")
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(Body::from("{ invalid json }"))
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_list_items_invalid_limit() {
+    let state = create_test_state();
+    let router = create_router(state);
+
+    // Limit is clamped, so this should still work
+    let request = Request::builder()
+        .method("GET")
+        .uri("/items?limit=999999")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_health_check_degraded() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let blockchain = Arc::new(MockBlockchainClient::new());
+    blockchain.set_healthy(false);
+    let state = Arc::new(AppState::new(
+        item_repo,
+        outbox_repo,
+        blockchain,
+        test_api_key(),
+    ));
+    let router = create_router(state);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/health")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let health: HealthResponse = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(health.status, HealthStatus::Unhealthy);
+    assert_eq!(health.database, HealthStatus::Healthy);
+    assert_eq!(health.blockchain, HealthStatus::Unhealthy);
+}
+
+#[tokio::test]
+async fn test_create_item_with_metadata() {
+    let state = create_test_state();
+    let router = create_router(state);
+
+    let payload = serde_json::json!({
+        "name": "Item with Metadata",
+        "content": "Content here",
+        "description": "A description",
+        "metadata": {
+            "author": "Test Author",
+            "version": "1.0.0",
+            "tags": ["tag1", "tag2"],
+            "custom_fields": {"key": "value"}
+        }
+    });
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/items")
+        .header("Content-Type", "application/json")
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(Body::from(serde_json::to_string(&payload).unwrap()))
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let item: Item = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(item.name, "Item with Metadata");
+    assert!(item.metadata.is_some());
+}
+
+#[tokio::test]
+async fn test_create_item_rejects_oversized_metadata() {
+    let state = Arc::try_unwrap(create_test_state())
+        .ok()
+        .unwrap()
+        .with_max_metadata_bytes(1024);
+    let router = create_router(Arc::new(state));
+
+    let payload = serde_json::json!({
+        "name": "Item with huge metadata",
+        "content": "Content here",
+        "metadata": {
+            "tags": [],
+            "custom_fields": {"blob": "x".repeat(2048)}
+        }
+    });
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/items")
+        .header("Content-Type", "application/json")
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(Body::from(serde_json::to_string(&payload).unwrap()))
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let error: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(error["error"]["type"], "field_too_large");
+    let message = error["error"]["message"].as_str().unwrap();
+    assert!(message.contains("'metadata'"));
+    assert!(message.contains("1024 bytes"));
+}
+
+#[tokio::test]
+async fn test_writes_rejected_while_migrations_pending() {
+    let state = Arc::try_unwrap(create_test_state())
+        .ok()
+        .unwrap()
+        .with_schema_status(SchemaStatus {
+            pending: vec![20990101000000],
+            unknown: Vec::new(),
+        });
+    let router = create_router(Arc::new(state));
+
+    let payload = CreateItemRequest::new("Test Item".to_string(), "Content".to_string());
+    let request = Request::builder()
+        .method("POST")
+        .uri("/items")
+        .header("Content-Type", "application/json")
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(Body::from(serde_json::to_string(&payload).unwrap()))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let error: ErrorResponse = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(error.error.r#type, "migrations_pending");
+
+    // Reads and health keep working; health reports the mismatch
+    let request = Request::builder()
+        .uri("/items")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = Request::builder()
+        .uri("/health")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let health: HealthResponse = serde_json::from_slice(&body_bytes).unwrap();
+    assert!(health.migrations_pending);
+    assert_eq!(health.status, HealthStatus::Degraded);
+
+    let request = Request::builder()
+        .uri("/health/ready")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_request_id_echoed_and_included_in_errors() {
+    let router = create_router(create_test_state());
+
+    // A client-supplied ID is kept and quoted in the error body
+    let request = Request::builder()
+        .uri("/items/nonexistent_id")
+        .header("x-request-id", "client-req-42")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers()["x-request-id"], "client-req-42");
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let error: ErrorResponse = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(error.error.request_id.as_deref(), Some("client-req-42"));
+
+    // Without one (or with an invalid one) an ID is generated
+    let request = Request::builder()
+        .uri("/items/nonexistent_id")
+        .header("x-request-id", "has spaces")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    let generated = response.headers()["x-request-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert_ne!(generated, "has spaces");
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let error: ErrorResponse = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(error.error.request_id, Some(generated));
+
+    // Successful responses carry the header but no body change
+    let request = Request::builder()
+        .uri("/health/live")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().contains_key("x-request-id"));
+}
+
+#[tokio::test]
+async fn test_retry_worker_follows_scripted_chain_through_backoff() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let blockchain = Arc::new(MockBlockchainClient::with_script(vec![
+        MockStep::Fail("timeout".into()),
+        MockStep::Fail("rate limit".into()),
+        MockStep::Succeed,
+    ]));
+    // Health probes fail without affecting submissions
+    blockchain.fail_method(MockMethod::HealthCheck, "probe down");
+    let state = Arc::new(AppState::new(
+        item_repo,
+        outbox_repo,
+        Arc::clone(&blockchain) as _,
+        test_api_key(),
+    ));
+    let worker = BlockchainRetryWorker::new(Arc::clone(&state.service), WorkerConfig::default());
+
+    let payload = CreateItemRequest::new("Scripted".to_string(), "Content".to_string());
+    let item = state
+        .service
+        .create_and_submit_item(&payload)
+        .await
+        .unwrap();
+
+    // Each failure schedules the next attempt 2^n seconds out; the worker leaves the
+    // item alone until the mock clock reaches it
+    for (attempt, (message, backoff_secs)) in
+        [("timeout", 2), ("rate limit", 4)].into_iter().enumerate()
+    {
+        worker.run_once().await;
+        let failed = mock.get_item(&item.id).await.unwrap().unwrap();
+        assert_eq!(
+            failed.blockchain_status,
+            BlockchainStatus::PendingSubmission
+        );
+        assert_eq!(failed.blockchain_retry_count, attempt as i32 + 1);
+        assert!(failed.blockchain_last_error.unwrap().contains(message));
+        let wait = failed.blockchain_next_retry_at.unwrap() - chrono::Utc::now();
+        assert!(wait <= chrono::Duration::seconds(backoff_secs));
+        assert!(wait > chrono::Duration::seconds(backoff_secs - 1));
+
+        worker.run_once().await;
+        assert_eq!(blockchain.submit_calls(), attempt as u64 + 1);
+        mock.advance_clock(chrono::Duration::seconds(backoff_secs));
+    }
+
+    worker.run_once().await;
+    let submitted = mock.get_item(&item.id).await.unwrap().unwrap();
+    assert_eq!(submitted.blockchain_status, BlockchainStatus::Submitted);
+    assert_eq!(blockchain.submit_calls(), 3);
+    assert_eq!(blockchain.script_remaining(), 0);
+    assert!(blockchain.health_check().await.is_err());
+}
+
+#[tokio::test]
+async fn test_bulk_dead_letter_requeue_runs_as_job() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let blockchain = Arc::new(MockBlockchainClient::with_script(vec![
+        MockStep::Fail("rpc down".into()),
+        MockStep::Fail("rpc down".into()),
+    ]));
+    let state = Arc::new(
+        AppState::new(item_repo, outbox_repo, blockchain, test_api_key())
+            .with_job_store(Arc::clone(&mock) as Arc<dyn JobStore>),
+    );
+
+    // Two items on their last attempt fail into the dead-letter queue
+    for name in ["First", "Second"] {
+        let payload = CreateItemRequest::new(name.to_string(), "Content".to_string());
+        let item = state
+            .service
+            .create_and_submit_item(&payload)
+            .await
+            .unwrap();
+        let entry = mock
+            .get_all_outbox_entries()
+            .into_iter()
+            .find(|e| e.aggregate_id == item.id)
+            .unwrap();
+        mock.fail_solana_outbox(
+            &entry.id,
+            &item.id,
+            9,
+            OutboxStatus::Pending,
+            BlockchainStatus::PendingSubmission,
+            "rpc down",
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+    state.service.process_pending_submissions(10).await.unwrap();
+    assert_eq!(
+        state
+            .service
+            .list_failed_submissions(50, false)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+
+    let router = create_router(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/admin/dlq/requeue")
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let location = response.headers()["location"].to_str().unwrap().to_string();
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let job: Job = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(location, format!("/jobs/{}", job.id));
+    assert_eq!(job.kind, "dlq_requeue");
+
+    let mut job = job;
+    for _ in 0..100 {
+        let request = Request::builder()
+            .uri(&location)
+            .header(API_KEY_HEADER, TEST_KEY)
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+        job = serde_json::from_slice(&body_bytes).unwrap();
+        if job.status.is_finished() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+    assert_eq!(job.status, JobStatus::Succeeded);
+    assert_eq!((job.processed, job.failed), (2, 0));
+    assert_eq!(job.result.unwrap()["requeued"], 2);
+    assert!(mock.get_all_items().iter().all(|i| {
+        i.blockchain_status == BlockchainStatus::PendingSubmission && i.blockchain_retry_count == 0
+    }));
+
+    // Job status needs a key; unknown jobs are 404
+    let request = Request::builder()
+        .uri(&location)
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let request = Request::builder()
+        .uri("/jobs/job_missing")
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_jobs_unavailable_without_store() {
+    let router = create_router(create_test_state());
+    let request = Request::builder()
+        .method("POST")
+        .uri("/admin/dlq/requeue")
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let error: ErrorResponse = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(error.error.r#type, "jobs_unavailable");
+}
+
+#[tokio::test]
+async fn test_admin_audit_lists_item_and_admin_changes() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let blockchain = Arc::new(MockBlockchainClient::new());
+    let state = Arc::new(
+        AppState::new(item_repo, outbox_repo, blockchain, test_api_key())
+            .with_api_key_store(Arc::clone(&mock) as Arc<dyn ApiKeyStore>)
+            .with_audit_logger(Arc::clone(&mock) as Arc<dyn AuditLogger>),
+    );
+    let router = create_router(state);
+    let send = |method: &str, uri: &str, body: Option<serde_json::Value>| {
+        let router = router.clone();
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(API_KEY_HEADER, TEST_KEY)
+            .header("content-type", "application/json")
+            .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+            .unwrap();
+        async move {
+            let response = router.oneshot(request).await.unwrap();
+            let status = response.status();
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            (status, body)
+        }
+    };
+    let audit = |uri: &str| {
+        let uri = uri.to_string();
+        async move {
+            let (status, body) = send("GET", &uri, None).await;
+            assert_eq!(status, StatusCode::OK);
+            serde_json::from_slice::<PaginatedResponse<AuditEntry>>(&body).unwrap()
+        }
+    };
+
+    let item = CreateItemRequest::new("Audited".to_string(), "Content".to_string());
+    let (status, body) = send("POST", "/items", Some(serde_json::to_value(&item).unwrap())).await;
+    assert_eq!(status, StatusCode::OK);
+    let item: Item = serde_json::from_slice(&body).unwrap();
+    let (status, body) = send(
+        "POST",
+        "/admin/api-keys",
+        Some(serde_json::json!({ "name": "ci", "scopes": ["items:read"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let issued: CreateApiKeyResponse = serde_json::from_slice(&body).unwrap();
+    let (status, _) = send(
+        "DELETE",
+        &format!("/admin/api-keys/{}", issued.key.id),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    // Failed changes are not recorded
+    let (status, _) = send("DELETE", "/admin/api-keys/key_missing", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Newest first; reads (including the audit listing itself) are not recorded
+    let page = audit("/admin/audit").await;
+    assert_eq!(
+        page.items
+            .iter()
+            .map(|e| e.action.as_str())
+            .collect::<Vec<_>>(),
+        ["api_key.revoke", "api_key.create", "item.create"]
+    );
+    assert!(page.items.iter().all(|e| e.principal == "bootstrap"));
+    assert!(page.items.iter().all(|e| e.request_id.is_some()));
+    assert_eq!(
+        page.items[0].resource_id.as_deref(),
+        Some(issued.key.id.as_str())
+    );
+    let created = &page.items[2];
+    assert_eq!(created.resource_id.as_deref(), Some(item.id.as_str()));
+    assert_eq!(created.diff.as_ref().unwrap()["name"]["to"], "Audited");
+
+    let page = audit(&format!("/admin/audit?resource_id={}", item.id)).await;
+    assert_eq!(page.items.len(), 1);
+    let page = audit("/admin/audit?action=api_key.create&principal=bootstrap").await;
+    assert_eq!(page.items.len(), 1);
+    let page = audit("/admin/audit?principal=someone-else").await;
+    assert!(page.items.is_empty());
+    let page = audit("/admin/audit?limit=2").await;
+    let cursor = page.next_cursor.unwrap();
+    let page = audit(&format!("/admin/audit?limit=2&cursor={cursor}")).await;
+    assert_eq!(page.items.len(), 1);
+    assert!(!page.has_more);
+}
+
+#[tokio::test]
+async fn test_admin_lists_page_webhook_deliveries_and_events() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let blockchain = Arc::new(MockBlockchainClient::new());
+    let state = Arc::new(
+        AppState::new(item_repo, outbox_repo, blockchain, test_api_key()).with_operational_logs(
+            Arc::clone(&mock) as Arc<dyn EventLog>,
+            Arc::clone(&mock) as Arc<dyn WebhookDeliveryLog>,
+        ),
+    );
+    for attempt in 1..=3 {
+        mock.record_webhook_delivery(&WebhookDelivery {
+            id: 0,
+            event_id: "evt_1".to_string(),
+            event: "item.submitted".to_string(),
+            item_id: "item_1".to_string(),
+            url: "https://hooks.example.com".to_string(),
+            attempt,
+            response_status: Some(500),
+            error: None,
+            succeeded: false,
+            attempted_at: chrono::Utc::now(),
+        })
+        .await
+        .unwrap();
+    }
+    let router = create_router(state);
+    let get = |uri: String| {
+        let router = router.clone();
+        async move {
+            let request = Request::builder()
+                .uri(uri)
+                .header(API_KEY_HEADER, TEST_KEY)
+                .body(Body::empty())
+                .unwrap();
+            router.oneshot(request).await.unwrap()
+        }
+    };
+
+    // Newest first, two per page
+    let response = get("/admin/webhook-deliveries?limit=2".to_string()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let page: PaginatedResponse<WebhookDelivery> = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(
+        page.items.iter().map(|d| d.attempt).collect::<Vec<_>>(),
+        [3, 2]
+    );
+    assert!(page.has_more);
+    let cursor = page.next_cursor.unwrap();
+
+    let response = get(format!("/admin/webhook-deliveries?limit=2&cursor={cursor}")).await;
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let page: PaginatedResponse<WebhookDelivery> = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(
+        page.items.iter().map(|d| d.attempt).collect::<Vec<_>>(),
+        [1]
+    );
+    assert!(!page.has_more);
+    assert!(page.next_cursor.is_none());
+
+    // A deliveries cursor is not valid for the event log
+    let response = get(format!("/admin/events?cursor={cursor}")).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let error: ErrorResponse = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(error.error.r#type, "invalid_cursor");
+
+    // Nothing was attempted in the future
+    let response = get("/admin/webhook-deliveries?since=2999-01-01T00:00:00Z".to_string()).await;
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let page: PaginatedResponse<WebhookDelivery> = serde_json::from_slice(&body_bytes).unwrap();
+    assert!(page.items.is_empty());
+
+    let response = get("/admin/events".to_string()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_admin_lists_unavailable_without_logs() {
+    let router = create_router(create_test_state());
+    for uri in ["/admin/events", "/admin/webhook-deliveries"] {
+        let request = Request::builder()
+            .uri(uri)
+            .header(API_KEY_HEADER, TEST_KEY)
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE, "{uri}");
+        let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let error: ErrorResponse = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(error.error.r#type, "logs_unavailable");
+    }
+}
+
+/// `multipart/form-data` body with one `file` part
+fn multipart_upload(file_name: &str, content_type: &str, contents: &str) -> (String, Body) {
+    let boundary = "import-boundary";
+    let body = format!(
+        "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\n\
+         Content-Type: {content_type}\r\n\r\n{contents}\r\n--{boundary}--\r\n"
+    );
+    (
+        format!("multipart/form-data; boundary={boundary}"),
+        Body::from(body),
+    )
+}
+
+#[tokio::test]
+async fn test_import_items_reports_each_line() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let state = Arc::new(AppState::new(
+        item_repo,
+        outbox_repo,
+        Arc::new(MockBlockchainClient::new()),
+        test_api_key(),
+    ));
+    let router = create_router(state);
+
+    let csv = "name,description,content\r\nFirst,,Alpha\r\n,Nameless,Beta\r\n\"Second\",\"multi\nline\",Gamma\r\n";
+    let (content_type, body) = multipart_upload("items.csv", "text/csv", csv);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/items/import")
+        .header("content-type", content_type)
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(body)
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let report: ImportReport = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!((report.total, report.imported, report.failed), (3, 2, 1));
+    assert_eq!(
+        report.lines.iter().map(|l| l.line).collect::<Vec<_>>(),
+        [2, 3, 4]
+    );
+    assert_eq!(
+        report.lines[1].error.as_ref().unwrap().r#type,
+        "validation_error"
+    );
+    let second = mock
+        .get_item(report.lines[2].item_id.as_deref().unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(second.description.as_deref(), Some("multi\nline"));
+
+    // NDJSON, e.g. a previous export
+    let ndjson = "{\"name\":\"Third\",\"content\":\"Delta\",\"id\":\"ignored\"}\n";
+    let (content_type, body) = multipart_upload("items.ndjson", "application/x-ndjson", ndjson);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/items/import")
+        .header("content-type", content_type)
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(body)
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let report: ImportReport = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(report.imported, 1);
+    assert_eq!(mock.get_all_items().len(), 3);
+
+    // Writing items needs a key
+    let (content_type, body) = multipart_upload("items.ndjson", "application/x-ndjson", ndjson);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/items/import")
+        .header("content-type", content_type)
+        .body(body)
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_bodies_over_the_route_limit_get_a_structured_413() {
+    let (item_repo, outbox_repo) = mock_repos(&Arc::new(MockProvider::new()));
+    let state = AppState::new(
+        item_repo,
+        outbox_repo,
+        Arc::new(MockBlockchainClient::new()),
+        test_api_key(),
+    )
+    .with_body_limits(BodyLimits {
+        items: 256,
+        import: 4096,
+        ..BodyLimits::default()
+    });
+    let router = create_router(Arc::new(state));
+    let payload = serde_json::to_string(&CreateItemRequest::new(
+        "Large".to_string(),
+        "x".repeat(512),
+    ))
+    .unwrap();
+
+    // Rejected from Content-Length before the body is read, and while reading without it
+    for content_length in [Some(payload.len()), None] {
+        let mut request = Request::builder()
+            .method("POST")
+            .uri("/items")
+            .header("content-type", "application/json")
+            .header(API_KEY_HEADER, TEST_KEY);
+        if let Some(length) = content_length {
+            request = request.header("content-length", length);
+        }
+        let request = request.body(Body::from(payload.clone())).unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let error: ErrorResponse = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(error.error.r#type, "payload_too_large");
+    }
+
+    // The import upload has its own, larger limit
+    let ndjson = format!(
+        "{{\"name\":\"Large\",\"content\":\"{}\"}}\n",
+        "x".repeat(512)
+    );
+    let (content_type, body) = multipart_upload("items.ndjson", "application/x-ndjson", &ndjson);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/items/import")
+        .header("content-type", content_type)
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(body)
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_unchanged_items_and_pages_revalidate_with_304() {
+    let router = create_router(create_test_state());
+    let payload = CreateItemRequest::new("Polled".to_string(), "Content".to_string());
+    let request = Request::builder()
+        .method("POST")
+        .uri("/items")
+        .header("content-type", "application/json")
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(Body::from(serde_json::to_string(&payload).unwrap()))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let item: Item = serde_json::from_slice(&body_bytes).unwrap();
+
+    for uri in [format!("/items/{}", item.id), "/items?limit=10".to_string()] {
+        let get = |if_none_match: Option<&str>| {
+            let mut request = Request::builder().uri(&uri);
+            if let Some(etag) = if_none_match {
+                request = request.header("if-none-match", etag);
+            }
+            request.body(Body::empty()).unwrap()
+        };
+        let response = router.clone().oneshot(get(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers()["etag"].to_str().unwrap().to_string();
+
+        let response = router.clone().oneshot(get(Some(&etag))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED, "{uri}");
+        assert_eq!(response.headers()["etag"], etag.as_str());
+        let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(body_bytes.is_empty());
+
+        let response = router
+            .clone()
+            .oneshot(get(Some("\"stale\"")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
+
+#[tokio::test]
+async fn test_responses_are_compressed_when_accepted() {
+    let router = create_router(create_test_state());
+    for i in 0..20 {
+        let payload = CreateItemRequest::new(format!("Item {i}"), "Compressible content".repeat(8));
+        let request = Request::builder()
+            .method("POST")
+            .uri("/items")
+            .header("content-type", "application/json")
+            .header(API_KEY_HEADER, TEST_KEY)
+            .body(Body::from(serde_json::to_string(&payload).unwrap()))
+            .unwrap();
+        router.clone().oneshot(request).await.unwrap();
+    }
+
+    for encoding in ["gzip", "br"] {
+        let request = Request::builder()
+            .uri("/items?limit=20")
+            .header("accept-encoding", encoding)
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-encoding"], encoding);
+    }
+
+    let request = Request::builder()
+        .uri("/items?limit=20")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert!(!response.headers().contains_key("content-encoding"));
+}
+
+#[tokio::test]
+async fn test_verify_receipt_accepts_retired_issuer_keys() {
+    use base64::Engine;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    let current = SigningKey::from_bytes(&[7; 32]);
+    let retired = SigningKey::from_bytes(&[8; 32]);
+    let public_key = |key: &SigningKey| bs58::encode(key.verifying_key().to_bytes()).into_string();
+    let issuer_keys = IssuerKeyRegistry::empty()
+        .with_key(&public_key(&current), IssuerKeyStatus::Current)
+        .unwrap()
+        .with_key(&public_key(&retired), IssuerKeyStatus::Retired)
+        .unwrap();
+    let state = Arc::new((*create_test_state()).clone().with_issuer_keys(issuer_keys));
+    let router = create_router(state);
+
+    let sign = |key: &SigningKey| {
+        let input = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(r#"{"alg":"EdDSA"}"#),
+            URL_SAFE_NO_PAD.encode(r#"{"item_id":"item_1"}"#)
+        );
+        let signature = URL_SAFE_NO_PAD.encode(key.sign(input.as_bytes()).to_bytes());
+        format!("{}.{}", input, signature)
+    };
+    let verify = |receipt: String| {
+        let router = router.clone();
+        async move {
+            let request = Request::builder()
+                .method("POST")
+                .uri("/verify/receipt")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({ "receipt": receipt }).to_string(),
+                ))
+                .unwrap();
+            let response = router.oneshot(request).await.unwrap();
+            let status = response.status();
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            (status, body)
+        }
+    };
+
+    // No API key needed
+    let (status, body) = verify(sign(&retired)).await;
+    assert_eq!(status, StatusCode::OK);
+    let result: ReceiptVerification = serde_json::from_slice(&body).unwrap();
+    assert!(result.valid);
+    assert_eq!(result.key_id, Some(public_key(&retired)));
+    assert_eq!(result.key_status, Some(IssuerKeyStatus::Retired));
+    assert_eq!(result.payload.unwrap()["item_id"], "item_1");
+
+    let (status, body) = verify(sign(&SigningKey::from_bytes(&[9; 32]))).await;
+    assert_eq!(status, StatusCode::OK);
+    let result: ReceiptVerification = serde_json::from_slice(&body).unwrap();
+    assert!(!result.valid);
+    assert!(result.key_id.is_none() && result.payload.is_none());
+
+    let (status, _) = verify("not-a-receipt".to_string()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_admin_token_maintenance_queue_and_key_rotation() {
+    use secrecy::SecretString;
+
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let blockchain = Arc::new(MockBlockchainClient::new());
+    let state = Arc::new(
+        AppState::new(item_repo, outbox_repo, blockchain, test_api_key())
+            .with_api_key_store(Arc::clone(&mock) as Arc<dyn ApiKeyStore>)
+            .with_admin_auth_key(SecretString::from("admin-token"))
+            .with_maintenance_retry_after(std::time::Duration::from_secs(120)),
+    );
+    let router = create_router(Arc::clone(&state));
+    let send = |method: &str, uri: &str, key: &str, body: Option<serde_json::Value>| {
+        let router = router.clone();
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(API_KEY_HEADER, key)
+            .header("content-type", "application/json")
+            .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+            .unwrap();
+        async move {
+            let response = router.oneshot(request).await.unwrap();
+            let status = response.status();
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            (status, body)
+        }
+    };
+
+    // With ADMIN_AUTH_KEY set the bootstrap key no longer administers
+    let (status, _) = send("GET", "/admin/queue", TEST_KEY, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let item = CreateItemRequest::new("Queued".to_string(), "Content".to_string());
+    let (status, _) = send(
+        "POST",
+        "/items",
+        TEST_KEY,
+        Some(serde_json::to_value(&item).unwrap()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send("GET", "/admin/queue", "admin-token", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let depth: QueueDepth = serde_json::from_slice(&body).unwrap();
+    assert_eq!(depth.pending, 1);
+    assert_eq!(depth.dead_lettered, 0);
+
+    // Maintenance rejects writes outside /admin; reads keep working
+    let enable = Some(serde_json::json!({ "enabled": true }));
+    let (status, _) = send("PUT", "/admin/maintenance", "admin-token", enable).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(state.maintenance_enabled());
+    let (status, body) = send(
+        "POST",
+        "/items",
+        TEST_KEY,
+        Some(serde_json::to_value(&item).unwrap()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    let error: ErrorResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error.error.r#type, "maintenance");
+    let request = Request::builder()
+        .method("DELETE")
+        .uri("/items/any")
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.headers()["retry-after"], "120");
+    let (status, _) = send("GET", "/items", TEST_KEY, None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Admin writes still go through: issue and rotate a key
+    let (status, body) = send(
+        "POST",
+        "/admin/api-keys",
+        "admin-token",
+        Some(serde_json::json!({ "name": "ci", "scopes": ["items:read"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let issued: CreateApiKeyResponse = serde_json::from_slice(&body).unwrap();
+    let uri = format!("/admin/api-keys/{}/rotate", issued.key.id);
+    let (status, body) = send("POST", &uri, "admin-token", None).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let rotated: CreateApiKeyResponse = serde_json::from_slice(&body).unwrap();
+    assert_ne!(rotated.key.id, issued.key.id);
+    assert_eq!(rotated.key.name, "ci");
+    assert_eq!(rotated.key.scopes, issued.key.scopes);
+    let (_, body) = send("GET", "/admin/api-keys", "admin-token", None).await;
+    let keys: Vec<ApiKey> = serde_json::from_slice(&body).unwrap();
+    let old = keys.iter().find(|k| k.id == issued.key.id).unwrap();
+    assert!(old.revoked_at.is_some());
+    let (status, _) = send("POST", &uri, "admin-token", None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let disable = Some(serde_json::json!({ "enabled": false }));
+    let (status, body) = send("PUT", "/admin/maintenance", "admin-token", disable).await;
+    assert_eq!(status, StatusCode::OK);
+    let mode: MaintenanceMode = serde_json::from_slice(&body).unwrap();
+    assert!(!mode.enabled);
+    assert!(!state.maintenance_enabled());
+
+    // SIGHUP flips the same flag
+    assert!(state.toggle_maintenance());
+    assert!(state.maintenance_enabled());
+    assert!(!state.toggle_maintenance());
+}
+
+#[tokio::test]
+async fn test_api_key_quota_is_enforced_and_reported() {
+    let mock = Arc::new(MockProvider::new());
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let blockchain = Arc::new(MockBlockchainClient::new());
+    let state = Arc::new(
+        AppState::new(item_repo, outbox_repo, blockchain, test_api_key())
+            .with_api_key_store(Arc::clone(&mock) as Arc<dyn ApiKeyStore>)
+            .with_usage_ledger(Arc::clone(&mock) as Arc<dyn UsageLedger>),
+    );
+    let router = create_router(state);
+    let send = |uri: &str, key: &str, body: Option<serde_json::Value>| {
+        let request = Request::builder()
+            .method(if body.is_some() { "POST" } else { "GET" })
+            .uri(uri)
+            .header(API_KEY_HEADER, key)
+            .header("content-type", "application/json")
+            .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+            .unwrap();
+        router.clone().oneshot(request)
+    };
+
+    let response = send(
+        "/admin/api-keys",
+        TEST_KEY,
+        Some(serde_json::json!({
+            "name": "ci",
+            "scopes": ["items:read"],
+            "quota": { "daily": 2 }
+        })),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let issued: CreateApiKeyResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(issued.key.quota.daily, Some(2));
+
+    for remaining in ["1", "0"] {
+        let lookups = mock.call_count("find_api_key_by_hash");
+        let response = send("/items", &issued.secret, None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-quota-remaining"], remaining);
+        // Quota and tenant scope share one key lookup on the public route
+        assert_eq!(mock.call_count("find_api_key_by_hash"), lookups + 1);
+    }
+    let response = send("/items", &issued.secret, None).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()["x-quota-remaining"], "0");
+    assert!(response.headers().contains_key("retry-after"));
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let error: ErrorResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error.error.r#type, "quota_exceeded");
+
+    // Keys without a quota are counted but get no header; admin routes are not counted
+    let response = send("/items", TEST_KEY, None).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!response.headers().contains_key("x-quota-remaining"));
+
+    let response = send("/admin/usage", TEST_KEY, None).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let report: UsageReport = serde_json::from_slice(&body).unwrap();
+    let ci = report
+        .keys
+        .iter()
+        .find(|k| k.key_id == issued.key.id)
+        .unwrap();
+    assert_eq!(ci.daily_requests, 3);
+    assert_eq!(ci.monthly_requests, 3);
+    assert_eq!(ci.name.as_deref(), Some("ci"));
+    let bootstrap = report
+        .keys
+        .iter()
+        .find(|k| k.key_id == "bootstrap")
+        .unwrap();
+    assert_eq!(bootstrap.daily_requests, 1);
+
+    let response = send("/admin/usage?day=2001-01-01", TEST_KEY, None)
+        .await
+        .unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let report: UsageReport = serde_json::from_slice(&body).unwrap();
+    assert!(report.keys.is_empty());
+}
+
+#[tokio::test]
+async fn test_live_feed_streams_filtered_events_over_websocket() {
+    use futures::StreamExt;
+    use tokio_tungstenite::tungstenite::{self, Message, protocol::frame::coding::CloseCode};
+
+    let state = create_test_state();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(axum::serve(listener, create_router(Arc::clone(&state))).into_future());
+
+    // Anonymous requests are confined to the default tenant
+    let Err(tungstenite::Error::Http(response)) =
+        tokio_tungstenite::connect_async(format!("ws://{addr}/ws?tenant=acme")).await
+    else {
+        panic!("expected the upgrade to be refused");
+    };
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws?tag=rust"))
+        .await
+        .unwrap();
+    while !state.service.feed().has_subscribers() {
+        tokio::task::yield_now().await;
+    }
+
+    let tagged = |name: &str, tag: &str| {
+        let mut request = CreateItemRequest::new(name.to_string(), format!("{name} content"));
+        request.metadata = Some(ItemMetadataRequest {
+            author: None,
+            version: None,
+            tags: vec![tag.to_string()],
+            custom_fields: Default::default(),
+        });
+        request
+    };
+    state
+        .service
+        .create_and_submit_item(&tagged("Go", "go"))
+        .await
+        .unwrap();
+    let rust = state
+        .service
+        .create_and_submit_item(&tagged("Rust", "rust"))
+        .await
+        .unwrap();
+    state.service.process_pending_submissions(10).await.unwrap();
+
+    let mut next_event = async || match socket.next().await.unwrap().unwrap() {
+        Message::Text(text) => serde_json::from_str::<serde_json::Value>(&text).unwrap(),
+        other => panic!("unexpected message {other:?}"),
+    };
+    let created = next_event().await;
+    assert_eq!(created["type"], "item_created");
+    assert_eq!(created["item_id"], rust.id.as_str());
+    let submitted = next_event().await;
+    assert_eq!(submitted["type"], "item_submitted");
+    assert_eq!(submitted["item_id"], rust.id.as_str());
+
+    // Shutdown closes the connection with "going away"
+    state.service.feed().close();
+    let Message::Close(Some(frame)) = socket.next().await.unwrap().unwrap() else {
+        panic!("expected a close frame");
+    };
+    assert_eq!(frame.code, CloseCode::Away);
 }
@@ -0,0 +1,318 @@
+//! Soak test: router, service, retry worker and event dispatcher against a flaky chain.
+//!
+//! Items are created through the HTTP API, submitted by the retry worker against a chain
+//! that randomly drops, times out, rejects and expires transactions (and has one long
+//! outage that pushes submissions into the dead-letter queue), requeued through the admin
+//! API and delivered to a webhook subscriber that rejects some batches. The mock's clock
+//! is advanced every tick, so hours of backoff run in seconds.
+//!
+//! Ignored by default; run with:
+//!
+//! ```text
+//! SOAK_ITEMS=20000 cargo test --test soak_test -- --ignored --nocapture
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use tokio::sync::watch;
+use tower::ServiceExt;
+
+use testable_rust_architecture_template::api::create_router;
+use testable_rust_architecture_template::app::{
+    AppState, BlockchainRetryWorker, DispatcherConfig, EventDispatcher, Subscription, WorkerConfig,
+};
+use testable_rust_architecture_template::domain::{
+    BlockchainClient, BlockchainError, BlockchainStatus, CreateItemRequest, EventLog,
+    HealthCheckError, Item, NotificationClient, OutboxStatus,
+};
+use testable_rust_architecture_template::test_utils::{
+    MockNotificationClient, MockProvider, mock_repos, test_api_key,
+};
+
+const API_KEY_HEADER: &str = "x-api-key";
+const TEST_KEY: &str = "test-api-key";
+/// Virtual time per tick
+const TICK_SECS: i64 = 30;
+/// Ticks during which every submission fails (long enough to exhaust all retries)
+const OUTAGE_TICKS: std::ops::Range<usize> = 10..60;
+const MAX_TICKS: usize = 5_000;
+
+/// Chain that lands a transaction at most once per (hash, blockhash) signature but
+/// reports failures at random, including failures after the transaction landed
+struct FlakyChain {
+    state: Mutex<ChainState>,
+}
+
+struct ChainState {
+    rng: StdRng,
+    next_blockhash: u64,
+    outage: bool,
+    /// Signatures that landed, per item hash
+    landed: HashMap<String, HashSet<String>>,
+}
+
+impl FlakyChain {
+    fn new(seed: u64) -> Self {
+        Self {
+            state: Mutex::new(ChainState {
+                rng: StdRng::seed_from_u64(seed),
+                next_blockhash: 0,
+                outage: false,
+                landed: HashMap::new(),
+            }),
+        }
+    }
+
+    fn set_outage(&self, outage: bool) {
+        self.state.lock().unwrap().outage = outage;
+    }
+
+    fn landed(&self) -> HashMap<String, HashSet<String>> {
+        self.state.lock().unwrap().landed.clone()
+    }
+}
+
+#[async_trait]
+impl BlockchainClient for FlakyChain {
+    async fn health_check(&self) -> Result<(), HealthCheckError> {
+        Ok(())
+    }
+
+    async fn submit_transaction(
+        &self,
+        hash: &str,
+        existing_blockhash: Option<&str>,
+    ) -> Result<(String, String), BlockchainError> {
+        let mut state = self.state.lock().unwrap();
+        let blockhash = match existing_blockhash {
+            Some(blockhash) => blockhash.to_string(),
+            None => {
+                state.next_blockhash += 1;
+                format!("bh_{}", state.next_blockhash)
+            }
+        };
+        let signature = format!("sig_{}_{}", hash, blockhash);
+
+        // Resubmitting a landed transaction is a no-op that reports its signature
+        if state
+            .landed
+            .get(hash)
+            .is_some_and(|s| s.contains(&signature))
+        {
+            return Ok((signature, blockhash));
+        }
+        if state.outage {
+            return Err(BlockchainError::NetworkError {
+                message: "connection refused".to_string(),
+                blockhash,
+            });
+        }
+
+        let roll = state.rng.gen_range(0..100);
+        let lands = roll < 60 || (75..85).contains(&roll);
+        if lands {
+            state
+                .landed
+                .entry(hash.to_string())
+                .or_default()
+                .insert(signature.clone());
+        }
+        match roll {
+            0..60 => Ok((signature, blockhash)),
+            60..75 => Err(BlockchainError::NetworkError {
+                message: "connection reset".to_string(),
+                blockhash,
+            }),
+            // Landed, but the confirmation never reached us
+            75..85 => Err(BlockchainError::Timeout {
+                message: "confirmation timed out".to_string(),
+                blockhash,
+            }),
+            85..95 => Err(BlockchainError::SubmissionFailed(
+                "node is behind".to_string(),
+            )),
+            _ => Err(BlockchainError::BlockhashExpired),
+        }
+    }
+}
+
+async fn send(router: &Router, method: &str, uri: &str, body: Body) -> (StatusCode, Vec<u8>) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .header(API_KEY_HEADER, TEST_KEY)
+        .body(body)
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, body.to_vec())
+}
+
+/// Requeue every open dead-letter entry through the admin API; returns how many
+async fn requeue_dead_letters(router: &Router) -> usize {
+    let mut requeued = 0;
+    loop {
+        let (status, body) = send(router, "GET", "/admin/dlq?limit=100", Body::empty()).await;
+        assert_eq!(status, StatusCode::OK);
+        let entries: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        if entries.is_empty() {
+            return requeued;
+        }
+        for entry in entries {
+            let uri = format!("/admin/dlq/{}/requeue", entry["id"].as_str().unwrap());
+            let (status, _) = send(router, "POST", &uri, Body::empty()).await;
+            assert_eq!(status, StatusCode::OK);
+            requeued += 1;
+        }
+    }
+}
+
+#[tokio::test]
+#[ignore = "long-running; run with --ignored"]
+async fn test_soak_no_item_lost_duplicated_or_stuck() {
+    let item_count: usize = std::env::var("SOAK_ITEMS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(5_000);
+    let seed: u64 = std::env::var("SOAK_SEED")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(42);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mock = Arc::new(MockProvider::new());
+    let chain = Arc::new(FlakyChain::new(seed));
+    let (item_repo, outbox_repo) = mock_repos(&mock);
+    let state = Arc::new(AppState::new(
+        item_repo,
+        outbox_repo,
+        Arc::clone(&chain) as Arc<dyn BlockchainClient>,
+        test_api_key(),
+    ));
+    let router = create_router(Arc::clone(&state));
+    let worker = BlockchainRetryWorker::new(
+        Arc::clone(&state.service),
+        WorkerConfig {
+            batch_size: 200,
+            ..WorkerConfig::default()
+        },
+    );
+    let webhook = Arc::new(MockNotificationClient::new());
+    let dispatcher = EventDispatcher::new(
+        Arc::clone(&mock) as Arc<dyn EventLog>,
+        vec![Subscription::new(
+            "https://hooks.example.com",
+            Arc::clone(&webhook) as Arc<dyn NotificationClient>,
+        )],
+        DispatcherConfig {
+            batch_size: 250,
+            ..DispatcherConfig::default()
+        },
+        watch::channel(false).1,
+    );
+
+    let mut created = HashMap::new();
+    for i in 0..item_count {
+        let payload = CreateItemRequest::new(format!("Soak item {}", i), format!("Content {}", i));
+        let (status, body) = send(
+            &router,
+            "POST",
+            "/items",
+            Body::from(serde_json::to_vec(&payload).unwrap()),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let item: Item = serde_json::from_slice(&body).unwrap();
+        created.insert(item.id.clone(), item);
+    }
+
+    let mut requeued = 0;
+    let mut ticks = 0;
+    loop {
+        assert!(ticks < MAX_TICKS, "did not settle after {} ticks", ticks);
+        chain.set_outage(OUTAGE_TICKS.contains(&ticks));
+
+        worker.run_once().await;
+        if !OUTAGE_TICKS.contains(&ticks) {
+            requeued += requeue_dead_letters(&router).await;
+        }
+        webhook.set_failing(rng.gen_bool(0.2));
+        dispatcher.dispatch_once().await;
+        mock.advance_clock(chrono::Duration::seconds(TICK_SECS));
+        ticks += 1;
+
+        let settled = ticks > OUTAGE_TICKS.end
+            && mock
+                .get_all_items()
+                .iter()
+                .all(|i| i.blockchain_status == BlockchainStatus::Submitted);
+        if settled {
+            webhook.set_failing(false);
+            dispatcher.dispatch_once().await;
+            break;
+        }
+    }
+
+    // Not lost: every created item is submitted, and nothing else appeared
+    let items = mock.get_all_items();
+    assert_eq!(items.len(), item_count);
+    assert!(items.iter().all(|i| created.contains_key(&i.id)));
+
+    // Not duplicated: one landed transaction per item, and it is the one recorded
+    let outbox = mock.get_all_outbox_entries();
+    let landed = chain.landed();
+    assert_eq!(landed.len(), item_count);
+    for item in &items {
+        let hash = &outbox
+            .iter()
+            .find(|e| e.aggregate_id == item.id)
+            .unwrap()
+            .payload
+            .hash;
+        let signatures = &landed[hash];
+        assert_eq!(signatures.len(), 1, "item {} landed twice", item.id);
+        assert_eq!(
+            item.blockchain_signature.as_ref(),
+            signatures.iter().next(),
+            "item {} records a different signature",
+            item.id
+        );
+    }
+
+    // Not stuck: no outbox entry is left waiting or claimed
+    assert!(
+        outbox
+            .iter()
+            .all(|e| !matches!(e.status, OutboxStatus::Pending | OutboxStatus::Processing))
+    );
+
+    // Every subscriber saw each item submitted exactly once
+    let mut submitted_events: HashMap<String, usize> = HashMap::new();
+    for event in webhook.get_events() {
+        if event.status == BlockchainStatus::Submitted {
+            *submitted_events.entry(event.item_id).or_default() += 1;
+        }
+    }
+    assert_eq!(submitted_events.len(), item_count);
+    assert!(submitted_events.values().all(|count| *count == 1));
+
+    assert!(requeued > 0, "the outage should have dead-lettered items");
+    println!(
+        "soak: {} items settled after {} ticks ({} virtual minutes), {} requeued from the DLQ",
+        item_count,
+        ticks,
+        ticks as i64 * TICK_SECS / 60,
+        requeued
+    );
+}